@@ -0,0 +1,1725 @@
+//! Static analysis of execution plans beyond conformance validation.
+//!
+//! Analyses never reject a document. They return [`Diagnostic`] warnings with
+//! `A-` codes for constructs that are valid but almost certainly not what the
+//! author intended.
+
+use crate::context::ResolvedContext;
+use crate::enums::{Category, IndicatorResult};
+use crate::error::{Diagnostic, DiagnosticSeverity};
+use crate::evaluate::{
+    CelEvaluator, SemanticEvaluator, evaluate_indicator, surface_text_extractor,
+};
+use crate::primitives::{
+    compute_effective_state, evaluate_condition, parse_duration, parse_event_qualifier,
+    resolve_wildcard_path_ref,
+};
+use crate::surface::lookup_surface;
+use crate::types::*;
+use regex::Regex;
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+
+/// Run every analysis and return the combined diagnostics.
+pub fn analyze(doc: &Document) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    diagnostics.extend(unreachable_responses(doc));
+    diagnostics.extend(trigger_liveness(doc));
+    diagnostics.extend(synthesize_prompts(doc, &PromptPolicy::default()));
+    diagnostics
+}
+
+fn warning(code: &str, path: impl Into<String>, message: impl Into<String>) -> Diagnostic {
+    Diagnostic {
+        severity: DiagnosticSeverity::Warning,
+        code: code.to_string(),
+        path: Some(path.into()),
+        message: message.into(),
+        suggestion: None,
+    }
+}
+
+// ─── Helper: visit every phase state ────────────────────────────────────────
+
+fn phase_states(doc: &Document) -> Vec<(String, &Value)> {
+    let mut states = Vec::new();
+    if let Some(state) = &doc.attack.execution.state {
+        states.push(("attack.execution.state".to_string(), state));
+    }
+    for actor_info in ResolvedContext::new(doc).actors() {
+        for (pi, phase) in actor_info.phases.iter().enumerate() {
+            if let Some(state) = &phase.state {
+                let path = format!("{}.phases[{}].state", actor_info.path_prefix, pi);
+                states.push((path, state));
+            }
+        }
+    }
+    states
+}
+
+// ─── A-001 / A-002: unreachable response entries ────────────────────────────
+
+/// Detect response entries that can never be selected.
+///
+/// Response lists are evaluated first-match-wins (§5.7), so an entry is
+/// reported as **A-001** when an earlier entry's `when` predicate matches every
+/// request that its own predicate matches. A catch-all entry (no `when`) is
+/// reported when some entry carries an empty predicate, which always matches.
+///
+/// An entry is reported as **A-002** when its `when` predicate cannot match any
+/// request: contradictory operators on one path (e.g. `gt: 10` with `lt: 5`),
+/// or, for MCP tools, conditions on `arguments.<name>` that the tool's
+/// `inputSchema` rules out (undeclared property with
+/// `additionalProperties: false`, type mismatch, value outside `enum`, or
+/// `exists: false` on a required property).
+///
+/// The analysis is conservative: it only reports entries that are provably
+/// unreachable, and it may miss some that are.
+pub fn unreachable_responses(doc: &Document) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for (path, state) in phase_states(doc) {
+        let Some(obj) = state.as_object() else {
+            continue;
+        };
+
+        if let Some(tools) = obj.get("tools").and_then(|v| v.as_array()) {
+            for (ti, tool) in tools.iter().enumerate() {
+                if let Some(responses) = tool.get("responses").and_then(|v| v.as_array()) {
+                    check_response_list(
+                        responses,
+                        tool.get("inputSchema"),
+                        &format!("{}.tools[{}].responses", path, ti),
+                        &mut diagnostics,
+                    );
+                }
+            }
+        }
+
+        if let Some(prompts) = obj.get("prompts").and_then(|v| v.as_array()) {
+            for (pi, prompt) in prompts.iter().enumerate() {
+                if let Some(responses) = prompt.get("responses").and_then(|v| v.as_array()) {
+                    check_response_list(
+                        responses,
+                        None,
+                        &format!("{}.prompts[{}].responses", path, pi),
+                        &mut diagnostics,
+                    );
+                }
+            }
+        }
+
+        if let Some(task_responses) = obj.get("task_responses").and_then(|v| v.as_array()) {
+            check_response_list(
+                task_responses,
+                None,
+                &format!("{}.task_responses", path),
+                &mut diagnostics,
+            );
+        }
+    }
+    diagnostics
+}
+
+fn check_response_list(
+    entries: &[Value],
+    input_schema: Option<&Value>,
+    path: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    // Entries that fail to deserialize are reported by validation, not here.
+    let predicates: Vec<Option<Option<MatchPredicate>>> = entries
+        .iter()
+        .map(|e| {
+            serde_json::from_value::<ResponseEntry>(e.clone())
+                .ok()
+                .map(|r| r.when)
+        })
+        .collect();
+
+    let always_matching = predicates
+        .iter()
+        .position(|p| matches!(p, Some(Some(pred)) if pred.is_empty()));
+
+    for (j, when) in predicates.iter().enumerate() {
+        let Some(when) = when else {
+            continue;
+        };
+        let entry_path = format!("{}[{}]", path, j);
+
+        let Some(pred) = when else {
+            if let Some(i) = always_matching {
+                diagnostics.push(warning(
+                    "A-001",
+                    entry_path,
+                    format!(
+                        "catch-all entry is unreachable: entry [{}] has an empty 'when' that matches every request",
+                        i
+                    ),
+                ));
+            }
+            continue;
+        };
+
+        let mut unsatisfiable = false;
+        for (key, entry) in pred {
+            let reason = entry_contradiction(entry)
+                .or_else(|| input_schema.and_then(|s| schema_conflict(s, key, entry)));
+            if let Some(reason) = reason {
+                diagnostics.push(warning(
+                    "A-002",
+                    format!("{}.when.{}", entry_path, key),
+                    format!("'when' predicate can never match: {}", reason),
+                ));
+                unsatisfiable = true;
+            }
+        }
+        if unsatisfiable {
+            continue;
+        }
+
+        let shadowed_by = predicates[..j].iter().position(
+            |earlier| matches!(earlier, Some(Some(earlier)) if predicate_implies(pred, earlier)),
+        );
+        if let Some(i) = shadowed_by {
+            diagnostics.push(warning(
+                "A-001",
+                entry_path,
+                format!(
+                    "entry is unreachable: every request matching its 'when' also matches earlier entry [{}]",
+                    i
+                ),
+            ));
+        }
+    }
+}
+
+// ─── Predicate implication ──────────────────────────────────────────────────
+
+/// Returns true if every value matching `b` also matches `a` (per path, AND).
+fn predicate_implies(b: &MatchPredicate, a: &MatchPredicate) -> bool {
+    a.iter().all(|(path, a_entry)| {
+        b.get(path)
+            .is_some_and(|b_entry| entry_implies(b_entry, a_entry))
+    })
+}
+
+fn entry_implies(b: &MatchEntry, a: &MatchEntry) -> bool {
+    if let Some(values) = finite_values(b) {
+        return values.iter().all(|v| entry_accepts(a, v));
+    }
+    let (MatchEntry::Condition(b), MatchEntry::Condition(a)) = (b, a) else {
+        return false;
+    };
+
+    match a.exists {
+        Some(false) => return b.exists == Some(false) && !has_value_operators(a),
+        Some(true) if b.exists != Some(true) && !has_value_operators(b) => return false,
+        _ => {}
+    }
+    if b.exists == Some(false) {
+        return false;
+    }
+
+    let strings = |t: &Option<String>, f: fn(&str, &str) -> bool, s: &str| {
+        t.as_deref().is_some_and(|t| f(t, s))
+    };
+    if let Some(s) = &a.contains
+        && !(strings(&b.contains, |t, s| t.contains(s), s)
+            || strings(&b.starts_with, |t, s| t.contains(s), s)
+            || strings(&b.ends_with, |t, s| t.contains(s), s))
+    {
+        return false;
+    }
+    if let Some(s) = &a.starts_with
+        && !strings(&b.starts_with, |t, s| t.starts_with(s), s)
+    {
+        return false;
+    }
+    if let Some(s) = &a.ends_with
+        && !strings(&b.ends_with, |t, s| t.ends_with(s), s)
+    {
+        return false;
+    }
+    if a.regex.is_some() && a.regex != b.regex {
+        return false;
+    }
+    if a.any_of.is_some() {
+        // Only a finite `b` can imply membership, handled above.
+        return false;
+    }
+    if a.json_patch.is_some() {
+        // Patch matches are not compared; assume no implication.
+        return false;
+    }
+    if let Some(x) = a.gt
+        && !(b.gt.is_some_and(|y| y >= x) || b.gte.is_some_and(|y| y > x))
+    {
+        return false;
+    }
+    if let Some(x) = a.gte
+        && !(b.gte.is_some_and(|y| y >= x) || b.gt.is_some_and(|y| y >= x))
+    {
+        return false;
+    }
+    if let Some(x) = a.lt
+        && !(b.lt.is_some_and(|y| y <= x) || b.lte.is_some_and(|y| y < x))
+    {
+        return false;
+    }
+    if let Some(x) = a.lte
+        && !(b.lte.is_some_and(|y| y <= x) || b.lt.is_some_and(|y| y <= x))
+    {
+        return false;
+    }
+    true
+}
+
+/// The complete set of values an entry can match, when that set is finite.
+fn finite_values(entry: &MatchEntry) -> Option<Vec<Value>> {
+    match entry {
+        MatchEntry::Scalar(v) => Some(vec![v.clone()]),
+        MatchEntry::Condition(c) if c.exists != Some(false) => c.any_of.as_ref().map(|items| {
+            items
+                .iter()
+                .filter(|v| entry_accepts(entry, v))
+                .cloned()
+                .collect()
+        }),
+        MatchEntry::Condition(_) => None,
+    }
+}
+
+/// Whether an entry matches a path that resolved to `value`.
+fn entry_accepts(entry: &MatchEntry, value: &Value) -> bool {
+    match entry {
+        MatchEntry::Scalar(expected) => {
+            evaluate_condition(&Condition::Equality(expected.clone()), value)
+        }
+        MatchEntry::Condition(c) => {
+            c.exists != Some(false) && evaluate_condition(&Condition::Operators(c.clone()), value)
+        }
+    }
+}
+
+fn has_string_operators(c: &MatchCondition) -> bool {
+    c.contains.is_some() || c.starts_with.is_some() || c.ends_with.is_some() || c.regex.is_some()
+}
+
+fn has_numeric_operators(c: &MatchCondition) -> bool {
+    c.gt.is_some() || c.lt.is_some() || c.gte.is_some() || c.lte.is_some()
+}
+
+fn has_value_operators(c: &MatchCondition) -> bool {
+    has_string_operators(c)
+        || has_numeric_operators(c)
+        || c.any_of.is_some()
+        || c.json_patch.is_some()
+}
+
+// ─── Predicate satisfiability ───────────────────────────────────────────────
+
+/// Reason a single predicate entry can never match, independent of schema.
+fn entry_contradiction(entry: &MatchEntry) -> Option<String> {
+    let MatchEntry::Condition(c) = entry else {
+        return None;
+    };
+    if c.exists == Some(false) && has_value_operators(c) {
+        return Some("'exists: false' combined with value operators".to_string());
+    }
+    if has_string_operators(c) && has_numeric_operators(c) {
+        return Some("string and numeric operators on the same path".to_string());
+    }
+    let lower =
+        c.gt.map(|x| (x, true))
+            .into_iter()
+            .chain(c.gte.map(|x| (x, false)));
+    let upper =
+        c.lt.map(|x| (x, true))
+            .into_iter()
+            .chain(c.lte.map(|x| (x, false)));
+    let upper: Vec<_> = upper.collect();
+    for (lo, lo_strict) in lower {
+        for &(hi, hi_strict) in &upper {
+            if lo > hi || (lo == hi && (lo_strict || hi_strict)) {
+                return Some(format!("numeric range is empty ({} .. {})", lo, hi));
+            }
+        }
+    }
+    if let Some(values) = finite_values(entry)
+        && values.is_empty()
+    {
+        return Some("no 'any_of' value satisfies the other operators".to_string());
+    }
+    None
+}
+
+/// Reason a predicate entry on `arguments.<name>` conflicts with a tool's
+/// `inputSchema`, if any.
+fn schema_conflict(schema: &Value, key: &str, entry: &MatchEntry) -> Option<String> {
+    let rest = key
+        .strip_prefix("params.arguments.")
+        .or_else(|| key.strip_prefix("arguments."))?;
+    let (name, nested) = match rest.split_once('.') {
+        Some((name, _)) => (name, true),
+        None => (rest, false),
+    };
+    let requires_presence = match entry {
+        MatchEntry::Scalar(_) => true,
+        MatchEntry::Condition(c) => c.exists != Some(false),
+    };
+
+    let property = schema
+        .get("properties")
+        .and_then(|p| p.as_object())
+        .and_then(|p| p.get(name));
+    let Some(property) = property else {
+        if requires_presence && schema.get("additionalProperties") == Some(&Value::Bool(false)) {
+            return Some(format!(
+                "'{}' is not declared in inputSchema and additionalProperties is false",
+                name
+            ));
+        }
+        return None;
+    };
+
+    if !requires_presence {
+        let required = schema
+            .get("required")
+            .and_then(|r| r.as_array())
+            .is_some_and(|r| r.iter().any(|v| v.as_str() == Some(name)));
+        if required && !nested {
+            return Some(format!("'{}' is required by inputSchema", name));
+        }
+        return None;
+    }
+    if nested {
+        return None;
+    }
+
+    if let Some(values) = finite_values(entry) {
+        if !values.is_empty() && !values.iter().any(|v| value_fits_schema(v, property)) {
+            return Some(format!(
+                "no value allowed by this predicate satisfies inputSchema for '{}'",
+                name
+            ));
+        }
+        return None;
+    }
+    if let MatchEntry::Condition(c) = entry {
+        let types = schema_types(property)?;
+        if has_string_operators(c) && !types.contains(&"string") {
+            return Some(format!(
+                "string operators used but inputSchema declares '{}' as {}",
+                name,
+                types.join(" | ")
+            ));
+        }
+        if has_numeric_operators(c) && !types.iter().any(|t| *t == "number" || *t == "integer") {
+            return Some(format!(
+                "numeric operators used but inputSchema declares '{}' as {}",
+                name,
+                types.join(" | ")
+            ));
+        }
+    }
+    None
+}
+
+fn schema_types(property: &Value) -> Option<Vec<&str>> {
+    match property.get("type")? {
+        Value::String(t) => Some(vec![t.as_str()]),
+        Value::Array(ts) => Some(ts.iter().filter_map(|t| t.as_str()).collect()),
+        _ => None,
+    }
+}
+
+fn value_fits_schema(value: &Value, property: &Value) -> bool {
+    if let Some(allowed) = property.get("enum").and_then(|e| e.as_array())
+        && !allowed
+            .iter()
+            .any(|a| evaluate_condition(&Condition::Equality(a.clone()), value))
+    {
+        return false;
+    }
+    let Some(types) = schema_types(property) else {
+        return true;
+    };
+    types.iter().any(|t| match *t {
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.as_f64().is_some_and(|f| f.fract() == 0.0),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => true,
+    })
+}
+
+// ─── A-003 / A-004: trigger liveness ────────────────────────────────────────
+
+/// Largest `trigger.count` that [`trigger_liveness`] treats as plausible.
+pub const MAX_PLAUSIBLE_TRIGGER_COUNT: i64 = 1_000;
+
+/// State lists exposed by an MCP server, keyed by the request event that
+/// targets one of their entries and the field that names an entry.
+const MCP_SERVER_TARGETS: &[(&str, &str, &str)] = &[
+    ("tools/call", "tools", "name"),
+    ("prompts/get", "prompts", "name"),
+    ("resources/read", "resources", "uri"),
+];
+
+/// Detect phases whose trigger can never fire.
+///
+/// Complements V-029 with state-aware reasoning. For `mcp_server` phases, the
+/// trigger event is checked against the phase's effective state (§5.11): a
+/// client cannot call a tool, get a prompt, or read a resource that the server
+/// never exposes. A phase is reported as **A-003** when its trigger waits on
+/// such a request and has no `after` timeout to fall back on. The target name
+/// is taken from the event qualifier (`tools/call:name`) or from a scalar
+/// `params.name` in `trigger.match`.
+///
+/// A trigger is reported as **A-004** when its `count` is below 1 or above
+/// [`MAX_PLAUSIBLE_TRIGGER_COUNT`].
+pub fn trigger_liveness(doc: &Document) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for actor_info in ResolvedContext::new(doc).actors() {
+        for (pi, phase) in actor_info.phases.iter().enumerate() {
+            let Some(trigger) = &phase.trigger else {
+                continue;
+            };
+            let trigger_path = format!("{}.phases[{}].trigger", actor_info.path_prefix, pi);
+
+            if let Some(count) = trigger.count
+                && !(1..=MAX_PLAUSIBLE_TRIGGER_COUNT).contains(&count)
+            {
+                diagnostics.push(warning(
+                    "A-004",
+                    format!("{}.count", trigger_path),
+                    format!(
+                        "trigger count {} is outside the plausible range 1..={}",
+                        count, MAX_PLAUSIBLE_TRIGGER_COUNT
+                    ),
+                ));
+            }
+
+            let Some(event) = &trigger.event else {
+                continue;
+            };
+            if trigger.after.is_some() {
+                continue;
+            }
+            let mode = actor_info.phase_mode(pi);
+            if mode.map(|m| Mode::parse(m).name).as_deref() != Some("mcp_server") {
+                continue;
+            }
+            let state = compute_effective_state(actor_info.phases, pi);
+            if let Some(reason) = unreachable_mcp_request(event, trigger, &state) {
+                diagnostics.push(warning(
+                    "A-003",
+                    format!("{}.event", trigger_path),
+                    format!("phase can never advance: {}", reason),
+                ));
+            }
+        }
+    }
+    diagnostics
+}
+
+fn unreachable_mcp_request(event: &str, trigger: &Trigger, state: &Value) -> Option<String> {
+    let (base, qualifier) = parse_event_qualifier(event);
+    let &(_, list_key, name_key) = MCP_SERVER_TARGETS.iter().find(|(e, _, _)| *e == base)?;
+    // Without a state object there is nothing to reason about.
+    let state = state.as_object()?;
+
+    let exposed: Vec<&str> = state
+        .get(list_key)
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.get(name_key).and_then(|n| n.as_str()))
+                .collect()
+        })
+        .unwrap_or_default();
+    if exposed.is_empty() {
+        return Some(format!(
+            "'{}' is awaited but the effective state exposes no {}",
+            base, list_key
+        ));
+    }
+
+    let matched_name = trigger
+        .match_predicate
+        .as_ref()
+        .and_then(|m| match m.get("params.name") {
+            Some(MatchEntry::Scalar(Value::String(name))) => Some(name.as_str()),
+            _ => None,
+        });
+    for name in qualifier.into_iter().chain(matched_name) {
+        if !exposed.contains(&name) {
+            return Some(format!(
+                "'{}' is awaited for '{}' but the effective state does not expose it",
+                base, name
+            ));
+        }
+    }
+    None
+}
+
+// ─── A-005 / A-006 / A-007: synthesize prompt content ──────────────────────
+
+/// Review policy for [`synthesize_prompts`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PromptPolicy {
+    /// Hosts that prompts may reference in absolute URLs. Entries are host
+    /// names or `*.` suffix patterns (matching subdomains only).
+    pub allowed_hosts: Vec<String>,
+    /// Largest prompt, in bytes, before it is reported.
+    pub max_prompt_bytes: usize,
+}
+
+impl Default for PromptPolicy {
+    fn default() -> Self {
+        PromptPolicy {
+            allowed_hosts: Vec::new(),
+            max_prompt_bytes: 4096,
+        }
+    }
+}
+
+/// Literal credentials that should be passed in through a template instead.
+static SECRET_PATTERNS: LazyLock<Vec<(&'static str, Regex)>> = LazyLock::new(|| {
+    [
+        ("AWS access key", r"\bAKIA[0-9A-Z]{16}\b"),
+        ("GitHub token", r"\bgh[pousr]_[A-Za-z0-9]{36,}\b"),
+        ("Slack token", r"\bxox[abprs]-[A-Za-z0-9-]{10,}"),
+        ("API secret key", r"\bsk-[A-Za-z0-9_-]{20,}"),
+        ("private key", r"-----BEGIN [A-Z ]*PRIVATE KEY-----"),
+        (
+            "credential assignment",
+            r"(?i)\b(password|passwd|secret|api[_-]?key|token)\s*[:=]\s*[^\s{]{8,}",
+        ),
+    ]
+    .into_iter()
+    .map(|(name, pattern)| (name, Regex::new(pattern).unwrap()))
+    .collect()
+});
+
+/// Review `synthesize.prompt` blocks before a generation provider runs them.
+///
+/// Every `synthesize` block anywhere in a phase state is checked:
+///
+/// - **A-005**: the prompt contains a literal secret (cloud or API token,
+///   private key, or a `password=`-style assignment). Secrets should be
+///   supplied through `{{template}}` parameters.
+/// - **A-006**: the prompt contains an absolute URL whose host is not in
+///   [`PromptPolicy::allowed_hosts`]. URLs with a templated host are skipped.
+/// - **A-007**: the prompt is larger than [`PromptPolicy::max_prompt_bytes`].
+///
+/// [`analyze`] runs this pass with [`PromptPolicy::default`], which allows
+/// no hosts.
+pub fn synthesize_prompts(doc: &Document, policy: &PromptPolicy) -> Vec<Diagnostic> {
+    let mut prompts = Vec::new();
+    for (path, state) in phase_states(doc) {
+        collect_prompts(state, path, &mut prompts);
+    }
+
+    let mut diagnostics = Vec::new();
+    for (path, prompt) in prompts {
+        for (name, pattern) in SECRET_PATTERNS.iter() {
+            if pattern.is_match(prompt) {
+                diagnostics.push(warning(
+                    "A-005",
+                    path.clone(),
+                    format!("synthesize prompt contains a literal {}", name),
+                ));
+            }
+        }
+        for origin in url_origins(prompt) {
+            let host = origin.split_once("://").map_or(origin.as_str(), |(_, a)| a);
+            let host = host.rsplit_once('@').map_or(host, |(_, h)| h);
+            let host = match host.strip_prefix('[') {
+                Some(v6) => v6.split(']').next().unwrap_or(v6),
+                None => host.split(':').next().unwrap_or(host),
+            }
+            .to_ascii_lowercase();
+            let allowed = policy.allowed_hosts.iter().any(|pattern| {
+                let pattern = pattern.to_ascii_lowercase();
+                match pattern.strip_prefix("*.") {
+                    Some(suffix) => host
+                        .strip_suffix(suffix)
+                        .is_some_and(|sub| sub.ends_with('.')),
+                    None => host == pattern,
+                }
+            });
+            if !allowed {
+                diagnostics.push(warning(
+                    "A-006",
+                    path.clone(),
+                    format!(
+                        "synthesize prompt references non-allowlisted host '{}'",
+                        host
+                    ),
+                ));
+            }
+        }
+        if prompt.len() > policy.max_prompt_bytes {
+            diagnostics.push(warning(
+                "A-007",
+                path.clone(),
+                format!(
+                    "synthesize prompt is {} bytes, over the {} byte budget",
+                    prompt.len(),
+                    policy.max_prompt_bytes
+                ),
+            ));
+        }
+    }
+    diagnostics
+}
+
+/// Every `synthesize.prompt` string under `value`, with its dot-path.
+fn collect_prompts<'a>(value: &'a Value, path: String, prompts: &mut Vec<(String, &'a str)>) {
+    match value {
+        Value::Object(map) => {
+            if let Some(prompt) = map
+                .get("synthesize")
+                .and_then(|s| s.get("prompt"))
+                .and_then(|p| p.as_str())
+            {
+                prompts.push((format!("{}.synthesize.prompt", path), prompt));
+            }
+            for (key, v) in map {
+                if key != "synthesize" {
+                    collect_prompts(v, format!("{}.{}", path, key), prompts);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for (i, v) in items.iter().enumerate() {
+                collect_prompts(v, format!("{}[{}]", path, i), prompts);
+            }
+        }
+        _ => {}
+    }
+}
+
+// ─── Cross-phase state diffs ────────────────────────────────────────────────
+
+/// Lists compared by [`state_diffs`]: state key and the field naming an item.
+const DIFFED_LISTS: &[(&str, &str)] =
+    &[("tools", "name"), ("prompts", "name"), ("resources", "uri")];
+
+/// How a listed item changed between consecutive phases.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// The item is new in the later phase.
+    Added,
+    /// The item is no longer listed in the later phase.
+    Removed,
+    /// The item is listed in both phases with different content.
+    Modified,
+}
+
+/// A tool, prompt, or resource that differs between consecutive phases.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ItemChange {
+    /// State key of the list: `tools`, `prompts`, or `resources`.
+    pub list: &'static str,
+    /// The item's name (its URI for resources).
+    pub name: String,
+    /// How the item changed.
+    pub kind: ChangeKind,
+    /// Top-level fields added, removed, or changed, sorted (e.g.,
+    /// `description`). Empty unless `kind` is `Modified`.
+    pub fields: Vec<String>,
+    /// The item in the earlier phase, unless it was added.
+    pub before: Option<Value>,
+    /// The item in the later phase, unless it was removed.
+    pub after: Option<Value>,
+}
+
+/// The changes to an actor's effective state on entering a phase.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StateDiff {
+    /// The actor's name.
+    pub actor: String,
+    /// Index of the phase entered.
+    pub phase: usize,
+    /// Path of that phase (e.g., `attack.execution.actors[0].phases[1]`).
+    pub path: String,
+    /// Changed items, by list in `tools`, `prompts`, `resources` order.
+    pub changes: Vec<ItemChange>,
+}
+
+impl StateDiff {
+    /// The later phase's added and modified items, shaped as a listing
+    /// result (`{"tools": [...], ...}`) so that indicators on listing
+    /// surfaces such as `tool_description` see only what changed.
+    pub fn changed_listing(&self) -> Value {
+        let mut listing = serde_json::Map::new();
+        for change in &self.changes {
+            if let Some(after) = &change.after {
+                listing
+                    .entry(change.list)
+                    .or_insert_with(|| Value::Array(Vec::new()))
+                    .as_array_mut()
+                    .expect("listing entries are arrays")
+                    .push(after.clone());
+            }
+        }
+        Value::Object(listing)
+    }
+}
+
+/// Compute the structural differences between the effective states (§5.11)
+/// of each actor's consecutive phases.
+///
+/// Tools and prompts are matched by `name` and resources by `uri`; an item
+/// is reported as added, removed, or modified with the top-level fields that
+/// changed. Only phases whose effective state changes something are
+/// returned. A benign tool whose `description` is modified in a later phase
+/// is the classic "rug pull"; [`evaluate_indicator_on_diff`] checks an
+/// indicator against just the changed items.
+pub fn state_diffs(doc: &Document) -> Vec<StateDiff> {
+    let mut diffs = Vec::new();
+    for actor_info in ResolvedContext::new(doc).actors() {
+        let mut before = compute_effective_state(actor_info.phases, 0);
+        for pi in 1..actor_info.phases.len() {
+            let after = compute_effective_state(actor_info.phases, pi);
+            let changes = diff_states(&before, &after);
+            if !changes.is_empty() {
+                diffs.push(StateDiff {
+                    actor: actor_info.name.to_string(),
+                    phase: pi,
+                    path: format!("{}.phases[{}]", actor_info.path_prefix, pi),
+                    changes,
+                });
+            }
+            before = after;
+        }
+    }
+    diffs
+}
+
+fn diff_states(before: &Value, after: &Value) -> Vec<ItemChange> {
+    let mut changes = Vec::new();
+    for &(list, name_key) in DIFFED_LISTS {
+        let items = |state: &Value| -> Vec<(String, Value)> {
+            state
+                .get(list)
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|item| {
+                    let name = item.get(name_key)?.as_str()?;
+                    Some((name.to_string(), item.clone()))
+                })
+                .collect()
+        };
+        let (old, new) = (items(before), items(after));
+        for (name, item) in &new {
+            match old.iter().find(|(n, _)| n == name) {
+                None => changes.push(ItemChange {
+                    list,
+                    name: name.clone(),
+                    kind: ChangeKind::Added,
+                    fields: Vec::new(),
+                    before: None,
+                    after: Some(item.clone()),
+                }),
+                Some((_, previous)) if previous != item => changes.push(ItemChange {
+                    list,
+                    name: name.clone(),
+                    kind: ChangeKind::Modified,
+                    fields: changed_fields(previous, item),
+                    before: Some(previous.clone()),
+                    after: Some(item.clone()),
+                }),
+                Some(_) => {}
+            }
+        }
+        for (name, item) in &old {
+            if !new.iter().any(|(n, _)| n == name) {
+                changes.push(ItemChange {
+                    list,
+                    name: name.clone(),
+                    kind: ChangeKind::Removed,
+                    fields: Vec::new(),
+                    before: Some(item.clone()),
+                    after: None,
+                });
+            }
+        }
+    }
+    changes
+}
+
+fn changed_fields(before: &Value, after: &Value) -> Vec<String> {
+    let empty = serde_json::Map::new();
+    let before = before.as_object().unwrap_or(&empty);
+    let after = after.as_object().unwrap_or(&empty);
+    let mut fields: Vec<String> = before
+        .keys()
+        .chain(after.keys())
+        .filter(|key| before.get(*key) != after.get(*key))
+        .cloned()
+        .collect();
+    fields.sort();
+    fields.dedup();
+    fields
+}
+
+/// Evaluate an indicator against the items a phase added or modified
+/// ([`StateDiff::changed_listing`]), so an indicator can assert on what a
+/// phase changed — for example, a `tool_description` pattern that matches
+/// only when a tool's description turns malicious mid-session. A match
+/// carries the diff's phase path as evidence. Like [`evaluate_indicator`],
+/// the indicator must be in normalized standard form.
+pub fn evaluate_indicator_on_diff(
+    indicator: &Indicator,
+    diff: &StateDiff,
+    cel_evaluator: Option<&dyn CelEvaluator>,
+    semantic_evaluator: Option<&dyn SemanticEvaluator>,
+) -> IndicatorVerdict {
+    let verdict = evaluate_indicator(
+        indicator,
+        &diff.changed_listing(),
+        cel_evaluator,
+        semantic_evaluator,
+    );
+    match verdict.result {
+        IndicatorResult::Matched => IndicatorVerdict {
+            evidence: Some(diff.path.clone()),
+            ..verdict
+        },
+        _ => verdict,
+    }
+}
+
+// ─── Indicator suggestions ──────────────────────────────────────────────────
+
+/// Phrases that commonly mark injected instructions or sensitive targets in
+/// attacker-controlled text. Matched ASCII case-insensitively.
+pub const SUSPICIOUS_PHRASES: &[&str] = &[
+    "<important>",
+    "<system>",
+    "ignore previous instructions",
+    "ignore all previous",
+    "do not tell the user",
+    "don't tell the user",
+    "do not mention",
+    "without informing",
+    "system prompt",
+    "id_rsa",
+    ".ssh/",
+    ".env",
+    "mcp.json",
+    "api key",
+    "password",
+];
+
+/// `inputSchema` property names that rarely belong to a legitimate tool and
+/// often smuggle context or secrets out of the caller.
+pub const SUSPICIOUS_SCHEMA_PROPERTIES: &[&str] = &[
+    "sidenote",
+    "instructions",
+    "system_prompt",
+    "conversation_history",
+    "chat_history",
+    "api_key",
+    "apikey",
+    "secret",
+    "password",
+    "credentials",
+    "private_key",
+    "ssh_key",
+];
+
+/// Propose candidate indicators from the attack's own payloads.
+///
+/// Every phase state is scanned for:
+///
+/// - [`SUSPICIOUS_PHRASES`] in tool, prompt, and resource descriptions
+///   (`tool_description`, `prompt_description`, `resource_description`) and
+///   in static tool and prompt response text (`tool_response`,
+///   `prompt_content`);
+/// - URLs in `synthesize.prompt` and static response text, suggested as a
+///   `contains` on the URL's origin for the surface the response is served on
+///   (`tool_response`, `prompt_content`, or `task_message`);
+/// - [`SUSPICIOUS_SCHEMA_PROPERTIES`] declared in a tool's `inputSchema`
+///   (`tool_input_schema`, `exists: true` on the property).
+///
+/// Suggestions are in standard form targeting the surface's text (the
+/// surface's default target, or the `text` members of response content), carry
+/// a description of why they were proposed, and have no `id` or `confidence`:
+/// authors are expected to curate them. Duplicates, and suggestions whose
+/// `contains` value an existing indicator on the same surface already uses,
+/// are dropped.
+pub fn suggest_indicators(doc: &Document) -> Vec<Indicator> {
+    let mut suggestions = Suggestions::default();
+    for indicator in doc.attack.indicators.iter().flatten() {
+        let contains = indicator.pattern.as_ref().and_then(|p| match &p.condition {
+            Some(Condition::Operators(c)) => c.contains.clone(),
+            _ => p.contains.clone(),
+        });
+        if let Some(contains) = contains {
+            suggestions
+                .seen
+                .push((indicator.surface.clone(), "contains".to_string(), contains));
+        }
+    }
+
+    for (_, state) in phase_states(doc) {
+        let Some(obj) = state.as_object() else {
+            continue;
+        };
+        for (list, surface) in [
+            ("tools", "tool_description"),
+            ("prompts", "prompt_description"),
+            ("resources", "resource_description"),
+        ] {
+            for item in obj
+                .get(list)
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+            {
+                if let Some(text) = item.get("description").and_then(|d| d.as_str()) {
+                    suggestions.phrases(surface, text);
+                }
+            }
+        }
+
+        for tool in obj
+            .get("tools")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+        {
+            for property in tool
+                .pointer("/inputSchema/properties")
+                .and_then(|p| p.as_object())
+                .into_iter()
+                .flat_map(|p| p.keys())
+            {
+                if SUSPICIOUS_SCHEMA_PROPERTIES.contains(&property.to_ascii_lowercase().as_str()) {
+                    suggestions.push(
+                        "tool_input_schema",
+                        format!("tools[*].inputSchema.properties.{}", property),
+                        "exists",
+                        Value::Bool(true),
+                        format!("Tool input schema declares a '{}' property", property),
+                    );
+                }
+            }
+            for entry in tool
+                .get("responses")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+            {
+                suggestions.response(entry, "tool_response", "content");
+            }
+        }
+        for prompt in obj
+            .get("prompts")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+        {
+            for entry in prompt
+                .get("responses")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+            {
+                suggestions.response(entry, "prompt_content", "messages");
+            }
+        }
+        for entry in obj
+            .get("task_responses")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+        {
+            suggestions.response(entry, "task_message", "messages");
+        }
+    }
+
+    suggestions.indicators
+}
+
+#[derive(Default)]
+struct Suggestions {
+    /// (surface, operator, value) already covered.
+    seen: Vec<(String, String, String)>,
+    indicators: Vec<Indicator>,
+}
+
+impl Suggestions {
+    fn push(
+        &mut self,
+        surface: &str,
+        target: String,
+        operator: &str,
+        value: Value,
+        description: String,
+    ) {
+        let key_value = match &value {
+            Value::String(s) => s.clone(),
+            other => format!("{}:{}", target, other),
+        };
+        let key = (surface.to_string(), operator.to_string(), key_value);
+        if self.seen.contains(&key) {
+            return;
+        }
+        self.seen.push(key);
+        let mut condition = serde_json::Map::new();
+        condition.insert(operator.to_string(), value);
+        let indicator = serde_json::json!({
+            "surface": surface,
+            "description": format!("Suggested: {}", description),
+            "pattern": {"target": target, "condition": condition},
+        });
+        self.indicators.push(
+            serde_json::from_value(indicator).expect("suggestion matches the indicator model"),
+        );
+    }
+
+    fn phrases(&mut self, surface: &str, text: &str) {
+        let lower = text.to_ascii_lowercase();
+        for phrase in SUSPICIOUS_PHRASES {
+            if let Some(start) = lower.find(phrase) {
+                // ASCII lowercasing preserves byte offsets.
+                let found = &text[start..start + phrase.len()];
+                let target = text_target(surface);
+                self.push(
+                    surface,
+                    target,
+                    "contains",
+                    Value::String(found.to_string()),
+                    format!("attack payload contains '{}'", found),
+                );
+            }
+        }
+    }
+
+    fn urls(&mut self, surface: &str, text: &str) {
+        let target = text_target(surface);
+        for origin in url_origins(text) {
+            self.push(
+                surface,
+                target.clone(),
+                "contains",
+                Value::String(origin.clone()),
+                format!("attack payload references '{}'", origin),
+            );
+        }
+    }
+
+    fn response(&mut self, entry: &Value, surface: &str, static_key: &str) {
+        if let Some(prompt) = entry.pointer("/synthesize/prompt").and_then(|p| p.as_str()) {
+            self.urls(surface, prompt);
+        }
+        let mut texts = Vec::new();
+        collect_text(entry.get(static_key).unwrap_or(&Value::Null), &mut texts);
+        for text in texts {
+            self.phrases(surface, text);
+            self.urls(surface, text);
+        }
+    }
+}
+
+/// Target resolving to the text of a surface. Response surfaces default to
+/// content items or messages, which `contains` cannot match, so suggestions
+/// reach into their `text` members.
+fn text_target(surface: &str) -> String {
+    match surface {
+        "tool_response" => "content[*].text".to_string(),
+        "prompt_content" => "messages[*].content.text".to_string(),
+        "task_message" => "messages[*].parts[*].text".to_string(),
+        other => lookup_surface(other)
+            .map(|s| s.default_target.to_string())
+            .unwrap_or_default(),
+    }
+}
+
+/// All `text` string members nested anywhere under `value`.
+fn collect_text<'a>(value: &'a Value, texts: &mut Vec<&'a str>) {
+    match value {
+        Value::Object(map) => {
+            for (key, v) in map {
+                match v {
+                    Value::String(s) if key == "text" => texts.push(s),
+                    _ => collect_text(v, texts),
+                }
+            }
+        }
+        Value::Array(items) => items.iter().for_each(|v| collect_text(v, texts)),
+        _ => {}
+    }
+}
+
+/// `scheme://authority` of every http(s) URL in `text`, in order, without
+/// duplicates. URLs whose authority is a template are skipped.
+fn url_origins(text: &str) -> Vec<String> {
+    let mut origins: Vec<String> = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("http") {
+        let candidate = &rest[start..];
+        let scheme = ["https://", "http://"]
+            .into_iter()
+            .find(|s| candidate.starts_with(s));
+        let Some(scheme) = scheme else {
+            rest = &candidate[4..];
+            continue;
+        };
+        let authority: String = candidate[scheme.len()..]
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || ".-:@[]".contains(*c))
+            .collect();
+        if !authority.is_empty() && !authority.starts_with('.') {
+            let origin = format!("{}{}", scheme, authority);
+            if !origins.contains(&origin) {
+                origins.push(origin);
+            }
+        }
+        rest = &candidate[scheme.len() + authority.len()..];
+    }
+    origins
+}
+
+// ─── A-008 / A-009 / A-010: indicator evaluation cost ───────────────────────
+
+/// Cost limits for [`profile_indicators_with`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProfileOptions {
+    /// Times each indicator is evaluated against each sample; timings are
+    /// averaged over all evaluations.
+    pub iterations: usize,
+    /// Mean evaluation time above which an indicator is reported (A-008).
+    pub max_mean_time: Duration,
+    /// Number of values a target may resolve to in one sample before it is
+    /// reported (A-009).
+    pub max_fan_out: usize,
+    /// Largest bounded repetition a regex may use before it is reported
+    /// (A-010).
+    pub max_regex_repetition: u32,
+}
+
+impl Default for ProfileOptions {
+    fn default() -> Self {
+        ProfileOptions {
+            iterations: 5,
+            max_mean_time: Duration::from_micros(500),
+            max_fan_out: 256,
+            max_regex_repetition: 100,
+        }
+    }
+}
+
+/// Measured evaluation cost of one indicator, from [`profile_indicators`].
+#[derive(Clone, Debug)]
+pub struct IndicatorProfile {
+    /// The indicator's ID after normalization.
+    pub indicator_id: String,
+    /// Path of the indicator (e.g., `attack.indicators[0]`).
+    pub path: String,
+    /// Number of evaluations timed.
+    pub evaluations: usize,
+    /// Mean wall-clock time of one evaluation.
+    pub mean_time: Duration,
+    /// Slowest single evaluation.
+    pub max_time: Duration,
+    /// Most values the pattern or semantic target resolved to in one sample.
+    pub max_fan_out: usize,
+    /// A-008 to A-010 findings for this indicator, each with a suggested
+    /// rewrite.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Measure how expensive each indicator is to evaluate on representative
+/// messages, with [`ProfileOptions::default`] limits. Expressions use
+/// [`DefaultCelEvaluator`](crate::evaluate::DefaultCelEvaluator) when the
+/// `cel-eval` feature is enabled and
+/// [`CelSubsetEvaluator`](crate::evaluate::CelSubsetEvaluator) otherwise.
+pub fn profile_indicators(doc: &Document, samples: &[Value]) -> Vec<IndicatorProfile> {
+    #[cfg(feature = "cel-eval")]
+    let cel: Option<&dyn CelEvaluator> = Some(&crate::evaluate::DefaultCelEvaluator);
+    #[cfg(not(feature = "cel-eval"))]
+    let cel: Option<&dyn CelEvaluator> = Some(&crate::evaluate::CelSubsetEvaluator);
+    profile_indicators_with(doc, samples, &ProfileOptions::default(), cel)
+}
+
+/// Measure each indicator's evaluation cost on `samples` and flag the ones
+/// likely to hurt gateway latency.
+///
+/// The document is normalized first, so shorthand patterns and default
+/// targets are profiled as they would be evaluated. Samples may be raw
+/// protocol messages, as for
+/// [`evaluate_indicator`](crate::evaluate::evaluate_indicator). Each
+/// indicator is reported with:
+///
+/// - **A-008**: its mean evaluation time exceeds
+///   [`ProfileOptions::max_mean_time`]. The suggested rewrite depends on the
+///   detection method: a CEL expression that is a simple conjunction is
+///   given as the equivalent pattern condition, semantic indicators are
+///   pointed at a pattern pre-filter.
+/// - **A-009**: its target resolves to more than
+///   [`ProfileOptions::max_fan_out`] values in some sample; narrow the
+///   wildcard segments.
+/// - **A-010**: its regex has a statically costly shape: a leading or
+///   trailing `.*` (redundant in an unanchored search), a bounded repetition
+///   above [`ProfileOptions::max_regex_repetition`], or a compiled program
+///   over 1 MiB.
+///
+/// Evaluations that come back `skipped` (semantic indicators, which need an
+/// external backend, or CEL without an evaluator) are not timed; such an
+/// indicator reports zero evaluations.
+pub fn profile_indicators_with(
+    doc: &Document,
+    samples: &[Value],
+    options: &ProfileOptions,
+    cel_evaluator: Option<&dyn CelEvaluator>,
+) -> Vec<IndicatorProfile> {
+    let doc = crate::normalize::normalize(doc.clone());
+    let iterations = options.iterations.max(1);
+    let mut profiles = Vec::new();
+
+    for (i, indicator) in doc.attack.indicators.iter().flatten().enumerate() {
+        let path = format!("attack.indicators[{}]", i);
+        let mut diagnostics = Vec::new();
+        let mut total = Duration::ZERO;
+        let mut max_time = Duration::ZERO;
+        let mut evaluations = 0;
+        let mut max_fan_out = 0;
+
+        let target = indicator
+            .pattern
+            .as_ref()
+            .and_then(|p| p.target.as_deref())
+            .or_else(|| indicator.semantic.as_ref()?.target.as_deref());
+        for sample in samples {
+            if let Some(target) = target {
+                let payload = surface_text_extractor(&indicator.surface)
+                    .map_or(sample, |e| e.payload(sample));
+                max_fan_out = max_fan_out.max(resolve_wildcard_path_ref(target, payload).len());
+            }
+            for _ in 0..iterations {
+                let start = Instant::now();
+                let verdict = evaluate_indicator(indicator, sample, cel_evaluator, None);
+                let elapsed = start.elapsed();
+                if verdict.result == IndicatorResult::Skipped {
+                    continue;
+                }
+                total += elapsed;
+                max_time = max_time.max(elapsed);
+                evaluations += 1;
+            }
+        }
+        let mean_time = if evaluations == 0 {
+            Duration::ZERO
+        } else {
+            total / evaluations as u32
+        };
+
+        if mean_time > options.max_mean_time {
+            diagnostics.push(warning(
+                "A-008",
+                path.clone(),
+                format!(
+                    "indicator takes {:?} per evaluation on average (limit {:?}); {}",
+                    mean_time,
+                    options.max_mean_time,
+                    slow_indicator_suggestion(indicator)
+                ),
+            ));
+        }
+        if max_fan_out > options.max_fan_out {
+            diagnostics.push(warning(
+                "A-009",
+                format!("{}.pattern.target", path),
+                format!(
+                    "target '{}' resolves to {} values in one sample (limit {}); replace [*] segments with specific indices or a deeper path",
+                    target.unwrap_or_default(),
+                    max_fan_out,
+                    options.max_fan_out
+                ),
+            ));
+        }
+        if let Some(regex) = indicator.pattern.as_ref().and_then(pattern_regex) {
+            for finding in regex_cost_findings(regex, options.max_regex_repetition) {
+                diagnostics.push(warning(
+                    "A-010",
+                    format!("{}.pattern.condition.regex", path),
+                    finding,
+                ));
+            }
+        }
+
+        profiles.push(IndicatorProfile {
+            indicator_id: indicator.id.clone().unwrap_or_default(),
+            path,
+            evaluations,
+            mean_time,
+            max_time,
+            max_fan_out,
+            diagnostics,
+        });
+    }
+    profiles
+}
+
+fn slow_indicator_suggestion(indicator: &Indicator) -> String {
+    if let Some(expression) = &indicator.expression {
+        if let Some(predicate) = crate::convert::cel_to_predicate(&expression.cel) {
+            return format!(
+                "the expression is a simple conjunction; rewrite it as trigger-style conditions: {}",
+                serde_json::to_string(&predicate).unwrap_or_default()
+            );
+        }
+        return "precompute bindings with `variables` or split the expression into pattern indicators".to_string();
+    }
+    if indicator.semantic.is_some() {
+        return "pair it with a cheap pattern indicator and correlate with `all`".to_string();
+    }
+    "use literal operators (contains, starts_with) instead of regex where possible".to_string()
+}
+
+fn pattern_regex(pattern: &PatternMatch) -> Option<&str> {
+    match &pattern.condition {
+        Some(Condition::Operators(c)) => c.regex.as_deref(),
+        _ => pattern.regex.as_deref(),
+    }
+}
+
+static BOUNDED_REPETITION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{(\d+)(?:,(\d*))?\}").unwrap());
+
+/// Costly shapes in a regex, as messages with a suggested rewrite.
+fn regex_cost_findings(regex: &str, max_repetition: u32) -> Vec<String> {
+    let mut findings = Vec::new();
+    let body = regex.strip_prefix("(?i)").unwrap_or(regex);
+    if body.starts_with(".*") || body.starts_with(".+") {
+        findings.push(
+            "leading '.*' is redundant in an unanchored search and widens every match; drop it"
+                .to_string(),
+        );
+    }
+    if (regex.ends_with(".*") || regex.ends_with(".+")) && !regex.ends_with("\\.*") {
+        findings.push(
+            "trailing '.*' is redundant in an unanchored search and widens every match; drop it"
+                .to_string(),
+        );
+    }
+    for caps in BOUNDED_REPETITION_RE.captures_iter(regex) {
+        let bound = caps
+            .get(2)
+            .filter(|m| !m.as_str().is_empty())
+            .unwrap_or_else(|| caps.get(1).unwrap());
+        if bound
+            .as_str()
+            .parse::<u32>()
+            .is_ok_and(|n| n > max_repetition)
+        {
+            findings.push(format!(
+                "repetition '{}' compiles into {} copies of its operand; use an unbounded repetition or check the length separately",
+                &caps[0],
+                bound.as_str()
+            ));
+        }
+    }
+    if regex::RegexBuilder::new(regex)
+        .size_limit(1 << 20)
+        .build()
+        .is_err()
+        && Regex::new(regex).is_ok()
+    {
+        findings.push(
+            "compiled regex exceeds 1 MiB; split it into several indicators or simplify Unicode classes (e.g. \\w → [A-Za-z0-9_])"
+                .to_string(),
+        );
+    }
+    findings
+}
+
+// ─── Execution cost ─────────────────────────────────────────────────────────
+
+/// Expected cost of one phase of an execution run, from [`estimate_cost`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PhaseCost {
+    /// The actor's name.
+    pub actor: String,
+    /// Path of the phase (e.g., `attack.execution.actors[0].phases[1]`).
+    pub path: String,
+    /// Messages the harness sends: the requests of a client phase or the
+    /// responses a server phase gives before its trigger fires, plus
+    /// `send_notification` actions.
+    pub messages: usize,
+    /// `synthesize` blocks in the phase's effective state, each one call to
+    /// the generation provider.
+    pub synthesize_calls: usize,
+    /// The trigger's `after` timeout.
+    pub timeout: Option<Duration>,
+    /// Longest the phase can take: its timeout plus pacing delays, or `None`
+    /// if a server phase waits on an event without a timeout.
+    pub worst_case_duration: Option<Duration>,
+}
+
+/// Expected cost of an execution run, from [`estimate_cost`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CostEstimate {
+    /// Messages sent by all actors, at most `safety.max_messages`.
+    pub messages: usize,
+    /// Generation provider calls by all actors.
+    pub synthesize_calls: usize,
+    /// Phases with an `after` timeout.
+    pub timeouts: usize,
+    /// Longest the run can take, with actors running concurrently, at most
+    /// `safety.max_runtime`. `None` if some phase can wait forever and the
+    /// run has no runtime limit.
+    pub worst_case_duration: Option<Duration>,
+    /// Per-phase costs, by actor and phase order.
+    pub phases: Vec<PhaseCost>,
+}
+
+/// Estimate what running `doc` costs, to budget harness runs and generation
+/// provider spend before launching them.
+///
+/// The document is normalized first, so paths refer to the multi-actor form.
+/// Every phase is assumed to run. A client phase sends each request its
+/// state lists; a server phase answers `trigger.count` (default 1) requests
+/// before advancing. A phase that times out runs its `on_timeout` actions
+/// too, and pacing adds `delay` plus the full `jitter` before every message
+/// after the first. Unparseable durations count as absent.
+pub fn estimate_cost(doc: &Document) -> CostEstimate {
+    let doc = crate::normalize::normalize(doc.clone());
+    let duration = |s: Option<&String>| s.and_then(|s| parse_duration(s).ok());
+    let mut phases = Vec::new();
+    let mut longest_actor = Some(Duration::ZERO);
+
+    for actor_info in ResolvedContext::new(&doc).actors() {
+        let mut actor_duration = Some(Duration::ZERO);
+        for (pi, phase) in actor_info.phases.iter().enumerate() {
+            let server = actor_info
+                .phase_mode(pi)
+                .is_some_and(|m| Mode::parse(m).name.ends_with("_server"));
+            let trigger = phase.trigger.as_ref();
+            let timeout = duration(trigger.and_then(|t| t.after.as_ref()));
+            let waits_on_event = trigger.is_some_and(|t| t.event.is_some());
+
+            let exchanges = if server {
+                match trigger {
+                    Some(t) if waits_on_event => t.count.unwrap_or(1).max(0) as usize,
+                    _ => 0,
+                }
+            } else {
+                crate::engine::client_requests(phase.state.as_ref()).len()
+            };
+            let notifications = |actions: &Option<Vec<Action>>| {
+                actions
+                    .iter()
+                    .flatten()
+                    .filter(|a| matches!(a, Action::SendNotification { .. }))
+                    .count()
+            };
+            let mut messages = exchanges + notifications(&phase.on_enter);
+            if timeout.is_some() {
+                messages += notifications(&phase.on_timeout);
+            }
+
+            let mut prompts = Vec::new();
+            let state = compute_effective_state(actor_info.phases, pi);
+            collect_prompts(&state, String::new(), &mut prompts);
+
+            let pacing = phase.pacing.as_ref();
+            let gap = duration(pacing.and_then(|p| p.delay.as_ref())).unwrap_or_default()
+                + duration(pacing.and_then(|p| p.jitter.as_ref())).unwrap_or_default();
+            let paced = gap * messages.saturating_sub(1) as u32;
+            let worst_case_duration = match timeout {
+                Some(timeout) => Some(timeout + paced),
+                None if server && waits_on_event => None,
+                None => Some(paced),
+            };
+            actor_duration = actor_duration.zip(worst_case_duration).map(|(a, b)| a + b);
+
+            phases.push(PhaseCost {
+                actor: actor_info.name.to_string(),
+                path: format!("{}.phases[{}]", actor_info.path_prefix, pi),
+                messages,
+                synthesize_calls: prompts.len(),
+                timeout,
+                worst_case_duration,
+            });
+        }
+        longest_actor = longest_actor.zip(actor_duration).map(|(a, b)| a.max(b));
+    }
+
+    let safety = doc.attack.safety.as_ref();
+    let max_runtime = duration(safety.and_then(|s| s.max_runtime.as_ref()));
+    let max_messages = safety
+        .and_then(|s| s.max_messages)
+        .map(|m| m.max(0) as usize);
+    let messages: usize = phases.iter().map(|p| p.messages).sum();
+    CostEstimate {
+        messages: max_messages.map_or(messages, |max| messages.min(max)),
+        synthesize_calls: phases.iter().map(|p| p.synthesize_calls).sum(),
+        timeouts: phases.iter().filter(|p| p.timeout.is_some()).count(),
+        worst_case_duration: match (longest_actor, max_runtime) {
+            (Some(run), Some(max)) => Some(run.min(max)),
+            (run, max) => run.or(max),
+        },
+        phases,
+    }
+}
+
+// ─── Similarity ─────────────────────────────────────────────────────────────
+
+/// Weights of the indicator, state, and classification components of
+/// [`similarity`].
+const SIMILARITY_WEIGHTS: [f64; 3] = [0.4, 0.35, 0.25];
+
+/// How alike two attacks are, from 0.0 (nothing in common) to 1.0, to find
+/// duplicates and prior art. The score is a weighted mean of:
+///
+/// - **indicator overlap** (weight 0.4): the Jaccard similarity of the
+///   indicators' surfaces and of the strings in their patterns, expressions,
+///   and semantic intents, per surface;
+/// - **state structure** (0.35): the Jaccard similarity of the field paths
+///   of every phase state, per mode, together with the names and URIs the
+///   states list;
+/// - **classification proximity** (0.25): whether the categories are the
+///   same, averaged with the Jaccard similarity of the framework mappings.
+///
+/// Both documents are normalized first, so shorthand and execution forms do
+/// not matter. A component that neither document has anything for (such as
+/// indicators, between two execution-only documents) is left out of the
+/// mean; documents with nothing to compare score 0.0.
+pub fn similarity(a: &Document, b: &Document) -> f64 {
+    Fingerprint::new(a).similarity(&Fingerprint::new(b))
+}
+
+/// The features of a document that [`similarity`] compares.
+pub(crate) struct Fingerprint {
+    indicators: BTreeSet<String>,
+    state: BTreeSet<String>,
+    category: Option<Category>,
+    mappings: BTreeSet<String>,
+}
+
+impl Fingerprint {
+    pub(crate) fn new(doc: &Document) -> Self {
+        let doc = crate::normalize::normalize(doc.clone());
+        let mut indicators = BTreeSet::new();
+        for indicator in doc.attack.indicators.iter().flatten() {
+            let surface = &indicator.surface;
+            indicators.insert(format!("surface:{}", surface));
+            let parts = [
+                serde_json::to_value(&indicator.pattern),
+                serde_json::to_value(&indicator.expression),
+                serde_json::to_value(&indicator.semantic),
+                serde_json::to_value(&indicator.frequency),
+            ];
+            for part in parts.iter().flatten() {
+                strings_in(part, &mut |s| {
+                    indicators.insert(format!("{}:{}", surface, s.to_lowercase()));
+                });
+            }
+        }
+
+        let mut state = BTreeSet::new();
+        for actor in ResolvedContext::new(&doc).actors() {
+            for (phase, mode) in actor.phases.iter().zip(&actor.phase_modes) {
+                if let Some(value) = &phase.state {
+                    state_features(value, mode.unwrap_or(""), "", &mut state);
+                }
+            }
+        }
+
+        let classification = doc.attack.classification.as_ref();
+        Fingerprint {
+            indicators,
+            state,
+            category: classification.and_then(|c| c.category.clone()),
+            mappings: classification
+                .and_then(|c| c.mappings.as_ref())
+                .into_iter()
+                .flatten()
+                .map(|m| format!("{}:{}", m.framework.to_lowercase(), m.id.to_lowercase()))
+                .collect(),
+        }
+    }
+
+    pub(crate) fn similarity(&self, other: &Fingerprint) -> f64 {
+        let category = match (&self.category, &other.category) {
+            (None, None) => None,
+            (a, b) => Some(if a == b { 1.0 } else { 0.0 }),
+        };
+        let mappings = jaccard(&self.mappings, &other.mappings);
+        let classification = match (category, mappings) {
+            (Some(c), Some(m)) => Some((c + m) / 2.0),
+            (c, m) => c.or(m),
+        };
+        let components = [
+            jaccard(&self.indicators, &other.indicators),
+            jaccard(&self.state, &other.state),
+            classification,
+        ];
+        let (total, weight) = components
+            .iter()
+            .zip(SIMILARITY_WEIGHTS)
+            .filter_map(|(score, weight)| score.map(|s| (s * weight, weight)))
+            .fold((0.0, 0.0), |(t, w), (s, weight)| (t + s, w + weight));
+        if weight == 0.0 { 0.0 } else { total / weight }
+    }
+}
+
+/// `|a ∩ b| / |a ∪ b|`, or `None` when both are empty.
+fn jaccard(a: &BTreeSet<String>, b: &BTreeSet<String>) -> Option<f64> {
+    let union = a.union(b).count();
+    if union == 0 {
+        return None;
+    }
+    Some(a.intersection(b).count() as f64 / union as f64)
+}
+
+fn strings_in(value: &Value, found: &mut impl FnMut(&str)) {
+    match value {
+        Value::String(s) => found(s),
+        Value::Array(items) => items.iter().for_each(|v| strings_in(v, found)),
+        Value::Object(fields) => fields.values().for_each(|v| strings_in(v, found)),
+        _ => {}
+    }
+}
+
+/// Record the field paths of `value` (list items as `[]`) and the `name` and
+/// `uri` values along them, prefixed by `mode`.
+fn state_features(value: &Value, mode: &str, path: &str, features: &mut BTreeSet<String>) {
+    match value {
+        Value::Object(fields) => {
+            for (key, field) in fields {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                features.insert(format!("{}:{}", mode, path));
+                if let ("name" | "uri", Value::String(s)) = (key.as_str(), field) {
+                    features.insert(format!("{}:{}={}", mode, path, s));
+                }
+                state_features(field, mode, &path, features);
+            }
+        }
+        Value::Array(items) => {
+            let path = format!("{}[]", path);
+            for item in items {
+                state_features(item, mode, &path, features);
+            }
+        }
+        _ => {}
+    }
+}
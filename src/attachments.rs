@@ -0,0 +1,263 @@
+//! Inline attachments: named blobs carried in `attack.attachments`.
+//!
+//! Malicious resources — an image with an embedded prompt, a large poisoned
+//! document — are declared once and referenced from execution state by name
+//! instead of being inlined as base64 in state strings:
+//!
+//! ```yaml
+//! attack:
+//!   attachments:
+//!     - name: invoice
+//!       content_type: image/png
+//!       encoding: base64
+//!       data: iVBORw0KGgo…
+//!   execution:
+//!     mode: mcp_server
+//!     state:
+//!       resources:
+//!         - uri: file:///invoice.png
+//!           blob: "{{attachment:invoice}}"
+//! ```
+//!
+//! `data` is standard base64, of the content itself (`base64`) or of its
+//! gzip compression (`gzip`); whitespace in `data` is ignored, so long
+//! values may be wrapped. Decoding is deferred until an attachment is used
+//! and is limited to [`MAX_ATTACHMENT_SIZE`] decoded bytes.
+//!
+//! A reference resolves to the decoded content as text when the content
+//! type is textual (`text/*`, JSON, XML, YAML) and the content is UTF-8, and
+//! to its base64 otherwise — the form MCP `blob` and image `data` fields
+//! expect. Prefix a reference with `\` to keep it literal.
+
+use crate::enums::AttachmentEncoding;
+use crate::error::{AttachmentError, AttachmentErrorKind};
+use crate::types::{Attachment, Document, Phase};
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD;
+use regex::{Captures, Regex};
+use serde_json::Value;
+use std::io::Read;
+use std::sync::{LazyLock, OnceLock};
+
+/// Largest decoded attachment, in bytes.
+pub const MAX_ATTACHMENT_SIZE: usize = 10 * 1024 * 1024;
+
+static REFERENCE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(\\)?\{\{attachment:([^{}]*)\}\}").unwrap());
+
+impl Attachment {
+    /// Decode `data` under the attachment's encoding.
+    ///
+    /// # Errors
+    ///
+    /// Returns `invalid_encoding` if `data` is not valid base64 or not a
+    /// gzip stream, or `too_large` if the content exceeds
+    /// [`MAX_ATTACHMENT_SIZE`].
+    pub fn decode(&self) -> Result<Vec<u8>, AttachmentError> {
+        let data: Vec<u8> = self
+            .data
+            .bytes()
+            .filter(|b| !b.is_ascii_whitespace())
+            .collect();
+        if self.encoding == AttachmentEncoding::Base64 && data.len() / 4 * 3 > MAX_ATTACHMENT_SIZE {
+            return Err(self.too_large());
+        }
+        let bytes = STANDARD
+            .decode(&data)
+            .map_err(|e| self.error(AttachmentErrorKind::InvalidEncoding, e.to_string()))?;
+        let content = match self.encoding {
+            AttachmentEncoding::Base64 => bytes,
+            AttachmentEncoding::Gzip => {
+                let mut content = Vec::new();
+                flate2::read::GzDecoder::new(bytes.as_slice())
+                    .take(MAX_ATTACHMENT_SIZE as u64 + 1)
+                    .read_to_end(&mut content)
+                    .map_err(|e| {
+                        self.error(
+                            AttachmentErrorKind::InvalidEncoding,
+                            format!("invalid gzip data: {}", e),
+                        )
+                    })?;
+                content
+            }
+        };
+        if content.len() > MAX_ATTACHMENT_SIZE {
+            return Err(self.too_large());
+        }
+        Ok(content)
+    }
+
+    /// Whether the content type is textual: `text/*`, or JSON, XML, or YAML
+    /// (including `+json`, `+xml`, and `+yaml` suffixes).
+    pub fn is_text(&self) -> bool {
+        let essence = self
+            .content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        essence.starts_with("text/")
+            || ["json", "xml", "yaml"].iter().any(|kind| {
+                essence == format!("application/{}", kind)
+                    || essence.ends_with(&format!("+{}", kind))
+            })
+    }
+
+    fn error(&self, kind: AttachmentErrorKind, message: String) -> AttachmentError {
+        AttachmentError {
+            kind,
+            name: self.name.clone(),
+            message,
+        }
+    }
+
+    fn too_large(&self) -> AttachmentError {
+        self.error(
+            AttachmentErrorKind::TooLarge,
+            format!("decoded content exceeds {} bytes", MAX_ATTACHMENT_SIZE),
+        )
+    }
+}
+
+/// An attachment's content, decoded on first access.
+type Decoded = OnceLock<Result<Vec<u8>, AttachmentError>>;
+
+/// A document's attachments, each decoded the first time it is used.
+#[derive(Debug, Default)]
+pub struct Attachments<'a> {
+    entries: Vec<(&'a Attachment, Decoded)>,
+}
+
+impl<'a> Attachments<'a> {
+    /// The attachments of `doc`, none decoded yet.
+    pub fn new(doc: &'a Document) -> Self {
+        Attachments {
+            entries: doc
+                .attack
+                .attachments
+                .iter()
+                .flatten()
+                .map(|a| (a, OnceLock::new()))
+                .collect(),
+        }
+    }
+
+    /// The declared attachment called `name`.
+    pub fn attachment(&self, name: &str) -> Option<&'a Attachment> {
+        self.entries
+            .iter()
+            .map(|(a, _)| *a)
+            .find(|a| a.name == name)
+    }
+
+    /// The decoded content of the attachment called `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `unknown_attachment` if no attachment is called `name`, or
+    /// the [`Attachment::decode`] error.
+    pub fn get(&self, name: &str) -> Result<&[u8], AttachmentError> {
+        let (attachment, decoded) = self
+            .entries
+            .iter()
+            .find(|(a, _)| a.name == name)
+            .ok_or_else(|| AttachmentError {
+                kind: AttachmentErrorKind::UnknownAttachment,
+                name: name.to_string(),
+                message: "no attachment with this name".to_string(),
+            })?;
+        decoded
+            .get_or_init(|| attachment.decode())
+            .as_deref()
+            .map_err(Clone::clone)
+    }
+
+    /// The value `{{attachment:name}}` resolves to: the content as text for
+    /// textual UTF-8 content, else its base64.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`get`](Self::get) error.
+    pub fn template_value(&self, name: &str) -> Result<String, AttachmentError> {
+        let content = self.get(name)?;
+        match std::str::from_utf8(content) {
+            Ok(text) if self.attachment(name).is_some_and(Attachment::is_text) => {
+                Ok(text.to_string())
+            }
+            _ => Ok(STANDARD.encode(content)),
+        }
+    }
+
+    /// Replace the attachment references in every string of `value`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`template_value`](Self::template_value) error.
+    pub fn resolve(&self, value: &Value) -> Result<Value, AttachmentError> {
+        Ok(match value {
+            Value::String(s) if s.contains("{{attachment:") => {
+                let mut error = None;
+                let resolved = REFERENCE_RE.replace_all(s, |cap: &Captures<'_>| {
+                    if cap.get(1).is_some() {
+                        return cap[0].to_string();
+                    }
+                    self.template_value(&cap[2]).unwrap_or_else(|e| {
+                        error.get_or_insert(e);
+                        String::new()
+                    })
+                });
+                if let Some(e) = error {
+                    return Err(e);
+                }
+                Value::String(resolved.into_owned())
+            }
+            Value::Array(items) => Value::Array(
+                items
+                    .iter()
+                    .map(|v| self.resolve(v))
+                    .collect::<Result<_, _>>()?,
+            ),
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(k, v)| Ok((k.clone(), self.resolve(v)?)))
+                    .collect::<Result<_, AttachmentError>>()?,
+            ),
+            _ => value.clone(),
+        })
+    }
+}
+
+/// Names of the attachments referenced in `text`, excluding escaped
+/// references.
+pub fn references(text: &str) -> impl Iterator<Item = &str> {
+    REFERENCE_RE
+        .captures_iter(text)
+        .filter(|cap| cap.get(1).is_none())
+        .map(|cap| cap.get(2).map_or("", |m| m.as_str()))
+}
+
+/// `doc` with the attachment references in all execution state resolved,
+/// decoding only the attachments that are referenced. Execution engines call
+/// this before running a document.
+///
+/// # Errors
+///
+/// Returns the first reference that cannot be resolved.
+pub fn resolve_document(doc: &Document) -> Result<Document, AttachmentError> {
+    let attachments = Attachments::new(doc);
+    let mut resolved = doc.clone();
+    let exec = &mut resolved.attack.execution;
+    if let Some(state) = &mut exec.state {
+        *state = attachments.resolve(state)?;
+    }
+    let actor_phases = exec.actors.iter_mut().flatten().map(|a| &mut a.phases);
+    for phases in exec.phases.iter_mut().chain(actor_phases) {
+        for Phase { state, .. } in phases {
+            if let Some(state) = state {
+                *state = attachments.resolve(state)?;
+            }
+        }
+    }
+    Ok(resolved)
+}
@@ -0,0 +1,481 @@
+//! An audit trail of document lifecycle operations.
+//!
+//! An [`AuditLog`] turns each [`AuditEvent`] it is given into an
+//! [`AuditEntry`] — who did what to which document, when, and with what
+//! outcome — and appends it to a pluggable [`AuditSink`]. The identity is
+//! supplied by the caller (a user name, service account, or key ID); the SDK
+//! records it but does not authenticate it.
+//!
+//! [`AuditEvent`] has constructors for the operations the SDK performs
+//! ([`load`](AuditEvent::load), [`normalize`](AuditEvent::normalize),
+//! [`catalog_insert`](AuditEvent::catalog_insert)) and for signing and
+//! publishing, which happen outside it ([`sign`](AuditEvent::sign),
+//! [`publish`](AuditEvent::publish)):
+//!
+//! ```rust
+//! # #[cfg(all(feature = "validate", feature = "normalize"))] {
+//! use oatf::audit::{AuditEvent, AuditLog, verify_chain};
+//!
+//! let input = "oatf: \"0.1\"\nattack:\n  execution:\n    mode: mcp_server\n    state:\n      tools: []\n";
+//! let mut log = AuditLog::new(Vec::new(), "alice@example.com").chained();
+//!
+//! let result = oatf::load(input);
+//! log.record(AuditEvent::load("attacks/a.yaml", input, &result)).unwrap();
+//! log.record(AuditEvent::publish("registry.example.com/feeds/mcp:1.0", "sha256:0f1e")).unwrap();
+//!
+//! let entries = log.into_sink();
+//! assert_eq!(entries[0].identity, "alice@example.com");
+//! assert!(verify_chain(&entries).is_ok());
+//! # }
+//! ```
+//!
+//! With [`AuditLog::chained`], each entry carries the SHA-256 hash of its
+//! contents and of the previous entry, so [`verify_chain`] detects edited,
+//! removed, inserted, or reordered entries. Requires the `audit` feature.
+
+use crate::digest::sha256_digest;
+use crate::error::{AuditError, AuditErrorKind};
+use crate::serialize::serialize_canonical;
+use crate::types::Document;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{BufRead, Write};
+use std::time::SystemTime;
+
+#[cfg(all(feature = "validate", feature = "normalize"))]
+use crate::{LoadResult, OATFError, catalog::CatalogEntry};
+
+/// A lifecycle operation on a document.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOperation {
+    /// Parsing, validating, and normalizing input.
+    Load,
+    /// Normalizing a parsed document.
+    Normalize,
+    /// Signing a document or bundle.
+    Sign,
+    /// Publishing a document or bundle to a registry or feed.
+    Publish,
+    /// Adding a document to a catalog.
+    CatalogInsert,
+}
+
+/// Whether an audited operation succeeded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOutcome {
+    /// The operation completed.
+    Succeeded,
+    /// The operation was attempted and failed.
+    Failed,
+}
+
+/// An operation to record, before the log assigns it a sequence number,
+/// time, and identity.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuditEvent {
+    /// What was done.
+    pub operation: AuditOperation,
+    /// What it was done to: a source path, catalog source, or artifact
+    /// reference.
+    pub subject: String,
+    /// Whether it succeeded.
+    pub outcome: AuditOutcome,
+    /// `sha256:<hex>` digest of the content operated on, if known.
+    pub digest: Option<String>,
+    /// Operation-specific details, such as the attack ID.
+    pub details: BTreeMap<String, String>,
+}
+
+impl AuditEvent {
+    /// A successful `operation` on `subject`.
+    pub fn new(operation: AuditOperation, subject: impl Into<String>) -> Self {
+        AuditEvent {
+            operation,
+            subject: subject.into(),
+            outcome: AuditOutcome::Succeeded,
+            digest: None,
+            details: BTreeMap::new(),
+        }
+    }
+
+    /// Mark the operation as failed.
+    pub fn failed(mut self) -> Self {
+        self.outcome = AuditOutcome::Failed;
+        self
+    }
+
+    /// Record the digest of `content`.
+    pub fn digest_of(mut self, content: &[u8]) -> Self {
+        self.digest = Some(sha256_digest(content));
+        self
+    }
+
+    /// Add a detail.
+    pub fn detail(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.details.insert(key.into(), value.into());
+        self
+    }
+
+    /// A [`crate::load`] of `input` from `source` with the given result: the
+    /// digest of the input, and the attack ID on success or the number of
+    /// errors on failure.
+    #[cfg(all(feature = "validate", feature = "normalize"))]
+    pub fn load(
+        source: impl Into<String>,
+        input: &str,
+        result: &Result<LoadResult, Vec<OATFError>>,
+    ) -> Self {
+        let event = AuditEvent::new(AuditOperation::Load, source).digest_of(input.as_bytes());
+        match result {
+            Ok(loaded) => event.with_attack_id(&loaded.document),
+            Err(errors) => event.failed().detail("errors", errors.len().to_string()),
+        }
+    }
+
+    /// A normalization producing `document`: the digest of its canonical
+    /// serialization and its attack ID.
+    pub fn normalize(source: impl Into<String>, document: &Document) -> Self {
+        AuditEvent::new(AuditOperation::Normalize, source).with_document(document)
+    }
+
+    /// A signature over `subject`: the digest of the signature bytes.
+    pub fn sign(subject: impl Into<String>, signature: &[u8]) -> Self {
+        AuditEvent::new(AuditOperation::Sign, subject).digest_of(signature)
+    }
+
+    /// A publication of `reference` whose pushed content has `digest`, such
+    /// as the manifest digest returned by `distribution::push`.
+    pub fn publish(reference: impl Into<String>, digest: impl Into<String>) -> Self {
+        AuditEvent {
+            digest: Some(digest.into()),
+            ..AuditEvent::new(AuditOperation::Publish, reference)
+        }
+    }
+
+    /// The addition of `entry` to a catalog: its source, the digest of the
+    /// document's canonical serialization, and its attack ID.
+    #[cfg(all(feature = "validate", feature = "normalize"))]
+    pub fn catalog_insert(entry: &CatalogEntry) -> Self {
+        AuditEvent::new(AuditOperation::CatalogInsert, entry.source.clone())
+            .with_document(&entry.document)
+    }
+
+    fn with_document(mut self, document: &Document) -> Self {
+        if let Ok(yaml) = serialize_canonical(document) {
+            self.digest = Some(sha256_digest(yaml.as_bytes()));
+        }
+        self.with_attack_id(document)
+    }
+
+    fn with_attack_id(self, document: &Document) -> Self {
+        match &document.attack.id {
+            Some(id) => self.detail("attack_id", id.clone()),
+            None => self,
+        }
+    }
+}
+
+/// One recorded operation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Position in the log, from 0.
+    pub sequence: u64,
+    /// Milliseconds since the Unix epoch.
+    pub recorded_at: u64,
+    /// Caller-supplied identity of whoever performed the operation.
+    pub identity: String,
+    /// What was done.
+    pub operation: AuditOperation,
+    /// What it was done to.
+    pub subject: String,
+    /// Whether it succeeded.
+    pub outcome: AuditOutcome,
+    /// `sha256:<hex>` digest of the content operated on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest: Option<String>,
+    /// Operation-specific details.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub details: BTreeMap<String, String>,
+    /// Hash of the previous entry in a chained log.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_hash: Option<String>,
+    /// Hash of this entry in a chained log (see [`AuditEntry::compute_hash`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+}
+
+impl AuditEntry {
+    /// `sha256:<hex>` digest of the entry's JSON encoding without `hash`.
+    /// Covers `previous_hash`, which links the entry to its predecessor.
+    pub fn compute_hash(&self) -> String {
+        let unhashed = AuditEntry {
+            hash: None,
+            ..self.clone()
+        };
+        sha256_digest(&serde_json::to_vec(&unhashed).expect("audit entries encode as JSON"))
+    }
+}
+
+// ─── Sinks ──────────────────────────────────────────────────────────────────
+
+/// A destination for audit entries.
+pub trait AuditSink {
+    /// Store one entry.
+    fn append(&mut self, entry: &AuditEntry) -> Result<(), AuditError>;
+
+    /// Persist anything the sink has buffered. The default does nothing.
+    fn flush(&mut self) -> Result<(), AuditError> {
+        Ok(())
+    }
+}
+
+impl<S: AuditSink + ?Sized> AuditSink for Box<S> {
+    fn append(&mut self, entry: &AuditEntry) -> Result<(), AuditError> {
+        (**self).append(entry)
+    }
+
+    fn flush(&mut self) -> Result<(), AuditError> {
+        (**self).flush()
+    }
+}
+
+/// Keeps entries in memory.
+impl AuditSink for Vec<AuditEntry> {
+    fn append(&mut self, entry: &AuditEntry) -> Result<(), AuditError> {
+        self.push(entry.clone());
+        Ok(())
+    }
+}
+
+/// Writes each entry as a line of JSON, for append-only log files. Read the
+/// lines back with [`read_json_lines`].
+pub struct JsonLines<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> JsonLines<W> {
+    /// A sink writing JSON lines to `writer`.
+    pub fn new(writer: W) -> Self {
+        JsonLines { writer }
+    }
+
+    /// Consume the sink and return its writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> AuditSink for JsonLines<W> {
+    fn append(&mut self, entry: &AuditEntry) -> Result<(), AuditError> {
+        let mut line = serde_json::to_vec(entry).map_err(|e| AuditError {
+            kind: AuditErrorKind::Malformed,
+            sequence: Some(entry.sequence),
+            message: format!("failed to encode entry: {}", e),
+        })?;
+        line.push(b'\n');
+        self.writer
+            .write_all(&line)
+            .map_err(|e| sink_error(Some(entry.sequence), e))
+    }
+
+    fn flush(&mut self) -> Result<(), AuditError> {
+        self.writer.flush().map_err(|e| sink_error(None, e))
+    }
+}
+
+fn sink_error(sequence: Option<u64>, e: std::io::Error) -> AuditError {
+    AuditError {
+        kind: AuditErrorKind::Sink,
+        sequence,
+        message: format!("failed to write audit log: {}", e),
+    }
+}
+
+/// Read entries written by [`JsonLines`], skipping blank lines.
+///
+/// # Errors
+///
+/// Returns `sink` if reading fails and `malformed` for a line that is not an
+/// entry.
+pub fn read_json_lines(reader: impl BufRead) -> Result<Vec<AuditEntry>, AuditError> {
+    let mut entries = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| AuditError {
+            kind: AuditErrorKind::Sink,
+            sequence: None,
+            message: format!("failed to read audit log: {}", e),
+        })?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        entries.push(serde_json::from_str(&line).map_err(|e| AuditError {
+            kind: AuditErrorKind::Malformed,
+            sequence: None,
+            message: format!("line {}: {}", i + 1, e),
+        })?);
+    }
+    Ok(entries)
+}
+
+// ─── Log ────────────────────────────────────────────────────────────────────
+
+/// Records [`AuditEvent`]s under a caller-supplied identity.
+pub struct AuditLog<S: AuditSink> {
+    sink: S,
+    identity: String,
+    chained: bool,
+    next_sequence: u64,
+    last_hash: Option<String>,
+    now: Box<dyn Fn() -> SystemTime + Send + Sync>,
+}
+
+impl<S: AuditSink> AuditLog<S> {
+    /// A log appending to `sink` as `identity`, starting at sequence 0.
+    pub fn new(sink: S, identity: impl Into<String>) -> Self {
+        AuditLog {
+            sink,
+            identity: identity.into(),
+            chained: false,
+            next_sequence: 0,
+            last_hash: None,
+            now: Box::new(SystemTime::now),
+        }
+    }
+
+    /// Hash-chain the entries, making the log tamper-evident.
+    pub fn chained(mut self) -> Self {
+        self.chained = true;
+        self
+    }
+
+    /// Continue an existing log after its `last` entry, keeping the
+    /// sequence and, when `last` is hashed, the chain.
+    pub fn resume(mut self, last: &AuditEntry) -> Self {
+        self.next_sequence = last.sequence + 1;
+        if last.hash.is_some() {
+            self.chained = true;
+            self.last_hash = last.hash.clone();
+        }
+        self
+    }
+
+    /// Replace the wall-clock source of `recorded_at` (defaults to
+    /// [`SystemTime::now`]).
+    pub fn with_time(mut self, now: impl Fn() -> SystemTime + Send + Sync + 'static) -> Self {
+        self.now = Box::new(now);
+        self
+    }
+
+    /// The identity recorded on new entries.
+    pub fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    /// Record later entries under `identity`.
+    pub fn set_identity(&mut self, identity: impl Into<String>) {
+        self.identity = identity.into();
+    }
+
+    /// Append `event` to the sink and return the recorded entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns the sink's error; the entry's sequence number is then reused
+    /// by the next entry, so the chain stays unbroken.
+    pub fn record(&mut self, event: AuditEvent) -> Result<AuditEntry, AuditError> {
+        let recorded_at = (self.now)()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis() as u64);
+        let mut entry = AuditEntry {
+            sequence: self.next_sequence,
+            recorded_at,
+            identity: self.identity.clone(),
+            operation: event.operation,
+            subject: event.subject,
+            outcome: event.outcome,
+            digest: event.digest,
+            details: event.details,
+            previous_hash: None,
+            hash: None,
+        };
+        if self.chained {
+            entry.previous_hash = self.last_hash.clone();
+            entry.hash = Some(entry.compute_hash());
+        }
+        self.sink.append(&entry)?;
+        self.next_sequence += 1;
+        if self.chained {
+            self.last_hash = entry.hash.clone();
+        }
+        Ok(entry)
+    }
+
+    /// Flush the sink.
+    ///
+    /// # Errors
+    ///
+    /// Returns the sink's error.
+    pub fn flush(&mut self) -> Result<(), AuditError> {
+        self.sink.flush()
+    }
+
+    /// The sink.
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// Consume the log and return its sink.
+    pub fn into_sink(self) -> S {
+        self.sink
+    }
+}
+
+/// Check that `entries` form an unbroken hash chain: every entry is hashed,
+/// its hash matches its contents, and it follows the previous entry in
+/// sequence and by `previous_hash`. The first entry is taken as given, so
+/// a log verified from its start should begin at sequence 0.
+///
+/// Returns the hash of the last entry (`None` for an empty slice). Keeping
+/// it outside the log, for example in a signed release note, also detects
+/// entries removed from the end.
+///
+/// # Errors
+///
+/// Returns `chain_broken` naming the first entry that does not verify.
+pub fn verify_chain(entries: &[AuditEntry]) -> Result<Option<String>, AuditError> {
+    let broken = |entry: &AuditEntry, message: String| AuditError {
+        kind: AuditErrorKind::ChainBroken,
+        sequence: Some(entry.sequence),
+        message,
+    };
+    let mut previous: Option<&AuditEntry> = None;
+    for entry in entries {
+        let Some(hash) = &entry.hash else {
+            return Err(broken(entry, "entry is not hashed".to_string()));
+        };
+        if *hash != entry.compute_hash() {
+            return Err(broken(
+                entry,
+                "hash does not match the entry's contents".to_string(),
+            ));
+        }
+        if let Some(previous) = previous {
+            if entry.sequence != previous.sequence + 1 {
+                return Err(broken(
+                    entry,
+                    format!("expected sequence {}", previous.sequence + 1),
+                ));
+            }
+            if entry.previous_hash != previous.hash {
+                return Err(broken(
+                    entry,
+                    format!("previous_hash does not match entry {}", previous.sequence),
+                ));
+            }
+        }
+        previous = Some(entry);
+    }
+    Ok(previous.and_then(|e| e.hash.clone()))
+}
@@ -0,0 +1,622 @@
+//! Authoring support for editors and form-based UIs.
+//!
+//! [`completion_model`] describes the document structure — objects, fields,
+//! value types, allowed enum values, and the cross-field requirements that
+//! validation enforces — as serializable data, so a YAML language server or
+//! a form builder can offer completions without re-encoding the format.
+//!
+//! The model is tied to the Rust types rather than maintained beside them:
+//! closed enum values are read from the enums' `Deserialize` impls, open
+//! values (modes, events, surfaces) from the registries, requirement
+//! descriptions from [`crate::spec::RULES`], and every object's field list is
+//! checked against its type by an exhaustive pattern, so adding a field to a
+//! document type does not compile until the model lists it.
+//!
+//! [`examples_for`] synthesizes a matching and a non-matching message for a
+//! pattern indicator, checked by evaluating the indicator against them, for
+//! documentation and hovers (feature `evaluate`).
+//!
+//! [`scaffold`] returns a starter document for a common attack archetype
+//! ([`ScaffoldKind`]) that already passes validation.
+
+use crate::enums::*;
+use crate::event_registry::EVENT_MODE_REGISTRY;
+use crate::spec::{SPEC_VERSION, rule_info};
+use crate::surface::{KNOWN_MODES, KNOWN_PROTOCOLS, SURFACE_REGISTRY};
+use crate::types::*;
+use serde::de::{self, DeserializeOwned, Deserializer, Visitor};
+use serde::{Serialize, forward_to_deserialize_any};
+
+#[cfg(feature = "evaluate")]
+mod examples;
+mod scaffold;
+#[cfg(feature = "evaluate")]
+pub use examples::{IndicatorExamples, examples_for};
+pub use scaffold::{ScaffoldKind, scaffold};
+
+/// Machine-readable model of the OATF document structure.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct CompletionModel {
+    /// OATF format version the model describes.
+    pub spec_version: &'static str,
+    /// Name of the object at the document root.
+    pub root: &'static str,
+    /// Object types, referenced by name from [`FieldType::Object`].
+    pub objects: Vec<ObjectModel>,
+    /// Cross-field requirements enforced by validation.
+    pub requirements: Vec<Requirement>,
+}
+
+impl CompletionModel {
+    /// Look up an object type by name.
+    pub fn object(&self, name: &str) -> Option<&ObjectModel> {
+        self.objects.iter().find(|o| o.name == name)
+    }
+}
+
+/// An object type and its fields.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ObjectModel {
+    /// Object type name (e.g., `"Phase"`).
+    pub name: &'static str,
+    /// What the object describes.
+    pub description: &'static str,
+    /// Whether `x-*` extension fields are accepted.
+    pub extensions: bool,
+    /// Fields in document order.
+    pub fields: Vec<FieldModel>,
+}
+
+impl ObjectModel {
+    /// Look up a field by its YAML key.
+    pub fn field(&self, name: &str) -> Option<&FieldModel> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+}
+
+/// A field of an [`ObjectModel`].
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct FieldModel {
+    /// YAML key.
+    pub name: &'static str,
+    /// Value type.
+    #[serde(rename = "type")]
+    pub ty: FieldType,
+    /// Whether the field must always be present. Fields that are required
+    /// only in some situations are optional here and covered by a
+    /// [`Requirement`].
+    pub required: bool,
+    /// What the field holds.
+    pub description: &'static str,
+}
+
+/// The value type of a field.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FieldType {
+    /// Any string.
+    String,
+    /// An integer.
+    Integer,
+    /// Any number.
+    Number,
+    /// `true` or `false`.
+    Boolean,
+    /// A duration string (e.g., `"30s"`, `"PT5M"`).
+    Duration,
+    /// Any YAML value (e.g., protocol-specific state).
+    Any,
+    /// One of a closed set of strings.
+    Enum {
+        /// Allowed values.
+        values: Vec<&'static str>,
+    },
+    /// A string with well-known values; other values are permitted.
+    OpenEnum {
+        /// Suggested values.
+        values: Vec<&'static str>,
+    },
+    /// An object of the named type.
+    Object {
+        /// Name of an [`ObjectModel`].
+        name: &'static str,
+    },
+    /// A sequence of values.
+    List {
+        /// Item type.
+        items: Box<FieldType>,
+    },
+    /// A mapping from strings to values.
+    Map {
+        /// Value type.
+        values: Box<FieldType>,
+    },
+    /// Any of several forms (e.g., a scalar shorthand or an object).
+    OneOf {
+        /// Accepted forms.
+        options: Vec<FieldType>,
+    },
+}
+
+/// A cross-field requirement on an object.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Requirement {
+    /// Rule that enforces the requirement (e.g., `"V-030"`).
+    pub rule: &'static str,
+    /// Specification section of the rule.
+    pub spec_ref: &'static str,
+    /// Object the requirement applies to.
+    pub object: &'static str,
+    /// Shape of the requirement.
+    pub kind: RequirementKind,
+    /// Fields involved.
+    pub fields: Vec<&'static str>,
+    /// Condition under which the requirement applies, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub when: Option<&'static str>,
+    /// The rule's summary.
+    pub description: &'static str,
+}
+
+/// Shape of a [`Requirement`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RequirementKind {
+    /// Exactly one of the fields must be present.
+    ExactlyOne,
+    /// At least one of the fields must be present.
+    AtLeastOne,
+    /// All of the fields must be present when the condition holds.
+    Required,
+}
+
+/// Return the completion model for the document structure accepted by this
+/// build.
+pub fn completion_model() -> CompletionModel {
+    CompletionModel {
+        spec_version: SPEC_VERSION,
+        root: "Document",
+        objects: objects(),
+        requirements: requirements(),
+    }
+}
+
+// ─── Objects ────────────────────────────────────────────────────────────────
+
+/// Build an [`ObjectModel`] for `$pat` and check at compile time that the
+/// listed fields, plus the `skip`ped ones, are exactly the fields of `$pat`.
+macro_rules! object {
+    (
+        $owner:ty, $($pat:ident)::+, $name:literal, $desc:literal, extensions: $ext:literal,
+        { $($field:ident $(as $yaml:literal)?: $req:ident $ty:expr, $fdesc:literal;)* }
+        $(skip [$($skip:ident),*])?
+    ) => {{
+        #[allow(unreachable_patterns, clippy::match_single_binding)]
+        fn _exhaustive(value: &$owner) {
+            match value {
+                $($pat)::+ { $($field: _,)* $($($skip: _,)*)? } => {}
+                _ => {}
+            }
+        }
+        ObjectModel {
+            name: $name,
+            description: $desc,
+            extensions: $ext,
+            fields: vec![$(FieldModel {
+                name: *[stringify!($field) $(, $yaml)?].last().unwrap(),
+                ty: $ty,
+                required: stringify!($req) == "required",
+                description: $fdesc,
+            },)*],
+        }
+    }};
+}
+
+fn objects() -> Vec<ObjectModel> {
+    use FieldType::*;
+    let modes = || OpenEnum {
+        values: KNOWN_MODES.to_vec(),
+    };
+    let condition = || OneOf {
+        options: vec![Any, object("MatchCondition")],
+    };
+    vec![
+        object!(Document, Document, "Document", "An OATF document.", extensions: false, {
+            oatf: required Enum { values: vec![SPEC_VERSION] }, "OATF format version.";
+            schema as "$schema": optional String, "JSON Schema URI for editor validation.";
+            attack: required object("Attack"), "The attack description.";
+        } skip [oatf_is_first_key]),
+        object!(Attack, Attack, "Attack", "Attack metadata, execution, and indicators.", extensions: true, {
+            id: optional String, "Unique attack identifier (e.g., `OATF-001`).";
+            name: optional String, "Human-readable attack name.";
+            version: optional Integer, "Positive document version number.";
+            status: optional enumeration::<Status>(), "Document lifecycle status.";
+            created: optional String, "ISO 8601 creation date.";
+            modified: optional String, "ISO 8601 last-modified date.";
+            author: optional String, "Author name or identifier.";
+            description: optional String, "Human-readable attack description.";
+            grace_period: optional Duration, "Responsible disclosure grace period.";
+            license: optional String, "SPDX license expression for the content.";
+            distribution: optional enumeration::<Tlp>(), "TLP marking limiting who may receive the document.";
+            severity: optional OneOf {
+                options: vec![enumeration::<SeverityLevel>(), object("Severity")],
+            }, "Severity level, or an object with level and confidence.";
+            impact: optional list(enumeration::<Impact>()), "Categories of harm.";
+            classification: optional object("Classification"), "Taxonomy classification.";
+            references: optional list(object("Reference")), "External references.";
+            execution: required object("Execution"), "Execution plan.";
+            attachments: optional list(object("Attachment")), "Named blobs referenced from state as `{{attachment:name}}`.";
+            indicators: optional list(object("Indicator")), "Detection indicators.";
+            correlation: optional object("Correlation"), "How indicator verdicts combine.";
+            safety: optional object("Safety"), "Limits an execution engine must enforce.";
+            supersedes: optional list(String), "IDs of the attacks this attack replaces.";
+            superseded_by: optional String, "ID of the attack that replaces this one.";
+        } skip [extensions]),
+        object!(Attachment, Attachment, "Attachment", "A named inline blob.", extensions: false, {
+            name: required String, "Attachment name, unique within the document.";
+            content_type: required String, "MIME type of the decoded content.";
+            encoding: required enumeration::<AttachmentEncoding>(), "`base64`, or `gzip` for base64 of gzip-compressed content.";
+            data: required String, "The encoded content.";
+        }),
+        object!(Severity, Severity::Object, "Severity", "Object form of a severity.", extensions: false, {
+            level: required enumeration::<SeverityLevel>(), "Severity level.";
+            confidence: optional Integer, "Confidence percentage (0–100).";
+        }),
+        object!(Classification, Classification, "Classification", "OATF taxonomy classification.", extensions: false, {
+            category: optional enumeration::<Category>(), "Taxonomy category.";
+            mappings: optional list(object("FrameworkMapping")), "Mappings to external frameworks.";
+            tags: optional list(String), "Free-form tags.";
+        }),
+        object!(FrameworkMapping, FrameworkMapping, "FrameworkMapping", "A mapping to an external framework entry.", extensions: false, {
+            framework: required String, "Framework name.";
+            id: required String, "Entry identifier within the framework.";
+            name: optional String, "Entry name.";
+            url: optional String, "Entry URL.";
+            relationship: optional enumeration::<Relationship>(), "Kind of mapping.";
+        }),
+        object!(Reference, Reference, "Reference", "An external reference.", extensions: false, {
+            url: required String, "Reference URL.";
+            title: optional String, "Reference title.";
+            description: optional String, "Reference description.";
+        }),
+        object!(Correlation, Correlation, "Correlation", "Verdict correlation configuration.", extensions: false, {
+            logic: optional enumeration::<CorrelationLogic>(), "Correlation logic; defaults to `any`.";
+            stages: optional list(String), "Stages that must each have a matched indicator.";
+            key: optional list(String), "Capture names matched indicators must agree on.";
+        }),
+        object!(Safety, Safety, "Safety", "Guardrails enforced by execution engines.", extensions: false, {
+            max_runtime: optional Duration, "Maximum wall-clock duration of a run.";
+            max_messages: optional Integer, "Maximum number of protocol messages sent.";
+            forbidden_hosts: optional list(String), "Hosts that must never be contacted; `*.` matches subdomains.";
+        }),
+        object!(Execution, Execution, "Execution", "Execution plan in single-phase, multi-phase, or multi-actor form.", extensions: true, {
+            mode: optional modes(), "Default mode, optionally pinned as `mode@version`.";
+            state: optional Any, "Protocol state of the single-phase form.";
+            phases: optional list(object("Phase")), "Phases of the multi-phase form.";
+            actors: optional list(object("Actor")), "Actors of the multi-actor form.";
+        } skip [extensions]),
+        object!(Actor, Actor, "Actor", "A named participant with its own phases.", extensions: true, {
+            name: required String, "Actor name in snake_case.";
+            description: optional String, "Human-readable description of the actor's role.";
+            mode: required modes(), "Actor mode, optionally pinned as `mode@version`.";
+            trust: optional enumeration::<ActorTrust>(), "Whether the target trusts the actor; `untrusted` when absent.";
+            endpoint: optional object("Endpoint"), "Where a harness binds the actor's transport.";
+            phases: required list(object("Phase")), "The actor's phases.";
+        } skip [extensions]),
+        object!(Endpoint, Endpoint, "Endpoint", "Transport binding hint for an actor.", extensions: false, {
+            url: optional String, "`http`, `https`, `ws`, or `wss` URL.";
+            command: optional list(String), "Program and arguments of a process spoken to over stdio.";
+        }),
+        object!(Phase, Phase, "Phase", "A stage of execution.", extensions: true, {
+            name: optional String, "Phase name, unique within its actor.";
+            description: optional String, "Human-readable phase description.";
+            mode: optional modes(), "Mode overriding the execution or actor mode.";
+            requires: optional map(condition()), "Conditions on extracted values, keyed by extractor name, for entering the phase.";
+            state: optional Any, "Protocol state served or sent during the phase.";
+            extractors: optional list(object("Extractor")), "Values captured from messages.";
+            on_enter: optional list(object("Action")), "Actions run when the phase begins.";
+            pacing: optional object("Pacing"), "Delay between messages sent during the phase.";
+            trigger: optional object("Trigger"), "Condition that advances to the next phase.";
+            on_timeout: optional list(object("Action")), "Actions run when the trigger advances by timeout.";
+        } skip [extensions]),
+        object!(Pacing, Pacing, "Pacing", "Timing of messages sent during a phase.", extensions: false, {
+            delay: optional Duration, "Delay before each message.";
+            jitter: optional Duration, "Random extra delay added to each message.";
+        }),
+        object!(Trigger, Trigger, "Trigger", "Condition for advancing to the next phase.", extensions: false, {
+            event: optional OpenEnum {
+                values: EVENT_MODE_REGISTRY.iter().map(|e| e.event).collect(),
+            }, "Protocol event to wait for, optionally with a `:qualifier`.";
+            count: optional Integer, "Number of matching events required.";
+            match_predicate as "match": optional map(condition()), "Conditions on event fields, keyed by dot path.";
+            origin: optional map(condition()), "Conditions on the event's origin: `role`, `endpoint`, `trusted`.";
+            after: optional Duration, "Advance unconditionally after this duration.";
+        }),
+        object!(Extractor, Extractor, "Extractor", "A value captured from a message.", extensions: false, {
+            name: required String, "Extractor name in snake_case.";
+            source: required enumeration::<ExtractorSource>(), "Message to extract from.";
+            extractor_type as "type": required enumeration::<ExtractorType>(), "Selector language.";
+            selector: required String, "JSONPath expression or regular expression.";
+        }),
+        action_object(),
+        object!(Action, Action::SendNotification, "SendNotification", "A protocol notification.", extensions: false, {
+            method: required String, "Notification method name.";
+            params: optional Any, "Notification parameters.";
+        } skip [extensions, non_ext_key_count]),
+        object!(Action, Action::Log, "Log", "A log message.", extensions: false, {
+            message: required String, "Log message text.";
+            level: optional enumeration::<LogLevel>(), "Log level; defaults to `info`.";
+        } skip [extensions, non_ext_key_count]),
+        object!(Action, Action::SendElicitation, "SendElicitation", "A user elicitation request.", extensions: false, {
+            message: required String, "Elicitation message text.";
+            mode: optional enumeration::<ElicitationMode>(), "Elicitation mode.";
+            requested_schema as "requestedSchema": optional Any, "JSON Schema for form-mode elicitation.";
+            url: optional String, "URL for url-mode elicitation.";
+        } skip [extensions, non_ext_key_count]),
+        object!(MatchCondition, MatchCondition, "MatchCondition", "Operator-based condition.", extensions: false, {
+            contains: optional String, "Substring the value must contain.";
+            starts_with: optional String, "Prefix the value must start with.";
+            ends_with: optional String, "Suffix the value must end with.";
+            regex: optional String, "Regular expression the value must match.";
+            any_of: optional list(Any), "Values the value must be one of.";
+            gt: optional Number, "Exclusive lower bound.";
+            lt: optional Number, "Exclusive upper bound.";
+            gte: optional Number, "Inclusive lower bound.";
+            lte: optional Number, "Inclusive upper bound.";
+            exists: optional Boolean, "Whether the value must be present.";
+            json_patch: optional object("PatchMatch"), "JSON Patch operation the value (a patch document) must contain.";
+        }),
+        object!(PatchMatch, PatchMatch, "PatchMatch", "A JSON Patch operation match.", extensions: false, {
+            op: optional enumeration::<PatchOp>(), "The operation.";
+            path: optional String, "JSON Pointer the operation targets; `*` matches one segment and a final `**` any remainder.";
+            value: optional condition(), "Value to compare with the operation's value, or an operator object.";
+        }),
+        object!(Indicator, Indicator, "Indicator", "A detection indicator.", extensions: true, {
+            id: optional String, "Indicator ID, prefixed by the attack ID.";
+            protocol: optional OpenEnum { values: KNOWN_PROTOCOLS.to_vec() }, "Protocol of the observed traffic.";
+            surface: required OpenEnum {
+                values: SURFACE_REGISTRY.iter().map(|s| s.surface).collect(),
+            }, "Protocol surface the indicator inspects.";
+            description: optional String, "Human-readable indicator description.";
+            pattern: optional object("PatternMatch"), "Pattern-based detection.";
+            expression: optional object("ExpressionMatch"), "CEL-based detection.";
+            semantic: optional object("SemanticMatch"), "Intent-based detection.";
+            frequency: optional object("FrequencyMatch"), "Count-based detection over a window of events.";
+            origin: optional map(condition()), "Conditions on the evaluated event's origin: `role`, `endpoint`, `trusted`.";
+            capture: optional list(object("Capture")), "Values to capture from matching messages for correlation.";
+            confidence: optional Integer, "Confidence percentage (0–100).";
+            severity: optional enumeration::<SeverityLevel>(), "Indicator severity.";
+            stage: optional String, "Kill-chain stage the indicator detects.";
+            false_positives: optional list(OneOf {
+                options: vec![String, object("FalsePositive")],
+            }), "Known benign situations that match, as descriptions or objects.";
+        } skip [extensions]),
+        object!(FalsePositive, FalsePositive, "FalsePositive", "A known benign case; matches satisfying `when` are reported as likely benign.", extensions: false, {
+            description: required String, "Description of the benign case.";
+            when: optional map(condition()), "Conditions on message fields, keyed by dot path.";
+        }),
+        object!(Capture, Capture, "Capture", "A value captured from a matching message.", extensions: false, {
+            name: required String, "Capture name in snake_case.";
+            capture_type as "type": required enumeration::<ExtractorType>(), "Selector language.";
+            selector: required String, "JSONPath expression or regular expression, applied to the surface's payload.";
+        }),
+        object!(PatternMatch, PatternMatch, "PatternMatch", "Pattern detection in standard (`target`/`condition`) or shorthand (operator keys) form.", extensions: false, {
+            target: optional String, "Path of the value to match; defaults to the surface's target.";
+            condition: optional condition(), "Value to compare, or an operator object.";
+            contains: optional String, "Shorthand: substring the value must contain.";
+            starts_with: optional String, "Shorthand: prefix the value must start with.";
+            ends_with: optional String, "Shorthand: suffix the value must end with.";
+            regex: optional String, "Shorthand: regular expression the value must match.";
+            any_of: optional list(Any), "Shorthand: values the value must be one of.";
+            gt: optional Number, "Shorthand: exclusive lower bound.";
+            lt: optional Number, "Shorthand: exclusive upper bound.";
+            gte: optional Number, "Shorthand: inclusive lower bound.";
+            lte: optional Number, "Shorthand: inclusive upper bound.";
+        }),
+        object!(ExpressionMatch, ExpressionMatch, "ExpressionMatch", "CEL expression detection.", extensions: false, {
+            cel: required String, "CEL expression evaluating to a boolean.";
+            variables: optional map(String), "Variable bindings from name to path.";
+        }),
+        object!(SemanticMatch, SemanticMatch, "SemanticMatch", "Intent-based detection.", extensions: false, {
+            target: optional String, "Path of the text to evaluate.";
+            intent: required String, "Natural-language description of the malicious intent.";
+            intent_class: optional enumeration::<SemanticIntentClass>(), "Intent classification hint.";
+            threshold: optional Number, "Match threshold (0.0–1.0).";
+            examples: optional object("SemanticExamples"), "Calibration examples.";
+        }),
+        object!(FrequencyMatch, FrequencyMatch, "FrequencyMatch", "Count-based detection: matches when enough events occur within a window.", extensions: false, {
+            event: required OpenEnum {
+                values: EVENT_MODE_REGISTRY.iter().map(|e| e.event).collect(),
+            }, "Protocol event to count, optionally with a `:qualifier`.";
+            match_predicate as "match": optional map(condition()), "Conditions on event fields, keyed by dot path.";
+            window: required Duration, "Sliding window the events must fall within.";
+            threshold: required Integer, "Number of events within the window at which the indicator matches.";
+            group_by: optional String, "Dot path of an event field to count by.";
+        }),
+        object!(SemanticExamples, SemanticExamples, "SemanticExamples", "Examples for semantic calibration.", extensions: false, {
+            positive: optional list(String), "Texts that should match.";
+            negative: optional list(String), "Texts that should not match.";
+        }),
+    ]
+}
+
+/// The `on_enter` entry wrapper: one known action key, or a single
+/// binding-specific key.
+fn action_object() -> ObjectModel {
+    // Fails to compile when an action variant is added without a model.
+    fn _exhaustive(action: &Action) {
+        match action {
+            Action::SendNotification { .. }
+            | Action::Log { .. }
+            | Action::SendElicitation { .. }
+            | Action::BindingSpecific { .. } => {}
+        }
+    }
+    let action = |name, ty, description| FieldModel {
+        name,
+        ty: object(ty),
+        required: false,
+        description,
+    };
+    ObjectModel {
+        name: "Action",
+        description: "An entry action: exactly one action key besides `x-*` extensions.",
+        extensions: true,
+        fields: vec![
+            action(
+                "send_notification",
+                "SendNotification",
+                "Send a protocol notification.",
+            ),
+            action("log", "Log", "Emit a log message."),
+            action(
+                "send_elicitation",
+                "SendElicitation",
+                "Send a user elicitation request.",
+            ),
+        ],
+    }
+}
+
+fn object(name: &'static str) -> FieldType {
+    FieldType::Object { name }
+}
+
+fn list(items: FieldType) -> FieldType {
+    FieldType::List {
+        items: Box::new(items),
+    }
+}
+
+fn map(values: FieldType) -> FieldType {
+    FieldType::Map {
+        values: Box::new(values),
+    }
+}
+
+fn enumeration<T: DeserializeOwned>() -> FieldType {
+    FieldType::Enum {
+        values: variants::<T>(),
+    }
+}
+
+// ─── Requirements ───────────────────────────────────────────────────────────
+
+fn requirements() -> Vec<Requirement> {
+    use RequirementKind::*;
+    vec![
+        requirement(
+            "V-030",
+            "Execution",
+            ExactlyOne,
+            &["state", "phases", "actors"],
+            None,
+        ),
+        requirement("V-060", "Endpoint", ExactlyOne, &["url", "command"], None),
+        requirement(
+            "V-030",
+            "Execution",
+            Required,
+            &["mode"],
+            Some("state is present"),
+        ),
+        requirement(
+            "V-028",
+            "Phase",
+            Required,
+            &["mode"],
+            Some("execution.mode and execution.actors are absent"),
+        ),
+        requirement(
+            "V-028",
+            "Indicator",
+            Required,
+            &["protocol"],
+            Some("execution.mode is absent"),
+        ),
+        requirement(
+            "V-008",
+            "Phase",
+            Required,
+            &["trigger"],
+            Some("the phase is not the last of its list"),
+        ),
+        requirement(
+            "V-009",
+            "Phase",
+            Required,
+            &["state"],
+            Some("the phase is the first of its list"),
+        ),
+        requirement(
+            "V-012",
+            "Indicator",
+            ExactlyOne,
+            &["pattern", "expression", "semantic", "frequency"],
+            None,
+        ),
+        requirement("V-042", "Trigger", AtLeastOne, &["event", "after"], None),
+        requirement(
+            "V-019",
+            "Trigger",
+            Required,
+            &["event"],
+            Some("count or match is present"),
+        ),
+    ]
+}
+
+fn requirement(
+    rule: &'static str,
+    object: &'static str,
+    kind: RequirementKind,
+    fields: &[&'static str],
+    when: Option<&'static str>,
+) -> Requirement {
+    let info = rule_info(rule).expect("requirement names a known rule");
+    Requirement {
+        rule,
+        spec_ref: info.spec_ref,
+        object,
+        kind,
+        fields: fields.to_vec(),
+        when,
+        description: info.description,
+    }
+}
+
+// ─── Enum probing ───────────────────────────────────────────────────────────
+
+/// Serialized variant names of a derived unit-variant enum, read from the
+/// list its `Deserialize` impl passes to `deserialize_enum`.
+fn variants<T: DeserializeOwned>() -> Vec<&'static str> {
+    let mut found = &[][..];
+    let _ = T::deserialize(VariantProbe(&mut found));
+    found.to_vec()
+}
+
+struct VariantProbe<'a>(&'a mut &'static [&'static str]);
+
+impl<'de> Deserializer<'de> for VariantProbe<'_> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("not an enum"))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        *self.0 = variants;
+        Err(de::Error::custom("probed"))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
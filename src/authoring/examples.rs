@@ -0,0 +1,241 @@
+//! Evaluated example messages for indicators (feature `evaluate`).
+
+use crate::enums::IndicatorResult;
+use crate::evaluate::evaluate_indicator;
+use crate::normalize::normalize_indicator_set;
+use crate::spec::SPEC_VERSION;
+use crate::types::*;
+use regex_syntax::hir::{Class, Hir, HirKind};
+use serde::Serialize;
+use serde_json::{Value, json};
+
+/// Synthetic messages illustrating what an indicator detects, for
+/// documentation and editor hovers.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct IndicatorExamples {
+    /// A message the indicator matches.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matching: Option<Value>,
+    /// A message carrying a value at the indicator's target that it does
+    /// not match.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub non_matching: Option<Value>,
+}
+
+/// Build example messages for a pattern indicator.
+///
+/// Each message is the payload the indicator's target is relative to, with
+/// the target defaulting to the surface's from the registry. A candidate is
+/// kept only if [`evaluate_indicator`] gives it the claimed result —
+/// `matched` or `not_matched` — so an example never misdescribes the
+/// indicator. Expression, semantic, and frequency indicators and targets
+/// with decoders have no examples, nor do conditions that none of the
+/// synthesized values satisfy.
+pub fn examples_for(indicator: &Indicator) -> IndicatorExamples {
+    let set = IndicatorSet {
+        oatf: SPEC_VERSION.to_string(),
+        schema: None,
+        indicators: vec![indicator.clone()],
+        oatf_is_first_key: true,
+    };
+    let indicator = normalize_indicator_set(set).indicators.remove(0);
+    let Some(PatternMatch {
+        target: Some(target),
+        condition: Some(condition),
+        ..
+    }) = &indicator.pattern
+    else {
+        return IndicatorExamples {
+            matching: None,
+            non_matching: None,
+        };
+    };
+    let example = |candidates: Vec<Value>, expected: IndicatorResult| {
+        candidates
+            .into_iter()
+            .filter_map(|value| message_at(target, value))
+            .find(|message| evaluate_indicator(&indicator, message, None, None).result == expected)
+    };
+    IndicatorExamples {
+        matching: example(matching_values(condition), IndicatorResult::Matched),
+        non_matching: example(non_matching_values(condition), IndicatorResult::NotMatched),
+    }
+}
+
+/// A message holding `value` at `target`, with one element per `[*]`.
+fn message_at(target: &str, value: Value) -> Option<Value> {
+    if target.contains('!') {
+        return None;
+    }
+    target.rsplit('.').try_fold(value, |inner, segment| {
+        let (name, inner) = match segment.strip_suffix("[*]") {
+            Some(name) => (name, Value::Array(vec![inner])),
+            None => (segment, inner),
+        };
+        if name.contains(['[', ']']) {
+            return None;
+        }
+        Some(if name.is_empty() {
+            inner
+        } else {
+            json!({ name: inner })
+        })
+    })
+}
+
+/// Values that may satisfy `condition`, most specific first.
+fn matching_values(condition: &Condition) -> Vec<Value> {
+    let cond = match condition {
+        Condition::Equality(expected) => return vec![expected.clone()],
+        Condition::Operators(cond) => cond,
+    };
+    if let Some(patch) = &cond.json_patch {
+        return patch_samples(patch);
+    }
+    let mut values = cond.any_of.clone().unwrap_or_default();
+    let (low, high) = bounds(cond);
+    if low.is_some() || high.is_some() {
+        let n = match (low, high) {
+            (Some(low), Some(high)) => (low + high) / 2.0,
+            (Some(low), None) => low.floor() + 1.0,
+            (None, high) => high.unwrap_or_default().ceil() - 1.0,
+        };
+        values.extend(number(n));
+    }
+    let part = |s: &Option<String>| s.clone().unwrap_or_default();
+    let regex = match &cond.regex {
+        Some(re) => regex_sample(re),
+        None => Some(String::new()),
+    };
+    if let Some(regex) = regex {
+        let (start, middle, end) = (
+            part(&cond.starts_with),
+            part(&cond.contains),
+            part(&cond.ends_with),
+        );
+        values.push(Value::String(format!(
+            "{}{}{}{}",
+            start, regex, middle, end
+        )));
+        values.push(Value::String(format!(
+            "{}{}{}{}",
+            regex, start, middle, end
+        )));
+    }
+    values
+}
+
+/// One-operation patches that may satisfy `patch`, with `*` path segments
+/// filled in and a final `**` dropped.
+fn patch_samples(patch: &PatchMatch) -> Vec<Value> {
+    let op = patch.op.map_or("add", |op| op.as_str());
+    let path = patch
+        .path
+        .as_deref()
+        .unwrap_or("/x")
+        .split('/')
+        .filter(|s| *s != "**")
+        .map(|s| if s == "*" { "x" } else { s })
+        .collect::<Vec<_>>()
+        .join("/");
+    let values = match patch.value.as_deref() {
+        None => vec![None],
+        Some(MatchEntry::Scalar(v)) => vec![Some(v.clone())],
+        Some(MatchEntry::Condition(c)) if c.exists == Some(false) => vec![None],
+        Some(MatchEntry::Condition(c)) => matching_values(&Condition::Operators(c.clone()))
+            .into_iter()
+            .map(Some)
+            .collect(),
+    };
+    values
+        .into_iter()
+        .map(|value| {
+            let mut operation = json!({"op": op, "path": path});
+            if let Some(value) = value {
+                operation["value"] = value;
+            }
+            json!([operation])
+        })
+        .collect()
+}
+
+/// Values that may fail `condition`: just outside numeric bounds, then
+/// generic benign values.
+fn non_matching_values(condition: &Condition) -> Vec<Value> {
+    let mut values = Vec::new();
+    if let Condition::Operators(cond) = condition {
+        let (low, high) = bounds(cond);
+        values.extend(low.and_then(|low| number(low.floor() - 1.0)));
+        values.extend(high.and_then(|high| number(high.ceil() + 1.0)));
+    }
+    values.extend([
+        json!("benign example"),
+        json!(""),
+        json!(0),
+        json!(false),
+        Value::Null,
+    ]);
+    values
+}
+
+/// The tightest lower and upper numeric bounds of `cond`.
+fn bounds(cond: &MatchCondition) -> (Option<f64>, Option<f64>) {
+    (
+        [cond.gt, cond.gte].into_iter().flatten().reduce(f64::max),
+        [cond.lt, cond.lte].into_iter().flatten().reduce(f64::min),
+    )
+}
+
+/// `n` as a JSON number, integral when it has no fractional part.
+fn number(n: f64) -> Option<Value> {
+    if n.fract() == 0.0 && n.abs() < i64::MAX as f64 {
+        Some(json!(n as i64))
+    } else {
+        serde_json::Number::from_f64(n).map(Value::Number)
+    }
+}
+
+/// A short string matching `pattern`: the first alternative of each
+/// alternation, the minimum count of each repetition, and a letter, digit,
+/// or space from each class where it has one.
+fn regex_sample(pattern: &str) -> Option<String> {
+    fn sample(hir: &Hir, out: &mut String) -> bool {
+        match hir.kind() {
+            HirKind::Empty | HirKind::Look(_) => true,
+            HirKind::Literal(lit) => std::str::from_utf8(&lit.0).map(|s| out.push_str(s)).is_ok(),
+            HirKind::Class(Class::Unicode(class)) => {
+                let ranges: Vec<_> = class
+                    .ranges()
+                    .iter()
+                    .map(|r| (r.start(), r.end()))
+                    .collect();
+                class_sample(&ranges).map(|c| out.push(c)).is_some()
+            }
+            HirKind::Class(Class::Bytes(class)) => {
+                let ranges: Vec<_> = class
+                    .ranges()
+                    .iter()
+                    .filter(|r| r.start().is_ascii())
+                    .map(|r| (r.start() as char, r.end().min(0x7f) as char))
+                    .collect();
+                class_sample(&ranges).map(|c| out.push(c)).is_some()
+            }
+            HirKind::Repetition(rep) => (0..rep.min).all(|_| sample(&rep.sub, out)),
+            HirKind::Capture(cap) => sample(&cap.sub, out),
+            HirKind::Concat(items) => items.iter().all(|item| sample(item, out)),
+            HirKind::Alternation(items) => items.first().is_some_and(|item| sample(item, out)),
+        }
+    }
+    let hir = regex_syntax::Parser::new().parse(pattern).ok()?;
+    let mut out = String::new();
+    sample(&hir, &mut out).then_some(out)
+}
+
+fn class_sample(ranges: &[(char, char)]) -> Option<char> {
+    ('a'..='z')
+        .chain('A'..='Z')
+        .chain('0'..='9')
+        .chain([' '])
+        .find(|c| ranges.iter().any(|(start, end)| (start..=end).contains(&c)))
+        .or_else(|| ranges.first().map(|(start, _)| *start))
+}
@@ -0,0 +1,271 @@
+//! Loading documents from files and compressed bundles.
+//!
+//! [`load_path`] picks a reader from the file name, and [`load_dir`] loads
+//! every such file under a directory:
+//!
+//! | Extension | Contents |
+//! |-----------|----------|
+//! | `.yaml`, `.yml`, `.json` | one document |
+//! | `.yaml.gz`, `.yml.gz`, `.json.gz` | one gzip-compressed document |
+//! | `.tar.gz`, `.tgz` | a gzip-compressed tar archive of documents |
+//!
+//! Compressed formats require the `bundle` feature. Archive members that are
+//! not `.yaml`, `.yml`, or `.json` files are ignored, so bundles may carry
+//! READMEs and signatures alongside the documents.
+//!
+//! A [`Pack`] is a `.tar.gz` bundle with a manifest naming the pack and
+//! listing each document's SHA-256 digest, for publishing curated document
+//! sets with integrity metadata. [`load_path`] loads a pack's documents like
+//! any bundle's, without checking the manifest; [`Pack::load`] verifies it
+//! first. Packs require the `bundle` feature.
+
+use crate::LoadResult;
+use crate::error::{LoadPathError, LoadPathErrorKind, OATFError, ParseError, ParseErrorKind};
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "bundle")]
+mod pack;
+
+#[cfg(feature = "bundle")]
+pub use pack::{PACK_MANIFEST_NAME, Pack, PackDocument, PackManifest};
+
+/// One document loaded by [`load_path`].
+#[derive(Debug)]
+pub struct PathEntry {
+    /// Source identity: the file path, or `archive!member` for bundle members.
+    pub source: String,
+    /// The outcome of [`crate::load`] on the entry's contents.
+    pub result: Result<LoadResult, Vec<OATFError>>,
+}
+
+/// Load every document at `path`, one [`PathEntry`] per document.
+///
+/// A plain or gzip-compressed document yields one entry; a `.tar.gz` bundle
+/// yields one entry per document member, in archive order. Entries that fail
+/// to parse or validate are reported in their own `result` and do not stop
+/// the remaining entries from loading.
+///
+/// # Errors
+///
+/// Returns `LoadPathError` if the file cannot be read, its format is not
+/// recognized, compressed input is given without the `bundle` feature, or the
+/// compressed stream or archive is corrupt.
+pub fn load_path(path: impl AsRef<Path>) -> Result<Vec<PathEntry>, LoadPathError> {
+    Ok(load_entries(&read_members(path.as_ref())?))
+}
+
+/// Load every document file under the directory `path`, recursively, one
+/// [`PathEntry`] per document.
+///
+/// Files are visited in path order and read as by [`load_path`]; files with
+/// other extensions are skipped. Documents are parsed, validated, and
+/// normalized in parallel, and entries are returned in path order (bundle
+/// members in archive order), so the result does not depend on scheduling.
+/// A file that cannot be read or unpacked becomes a single entry whose
+/// result is an `io` parse error, and does not stop the remaining files.
+///
+/// # Errors
+///
+/// Returns `LoadPathError` of kind `io` if `path` or a directory below it
+/// cannot be listed.
+pub fn load_dir(path: impl AsRef<Path>) -> Result<Vec<PathEntry>, LoadPathError> {
+    let mut files = Vec::new();
+    collect_files(path.as_ref(), &mut files)?;
+    files.sort();
+
+    let read = crate::parallel::map_ordered(&files, |file| read_members(file));
+    let members: Vec<(String, Result<Vec<u8>, String>)> = files
+        .iter()
+        .zip(read)
+        .flat_map(|(file, result)| match result {
+            Ok(members) => members
+                .into_iter()
+                .map(|(source, bytes)| (source, Ok(bytes)))
+                .collect(),
+            Err(e) => vec![(file.display().to_string(), Err(e.message))],
+        })
+        .collect();
+    Ok(crate::parallel::map_ordered(
+        &members,
+        |(source, bytes)| match bytes {
+            Ok(bytes) => load_entry(source.clone(), bytes),
+            Err(message) => PathEntry {
+                source: source.clone(),
+                result: Err(vec![io_error(message.clone())]),
+            },
+        },
+    ))
+}
+
+/// Load `members`, given as `(source, contents)` pairs, in parallel, keeping
+/// their order.
+pub(crate) fn load_entries<B: AsRef<[u8]> + Sync>(members: &[(String, B)]) -> Vec<PathEntry> {
+    crate::parallel::map_ordered(members, |(source, bytes)| {
+        load_entry(source.clone(), bytes.as_ref())
+    })
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), LoadPathError> {
+    let error = |e: std::io::Error| LoadPathError {
+        kind: LoadPathErrorKind::Io,
+        path: dir.display().to_string(),
+        message: format!("failed to list directory: {}", e),
+    };
+    for entry in std::fs::read_dir(dir).map_err(error)? {
+        let entry = entry.map_err(error)?;
+        let path = entry.path();
+        if entry.file_type().map_err(error)?.is_dir() {
+            collect_files(&path, files)?;
+        } else if Format::detect(&entry.file_name().to_string_lossy().to_lowercase()).is_some() {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Read the documents at `path` as `(source, contents)` pairs without
+/// parsing them.
+fn read_members(path: &Path) -> Result<Vec<(String, Vec<u8>)>, LoadPathError> {
+    let display = path.display().to_string();
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let error = |kind, message: String| LoadPathError {
+        kind,
+        path: display.clone(),
+        message,
+    };
+
+    let format = Format::detect(&name).ok_or_else(|| {
+        error(
+            LoadPathErrorKind::UnsupportedFormat,
+            "expected .yaml, .yml, .json, their .gz forms, or a .tar.gz bundle".to_string(),
+        )
+    })?;
+    let bytes = std::fs::read(path)
+        .map_err(|e| error(LoadPathErrorKind::Io, format!("failed to read: {}", e)))?;
+
+    match format {
+        Format::Document => Ok(vec![(display.clone(), bytes)]),
+        Format::Compressed => {
+            let bytes = gunzip(&bytes).map_err(|(kind, message)| error(kind, message))?;
+            Ok(vec![(display.clone(), bytes)])
+        }
+        Format::Bundle => {
+            let members = untar_gz(&bytes).map_err(|(kind, message)| error(kind, message))?;
+            Ok(members
+                .into_iter()
+                .map(|(member, bytes)| (format!("{}!{}", display, member), bytes))
+                .collect())
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Format {
+    Document,
+    Compressed,
+    Bundle,
+}
+
+const DOCUMENT_EXTENSIONS: &[&str] = &[".yaml", ".yml", ".json"];
+
+impl Format {
+    fn detect(name: &str) -> Option<Format> {
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(Format::Bundle)
+        } else if let Some(inner) = name.strip_suffix(".gz") {
+            is_document_name(inner).then_some(Format::Compressed)
+        } else {
+            is_document_name(name).then_some(Format::Document)
+        }
+    }
+}
+
+fn is_document_name(name: &str) -> bool {
+    let name = name.to_lowercase();
+    DOCUMENT_EXTENSIONS.iter().any(|ext| name.ends_with(ext))
+}
+
+pub(crate) fn load_entry(source: String, bytes: &[u8]) -> PathEntry {
+    let result = match std::str::from_utf8(bytes) {
+        Ok(input) => crate::load(input),
+        Err(e) => Err(vec![io_error(format!("input is not valid UTF-8: {}", e))]),
+    };
+    PathEntry { source, result }
+}
+
+fn io_error(message: String) -> OATFError {
+    OATFError::Parse(ParseError {
+        kind: ParseErrorKind::Io,
+        message,
+        path: None,
+        line: None,
+        column: None,
+    })
+}
+
+type UnpackError = (LoadPathErrorKind, String);
+
+#[cfg(feature = "bundle")]
+fn gunzip(bytes: &[u8]) -> Result<Vec<u8>, UnpackError> {
+    use std::io::Read;
+
+    let mut out = Vec::new();
+    flate2::read::GzDecoder::new(bytes)
+        .read_to_end(&mut out)
+        .map_err(|e| {
+            (
+                LoadPathErrorKind::Archive,
+                format!("invalid gzip stream: {}", e),
+            )
+        })?;
+    Ok(out)
+}
+
+#[cfg(feature = "bundle")]
+fn untar_gz(bytes: &[u8]) -> Result<Vec<(String, Vec<u8>)>, UnpackError> {
+    use std::io::Read;
+
+    let archive_error =
+        |e: std::io::Error| (LoadPathErrorKind::Archive, format!("invalid bundle: {}", e));
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(bytes));
+    let mut members = Vec::new();
+    for entry in archive.entries().map_err(archive_error)? {
+        let mut entry = entry.map_err(archive_error)?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let name = entry
+            .path()
+            .map_err(archive_error)?
+            .to_string_lossy()
+            .into_owned();
+        if !is_document_name(&name) || name == PACK_MANIFEST_NAME {
+            continue;
+        }
+        let mut contents = Vec::new();
+        entry.read_to_end(&mut contents).map_err(archive_error)?;
+        members.push((name, contents));
+    }
+    Ok(members)
+}
+
+#[cfg(not(feature = "bundle"))]
+fn gunzip(_bytes: &[u8]) -> Result<Vec<u8>, UnpackError> {
+    Err(feature_disabled())
+}
+
+#[cfg(not(feature = "bundle"))]
+fn untar_gz(_bytes: &[u8]) -> Result<Vec<(String, Vec<u8>)>, UnpackError> {
+    Err(feature_disabled())
+}
+
+#[cfg(not(feature = "bundle"))]
+fn feature_disabled() -> UnpackError {
+    (
+        LoadPathErrorKind::FeatureDisabled,
+        "compressed input requires the `bundle` feature".to_string(),
+    )
+}
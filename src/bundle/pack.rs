@@ -0,0 +1,356 @@
+//! OATF packs: `.tar.gz` bundles with an integrity manifest.
+//!
+//! A pack is a curated set of documents published together. Its archive
+//! holds [`PACK_MANIFEST_NAME`] at the root, naming the pack and listing
+//! every document with its SHA-256 digest, followed by the documents:
+//!
+//! ```json
+//! {
+//!   "name": "acme-mcp-detections",
+//!   "version": "1.2.0",
+//!   "publisher": "ACME Security",
+//!   "documents": [
+//!     {"path": "attacks/oatf-001.yaml", "digest": "sha256:9f86…"}
+//!   ]
+//! }
+//! ```
+//!
+//! [`Pack::verify`] checks that the archive carries exactly the listed
+//! documents with matching digests; [`Pack::load`] verifies and then loads
+//! them. A pack built [`with_sharing`](Pack::with_sharing) refuses documents
+//! its TLP policy does not permit.
+
+use super::{PathEntry, is_document_name, load_entries};
+use crate::digest::sha256_digest;
+use crate::error::{PackError, PackErrorKind};
+use crate::sharing::SharingPolicy;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::Path;
+
+/// Path of the manifest inside a pack archive.
+pub const PACK_MANIFEST_NAME: &str = "oatf-pack.json";
+
+/// A pack's name, version, publisher, and document digests.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackManifest {
+    /// Pack name.
+    pub name: String,
+    /// Pack version.
+    pub version: String,
+    /// Who publishes the pack.
+    pub publisher: String,
+    /// The pack's documents, in load order.
+    pub documents: Vec<PackDocument>,
+}
+
+/// A document listed in a [`PackManifest`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackDocument {
+    /// Path of the document inside the archive.
+    pub path: String,
+    /// `sha256:<hex>` digest of the document's bytes.
+    pub digest: String,
+}
+
+/// An OATF pack: a manifest and the documents it lists.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pack {
+    /// The pack manifest.
+    pub manifest: PackManifest,
+    /// Document contents by archive path.
+    pub documents: BTreeMap<String, Vec<u8>>,
+    /// Policy every document must satisfy to be added or archived. Not
+    /// stored in the archive.
+    pub sharing: Option<SharingPolicy>,
+}
+
+impl Pack {
+    /// An empty pack.
+    pub fn new(
+        name: impl Into<String>,
+        version: impl Into<String>,
+        publisher: impl Into<String>,
+    ) -> Self {
+        Pack {
+            manifest: PackManifest {
+                name: name.into(),
+                version: version.into(),
+                publisher: publisher.into(),
+                documents: Vec::new(),
+            },
+            documents: BTreeMap::new(),
+            sharing: None,
+        }
+    }
+
+    /// Refuse documents that `policy` does not permit, in
+    /// [`add_document`](Self::add_document) and when archiving.
+    pub fn with_sharing(mut self, policy: SharingPolicy) -> Self {
+        self.sharing = Some(policy);
+        self
+    }
+
+    /// Add a document and record its digest in the manifest.
+    ///
+    /// # Errors
+    ///
+    /// Returns `invalid_manifest` if `path` is not a relative `.yaml`,
+    /// `.yml`, or `.json` path, or is already in the pack, and `restricted`
+    /// if the pack's sharing policy refuses the document.
+    pub fn add_document(
+        &mut self,
+        path: impl Into<String>,
+        content: impl Into<Vec<u8>>,
+    ) -> Result<(), PackError> {
+        let path = path.into();
+        check_path(&path)?;
+        if self.documents.contains_key(&path) {
+            return Err(invalid_manifest(format!(
+                "document '{}' is already in the pack",
+                path
+            )));
+        }
+        let content = content.into();
+        self.check_sharing(&path, &content)?;
+        self.manifest.documents.push(PackDocument {
+            path: path.clone(),
+            digest: sha256_digest(&content),
+        });
+        self.documents.insert(path, content);
+        Ok(())
+    }
+
+    /// Check that the pack carries exactly the documents its manifest lists,
+    /// each matching its digest.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first problem found: `invalid_manifest` for an empty name,
+    /// version, or publisher or an invalid or repeated path,
+    /// `missing_document`, `digest_mismatch`, or `unlisted_document`.
+    pub fn verify(&self) -> Result<(), PackError> {
+        let manifest = &self.manifest;
+        for (field, value) in [
+            ("name", &manifest.name),
+            ("version", &manifest.version),
+            ("publisher", &manifest.publisher),
+        ] {
+            if value.trim().is_empty() {
+                return Err(invalid_manifest(format!("pack {} is empty", field)));
+            }
+        }
+
+        let mut listed = std::collections::HashSet::new();
+        for document in &manifest.documents {
+            check_path(&document.path)?;
+            if !listed.insert(document.path.as_str()) {
+                return Err(invalid_manifest(format!(
+                    "document '{}' is listed more than once",
+                    document.path
+                )));
+            }
+            let Some(content) = self.documents.get(&document.path) else {
+                return Err(PackError {
+                    kind: PackErrorKind::MissingDocument,
+                    message: format!("document '{}' is not in the pack", document.path),
+                });
+            };
+            let actual = sha256_digest(content);
+            if actual != document.digest {
+                return Err(PackError {
+                    kind: PackErrorKind::DigestMismatch,
+                    message: format!(
+                        "document '{}' does not match {} ({})",
+                        document.path, document.digest, actual
+                    ),
+                });
+            }
+        }
+        if let Some(path) = self.documents.keys().find(|p| !listed.contains(p.as_str())) {
+            return Err(PackError {
+                kind: PackErrorKind::UnlistedDocument,
+                message: format!("document '{}' is not listed in the manifest", path),
+            });
+        }
+        Ok(())
+    }
+
+    /// [`verify`](Self::verify) the pack, then load each document via
+    /// [`crate::load`] in manifest order, one [`PathEntry`] per document
+    /// with source `name@version!path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`verify`](Self::verify) error; documents that fail to
+    /// parse or validate are reported in their own entry instead.
+    pub fn load(&self) -> Result<Vec<PathEntry>, PackError> {
+        self.verify()?;
+        let prefix = format!("{}@{}", self.manifest.name, self.manifest.version);
+        let members: Vec<_> = self
+            .manifest
+            .documents
+            .iter()
+            .map(|d| (format!("{}!{}", prefix, d.path), &self.documents[&d.path]))
+            .collect();
+        Ok(load_entries(&members))
+    }
+
+    /// Encode the pack as a `.tar.gz` archive: the manifest, then the
+    /// documents in manifest order. Archives of equal packs are
+    /// byte-identical.
+    ///
+    /// # Errors
+    ///
+    /// Returns `invalid_manifest` if the manifest lists a document the pack
+    /// does not carry, `restricted` if the pack's sharing policy refuses a
+    /// document, or `io` if encoding fails.
+    pub fn to_tar_gz(&self) -> Result<Vec<u8>, PackError> {
+        let manifest = serde_json::to_vec_pretty(&self.manifest)
+            .map_err(|e| invalid_manifest(format!("failed to encode manifest: {}", e)))?;
+        let mut members = vec![(PACK_MANIFEST_NAME, manifest.as_slice())];
+        for document in &self.manifest.documents {
+            let content = self.documents.get(&document.path).ok_or_else(|| {
+                invalid_manifest(format!("document '{}' is not in the pack", document.path))
+            })?;
+            self.check_sharing(&document.path, content)?;
+            members.push((document.path.as_str(), content.as_slice()));
+        }
+
+        let io_error = |e: std::io::Error| PackError {
+            kind: PackErrorKind::Io,
+            message: format!("failed to encode pack: {}", e),
+        };
+        let encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+        for (path, content) in members {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_mtime(0);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, content)
+                .map_err(io_error)?;
+        }
+        builder
+            .into_inner()
+            .and_then(|encoder| encoder.finish())
+            .map_err(io_error)
+    }
+
+    /// Decode a pack archive. The pack is not verified.
+    ///
+    /// # Errors
+    ///
+    /// Returns `archive` if the archive is corrupt, or `invalid_manifest` if
+    /// it has no manifest or the manifest is malformed.
+    pub fn from_tar_gz(bytes: &[u8]) -> Result<Pack, PackError> {
+        let archive_error = |e: std::io::Error| PackError {
+            kind: PackErrorKind::Archive,
+            message: format!("invalid pack: {}", e),
+        };
+        let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(bytes));
+        let mut manifest = None;
+        let mut documents = BTreeMap::new();
+        for entry in archive.entries().map_err(archive_error)? {
+            let mut entry = entry.map_err(archive_error)?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            let path = entry
+                .path()
+                .map_err(archive_error)?
+                .to_string_lossy()
+                .into_owned();
+            let is_manifest = path == PACK_MANIFEST_NAME;
+            if !is_manifest && !is_document_name(&path) {
+                continue;
+            }
+            let mut content = Vec::new();
+            entry.read_to_end(&mut content).map_err(archive_error)?;
+            if is_manifest {
+                manifest = Some(content);
+            } else {
+                documents.insert(path, content);
+            }
+        }
+
+        let manifest = manifest
+            .ok_or_else(|| invalid_manifest(format!("pack has no {}", PACK_MANIFEST_NAME)))?;
+        let manifest = serde_json::from_slice(&manifest)
+            .map_err(|e| invalid_manifest(format!("malformed manifest: {}", e)))?;
+        Ok(Pack {
+            manifest,
+            documents,
+            sharing: None,
+        })
+    }
+
+    /// Write the pack to a `.tar.gz` file.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`to_tar_gz`](Self::to_tar_gz) error, or `io` if the file
+    /// cannot be written.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), PackError> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_tar_gz()?).map_err(|e| PackError {
+            kind: PackErrorKind::Io,
+            message: format!("{}: failed to write: {}", path.display(), e),
+        })
+    }
+
+    /// Read a pack from a `.tar.gz` file. The pack is not verified.
+    ///
+    /// # Errors
+    ///
+    /// Returns `io` if the file cannot be read, or the
+    /// [`from_tar_gz`](Self::from_tar_gz) error.
+    pub fn read(path: impl AsRef<Path>) -> Result<Pack, PackError> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).map_err(|e| PackError {
+            kind: PackErrorKind::Io,
+            message: format!("{}: failed to read: {}", path.display(), e),
+        })?;
+        Pack::from_tar_gz(&bytes)
+    }
+
+    fn check_sharing(&self, path: &str, content: &[u8]) -> Result<(), PackError> {
+        match &self.sharing {
+            Some(policy) => policy.check_source(content).map_err(|e| PackError {
+                kind: PackErrorKind::Restricted,
+                message: format!("{}: {}", path, e),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+fn invalid_manifest(message: String) -> PackError {
+    PackError {
+        kind: PackErrorKind::InvalidManifest,
+        message,
+    }
+}
+
+/// Document paths are relative, `/`-separated, and free of `.` and `..`
+/// segments, so a pack cannot name files outside its own tree.
+fn check_path(path: &str) -> Result<(), PackError> {
+    let valid = is_document_name(path)
+        && path != PACK_MANIFEST_NAME
+        && !path.starts_with('/')
+        && !path.contains('\\')
+        && path
+            .split('/')
+            .all(|segment| !segment.is_empty() && segment != "." && segment != "..");
+    if valid {
+        Ok(())
+    } else {
+        Err(invalid_manifest(format!(
+            "invalid document path '{}': expected a relative .yaml, .yml, or .json path",
+            path
+        )))
+    }
+}
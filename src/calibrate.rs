@@ -0,0 +1,278 @@
+//! Confidence calibration from labeled evaluation outcomes.
+//!
+//! An indicator's `confidence` states how likely a match is to be a true
+//! attack. Given labeled outcomes — each indicator evaluated against samples
+//! known to be malicious or benign — this module measures every indicator's
+//! precision and recall and recommends updated `confidence` values and
+//! semantic `threshold`s, grouped into one [`DocumentPatch`] per document.
+
+use crate::catalog::CatalogEntry;
+use crate::types::Document;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Semantic threshold assumed when an indicator does not set one (§2.15).
+const DEFAULT_SEMANTIC_THRESHOLD: f64 = 0.7;
+
+/// The result of evaluating one indicator against one labeled sample.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LabeledOutcome {
+    /// ID of the evaluated indicator (unique across the corpus, C-002).
+    pub indicator_id: String,
+    /// Whether the indicator matched the sample.
+    pub matched: bool,
+    /// Ground truth: whether the sample is an actual attack.
+    pub malicious: bool,
+}
+
+/// Confusion counts for a single indicator.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndicatorStats {
+    /// Matched a malicious sample.
+    pub true_positives: u64,
+    /// Matched a benign sample.
+    pub false_positives: u64,
+    /// Did not match a benign sample.
+    pub true_negatives: u64,
+    /// Did not match a malicious sample.
+    pub false_negatives: u64,
+}
+
+impl IndicatorStats {
+    /// Fraction of matches that were malicious, or `None` without matches.
+    pub fn precision(&self) -> Option<f64> {
+        let matched = self.true_positives + self.false_positives;
+        (matched > 0).then(|| self.true_positives as f64 / matched as f64)
+    }
+
+    /// Fraction of malicious samples that matched, or `None` without
+    /// malicious samples.
+    pub fn recall(&self) -> Option<f64> {
+        let malicious = self.true_positives + self.false_negatives;
+        (malicious > 0).then(|| self.true_positives as f64 / malicious as f64)
+    }
+}
+
+/// Tuning knobs for [`calibrate`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CalibrationOptions {
+    /// Minimum number of matches before `confidence` or a precision-driven
+    /// threshold change is recommended, and minimum number of malicious
+    /// samples before a recall-driven threshold change is recommended.
+    pub min_samples: u64,
+    /// Smallest difference (in percentage points) between the current and
+    /// measured confidence that is worth a change.
+    pub tolerance: i64,
+    /// Semantic indicators below this precision get a higher threshold.
+    pub target_precision: f64,
+    /// Semantic indicators meeting the precision target but below this
+    /// recall get a lower threshold.
+    pub target_recall: f64,
+    /// Amount a semantic threshold moves per recommendation.
+    pub threshold_step: f64,
+}
+
+impl Default for CalibrationOptions {
+    fn default() -> Self {
+        CalibrationOptions {
+            min_samples: 10,
+            tolerance: 5,
+            target_precision: 0.9,
+            target_recall: 0.8,
+            threshold_step: 0.05,
+        }
+    }
+}
+
+/// The indicator field an [`Adjustment`] changes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CalibratedField {
+    /// `indicators[].confidence`.
+    Confidence,
+    /// `indicators[].semantic.threshold`.
+    Threshold,
+}
+
+/// A recommended change to one indicator field.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Adjustment {
+    /// ID of the indicator to change.
+    pub indicator_id: String,
+    /// Which field changes.
+    pub field: CalibratedField,
+    /// Dot-path of the field in the document (e.g., `attack.indicators[2].confidence`).
+    pub path: String,
+    /// Current value, or `None` when the field is unset.
+    pub current: Option<Value>,
+    /// Recommended value.
+    pub recommended: Value,
+    /// Human-readable justification, citing the measured rates.
+    pub reason: String,
+}
+
+/// Recommended changes for a single document.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DocumentPatch {
+    /// Source identity of the document (see [`CatalogEntry::source`]).
+    pub source: String,
+    /// Attack ID of the document, if it has one.
+    pub attack_id: Option<String>,
+    /// Changes in indicator order.
+    pub adjustments: Vec<Adjustment>,
+}
+
+impl DocumentPatch {
+    /// Apply every adjustment to `doc`, matching indicators by ID.
+    /// Adjustments whose indicator is missing are ignored.
+    pub fn apply(&self, doc: &mut Document) {
+        for adjustment in &self.adjustments {
+            let Some(indicator) = doc
+                .attack
+                .indicators
+                .iter_mut()
+                .flatten()
+                .find(|i| i.id.as_deref() == Some(adjustment.indicator_id.as_str()))
+            else {
+                continue;
+            };
+            match adjustment.field {
+                CalibratedField::Confidence => {
+                    indicator.confidence = adjustment.recommended.as_i64();
+                }
+                CalibratedField::Threshold => {
+                    if let Some(semantic) = &mut indicator.semantic {
+                        semantic.threshold = adjustment.recommended.as_f64();
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Aggregate outcomes into confusion counts per indicator ID.
+pub fn tally(outcomes: &[LabeledOutcome]) -> HashMap<String, IndicatorStats> {
+    let mut stats: HashMap<String, IndicatorStats> = HashMap::new();
+    for outcome in outcomes {
+        let entry = stats.entry(outcome.indicator_id.clone()).or_default();
+        match (outcome.matched, outcome.malicious) {
+            (true, true) => entry.true_positives += 1,
+            (true, false) => entry.false_positives += 1,
+            (false, false) => entry.true_negatives += 1,
+            (false, true) => entry.false_negatives += 1,
+        }
+    }
+    stats
+}
+
+/// Recommend calibrated values for every indicator with enough outcomes.
+///
+/// - **Confidence** is set to the indicator's smoothed precision,
+///   `(TP + 1) / (TP + FP + 2)`, as a percentage. It is recommended once the
+///   indicator has at least `min_samples` matches and the value differs from
+///   the current one by at least `tolerance` points (or none is set).
+/// - **Semantic threshold** is raised by `threshold_step` when precision is
+///   below `target_precision`, and lowered by `threshold_step` when precision
+///   meets the target but recall is below `target_recall`. Thresholds stay
+///   within `[threshold_step, 1 - threshold_step]`.
+///
+/// Indicators without an ID, or without outcomes, are left alone. Documents
+/// without any adjustment produce no patch.
+pub fn calibrate(
+    entries: &[CatalogEntry],
+    outcomes: &[LabeledOutcome],
+    options: &CalibrationOptions,
+) -> Vec<DocumentPatch> {
+    let stats = tally(outcomes);
+    let mut patches = Vec::new();
+
+    for entry in entries {
+        let mut adjustments = Vec::new();
+        for (i, indicator) in entry
+            .document
+            .attack
+            .indicators
+            .iter()
+            .flatten()
+            .enumerate()
+        {
+            let Some(id) = indicator.id.as_deref() else {
+                continue;
+            };
+            let Some(s) = stats.get(id) else {
+                continue;
+            };
+            let matched = s.true_positives + s.false_positives;
+            let malicious = s.true_positives + s.false_negatives;
+
+            if matched >= options.min_samples {
+                let measured =
+                    ((s.true_positives + 1) as f64 / (matched + 2) as f64 * 100.0).round() as i64;
+                if indicator
+                    .confidence
+                    .is_none_or(|c| (c - measured).abs() >= options.tolerance)
+                {
+                    adjustments.push(Adjustment {
+                        indicator_id: id.to_string(),
+                        field: CalibratedField::Confidence,
+                        path: format!("attack.indicators[{}].confidence", i),
+                        current: indicator.confidence.map(Value::from),
+                        recommended: Value::from(measured),
+                        reason: format!(
+                            "{} of {} matches were malicious",
+                            s.true_positives, matched
+                        ),
+                    });
+                }
+            }
+
+            let Some(semantic) = &indicator.semantic else {
+                continue;
+            };
+            let current = semantic.threshold.unwrap_or(DEFAULT_SEMANTIC_THRESHOLD);
+            let precision = s.precision().filter(|_| matched >= options.min_samples);
+            let recall = s.recall().filter(|_| malicious >= options.min_samples);
+            let (delta, reason) = match (precision, recall) {
+                (Some(p), _) if p < options.target_precision => (
+                    options.threshold_step,
+                    format!(
+                        "precision {:.2} is below the target {:.2}",
+                        p, options.target_precision
+                    ),
+                ),
+                (Some(_), Some(r)) if r < options.target_recall => (
+                    -options.threshold_step,
+                    format!(
+                        "recall {:.2} is below the target {:.2}",
+                        r, options.target_recall
+                    ),
+                ),
+                _ => continue,
+            };
+            let bounds = options.threshold_step..=1.0 - options.threshold_step;
+            let recommended =
+                ((current + delta).clamp(*bounds.start(), *bounds.end()) * 100.0).round() / 100.0;
+            if (recommended - current).abs() > f64::EPSILON {
+                adjustments.push(Adjustment {
+                    indicator_id: id.to_string(),
+                    field: CalibratedField::Threshold,
+                    path: format!("attack.indicators[{}].semantic.threshold", i),
+                    current: semantic.threshold.map(Value::from),
+                    recommended: Value::from(recommended),
+                    reason,
+                });
+            }
+        }
+
+        if !adjustments.is_empty() {
+            patches.push(DocumentPatch {
+                source: entry.source.clone(),
+                attack_id: entry.document.attack.id.clone(),
+                adjustments,
+            });
+        }
+    }
+
+    patches
+}
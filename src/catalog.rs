@@ -0,0 +1,646 @@
+//! Collections of documents with source identities and corpus-level checks.
+//!
+//! Conformance validation (V-rules) is per document. A [`Catalog`] holds many
+//! documents, each tagged with the source it came from (typically a file
+//! path), and enforces invariants that only make sense across a corpus.
+//!
+//! Catalogs are filled in bulk with [`Catalog::load_all`] and
+//! [`Catalog::load_dir`], and checked with [`Catalog::validate_documents`];
+//! these process documents in parallel and report results in input order.
+//!
+//! Attacks move through a lifecycle (`status`) and may name the attacks they
+//! replace (`supersedes`) and their replacement (`superseded_by`). The
+//! catalog checks these links across documents ([`validate_corpus`],
+//! [`lifecycle_warnings`]) and can filter by lifecycle ([`Catalog::active`],
+//! [`Catalog::with_status`], [`Catalog::successor`]).
+//!
+//! [`Catalog::shareable`] keeps the entries a TLP [`SharingPolicy`] permits
+//! sharing.
+//!
+//! [`Catalog::query`] searches entries with a small query language
+//! (`protocol:mcp AND severity>=high AND tag:exfiltration`) for CLIs and
+//! UIs; see [`CatalogQuery`].
+//!
+//! Verdicts for catalog attacks can be routed to host-defined actions and
+//! destinations by severity, category, and impact ([`route`],
+//! [`Catalog::route`]).
+//!
+//! A [`SymbolIndex`] maps attack IDs, indicator IDs, actor, extractor, and
+//! capture names, and framework techniques to where each document defines
+//! or uses them, for find-references and queries such as "which documents
+//! map to T1059" ([`Catalog::symbols`]). It is updated one document at a
+//! time as sources change.
+//!
+//! [`Catalog::nearest`] ranks entries by
+//! [`similarity`](crate::analyze::similarity) to a document being imported,
+//! to surface likely duplicates and prior art (feature `evaluate`).
+
+use crate::bundle::PathEntry;
+use crate::enums::{AttackResult, Category, Impact, IndicatorResult, SeverityLevel, Status};
+use crate::error::{
+    ConfigError, ConfigErrorKind, CorpusError, CorpusWarning, LoadPathError, OATFError,
+    ValidationResult,
+};
+use crate::sharing::SharingPolicy;
+use crate::types::{Attack, AttackVerdict, Document, Severity};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+mod query;
+mod symbols;
+
+pub use query::{CatalogQuery, Comparison, Filter};
+pub use symbols::{Occurrence, SymbolIndex, SymbolKind, SymbolRole};
+
+/// A document together with the identity of the source it was loaded from.
+#[derive(Clone, Debug)]
+pub struct CatalogEntry {
+    /// Source identity (e.g., a file path or registry URL).
+    pub source: String,
+    /// The document, normalized when added through [`Catalog::load_str`].
+    pub document: Document,
+}
+
+/// An ordered collection of documents.
+#[derive(Clone, Debug, Default)]
+pub struct Catalog {
+    entries: Vec<CatalogEntry>,
+}
+
+impl Catalog {
+    /// Create an empty catalog.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an already-parsed document under the given source identity.
+    pub fn insert(&mut self, source: impl Into<String>, document: Document) {
+        self.entries.push(CatalogEntry {
+            source: source.into(),
+            document,
+        });
+    }
+
+    /// Parse, validate, and normalize `input` via [`crate::load`] and add the
+    /// result under the given source identity.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors from [`crate::load`]; the catalog is unchanged.
+    pub fn load_str(
+        &mut self,
+        source: impl Into<String>,
+        input: &str,
+    ) -> Result<&CatalogEntry, Vec<OATFError>> {
+        let result = crate::load(input)?;
+        self.insert(source, result.document);
+        Ok(self.entries.last().expect("entry was just inserted"))
+    }
+
+    /// Parse, validate, and normalize each `(source, input)` pair via
+    /// [`crate::load`] in parallel, then add the documents that loaded in
+    /// input order. Returns the source and errors of every input that
+    /// failed, in input order.
+    pub fn load_all<S, I>(&mut self, inputs: &[(S, I)]) -> Vec<(String, Vec<OATFError>)>
+    where
+        S: AsRef<str> + Sync,
+        I: AsRef<str> + Sync,
+    {
+        let entries = crate::parallel::map_ordered(inputs, |(source, input)| PathEntry {
+            source: source.as_ref().to_string(),
+            result: crate::load(input.as_ref()),
+        });
+        self.ingest(entries)
+    }
+
+    /// [`load_dir`](crate::load_dir) the directory `path` and add the
+    /// documents that loaded, in path order. Returns the source and errors of
+    /// every document that failed.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`load_dir`](crate::load_dir) error; the catalog is
+    /// unchanged.
+    pub fn load_dir(
+        &mut self,
+        path: impl AsRef<Path>,
+    ) -> Result<Vec<(String, Vec<OATFError>)>, LoadPathError> {
+        Ok(self.ingest(crate::load_dir(path)?))
+    }
+
+    /// Add the documents of loaded `entries`, such as those from
+    /// [`load_path`](crate::load_path), in order. Returns the source and
+    /// errors of every entry that failed to load.
+    pub fn ingest(
+        &mut self,
+        entries: impl IntoIterator<Item = PathEntry>,
+    ) -> Vec<(String, Vec<OATFError>)> {
+        let mut failed = Vec::new();
+        for entry in entries {
+            match entry.result {
+                Ok(loaded) => self.insert(entry.source, loaded.document),
+                Err(errors) => failed.push((entry.source, errors)),
+            }
+        }
+        failed
+    }
+
+    /// All entries in insertion order.
+    pub fn entries(&self) -> &[CatalogEntry] {
+        &self.entries
+    }
+
+    /// Find the first entry whose attack ID equals `id`.
+    pub fn get(&self, id: &str) -> Option<&CatalogEntry> {
+        self.entries
+            .iter()
+            .find(|e| e.document.attack.id.as_deref() == Some(id))
+    }
+
+    /// Number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the catalog has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Check corpus-level invariants over all entries. See [`validate_corpus`].
+    pub fn validate(&self) -> Vec<CorpusError> {
+        validate_corpus(&self.entries)
+    }
+
+    /// Run conformance validation on every entry. See [`validate_documents`].
+    pub fn validate_documents(&self) -> Vec<ValidationResult> {
+        validate_documents(&self.entries)
+    }
+
+    /// Check lifecycle links over all entries. See [`lifecycle_warnings`].
+    pub fn lifecycle_warnings(&self) -> Vec<CorpusWarning> {
+        lifecycle_warnings(&self.entries)
+    }
+
+    /// Entries whose attack has the given status.
+    pub fn with_status(&self, status: Status) -> impl Iterator<Item = &CatalogEntry> {
+        self.entries
+            .iter()
+            .filter(move |e| e.document.attack.status.as_ref() == Some(&status))
+    }
+
+    /// Entries that are not deprecated. Attacks without a status count as
+    /// active.
+    pub fn active(&self) -> impl Iterator<Item = &CatalogEntry> {
+        self.entries
+            .iter()
+            .filter(|e| e.document.attack.status != Some(Status::Deprecated))
+    }
+
+    /// Entries whose TLP marking `policy` permits sharing.
+    pub fn shareable(&self, policy: SharingPolicy) -> impl Iterator<Item = &CatalogEntry> {
+        self.entries
+            .iter()
+            .filter(move |e| policy.permits(&e.document))
+    }
+
+    /// Follow `superseded_by` links from the attack `id` to the newest
+    /// replacement in the catalog. Returns the entry for `id` itself when it
+    /// has no replacement, and `None` when `id` is unknown. A chain that
+    /// leaves the catalog or loops stops at the last attack reached.
+    pub fn successor(&self, id: &str) -> Option<&CatalogEntry> {
+        let mut current = self.get(id)?;
+        let mut visited = HashSet::from([id]);
+        while let Some(next) = current.document.attack.superseded_by.as_deref() {
+            if !visited.insert(next) {
+                break;
+            }
+            match self.get(next) {
+                Some(entry) => current = entry,
+                None => break,
+            }
+        }
+        Some(current)
+    }
+
+    /// Route `verdict` using the attack named by its `attack_id`. See
+    /// [`route`]. Returns an empty list when the attack is not in the
+    /// catalog.
+    pub fn route(&self, verdict: &AttackVerdict, policy: &RoutingPolicy) -> Vec<RoutingDecision> {
+        verdict
+            .attack_id
+            .as_deref()
+            .and_then(|id| self.get(id))
+            .map(|entry| route(verdict, &entry.document.attack, policy))
+            .unwrap_or_default()
+    }
+
+    /// The `k` entries most [similar](crate::analyze::similarity) to `doc`,
+    /// most similar first, with their scores, to spot likely duplicates or
+    /// prior art before adding `doc`. Entries with nothing in common with
+    /// `doc` are left out; ties keep catalog order.
+    #[cfg(feature = "evaluate")]
+    pub fn nearest(&self, doc: &Document, k: usize) -> Vec<(&CatalogEntry, f64)> {
+        use crate::analyze::Fingerprint;
+
+        let fingerprint = Fingerprint::new(doc);
+        let mut scored: Vec<_> = self
+            .entries
+            .iter()
+            .map(|e| (e, fingerprint.similarity(&Fingerprint::new(&e.document))))
+            .filter(|(_, score)| *score > 0.0)
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.truncate(k);
+        scored
+    }
+}
+
+/// [`validate`](crate::validate()) every entry's document, one result per
+/// entry in entry order.
+///
+/// Documents are validated in parallel on a bounded set of threads sharing
+/// the built-in rule registry; the results do not depend on scheduling.
+pub fn validate_documents(entries: &[CatalogEntry]) -> Vec<ValidationResult> {
+    crate::parallel::map_ordered(entries, |entry| crate::validate(&entry.document))
+}
+
+/// Check identifier uniqueness and lifecycle link consistency across a set
+/// of documents.
+///
+/// - **C-001**: attack IDs must be unique across the corpus.
+/// - **C-002**: indicator IDs must be unique across the corpus.
+/// - **C-003**: lifecycle links must agree. When A is `superseded_by` B and
+///   B lists `supersedes`, the list must include A; when A `supersedes` B
+///   and B names a `superseded_by`, it must be A. Links to attacks outside
+///   the corpus are [`lifecycle_warnings`]' concern.
+///
+/// Each collision is reported once per repeated occurrence, naming the source
+/// of the first occurrence and of the repeat. Collisions inside a single
+/// document are V-010's concern and are not reported here. Documents or
+/// indicators without an ID are skipped; normalize first so indicator IDs
+/// are assigned (N-003).
+pub fn validate_corpus(entries: &[CatalogEntry]) -> Vec<CorpusError> {
+    let mut errors = Vec::new();
+    let mut attack_ids: HashMap<&str, &str> = HashMap::new();
+    let mut indicator_ids: HashMap<&str, &str> = HashMap::new();
+
+    for entry in entries {
+        let attack = &entry.document.attack;
+        if let Some(id) = attack.id.as_deref() {
+            check_unique(
+                "C-001",
+                "attack ID",
+                id,
+                &entry.source,
+                &mut attack_ids,
+                &mut errors,
+            );
+        }
+        for ind in attack.indicators.iter().flatten() {
+            if let Some(id) = ind.id.as_deref() {
+                check_unique(
+                    "C-002",
+                    "indicator ID",
+                    id,
+                    &entry.source,
+                    &mut indicator_ids,
+                    &mut errors,
+                );
+            }
+        }
+    }
+
+    check_lifecycle_links(entries, &mut errors);
+    errors
+}
+
+fn check_lifecycle_links(entries: &[CatalogEntry], errors: &mut Vec<CorpusError>) {
+    let by_id = index_by_id(entries);
+    for entry in entries {
+        let attack = &entry.document.attack;
+        let Some(id) = attack.id.as_deref() else {
+            continue;
+        };
+        if let Some(next) = attack.superseded_by.as_deref()
+            && let Some(target) = by_id.get(next)
+            && let Some(list) = &target.document.attack.supersedes
+            && !list.iter().any(|s| s == id)
+        {
+            errors.push(link_error(
+                next,
+                entry,
+                target,
+                format!(
+                    "'{}' is superseded by '{}', which does not list it in supersedes",
+                    id, next
+                ),
+            ));
+        }
+        for old in attack.supersedes.iter().flatten() {
+            if let Some(target) = by_id.get(old.as_str())
+                && let Some(replacement) = target.document.attack.superseded_by.as_deref()
+                && replacement != id
+            {
+                errors.push(link_error(
+                    old,
+                    entry,
+                    target,
+                    format!(
+                        "'{}' supersedes '{}', which names '{}' as its replacement",
+                        id, old, replacement
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+fn link_error(id: &str, from: &CatalogEntry, to: &CatalogEntry, message: String) -> CorpusError {
+    CorpusError {
+        rule: "C-003".to_string(),
+        id: id.to_string(),
+        first_source: from.source.clone(),
+        second_source: to.source.clone(),
+        message,
+    }
+}
+
+/// Index entries by attack ID, keeping the first entry for repeated IDs.
+fn index_by_id(entries: &[CatalogEntry]) -> HashMap<&str, &CatalogEntry> {
+    let mut by_id = HashMap::new();
+    for entry in entries {
+        if let Some(id) = entry.document.attack.id.as_deref() {
+            by_id.entry(id).or_insert(entry);
+        }
+    }
+    by_id
+}
+
+/// Report lifecycle links that are permitted but likely stale.
+///
+/// - **C-004**: a `supersedes` or `superseded_by` link names an attack that
+///   is not in the corpus.
+/// - **C-005**: a `superseded_by` link names a deprecated attack, so users
+///   are routed to a replacement that is itself retired.
+/// - **C-006**: an attack listed in another attack's `supersedes` is not
+///   marked `deprecated`.
+pub fn lifecycle_warnings(entries: &[CatalogEntry]) -> Vec<CorpusWarning> {
+    let by_id = index_by_id(entries);
+    let mut warnings = Vec::new();
+    let mut warn = |rule: &str, id: &str, entry: &CatalogEntry, message: String| {
+        warnings.push(CorpusWarning {
+            rule: rule.to_string(),
+            id: id.to_string(),
+            source: entry.source.clone(),
+            message,
+        })
+    };
+
+    for entry in entries {
+        let attack = &entry.document.attack;
+        if let Some(next) = attack.superseded_by.as_deref() {
+            match by_id.get(next) {
+                None => warn(
+                    "C-004",
+                    next,
+                    entry,
+                    format!("superseded_by names '{}', which is not in the corpus", next),
+                ),
+                Some(target) if target.document.attack.status == Some(Status::Deprecated) => warn(
+                    "C-005",
+                    next,
+                    entry,
+                    format!("superseded_by names '{}', which is deprecated", next),
+                ),
+                Some(_) => {}
+            }
+        }
+        for old in attack.supersedes.iter().flatten() {
+            match by_id.get(old.as_str()) {
+                None => warn(
+                    "C-004",
+                    old,
+                    entry,
+                    format!("supersedes names '{}', which is not in the corpus", old),
+                ),
+                Some(target) if target.document.attack.status != Some(Status::Deprecated) => warn(
+                    "C-006",
+                    old,
+                    entry,
+                    format!("'{}' is superseded but not marked deprecated", old),
+                ),
+                Some(_) => {}
+            }
+        }
+    }
+    warnings
+}
+
+fn check_unique<'a>(
+    rule: &str,
+    what: &str,
+    id: &'a str,
+    source: &'a str,
+    seen: &mut HashMap<&'a str, &'a str>,
+    errors: &mut Vec<CorpusError>,
+) {
+    match seen.get(id) {
+        Some(&first) if first != source => errors.push(CorpusError {
+            rule: rule.to_string(),
+            id: id.to_string(),
+            first_source: first.to_string(),
+            second_source: source.to_string(),
+            message: format!("{} '{}' is not unique across the corpus", what, id),
+        }),
+        Some(_) => {}
+        None => {
+            seen.insert(id, source);
+        }
+    }
+}
+
+// ─── Routing ────────────────────────────────────────────────────────────────
+
+/// What a host application should do with a verdict.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoutingAction {
+    /// Record the verdict only.
+    LogOnly,
+    /// Alert the configured destinations.
+    Notify,
+    /// Block the traffic that produced the verdict.
+    Block,
+}
+
+/// Maps verdicts to actions and destinations. Loadable from YAML with
+/// [`RoutingPolicy::from_yaml`]:
+///
+/// ```yaml
+/// rules:
+///   - name: block-critical
+///     min_severity: critical
+///     action: block
+///     destinations: [pagerduty]
+///   - name: exfiltration
+///     impacts: [data_exfiltration, credential_theft]
+///     action: notify
+///     destinations: [security-channel]
+/// default: log_only
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RoutingPolicy {
+    /// Rules in priority order.
+    pub rules: Vec<RoutingRule>,
+    /// Action when no rule matches; no decision is made when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<RoutingAction>,
+    /// Destinations of the default action.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub default_destinations: Vec<String>,
+}
+
+/// One routing rule. A rule matches when every criterion it sets holds;
+/// list criteria match when the attack has any of the listed values.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RoutingRule {
+    /// Rule name, reported in decisions.
+    pub name: String,
+    /// Verdict results the rule applies to. Defaults to `[exploited]`.
+    #[serde(default = "default_results")]
+    pub results: Vec<AttackResult>,
+    /// Minimum effective severity (see [`route`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_severity: Option<SeverityLevel>,
+    /// Attack categories.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<Category>,
+    /// Attack impacts.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub impacts: Vec<Impact>,
+    /// Action to take.
+    pub action: RoutingAction,
+    /// Destination names, interpreted by the host application.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub destinations: Vec<String>,
+}
+
+fn default_results() -> Vec<AttackResult> {
+    vec![AttackResult::Exploited]
+}
+
+/// A routing outcome for one verdict.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoutingDecision {
+    /// Name of the matching rule; `None` for the policy default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rule: Option<String>,
+    /// Action to take.
+    pub action: RoutingAction,
+    /// Destinations to deliver to.
+    pub destinations: Vec<String>,
+    /// The effective severity the decision was made on.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub severity: Option<SeverityLevel>,
+}
+
+impl RoutingPolicy {
+    /// Parse a policy from YAML.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError` of kind `parse` for malformed YAML or unknown
+    /// fields, and of kind `invalid` for rules without a name or results.
+    pub fn from_yaml(input: &str) -> Result<Self, ConfigError> {
+        let policy: Self = serde_saphyr::from_str(input).map_err(|e| ConfigError {
+            kind: ConfigErrorKind::Parse,
+            message: format!("invalid routing policy: {}", e),
+        })?;
+        for (i, rule) in policy.rules.iter().enumerate() {
+            if rule.name.is_empty() || rule.results.is_empty() {
+                return Err(ConfigError {
+                    kind: ConfigErrorKind::Invalid,
+                    message: format!("rules[{}] must have a name and at least one result", i),
+                });
+            }
+        }
+        Ok(policy)
+    }
+}
+
+/// Route `verdict` for `attack` through `policy`.
+///
+/// Returns a decision for every matching rule, in policy order, or the
+/// policy default when none match (or nothing without a default). The
+/// effective severity is the highest severity override among the
+/// indicators that matched, falling back to the attack's severity; a rule
+/// with `min_severity` never matches an attack without severity.
+pub fn route(
+    verdict: &AttackVerdict,
+    attack: &Attack,
+    policy: &RoutingPolicy,
+) -> Vec<RoutingDecision> {
+    let severity = effective_severity(verdict, attack);
+    let category = attack
+        .classification
+        .as_ref()
+        .and_then(|c| c.category.as_ref());
+    let impacts = attack.impact.as_deref().unwrap_or_default();
+
+    let mut decisions: Vec<RoutingDecision> = policy
+        .rules
+        .iter()
+        .filter(|rule| rule.results.contains(&verdict.result))
+        .filter(|rule| {
+            rule.min_severity
+                .as_ref()
+                .is_none_or(|min| severity.as_ref().is_some_and(|s| s >= min))
+        })
+        .filter(|rule| {
+            rule.categories.is_empty() || category.is_some_and(|c| rule.categories.contains(c))
+        })
+        .filter(|rule| rule.impacts.is_empty() || impacts.iter().any(|i| rule.impacts.contains(i)))
+        .map(|rule| RoutingDecision {
+            rule: Some(rule.name.clone()),
+            action: rule.action,
+            destinations: rule.destinations.clone(),
+            severity: severity.clone(),
+        })
+        .collect();
+    if decisions.is_empty()
+        && let Some(action) = policy.default
+    {
+        decisions.push(RoutingDecision {
+            rule: None,
+            action,
+            destinations: policy.default_destinations.clone(),
+            severity,
+        });
+    }
+    decisions
+}
+
+fn effective_severity(verdict: &AttackVerdict, attack: &Attack) -> Option<SeverityLevel> {
+    let matched: HashSet<&str> = verdict
+        .indicator_verdicts
+        .iter()
+        .filter(|v| v.result == IndicatorResult::Matched)
+        .map(|v| v.indicator_id.as_str())
+        .collect();
+    let indicator_severity = attack
+        .indicators
+        .iter()
+        .flatten()
+        .filter(|ind| ind.id.as_deref().is_some_and(|id| matched.contains(id)))
+        .filter_map(|ind| ind.severity.clone())
+        .max();
+    indicator_severity.or_else(|| {
+        attack.severity.as_ref().map(|s| match s {
+            Severity::Scalar(level) | Severity::Object { level, .. } => level.clone(),
+        })
+    })
+}
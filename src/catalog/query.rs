@@ -0,0 +1,499 @@
+//! A search language over catalog entries.
+//!
+//! ```text
+//! protocol:mcp AND severity>=high AND tag:exfiltration
+//! (status:draft OR status:experimental) NOT tlp>green
+//! id:OATF-04* "tool poisoning"
+//! ```
+//!
+//! A query combines filters with `AND`, `OR`, and `NOT` (upper case) and
+//! parentheses; `AND` binds tighter than `OR`, and adjacent filters are
+//! joined by `AND`. A filter is `field:value`; `severity`, `tlp`, and
+//! `version` also take `=`, `<`, `<=`, `>`, and `>=`. Values with spaces or
+//! parentheses are quoted, and a bare word or quoted string searches the
+//! attack's ID, name, and description. Matching ignores case, and `*` in an
+//! `id`, `protocol`, `mode`, `surface`, `tag`, or `technique` value matches
+//! any text.
+//!
+//! | Field | Matches |
+//! |-------|---------|
+//! | `id` | `attack.id` |
+//! | `text` | the ID, name, or description containing the value |
+//! | `protocol` | any protocol of the execution modes or indicators |
+//! | `mode` | any mode of the execution, its actors, or phases |
+//! | `surface` | any indicator surface |
+//! | `tag` | any classification tag |
+//! | `technique` | any framework mapping ID (e.g. `T1059`) |
+//! | `category` | the classification category |
+//! | `impact` | any impact |
+//! | `status` | the lifecycle status |
+//! | `severity` | the severity level, ordered `informational` to `critical` |
+//! | `tlp` | the `distribution` marking, ordered `clear` to `red` |
+//! | `version` | the attack version |
+//!
+//! A filter on a field the attack does not set never matches. Queries
+//! compile to a [`CatalogQuery`], which can also be built directly and run
+//! with [`Catalog::filter`].
+
+use super::{Catalog, CatalogEntry};
+use crate::context::ResolvedContext;
+use crate::enums::{Category, Impact, SeverityLevel, Status, Tlp};
+use crate::error::{QueryError, QueryErrorKind};
+use crate::event_registry::extract_protocol;
+use crate::types::{Document, Severity};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::str::FromStr;
+
+/// A parsed catalog query.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CatalogQuery {
+    /// Every query holds.
+    All(Vec<CatalogQuery>),
+    /// At least one query holds.
+    Any(Vec<CatalogQuery>),
+    /// The query does not hold.
+    Not(Box<CatalogQuery>),
+    /// The filter holds.
+    Filter(Filter),
+}
+
+/// One field filter of a [`CatalogQuery`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Filter {
+    /// `attack.id` matches the pattern.
+    Id(String),
+    /// The ID, name, or description contains the text.
+    Text(String),
+    /// A protocol of the document matches the pattern.
+    Protocol(String),
+    /// A mode of the document matches the pattern.
+    Mode(String),
+    /// An indicator surface matches the pattern.
+    Surface(String),
+    /// A classification tag matches the pattern.
+    Tag(String),
+    /// A framework mapping ID matches the pattern.
+    Technique(String),
+    /// The classification category.
+    Category(Category),
+    /// One of the impacts.
+    Impact(Impact),
+    /// The lifecycle status.
+    Status(Status),
+    /// The severity level compared with a level.
+    Severity(Comparison, SeverityLevel),
+    /// The `distribution` marking compared with a marking.
+    Tlp(Comparison, Tlp),
+    /// The attack version compared with a number.
+    Version(Comparison, i64),
+}
+
+/// How an ordered field compares with a filter's value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Comparison {
+    /// `:` or `=`.
+    Eq,
+    /// `<`.
+    Lt,
+    /// `<=`.
+    Le,
+    /// `>`.
+    Gt,
+    /// `>=`.
+    Ge,
+}
+
+impl Comparison {
+    fn holds<T: Ord>(self, actual: &T, expected: &T) -> bool {
+        match self {
+            Comparison::Eq => actual == expected,
+            Comparison::Lt => actual < expected,
+            Comparison::Le => actual <= expected,
+            Comparison::Gt => actual > expected,
+            Comparison::Ge => actual >= expected,
+        }
+    }
+}
+
+impl CatalogQuery {
+    /// Parse a query.
+    ///
+    /// # Errors
+    ///
+    /// Returns `syntax` for empty or malformed queries, `unknown_field` for
+    /// filters on unknown fields, and `invalid_value` for values their field
+    /// does not accept.
+    pub fn parse(input: &str) -> Result<Self, QueryError> {
+        let tokens = tokenize(input)?;
+        let mut pos = 0;
+        let query = parse_any(&tokens, &mut pos)?;
+        match tokens.get(pos) {
+            None => Ok(query),
+            Some(token) => Err(syntax(format!("unexpected {}", token))),
+        }
+    }
+
+    /// Whether `doc` matches the query.
+    pub fn matches(&self, doc: &Document) -> bool {
+        match self {
+            CatalogQuery::All(queries) => queries.iter().all(|q| q.matches(doc)),
+            CatalogQuery::Any(queries) => queries.iter().any(|q| q.matches(doc)),
+            CatalogQuery::Not(query) => !query.matches(doc),
+            CatalogQuery::Filter(filter) => filter.matches(doc),
+        }
+    }
+}
+
+impl FromStr for CatalogQuery {
+    type Err = QueryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl Filter {
+    /// Whether `doc` matches the filter.
+    pub fn matches(&self, doc: &Document) -> bool {
+        let attack = &doc.attack;
+        let classification = attack.classification.as_ref();
+        match self {
+            Filter::Id(pattern) => attack.id.as_deref().is_some_and(|id| glob(pattern, id)),
+            Filter::Text(text) => {
+                let text = text.to_lowercase();
+                [&attack.id, &attack.name, &attack.description]
+                    .into_iter()
+                    .flatten()
+                    .any(|field| field.to_lowercase().contains(&text))
+            }
+            Filter::Protocol(pattern) => {
+                let cx = ResolvedContext::new(doc);
+                let indicators = attack.indicators.as_deref().unwrap_or_default();
+                cx.modes()
+                    .map(extract_protocol)
+                    .chain((0..indicators.len()).filter_map(|i| cx.indicator_protocol(i)))
+                    .any(|protocol| glob(pattern, protocol))
+            }
+            Filter::Mode(pattern) => ResolvedContext::new(doc)
+                .modes()
+                .any(|mode| glob(pattern, mode)),
+            Filter::Surface(pattern) => attack
+                .indicators
+                .iter()
+                .flatten()
+                .any(|indicator| glob(pattern, &indicator.surface)),
+            Filter::Tag(pattern) => classification
+                .and_then(|c| c.tags.as_ref())
+                .is_some_and(|tags| tags.iter().any(|tag| glob(pattern, tag))),
+            Filter::Technique(pattern) => classification
+                .and_then(|c| c.mappings.as_ref())
+                .is_some_and(|mappings| mappings.iter().any(|m| glob(pattern, &m.id))),
+            Filter::Category(category) => {
+                classification.and_then(|c| c.category.as_ref()) == Some(category)
+            }
+            Filter::Impact(impact) => attack.impact.iter().flatten().any(|i| i == impact),
+            Filter::Status(status) => attack.status.as_ref() == Some(status),
+            Filter::Severity(comparison, level) => {
+                attack
+                    .severity
+                    .as_ref()
+                    .is_some_and(|severity| match severity {
+                        Severity::Scalar(actual) | Severity::Object { level: actual, .. } => {
+                            comparison.holds(actual, level)
+                        }
+                    })
+            }
+            Filter::Tlp(comparison, marking) => attack
+                .distribution
+                .is_some_and(|actual| comparison.holds(&actual, marking)),
+            Filter::Version(comparison, version) => attack
+                .version
+                .is_some_and(|actual| comparison.holds(&actual, version)),
+        }
+    }
+}
+
+impl Catalog {
+    /// Entries matching `query`, in catalog order. See
+    /// [`CatalogQuery`] for the syntax.
+    ///
+    /// ```rust
+    /// # let mut catalog = oatf::catalog::Catalog::new();
+    /// let high_mcp = catalog.query("protocol:mcp AND severity>=high").unwrap();
+    /// # assert!(high_mcp.is_empty());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`QueryError`] if the query cannot be parsed.
+    pub fn query(&self, query: &str) -> Result<Vec<&CatalogEntry>, QueryError> {
+        let query = CatalogQuery::parse(query)?;
+        Ok(self
+            .entries
+            .iter()
+            .filter(|e| query.matches(&e.document))
+            .collect())
+    }
+
+    /// Entries matching a parsed or constructed query, in catalog order.
+    pub fn filter<'a>(
+        &'a self,
+        query: &'a CatalogQuery,
+    ) -> impl Iterator<Item = &'a CatalogEntry> + 'a {
+        self.entries
+            .iter()
+            .filter(move |e| query.matches(&e.document))
+    }
+}
+
+/// Case-insensitive match of `text` against `pattern`, where `*` matches
+/// any text.
+fn glob(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    let text = text.to_lowercase();
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else {
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+// ─── Parsing ────────────────────────────────────────────────────────────────
+
+#[derive(Debug)]
+enum Token<'a> {
+    Open,
+    Close,
+    Word(&'a str),
+    Quoted(String),
+    Filter(&'a str, Comparison, String),
+}
+
+impl std::fmt::Display for Token<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Open => write!(f, "'('"),
+            Token::Close => write!(f, "')'"),
+            Token::Word(word) => write!(f, "'{}'", word),
+            Token::Quoted(text) => write!(f, "\"{}\"", text),
+            Token::Filter(field, _, value) => write!(f, "filter '{}' on '{}'", field, value),
+        }
+    }
+}
+
+fn syntax(message: String) -> QueryError {
+    QueryError {
+        kind: QueryErrorKind::Syntax,
+        message,
+    }
+}
+
+fn invalid(message: String) -> QueryError {
+    QueryError {
+        kind: QueryErrorKind::InvalidValue,
+        message,
+    }
+}
+
+fn is_delimiter(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '(' | ')' | '"')
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token<'_>>, QueryError> {
+    let mut tokens = Vec::new();
+    let mut rest = input;
+    while let Some(c) = rest.chars().next() {
+        if c.is_whitespace() {
+            rest = &rest[c.len_utf8()..];
+        } else if c == '(' || c == ')' {
+            tokens.push(if c == '(' { Token::Open } else { Token::Close });
+            rest = &rest[1..];
+        } else if c == '"' {
+            let (text, after) = quoted(rest)?;
+            tokens.push(Token::Quoted(text));
+            rest = after;
+        } else {
+            let end = rest
+                .find(|c: char| is_delimiter(c) || matches!(c, ':' | '=' | '<' | '>'))
+                .unwrap_or(rest.len());
+            let word = &rest[..end];
+            rest = &rest[end..];
+            let op = [
+                (">=", Comparison::Ge),
+                ("<=", Comparison::Le),
+                (":", Comparison::Eq),
+                ("=", Comparison::Eq),
+                ("<", Comparison::Lt),
+                (">", Comparison::Gt),
+            ]
+            .into_iter()
+            .find(|(symbol, _)| rest.starts_with(symbol));
+            let Some((symbol, comparison)) = op else {
+                tokens.push(Token::Word(word));
+                continue;
+            };
+            if word.is_empty() {
+                return Err(syntax(format!("expected a field name before '{}'", symbol)));
+            }
+            rest = &rest[symbol.len()..];
+            let value = if rest.starts_with('"') {
+                let (text, after) = quoted(rest)?;
+                rest = after;
+                text
+            } else {
+                let end = rest.find(is_delimiter).unwrap_or(rest.len());
+                let value = &rest[..end];
+                rest = &rest[end..];
+                value.to_string()
+            };
+            if value.is_empty() {
+                return Err(syntax(format!(
+                    "expected a value after '{}{}'",
+                    word, symbol
+                )));
+            }
+            tokens.push(Token::Filter(word, comparison, value));
+        }
+    }
+    if tokens.is_empty() {
+        return Err(syntax("empty query".to_string()));
+    }
+    Ok(tokens)
+}
+
+/// The quoted string at the start of `input` and the input after it. A
+/// backslash escapes the next character.
+fn quoted(input: &str) -> Result<(String, &str), QueryError> {
+    let mut text = String::new();
+    let mut chars = input.char_indices().skip(1);
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((text, &input[i + 1..])),
+            '\\' => match chars.next() {
+                Some((_, escaped)) => text.push(escaped),
+                None => break,
+            },
+            _ => text.push(c),
+        }
+    }
+    Err(syntax("unterminated quoted string".to_string()))
+}
+
+fn parse_any(tokens: &[Token<'_>], pos: &mut usize) -> Result<CatalogQuery, QueryError> {
+    let mut queries = vec![parse_all(tokens, pos)?];
+    while matches!(tokens.get(*pos), Some(Token::Word("OR"))) {
+        *pos += 1;
+        queries.push(parse_all(tokens, pos)?);
+    }
+    Ok(if queries.len() == 1 {
+        queries.remove(0)
+    } else {
+        CatalogQuery::Any(queries)
+    })
+}
+
+fn parse_all(tokens: &[Token<'_>], pos: &mut usize) -> Result<CatalogQuery, QueryError> {
+    let mut queries = vec![parse_unary(tokens, pos)?];
+    loop {
+        match tokens.get(*pos) {
+            None | Some(Token::Close) | Some(Token::Word("OR")) => break,
+            Some(Token::Word("AND")) => *pos += 1,
+            Some(_) => {}
+        }
+        queries.push(parse_unary(tokens, pos)?);
+    }
+    Ok(if queries.len() == 1 {
+        queries.remove(0)
+    } else {
+        CatalogQuery::All(queries)
+    })
+}
+
+fn parse_unary(tokens: &[Token<'_>], pos: &mut usize) -> Result<CatalogQuery, QueryError> {
+    let Some(token) = tokens.get(*pos) else {
+        let message = match pos.checked_sub(1).and_then(|i| tokens.get(i)) {
+            Some(previous) => format!("expected a filter after {}", previous),
+            None => "expected a filter".to_string(),
+        };
+        return Err(syntax(message));
+    };
+    *pos += 1;
+    match token {
+        Token::Word("NOT") => Ok(CatalogQuery::Not(Box::new(parse_unary(tokens, pos)?))),
+        Token::Open => {
+            let query = parse_any(tokens, pos)?;
+            match tokens.get(*pos) {
+                Some(Token::Close) => {
+                    *pos += 1;
+                    Ok(query)
+                }
+                _ => Err(syntax("unbalanced '('".to_string())),
+            }
+        }
+        Token::Word(word @ ("AND" | "OR")) => {
+            Err(syntax(format!("expected a filter before '{}'", word)))
+        }
+        Token::Close => Err(syntax("unbalanced ')'".to_string())),
+        Token::Word(word) => Ok(CatalogQuery::Filter(Filter::Text(word.to_string()))),
+        Token::Quoted(text) => Ok(CatalogQuery::Filter(Filter::Text(text.clone()))),
+        Token::Filter(field, comparison, value) => {
+            filter(field, *comparison, value).map(CatalogQuery::Filter)
+        }
+    }
+}
+
+fn filter(field: &str, comparison: Comparison, value: &str) -> Result<Filter, QueryError> {
+    let field = field.to_ascii_lowercase();
+    let ordered = matches!(field.as_str(), "severity" | "tlp" | "version");
+    if comparison != Comparison::Eq && !ordered {
+        return Err(invalid(format!(
+            "'{}' can only be compared with ':' or '='",
+            field
+        )));
+    }
+    let text = value.to_string();
+    Ok(match field.as_str() {
+        "id" => Filter::Id(text),
+        "text" => Filter::Text(text),
+        "protocol" => Filter::Protocol(text),
+        "mode" => Filter::Mode(text),
+        "surface" => Filter::Surface(text),
+        "tag" => Filter::Tag(text),
+        "technique" => Filter::Technique(text),
+        "category" => Filter::Category(variant(&field, value)?),
+        "impact" => Filter::Impact(variant(&field, value)?),
+        "status" => Filter::Status(variant(&field, value)?),
+        "severity" => Filter::Severity(comparison, variant(&field, value)?),
+        "tlp" => Filter::Tlp(comparison, variant(&field, value)?),
+        "version" => Filter::Version(
+            comparison,
+            value
+                .parse()
+                .map_err(|_| invalid(format!("version must be an integer, got '{}'", value)))?,
+        ),
+        _ => {
+            return Err(QueryError {
+                kind: QueryErrorKind::UnknownField,
+                message: format!("unknown field '{}'", field),
+            });
+        }
+    })
+}
+
+/// The enum variant named `value`, ignoring case.
+fn variant<T: DeserializeOwned>(field: &str, value: &str) -> Result<T, QueryError> {
+    serde_json::from_value(Value::String(value.to_ascii_lowercase()))
+        .map_err(|_| invalid(format!("unknown {} '{}'", field, value)))
+}
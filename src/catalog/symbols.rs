@@ -0,0 +1,344 @@
+//! Cross-document index of named symbols.
+
+use super::{Catalog, CatalogEntry};
+use crate::context::ResolvedContext;
+use crate::types::Document;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::LazyLock;
+
+/// `{{name}}` and `{{actor.name}}` template references; a leading backslash
+/// escapes the reference.
+static TEMPLATE_REF_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(\\)?\{\{([a-zA-Z_][a-zA-Z0-9_]*)(?:\.([a-zA-Z_][a-zA-Z0-9_]*))?\}\}").unwrap()
+});
+
+/// The kind of a named symbol.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SymbolKind {
+    /// An attack ID: defined by `attack.id`, referenced by `supersedes` and
+    /// `superseded_by`.
+    Attack,
+    /// An indicator ID, defined by `indicators[].id`.
+    Indicator,
+    /// An actor name: defined by `execution.actors[].name`, referenced by
+    /// `{{actor.extractor}}` templates.
+    Actor,
+    /// An extractor name: defined by a phase's `extractors[].name`,
+    /// referenced by `{{extractor}}` and `{{actor.extractor}}` templates.
+    Extractor,
+    /// A capture name: defined by an indicator's `capture[].name`,
+    /// referenced by `correlation.key`.
+    Capture,
+    /// A framework technique ID (e.g., `T1059`), referenced by
+    /// `classification.mappings[].id`.
+    Technique,
+}
+
+/// Whether an occurrence defines a symbol or refers to it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SymbolRole {
+    /// The symbol is declared here.
+    Definition,
+    /// The symbol is used here.
+    Reference,
+}
+
+/// One place a symbol appears.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Occurrence {
+    /// Source identity of the document.
+    pub source: String,
+    /// Document path of the occurrence (e.g.,
+    /// `attack.execution.actors[0].phases[1].state.tools[0].description`).
+    pub path: String,
+    /// Definition or reference.
+    pub role: SymbolRole,
+}
+
+/// An index of the attack IDs, indicator IDs, actor, extractor, and capture
+/// names, and framework techniques of a set of documents, for
+/// find-references and catalog queries.
+///
+/// Documents are indexed by source identity: [`update`](Self::update)
+/// replaces what one source contributes and [`remove`](Self::remove) drops
+/// it, so an index can follow edits without rebuilding. Occurrences are
+/// listed in the order their sources were indexed, then in document order.
+///
+/// Names are not scoped: every extractor called `token` shares one entry,
+/// whichever actor defines it.
+#[derive(Clone, Debug, Default)]
+pub struct SymbolIndex {
+    symbols: BTreeMap<(SymbolKind, String), Vec<Occurrence>>,
+    sources: HashMap<String, Vec<(SymbolKind, String)>>,
+}
+
+impl SymbolIndex {
+    /// Create an empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index every entry of `entries`.
+    pub fn from_entries(entries: &[CatalogEntry]) -> Self {
+        let mut index = Self::new();
+        for entry in entries {
+            index.update(&entry.source, &entry.document);
+        }
+        index
+    }
+
+    /// Index `doc` under `source`, replacing anything indexed for `source`
+    /// before.
+    pub fn update(&mut self, source: &str, doc: &Document) {
+        self.remove(source);
+        let mut found = Vec::new();
+        collect(doc, &mut |kind, name, path, role| {
+            found.push((kind, name.to_string(), path, role))
+        });
+        let mut keys = Vec::new();
+        for (kind, name, path, role) in found {
+            let key = (kind, name);
+            if !keys.contains(&key) {
+                keys.push(key.clone());
+            }
+            self.symbols.entry(key).or_default().push(Occurrence {
+                source: source.to_string(),
+                path,
+                role,
+            });
+        }
+        self.sources.insert(source.to_string(), keys);
+    }
+
+    /// Drop everything indexed for `source`. Returns whether it was indexed.
+    pub fn remove(&mut self, source: &str) -> bool {
+        let Some(keys) = self.sources.remove(source) else {
+            return false;
+        };
+        for key in keys {
+            if let Some(occurrences) = self.symbols.get_mut(&key) {
+                occurrences.retain(|o| o.source != source);
+                if occurrences.is_empty() {
+                    self.symbols.remove(&key);
+                }
+            }
+        }
+        true
+    }
+
+    /// Every occurrence of the symbol `name` of `kind`.
+    pub fn occurrences(&self, kind: SymbolKind, name: &str) -> &[Occurrence] {
+        self.symbols
+            .get(&(kind, name.to_string()))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Where the symbol is defined.
+    pub fn definitions(&self, kind: SymbolKind, name: &str) -> impl Iterator<Item = &Occurrence> {
+        self.occurrences(kind, name)
+            .iter()
+            .filter(|o| o.role == SymbolRole::Definition)
+    }
+
+    /// Where the symbol is used.
+    pub fn references(&self, kind: SymbolKind, name: &str) -> impl Iterator<Item = &Occurrence> {
+        self.occurrences(kind, name)
+            .iter()
+            .filter(|o| o.role == SymbolRole::Reference)
+    }
+
+    /// Sources of the documents in which the symbol appears, each once.
+    pub fn sources(&self, kind: SymbolKind, name: &str) -> Vec<&str> {
+        let mut sources: Vec<&str> = Vec::new();
+        for occurrence in self.occurrences(kind, name) {
+            if !sources.contains(&occurrence.source.as_str()) {
+                sources.push(&occurrence.source);
+            }
+        }
+        sources
+    }
+
+    /// Names of every indexed symbol of `kind`, in sorted order.
+    pub fn names(&self, kind: SymbolKind) -> impl Iterator<Item = &str> {
+        self.symbols
+            .keys()
+            .filter(move |(k, _)| *k == kind)
+            .map(|(_, name)| name.as_str())
+    }
+
+    /// Number of indexed sources.
+    pub fn len(&self) -> usize {
+        self.sources.len()
+    }
+
+    /// Whether no source is indexed.
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+}
+
+impl Catalog {
+    /// Build a [`SymbolIndex`] over every entry.
+    pub fn symbols(&self) -> SymbolIndex {
+        SymbolIndex::from_entries(self.entries())
+    }
+}
+
+/// Report every symbol occurrence in `doc`, in document order.
+fn collect(doc: &Document, found: &mut impl FnMut(SymbolKind, &str, String, SymbolRole)) {
+    use SymbolKind::*;
+    use SymbolRole::*;
+
+    let attack = &doc.attack;
+    if let Some(id) = &attack.id {
+        found(Attack, id, "attack.id".to_string(), Definition);
+    }
+    let mappings = attack
+        .classification
+        .as_ref()
+        .and_then(|c| c.mappings.as_ref());
+    for (i, mapping) in mappings.into_iter().flatten().enumerate() {
+        found(
+            Technique,
+            &mapping.id,
+            format!("attack.classification.mappings[{}].id", i),
+            Reference,
+        );
+    }
+
+    let cx = ResolvedContext::new(doc);
+    let explicit_actors = attack.execution.actors.is_some();
+    for actor in cx.actors() {
+        if explicit_actors {
+            found(
+                Actor,
+                actor.name,
+                format!("{}.name", actor.path_prefix),
+                Definition,
+            );
+        }
+        for (pi, phase) in actor.phases.iter().enumerate() {
+            let phase_path = format!("{}.phases[{}]", actor.path_prefix, pi);
+            if let Some(state) = &phase.state {
+                templates(state, &format!("{}.state", phase_path), found);
+            }
+            for (ei, extractor) in phase.extractors.iter().flatten().enumerate() {
+                found(
+                    Extractor,
+                    &extractor.name,
+                    format!("{}.extractors[{}].name", phase_path, ei),
+                    Definition,
+                );
+            }
+            let actions = [
+                ("on_enter", &phase.on_enter),
+                ("on_timeout", &phase.on_timeout),
+            ];
+            for (field, actions) in actions {
+                for (ai, action) in actions.iter().flatten().enumerate() {
+                    if let Ok(value) = serde_json::to_value(action) {
+                        templates(&value, &format!("{}.{}[{}]", phase_path, field, ai), found);
+                    }
+                }
+            }
+        }
+    }
+    // The single-state form has no phases to resolve.
+    if let Some(state) = &attack.execution.state {
+        templates(state, "attack.execution.state", found);
+    }
+
+    for (i, indicator) in attack.indicators.iter().flatten().enumerate() {
+        if let Some(id) = &indicator.id {
+            found(
+                Indicator,
+                id,
+                format!("attack.indicators[{}].id", i),
+                Definition,
+            );
+        }
+        for (ci, capture) in indicator.capture.iter().flatten().enumerate() {
+            found(
+                Capture,
+                &capture.name,
+                format!("attack.indicators[{}].capture[{}].name", i, ci),
+                Definition,
+            );
+        }
+    }
+    let keys = attack.correlation.as_ref().and_then(|c| c.key.as_ref());
+    for (i, key) in keys.into_iter().flatten().enumerate() {
+        found(
+            Capture,
+            key,
+            format!("attack.correlation.key[{}]", i),
+            Reference,
+        );
+    }
+
+    for (i, id) in attack.supersedes.iter().flatten().enumerate() {
+        found(Attack, id, format!("attack.supersedes[{}]", i), Reference);
+    }
+    if let Some(id) = &attack.superseded_by {
+        found(Attack, id, "attack.superseded_by".to_string(), Reference);
+    }
+}
+
+/// Report the extractor and actor references in the template strings of
+/// `value`.
+fn templates(
+    value: &Value,
+    path: &str,
+    found: &mut impl FnMut(SymbolKind, &str, String, SymbolRole),
+) {
+    match value {
+        Value::String(s) => {
+            for cap in TEMPLATE_REF_RE.captures_iter(s) {
+                if cap.get(1).is_some() {
+                    continue;
+                }
+                let first = &cap[2];
+                match cap.get(3) {
+                    None => found(
+                        SymbolKind::Extractor,
+                        first,
+                        path.to_string(),
+                        SymbolRole::Reference,
+                    ),
+                    Some(_) if first == "request" || first == "response" => {}
+                    Some(name) => {
+                        found(
+                            SymbolKind::Actor,
+                            first,
+                            path.to_string(),
+                            SymbolRole::Reference,
+                        );
+                        found(
+                            SymbolKind::Extractor,
+                            name.as_str(),
+                            path.to_string(),
+                            SymbolRole::Reference,
+                        );
+                    }
+                }
+            }
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                templates(item, &format!("{}[{}]", path, i), found);
+            }
+        }
+        Value::Object(map) => {
+            for (key, item) in map {
+                templates(item, &format!("{}.{}", path, key), found);
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,112 @@
+//! Time sources for trigger timeouts and execution.
+//!
+//! Everything time-dependent in the SDK — `after` timeouts
+//! ([`evaluate_trigger_at`](crate::primitives::evaluate_trigger_at)), pacing
+//! and `max_runtime` in [`McpClient`](crate::engine::McpClient), and the
+//! [`ActorSystem`](crate::engine::actors::ActorSystem) scheduler — reads the
+//! time from a [`Clock`]. Production code uses [`SystemClock`]; tests use
+//! [`MockClock`], whose time only moves when told to, so timeouts fire at
+//! exactly the expected step. A recorded trace can be replayed with its
+//! original timings by setting a [`MockClock`] to each message's recorded
+//! offset (e.g. [`Exchange::at`](crate::engine::Exchange::at)) before
+//! delivering it.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for super::SystemClock {}
+    impl Sealed for super::MockClock {}
+}
+
+/// A monotonic time source.
+///
+/// This trait is sealed: [`SystemClock`] and [`MockClock`] are its only
+/// implementations, so the engines can rely on their semantics and the trait
+/// can gain methods without a breaking change.
+pub trait Clock: sealed::Sealed + Send + Sync {
+    /// Time elapsed since the clock's origin.
+    fn now(&self) -> Duration;
+
+    /// Let `duration` pass. Real clocks block the thread; mock clocks
+    /// advance their time instead.
+    fn sleep(&self, duration: Duration);
+}
+
+/// The monotonic system clock, measured from its creation.
+#[derive(Clone, Copy, Debug)]
+pub struct SystemClock {
+    origin: Instant,
+}
+
+impl SystemClock {
+    /// A clock whose origin is now.
+    pub fn new() -> Self {
+        SystemClock {
+            origin: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.origin.elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+}
+
+/// A manually driven clock for deterministic tests and trace replay.
+///
+/// Clones share the same time, so a test can keep a handle while a runtime
+/// owns another. [`Clock::sleep`] advances the time without blocking.
+#[derive(Clone, Debug, Default)]
+pub struct MockClock {
+    now: Arc<Mutex<Duration>>,
+}
+
+impl MockClock {
+    /// A clock starting at `start`.
+    pub fn new(start: Duration) -> Self {
+        MockClock {
+            now: Arc::new(Mutex::new(start)),
+        }
+    }
+
+    /// Move the time forward by `by`.
+    pub fn advance(&self, by: Duration) {
+        *self.lock() += by;
+    }
+
+    /// Set the time to `to`, for example a recorded message offset. Moving
+    /// backwards is allowed but makes elapsed times saturate at zero.
+    pub fn set(&self, to: Duration) {
+        *self.lock() = to;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Duration> {
+        // The guarded value is a plain Duration; a poisoned lock still holds
+        // a valid time.
+        self.now.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Duration {
+        *self.lock()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+    }
+}
@@ -0,0 +1,148 @@
+//! Modes, protocols, and actors of a document, resolved once.
+//!
+//! A document's execution can take three forms (single-phase, multi-phase,
+//! multi-actor), and a phase's mode falls back to its actor's and then to
+//! `execution.mode`. [`ResolvedContext`] applies these fallbacks in one place
+//! so validation rules, normalization, and evaluation agree on them.
+
+use crate::event_registry::extract_protocol;
+use crate::types::*;
+
+/// The actors, effective modes, and indicator protocols of a document.
+#[derive(Clone, Debug)]
+pub struct ResolvedContext<'a> {
+    doc: &'a Document,
+    actors: Vec<ResolvedActor<'a>>,
+    default_protocol: Option<&'a str>,
+    indicator_protocols: Vec<Option<&'a str>>,
+}
+
+/// An actor with the effective mode of each of its phases.
+///
+/// A multi-phase document has a single actor named `default`; a single-phase
+/// document has none.
+#[derive(Clone, Debug)]
+pub struct ResolvedActor<'a> {
+    /// Actor name.
+    pub name: &'a str,
+    /// The actor's mode (`execution.mode` for the multi-phase form).
+    pub mode: Option<&'a str>,
+    /// The actor's phases.
+    pub phases: &'a [Phase],
+    /// Effective mode of each phase: its own, else the actor's, else
+    /// `execution.mode`.
+    pub phase_modes: Vec<Option<&'a str>>,
+    /// Path of the actor (e.g. `attack.execution.actors[0]`), to which
+    /// `.phases[N]` is appended.
+    pub path_prefix: String,
+}
+
+impl<'a> ResolvedActor<'a> {
+    /// Effective mode of the phase at `index`.
+    pub fn phase_mode(&self, index: usize) -> Option<&'a str> {
+        self.phase_modes.get(index).copied().flatten()
+    }
+}
+
+impl<'a> ResolvedContext<'a> {
+    /// Resolve `doc`.
+    pub fn new(doc: &'a Document) -> Self {
+        let exec = &doc.attack.execution;
+        let actor = |name, mode: Option<&'a str>, phases: &'a [Phase], path_prefix| ResolvedActor {
+            name,
+            mode,
+            phases,
+            phase_modes: phases
+                .iter()
+                .map(|p| p.mode.as_deref().or(mode).or(exec.mode.as_deref()))
+                .collect(),
+            path_prefix,
+        };
+        let actors = if let Some(actors) = &exec.actors {
+            actors
+                .iter()
+                .enumerate()
+                .map(|(i, a)| {
+                    actor(
+                        a.name.as_str(),
+                        Some(a.mode.as_str()),
+                        &a.phases,
+                        format!("attack.execution.actors[{}]", i),
+                    )
+                })
+                .collect()
+        } else if let Some(phases) = &exec.phases {
+            vec![actor(
+                "default",
+                exec.mode.as_deref(),
+                phases,
+                "attack.execution".to_string(),
+            )]
+        } else {
+            Vec::new()
+        };
+
+        // An indicator's protocol defaults to that of `execution.mode`, or of
+        // the only actor.
+        let default_protocol = match (exec.mode.as_deref(), exec.actors.as_deref()) {
+            (Some(mode), _) => Some(extract_protocol(mode)),
+            (None, Some([only])) => Some(extract_protocol(&only.mode)),
+            _ => None,
+        };
+        let indicator_protocols = doc
+            .attack
+            .indicators
+            .iter()
+            .flatten()
+            .map(|ind| ind.protocol.as_deref().or(default_protocol))
+            .collect();
+
+        ResolvedContext {
+            doc,
+            actors,
+            default_protocol,
+            indicator_protocols,
+        }
+    }
+
+    /// The resolved document.
+    pub fn document(&self) -> &'a Document {
+        self.doc
+    }
+
+    /// The document's actors, in declaration order.
+    pub fn actors(&self) -> &[ResolvedActor<'a>] {
+        &self.actors
+    }
+
+    /// The actor named `name`.
+    pub fn actor(&self, name: &str) -> Option<&ResolvedActor<'a>> {
+        self.actors.iter().find(|a| a.name == name)
+    }
+
+    /// The protocol indicators without a `protocol` default to, if the
+    /// execution has a single protocol-defining mode.
+    pub fn default_protocol(&self) -> Option<&'a str> {
+        self.default_protocol
+    }
+
+    /// Effective protocol of the indicator at `index`.
+    pub fn indicator_protocol(&self, index: usize) -> Option<&'a str> {
+        self.indicator_protocols.get(index).copied().flatten()
+    }
+
+    /// Every mode the document declares: `execution.mode`, actor modes, and
+    /// phase modes.
+    pub fn modes(&self) -> impl Iterator<Item = &'a str> + '_ {
+        let exec = &self.doc.attack.execution;
+        exec.mode
+            .as_deref()
+            .into_iter()
+            .chain(exec.actors.iter().flatten().map(|a| a.mode.as_str()))
+            .chain(
+                self.actors
+                    .iter()
+                    .flat_map(|a| a.phases.iter().filter_map(|p| p.mode.as_deref())),
+            )
+    }
+}
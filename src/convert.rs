@@ -0,0 +1,563 @@
+//! Conversion between declarative match predicates and CEL expressions.
+//!
+//! Authors move between the two forms by hand: a predicate becomes an
+//! expression once it needs OR logic, and a simple expression reads better
+//! as a predicate. [`predicate_to_cel`] always succeeds;
+//! [`cel_to_predicate`] handles the conjunctions [`predicate_to_cel`]
+//! produces (and equivalent hand-written ones) and returns `None` for
+//! anything else.
+//!
+//! Paths are rooted at `message`, matching the CEL context built by
+//! [`crate::evaluate::evaluate_expression`].
+
+use crate::types::{MatchCondition, MatchEntry, MatchPredicate, PatchMatch};
+use indexmap::IndexMap;
+use serde_json::Value;
+
+const CEL_RESERVED: &[&str] = &[
+    "true",
+    "false",
+    "null",
+    "in",
+    "as",
+    "break",
+    "const",
+    "continue",
+    "else",
+    "for",
+    "function",
+    "if",
+    "import",
+    "let",
+    "loop",
+    "package",
+    "namespace",
+    "return",
+    "var",
+    "void",
+    "while",
+];
+
+/// Render `predicate` as an equivalent CEL expression over `message`.
+///
+/// Entries are joined with `&&` in the predicate's order. An empty
+/// predicate becomes `true`.
+pub fn predicate_to_cel(predicate: &MatchPredicate) -> String {
+    let mut terms = Vec::new();
+    for (path, entry) in predicate {
+        let segments: Vec<&str> = path.split('.').collect();
+        let target = cel_path(&segments);
+        match entry {
+            MatchEntry::Scalar(expected) => {
+                terms.push(format!("{} == {}", target, cel_literal(expected)))
+            }
+            MatchEntry::Condition(cond) => {
+                condition_terms(cond, &cel_has(&segments), &target, &mut terms)
+            }
+        }
+    }
+    if terms.is_empty() {
+        "true".to_string()
+    } else {
+        terms.join(" && ")
+    }
+}
+
+/// Terms for `cond` on `target`, whose presence test is `has`.
+fn condition_terms(cond: &MatchCondition, has: &str, target: &str, terms: &mut Vec<String>) {
+    match cond.exists {
+        Some(true) => terms.push(has.to_string()),
+        Some(false) => terms.push(negate(has.to_string())),
+        None => {}
+    }
+    let string_ops = [
+        ("contains", &cond.contains),
+        ("startsWith", &cond.starts_with),
+        ("endsWith", &cond.ends_with),
+        ("matches", &cond.regex),
+    ];
+    for (method, operand) in string_ops {
+        if let Some(s) = operand {
+            terms.push(format!(
+                "{}.{}({})",
+                target,
+                method,
+                cel_literal(&Value::String(s.clone()))
+            ));
+        }
+    }
+    if let Some(items) = &cond.any_of {
+        terms.push(format!(
+            "{} in {}",
+            target,
+            cel_literal(&Value::Array(items.clone()))
+        ));
+    }
+    let numeric_ops = [
+        (">", cond.gt),
+        ("<", cond.lt),
+        (">=", cond.gte),
+        ("<=", cond.lte),
+    ];
+    for (op, operand) in numeric_ops {
+        if let Some(n) = operand {
+            terms.push(format!("double({}) {} {}", target, op, cel_number(n)));
+        }
+    }
+    if let Some(patch) = &cond.json_patch {
+        terms.push(patch_term(patch, target));
+    }
+}
+
+/// `target.exists(op, ...)` over the operations of a patch list. A single
+/// operation object is not a list, so the expression does not match one.
+fn patch_term(patch: &PatchMatch, target: &str) -> String {
+    let mut terms = Vec::new();
+    if let Some(op) = patch.op {
+        terms.push(format!(
+            "op.op == {}",
+            cel_literal(&Value::String(op.as_str().to_string()))
+        ));
+    }
+    if let Some(path) = &patch.path {
+        terms.push(format!(
+            "op.path.matches({})",
+            cel_literal(&Value::String(pointer_regex(path)))
+        ));
+    }
+    match patch.value.as_deref() {
+        Some(MatchEntry::Scalar(expected)) => {
+            terms.push(format!("op.value == {}", cel_literal(expected)))
+        }
+        Some(MatchEntry::Condition(cond)) => {
+            condition_terms(cond, "has(op.value)", "op.value", &mut terms)
+        }
+        None => {}
+    }
+    let body = if terms.is_empty() {
+        "true".to_string()
+    } else {
+        terms.join(" && ")
+    };
+    format!("{}.exists(op, {})", target, body)
+}
+
+/// An anchored regex for a `json_patch` path pattern.
+fn pointer_regex(pattern: &str) -> String {
+    let segments: Vec<&str> = pattern.split('/').collect();
+    let mut out = "^".to_string();
+    for (i, segment) in segments.iter().enumerate() {
+        let last = i + 1 == segments.len();
+        match *segment {
+            "**" if last && i == 0 => out.push_str(".*"),
+            "**" if last => out.push_str("(/.*)?"),
+            "*" => {
+                if i > 0 {
+                    out.push('/');
+                }
+                out.push_str("[^/]*");
+            }
+            literal => {
+                if i > 0 {
+                    out.push('/');
+                }
+                out.push_str(&regex::escape(literal));
+            }
+        }
+    }
+    out.push('$');
+    out
+}
+
+fn is_cel_ident(segment: &str) -> bool {
+    let mut chars = segment.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !CEL_RESERVED.contains(&segment)
+}
+
+fn cel_path(segments: &[&str]) -> String {
+    let mut out = "message".to_string();
+    for segment in segments {
+        if is_cel_ident(segment) {
+            out.push('.');
+            out.push_str(segment);
+        } else {
+            out.push('[');
+            out.push_str(&cel_literal(&Value::String(segment.to_string())));
+            out.push(']');
+        }
+    }
+    out
+}
+
+/// `has(parent.field)`, or `"field" in parent` when the last segment is not
+/// an identifier.
+fn cel_has(segments: &[&str]) -> String {
+    let (last, parent) = segments.split_last().expect("paths have a segment");
+    if is_cel_ident(last) {
+        format!("has({})", cel_path(segments))
+    } else {
+        format!(
+            "{} in {}",
+            cel_literal(&Value::String(last.to_string())),
+            cel_path(parent)
+        )
+    }
+}
+
+/// JSON literals are valid CEL literals for strings, numbers, booleans,
+/// `null`, lists, and maps.
+fn cel_literal(value: &Value) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| "null".to_string())
+}
+
+fn negate(term: String) -> String {
+    if term.starts_with("has(") {
+        format!("!{}", term)
+    } else {
+        format!("!({})", term)
+    }
+}
+
+/// CEL has no mixed int/double comparisons, so numeric operands are always
+/// written as doubles and compared against `double(...)`.
+fn cel_number(n: f64) -> String {
+    if n.fract() == 0.0 && n.is_finite() {
+        format!("{:.1}", n)
+    } else {
+        format!("{}", n)
+    }
+}
+
+/// Convert a conjunction of simple comparisons over `message` back into a
+/// match predicate.
+///
+/// Supported terms are `==`, `<`, `<=`, `>`, `>=` (on a path or
+/// `double(path)`), `in [...]`, the `contains`/`startsWith`/`endsWith`/
+/// `matches` methods with a string argument, and presence tests (`has(...)`,
+/// `"key" in ...`) and their negations, with double-quoted string
+/// literals. Returns `None` for anything else, including `||`, negated
+/// comparisons, and conflicting terms on one path.
+pub fn cel_to_predicate(expression: &str) -> Option<MatchPredicate> {
+    let mut parser = Parser {
+        input: expression.trim(),
+        pos: 0,
+    };
+    let mut terms: IndexMap<String, Term> = IndexMap::new();
+    if parser.input == "true" {
+        return Some(MatchPredicate::new());
+    }
+    loop {
+        let (path, op) = parser.term()?;
+        let term = terms.entry(path).or_insert(Term::Empty);
+        term.add(op)?;
+        parser.skip_ws();
+        if parser.at_end() {
+            break;
+        }
+        parser.expect("&&")?;
+    }
+    terms
+        .into_iter()
+        .map(|(path, term)| term.into_entry().map(|entry| (path, entry)))
+        .collect()
+}
+
+enum Op {
+    Eq(Value),
+    Contains(String),
+    StartsWith(String),
+    EndsWith(String),
+    Matches(String),
+    AnyOf(Vec<Value>),
+    Gt(f64),
+    Lt(f64),
+    Gte(f64),
+    Lte(f64),
+    Exists(bool),
+}
+
+enum Term {
+    Empty,
+    Scalar(Value),
+    Condition(MatchCondition),
+}
+
+impl Term {
+    fn add(&mut self, op: Op) -> Option<()> {
+        if let Op::Eq(value) = op {
+            return match self {
+                Term::Empty => {
+                    *self = Term::Scalar(value);
+                    Some(())
+                }
+                _ => None,
+            };
+        }
+        if let Term::Empty = self {
+            *self = Term::Condition(empty_condition());
+        }
+        let Term::Condition(cond) = self else {
+            return None;
+        };
+        let slot_filled = match op {
+            Op::Contains(s) => cond.contains.replace(s).is_some(),
+            Op::StartsWith(s) => cond.starts_with.replace(s).is_some(),
+            Op::EndsWith(s) => cond.ends_with.replace(s).is_some(),
+            Op::Matches(s) => cond.regex.replace(s).is_some(),
+            Op::AnyOf(items) => cond.any_of.replace(items).is_some(),
+            Op::Gt(n) => cond.gt.replace(n).is_some(),
+            Op::Lt(n) => cond.lt.replace(n).is_some(),
+            Op::Gte(n) => cond.gte.replace(n).is_some(),
+            Op::Lte(n) => cond.lte.replace(n).is_some(),
+            Op::Exists(b) => cond.exists.replace(b).is_some(),
+            Op::Eq(_) => unreachable!("handled above"),
+        };
+        (!slot_filled).then_some(())
+    }
+
+    fn into_entry(self) -> Option<MatchEntry> {
+        match self {
+            Term::Empty => None,
+            Term::Scalar(value) => Some(MatchEntry::Scalar(value)),
+            Term::Condition(cond) => Some(MatchEntry::Condition(cond)),
+        }
+    }
+}
+
+fn empty_condition() -> MatchCondition {
+    MatchCondition {
+        contains: None,
+        starts_with: None,
+        ends_with: None,
+        regex: None,
+        any_of: None,
+        gt: None,
+        lt: None,
+        gte: None,
+        lte: None,
+        exists: None,
+        json_patch: None,
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn rest(&self) -> &str {
+        &self.input[self.pos..]
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos >= self.input.len()
+    }
+
+    fn skip_ws(&mut self) {
+        let trimmed = self.rest().trim_start();
+        self.pos = self.input.len() - trimmed.len();
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_ws();
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Option<()> {
+        self.eat(token).then_some(())
+    }
+
+    fn term(&mut self) -> Option<(String, Op)> {
+        if self.eat("!(") {
+            let (path, Op::Exists(true)) = self.term()? else {
+                return None;
+            };
+            self.expect(")")?;
+            return Some((path, Op::Exists(false)));
+        }
+        if self.eat("double(") {
+            let path = self.path().filter(|p| !p.is_empty())?;
+            self.expect(")")?;
+            let op = self.numeric_op()?;
+            return Some((path, op));
+        }
+        for (prefix, exists) in [("!has(", false), ("has(", true)] {
+            if self.eat(prefix) {
+                let path = self.path().filter(|p| !p.is_empty())?;
+                self.expect(")")?;
+                return Some((path, Op::Exists(exists)));
+            }
+        }
+        if self.rest().starts_with('"') {
+            // "field" in parent
+            let Value::String(field) = self.literal()? else {
+                return None;
+            };
+            self.expect("in")?;
+            let parent = self.path()?;
+            let path = if parent.is_empty() {
+                field
+            } else {
+                format!("{}.{}", parent, field)
+            };
+            return Some((path, Op::Exists(true)));
+        }
+
+        let (path, method) = self.path_and_method()?;
+        if let Some(method) = method {
+            let Value::String(arg) = self.literal()? else {
+                return None;
+            };
+            self.expect(")")?;
+            let op = match method.as_str() {
+                "contains" => Op::Contains(arg),
+                "startsWith" => Op::StartsWith(arg),
+                "endsWith" => Op::EndsWith(arg),
+                "matches" => Op::Matches(arg),
+                _ => return None,
+            };
+            return Some((path, op));
+        }
+
+        let op = if self.eat("==") {
+            Op::Eq(self.literal()?)
+        } else if self.eat("in ") {
+            let Value::Array(items) = self.literal()? else {
+                return None;
+            };
+            Op::AnyOf(items)
+        } else {
+            self.numeric_op()?
+        };
+        Some((path, op))
+    }
+
+    fn numeric_op(&mut self) -> Option<Op> {
+        let op = if self.eat(">=") {
+            Op::Gte(self.literal()?.as_f64()?)
+        } else if self.eat("<=") {
+            Op::Lte(self.literal()?.as_f64()?)
+        } else if self.eat(">") {
+            Op::Gt(self.literal()?.as_f64()?)
+        } else if self.eat("<") {
+            Op::Lt(self.literal()?.as_f64()?)
+        } else {
+            return None;
+        };
+        Some(op)
+    }
+
+    /// A `message`-rooted path, with no trailing method call. The root
+    /// itself is the empty path.
+    fn path(&mut self) -> Option<String> {
+        match self.path_and_method()? {
+            (path, None) => Some(path),
+            _ => None,
+        }
+    }
+
+    /// A `message`-rooted path, and the name of a method called on it (with
+    /// the opening parenthesis consumed).
+    fn path_and_method(&mut self) -> Option<(String, Option<String>)> {
+        self.skip_ws();
+        let root = self.ident()?;
+        if root != "message" {
+            return None;
+        }
+        let mut segments: Vec<String> = Vec::new();
+        loop {
+            if self.rest().starts_with('.') {
+                self.pos += 1;
+                let segment = self.ident()?;
+                if self.rest().starts_with('(') {
+                    self.pos += 1;
+                    return Some((segments.join("."), Some(segment)));
+                }
+                segments.push(segment);
+            } else if self.rest().starts_with('[') {
+                self.pos += 1;
+                let Value::String(segment) = self.literal()? else {
+                    return None;
+                };
+                self.expect("]")?;
+                segments.push(segment);
+            } else {
+                break;
+            }
+        }
+        if segments.iter().any(|s| s.contains('.')) {
+            return None;
+        }
+        Some((segments.join("."), None))
+    }
+
+    fn ident(&mut self) -> Option<String> {
+        let len = self
+            .rest()
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(self.rest().len());
+        if len == 0 {
+            return None;
+        }
+        let ident = self.rest()[..len].to_string();
+        self.pos += len;
+        Some(ident)
+    }
+
+    /// A JSON-compatible literal: double-quoted string, number, boolean,
+    /// `null`, list, or map.
+    fn literal(&mut self) -> Option<Value> {
+        self.skip_ws();
+        let rest = self.rest();
+        let mut depth = 0usize;
+        let mut in_string = false;
+        let mut escaped = false;
+        let mut end = rest.len();
+        for (i, c) in rest.char_indices() {
+            if in_string {
+                match c {
+                    _ if escaped => escaped = false,
+                    '\\' => escaped = true,
+                    '"' => {
+                        in_string = false;
+                        if depth == 0 {
+                            end = i + 1;
+                            break;
+                        }
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+            match c {
+                '"' => in_string = true,
+                '[' | '{' => depth += 1,
+                ']' | '}' if depth > 0 => {
+                    depth -= 1;
+                    if depth == 0 {
+                        end = i + 1;
+                        break;
+                    }
+                }
+                _ if depth == 0 && !(c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+')) => {
+                    end = i;
+                    break;
+                }
+                _ => {}
+            }
+        }
+        let value = serde_json::from_str(&rest[..end]).ok()?;
+        self.pos += end;
+        Some(value)
+    }
+}
@@ -0,0 +1,144 @@
+//! Organization-wide defaults for attack documents.
+//!
+//! Teams that author many attacks repeat the same metadata in each one: the
+//! author, the grace period, the correlation logic, the confidence of their
+//! severity ratings. An [`OrgDefaults`] profile holds these once and
+//! [`load_with_defaults`](crate::load_with_defaults) merges it into each
+//! document before validation.
+//!
+//! Precedence:
+//!
+//! - A value written in the document always wins; defaults only fill fields
+//!   the document leaves out.
+//! - `correlation.logic` is filled only for documents with indicators whose
+//!   correlation sets neither `logic` nor `stages` (stages take precedence
+//!   over logic).
+//! - `severity.confidence` is filled only for documents with a severity that
+//!   has no confidence; the level always comes from the document.
+//!
+//! Fields filled from defaults are reported as
+//! [`FieldProvenance::Inherited`](crate::normalize::FieldProvenance::Inherited)
+//! by [`LoadResult::provenance`](crate::LoadResult::provenance), and
+//! [`LoadResult::serialize_minimal`](crate::LoadResult::serialize_minimal)
+//! leaves them out.
+
+use crate::enums::CorrelationLogic;
+use crate::error::ParseError;
+use crate::types::{Correlation, Document, Severity};
+use serde::{Deserialize, Serialize};
+
+/// Defaults merged into every document an organization loads.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OrgDefaults {
+    /// Default `attack.author`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Default `attack.grace_period` (e.g. `"30s"`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grace_period: Option<String>,
+    /// Default `attack.correlation`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correlation: Option<CorrelationDefaults>,
+    /// Default `attack.severity` fields.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub severity: Option<SeverityDefaults>,
+}
+
+/// Correlation fields of [`OrgDefaults`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CorrelationDefaults {
+    /// Default `attack.correlation.logic`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logic: Option<CorrelationLogic>,
+}
+
+/// Severity fields of [`OrgDefaults`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SeverityDefaults {
+    /// Default `attack.severity.confidence` (0–100).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<i64>,
+}
+
+impl OrgDefaults {
+    /// Parse a YAML defaults profile.
+    ///
+    /// Values are not checked here: invalid ones are reported by validation
+    /// at the document path they were merged into.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use oatf::defaults::OrgDefaults;
+    ///
+    /// let defaults = OrgDefaults::from_yaml("author: Red Team\ngrace_period: 30s\n")?;
+    /// assert_eq!(defaults.author.as_deref(), Some("Red Team"));
+    /// # Ok::<(), oatf::ParseError>(())
+    /// ```
+    pub fn from_yaml(input: &str) -> Result<Self, ParseError> {
+        crate::parse::fragment_from_yaml(input, "defaults profile")
+    }
+
+    /// Fill the fields `doc` leaves out, following the module's precedence
+    /// rules. Returns the paths of the fields filled in, in document order.
+    pub fn apply(&self, doc: &mut Document) -> Vec<String> {
+        let attack = &mut doc.attack;
+        let mut filled = Vec::new();
+
+        if attack.author.is_none() && self.author.is_some() {
+            attack.author = self.author.clone();
+            filled.push("attack.author".to_string());
+        }
+        if attack.grace_period.is_none() && self.grace_period.is_some() {
+            attack.grace_period = self.grace_period.clone();
+            filled.push("attack.grace_period".to_string());
+        }
+
+        if let Some(confidence) = self.severity.as_ref().and_then(|s| s.confidence) {
+            match &mut attack.severity {
+                Some(Severity::Scalar(level)) => {
+                    attack.severity = Some(Severity::Object {
+                        level: level.clone(),
+                        confidence: Some(confidence),
+                    });
+                    filled.push("attack.severity.confidence".to_string());
+                }
+                Some(Severity::Object {
+                    confidence: slot @ None,
+                    ..
+                }) => {
+                    *slot = Some(confidence);
+                    filled.push("attack.severity.confidence".to_string());
+                }
+                _ => {}
+            }
+        }
+
+        let has_indicators = attack.indicators.as_ref().is_some_and(|i| !i.is_empty());
+        if let Some(logic) = self.correlation.as_ref().and_then(|c| c.logic.clone())
+            && has_indicators
+        {
+            match &mut attack.correlation {
+                None => {
+                    attack.correlation = Some(Correlation {
+                        logic: Some(logic),
+                        stages: None,
+                        key: None,
+                    });
+                    filled.push("attack.correlation".to_string());
+                }
+                Some(correlation)
+                    if correlation.logic.is_none() && correlation.stages.is_none() =>
+                {
+                    correlation.logic = Some(logic);
+                    filled.push("attack.correlation.logic".to_string());
+                }
+                _ => {}
+            }
+        }
+        filled
+    }
+}
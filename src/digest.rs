@@ -0,0 +1,11 @@
+//! Content digests shared by the `audit`, `bundle`, `distribution`, and `feed`
+//! features.
+
+use sha2::{Digest, Sha256};
+
+/// `sha256:<hex>` digest of `content`.
+pub fn sha256_digest(content: &[u8]) -> String {
+    let hash = Sha256::digest(content);
+    let hex: String = hash.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("sha256:{}", hex)
+}
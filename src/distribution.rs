@@ -0,0 +1,373 @@
+//! Distributing OATF bundles as OCI artifacts.
+//!
+//! A bundle is pushed ORAS-style: an OCI image manifest with
+//! `artifactType` [`BUNDLE_ARTIFACT_TYPE`], the empty config blob, and one
+//! layer per file. Documents use [`DOCUMENT_MEDIA_TYPE`] and detached
+//! signatures [`SIGNATURE_MEDIA_TYPE`]; each layer's file name is kept in the
+//! `org.opencontainers.image.title` annotation.
+//!
+//! [`push_with_policy`] refuses to push documents marked above a TLP
+//! [`SharingPolicy`].
+//!
+//! Registry I/O goes through a caller-supplied [`RegistryTransport`], so the
+//! crate carries no HTTP client or credential handling. [`pull`] verifies the
+//! manifest digest (when pulling by digest) and every blob's digest and size
+//! before returning anything.
+//!
+//! Requires the `distribution` feature.
+
+use crate::bundle::{PathEntry, load_entries};
+use crate::error::{DistributionError, DistributionErrorKind};
+use crate::sharing::SharingPolicy;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub use crate::digest::sha256_digest;
+
+/// `artifactType` of an OATF bundle manifest.
+pub const BUNDLE_ARTIFACT_TYPE: &str = "application/vnd.oatf.bundle.v1";
+/// Media type of an OATF document layer.
+pub const DOCUMENT_MEDIA_TYPE: &str = "application/vnd.oatf.document.v1+yaml";
+/// Media type of a detached signature layer.
+pub const SIGNATURE_MEDIA_TYPE: &str = "application/vnd.oatf.signature.v1";
+/// Media type of the OCI image manifest.
+pub const MANIFEST_MEDIA_TYPE: &str = "application/vnd.oci.image.manifest.v1+json";
+/// Media type of the empty config blob used by artifacts.
+pub const EMPTY_CONFIG_MEDIA_TYPE: &str = "application/vnd.oci.empty.v1+json";
+
+const EMPTY_CONFIG: &[u8] = b"{}";
+const TITLE_ANNOTATION: &str = "org.opencontainers.image.title";
+
+/// Registry operations needed to push and pull artifacts, following the OCI
+/// distribution API. Implementations own the HTTP client, authentication,
+/// and retries, and report failures with kind `transport`.
+pub trait RegistryTransport {
+    /// Upload a blob under its digest (`sha256:<hex>`).
+    fn put_blob(
+        &self,
+        repository: &str,
+        digest: &str,
+        content: &[u8],
+    ) -> Result<(), DistributionError>;
+
+    /// Download the blob with the given digest.
+    fn get_blob(&self, repository: &str, digest: &str) -> Result<Vec<u8>, DistributionError>;
+
+    /// Upload a manifest under a tag or digest.
+    fn put_manifest(
+        &self,
+        repository: &str,
+        reference: &str,
+        media_type: &str,
+        content: &[u8],
+    ) -> Result<(), DistributionError>;
+
+    /// Download the manifest stored under a tag or digest.
+    fn get_manifest(&self, repository: &str, reference: &str)
+    -> Result<Vec<u8>, DistributionError>;
+}
+
+/// A parsed artifact reference: `registry/repository[:tag][@digest]`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reference {
+    /// Registry host, with port if any (e.g. `ghcr.io`, `localhost:5000`).
+    pub registry: String,
+    /// Repository path within the registry.
+    pub repository: String,
+    /// Tag, when given. Defaults to `latest` if neither tag nor digest is set.
+    pub tag: Option<String>,
+    /// Manifest digest, when given.
+    pub digest: Option<String>,
+}
+
+impl Reference {
+    /// Parse a reference such as `ghcr.io/acme/attacks:v1` or
+    /// `localhost:5000/attacks@sha256:…`. The registry host is required.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DistributionError` of kind `invalid_reference` if the
+    /// reference has no registry host, an empty repository, or a malformed
+    /// digest.
+    pub fn parse(input: &str) -> Result<Reference, DistributionError> {
+        let invalid = |message: &str| DistributionError {
+            kind: DistributionErrorKind::InvalidReference,
+            message: format!("invalid reference '{}': {}", input, message),
+        };
+
+        let (name, digest) = match input.split_once('@') {
+            Some((name, digest)) => {
+                if !is_sha256_digest(digest) {
+                    return Err(invalid("digest must be sha256:<64 hex digits>"));
+                }
+                (name, Some(digest.to_string()))
+            }
+            None => (input, None),
+        };
+        let (registry, path) = name
+            .split_once('/')
+            .filter(|(host, _)| host.contains('.') || host.contains(':') || *host == "localhost")
+            .ok_or_else(|| invalid("missing registry host"))?;
+        let (repository, tag) = match path.rsplit_once(':') {
+            Some((repository, tag)) if !tag.contains('/') => (repository, Some(tag.to_string())),
+            _ => (path, None),
+        };
+        if repository.is_empty() || tag.as_deref() == Some("") {
+            return Err(invalid("empty repository or tag"));
+        }
+        Ok(Reference {
+            registry: registry.to_string(),
+            repository: repository.to_string(),
+            tag,
+            digest,
+        })
+    }
+
+    /// The tag or digest used to address the manifest; a digest wins.
+    pub fn manifest_reference(&self) -> &str {
+        self.digest
+            .as_deref()
+            .or(self.tag.as_deref())
+            .unwrap_or("latest")
+    }
+}
+
+/// A file carried in a bundle artifact.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArtifactFile {
+    /// File name, stored as the layer title.
+    pub name: String,
+    /// Layer media type.
+    pub media_type: String,
+    /// File contents.
+    pub content: Vec<u8>,
+}
+
+impl ArtifactFile {
+    /// A document layer.
+    pub fn document(name: impl Into<String>, content: impl Into<Vec<u8>>) -> Self {
+        ArtifactFile {
+            name: name.into(),
+            media_type: DOCUMENT_MEDIA_TYPE.to_string(),
+            content: content.into(),
+        }
+    }
+
+    /// A detached signature layer.
+    pub fn signature(name: impl Into<String>, content: impl Into<Vec<u8>>) -> Self {
+        ArtifactFile {
+            name: name.into(),
+            media_type: SIGNATURE_MEDIA_TYPE.to_string(),
+            content: content.into(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Manifest {
+    schema_version: u32,
+    media_type: String,
+    #[serde(default)]
+    artifact_type: Option<String>,
+    config: Descriptor,
+    layers: Vec<Descriptor>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Descriptor {
+    media_type: String,
+    digest: String,
+    size: u64,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    annotations: BTreeMap<String, String>,
+}
+
+impl Descriptor {
+    fn of(media_type: &str, content: &[u8]) -> Descriptor {
+        Descriptor {
+            media_type: media_type.to_string(),
+            digest: sha256_digest(content),
+            size: content.len() as u64,
+            annotations: BTreeMap::new(),
+        }
+    }
+}
+
+fn is_sha256_digest(digest: &str) -> bool {
+    digest.strip_prefix("sha256:").is_some_and(|hex| {
+        hex.len() == 64
+            && hex
+                .bytes()
+                .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+    })
+}
+
+/// Push `files` as a bundle artifact and tag it with the reference's tag
+/// (or `latest`). Returns the manifest digest, which can be used to pull the
+/// exact artifact later.
+///
+/// # Errors
+///
+/// Returns transport errors unchanged, or `invalid_reference` if the
+/// reference pins a digest (a pushed manifest's digest is not known in
+/// advance).
+pub fn push(
+    transport: &dyn RegistryTransport,
+    reference: &Reference,
+    files: &[ArtifactFile],
+) -> Result<String, DistributionError> {
+    if reference.digest.is_some() {
+        return Err(DistributionError {
+            kind: DistributionErrorKind::InvalidReference,
+            message: "cannot push to a digest reference; use a tag".to_string(),
+        });
+    }
+    let repository = &reference.repository;
+
+    let config = Descriptor::of(EMPTY_CONFIG_MEDIA_TYPE, EMPTY_CONFIG);
+    transport.put_blob(repository, &config.digest, EMPTY_CONFIG)?;
+
+    let mut layers = Vec::with_capacity(files.len());
+    for file in files {
+        let mut layer = Descriptor::of(&file.media_type, &file.content);
+        layer
+            .annotations
+            .insert(TITLE_ANNOTATION.to_string(), file.name.clone());
+        transport.put_blob(repository, &layer.digest, &file.content)?;
+        layers.push(layer);
+    }
+
+    let manifest = Manifest {
+        schema_version: 2,
+        media_type: MANIFEST_MEDIA_TYPE.to_string(),
+        artifact_type: Some(BUNDLE_ARTIFACT_TYPE.to_string()),
+        config,
+        layers,
+    };
+    let bytes = serde_json::to_vec(&manifest).map_err(|e| DistributionError {
+        kind: DistributionErrorKind::InvalidManifest,
+        message: format!("failed to encode manifest: {}", e),
+    })?;
+    transport.put_manifest(
+        repository,
+        reference.manifest_reference(),
+        MANIFEST_MEDIA_TYPE,
+        &bytes,
+    )?;
+    Ok(sha256_digest(&bytes))
+}
+
+/// [`push`], refusing the whole artifact if `policy` does not permit one of
+/// its documents. Signatures are not checked. Nothing is uploaded when a
+/// document is refused.
+///
+/// # Errors
+///
+/// Returns `restricted` if a document is refused, otherwise as [`push`].
+pub fn push_with_policy(
+    transport: &dyn RegistryTransport,
+    reference: &Reference,
+    files: &[ArtifactFile],
+    policy: SharingPolicy,
+) -> Result<String, DistributionError> {
+    for file in files {
+        if file.media_type != DOCUMENT_MEDIA_TYPE {
+            continue;
+        }
+        policy
+            .check_source(&file.content)
+            .map_err(|e| DistributionError {
+                kind: DistributionErrorKind::Restricted,
+                message: format!("{}: {}", file.name, e),
+            })?;
+    }
+    push(transport, reference, files)
+}
+
+/// Pull a bundle artifact, verifying every blob against the manifest.
+///
+/// # Errors
+///
+/// Returns `invalid_manifest` if the manifest is malformed or not an OATF
+/// bundle, `digest_mismatch` if the manifest (when pulled by digest) or any
+/// blob does not match its digest or size, and transport errors unchanged.
+pub fn pull(
+    transport: &dyn RegistryTransport,
+    reference: &Reference,
+) -> Result<Vec<ArtifactFile>, DistributionError> {
+    let repository = &reference.repository;
+    let bytes = transport.get_manifest(repository, reference.manifest_reference())?;
+    if let Some(expected) = &reference.digest {
+        verify(expected, None, &bytes, "manifest")?;
+    }
+
+    let invalid = |message: String| DistributionError {
+        kind: DistributionErrorKind::InvalidManifest,
+        message,
+    };
+    let manifest: Manifest = serde_json::from_slice(&bytes)
+        .map_err(|e| invalid(format!("malformed manifest: {}", e)))?;
+    if manifest.artifact_type.as_deref() != Some(BUNDLE_ARTIFACT_TYPE) {
+        return Err(invalid(format!(
+            "artifact type {:?} is not {}",
+            manifest.artifact_type, BUNDLE_ARTIFACT_TYPE
+        )));
+    }
+
+    let mut files = Vec::with_capacity(manifest.layers.len());
+    for (i, layer) in manifest.layers.iter().enumerate() {
+        if !is_sha256_digest(&layer.digest) {
+            return Err(invalid(format!("layer {} has an unsupported digest", i)));
+        }
+        let content = transport.get_blob(repository, &layer.digest)?;
+        let name = layer
+            .annotations
+            .get(TITLE_ANNOTATION)
+            .cloned()
+            .unwrap_or_else(|| format!("layer-{}", i));
+        verify(&layer.digest, Some(layer.size), &content, &name)?;
+        files.push(ArtifactFile {
+            name,
+            media_type: layer.media_type.clone(),
+            content,
+        });
+    }
+    Ok(files)
+}
+
+fn verify(
+    expected: &str,
+    size: Option<u64>,
+    content: &[u8],
+    what: &str,
+) -> Result<(), DistributionError> {
+    let actual = sha256_digest(content);
+    let size_ok = size.is_none_or(|s| s == content.len() as u64);
+    if actual != expected || !size_ok {
+        return Err(DistributionError {
+            kind: DistributionErrorKind::DigestMismatch,
+            message: format!(
+                "{} does not match {} ({} bytes, {})",
+                what,
+                expected,
+                content.len(),
+                actual
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Load the document layers of pulled `files` via [`crate::load`], one
+/// [`PathEntry`] per document with source `reference!name`. Signature and
+/// other layers are skipped.
+pub fn load_documents(reference: &str, files: &[ArtifactFile]) -> Vec<PathEntry> {
+    let members: Vec<_> = files
+        .iter()
+        .filter(|f| f.media_type == DOCUMENT_MEDIA_TYPE)
+        .map(|f| (format!("{}!{}", reference, f.name), &f.content))
+        .collect();
+    load_entries(&members)
+}
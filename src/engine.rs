@@ -0,0 +1,850 @@
+//! Execution runtimes that drive a peer according to a document's phases.
+//!
+//! Runtimes are transport-agnostic: the caller supplies a [`Transport`] that
+//! delivers JSON-RPC requests and notifications (stdio, HTTP, in-memory, …),
+//! and the runtime decides what to send based on the actor's phases.
+//! Documents must be normalized (multi-actor form) before execution.
+
+use crate::clock::{Clock, SystemClock};
+use crate::enums::{AdvanceReason, ExtractorSource, OriginRole};
+use crate::error::{Diagnostic, EngineError, EngineErrorKind};
+use crate::primitives::{
+    evaluate_extractor, evaluate_requires, evaluate_trigger_at, interpolate_predicate,
+    interpolate_value, parse_duration, trigger_deadline,
+};
+use crate::surface::is_known_protocol_version;
+use crate::types::*;
+use crate::validate::is_valid_simple_dot_path;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+pub mod actors;
+pub mod journal;
+pub mod trace;
+
+/// MCP protocol revision requested during `initialize` when the actor's mode
+/// does not pin one (e.g., `mcp_client@2025-03-26`).
+pub const DEFAULT_MCP_PROTOCOL_VERSION: &str = "2025-06-18";
+
+// ─── Transport ──────────────────────────────────────────────────────────────
+
+/// Delivers JSON-RPC messages to the peer under test.
+pub trait Transport {
+    /// Send a request and return the `result` member of the reply.
+    ///
+    /// A JSON-RPC error reply should be reported as an [`EngineError`] with
+    /// kind [`EngineErrorKind::Protocol`].
+    fn request(&mut self, method: &str, params: Value) -> Result<Value, EngineError>;
+
+    /// Send a notification (no reply expected).
+    fn notify(&mut self, method: &str, params: Value) -> Result<(), EngineError>;
+
+    /// Host (or URL) of the peer, checked against `attack.safety.forbidden_hosts`
+    /// before anything is sent. Transports without a network peer return `None`.
+    fn target(&self) -> Option<String> {
+        None
+    }
+}
+
+// ─── Execution report ───────────────────────────────────────────────────────
+
+/// One request sent by the runtime and the reply it received.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Exchange {
+    /// JSON-RPC method (e.g., `"tools/call"`).
+    pub method: String,
+    /// Request parameters after template interpolation.
+    pub params: Value,
+    /// The `result` member of the reply.
+    pub response: Value,
+    /// When the reply arrived, as time since the run started.
+    pub at: Duration,
+}
+
+/// What happened while a single phase was active.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PhaseRecord {
+    /// Phase name, if the phase has one.
+    pub name: Option<String>,
+    /// When the phase was entered, as time since the run started.
+    pub entered_at: Duration,
+    /// Requests issued during the phase, in order.
+    pub exchanges: Vec<Exchange>,
+    /// Notifications sent by `on_enter` and `on_timeout` actions, as
+    /// `(method, params)`.
+    pub notifications: Vec<(String, Value)>,
+    /// Messages emitted by `log` actions.
+    pub logs: Vec<String>,
+    /// Why the phase's trigger advanced, or `None` for the terminal phase or a
+    /// phase that stalled.
+    pub advance: Option<AdvanceReason>,
+}
+
+/// Outcome of running an actor to completion.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExecutionReport {
+    /// Name of the actor that was executed.
+    pub actor: String,
+    /// The `result` of the `initialize` handshake.
+    pub server_info: Value,
+    /// Phases entered, in order.
+    pub phases: Vec<PhaseRecord>,
+    /// Indices of phases skipped because their `requires` did not hold.
+    pub skipped: Vec<usize>,
+    /// Extractor values captured during the run, by extractor name.
+    pub extracted: HashMap<String, String>,
+    /// True if the terminal phase was reached, or every remaining phase was
+    /// skipped.
+    pub completed: bool,
+    /// Template interpolation warnings (W-004).
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+// ─── Recording ──────────────────────────────────────────────────────────────
+
+/// Build a draft `mcp_server` document that replays the server observed in a
+/// client run.
+///
+/// Each recorded phase with at least one exchange becomes a server phase
+/// whose state exposes the tools and prompts that were called. Every
+/// `tools/call` reply becomes a `responses[]` entry on its tool (`content`,
+/// plus `isError` when set) and every `prompts/get` reply one on its prompt
+/// (`messages`), keyed by a `when` predicate matching the recorded arguments
+/// literally; arguments whose names are not a single path segment are left
+/// out of the predicate. Tool and prompt descriptions are copied from recorded
+/// `tools/list` and `prompts/list` replies when present. Other methods are
+/// not replayed.
+///
+/// A phase that advanced gets a trigger on the event of its last exchange.
+/// When a request argument carried a value the client had extracted earlier,
+/// the server phase receiving it gets a `json_path` extractor of the same name
+/// on that argument, so later responses can refer to it as `{{name}}`.
+///
+/// The draft has `status: draft`, a mode pinned to the negotiated protocol
+/// version when it is known, and no indicators.
+pub fn record(report: &ExecutionReport) -> Document {
+    let mut listed: HashMap<(&str, String), Value> = HashMap::new();
+    for exchange in report.phases.iter().flat_map(|p| &p.exchanges) {
+        for (method, key) in [("tools/list", "tools"), ("prompts/list", "prompts")] {
+            if exchange.method != method {
+                continue;
+            }
+            for item in exchange
+                .response
+                .get(key)
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+            {
+                if let Some(name) = item.get("name").and_then(|n| n.as_str()) {
+                    listed.insert((key, name.to_string()), item.clone());
+                }
+            }
+        }
+    }
+
+    let recorded: Vec<&PhaseRecord> = report
+        .phases
+        .iter()
+        .filter(|p| !p.exchanges.is_empty())
+        .collect();
+    let mut captured: HashMap<&str, &str> = HashMap::new();
+    let mut phases = Vec::new();
+
+    for (index, record) in recorded.iter().enumerate() {
+        let mut state = serde_json::Map::new();
+        let mut extractors = Vec::new();
+
+        for exchange in &record.exchanges {
+            let (key, reply) = match exchange.method.as_str() {
+                "tools/call" => {
+                    let mut reply = serde_json::Map::new();
+                    reply.insert(
+                        "content".to_string(),
+                        exchange
+                            .response
+                            .get("content")
+                            .cloned()
+                            .unwrap_or(json!([])),
+                    );
+                    if let Some(is_error) = exchange.response.get("isError") {
+                        reply.insert("isError".to_string(), is_error.clone());
+                    }
+                    ("tools", reply)
+                }
+                "prompts/get" => {
+                    let mut reply = serde_json::Map::new();
+                    reply.insert(
+                        "messages".to_string(),
+                        exchange
+                            .response
+                            .get("messages")
+                            .cloned()
+                            .unwrap_or(json!([])),
+                    );
+                    ("prompts", reply)
+                }
+                _ => continue,
+            };
+            let Some(name) = exchange.params.get("name").and_then(|n| n.as_str()) else {
+                continue;
+            };
+
+            let mut when = serde_json::Map::new();
+            for (arg, value) in exchange
+                .params
+                .get("arguments")
+                .and_then(|a| a.as_object())
+                .into_iter()
+                .flatten()
+            {
+                if value.is_object() || value.is_array() {
+                    continue;
+                }
+                // A name that is not a single path segment cannot be keyed.
+                if !arg.is_empty() && !arg.contains('.') && is_valid_simple_dot_path(arg) {
+                    when.insert(format!("arguments.{}", arg), value.clone());
+                }
+                if let Some(text) = value.as_str() {
+                    for (extracted, captured_value) in &report.extracted {
+                        if !captured_value.is_empty()
+                            && text.contains(captured_value.as_str())
+                            && !captured.contains_key(extracted.as_str())
+                        {
+                            captured.insert(extracted, captured_value);
+                            extractors.push(json!({
+                                "name": extracted,
+                                "source": "request",
+                                "type": "json_path",
+                                "selector": format!(
+                                    "$.params.arguments['{}']",
+                                    arg.replace('\\', "\\\\").replace('\'', "\\'")
+                                ),
+                            }));
+                        }
+                    }
+                }
+            }
+
+            let mut entry = serde_json::Map::new();
+            if !when.is_empty() {
+                entry.insert("when".to_string(), Value::Object(when));
+            }
+            entry.extend(reply);
+
+            let items = state
+                .entry(key)
+                .or_insert_with(|| json!([]))
+                .as_array_mut()
+                .expect("state lists are arrays");
+            let position = match items.iter().position(|i| i["name"] == name) {
+                Some(position) => position,
+                None => {
+                    let mut item = json!({"name": name});
+                    if let Some(definition) = listed.get(&(key, name.to_string())) {
+                        for field in ["description", "inputSchema", "arguments"] {
+                            if let Some(v) = definition.get(field) {
+                                item[field] = v.clone();
+                            }
+                        }
+                    }
+                    item["responses"] = json!([]);
+                    items.push(item);
+                    items.len() - 1
+                }
+            };
+            let responses = items[position]["responses"]
+                .as_array_mut()
+                .expect("responses is an array");
+            let entry = Value::Object(entry);
+            if !responses.contains(&entry) {
+                responses.push(entry);
+            }
+        }
+
+        let mut phase = json!({
+            "name": record.name.clone().unwrap_or_else(|| format!("phase-{}", index + 1)),
+            "state": state,
+        });
+        if !extractors.is_empty() {
+            phase["extractors"] = Value::Array(extractors);
+        }
+        if index + 1 < recorded.len()
+            && record.advance.is_some()
+            && let Some(last) = record.exchanges.last()
+        {
+            let event = match last.params.get("name").and_then(|n| n.as_str()) {
+                Some(name) => format!("{}:{}", last.method, name),
+                None => last.method.clone(),
+            };
+            phase["trigger"] = json!({"event": event});
+        }
+        phases.push(phase);
+    }
+
+    let mut mode = "mcp_server".to_string();
+    if let Some(version) = report
+        .server_info
+        .get("protocolVersion")
+        .and_then(|v| v.as_str())
+        && is_known_protocol_version("mcp", version) == Some(true)
+    {
+        mode = format!("mcp_server@{}", version);
+    }
+    let server_name = report
+        .server_info
+        .get("serverInfo")
+        .and_then(|i| i.get("name"))
+        .and_then(|n| n.as_str())
+        .unwrap_or("MCP server");
+
+    let document = json!({
+        "oatf": "0.1",
+        "attack": {
+            "name": format!("Recorded session with {}", server_name),
+            "status": "draft",
+            "execution": {
+                "actors": [{
+                    "name": "recorded_server",
+                    "mode": mode,
+                    "phases": phases,
+                }],
+            },
+        },
+    });
+    serde_json::from_value(document).expect("recorded document matches the document model")
+}
+
+// ─── MCP client runtime ─────────────────────────────────────────────────────
+
+/// Drives a target MCP server as the document's `mcp_client` actor.
+///
+/// A phase whose `requires` does not hold for the values extracted so far is
+/// skipped (see [`ExecutionReport::skipped`]). For each phase it enters, the
+/// runtime:
+///
+/// 1. Executes `on_enter` actions: `send_notification` is sent over the
+///    transport and `log` is recorded; other actions are server-side or
+///    binding-specific and are skipped.
+/// 2. Issues the requests described by the phase's own `state` (inherited
+///    state is not re-sent): every `requests[]` entry (`{method, params}`),
+///    then a `tools/call` for every `tools[]` entry and a `prompts/get` for
+///    every `prompts[]` entry (`{name, arguments}`). Templates in parameters
+///    are interpolated with the values extracted so far.
+/// 3. Applies the phase's extractors to each request and reply, and
+///    evaluates the trigger against each reply. The event type is the request
+///    method, the qualifier is the request's `name` parameter, and the content
+///    is the reply's `result`; templates in the trigger's `match` predicate
+///    are interpolated with the values extracted so far, so a phase can wait
+///    for a value captured earlier. Once the trigger advances, remaining
+///    requests are skipped.
+///
+/// If no reply advances the trigger, the runtime waits out the trigger's
+/// `after` timeout when one is set; otherwise the run stops with
+/// [`ExecutionReport::completed`] set to false. When the trigger advances by
+/// timeout, the phase's `on_timeout` actions are executed like `on_enter`
+/// actions before the next phase begins.
+///
+/// A phase's `pacing` is honored between consecutive messages (notifications
+/// and requests) sent during that phase: the runtime waits `delay` plus a
+/// random amount up to `jitter` before every message after the first.
+///
+/// Limits in `attack.safety` are enforced for the whole run, including the
+/// `initialize` handshake: the run stops with
+/// [`EngineErrorKind::SafetyViolation`] before a message would exceed
+/// `max_messages`, before a wait would exceed `max_runtime`, or before
+/// anything is sent when the [`Transport::target`] or the actor's endpoint
+/// URL names a forbidden host.
+pub struct McpClient<T: Transport> {
+    transport: T,
+    clock: Box<dyn Clock>,
+    sleep: Option<Box<dyn FnMut(Duration)>>,
+    rng: u64,
+    guard: Guard,
+    started: Duration,
+}
+
+impl<T: Transport> McpClient<T> {
+    /// Create a runtime that talks to the target server over `transport`.
+    pub fn new(transport: T) -> Self {
+        let seed = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or_default();
+        McpClient {
+            transport,
+            clock: Box::new(SystemClock::new()),
+            sleep: None,
+            rng: seed | 1,
+            guard: Guard::new(None, Duration::ZERO),
+            started: Duration::ZERO,
+        }
+    }
+
+    /// Replace the time source used for trigger timeouts, pacing, safety
+    /// limits, and report timestamps (defaults to [`SystemClock`]). With a
+    /// [`MockClock`](crate::clock::MockClock), waits advance the mock time
+    /// instead of blocking.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+
+    /// Replace the function used to wait for pacing delays and trigger
+    /// timeouts (defaults to the clock's [`Clock::sleep`]).
+    pub fn with_sleep(mut self, sleep: impl FnMut(Duration) + 'static) -> Self {
+        self.sleep = Some(Box::new(sleep));
+        self
+    }
+
+    /// Seed the pacing jitter generator for reproducible runs.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = seed | 1;
+        self
+    }
+
+    /// Consume the runtime and return its transport.
+    pub fn into_transport(self) -> T {
+        self.transport
+    }
+
+    /// Execute the first `mcp_client` actor of a normalized document.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EngineErrorKind::Configuration`] if the document has no
+    /// `mcp_client` actor or an attachment reference cannot be resolved,
+    /// [`EngineErrorKind::SafetyViolation`] if a safety
+    /// limit is hit, and propagates transport and protocol errors.
+    pub fn run(&mut self, doc: &Document) -> Result<ExecutionReport, EngineError> {
+        let doc = &resolve_attachments(doc)?;
+        let actor = find_actor(doc, "mcp_client")?;
+        let mode = Mode::parse(&actor.mode);
+        let protocol_version = mode
+            .version
+            .as_deref()
+            .unwrap_or(DEFAULT_MCP_PROTOCOL_VERSION);
+
+        self.started = self.clock.now();
+        self.guard = Guard::new(doc.attack.safety.as_ref(), self.started);
+        if let Some(target) = self.transport.target() {
+            self.guard.check_target(&target)?;
+        }
+        check_endpoint(&self.guard, actor)?;
+
+        self.guard.before_send("initialize", self.clock.now())?;
+        let server_info = self.transport.request(
+            "initialize",
+            json!({
+                "protocolVersion": protocol_version,
+                "capabilities": {},
+                "clientInfo": {"name": "oatf", "version": env!("CARGO_PKG_VERSION")},
+            }),
+        )?;
+        self.guard
+            .before_send("notifications/initialized", self.clock.now())?;
+        self.transport
+            .notify("notifications/initialized", json!({}))?;
+
+        let mut report = ExecutionReport {
+            actor: actor.name.clone(),
+            server_info,
+            phases: Vec::new(),
+            skipped: Vec::new(),
+            extracted: HashMap::new(),
+            completed: false,
+            diagnostics: Vec::new(),
+        };
+
+        for (index, phase) in actor.phases.iter().enumerate() {
+            if !evaluate_requires(phase.requires.as_ref(), &report.extracted) {
+                report.skipped.push(index);
+                report.completed = index + 1 == actor.phases.len();
+                continue;
+            }
+            let record = self
+                .run_phase(phase, &mut report)
+                .map_err(|e| with_phase(e, phase))?;
+            let advanced = record.advance.is_some();
+            report.phases.push(record);
+            if phase.trigger.is_none() {
+                report.completed = true;
+                break;
+            }
+            if !advanced {
+                break;
+            }
+        }
+
+        Ok(report)
+    }
+
+    fn run_phase(
+        &mut self,
+        phase: &Phase,
+        report: &mut ExecutionReport,
+    ) -> Result<PhaseRecord, EngineError> {
+        let started = self.clock.now();
+        let mut record = PhaseRecord {
+            name: phase.name.clone(),
+            entered_at: started.saturating_sub(self.started),
+            exchanges: Vec::new(),
+            notifications: Vec::new(),
+            logs: Vec::new(),
+            advance: None,
+        };
+        let mut pacer = Pacer::new(phase.pacing.as_ref());
+
+        self.run_actions(phase.on_enter.as_deref(), &mut pacer, &mut record, report)?;
+
+        let mut trigger_state = TriggerState::default();
+        for (method, params) in client_requests(phase.state.as_ref()) {
+            let (params, diags) = interpolate_value(&params, &report.extracted, None, None);
+            report.diagnostics.extend(diags);
+            self.pace(&mut pacer)?;
+            self.guard.before_send(&method, self.clock.now())?;
+            let response = self.transport.request(&method, params.clone())?;
+
+            let request_message = json!({"method": method, "params": params});
+            for extractor in phase.extractors.iter().flatten() {
+                let captured =
+                    evaluate_extractor(extractor, &request_message, ExtractorSource::Request)
+                        .or_else(|| {
+                            evaluate_extractor(extractor, &response, ExtractorSource::Response)
+                        });
+                if let Some(value) = captured {
+                    report.extracted.insert(extractor.name.clone(), value);
+                }
+            }
+
+            let event = ProtocolEvent {
+                event_type: method.clone(),
+                qualifier: params
+                    .get("name")
+                    .and_then(|n| n.as_str())
+                    .map(str::to_string),
+                content: response.clone(),
+                origin: Some(Origin {
+                    role: OriginRole::Server,
+                    endpoint: None,
+                    trusted: None,
+                }),
+            };
+            record.exchanges.push(Exchange {
+                method,
+                params,
+                response,
+                at: self.clock.now().saturating_sub(self.started),
+            });
+
+            if let Some(trigger) = &phase.trigger {
+                let trigger =
+                    interpolated_trigger(trigger, &report.extracted, &mut report.diagnostics);
+                if let TriggerResult::Advanced { reason } = evaluate_trigger_at(
+                    &trigger,
+                    Some(&event),
+                    started,
+                    self.clock.as_ref(),
+                    &mut trigger_state,
+                    "mcp",
+                ) {
+                    if reason == AdvanceReason::Timeout {
+                        let actions = phase.on_timeout.as_deref();
+                        self.run_actions(actions, &mut pacer, &mut record, report)?;
+                    }
+                    record.advance = Some(reason);
+                    return Ok(record);
+                }
+            }
+        }
+
+        if let Some(deadline) = phase
+            .trigger
+            .as_ref()
+            .and_then(|t| trigger_deadline(t, started))
+        {
+            self.wait(deadline.saturating_sub(self.clock.now()))?;
+            let actions = phase.on_timeout.as_deref();
+            self.run_actions(actions, &mut pacer, &mut record, report)?;
+            record.advance = Some(AdvanceReason::Timeout);
+        }
+
+        Ok(record)
+    }
+
+    /// Execute `on_enter` or `on_timeout` actions: `send_notification` is
+    /// sent over the transport and `log` is recorded.
+    fn run_actions(
+        &mut self,
+        actions: Option<&[Action]>,
+        pacer: &mut Pacer,
+        record: &mut PhaseRecord,
+        report: &mut ExecutionReport,
+    ) -> Result<(), EngineError> {
+        for action in actions.into_iter().flatten() {
+            match action {
+                Action::SendNotification { method, params, .. } => {
+                    let params = params.clone().unwrap_or_else(|| json!({}));
+                    let (params, diags) = interpolate_value(&params, &report.extracted, None, None);
+                    report.diagnostics.extend(diags);
+                    self.pace(pacer)?;
+                    self.guard.before_send(method, self.clock.now())?;
+                    self.transport.notify(method, params.clone())?;
+                    record.notifications.push((method.clone(), params));
+                }
+                Action::Log { message, .. } => record.logs.push(message.clone()),
+                Action::SendElicitation { .. } | Action::BindingSpecific { .. } => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Wait according to the phase's pacing before sending a message.
+    fn pace(&mut self, pacer: &mut Pacer) -> Result<(), EngineError> {
+        if pacer.sent > 0 {
+            let mut wait = pacer.delay;
+            if !pacer.jitter.is_zero() {
+                // xorshift64
+                self.rng ^= self.rng << 13;
+                self.rng ^= self.rng >> 7;
+                self.rng ^= self.rng << 17;
+                let fraction = (self.rng >> 11) as f64 / (1u64 << 53) as f64;
+                wait += pacer.jitter.mul_f64(fraction);
+            }
+            self.wait(wait)?;
+        }
+        pacer.sent += 1;
+        Ok(())
+    }
+
+    fn wait(&mut self, duration: Duration) -> Result<(), EngineError> {
+        if duration.is_zero() {
+            return Ok(());
+        }
+        self.guard.before_wait(duration, self.clock.now())?;
+        match &mut self.sleep {
+            Some(sleep) => sleep(duration),
+            None => self.clock.sleep(duration),
+        }
+        Ok(())
+    }
+}
+
+/// Enforces `attack.safety` limits for a run.
+struct Guard {
+    started: Duration,
+    max_runtime: Option<Duration>,
+    max_messages: Option<u64>,
+    forbidden_hosts: Vec<String>,
+    sent: u64,
+}
+
+impl Guard {
+    fn new(safety: Option<&Safety>, now: Duration) -> Self {
+        Guard {
+            started: now,
+            // Invalid values are reported by V-048 and ignored here.
+            max_runtime: safety
+                .and_then(|s| s.max_runtime.as_deref())
+                .and_then(|d| parse_duration(d).ok()),
+            max_messages: safety
+                .and_then(|s| s.max_messages)
+                .and_then(|m| u64::try_from(m).ok()),
+            forbidden_hosts: safety
+                .and_then(|s| s.forbidden_hosts.clone())
+                .unwrap_or_default(),
+            sent: 0,
+        }
+    }
+
+    fn check_target(&self, target: &str) -> Result<(), EngineError> {
+        let host = host_of(target);
+        for pattern in &self.forbidden_hosts {
+            let pattern = host_of(pattern);
+            let forbidden = match pattern.strip_prefix("*.") {
+                Some(suffix) => host
+                    .strip_suffix(suffix)
+                    .is_some_and(|sub| sub.ends_with('.')),
+                None => host == pattern,
+            };
+            if forbidden {
+                return Err(violation(format!(
+                    "target host '{}' is forbidden by safety rule '{}'",
+                    host, pattern
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn before_send(&mut self, method: &str, now: Duration) -> Result<(), EngineError> {
+        self.check_runtime(now, Duration::ZERO)?;
+        if let Some(max) = self.max_messages
+            && self.sent >= max
+        {
+            return Err(violation(format!(
+                "sending '{}' would exceed max_messages ({})",
+                method, max
+            )));
+        }
+        self.sent += 1;
+        Ok(())
+    }
+
+    fn before_wait(&self, duration: Duration, now: Duration) -> Result<(), EngineError> {
+        self.check_runtime(now, duration)
+    }
+
+    fn check_runtime(&self, now: Duration, upcoming: Duration) -> Result<(), EngineError> {
+        if let Some(max) = self.max_runtime
+            && now.saturating_sub(self.started) + upcoming > max
+        {
+            return Err(violation(format!(
+                "run would exceed max_runtime ({:?})",
+                max
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Rejects an actor whose endpoint URL names a host forbidden by the guard.
+fn check_endpoint(guard: &Guard, actor: &Actor) -> Result<(), EngineError> {
+    let Some(url) = actor.endpoint.as_ref().and_then(|e| e.url.as_deref()) else {
+        return Ok(());
+    };
+    guard.check_target(url).map_err(|e| EngineError {
+        message: format!("actor '{}' endpoint: {}", actor.name, e.message),
+        ..e
+    })
+}
+
+fn violation(message: String) -> EngineError {
+    EngineError {
+        kind: EngineErrorKind::SafetyViolation,
+        message,
+        phase_name: None,
+    }
+}
+
+/// Lowercased host of a target given as a host, `host:port`, or URL.
+fn host_of(target: &str) -> String {
+    let rest = target.split_once("://").map_or(target, |(_, r)| r);
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or(rest);
+    let authority = authority.rsplit_once('@').map_or(authority, |(_, a)| a);
+    let host = if let Some(v6) = authority.strip_prefix('[') {
+        v6.split(']').next().unwrap_or(v6)
+    } else {
+        authority.split(':').next().unwrap_or(authority)
+    };
+    host.to_ascii_lowercase()
+}
+
+/// Pacing state for a single phase.
+struct Pacer {
+    delay: Duration,
+    jitter: Duration,
+    sent: usize,
+}
+
+impl Pacer {
+    fn new(pacing: Option<&Pacing>) -> Self {
+        // Invalid durations are reported by V-047 and treated as zero here.
+        let parse = |d: Option<&String>| d.and_then(|d| parse_duration(d).ok()).unwrap_or_default();
+        Pacer {
+            delay: parse(pacing.and_then(|p| p.delay.as_ref())),
+            jitter: parse(pacing.and_then(|p| p.jitter.as_ref())),
+            sent: 0,
+        }
+    }
+}
+
+/// Find the first actor whose base mode (version pin stripped) is `mode`.
+fn find_actor<'a>(doc: &'a Document, mode: &str) -> Result<&'a Actor, EngineError> {
+    doc.attack
+        .execution
+        .actors
+        .iter()
+        .flatten()
+        .find(|a| Mode::parse(&a.mode).name == mode)
+        .ok_or_else(|| EngineError {
+            kind: EngineErrorKind::Configuration,
+            message: format!(
+                "document has no '{}' actor (normalize the document first)",
+                mode
+            ),
+            phase_name: None,
+        })
+}
+
+/// Requests described by a client phase's state, in issue order.
+pub(crate) fn client_requests(state: Option<&Value>) -> Vec<(String, Value)> {
+    let Some(obj) = state.and_then(|s| s.as_object()) else {
+        return Vec::new();
+    };
+    let mut requests = Vec::new();
+
+    for request in obj
+        .get("requests")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+    {
+        if let Some(method) = request.get("method").and_then(|m| m.as_str()) {
+            let params = request.get("params").cloned().unwrap_or_else(|| json!({}));
+            requests.push((method.to_string(), params));
+        }
+    }
+
+    for (key, method) in [("tools", "tools/call"), ("prompts", "prompts/get")] {
+        for item in obj
+            .get(key)
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+        {
+            if let Some(name) = item.get("name") {
+                let arguments = item.get("arguments").cloned().unwrap_or_else(|| json!({}));
+                requests.push((
+                    method.to_string(),
+                    json!({"name": name, "arguments": arguments}),
+                ));
+            }
+        }
+    }
+
+    requests
+}
+
+/// `trigger` with the templates in its `match` predicate resolved against
+/// the values extracted so far.
+fn interpolated_trigger(
+    trigger: &Trigger,
+    extracted: &HashMap<String, String>,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Trigger {
+    let mut trigger = trigger.clone();
+    if let Some(predicate) = &trigger.match_predicate {
+        let (predicate, diags) = interpolate_predicate(predicate, extracted);
+        diagnostics.extend(diags);
+        trigger.match_predicate = Some(predicate);
+    }
+    trigger
+}
+
+/// `doc` with its `{{attachment:name}}` references resolved.
+fn resolve_attachments(doc: &Document) -> Result<Document, EngineError> {
+    crate::attachments::resolve_document(doc).map_err(|e| EngineError {
+        kind: EngineErrorKind::Configuration,
+        message: e.to_string(),
+        phase_name: None,
+    })
+}
+
+fn with_phase(mut error: EngineError, phase: &Phase) -> EngineError {
+    if error.phase_name.is_none() {
+        error.phase_name = phase.name.clone();
+    }
+    error
+}
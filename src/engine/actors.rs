@@ -0,0 +1,1293 @@
+//! Per-actor execution for multi-actor documents.
+//!
+//! Each actor of a normalized document runs as an independent
+//! [`ActorMachine`]: a sans-IO state machine that consumes the messages its
+//! peer sends ([`ActorInput`]) and produces what the actor does in response
+//! ([`ActorOutput`]) — replies for server actors, requests for client
+//! actors, `on_enter` and `on_timeout` notifications, phase transitions, and
+//! extracted values.
+//! Machines never perform I/O or read the clock; the caller passes the time
+//! elapsed since the run started.
+//!
+//! Two schedulers drive a document's machines:
+//!
+//! - [`ActorSystem`] runs all actors on the calling thread against a
+//!   [`Clock`] — by default a [`MockClock`] that only moves through
+//!   [`ActorSystem::advance`]. Inputs are processed in the order they are
+//!   delivered, so runs are fully deterministic — use it in tests.
+//! - [`ConcurrentActorSystem`] runs each actor on its own thread behind an
+//!   inbox channel, waking actors when their `after` timeouts expire. Peers
+//!   talk to an actor through a cloneable [`ActorSender`].
+//!
+//! In both, a value an actor extracts is propagated to every other actor as
+//! `{{actor.name}}` (the cross-actor reference form checked by V-032), so an
+//! attacker server can serve a token its client actor obtained elsewhere.
+//! A phase whose `requires` does not hold for the values extracted so far,
+//! including those shared by other actors, is skipped
+//! ([`ActorOutput::PhaseSkipped`]) and the next phase is considered instead.
+//!
+//! Server actors answer MCP requests (`initialize`, `tools/list`,
+//! `tools/call`, `prompts/list`, `prompts/get`, `resources/list`,
+//! `resources/read`) from their effective state, selecting `responses`
+//! entries with their `when` predicates; other methods, and protocols other
+//! than A2A (below), are answered with an empty object. They accept `resources/subscribe`, and on
+//! entering a phase that changes a subscribed resource send
+//! `notifications/resources/updated` for it. Client actors issue the requests
+//! described by each phase's own state one at a time, as
+//! [`McpClient`](super::McpClient) does, and answer the server's
+//! `roots/list`, `sampling/createMessage`, and `elicitation/create` requests
+//! from the state's `roots`, `sampling.responses`, and
+//! `elicitation.responses`.
+//!
+//! A2A server actors serve `agent_card/get` from the state's `agent_card` and
+//! answer `message/send`, `message/stream`, and `tasks/resubscribe` with the
+//! first matching `task_responses` entry. The entry's `updates` —
+//! `status-update` and `artifact-update` events — follow the reply as
+//! `task/status` and `task/artifact` notifications when the request streams,
+//! or when a push notification config was registered with
+//! `tasks/pushNotificationConfig/set`.
+//!
+//! Actors keep their `trust` and `endpoint` for harnesses that bind their
+//! transports ([`ActorMachine::trust`], [`ActorMachine::endpoint`]). Pacing
+//! and `attack.safety` limits are left to the transports that carry the
+//! messages, except that a system refuses to start an actor whose endpoint
+//! URL names a forbidden host.
+
+use super::journal::{ActorSnapshot, Journal, JournalAction, JournalStep, ResponseSelection};
+use super::trace::{ExecutionTrace, TraceEntry, TraceEvent};
+use super::{
+    DEFAULT_MCP_PROTOCOL_VERSION, Guard, check_endpoint, client_requests, interpolated_trigger,
+    resolve_attachments,
+};
+use crate::clock::{Clock, MockClock, SystemClock};
+use crate::enums::{ActorTrust, AdvanceReason, ExtractorSource, OriginRole};
+use crate::error::{Diagnostic, EngineError, EngineErrorKind, TraceError, TraceErrorKind};
+use crate::primitives::{
+    compute_effective_state, evaluate_extractor, evaluate_requires, evaluate_trigger,
+    extract_protocol, interpolate_value, select_response, trigger_deadline,
+};
+use crate::types::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::sync::Arc;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+// ─── Messages ───────────────────────────────────────────────────────────────
+
+/// A message an actor receives from its peer.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActorInput {
+    /// A request to a server actor, answered with [`ActorOutput::Reply`].
+    Request {
+        /// JSON-RPC method.
+        method: String,
+        /// Request parameters.
+        params: Value,
+    },
+    /// The peer's reply to an [`ActorOutput::Request`] of a client actor.
+    Response {
+        /// Method of the request being answered.
+        method: String,
+        /// Parameters of the request being answered.
+        params: Value,
+        /// The `result` member of the reply.
+        result: Value,
+    },
+    /// A notification from the peer.
+    Notification {
+        /// JSON-RPC method.
+        method: String,
+        /// Notification parameters.
+        params: Value,
+    },
+}
+
+/// Something an actor does, in the order it happens.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActorOutput {
+    /// The actor entered a phase.
+    PhaseEntered {
+        /// Index of the phase in the actor's `phases`.
+        index: usize,
+        /// Phase name, if the phase has one.
+        name: Option<String>,
+    },
+    /// The actor skipped a phase because its `requires` did not hold.
+    PhaseSkipped {
+        /// Index of the phase in the actor's `phases`.
+        index: usize,
+        /// Phase name, if the phase has one.
+        name: Option<String>,
+    },
+    /// A client actor sends a request; feed the reply back as
+    /// [`ActorInput::Response`].
+    Request {
+        /// JSON-RPC method.
+        method: String,
+        /// Parameters after template interpolation.
+        params: Value,
+    },
+    /// A server actor's reply to an [`ActorInput::Request`].
+    Reply {
+        /// Method of the request being answered.
+        method: String,
+        /// The `result` member of the reply.
+        result: Value,
+    },
+    /// A `send_notification` action.
+    Notify {
+        /// JSON-RPC method.
+        method: String,
+        /// Parameters after template interpolation.
+        params: Value,
+    },
+    /// A `log` action's message.
+    Log(String),
+    /// An extractor captured a value.
+    Extracted {
+        /// Extractor name.
+        name: String,
+        /// Captured value.
+        value: String,
+    },
+    /// The current phase's trigger advanced.
+    Advanced {
+        /// Why the trigger advanced.
+        reason: AdvanceReason,
+    },
+    /// The actor entered its terminal phase, or skipped every remaining
+    /// phase.
+    Completed,
+}
+
+/// An [`ActorOutput`] attributed to the actor that produced it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+    /// Name of the actor.
+    pub actor: String,
+    /// What the actor did.
+    pub output: ActorOutput,
+}
+
+// ─── Actor state machine ────────────────────────────────────────────────────
+
+/// One actor's phases as a state machine.
+#[derive(Clone, Debug)]
+pub struct ActorMachine {
+    actor: Actor,
+    protocol: String,
+    server: bool,
+    phase: usize,
+    phase_started: Duration,
+    trigger_state: TriggerState,
+    pending: VecDeque<(String, Value)>,
+    extracted: HashMap<String, String>,
+    shared: HashMap<String, String>,
+    subscriptions: BTreeSet<String>,
+    push_configs: BTreeMap<String, Value>,
+    completed: bool,
+    diagnostics: Vec<Diagnostic>,
+    selections: Vec<ResponseSelection>,
+}
+
+impl ActorMachine {
+    /// Create a machine for `actor`. It does nothing until [`start`](Self::start).
+    pub fn new(actor: &Actor) -> Self {
+        let mode = Mode::parse(&actor.mode);
+        ActorMachine {
+            protocol: extract_protocol(&actor.mode).to_string(),
+            server: mode.name.ends_with("_server"),
+            actor: actor.clone(),
+            phase: 0,
+            phase_started: Duration::ZERO,
+            trigger_state: TriggerState::default(),
+            pending: VecDeque::new(),
+            extracted: HashMap::new(),
+            shared: HashMap::new(),
+            subscriptions: BTreeSet::new(),
+            push_configs: BTreeMap::new(),
+            completed: false,
+            diagnostics: Vec::new(),
+            selections: Vec::new(),
+        }
+    }
+
+    /// The actor's name.
+    pub fn name(&self) -> &str {
+        &self.actor.name
+    }
+
+    /// The actor's trust level.
+    pub fn trust(&self) -> ActorTrust {
+        self.actor.trust()
+    }
+
+    /// Where a harness binds the actor's transport, if the document says.
+    pub fn endpoint(&self) -> Option<&Endpoint> {
+        self.actor.endpoint.as_ref()
+    }
+
+    /// Index of the current phase.
+    pub fn phase(&self) -> usize {
+        self.phase
+    }
+
+    /// Name of the current phase, if it has one.
+    pub fn phase_name(&self) -> Option<&str> {
+        self.actor.phases.get(self.phase)?.name.as_deref()
+    }
+
+    /// Values captured by this actor's extractors, by extractor name.
+    pub fn extracted(&self) -> &HashMap<String, String> {
+        &self.extracted
+    }
+
+    /// True once the terminal phase has been entered, or every remaining
+    /// phase skipped.
+    pub fn is_complete(&self) -> bool {
+        self.completed
+    }
+
+    /// Template interpolation warnings (W-004) collected so far.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Time (since the run started) at which the current phase's `after`
+    /// timeout expires, if it has one.
+    pub fn next_deadline(&self) -> Option<Duration> {
+        if self.completed {
+            return None;
+        }
+        trigger_deadline(self.current().trigger.as_ref()?, self.phase_started)
+    }
+
+    /// Enter the first phase at time `now`.
+    pub fn start(&mut self, now: Duration) -> Vec<ActorOutput> {
+        let mut outputs = Vec::new();
+        self.enter(0, now, &mut outputs);
+        outputs
+    }
+
+    /// Make a value extracted by another actor available to templates as
+    /// `{{actor.name}}`.
+    pub fn share(&mut self, actor: &str, name: &str, value: &str) {
+        self.shared
+            .insert(format!("{}.{}", actor, name), value.to_string());
+    }
+
+    /// Process a message from the peer at time `now`.
+    pub fn handle(&mut self, input: ActorInput, now: Duration) -> Vec<ActorOutput> {
+        let mut outputs = Vec::new();
+        self.selections.clear();
+        let (method, params, response) = match input {
+            ActorInput::Request { method, params } => {
+                let (result, updates) = match (self.server, self.protocol.as_str()) {
+                    (true, "a2a") => self.a2a_reply(&method, &params),
+                    (true, _) => (self.reply(&method, &params), Vec::new()),
+                    (false, _) => (self.client_reply(&method, &params), Vec::new()),
+                };
+                outputs.push(ActorOutput::Reply {
+                    method: method.clone(),
+                    result: result.clone(),
+                });
+                outputs.extend(updates);
+                (method, params, Some(result))
+            }
+            ActorInput::Response {
+                method,
+                params,
+                result,
+            } => (method, params, Some(result)),
+            ActorInput::Notification { method, params } => (method, params, None),
+        };
+
+        let request_message = json!({"method": method, "params": params});
+        let extractors = self.current().extractors.clone().unwrap_or_default();
+        for extractor in &extractors {
+            let captured =
+                evaluate_extractor(extractor, &request_message, ExtractorSource::Request).or_else(
+                    || {
+                        response.as_ref().and_then(|r| {
+                            evaluate_extractor(extractor, r, ExtractorSource::Response)
+                        })
+                    },
+                );
+            if let Some(value) = captured {
+                outputs.push(ActorOutput::Extracted {
+                    name: extractor.name.clone(),
+                    value: value.clone(),
+                });
+                self.extracted.insert(extractor.name.clone(), value);
+            }
+        }
+
+        // Client triggers see the reply; server triggers see the request.
+        let is_response = !self.server && response.is_some();
+        let event = ProtocolEvent {
+            event_type: method,
+            qualifier: params
+                .get("name")
+                .and_then(|n| n.as_str())
+                .map(str::to_string),
+            content: match response {
+                Some(result) if is_response => result,
+                _ => params,
+            },
+            origin: Some(Origin {
+                role: if is_response {
+                    OriginRole::Server
+                } else {
+                    OriginRole::Client
+                },
+                endpoint: None,
+                trusted: None,
+            }),
+        };
+        let advanced = self.evaluate(Some(&event), now, &mut outputs);
+        if !advanced && is_response {
+            self.send_next(&mut outputs);
+        }
+        outputs
+    }
+
+    /// Advance the current phase if its `after` timeout expired by `now`.
+    pub fn tick(&mut self, now: Duration) -> Vec<ActorOutput> {
+        let mut outputs = Vec::new();
+        self.evaluate(None, now, &mut outputs);
+        outputs
+    }
+
+    fn current(&self) -> &Phase {
+        &self.actor.phases[self.phase]
+    }
+
+    /// Evaluate the current trigger, its `match` predicate interpolated with
+    /// the values extracted so far; on advancement run `on_timeout` actions
+    /// if the trigger timed out, then enter the next phase.
+    fn evaluate(
+        &mut self,
+        event: Option<&ProtocolEvent>,
+        now: Duration,
+        outputs: &mut Vec<ActorOutput>,
+    ) -> bool {
+        if self.completed {
+            return false;
+        }
+        let Some(trigger) = &self.actor.phases[self.phase].trigger else {
+            return false;
+        };
+        let trigger = if event.is_some() {
+            let mut values = self.shared.clone();
+            values.extend(self.extracted.clone());
+            interpolated_trigger(trigger, &values, &mut self.diagnostics)
+        } else {
+            trigger.clone()
+        };
+        let elapsed = now.saturating_sub(self.phase_started);
+        match evaluate_trigger(
+            &trigger,
+            event,
+            elapsed,
+            &mut self.trigger_state,
+            &self.protocol,
+        ) {
+            TriggerResult::Advanced { reason } => {
+                let timed_out = reason == AdvanceReason::Timeout;
+                outputs.push(ActorOutput::Advanced { reason });
+                if timed_out {
+                    let actions = self.current().on_timeout.clone().unwrap_or_default();
+                    self.run_actions(actions, outputs);
+                }
+                self.enter(self.phase + 1, now, outputs);
+                true
+            }
+            TriggerResult::NotAdvanced => false,
+        }
+    }
+
+    fn enter(&mut self, mut index: usize, now: Duration, outputs: &mut Vec<ActorOutput>) {
+        while let Some(phase) = self.actor.phases.get(index)
+            && !self.requirements_met(phase)
+        {
+            outputs.push(ActorOutput::PhaseSkipped {
+                index,
+                name: phase.name.clone(),
+            });
+            index += 1;
+        }
+        let Some(phase) = self.actor.phases.get(index) else {
+            // Every remaining phase was skipped, or the last phase has a
+            // trigger (V-008 reports this).
+            self.completed = true;
+            outputs.push(ActorOutput::Completed);
+            return;
+        };
+        let previous = (index != self.phase && !self.subscriptions.is_empty())
+            .then(|| compute_effective_state(&self.actor.phases, self.phase));
+        self.phase = index;
+        self.phase_started = now;
+        self.trigger_state = TriggerState::default();
+        outputs.push(ActorOutput::PhaseEntered {
+            index,
+            name: phase.name.clone(),
+        });
+
+        let actions = phase.on_enter.clone().unwrap_or_default();
+        self.run_actions(actions, outputs);
+        if let Some(previous) = previous {
+            self.notify_updated(&previous, outputs);
+        }
+
+        self.pending = if self.server {
+            VecDeque::new()
+        } else {
+            client_requests(self.actor.phases[index].state.as_ref()).into()
+        };
+        if self.actor.phases[index].trigger.is_none() {
+            self.completed = true;
+        }
+        self.send_next(outputs);
+        if self.completed {
+            outputs.push(ActorOutput::Completed);
+        }
+    }
+
+    /// Emit `on_enter` or `on_timeout` actions as outputs.
+    fn run_actions(&mut self, actions: Vec<Action>, outputs: &mut Vec<ActorOutput>) {
+        for action in actions {
+            match action {
+                Action::SendNotification { method, params, .. } => {
+                    let params = self.interpolate(&params.unwrap_or_else(|| json!({})), None);
+                    outputs.push(ActorOutput::Notify { method, params });
+                }
+                Action::Log { message, .. } => outputs.push(ActorOutput::Log(message)),
+                Action::SendElicitation { .. } | Action::BindingSpecific { .. } => {}
+            }
+        }
+    }
+
+    /// Send `notifications/resources/updated` for every subscribed resource
+    /// the current phase's effective state changed from `previous`.
+    fn notify_updated(&self, previous: &Value, outputs: &mut Vec<ActorOutput>) {
+        let current = compute_effective_state(&self.actor.phases, self.phase);
+        for uri in &self.subscriptions {
+            if resource(previous, uri) != resource(&current, uri) {
+                outputs.push(ActorOutput::Notify {
+                    method: "notifications/resources/updated".to_string(),
+                    params: json!({"uri": uri}),
+                });
+            }
+        }
+    }
+
+    fn send_next(&mut self, outputs: &mut Vec<ActorOutput>) {
+        if let Some((method, params)) = self.pending.pop_front() {
+            let params = self.interpolate(&params, None);
+            outputs.push(ActorOutput::Request { method, params });
+        }
+    }
+
+    /// Whether `phase`'s `requires` holds for the values extracted so far.
+    fn requirements_met(&self, phase: &Phase) -> bool {
+        let mut values = self.shared.clone();
+        values.extend(self.extracted.clone());
+        evaluate_requires(phase.requires.as_ref(), &values)
+    }
+
+    fn interpolate(&mut self, value: &Value, request: Option<&Value>) -> Value {
+        let mut values = self.shared.clone();
+        values.extend(self.extracted.clone());
+        let (value, diags) = interpolate_value(value, &values, request, None);
+        self.diagnostics.extend(diags);
+        value
+    }
+
+    /// An MCP server's reply from the effective state of the current phase.
+    fn reply(&mut self, method: &str, params: &Value) -> Value {
+        if self.protocol != "mcp" {
+            return json!({});
+        }
+        let state = compute_effective_state(&self.actor.phases, self.phase);
+        let list = |key: &str| -> Vec<Value> {
+            state
+                .get(key)
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .map(|item| {
+                    let mut item = item.clone();
+                    if let Some(obj) = item.as_object_mut() {
+                        obj.remove("responses");
+                    }
+                    item
+                })
+                .collect()
+        };
+        let find = |key: &str, field: &str| -> Option<Value> {
+            let wanted = params.get(field)?;
+            state
+                .get(key)?
+                .as_array()?
+                .iter()
+                .find(|item| item.get(field) == Some(wanted))
+                .cloned()
+        };
+        match method {
+            "initialize" => {
+                let version = Mode::parse(&self.actor.mode)
+                    .version
+                    .unwrap_or_else(|| DEFAULT_MCP_PROTOCOL_VERSION.to_string());
+                json!({
+                    "protocolVersion": version,
+                    "capabilities": {"tools": {}, "prompts": {}, "resources": {"subscribe": true}},
+                    "serverInfo": {"name": self.actor.name, "version": env!("CARGO_PKG_VERSION")},
+                })
+            }
+            "tools/list" => json!({"tools": list("tools")}),
+            "prompts/list" => json!({"prompts": list("prompts")}),
+            "resources/list" => json!({"resources": list("resources")}),
+            "tools/call" | "prompts/get" => {
+                let key = if method == "tools/call" {
+                    "tools"
+                } else {
+                    "prompts"
+                };
+                let item = find(key, "name");
+                match self.respond(method, item.as_ref(), params) {
+                    Some(content) => content,
+                    None if key == "tools" => json!({"content": []}),
+                    None => json!({"messages": []}),
+                }
+            }
+            "resources/read" => {
+                let contents: Vec<Value> = find("resources", "uri").into_iter().collect();
+                json!({"contents": contents})
+            }
+            "resources/subscribe" | "resources/unsubscribe" => {
+                if let Some(uri) = params.get("uri").and_then(Value::as_str) {
+                    if method == "resources/subscribe" {
+                        self.subscriptions.insert(uri.to_string());
+                    } else {
+                        self.subscriptions.remove(uri);
+                    }
+                }
+                json!({})
+            }
+            _ => json!({}),
+        }
+    }
+
+    /// An A2A server's reply from the effective state of the current phase,
+    /// with the task updates it streams or pushes afterwards.
+    fn a2a_reply(&mut self, method: &str, params: &Value) -> (Value, Vec<ActorOutput>) {
+        let state = compute_effective_state(&self.actor.phases, self.phase);
+        let task_id = params
+            .get("taskId")
+            .or_else(|| params.get("id"))
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        let result = match method {
+            "agent_card/get" => state.get("agent_card").cloned().unwrap_or(json!({})),
+            "message/send" | "message/stream" | "tasks/resubscribe" => {
+                let entries: Vec<ResponseEntry> = state
+                    .get("task_responses")
+                    .cloned()
+                    .and_then(|r| serde_json::from_value(r).ok())
+                    .unwrap_or_default();
+                let selected = select_response(&entries, params);
+                self.record_selection(method, &entries, selected);
+                let Some(entry) = selected else {
+                    return (json!({}), Vec::new());
+                };
+                let mut task = entry.extra.clone();
+                let updates = task.remove("updates");
+                let task =
+                    self.interpolate(&Value::Object(task.into_iter().collect()), Some(params));
+                // A plain `message/send` only delivers updates to a registered
+                // push notification endpoint.
+                let delivered = method != "message/send" || !self.push_configs.is_empty();
+                let updates = updates
+                    .filter(|_| delivered)
+                    .and_then(|u| u.as_array().cloned())
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|update| {
+                        let params = self.interpolate(update, Some(params));
+                        let method = match params.get("kind").and_then(Value::as_str) {
+                            Some("artifact-update") => "task/artifact",
+                            _ => "task/status",
+                        };
+                        ActorOutput::Notify {
+                            method: method.to_string(),
+                            params,
+                        }
+                    })
+                    .collect();
+                return (task, updates);
+            }
+            "tasks/pushNotification/set" | "tasks/pushNotificationConfig/set" => {
+                self.push_configs.insert(task_id, params.clone());
+                params.clone()
+            }
+            "tasks/pushNotification/get" | "tasks/pushNotificationConfig/get" => self
+                .push_configs
+                .get(&task_id)
+                .cloned()
+                .unwrap_or(json!({})),
+            "tasks/pushNotificationConfig/list" => {
+                Value::Array(self.push_configs.values().cloned().collect())
+            }
+            "tasks/pushNotificationConfig/delete" => {
+                self.push_configs.remove(&task_id);
+                json!({})
+            }
+            _ => json!({}),
+        };
+        (result, Vec::new())
+    }
+
+    /// An MCP client's reply to a server request from the effective state of
+    /// the current phase.
+    fn client_reply(&mut self, method: &str, params: &Value) -> Value {
+        if self.protocol != "mcp" {
+            return json!({});
+        }
+        let state = compute_effective_state(&self.actor.phases, self.phase);
+        match method {
+            "roots/list" => json!({"roots": state.get("roots").cloned().unwrap_or(json!([]))}),
+            "sampling/createMessage" => self
+                .respond(method, state.get("sampling"), params)
+                .unwrap_or_else(|| json!({})),
+            "elicitation/create" => self
+                .respond(method, state.get("elicitation"), params)
+                .unwrap_or_else(|| json!({"action": "decline"})),
+            _ => json!({}),
+        }
+    }
+
+    /// The first of `item`'s `responses` entries whose `when` predicate
+    /// matches `params`, interpolated.
+    fn respond(&mut self, method: &str, item: Option<&Value>, params: &Value) -> Option<Value> {
+        let entries: Vec<ResponseEntry> = item
+            .and_then(|item| item.get("responses").cloned())
+            .and_then(|r| serde_json::from_value(r).ok())
+            .unwrap_or_default();
+        let selected = select_response(&entries, params);
+        self.record_selection(method, &entries, selected);
+        let content = Value::Object(selected?.extra.clone().into_iter().collect());
+        Some(self.interpolate(&content, Some(params)))
+    }
+
+    /// Note which of `entries` a reply to `method` was built from, for
+    /// journals.
+    fn record_selection(
+        &mut self,
+        method: &str,
+        entries: &[ResponseEntry],
+        selected: Option<&ResponseEntry>,
+    ) {
+        self.selections.push(ResponseSelection {
+            actor: self.actor.name.clone(),
+            method: method.to_string(),
+            phase: self.phase,
+            entry: selected.and_then(|s| entries.iter().position(|e| std::ptr::eq(e, s))),
+        });
+    }
+
+    /// Selections made while handling the last input.
+    fn take_selections(&mut self) -> Vec<ResponseSelection> {
+        std::mem::take(&mut self.selections)
+    }
+}
+
+/// The entry of `state`'s `resources` with URI `uri`.
+fn resource<'a>(state: &'a Value, uri: &str) -> Option<&'a Value> {
+    state
+        .get("resources")?
+        .as_array()?
+        .iter()
+        .find(|r| r.get("uri").and_then(Value::as_str) == Some(uri))
+}
+
+// ─── Deterministic scheduler ────────────────────────────────────────────────
+
+/// All actors of a document on the calling thread, driven by a [`Clock`].
+///
+/// Inputs are processed synchronously in delivery order. With the default
+/// [`MockClock`], time only moves through [`advance`](Self::advance), so the
+/// same sequence of calls always produces the same outputs.
+///
+/// With [`with_journal`](Self::with_journal), every step is recorded in a
+/// [`Journal`], and the system can be rewound to an earlier step
+/// ([`rewind_to`](Self::rewind_to)) or rebuilt from a saved journal
+/// ([`replay`](Self::replay)).
+#[derive(Clone)]
+pub struct ActorSystem {
+    machines: Vec<ActorMachine>,
+    clock: Arc<dyn Clock>,
+    started: Duration,
+    trace: Option<ExecutionTrace>,
+    journal: Option<Journal>,
+    initial: Vec<ActorMachine>,
+}
+
+impl std::fmt::Debug for ActorSystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ActorSystem")
+            .field("machines", &self.machines)
+            .field("now", &self.now())
+            .finish()
+    }
+}
+
+impl ActorSystem {
+    /// Create machines for every actor of a normalized document, on a
+    /// [`MockClock`] starting at zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EngineErrorKind::Configuration`] if the document has no
+    /// actors or an attachment reference cannot be resolved, and
+    /// [`EngineErrorKind::SafetyViolation`] if an actor's endpoint URL names
+    /// a host forbidden by `attack.safety`.
+    pub fn new(doc: &Document) -> Result<Self, EngineError> {
+        Self::with_clock(doc, MockClock::default())
+    }
+
+    /// [`new`](Self::new) with an explicit clock, e.g. a shared
+    /// [`MockClock`] set to recorded offsets to replay a trace.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EngineErrorKind::Configuration`] if the document has no
+    /// actors or an attachment reference cannot be resolved, and
+    /// [`EngineErrorKind::SafetyViolation`] if an actor's endpoint URL names
+    /// a host forbidden by `attack.safety`.
+    pub fn with_clock(doc: &Document, clock: impl Clock + 'static) -> Result<Self, EngineError> {
+        let started = clock.now();
+        Ok(ActorSystem {
+            machines: machines(doc)?,
+            clock: Arc::new(clock),
+            started,
+            trace: None,
+            journal: None,
+            initial: Vec::new(),
+        })
+    }
+
+    /// Record every input delivered and every output produced from now on
+    /// as an [`ExecutionTrace`]. Call it before [`start`](Self::start) to
+    /// record a run that [`verify_trace`](super::trace::verify_trace) can
+    /// check.
+    pub fn with_trace(mut self) -> Self {
+        self.trace.get_or_insert_with(ExecutionTrace::default);
+        self
+    }
+
+    /// The trace recorded since [`with_trace`](Self::with_trace), if any.
+    pub fn trace(&self) -> Option<&ExecutionTrace> {
+        self.trace.as_ref()
+    }
+
+    /// Record every step from now on in a [`Journal`]. Call it before
+    /// [`start`](Self::start): [`rewind_to`](Self::rewind_to) replays the
+    /// journal from the state the system had when it was called.
+    pub fn with_journal(mut self) -> Self {
+        if self.journal.is_none() {
+            self.journal = Some(Journal::default());
+            self.initial = self.machines.clone();
+        }
+        self
+    }
+
+    /// The journal recorded since [`with_journal`](Self::with_journal), if
+    /// any.
+    pub fn journal(&self) -> Option<&Journal> {
+        self.journal.as_ref()
+    }
+
+    /// Return to the state right after journal step `step`, discarding the
+    /// later steps.
+    ///
+    /// The steps up to `step` are re-executed at their recorded times, and
+    /// the trace, if one is recorded, is rebuilt from them. The system's
+    /// time is set back to the step's, so a [`MockClock`] run continues from
+    /// there.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EngineErrorKind::Configuration`] if the system has no
+    /// journal or the journal has no step `step`.
+    pub fn rewind_to(&mut self, step: usize) -> Result<(), EngineError> {
+        let steps = match &self.journal {
+            Some(journal) if step < journal.steps.len() => journal.steps[..=step].to_vec(),
+            journal => {
+                return Err(EngineError {
+                    kind: EngineErrorKind::Configuration,
+                    message: match journal {
+                        Some(journal) => format!(
+                            "cannot rewind to step {}: the journal has {} steps",
+                            step,
+                            journal.steps.len()
+                        ),
+                        None => "cannot rewind without a journal (use with_journal)".to_string(),
+                    },
+                    phase_name: None,
+                });
+            }
+        };
+        self.machines = self.initial.clone();
+        self.journal = Some(Journal::default());
+        if self.trace.is_some() {
+            self.trace = Some(ExecutionTrace::default());
+        }
+        let mut at = Duration::ZERO;
+        for recorded in steps {
+            at = recorded.at;
+            self.step(recorded.action, at);
+        }
+        self.started = self.clock.now().saturating_sub(at);
+        Ok(())
+    }
+
+    /// Re-execute a saved journal against the normalized document `doc` on
+    /// a [`MockClock`], checking that every step produces what the journal
+    /// records. The returned system holds the re-recorded journal, so it can
+    /// be rewound to any of its steps.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TraceError`] (with the step index as its `entry`):
+    /// `configuration` if the document cannot be executed, `unknown_actor`
+    /// or `out_of_order` for a malformed step, and `diverged` for the first
+    /// step whose outputs, reply selections, or actor states differ from the
+    /// journal's.
+    pub fn replay(doc: &Document, journal: &Journal) -> Result<Self, TraceError> {
+        let clock = MockClock::default();
+        let mut system = Self::with_clock(doc, clock.clone())
+            .map_err(|e| TraceError {
+                kind: TraceErrorKind::Configuration,
+                entry: None,
+                message: e.message,
+            })?
+            .with_journal();
+        for (index, recorded) in journal.steps.iter().enumerate() {
+            let error = |kind, message: String| TraceError {
+                kind,
+                entry: Some(index),
+                message,
+            };
+            if recorded.at < clock.now() {
+                return Err(error(
+                    TraceErrorKind::OutOfOrder,
+                    format!(
+                        "recorded at {:?}, before the previous step at {:?}",
+                        recorded.at,
+                        clock.now()
+                    ),
+                ));
+            }
+            if let JournalAction::Deliver { actor, .. } = &recorded.action
+                && system.actor(actor).is_none()
+            {
+                return Err(error(
+                    TraceErrorKind::UnknownActor,
+                    format!("the document has no actor '{}'", actor),
+                ));
+            }
+            clock.set(recorded.at);
+            system.step(recorded.action.clone(), recorded.at);
+            let replayed = system.journal.as_ref().and_then(|j| j.steps.last());
+            if replayed != Some(recorded) {
+                let message = match replayed {
+                    Some(step) if step.outputs != recorded.outputs => format!(
+                        "the document produces {:?}, but the journal records {:?}",
+                        step.outputs, recorded.outputs
+                    ),
+                    Some(step) if step.responses != recorded.responses => format!(
+                        "the document selects responses {:?}, but the journal records {:?}",
+                        step.responses, recorded.responses
+                    ),
+                    _ => "the actors' states differ from the journal's".to_string(),
+                };
+                return Err(error(TraceErrorKind::Diverged, message));
+            }
+        }
+        Ok(system)
+    }
+
+    /// The actors' machines, in document order.
+    pub fn machines(&self) -> &[ActorMachine] {
+        &self.machines
+    }
+
+    /// The machine of the actor called `name`.
+    pub fn actor(&self, name: &str) -> Option<&ActorMachine> {
+        self.machines.iter().find(|m| m.name() == name)
+    }
+
+    /// Clock time since the system was created.
+    pub fn now(&self) -> Duration {
+        self.clock.now().saturating_sub(self.started)
+    }
+
+    /// True once every actor has entered its terminal phase.
+    pub fn is_complete(&self) -> bool {
+        self.machines.iter().all(ActorMachine::is_complete)
+    }
+
+    /// Start every actor, in document order.
+    pub fn start(&mut self) -> Vec<Output> {
+        let now = self.now();
+        self.step(JournalAction::Start, now)
+    }
+
+    /// Deliver a message from its peer to the actor called `actor`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EngineErrorKind::Configuration`] for an unknown actor.
+    pub fn deliver(&mut self, actor: &str, input: ActorInput) -> Result<Vec<Output>, EngineError> {
+        if self.actor(actor).is_none() {
+            return Err(unknown_actor(actor));
+        }
+        let now = self.now();
+        let action = JournalAction::Deliver {
+            actor: actor.to_string(),
+            input,
+        };
+        Ok(self.step(action, now))
+    }
+
+    /// Let `by` pass on the clock ([`Clock::sleep`]; a [`MockClock`] just
+    /// advances) and fire expired `after` timeouts.
+    pub fn advance(&mut self, by: Duration) -> Vec<Output> {
+        self.clock.sleep(by);
+        self.tick()
+    }
+
+    /// Fire `after` timeouts expired at the clock's current time, in document
+    /// order — for clocks moved from outside, such as a [`MockClock`] set to
+    /// a recorded offset.
+    pub fn tick(&mut self) -> Vec<Output> {
+        let now = self.now();
+        self.step(JournalAction::Tick, now)
+    }
+
+    /// Apply `action` at time `now` and record it in the journal.
+    fn step(&mut self, action: JournalAction, now: Duration) -> Vec<Output> {
+        let mut outputs = Vec::new();
+        match &action {
+            JournalAction::Start | JournalAction::Tick => {
+                for i in 0..self.machines.len() {
+                    let machine = &mut self.machines[i];
+                    let produced = match action {
+                        JournalAction::Start => machine.start(now),
+                        _ => machine.tick(now),
+                    };
+                    self.publish(i, now, produced, &mut outputs);
+                }
+            }
+            JournalAction::Deliver { actor, input } => {
+                let Some(i) = self.machines.iter().position(|m| m.name() == actor) else {
+                    return outputs;
+                };
+                if let Some(trace) = &mut self.trace {
+                    trace.entries.push(TraceEntry {
+                        at: now,
+                        actor: actor.clone(),
+                        event: TraceEvent::Received(input.clone()),
+                    });
+                }
+                let produced = self.machines[i].handle(input.clone(), now);
+                self.publish(i, now, produced, &mut outputs);
+            }
+        }
+        let responses: Vec<_> = self
+            .machines
+            .iter_mut()
+            .flat_map(ActorMachine::take_selections)
+            .collect();
+        if let Some(journal) = &mut self.journal {
+            journal.steps.push(JournalStep {
+                at: now,
+                action,
+                outputs: outputs.clone(),
+                responses,
+                actors: self.machines.iter().map(ActorSnapshot::of).collect(),
+            });
+        }
+        outputs
+    }
+
+    /// Attribute `produced` to actor `i`, share its extracted values with
+    /// every other actor, and record it in the trace.
+    fn publish(
+        &mut self,
+        i: usize,
+        now: Duration,
+        produced: Vec<ActorOutput>,
+        outputs: &mut Vec<Output>,
+    ) {
+        let name = self.machines[i].name().to_string();
+        for output in produced {
+            if let Some(trace) = &mut self.trace {
+                trace.entries.push(TraceEntry {
+                    at: now,
+                    actor: name.clone(),
+                    event: TraceEvent::Emitted(output.clone()),
+                });
+            }
+            if let ActorOutput::Extracted { name: key, value } = &output {
+                for (j, other) in self.machines.iter_mut().enumerate() {
+                    if j != i {
+                        other.share(&name, key, value);
+                    }
+                }
+            }
+            outputs.push(Output {
+                actor: name.clone(),
+                output,
+            });
+        }
+    }
+}
+
+// ─── Concurrent scheduler ───────────────────────────────────────────────────
+
+enum Envelope {
+    Input(ActorInput, Option<Sender<Vec<ActorOutput>>>),
+    Shared {
+        actor: String,
+        name: String,
+        value: String,
+    },
+    Shutdown,
+}
+
+/// Sends messages to one actor of a [`ConcurrentActorSystem`].
+#[derive(Clone)]
+pub struct ActorSender {
+    actor: String,
+    inbox: Sender<Envelope>,
+}
+
+impl std::fmt::Debug for ActorSender {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ActorSender")
+            .field("actor", &self.actor)
+            .finish()
+    }
+}
+
+impl ActorSender {
+    /// Name of the actor this sender delivers to.
+    pub fn actor(&self) -> &str {
+        &self.actor
+    }
+
+    /// Deliver a message without waiting; the actor's outputs appear on
+    /// [`ConcurrentActorSystem::outputs`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EngineErrorKind::Transport`] if the actor has shut down.
+    pub fn send(&self, input: ActorInput) -> Result<(), EngineError> {
+        self.inbox
+            .send(Envelope::Input(input, None))
+            .map_err(|_| self.closed())
+    }
+
+    /// Deliver a message and wait for the outputs it produced (for a server
+    /// actor's [`ActorInput::Request`], these include the
+    /// [`ActorOutput::Reply`]). The outputs also appear on
+    /// [`ConcurrentActorSystem::outputs`], and values extracted while handling
+    /// the message are queued to the other actors before this returns.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EngineErrorKind::Transport`] if the actor has shut down.
+    pub fn call(&self, input: ActorInput) -> Result<Vec<ActorOutput>, EngineError> {
+        let (reply, outputs) = mpsc::channel();
+        self.inbox
+            .send(Envelope::Input(input, Some(reply)))
+            .map_err(|_| self.closed())?;
+        outputs.recv().map_err(|_| self.closed())
+    }
+
+    fn closed(&self) -> EngineError {
+        EngineError {
+            kind: EngineErrorKind::Transport,
+            message: format!("actor '{}' has shut down", self.actor),
+            phase_name: None,
+        }
+    }
+}
+
+/// Each actor of a document on its own thread, communicating over channels.
+///
+/// Actors start as soon as the system is spawned. Every output of every
+/// actor is published on [`outputs`](Self::outputs) in the order that actor
+/// produced it; the interleaving across actors follows thread scheduling.
+#[derive(Debug)]
+pub struct ConcurrentActorSystem {
+    senders: Vec<ActorSender>,
+    outputs: Receiver<Output>,
+    threads: Vec<JoinHandle<ActorMachine>>,
+}
+
+impl ConcurrentActorSystem {
+    /// Spawn one thread per actor of a normalized document and start them.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EngineErrorKind::Configuration`] if the document has no
+    /// actors or an attachment reference cannot be resolved, and
+    /// [`EngineErrorKind::SafetyViolation`] if an actor's endpoint URL names
+    /// a host forbidden by `attack.safety`.
+    pub fn spawn(doc: &Document) -> Result<Self, EngineError> {
+        let machines = machines(doc)?;
+        let clock = SystemClock::new();
+        let (output_tx, outputs) = mpsc::channel();
+        let mut senders = Vec::new();
+        let mut inboxes = Vec::new();
+        for machine in &machines {
+            let (inbox, rx) = mpsc::channel();
+            senders.push(ActorSender {
+                actor: machine.name().to_string(),
+                inbox,
+            });
+            inboxes.push(rx);
+        }
+        let threads = machines
+            .into_iter()
+            .zip(inboxes)
+            .map(|(machine, inbox)| {
+                let peers: Vec<ActorSender> = senders
+                    .iter()
+                    .filter(|s| s.actor != machine.name())
+                    .cloned()
+                    .collect();
+                let output_tx = output_tx.clone();
+                std::thread::spawn(move || run_actor(machine, inbox, peers, output_tx, clock))
+            })
+            .collect();
+        Ok(ConcurrentActorSystem {
+            senders,
+            outputs,
+            threads,
+        })
+    }
+
+    /// A sender for the actor called `name`.
+    pub fn sender(&self, name: &str) -> Option<ActorSender> {
+        self.senders.iter().find(|s| s.actor == name).cloned()
+    }
+
+    /// Outputs of all actors as they happen.
+    pub fn outputs(&self) -> &Receiver<Output> {
+        &self.outputs
+    }
+
+    /// Stop every actor and return their final machines, in document order.
+    pub fn shutdown(self) -> Vec<ActorMachine> {
+        for sender in &self.senders {
+            let _ = sender.inbox.send(Envelope::Shutdown);
+        }
+        self.threads
+            .into_iter()
+            .map(|t| t.join().expect("actor thread panicked"))
+            .collect()
+    }
+}
+
+/// An actor thread: handle envelopes until shutdown, waking for timeouts.
+fn run_actor(
+    mut machine: ActorMachine,
+    inbox: Receiver<Envelope>,
+    peers: Vec<ActorSender>,
+    outputs: Sender<Output>,
+    clock: SystemClock,
+) -> ActorMachine {
+    let publish = |machine: &ActorMachine, produced: &[ActorOutput]| {
+        for output in produced {
+            if let ActorOutput::Extracted { name, value } = output {
+                for peer in &peers {
+                    let _ = peer.inbox.send(Envelope::Shared {
+                        actor: machine.name().to_string(),
+                        name: name.clone(),
+                        value: value.clone(),
+                    });
+                }
+            }
+            let _ = outputs.send(Output {
+                actor: machine.name().to_string(),
+                output: output.clone(),
+            });
+        }
+    };
+
+    let produced = machine.start(clock.now());
+    publish(&machine, &produced);
+    loop {
+        let envelope = match machine.next_deadline() {
+            Some(deadline) => match inbox.recv_timeout(deadline.saturating_sub(clock.now())) {
+                Ok(envelope) => envelope,
+                Err(RecvTimeoutError::Timeout) => {
+                    let produced = machine.tick(clock.now());
+                    publish(&machine, &produced);
+                    continue;
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            },
+            None => match inbox.recv() {
+                Ok(envelope) => envelope,
+                Err(_) => break,
+            },
+        };
+        match envelope {
+            Envelope::Input(input, reply) => {
+                let produced = machine.handle(input, clock.now());
+                publish(&machine, &produced);
+                if let Some(reply) = reply {
+                    let _ = reply.send(produced);
+                }
+            }
+            Envelope::Shared { actor, name, value } => machine.share(&actor, &name, &value),
+            Envelope::Shutdown => break,
+        }
+    }
+    machine
+}
+
+fn machines(doc: &Document) -> Result<Vec<ActorMachine>, EngineError> {
+    let doc = &resolve_attachments(doc)?;
+    let actors = doc.attack.execution.actors.as_deref().unwrap_or_default();
+    if actors.is_empty() {
+        return Err(EngineError {
+            kind: EngineErrorKind::Configuration,
+            message: "document has no actors (normalize the document first)".to_string(),
+            phase_name: None,
+        });
+    }
+    let guard = Guard::new(doc.attack.safety.as_ref(), Duration::ZERO);
+    for actor in actors {
+        check_endpoint(&guard, actor)?;
+    }
+    Ok(actors.iter().map(ActorMachine::new).collect())
+}
+
+fn unknown_actor(actor: &str) -> EngineError {
+    EngineError {
+        kind: EngineErrorKind::Configuration,
+        message: format!("document has no actor named '{}'", actor),
+        phase_name: None,
+    }
+}
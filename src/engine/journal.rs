@@ -0,0 +1,113 @@
+//! Step-by-step journals of deterministic runs.
+//!
+//! A [`Journal`] records every call that changes an
+//! [`ActorSystem`](super::actors::ActorSystem) — starting the actors,
+//! delivering a message, firing timeouts — together with what it produced,
+//! which `responses` entry each reply was selected from, and every actor's
+//! phase and extracted values afterwards.
+//! [`ActorSystem::with_journal`](super::actors::ActorSystem::with_journal)
+//! records one.
+//!
+//! Because actors are deterministic, a journal is enough to reconstruct any
+//! point of the run: [`ActorSystem::rewind_to`](super::actors::ActorSystem::rewind_to)
+//! returns a system to the state after an earlier step, and
+//! [`ActorSystem::replay`](super::actors::ActorSystem::replay) re-executes a
+//! saved journal against its document, checking each step produces what was
+//! recorded. Stepping through the journal shows when each phase advanced
+//! and why.
+
+use super::actors::{ActorInput, ActorMachine, Output};
+use super::trace::millis;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// The steps of a run, in the order they happened.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Journal {
+    /// Steps in order; step `n` is `steps[n]`.
+    pub steps: Vec<JournalStep>,
+}
+
+/// One call into the system and its effect.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JournalStep {
+    /// Time since the run started, serialized as whole milliseconds
+    /// (`at_ms`).
+    #[serde(rename = "at_ms", with = "millis")]
+    pub at: Duration,
+    /// What was done.
+    pub action: JournalAction,
+    /// What the actors did in response, in order.
+    pub outputs: Vec<Output>,
+    /// How each reply made during the step was chosen.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub responses: Vec<ResponseSelection>,
+    /// Every actor's state after the step, in document order.
+    pub actors: Vec<ActorSnapshot>,
+}
+
+/// A call that changes the system.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalAction {
+    /// Every actor was started.
+    Start,
+    /// A message was delivered to an actor.
+    Deliver {
+        /// Name of the actor.
+        actor: String,
+        /// The message.
+        input: ActorInput,
+    },
+    /// Expired `after` timeouts were fired.
+    Tick,
+}
+
+/// The `responses` entry a reply was built from.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResponseSelection {
+    /// Name of the replying actor.
+    pub actor: String,
+    /// Method of the request answered.
+    pub method: String,
+    /// Index of the actor's phase when it replied.
+    pub phase: usize,
+    /// Index of the selected entry in the `responses` (or
+    /// `task_responses`) list: the first whose `when` matched, else the
+    /// first without `when`. `None` when no entry applied and the default
+    /// reply was sent.
+    pub entry: Option<usize>,
+}
+
+/// An actor's state between steps.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActorSnapshot {
+    /// Name of the actor.
+    pub actor: String,
+    /// Index of the current phase.
+    pub phase: usize,
+    /// Name of the current phase, if it has one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phase_name: Option<String>,
+    /// Values captured by the actor's extractors, by extractor name.
+    pub extracted: BTreeMap<String, String>,
+    /// Whether the actor has completed.
+    pub completed: bool,
+}
+
+impl ActorSnapshot {
+    pub(super) fn of(machine: &ActorMachine) -> Self {
+        ActorSnapshot {
+            actor: machine.name().to_string(),
+            phase: machine.phase(),
+            phase_name: machine.phase_name().map(str::to_string),
+            extracted: machine
+                .extracted()
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            completed: machine.is_complete(),
+        }
+    }
+}
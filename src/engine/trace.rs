@@ -0,0 +1,180 @@
+//! Execution traces and their verification.
+//!
+//! An [`ExecutionTrace`] is a serializable log of a run: every message an
+//! actor received and everything it did in response — phases entered,
+//! requests, replies, `on_enter` and `on_timeout` actions, extracted values,
+//! and trigger advancements — each stamped with the time since the run
+//! started. [`ActorSystem::with_trace`](super::actors::ActorSystem::with_trace)
+//! records one.
+//!
+//! [`verify_trace`] lets an auditor confirm that a claimed reproduction
+//! followed the documented attack: it replays the trace's inputs against the
+//! document at the recorded times and checks that every recorded output is
+//! exactly what the document's states, triggers, and actions produce, in the
+//! same order.
+
+use super::actors::{ActorInput, ActorOutput, ActorSystem, Output};
+use crate::clock::MockClock;
+use crate::error::{EngineErrorKind, TraceError, TraceErrorKind};
+use crate::types::Document;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// A recorded run of a document's actors.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionTrace {
+    /// Entries in the order they happened.
+    pub entries: Vec<TraceEntry>,
+}
+
+/// One input or output of one actor.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TraceEntry {
+    /// Time since the run started, serialized as whole milliseconds
+    /// (`at_ms`).
+    #[serde(rename = "at_ms", with = "millis")]
+    pub at: Duration,
+    /// Name of the actor.
+    pub actor: String,
+    /// What was received or emitted.
+    #[serde(flatten)]
+    pub event: TraceEvent,
+}
+
+/// The content of a [`TraceEntry`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TraceEvent {
+    /// A message from the actor's peer.
+    Received(ActorInput),
+    /// Something the actor did.
+    Emitted(ActorOutput),
+}
+
+pub(super) mod millis {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(at: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(u64::try_from(at.as_millis()).unwrap_or(u64::MAX))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_millis)
+    }
+}
+
+/// Check that `trace` is a run of the normalized document `doc`.
+///
+/// The document's actors are started at the first entry's time and every
+/// `received` entry is delivered to its actor at its recorded time. The
+/// `emitted` entries must then match the outputs this produces, one for one
+/// and in order; when no produced output is outstanding, an `emitted` entry
+/// may also be an `after` timeout firing at its recorded time. A run that
+/// stops early is accepted as long as every output already produced was
+/// recorded.
+///
+/// # Errors
+///
+/// Returns `configuration` if the document cannot be executed,
+/// `unknown_actor` or `out_of_order` for a malformed entry, `diverged` for
+/// the first entry the document does not account for, and `incomplete` if
+/// the trace ends before outputs the document produced were recorded.
+pub fn verify_trace(doc: &Document, trace: &ExecutionTrace) -> Result<(), TraceError> {
+    let clock = MockClock::default();
+    let mut system = ActorSystem::with_clock(doc, clock.clone()).map_err(|e| TraceError {
+        kind: TraceErrorKind::Configuration,
+        entry: None,
+        message: e.message,
+    })?;
+    let mut expected: VecDeque<Output> = VecDeque::new();
+    let mut started = false;
+    let mut last = Duration::ZERO;
+
+    for (index, entry) in trace.entries.iter().enumerate() {
+        let error = |kind, message: String| TraceError {
+            kind,
+            entry: Some(index),
+            message,
+        };
+        if system.actor(&entry.actor).is_none() {
+            return Err(error(
+                TraceErrorKind::UnknownActor,
+                format!("the document has no actor '{}'", entry.actor),
+            ));
+        }
+        if entry.at < last {
+            return Err(error(
+                TraceErrorKind::OutOfOrder,
+                format!(
+                    "recorded at {:?}, before the previous entry at {:?}",
+                    entry.at, last
+                ),
+            ));
+        }
+        last = entry.at;
+        clock.set(entry.at);
+        if !started {
+            expected.extend(system.start());
+            started = true;
+        }
+
+        match &entry.event {
+            TraceEvent::Received(input) => {
+                if let Some(missing) = expected.front() {
+                    return Err(error(
+                        TraceErrorKind::Diverged,
+                        format!(
+                            "actor '{}' received a message before recording {:?}",
+                            missing.actor, missing.output
+                        ),
+                    ));
+                }
+                let outputs = system.deliver(&entry.actor, input.clone()).map_err(|e| {
+                    let kind = match e.kind {
+                        EngineErrorKind::Configuration => TraceErrorKind::UnknownActor,
+                        _ => TraceErrorKind::Diverged,
+                    };
+                    error(kind, e.message)
+                })?;
+                expected.extend(outputs);
+            }
+            TraceEvent::Emitted(output) => {
+                if expected.is_empty() {
+                    expected.extend(system.tick());
+                }
+                let Some(next) = expected.pop_front() else {
+                    return Err(error(
+                        TraceErrorKind::Diverged,
+                        format!(
+                            "actor '{}' emitted {:?}, which the document does not produce here",
+                            entry.actor, output
+                        ),
+                    ));
+                };
+                if next.actor != entry.actor || &next.output != output {
+                    return Err(error(
+                        TraceErrorKind::Diverged,
+                        format!(
+                            "actor '{}' emitted {:?}, but the document has actor '{}' emit {:?}",
+                            entry.actor, output, next.actor, next.output
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    match expected.front() {
+        Some(missing) => Err(TraceError {
+            kind: TraceErrorKind::Incomplete,
+            entry: None,
+            message: format!(
+                "the trace ends before actor '{}' recorded {:?}",
+                missing.actor, missing.output
+            ),
+        }),
+        None => Ok(()),
+    }
+}
@@ -6,7 +6,7 @@
 use serde::{Deserialize, Serialize};
 
 /// Severity classification.
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum SeverityLevel {
     /// Advisory or informational finding.
@@ -77,6 +77,41 @@ pub enum Status {
     Deprecated,
 }
 
+/// Traffic Light Protocol (TLP 2.0) marking limiting who a document may be
+/// shared with. Ordered from least to most restricted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Tlp {
+    /// No limit on disclosure.
+    Clear,
+    /// Shareable within the recipient's community, not publicly.
+    Green,
+    /// Shareable within the recipient's organization and its clients on a
+    /// need-to-know basis.
+    Amber,
+    /// For the named recipients only.
+    Red,
+}
+
+impl Tlp {
+    /// The marking as written in documents (e.g., `"amber"`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Tlp::Clear => "clear",
+            Tlp::Green => "green",
+            Tlp::Amber => "amber",
+            Tlp::Red => "red",
+        }
+    }
+}
+
+impl std::fmt::Display for Tlp {
+    /// The standard label, e.g. `TLP:AMBER`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "TLP:{}", self.as_str().to_ascii_uppercase())
+    }
+}
+
 /// How indicator verdicts combine into an attack-level result.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -87,12 +122,27 @@ pub enum CorrelationLogic {
     All,
 }
 
+/// An evaluator extension point, as named in capability reports.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EvaluatorKind {
+    /// A `CelEvaluator`, for expression indicators.
+    Cel,
+    /// A `SemanticEvaluator`, for semantic indicators.
+    Semantic,
+    /// A `StreamEvaluator`, for frequency indicators.
+    Stream,
+}
+
 /// Individual indicator evaluation result.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum IndicatorResult {
     /// The indicator matched the observed data.
     Matched,
+    /// The indicator matched, but the message fits one of the indicator's
+    /// structured `false_positives`.
+    MatchedLikelyBenign,
     /// The indicator did not match.
     NotMatched,
     /// An error occurred during evaluation.
@@ -135,6 +185,73 @@ pub enum ExtractorType {
     Regex,
 }
 
+/// A JSON Patch (RFC 6902) operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PatchOp {
+    /// Add a value.
+    Add,
+    /// Remove a value.
+    Remove,
+    /// Replace a value.
+    Replace,
+    /// Move a value from another location.
+    Move,
+    /// Copy a value from another location.
+    Copy,
+    /// Test that a value is present.
+    Test,
+}
+
+impl PatchOp {
+    /// The operation's name as it appears in a patch (`op`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PatchOp::Add => "add",
+            PatchOp::Remove => "remove",
+            PatchOp::Replace => "replace",
+            PatchOp::Move => "move",
+            PatchOp::Copy => "copy",
+            PatchOp::Test => "test",
+        }
+    }
+}
+
+/// How an attachment's `data` is encoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttachmentEncoding {
+    /// Standard base64 of the content.
+    Base64,
+    /// Standard base64 of the gzip-compressed content.
+    Gzip,
+}
+
+/// Whether the target treats an actor as trusted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActorTrust {
+    /// A participant the target trusts, such as the legitimate server an
+    /// attacker impersonates or shares the agent with.
+    Trusted,
+    /// A participant under the attacker's control.
+    #[default]
+    Untrusted,
+}
+
+/// Transport implied by an actor's [`Endpoint`](crate::types::Endpoint).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EndpointTransport {
+    /// HTTP, including Streamable HTTP and SSE (`http`/`https` URLs).
+    Http,
+    /// WebSocket (`ws`/`wss` URLs).
+    #[serde(rename = "websocket")]
+    WebSocket,
+    /// Standard input and output of a spawned process.
+    Stdio,
+}
+
 /// Semantic intent classification hint.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -192,3 +309,56 @@ pub enum AdvanceReason {
     /// The trigger's timeout elapsed.
     Timeout,
 }
+
+/// Validation profile: which content a document must carry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Profile {
+    /// Execution and detection content; `attack.indicators` is required.
+    Full,
+    /// Adversary-emulation content only; `attack.indicators` and
+    /// `attack.correlation` are absent.
+    ExecutionOnly,
+}
+
+/// Regular expression dialect that indicators and match predicates must stay
+/// within.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RegexDialect {
+    /// Everything the SDK's own regex engine accepts.
+    #[default]
+    Native,
+    /// The subset shared by RE2-class engines (RE2, Hyperscan, Go `regexp`):
+    /// no backreferences, lookaround, atomic groups, possessive quantifiers,
+    /// character-class set operations, nested classes, or `\b{…}`/`\<`/`\>`
+    /// word boundaries, and repetition bounds of at most 1000.
+    Portable,
+}
+
+/// Party a protocol event came from, relative to the trust boundary of the
+/// agent under test.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OriginRole {
+    /// The client side of the connection (e.g., the host application or a
+    /// calling agent).
+    Client,
+    /// The server side of the connection (e.g., an MCP server or remote
+    /// agent).
+    Server,
+    /// A principal outside both endpoints whose content is relayed through
+    /// the connection, such as a fetched web page or forwarded message.
+    ThirdParty,
+}
+
+impl OriginRole {
+    /// The role as written in documents (e.g., `"third_party"`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OriginRole::Client => "client",
+            OriginRole::Server => "server",
+            OriginRole::ThirdParty => "third_party",
+        }
+    }
+}
@@ -25,6 +25,9 @@ pub struct Diagnostic {
     pub path: Option<String>,
     /// Human-readable description of the issue.
     pub message: String,
+    /// The closest known value to an unrecognized one, for quick-fixes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
 }
 
 /// Error kind for parse failures.
@@ -37,6 +40,8 @@ pub enum ParseErrorKind {
     TypeMismatch,
     /// An unrecognized enum variant or key was encountered.
     UnknownVariant,
+    /// The input could not be read (I/O failure or invalid UTF-8).
+    Io,
 }
 
 /// Produced by `parse` when YAML deserialization fails.
@@ -80,6 +85,9 @@ pub struct ValidationError {
     pub path: String,
     /// Human-readable description of the violation.
     pub message: String,
+    /// The closest known value to an unrecognized one, for quick-fixes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
 }
 
 impl fmt::Display for ValidationError {
@@ -109,6 +117,125 @@ impl ValidationResult {
     }
 }
 
+/// Error kind for [`RuleEngine::register`](crate::validate::rules::RuleEngine::register)
+/// failures.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleEngineErrorKind {
+    /// A rule with the same ID is already registered.
+    DuplicateRule,
+    /// The rule's prerequisites lead back to the rule itself.
+    PrerequisiteCycle,
+}
+
+/// Produced when a validation rule cannot be registered.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleEngineError {
+    /// Classification of the failure.
+    pub kind: RuleEngineErrorKind,
+    /// ID of the rule being registered.
+    pub rule: String,
+    /// Human-readable error description.
+    pub message: String,
+}
+
+impl fmt::Display for RuleEngineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.rule, self.message)
+    }
+}
+
+impl std::error::Error for RuleEngineError {}
+
+/// Produced by corpus-level validation when an identifier collides across
+/// documents or two documents' lifecycle links contradict each other.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CorpusError {
+    /// Corpus rule identifier (`"C-001"` attack ID, `"C-002"` indicator ID,
+    /// `"C-003"` lifecycle links).
+    pub rule: String,
+    /// The colliding identifier.
+    pub id: String,
+    /// Source identity of the document where the identifier first appears
+    /// (for C-003, the document holding the link).
+    pub first_source: String,
+    /// Source identity of the document that repeats the identifier (for
+    /// C-003, the linked document).
+    pub second_source: String,
+    /// Human-readable description of the collision.
+    pub message: String,
+}
+
+impl fmt::Display for CorpusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} '{}' in {} and {}: {}",
+            self.rule, self.id, self.first_source, self.second_source, self.message
+        )
+    }
+}
+
+impl std::error::Error for CorpusError {}
+
+/// Produced by corpus-level checks for suspicious but permitted states, such
+/// as references to deprecated attacks.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CorpusWarning {
+    /// Corpus rule identifier (e.g., `"C-004"`).
+    pub rule: String,
+    /// The attack ID the warning concerns.
+    pub id: String,
+    /// Source identity of the document that triggered the warning.
+    pub source: String,
+    /// Human-readable description of the issue.
+    pub message: String,
+}
+
+impl fmt::Display for CorpusWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} '{}' in {}: {}",
+            self.rule, self.id, self.source, self.message
+        )
+    }
+}
+
+/// Error kind for execution engine failures.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EngineErrorKind {
+    /// The document cannot be executed by this runtime (e.g., no matching actor).
+    Configuration,
+    /// The transport failed to deliver a message or receive a reply.
+    Transport,
+    /// The peer replied with a protocol-level error.
+    Protocol,
+    /// A limit from `attack.safety` would be exceeded; the run was stopped.
+    SafetyViolation,
+}
+
+/// Produced by the execution engine when a run cannot continue.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EngineError {
+    /// Classification of the engine failure.
+    pub kind: EngineErrorKind,
+    /// Human-readable error description.
+    pub message: String,
+    /// Phase being executed when the failure occurred, if applicable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phase_name: Option<String>,
+}
+
+impl fmt::Display for EngineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for EngineError {}
+
 /// Error kind for evaluation failures.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -125,6 +252,8 @@ pub enum EvaluationErrorKind {
     SemanticError,
     /// The CEL expression used an unsupported method.
     UnsupportedMethod,
+    /// The expression uses a construct the evaluator does not support.
+    Unsupported,
 }
 
 /// Produced during indicator evaluation when a runtime error occurs.
@@ -186,6 +315,527 @@ impl fmt::Display for GenerationError {
 
 impl std::error::Error for GenerationError {}
 
+/// Error kind for evaluator configuration failures.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigErrorKind {
+    /// The configuration source could not be parsed.
+    Parse,
+    /// A configuration value is missing or out of range.
+    Invalid,
+    /// A configured backend could not be constructed.
+    Backend,
+}
+
+/// Produced when an evaluator stack cannot be configured.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigError {
+    /// Classification of the configuration failure.
+    pub kind: ConfigErrorKind,
+    /// Human-readable error description.
+    pub message: String,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Error kind for [`load_path`](crate::load_path) failures.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LoadPathErrorKind {
+    /// The file could not be read.
+    Io,
+    /// The file extension is not a supported document or bundle format.
+    UnsupportedFormat,
+    /// The file is a compressed document or bundle but the `bundle` feature
+    /// is disabled.
+    FeatureDisabled,
+    /// The compressed stream or archive is corrupt.
+    Archive,
+}
+
+/// Produced when a path cannot be opened or unpacked at all. Failures of
+/// individual documents are reported per entry instead.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LoadPathError {
+    /// Classification of the failure.
+    pub kind: LoadPathErrorKind,
+    /// The path that failed.
+    pub path: String,
+    /// Human-readable error description.
+    pub message: String,
+}
+
+impl fmt::Display for LoadPathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+impl std::error::Error for LoadPathError {}
+
+/// Error kind for OCI registry distribution failures.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DistributionErrorKind {
+    /// The artifact reference is malformed.
+    InvalidReference,
+    /// The registry transport failed or the registry rejected the request.
+    Transport,
+    /// The manifest is malformed or is not an OATF bundle.
+    InvalidManifest,
+    /// A pulled manifest or blob does not match its expected digest or size.
+    DigestMismatch,
+    /// The sharing policy refuses a document being pushed.
+    Restricted,
+}
+
+/// Produced when pushing or pulling an OATF bundle artifact fails.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DistributionError {
+    /// Classification of the failure.
+    pub kind: DistributionErrorKind,
+    /// Human-readable error description.
+    pub message: String,
+}
+
+impl fmt::Display for DistributionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for DistributionError {}
+
+/// Error kind for OATF pack failures.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PackErrorKind {
+    /// The pack file could not be read or written.
+    Io,
+    /// The archive is corrupt.
+    Archive,
+    /// The manifest is missing or malformed, or a document path is invalid.
+    InvalidManifest,
+    /// A document listed in the manifest is not in the pack.
+    MissingDocument,
+    /// A document in the pack is not listed in the manifest.
+    UnlistedDocument,
+    /// A document does not match its manifest digest.
+    DigestMismatch,
+    /// The pack's sharing policy refuses a document.
+    Restricted,
+}
+
+/// Produced when creating, reading, or verifying an OATF pack fails.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackError {
+    /// Classification of the failure.
+    pub kind: PackErrorKind,
+    /// Human-readable error description.
+    pub message: String,
+}
+
+impl fmt::Display for PackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for PackError {}
+
+/// Error kind for feed synchronization failures.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedErrorKind {
+    /// A feed or document URL is not an absolute `https://` URL.
+    InvalidUrl,
+    /// The transport failed or the server returned an error.
+    Transport,
+    /// The feed index is malformed.
+    InvalidIndex,
+}
+
+/// Produced when a feed or one of its documents cannot be fetched.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeedError {
+    /// Classification of the failure.
+    pub kind: FeedErrorKind,
+    /// The URL being fetched.
+    pub url: String,
+    /// Human-readable error description.
+    pub message: String,
+}
+
+impl fmt::Display for FeedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.url, self.message)
+    }
+}
+
+impl std::error::Error for FeedError {}
+
+/// Error kind for verdict sink failures.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SinkErrorKind {
+    /// The destination is temporarily unavailable; retrying may succeed.
+    Unavailable,
+    /// The destination rejected the verdict; retrying will not help.
+    Rejected,
+    /// A bounded buffer is full.
+    Full,
+    /// The sink has been shut down.
+    Closed,
+}
+
+/// Produced when a verdict cannot be published.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SinkError {
+    /// Classification of the failure.
+    pub kind: SinkErrorKind,
+    /// Human-readable error description.
+    pub message: String,
+}
+
+impl fmt::Display for SinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for SinkError {}
+
+/// Error kind for [`store`](crate::store) failures.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StoreErrorKind {
+    /// The database could not be opened, read, or written.
+    Database,
+    /// A verdict or session could not be encoded, or a stored one decoded.
+    Encoding,
+}
+
+/// Produced when the verdict store cannot complete an operation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoreError {
+    /// Classification of the failure.
+    pub kind: StoreErrorKind,
+    /// Human-readable error description.
+    pub message: String,
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+/// Error kind for [`transform`](crate::transform) failures.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransformErrorKind {
+    /// The policy names an invalid domain or identifier pattern.
+    InvalidPolicy,
+    /// The transformed document could not be rebuilt.
+    Encoding,
+    /// A new identifier does not satisfy the ID format.
+    InvalidId,
+}
+
+/// Produced when a document transformation cannot be applied.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransformError {
+    /// Classification of the failure.
+    pub kind: TransformErrorKind,
+    /// Human-readable error description.
+    pub message: String,
+}
+
+impl fmt::Display for TransformError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for TransformError {}
+
+/// One place where a three-way [`merge`](crate::transform::merge) could not
+/// reconcile both sides' edits.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MergeConflict {
+    /// Document path of the conflicting value (e.g.,
+    /// `attack.indicators[1].pattern.contains`).
+    pub path: String,
+    /// The value in the common ancestor; `None` if absent.
+    pub base: Option<serde_json::Value>,
+    /// Our value; `None` if we removed it.
+    pub ours: Option<serde_json::Value>,
+    /// Their value; `None` if they removed it.
+    pub theirs: Option<serde_json::Value>,
+    /// Human-readable description of the conflict.
+    pub message: String,
+}
+
+impl fmt::Display for MergeConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// Produced when a three-way merge leaves conflicts to resolve by hand.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MergeConflicts {
+    /// Every conflict, in document order.
+    pub conflicts: Vec<MergeConflict>,
+}
+
+impl fmt::Display for MergeConflicts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} merge conflict(s)", self.conflicts.len())?;
+        for conflict in &self.conflicts {
+            write!(f, "\n  {}", conflict)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for MergeConflicts {}
+
+/// Error kind for [`verify_registries`](crate::spec::verify_registries)
+/// failures.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RegistryErrorKind {
+    /// A registry file could not be read.
+    Io,
+    /// A registry file is not in the expected shape.
+    Parse,
+}
+
+/// Produced when the specification's registry files cannot be read.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistryError {
+    /// Classification of the failure.
+    pub kind: RegistryErrorKind,
+    /// Human-readable error description.
+    pub message: String,
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+/// Error kind for [`interop::import`](crate::interop::import) failures.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportErrorKind {
+    /// The input is not well-formed YAML, JSON, or JSON Lines.
+    Parse,
+    /// The input is well-formed but contains nothing that can be converted.
+    Unsupported,
+}
+
+/// Produced when a third-party configuration or report cannot be imported.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportError {
+    /// Classification of the failure.
+    pub kind: ImportErrorKind,
+    /// Human-readable error description.
+    pub message: String,
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+/// Error kind for attachment decoding and resolution failures.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttachmentErrorKind {
+    /// `data` is not valid under the attachment's encoding.
+    InvalidEncoding,
+    /// The decoded content exceeds the size limit.
+    TooLarge,
+    /// A reference names an attachment the document does not declare.
+    UnknownAttachment,
+}
+
+/// Produced when an attachment cannot be decoded or a reference to one
+/// cannot be resolved.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttachmentError {
+    /// Classification of the failure.
+    pub kind: AttachmentErrorKind,
+    /// Name of the attachment.
+    pub name: String,
+    /// Human-readable error description.
+    pub message: String,
+}
+
+impl fmt::Display for AttachmentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "attachment '{}': {}", self.name, self.message)
+    }
+}
+
+impl std::error::Error for AttachmentError {}
+
+/// Error kind for compiled artifact loading failures.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactErrorKind {
+    /// The bytes are not a compiled artifact, or are truncated or corrupt.
+    InvalidFormat,
+    /// The artifact was written by an incompatible format or spec version.
+    UnsupportedVersion,
+}
+
+/// Produced when a compiled artifact cannot be loaded.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactError {
+    /// Classification of the failure.
+    pub kind: ArtifactErrorKind,
+    /// Human-readable error description.
+    pub message: String,
+}
+
+impl fmt::Display for ArtifactError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ArtifactError {}
+
+/// Error kind for [`verify_trace`](crate::engine::trace::verify_trace)
+/// failures.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TraceErrorKind {
+    /// The document cannot be executed (e.g., it has no actors).
+    Configuration,
+    /// An entry names an actor the document does not define.
+    UnknownActor,
+    /// An entry is timestamped before the entry preceding it.
+    OutOfOrder,
+    /// An entry records something the document does not do at that point.
+    Diverged,
+    /// The trace ends before outputs the document requires were recorded.
+    Incomplete,
+}
+
+/// Produced when an execution trace is inconsistent with its document.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceError {
+    /// Classification of the inconsistency.
+    pub kind: TraceErrorKind,
+    /// Index of the offending trace entry, if one is at fault.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entry: Option<usize>,
+    /// Human-readable error description.
+    pub message: String,
+}
+
+impl fmt::Display for TraceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.entry {
+            Some(entry) => write!(f, "trace entry {}: {}", entry, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for TraceError {}
+
+/// Error kind for [`FieldSelection`](crate::select::FieldSelection) parsing
+/// failures.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SelectionErrorKind {
+    /// The selection is empty or malformed (e.g., an unbalanced brace).
+    Syntax,
+    /// A selected field does not exist on the verdict type it applies to.
+    UnknownField,
+}
+
+/// Produced when a verdict field selection cannot be parsed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SelectionError {
+    /// Classification of the failure.
+    pub kind: SelectionErrorKind,
+    /// Human-readable error description.
+    pub message: String,
+}
+
+impl fmt::Display for SelectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for SelectionError {}
+
+/// Classification of catalog query failures.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryErrorKind {
+    /// The query is empty or malformed (e.g., an unbalanced parenthesis).
+    Syntax,
+    /// A filter names a field the query language does not know.
+    UnknownField,
+    /// A filter's value is not valid for its field (e.g., an unknown
+    /// severity), or its field does not support the comparison.
+    InvalidValue,
+}
+
+/// Produced when a catalog query cannot be parsed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueryError {
+    /// Classification of the failure.
+    pub kind: QueryErrorKind,
+    /// Human-readable error description.
+    pub message: String,
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+/// Produced when a JSONPath engine rejects or cannot evaluate a query.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonPathError {
+    /// Human-readable error description.
+    pub message: String,
+}
+
+impl fmt::Display for JsonPathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for JsonPathError {}
+
 /// Serialization error.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SerializeError {
@@ -201,6 +851,69 @@ impl fmt::Display for SerializeError {
 
 impl std::error::Error for SerializeError {}
 
+/// Error kind for [`audit`](crate::audit) failures.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditErrorKind {
+    /// The sink could not store the entry.
+    Sink,
+    /// An entry could not be encoded or decoded.
+    Malformed,
+    /// A hash-chained log has a gap, a reordered entry, or an entry whose
+    /// hash does not match its contents.
+    ChainBroken,
+}
+
+/// Produced when an audit entry cannot be recorded, read, or verified.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditError {
+    /// Classification of the failure.
+    pub kind: AuditErrorKind,
+    /// Sequence number of the entry concerned, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence: Option<u64>,
+    /// Human-readable error description.
+    pub message: String,
+}
+
+impl fmt::Display for AuditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.sequence {
+            Some(sequence) => write!(f, "entry {}: {}", sequence, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for AuditError {}
+
+/// Error kind for [`sharing`](crate::sharing) policy refusals.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SharingErrorKind {
+    /// The document is marked above the policy's limit.
+    Restricted,
+    /// The document's marking could not be read.
+    Unreadable,
+}
+
+/// Produced when a sharing policy refuses a document.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SharingError {
+    /// Classification of the failure.
+    pub kind: SharingErrorKind,
+    /// Human-readable error description.
+    pub message: String,
+}
+
+impl fmt::Display for SharingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for SharingError {}
+
 /// Combined error type for the `load` entry point.
 #[derive(Clone, Debug)]
 pub enum OATFError {
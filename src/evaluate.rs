@@ -5,23 +5,47 @@
 
 use crate::enums::*;
 use crate::error::*;
-use crate::primitives::{evaluate_condition, resolve_simple_path, resolve_wildcard_path};
+use crate::primitives::{
+    evaluate_capture, evaluate_condition, evaluate_predicate, resolve_simple_path_ref,
+    resolve_wildcard_path, resolve_wildcard_path_ref,
+};
 use crate::types::*;
 use serde_json::Value;
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+
+pub use cache::{CacheMetrics, CacheStats, CachedGenerationProvider};
+pub use capabilities::{CapabilitySet, capability_gap, unsupported_indicators};
+pub use cel_subset::CelSubsetEvaluator;
+pub use regression::{RegressionReport, compare_runs, compare_runs_with};
+
+pub mod cache;
+pub mod capabilities;
+pub mod cel_subset;
+pub mod config;
+#[cfg(feature = "prefilter")]
+pub mod prefilter;
+pub mod regression;
+pub mod stream;
 
 // ─── §6.1 CelEvaluator ─────────────────────────────────────────────────────
 
 /// Extension point for CEL expression evaluation.
 ///
 /// SDKs SHOULD ship a default implementation when a production-quality
-/// CEL library is available. See [`DefaultCelEvaluator`] (requires `cel-eval` feature).
+/// CEL library is available. See [`DefaultCelEvaluator`] (requires `cel-eval` feature);
+/// without it, [`CelSubsetEvaluator`] evaluates a small subset of CEL.
 pub trait CelEvaluator {
     /// Evaluates a CEL expression against a context of named variables.
     ///
     /// `context` is a JSON object where each key is a variable name available
     /// in the CEL expression. Returns the expression result or an error.
     fn evaluate(&self, expression: &str, context: &Value) -> Result<Value, EvaluationError>;
+
+    /// What this evaluator supports; unrestricted unless overridden.
+    fn capabilities(&self) -> &CapabilitySet {
+        &CapabilitySet::UNRESTRICTED
+    }
 }
 
 // ─── §6.2 SemanticEvaluator ─────────────────────────────────────────────────
@@ -42,6 +66,11 @@ pub trait SemanticEvaluator {
         threshold: Option<f64>,
         examples: Option<&SemanticExamples>,
     ) -> Result<f64, EvaluationError>;
+
+    /// What this evaluator supports; unrestricted unless overridden.
+    fn capabilities(&self) -> &CapabilitySet {
+        &CapabilitySet::UNRESTRICTED
+    }
 }
 
 // ─── §6.3 GenerationProvider ────────────────────────────────────────────────
@@ -50,6 +79,7 @@ pub trait SemanticEvaluator {
 ///
 /// SDKs MUST NOT ship a default implementation (SDK spec §6.3).
 /// Used by adversarial tools to execute `synthesize` blocks.
+/// [`CachedGenerationProvider`] reuses the output of repeated calls.
 pub trait GenerationProvider {
     /// Generates adversarial content from a prompt for the given protocol.
     fn generate(
@@ -58,6 +88,26 @@ pub trait GenerationProvider {
         protocol: &str,
         response_context: &Value,
     ) -> Result<Value, GenerationError>;
+
+    /// What this provider supports; unrestricted unless overridden.
+    fn capabilities(&self) -> &CapabilitySet {
+        &CapabilitySet::UNRESTRICTED
+    }
+}
+
+impl<P: GenerationProvider + ?Sized> GenerationProvider for Box<P> {
+    fn generate(
+        &self,
+        prompt: &str,
+        protocol: &str,
+        response_context: &Value,
+    ) -> Result<Value, GenerationError> {
+        (**self).generate(prompt, protocol, response_context)
+    }
+
+    fn capabilities(&self) -> &CapabilitySet {
+        (**self).capabilities()
+    }
 }
 
 // ─── Default CEL Evaluator (behind `cel-eval` feature) ──────────────────────
@@ -195,25 +245,41 @@ fn cel_to_json(value: &cel::Value) -> Value {
 ///
 /// Returns `Ok(true)` if any resolved value matches the condition.
 /// Returns `Ok(false)` if no values match or if the target resolves to nothing.
+/// See [`find_pattern_match`] for the value that matched.
 pub fn evaluate_pattern(pattern: &PatternMatch, message: &Value) -> Result<bool, EvaluationError> {
+    Ok(find_pattern_match(pattern, message)?.is_some())
+}
+
+/// The first value a pattern's target resolves to that satisfies its
+/// condition, from [`find_pattern_match`].
+#[must_use]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PatternEvidence<'a> {
+    /// Position of the value among those the target resolved to.
+    pub index: usize,
+    /// The value, borrowed from the message unless a `!json` or `!url`
+    /// decoder produced it.
+    pub value: Cow<'a, Value>,
+}
+
+/// [`evaluate_pattern`], returning the matching value instead of a flag.
+/// The message is only borrowed: resolved values are not copied.
+pub fn find_pattern_match<'a>(
+    pattern: &PatternMatch,
+    message: &'a Value,
+) -> Result<Option<PatternEvidence<'a>>, EvaluationError> {
     let target = pattern.target.as_deref().unwrap_or("");
     let condition = match &pattern.condition {
         Some(c) => c,
-        None => return Ok(false),
+        None => return Ok(None),
     };
 
-    let resolved = resolve_wildcard_path(target, message);
-    if resolved.is_empty() {
-        return Ok(false);
-    }
-
-    for value in &resolved {
-        if evaluate_condition(condition, value) {
-            return Ok(true);
-        }
-    }
-
-    Ok(false)
+    Ok(resolve_wildcard_path_ref(target, message)
+        .into_values()
+        .into_iter()
+        .enumerate()
+        .find(|(_, value)| evaluate_condition(condition, value))
+        .map(|(index, value)| PatternEvidence { index, value }))
 }
 
 // ─── §4.3 evaluate_expression ───────────────────────────────────────────────
@@ -236,7 +302,9 @@ pub fn evaluate_expression(
     // Resolve variables
     if let Some(vars) = &expression.variables {
         for (name, path) in vars {
-            let resolved = resolve_simple_path(path, message).unwrap_or(Value::Null);
+            let resolved = resolve_simple_path_ref(path, message)
+                .cloned()
+                .unwrap_or(Value::Null);
             context.insert(name.clone(), resolved);
         }
     }
@@ -260,11 +328,103 @@ pub fn evaluate_expression(
 
 /// Top-level indicator evaluation. Dispatches to the appropriate evaluator
 /// and wraps the result in an [`IndicatorVerdict`].
+///
+/// A match is downgraded to `matched_likely_benign` when the message
+/// satisfies the `when` predicate of one of the indicator's
+/// `false_positives`; the evidence names the benign case.
+///
+/// Matches carry the values of the indicator's `capture` entries (see
+/// [`compute_verdict`] for how `correlation.key` uses them).
+///
+/// `message` may be a raw JSON-RPC message: for registered surfaces it is
+/// first reduced to the payload the surface's target is relative to (see
+/// [`SurfaceTextExtractor::payload`]). Other values are used as given.
+///
+/// A bare message has no sender, so the indicator's `origin` predicate is
+/// not checked; use [`evaluate_indicator_event`] to take it into account.
 pub fn evaluate_indicator(
     indicator: &Indicator,
     message: &Value,
     cel_evaluator: Option<&dyn CelEvaluator>,
     semantic_evaluator: Option<&dyn SemanticEvaluator>,
+) -> IndicatorVerdict {
+    let message =
+        surface_text_extractor(&indicator.surface).map_or(message, |e| e.payload(message));
+    let mut verdict = evaluate_detection(indicator, message, cel_evaluator, semantic_evaluator);
+    if verdict.result != IndicatorResult::Matched {
+        return verdict;
+    }
+    let benign = indicator.false_positives.iter().flatten().find(|fp| {
+        fp.when
+            .as_ref()
+            .is_some_and(|when| evaluate_predicate(when, message))
+    });
+    if let Some(fp) = benign {
+        verdict.result = IndicatorResult::MatchedLikelyBenign;
+        verdict.evidence = Some(format!("likely benign: {}", fp.description));
+    }
+    verdict.captures = capture_values(indicator, message);
+    verdict
+}
+
+/// [`evaluate_indicator`] against a protocol event's content, first checking
+/// the indicator's `origin` predicate against the event's [`Origin`]. An
+/// event whose origin does not satisfy the predicate is `not_matched`, with
+/// evidence naming the origin, whatever its content.
+pub fn evaluate_indicator_event(
+    indicator: &Indicator,
+    event: &ProtocolEvent,
+    cel_evaluator: Option<&dyn CelEvaluator>,
+    semantic_evaluator: Option<&dyn SemanticEvaluator>,
+) -> IndicatorVerdict {
+    if let Some(origin) = &indicator.origin
+        && !crate::primitives::evaluate_origin(origin, event.origin.as_ref())
+    {
+        return IndicatorVerdict {
+            indicator_id: indicator.id.clone().unwrap_or_default(),
+            result: IndicatorResult::NotMatched,
+            timestamp: None,
+            evidence: Some(format!(
+                "event origin {} does not satisfy the indicator's origin",
+                origin_text(event.origin.as_ref())
+            )),
+            source: None,
+            captures: None,
+            skip_reason: None,
+        };
+    }
+    evaluate_indicator(indicator, &event.content, cel_evaluator, semantic_evaluator)
+}
+
+/// `role` or `role (endpoint)`, or `unknown`.
+fn origin_text(origin: Option<&Origin>) -> String {
+    match origin {
+        None => "unknown".to_string(),
+        Some(Origin {
+            role,
+            endpoint: Some(endpoint),
+            ..
+        }) => format!("{} ({})", role.as_str(), endpoint),
+        Some(origin) => origin.role.as_str().to_string(),
+    }
+}
+
+/// Values of the indicator's `capture` entries that resolve in `message`.
+fn capture_values(indicator: &Indicator, message: &Value) -> Option<BTreeMap<String, String>> {
+    let captures: BTreeMap<String, String> = indicator
+        .capture
+        .iter()
+        .flatten()
+        .filter_map(|c| Some((c.name.clone(), evaluate_capture(c, message)?)))
+        .collect();
+    (!captures.is_empty()).then_some(captures)
+}
+
+fn evaluate_detection(
+    indicator: &Indicator,
+    message: &Value,
+    cel_evaluator: Option<&dyn CelEvaluator>,
+    semantic_evaluator: Option<&dyn SemanticEvaluator>,
 ) -> IndicatorVerdict {
     let indicator_id = indicator.id.clone().unwrap_or_default();
 
@@ -277,6 +437,8 @@ pub fn evaluate_indicator(
                 timestamp: None,
                 evidence: None,
                 source: None,
+                captures: None,
+                skip_reason: None,
             },
             Ok(false) => IndicatorVerdict {
                 indicator_id,
@@ -284,6 +446,8 @@ pub fn evaluate_indicator(
                 timestamp: None,
                 evidence: None,
                 source: None,
+                captures: None,
+                skip_reason: None,
             },
             Err(e) => IndicatorVerdict {
                 indicator_id,
@@ -291,66 +455,117 @@ pub fn evaluate_indicator(
                 timestamp: None,
                 evidence: Some(e.message),
                 source: None,
+                captures: None,
+                skip_reason: None,
             },
         }
     } else if let Some(ref expr) = indicator.expression {
         // Expression dispatch
-        match cel_evaluator {
-            None => IndicatorVerdict {
+        let Some(cel_eval) = cel_evaluator else {
+            return skipped(
                 indicator_id,
-                result: IndicatorResult::Skipped,
+                SkipReason::MissingEvaluator {
+                    evaluator: EvaluatorKind::Cel,
+                },
+            );
+        };
+        let oversized = cel_eval.capabilities().max_input_bytes.and_then(|_| {
+            let size = serde_json::to_string(message).map_or(0, |s| s.len());
+            cel_eval.capabilities().check_input(size)
+        });
+        if let Some(reason) = capability_gap(indicator, cel_evaluator, None).or(oversized) {
+            return skipped(indicator_id, reason);
+        }
+        match evaluate_expression(expr, message, cel_eval) {
+            Ok(true) => IndicatorVerdict {
+                indicator_id,
+                result: IndicatorResult::Matched,
                 timestamp: None,
-                evidence: Some("CEL evaluator not available".to_string()),
+                evidence: None,
                 source: None,
+                captures: None,
+                skip_reason: None,
             },
-            Some(cel_eval) => match evaluate_expression(expr, message, cel_eval) {
-                Ok(true) => IndicatorVerdict {
-                    indicator_id,
-                    result: IndicatorResult::Matched,
-                    timestamp: None,
-                    evidence: None,
-                    source: None,
-                },
-                Ok(false) => IndicatorVerdict {
-                    indicator_id,
-                    result: IndicatorResult::NotMatched,
-                    timestamp: None,
-                    evidence: None,
-                    source: None,
-                },
-                Err(e) => IndicatorVerdict {
-                    indicator_id,
-                    result: IndicatorResult::Error,
-                    timestamp: None,
-                    evidence: Some(e.message),
-                    source: None,
-                },
+            Ok(false) => IndicatorVerdict {
+                indicator_id,
+                result: IndicatorResult::NotMatched,
+                timestamp: None,
+                evidence: None,
+                source: None,
+                captures: None,
+                skip_reason: None,
             },
-        }
-    } else if let Some(ref semantic) = indicator.semantic {
-        // Semantic dispatch
-        match semantic_evaluator {
-            None => IndicatorVerdict {
+            // The evaluator cannot run this expression: the indicator is not
+            // evaluated rather than in error.
+            Err(e) if e.kind == EvaluationErrorKind::Unsupported => IndicatorVerdict {
                 indicator_id,
                 result: IndicatorResult::Skipped,
                 timestamp: None,
-                evidence: Some("Semantic evaluator not available".to_string()),
+                evidence: Some(e.message),
+                source: None,
+                captures: None,
+                skip_reason: Some(SkipReason::UnsupportedExpression),
+            },
+            Err(e) => IndicatorVerdict {
+                indicator_id,
+                result: IndicatorResult::Error,
+                timestamp: None,
+                evidence: Some(e.message),
                 source: None,
+                captures: None,
+                skip_reason: None,
             },
-            Some(sem_eval) => evaluate_semantic(semantic, message, sem_eval, &indicator_id),
         }
+    } else if let Some(ref semantic) = indicator.semantic {
+        // Semantic dispatch
+        let Some(sem_eval) = semantic_evaluator else {
+            return skipped(
+                indicator_id,
+                SkipReason::MissingEvaluator {
+                    evaluator: EvaluatorKind::Semantic,
+                },
+            );
+        };
+        if let Some(reason) = capability_gap(indicator, None, semantic_evaluator) {
+            return skipped(indicator_id, reason);
+        }
+        evaluate_semantic(semantic, message, sem_eval, &indicator_id)
+    } else if indicator.frequency.is_some() {
+        // Frequency indicators count events over time; one message says nothing
+        skipped(
+            indicator_id,
+            SkipReason::MissingEvaluator {
+                evaluator: EvaluatorKind::Stream,
+            },
+        )
     } else {
         // No detection key present
         IndicatorVerdict {
             indicator_id,
             result: IndicatorResult::Error,
             timestamp: None,
-            evidence: Some("No detection key (pattern/expression/semantic) present".to_string()),
+            evidence: Some(
+                "No detection key (pattern/expression/semantic/frequency) present".to_string(),
+            ),
             source: None,
+            captures: None,
+            skip_reason: None,
         }
     }
 }
 
+fn skipped(indicator_id: String, reason: SkipReason) -> IndicatorVerdict {
+    IndicatorVerdict {
+        indicator_id,
+        result: IndicatorResult::Skipped,
+        timestamp: None,
+        evidence: Some(reason.to_string()),
+        source: None,
+        captures: None,
+        skip_reason: Some(reason),
+    }
+}
+
 /// Semantic indicator evaluation per §4.4.
 fn evaluate_semantic(
     semantic: &SemanticMatch,
@@ -359,7 +574,7 @@ fn evaluate_semantic(
     indicator_id: &str,
 ) -> IndicatorVerdict {
     let target = semantic.target.as_deref().unwrap_or("");
-    let resolved = resolve_wildcard_path(target, message);
+    let resolved = resolve_wildcard_path_ref(target, message);
 
     if resolved.is_empty() {
         return IndicatorVerdict {
@@ -368,14 +583,19 @@ fn evaluate_semantic(
             timestamp: None,
             evidence: None,
             source: None,
+            captures: None,
+            skip_reason: None,
         };
     }
 
     let threshold = semantic.threshold.unwrap_or(0.7);
     let mut highest_score: f64 = 0.0;
 
-    for value in &resolved {
-        let text = value_to_text(value);
+    for value in resolved.iter() {
+        let text = canonical_text(value);
+        if let Some(reason) = evaluator.capabilities().check_input(text.len()) {
+            return skipped(indicator_id.to_string(), reason);
+        }
         match evaluator.evaluate(
             &text,
             &semantic.intent,
@@ -395,6 +615,8 @@ fn evaluate_semantic(
                     timestamp: None,
                     evidence: Some(e.message),
                     source: None,
+                    captures: None,
+                    skip_reason: None,
                 };
             }
         }
@@ -407,6 +629,8 @@ fn evaluate_semantic(
             timestamp: None,
             evidence: Some(format!("{:.2}", highest_score)),
             source: None,
+            captures: None,
+            skip_reason: None,
         }
     } else {
         IndicatorVerdict {
@@ -415,18 +639,99 @@ fn evaluate_semantic(
             timestamp: None,
             evidence: Some(format!("{:.2}", highest_score)),
             source: None,
+            captures: None,
+            skip_reason: None,
         }
     }
 }
 
-/// Serialize a value to text for semantic evaluation.
-fn value_to_text(value: &Value) -> String {
+// ─── Surface text extraction ────────────────────────────────────────────────
+
+/// Knows where a surface lives in a raw protocol message and how to read its
+/// canonical text, using the surface registry's default targets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SurfaceTextExtractor {
+    /// The surface name, e.g. `tool_description`.
+    pub surface: &'static str,
+    /// The protocol the surface belongs to.
+    pub protocol: &'static str,
+    /// Path of the surface within the message payload.
+    pub target: &'static str,
+}
+
+/// Look up the extractor for a registered surface.
+/// Returns None for unknown and `x-` extension surfaces.
+pub fn surface_text_extractor(surface: &str) -> Option<SurfaceTextExtractor> {
+    crate::surface::lookup_surface(surface).map(|entry| SurfaceTextExtractor {
+        surface: entry.surface,
+        protocol: entry.protocol,
+        target: entry.default_target,
+    })
+}
+
+impl SurfaceTextExtractor {
+    /// The part of `message` the surface's target is relative to.
+    ///
+    /// A JSON-RPC envelope (an object with a `jsonrpc` member) is reduced to
+    /// its `result`, or its `params` for requests and notifications — unless
+    /// the target itself starts at `params` or `result`, as for
+    /// `server_notification` or `elicitation_response`. Anything else is
+    /// assumed to be the payload already and returned unchanged.
+    pub fn payload<'a>(&self, message: &'a Value) -> &'a Value {
+        let Some(obj) = message.as_object().filter(|o| o.contains_key("jsonrpc")) else {
+            return message;
+        };
+        let first = self.target.split(['.', '[', '!']).next().unwrap_or("");
+        if first == "params" || first == "result" {
+            return message;
+        }
+        obj.get("result")
+            .or_else(|| obj.get("params"))
+            .unwrap_or(message)
+    }
+
+    /// The surface's values in `message`, which may be a raw protocol message.
+    pub fn values(&self, message: &Value) -> Vec<Value> {
+        resolve_wildcard_path(self.target, self.payload(message))
+    }
+
+    /// The canonical text of each of the surface's values in `message`.
+    pub fn texts(&self, message: &Value) -> Vec<String> {
+        resolve_wildcard_path_ref(self.target, self.payload(message))
+            .iter()
+            .map(canonical_text)
+            .collect()
+    }
+}
+
+/// The text a protocol value carries, for semantic evaluation.
+///
+/// Strings are used as-is; MCP content items, resource contents, and A2A
+/// text parts yield their `text`; prompt messages yield the text of their
+/// `content`; A2A messages and artifacts yield the text of their `parts`,
+/// one per line. Other values are serialized as JSON.
+pub fn canonical_text(value: &Value) -> String {
     match value {
         Value::String(s) => s.clone(),
         Value::Null => "null".to_string(),
         Value::Bool(b) => b.to_string(),
         Value::Number(n) => n.to_string(),
-        _ => serde_json::to_string(value).unwrap_or_default(),
+        Value::Object(obj) => {
+            if let Some(Value::String(text)) = obj.get("text") {
+                text.clone()
+            } else if let Some(content @ Value::Object(_)) = obj.get("content") {
+                canonical_text(content)
+            } else if let Some(Value::Array(parts)) = obj.get("parts") {
+                parts
+                    .iter()
+                    .filter_map(|part| part.get("text").and_then(Value::as_str))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            } else {
+                serde_json::to_string(value).unwrap_or_default()
+            }
+        }
+        Value::Array(_) => serde_json::to_string(value).unwrap_or_default(),
     }
 }
 
@@ -438,7 +743,19 @@ fn value_to_text(value: &Value) -> String {
 /// - `any` (default): error > any matched=exploited > not_exploited
 /// - `all`: error > all matched=exploited > mixed=partial > not_exploited
 ///
-/// Skipped verdicts are treated as not_matched for verdict computation.
+/// When `correlation.stages` is set it replaces `logic`: error > a matched
+/// indicator in every listed stage=exploited > any matched=partial >
+/// not_exploited.
+///
+/// When `correlation.key` names captures, matches only combine if their
+/// captured values for those names agree: matches outside the agreeing group
+/// that gives the strongest result are reported as not matched, so unrelated
+/// traffic is not correlated into one verdict.
+///
+/// Skipped verdicts are treated as not_matched for verdict computation, and
+/// so are `matched_likely_benign` verdicts, which are counted as not matched
+/// in the summary. When indicators have stages (or `correlation.stages` is
+/// set), the verdict also carries per-stage counts.
 pub fn compute_verdict(
     attack: &Attack,
     indicator_verdicts: &HashMap<String, IndicatorVerdict>,
@@ -450,109 +767,483 @@ pub fn compute_verdict(
                 attack_id: attack.id.clone(),
                 result: AttackResult::Error,
                 indicator_verdicts: vec![],
-                evaluation_summary: EvaluationSummary {
-                    matched: 0,
-                    not_matched: 0,
-                    error: 0,
-                    skipped: 0,
-                },
+                evaluation_summary: empty_summary(),
+                stages: None,
                 timestamp: None,
                 source: None,
             };
         }
     };
 
-    let logic = attack
-        .correlation
-        .as_ref()
+    let correlation = attack.correlation.as_ref();
+    let mut verdicts: Vec<IndicatorVerdict> = indicators
+        .iter()
+        .map(|indicator| {
+            let ind_id = indicator.id.as_deref().unwrap_or("");
+            match indicator_verdicts.get(ind_id) {
+                Some(v) => v.clone(),
+                // Missing entry → treated as skipped
+                None => skipped(ind_id.to_string(), SkipReason::NotEvaluated),
+            }
+        })
+        .collect();
+    if let Some(key) = correlation.and_then(|c| c.key.as_ref())
+        && !key.is_empty()
+    {
+        verdicts = agree_on_key(indicators, verdicts, key, correlation);
+    }
+    let (result, summary, stage_summaries) = correlate(indicators, &verdicts, correlation);
+
+    AttackVerdict {
+        attack_id: attack.id.clone(),
+        result,
+        indicator_verdicts: verdicts,
+        evaluation_summary: summary,
+        stages: (!stage_summaries.is_empty()).then_some(stage_summaries),
+        timestamp: None,
+        source: None,
+    }
+}
+
+/// The attack result, summary, and stage summaries for `verdicts`, one per
+/// indicator.
+fn correlate(
+    indicators: &[Indicator],
+    verdicts: &[IndicatorVerdict],
+    correlation: Option<&Correlation>,
+) -> (AttackResult, EvaluationSummary, Vec<StageSummary>) {
+    let logic = correlation
         .and_then(|c| c.logic.as_ref())
         .unwrap_or(&CorrelationLogic::Any);
+    let required_stages = correlation.and_then(|c| c.stages.as_ref());
+
+    let mut summary = empty_summary();
+    let mut stage_summaries: Vec<StageSummary> = required_stages
+        .into_iter()
+        .flatten()
+        .map(|stage| StageSummary {
+            stage: stage.clone(),
+            summary: empty_summary(),
+        })
+        .collect();
+
+    for (indicator, verdict) in indicators.iter().zip(verdicts) {
+        count_result(&mut summary, &verdict.result);
+        if let Some(stage) = &indicator.stage {
+            let index = match stage_summaries.iter().position(|s| &s.stage == stage) {
+                Some(index) => index,
+                None => {
+                    stage_summaries.push(StageSummary {
+                        stage: stage.clone(),
+                        summary: empty_summary(),
+                    });
+                    stage_summaries.len() - 1
+                }
+            };
+            count_result(&mut stage_summaries[index].summary, &verdict.result);
+        }
+    }
 
-    let mut matched: i64 = 0;
-    let mut not_matched: i64 = 0;
-    let mut error: i64 = 0;
-    let mut skipped: i64 = 0;
-    let mut collected_verdicts = Vec::new();
-
-    for indicator in indicators {
-        let ind_id = indicator.id.as_deref().unwrap_or("");
-        let verdict = indicator_verdicts.get(ind_id);
-
-        match verdict {
-            Some(v) => {
-                match v.result {
-                    IndicatorResult::Matched => matched += 1,
-                    IndicatorResult::NotMatched => not_matched += 1,
-                    IndicatorResult::Error => error += 1,
-                    IndicatorResult::Skipped => skipped += 1,
+    let EvaluationSummary {
+        matched,
+        not_matched,
+        error,
+        skipped,
+    } = summary;
+
+    let result = if skipped > 0 && matched == 0 && not_matched == 0 && error == 0 {
+        // All-skipped → error: no evaluation occurred (§4.5)
+        AttackResult::Error
+    } else if error > 0 {
+        AttackResult::Error
+    } else if let Some(required) = required_stages {
+        let stage_matched = |stage: &String| {
+            stage_summaries
+                .iter()
+                .any(|s| &s.stage == stage && s.summary.matched > 0)
+        };
+        if matched > 0 && required.iter().all(stage_matched) {
+            AttackResult::Exploited
+        } else if matched > 0 {
+            AttackResult::Partial
+        } else {
+            AttackResult::NotExploited
+        }
+    } else {
+        match logic {
+            CorrelationLogic::Any => {
+                if matched > 0 {
+                    AttackResult::Exploited
+                } else {
+                    AttackResult::NotExploited
                 }
-                collected_verdicts.push(v.clone());
             }
-            None => {
-                // Missing entry → treated as skipped
-                skipped += 1;
-                collected_verdicts.push(IndicatorVerdict {
-                    indicator_id: ind_id.to_string(),
-                    result: IndicatorResult::Skipped,
-                    timestamp: None,
-                    evidence: Some("No evaluation result provided".to_string()),
-                    source: None,
-                });
+            CorrelationLogic::All => {
+                if matched > 0 && not_matched == 0 && skipped == 0 {
+                    AttackResult::Exploited
+                } else if matched > 0 {
+                    AttackResult::Partial
+                } else {
+                    AttackResult::NotExploited
+                }
+            }
+        }
+    };
+
+    (result, summary, stage_summaries)
+}
+
+/// Keep only the matches whose captured `key` values agree, choosing the
+/// group of agreeing matches that yields the strongest result (the first
+/// such group on ties). Other matches become `not_matched`.
+///
+/// An indicator constrains only the key names it captures; a match that
+/// captures none of them agrees with every group, and a match that failed
+/// to capture a key name it declares agrees with none.
+fn agree_on_key(
+    indicators: &[Indicator],
+    verdicts: Vec<IndicatorVerdict>,
+    key: &[String],
+    correlation: Option<&Correlation>,
+) -> Vec<IndicatorVerdict> {
+    // None: not a match. Some(None): cannot agree. Some(Some(b)): key bindings.
+    let bindings: Vec<Option<Option<BTreeMap<&str, &str>>>> = indicators
+        .iter()
+        .zip(&verdicts)
+        .map(|(indicator, verdict)| {
+            if verdict.result != IndicatorResult::Matched {
+                return None;
             }
+            let declared = indicator
+                .capture
+                .iter()
+                .flatten()
+                .map(|c| c.name.as_str())
+                .filter(|name| key.iter().any(|k| k == name));
+            let captured = verdict.captures.as_ref();
+            Some(
+                declared
+                    .map(|name| Some((name, captured?.get(name)?.as_str())))
+                    .collect::<Option<BTreeMap<_, _>>>(),
+            )
+        })
+        .collect();
+
+    let mut groups: Vec<&BTreeMap<&str, &str>> = Vec::new();
+    for binding in bindings.iter().flatten().flatten() {
+        if !binding.is_empty() && !groups.contains(&binding) {
+            groups.push(binding);
         }
     }
+    let unconstrained = BTreeMap::new();
+    if groups.is_empty() {
+        groups.push(&unconstrained);
+    }
 
-    // All-skipped → error: no evaluation occurred (§4.5)
-    if skipped > 0 && matched == 0 && not_matched == 0 && error == 0 {
-        return AttackVerdict {
-            attack_id: attack.id.clone(),
-            result: AttackResult::Error,
-            indicator_verdicts: collected_verdicts,
-            evaluation_summary: EvaluationSummary {
-                matched,
-                not_matched,
-                error,
-                skipped,
-            },
-            timestamp: None,
-            source: None,
+    let mut best: Option<(u8, Vec<IndicatorVerdict>)> = None;
+    for group in groups {
+        let agrees = |binding: &BTreeMap<&str, &str>| {
+            binding
+                .iter()
+                .all(|(name, value)| group.get(name).is_none_or(|v| v == value))
         };
+        let candidate: Vec<IndicatorVerdict> = verdicts
+            .iter()
+            .zip(&bindings)
+            .map(|(verdict, binding)| match binding {
+                Some(Some(binding)) if agrees(binding) => verdict.clone(),
+                Some(_) => IndicatorVerdict {
+                    result: IndicatorResult::NotMatched,
+                    evidence: Some(format!(
+                        "captures do not agree with the correlated matches on {}",
+                        key.join(", ")
+                    )),
+                    ..verdict.clone()
+                },
+                None => verdict.clone(),
+            })
+            .collect();
+        let rank = match correlate(indicators, &candidate, correlation).0 {
+            AttackResult::Exploited => 3,
+            AttackResult::Partial => 2,
+            AttackResult::NotExploited => 1,
+            AttackResult::Error => 0,
+        };
+        if best.as_ref().is_none_or(|(r, _)| rank > *r) {
+            best = Some((rank, candidate));
+        }
     }
+    best.map(|(_, verdicts)| verdicts).unwrap_or_default()
+}
 
-    let result = match logic {
-        CorrelationLogic::Any => {
-            if error > 0 {
-                AttackResult::Error
-            } else if matched > 0 {
-                AttackResult::Exploited
-            } else {
-                AttackResult::NotExploited
-            }
+fn empty_summary() -> EvaluationSummary {
+    EvaluationSummary {
+        matched: 0,
+        not_matched: 0,
+        error: 0,
+        skipped: 0,
+    }
+}
+
+fn count_result(summary: &mut EvaluationSummary, result: &IndicatorResult) {
+    match result {
+        IndicatorResult::Matched => summary.matched += 1,
+        IndicatorResult::NotMatched | IndicatorResult::MatchedLikelyBenign => {
+            summary.not_matched += 1
         }
-        CorrelationLogic::All => {
-            if error > 0 {
-                AttackResult::Error
-            } else if matched > 0 && not_matched == 0 && skipped == 0 {
-                AttackResult::Exploited
-            } else if matched > 0 {
-                AttackResult::Partial
-            } else {
-                AttackResult::NotExploited
+        IndicatorResult::Error => summary.error += 1,
+        IndicatorResult::Skipped => summary.skipped += 1,
+    }
+}
+
+// ─── Document scanning ──────────────────────────────────────────────────────
+
+pub use crate::projection::{ProjectedMessage, project_document};
+
+/// Evaluate an indicator against the content of an OATF document rather than
+/// live traffic — for example, to scan a third-party attack feed for
+/// malicious synthesize prompts or exfiltration URLs before importing it.
+///
+/// The indicator is evaluated against every [`project_document`] message
+/// whose surfaces include the indicator's surface. The verdict is `Matched`
+/// as soon as one message matches, with the message's path as evidence;
+/// `Error` or `Skipped` if evaluation failed or was skipped for a message and
+/// nothing matched; otherwise `NotMatched`. Like [`evaluate_indicator`], the
+/// indicator must be in normalized standard form.
+pub fn evaluate_indicator_on_document(
+    indicator: &Indicator,
+    doc: &Document,
+    cel_evaluator: Option<&dyn CelEvaluator>,
+    semantic_evaluator: Option<&dyn SemanticEvaluator>,
+) -> IndicatorVerdict {
+    let mut fallback = IndicatorVerdict {
+        indicator_id: indicator.id.clone().unwrap_or_default(),
+        result: IndicatorResult::NotMatched,
+        timestamp: None,
+        evidence: None,
+        source: None,
+        captures: None,
+        skip_reason: None,
+    };
+    for projected in project_document(doc) {
+        if !projected.surfaces.contains(&indicator.surface.as_str()) {
+            continue;
+        }
+        let verdict = evaluate_indicator(
+            indicator,
+            &projected.message,
+            cel_evaluator,
+            semantic_evaluator,
+        );
+        match verdict.result {
+            IndicatorResult::Matched => {
+                return IndicatorVerdict {
+                    evidence: Some(projected.path),
+                    ..verdict
+                };
+            }
+            IndicatorResult::Error => fallback = verdict,
+            IndicatorResult::MatchedLikelyBenign if fallback.result != IndicatorResult::Error => {
+                fallback = verdict
+            }
+            IndicatorResult::Skipped if fallback.result == IndicatorResult::NotMatched => {
+                fallback = verdict
             }
+            _ => {}
+        }
+    }
+    fallback
+}
+
+// ─── Indicator dry run ──────────────────────────────────────────────────────
+
+/// Trace of evaluating one indicator fragment against one message with
+/// [`dry_run`].
+#[derive(Clone, Debug)]
+pub struct DryRunReport {
+    /// The indicator in normalized standard form, if the fragment parsed.
+    pub indicator: Option<Indicator>,
+    /// The part of the message the indicator is evaluated against: the
+    /// message itself, or its JSON-RPC payload for registered surfaces.
+    pub payload: Option<Value>,
+    /// The resolved pattern or semantic target, if any.
+    pub target: Option<String>,
+    /// The values the target resolved to in `payload`.
+    pub resolved: Vec<Value>,
+    /// The verdict, when the fragment is valid and the message parsed.
+    pub verdict: Option<IndicatorVerdict>,
+    /// Parse errors in the fragment or message, and validation errors in
+    /// the fragment with paths relative to it (e.g. `pattern.target`).
+    pub errors: Vec<OATFError>,
+}
+
+impl DryRunReport {
+    /// Whether the indicator matched the message.
+    pub fn matched(&self) -> bool {
+        self.verdict
+            .as_ref()
+            .is_some_and(|v| v.result == IndicatorResult::Matched)
+    }
+}
+
+/// Evaluate a standalone indicator fragment (the YAML of one
+/// `attack.indicators` entry) against a JSON message.
+///
+/// The fragment is validated and normalized as if it were the only
+/// indicator of an attack on its protocol — the `protocol` it declares, or
+/// else its surface's — so shorthand patterns are expanded and default
+/// targets resolved. Expressions use [`DefaultCelEvaluator`] when the
+/// `cel-eval` feature is enabled and [`CelSubsetEvaluator`] otherwise;
+/// semantic indicators are skipped. Use [`dry_run_with`] to supply
+/// evaluators.
+pub fn dry_run(indicator_yaml: &str, message_json: &str) -> DryRunReport {
+    #[cfg(feature = "cel-eval")]
+    let cel: Option<&dyn CelEvaluator> = Some(&DefaultCelEvaluator);
+    #[cfg(not(feature = "cel-eval"))]
+    let cel: Option<&dyn CelEvaluator> = Some(&CelSubsetEvaluator);
+    dry_run_with(indicator_yaml, message_json, cel, None)
+}
+
+/// [`dry_run`] with caller-supplied evaluators.
+pub fn dry_run_with(
+    indicator_yaml: &str,
+    message_json: &str,
+    cel_evaluator: Option<&dyn CelEvaluator>,
+    semantic_evaluator: Option<&dyn SemanticEvaluator>,
+) -> DryRunReport {
+    let mut report = DryRunReport {
+        indicator: None,
+        payload: None,
+        target: None,
+        resolved: Vec::new(),
+        verdict: None,
+        errors: Vec::new(),
+    };
+    let parse_error = |kind, message: String, path: Option<&str>| {
+        OATFError::Parse(ParseError {
+            kind,
+            message,
+            path: path.map(str::to_string),
+            line: None,
+            column: None,
+        })
+    };
+
+    let message = serde_json::from_str::<Value>(message_json).map_err(|e| {
+        parse_error(
+            ParseErrorKind::Syntax,
+            format!("malformed message: {}", e),
+            None,
+        )
+    });
+    let indicator = match parse_indicator_fragment(indicator_yaml) {
+        Ok(indicator) => Some(indicator),
+        Err(errors) => {
+            report.errors.extend(errors);
+            None
         }
     };
+    let message = match message {
+        Ok(message) => Some(message),
+        Err(e) => {
+            report.errors.push(e);
+            None
+        }
+    };
+    let (Some(indicator), Some(message)) = (indicator, message) else {
+        return report;
+    };
 
-    AttackVerdict {
-        attack_id: attack.id.clone(),
-        result,
-        indicator_verdicts: collected_verdicts,
-        evaluation_summary: EvaluationSummary {
-            matched,
-            not_matched,
-            error,
-            skipped,
+    let payload = surface_text_extractor(&indicator.surface)
+        .map_or(&message, |e| e.payload(&message))
+        .clone();
+    report.target = indicator
+        .pattern
+        .as_ref()
+        .and_then(|p| p.target.clone())
+        .or_else(|| indicator.semantic.as_ref().and_then(|s| s.target.clone()));
+    if let Some(target) = &report.target {
+        report.resolved = resolve_wildcard_path(target, &payload);
+    }
+    report.verdict = Some(evaluate_indicator(
+        &indicator,
+        &message,
+        cel_evaluator,
+        semantic_evaluator,
+    ));
+    report.payload = Some(payload);
+    report.indicator = Some(indicator);
+    report
+}
+
+/// Parse, validate, and normalize an indicator fragment by wrapping it in a
+/// minimal attack document.
+fn parse_indicator_fragment(input: &str) -> Result<Indicator, Vec<OATFError>> {
+    let fail = |kind, message: String| {
+        vec![OATFError::Parse(ParseError {
+            kind,
+            message,
+            path: None,
+            line: None,
+            column: None,
+        })]
+    };
+    let fragment: Value =
+        serde_saphyr::from_str(input).map_err(|e| fail(ParseErrorKind::Syntax, e.to_string()))?;
+    let Some(fields) = fragment.as_object() else {
+        return Err(fail(
+            ParseErrorKind::TypeMismatch,
+            "indicator must be a YAML mapping".to_string(),
+        ));
+    };
+    let protocol = fields
+        .get("protocol")
+        .and_then(Value::as_str)
+        .or_else(|| {
+            fields
+                .get("surface")
+                .and_then(Value::as_str)
+                .and_then(surface_text_extractor)
+                .map(|e| e.protocol)
+        })
+        .unwrap_or("mcp");
+
+    let document = serde_json::json!({
+        "oatf": "0.1",
+        "attack": {
+            "execution": {"mode": format!("{}_server", protocol), "state": {}},
+            "indicators": [fragment],
         },
-        timestamp: None,
-        source: None,
+    });
+    let doc = crate::parse::document_from_value(document).map_err(|mut e| {
+        e.path = e.path.map(|p| relative_fragment_path(&p));
+        vec![OATFError::Parse(e)]
+    })?;
+
+    let errors: Vec<OATFError> = crate::validate::validate(&doc)
+        .errors
+        .into_iter()
+        .filter(|e| e.path.starts_with("attack.indicators"))
+        .map(|mut e| {
+            e.path = relative_fragment_path(&e.path);
+            OATFError::Validation(e)
+        })
+        .collect();
+    if !errors.is_empty() {
+        return Err(errors);
     }
+
+    let doc = crate::normalize::normalize(doc);
+    Ok(doc
+        .attack
+        .indicators
+        .and_then(|mut indicators| indicators.pop())
+        .expect("the wrapper document has one indicator"))
+}
+
+fn relative_fragment_path(path: &str) -> String {
+    path.strip_prefix("attack.indicators[0]")
+        .or_else(|| path.strip_prefix("attack.indicators"))
+        .map(|rest| rest.trim_start_matches('.').to_string())
+        .unwrap_or_else(|| path.to_string())
 }
@@ -0,0 +1,227 @@
+//! Caching for [`GenerationProvider`]s.
+//!
+//! The same `synthesize` block runs in every phase that inherits it and in
+//! every run of an attack. A [`CachedGenerationProvider`] wraps any provider
+//! and answers repeated calls (same prompt, protocol, and response context)
+//! from memory until the entry's time to live passes. Entries beyond the
+//! capacity are evicted least recently used first. Failed generations are
+//! not cached.
+//!
+//! The cache is safe to share between threads: lookups hold a lock only
+//! while reading or updating the table, never while the wrapped provider
+//! generates, so concurrent misses do not wait on each other.
+
+use super::{CapabilitySet, GenerationProvider};
+use crate::clock::{Clock, SystemClock};
+use crate::error::GenerationError;
+use indexmap::IndexMap;
+use serde_json::Value;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Receives the events of a [`CachedGenerationProvider`], e.g. to export
+/// them as metrics. Every method defaults to doing nothing.
+pub trait CacheMetrics: Send + Sync {
+    /// A call was answered from the cache.
+    fn hit(&self, _protocol: &str) {}
+
+    /// A call was passed to the wrapped provider.
+    fn miss(&self, _protocol: &str) {}
+
+    /// An entry was dropped to stay within the capacity.
+    fn evicted(&self) {}
+}
+
+/// Counters describing a [`CachedGenerationProvider`]'s use.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Calls answered from the cache.
+    pub hits: usize,
+    /// Calls passed to the wrapped provider.
+    pub misses: usize,
+    /// Entries dropped to stay within the capacity.
+    pub evictions: usize,
+    /// Entries currently held, including any expired but not yet dropped.
+    pub entries: usize,
+}
+
+/// A [`GenerationProvider`] that remembers what the provider it wraps
+/// generated.
+///
+/// Defaults to 256 entries kept for 10 minutes of [`SystemClock`] time.
+pub struct CachedGenerationProvider<P> {
+    inner: P,
+    ttl: Duration,
+    max_entries: usize,
+    clock: Box<dyn Clock>,
+    metrics: Option<Box<dyn CacheMetrics>>,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    /// Entries by key hash, least recently used first.
+    entries: IndexMap<u64, Entry>,
+    stats: CacheStats,
+}
+
+struct Entry {
+    prompt: String,
+    protocol: String,
+    context: String,
+    value: Value,
+    expires: Duration,
+}
+
+impl Entry {
+    fn is_for(&self, prompt: &str, protocol: &str, context: &str) -> bool {
+        self.prompt == prompt && self.protocol == protocol && self.context == context
+    }
+}
+
+impl<P: GenerationProvider> CachedGenerationProvider<P> {
+    /// Wrap `inner` with the default capacity and time to live.
+    pub fn new(inner: P) -> Self {
+        CachedGenerationProvider {
+            inner,
+            ttl: Duration::from_secs(600),
+            max_entries: 256,
+            clock: Box::new(SystemClock::new()),
+            metrics: None,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// How long an entry is reused after it was generated.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// The most entries to keep. Zero disables caching.
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    /// Measure time to live with `clock` instead of the system clock.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+
+    /// Report hits, misses, and evictions to `metrics`.
+    pub fn with_metrics(mut self, metrics: impl CacheMetrics + 'static) -> Self {
+        self.metrics = Some(Box::new(metrics));
+        self
+    }
+
+    /// The wrapped provider.
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    /// Unwrap the provider, dropping the cache.
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+
+    /// Current counters.
+    pub fn stats(&self) -> CacheStats {
+        let state = self.lock();
+        CacheStats {
+            entries: state.entries.len(),
+            ..state.stats
+        }
+    }
+
+    /// Drop every entry. Counters are kept.
+    pub fn clear(&self) {
+        self.lock().entries.clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        // A panic elsewhere cannot leave the table inconsistent.
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn lookup(&self, key: u64, prompt: &str, protocol: &str, context: &str) -> Option<Value> {
+        let now = self.clock.now();
+        let mut state = self.lock();
+        let found = match state.entries.get_full(&key) {
+            Some((index, _, entry)) if entry.is_for(prompt, protocol, context) => {
+                (entry.expires > now).then_some(index)
+            }
+            _ => None,
+        };
+        let Some(index) = found else {
+            state.stats.misses += 1;
+            return None;
+        };
+        state.stats.hits += 1;
+        let last = state.entries.len() - 1;
+        state.entries.move_index(index, last);
+        state.entries.get_index(last).map(|(_, e)| e.value.clone())
+    }
+
+    fn store(&self, key: u64, entry: Entry) {
+        let now = self.clock.now();
+        let mut evicted = 0;
+        {
+            let mut state = self.lock();
+            state.entries.shift_remove(&key);
+            state.entries.retain(|_, e| e.expires > now);
+            while state.entries.len() >= self.max_entries && !state.entries.is_empty() {
+                state.entries.shift_remove_index(0);
+                evicted += 1;
+            }
+            state.stats.evictions += evicted;
+            state.entries.insert(key, entry);
+        }
+        if let Some(metrics) = &self.metrics {
+            (0..evicted).for_each(|_| metrics.evicted());
+        }
+    }
+}
+
+impl<P: GenerationProvider> GenerationProvider for CachedGenerationProvider<P> {
+    fn generate(
+        &self,
+        prompt: &str,
+        protocol: &str,
+        response_context: &Value,
+    ) -> Result<Value, GenerationError> {
+        let context = serde_json::to_string(response_context).unwrap_or_default();
+        let mut hasher = DefaultHasher::new();
+        (prompt, protocol, &context).hash(&mut hasher);
+        let key = hasher.finish();
+
+        if let Some(value) = self.lookup(key, prompt, protocol, &context) {
+            if let Some(metrics) = &self.metrics {
+                metrics.hit(protocol);
+            }
+            return Ok(value);
+        }
+        if let Some(metrics) = &self.metrics {
+            metrics.miss(protocol);
+        }
+        let value = self.inner.generate(prompt, protocol, response_context)?;
+        if self.max_entries > 0 {
+            let entry = Entry {
+                prompt: prompt.to_string(),
+                protocol: protocol.to_string(),
+                context,
+                value: value.clone(),
+                expires: self.clock.now() + self.ttl,
+            };
+            self.store(key, entry);
+        }
+        Ok(value)
+    }
+
+    fn capabilities(&self) -> &CapabilitySet {
+        self.inner.capabilities()
+    }
+}
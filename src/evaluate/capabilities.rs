@@ -0,0 +1,180 @@
+//! Evaluator capabilities and the indicator requirements checked against
+//! them.
+//!
+//! CEL evaluators, semantic evaluators, and generation providers describe
+//! what they support with a [`CapabilitySet`] (their `capabilities` method;
+//! unrestricted unless overridden). Before evaluating an indicator,
+//! [`evaluate_indicator`](super::evaluate_indicator) checks it against the
+//! evaluator's capabilities: an expression calling a function the CEL
+//! evaluator lacks, a semantic indicator in a language the semantic
+//! evaluator does not handle, or an input over the evaluator's size limit
+//! is reported as `skipped` with a [`SkipReason`] instead of being
+//! evaluated. [`unsupported_indicators`] lists the indicators of a document
+//! that a deployment cannot evaluate, before any traffic is seen.
+
+use super::{CelEvaluator, SemanticEvaluator};
+use crate::enums::EvaluatorKind;
+use crate::types::{Document, Indicator, SkipReason};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+/// Extension holding the BCP 47 language tag of an indicator's text.
+pub const LANGUAGE_EXTENSION: &str = "x-language";
+
+/// What an evaluator supports. `None` places no restriction.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CapabilitySet {
+    /// CEL functions and methods supported (e.g., `size`, `matches`,
+    /// `exists`).
+    pub functions: Option<BTreeSet<String>>,
+    /// Largest input accepted, in bytes: the JSON message for CEL
+    /// evaluators, each extracted text for semantic evaluators, and the
+    /// prompt for generation providers.
+    pub max_input_bytes: Option<usize>,
+    /// Natural languages handled, as BCP 47 tags. A tag covers its subtags:
+    /// `en` covers `en-GB`.
+    pub languages: Option<BTreeSet<String>>,
+}
+
+impl CapabilitySet {
+    /// Capabilities without restrictions, the default for every evaluator.
+    pub const UNRESTRICTED: CapabilitySet = CapabilitySet {
+        functions: None,
+        max_input_bytes: None,
+        languages: None,
+    };
+
+    /// Whether the CEL function or method `name` is supported.
+    pub fn supports_function(&self, name: &str) -> bool {
+        self.functions.as_ref().is_none_or(|f| f.contains(name))
+    }
+
+    /// Whether the language `tag` is supported.
+    pub fn supports_language(&self, tag: &str) -> bool {
+        let Some(languages) = &self.languages else {
+            return true;
+        };
+        let tag = tag.to_ascii_lowercase();
+        languages.iter().any(|supported| {
+            let supported = supported.to_ascii_lowercase();
+            tag == supported
+                || tag
+                    .strip_prefix(&supported)
+                    .is_some_and(|rest| rest.starts_with('-'))
+        })
+    }
+
+    /// A [`SkipReason::InputTooLarge`] when `size` exceeds the input limit.
+    pub fn check_input(&self, size: usize) -> Option<SkipReason> {
+        let limit = self.max_input_bytes?;
+        (size > limit).then_some(SkipReason::InputTooLarge { size, limit })
+    }
+}
+
+/// Functions and methods called by a CEL expression, including macros such
+/// as `exists`.
+pub fn cel_functions(expression: &str) -> BTreeSet<String> {
+    let mut functions = BTreeSet::new();
+    let mut chars = expression.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if c == '"' || c == '\'' {
+            // Skip the string literal.
+            while let Some((_, next)) = chars.next() {
+                if next == '\\' {
+                    chars.next();
+                } else if next == c {
+                    break;
+                }
+            }
+        } else if c.is_ascii_alphanumeric() || c == '_' {
+            let mut end = start + c.len_utf8();
+            while let Some(&(i, next)) = chars.peek() {
+                if !(next.is_ascii_alphanumeric() || next == '_') {
+                    break;
+                }
+                end = i + next.len_utf8();
+                chars.next();
+            }
+            let rest = expression[end..].trim_start();
+            if !c.is_ascii_digit() && rest.starts_with('(') {
+                functions.insert(expression[start..end].to_string());
+            }
+        }
+    }
+    functions
+}
+
+/// The language `indicator` declares with its `x-language` extension.
+pub fn indicator_language(indicator: &Indicator) -> Option<&str> {
+    indicator
+        .extensions
+        .get(LANGUAGE_EXTENSION)
+        .and_then(Value::as_str)
+}
+
+/// Why `indicator` cannot be evaluated with these evaluators, whatever the
+/// message: a missing evaluator, an expression calling unsupported
+/// functions, or an unsupported language.
+///
+/// Frequency indicators need a
+/// [`StreamEvaluator`](super::stream::StreamEvaluator) and are not checked.
+pub fn capability_gap(
+    indicator: &Indicator,
+    cel_evaluator: Option<&dyn CelEvaluator>,
+    semantic_evaluator: Option<&dyn SemanticEvaluator>,
+) -> Option<SkipReason> {
+    if indicator.pattern.is_some() {
+        None
+    } else if let Some(expression) = &indicator.expression {
+        let Some(cel) = cel_evaluator else {
+            return Some(SkipReason::MissingEvaluator {
+                evaluator: EvaluatorKind::Cel,
+            });
+        };
+        let capabilities = cel.capabilities();
+        capabilities.functions.as_ref()?;
+        let functions: Vec<String> = cel_functions(&expression.cel)
+            .into_iter()
+            .filter(|f| !capabilities.supports_function(f))
+            .collect();
+        (!functions.is_empty()).then_some(SkipReason::UnsupportedFunctions { functions })
+    } else if indicator.semantic.is_some() {
+        let Some(semantic) = semantic_evaluator else {
+            return Some(SkipReason::MissingEvaluator {
+                evaluator: EvaluatorKind::Semantic,
+            });
+        };
+        let language = indicator_language(indicator)?;
+        (!semantic.capabilities().supports_language(language)).then(|| {
+            SkipReason::UnsupportedLanguage {
+                language: language.to_string(),
+            }
+        })
+    } else {
+        None
+    }
+}
+
+/// The indicators of a normalized document that these evaluators cannot
+/// evaluate, by ID, with the reason, in document order.
+///
+/// Only requirements that do not depend on the message are checked (see
+/// [`capability_gap`]); inputs over a size limit are still skipped when
+/// evaluated.
+pub fn unsupported_indicators(
+    doc: &Document,
+    cel_evaluator: Option<&dyn CelEvaluator>,
+    semantic_evaluator: Option<&dyn SemanticEvaluator>,
+) -> Vec<(String, SkipReason)> {
+    doc.attack
+        .indicators
+        .iter()
+        .flatten()
+        .filter_map(|indicator| {
+            let reason = capability_gap(indicator, cel_evaluator, semantic_evaluator)?;
+            Some((indicator.id.clone().unwrap_or_default(), reason))
+        })
+        .collect()
+}
@@ -0,0 +1,587 @@
+//! A built-in evaluator for a small, safe subset of CEL.
+//!
+//! Builds without the `cel-eval` feature use [`CelSubsetEvaluator`] as their
+//! default, so expression indicators written in the subset still evaluate.
+//! The subset covers:
+//!
+//! - literals: integers, doubles, strings, `true`, `false`, `null`
+//! - variables, field access (`message.params.name`), and indexing
+//!   (`message.items[0]`, `message["content-type"]`)
+//! - `==`, `!=`, `<`, `<=`, `>`, `>=`, `&&`, `||`, `!`, and parentheses
+//! - `contains`, `startsWith`, and `endsWith` on strings, and `size` as a
+//!   function or method on strings, lists, and maps
+//!
+//! Its [capabilities](CelEvaluator::capabilities) list those four functions,
+//! so [`evaluate_indicator`](super::evaluate_indicator) skips expressions
+//! calling any other (such as `matches` or `exists`) without evaluating
+//! them. Other constructs outside the subset — arithmetic, the ternary
+//! operator, list and map literals — are rejected with
+//! [`EvaluationErrorKind::Unsupported`] before evaluation, and the
+//! indicator is reported as skipped with the reason as evidence.
+//!
+//! As with [`DefaultCelEvaluator`](super::DefaultCelEvaluator), a field or
+//! variable that does not exist makes the expression `false`.
+
+use super::{CapabilitySet, CelEvaluator};
+use crate::error::{EvaluationError, EvaluationErrorKind};
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::sync::LazyLock;
+
+/// Evaluates the CEL subset described in the [module docs](self).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CelSubsetEvaluator;
+
+static CAPABILITIES: LazyLock<CapabilitySet> = LazyLock::new(|| CapabilitySet {
+    functions: Some(
+        ["size", "contains", "startsWith", "endsWith"]
+            .map(String::from)
+            .into(),
+    ),
+    ..CapabilitySet::default()
+});
+
+impl CelEvaluator for CelSubsetEvaluator {
+    fn evaluate(&self, expression: &str, context: &Value) -> Result<Value, EvaluationError> {
+        let expr = Parser::new(expression)?.parse()?;
+        match eval(&expr, context) {
+            Ok(value) => Ok(value),
+            // Missing fields produce not_matched per §4.1
+            Err(Failure::Missing) => Ok(Value::Bool(false)),
+            Err(Failure::Error(message)) => Err(cel_error(message)),
+        }
+    }
+
+    fn capabilities(&self) -> &CapabilitySet {
+        &CAPABILITIES
+    }
+}
+
+fn cel_error(message: String) -> EvaluationError {
+    EvaluationError {
+        kind: EvaluationErrorKind::CelError,
+        message: format!("CEL execution error: {}", message),
+        indicator_id: None,
+    }
+}
+
+fn unsupported(what: impl std::fmt::Display) -> EvaluationError {
+    EvaluationError {
+        kind: EvaluationErrorKind::Unsupported,
+        message: format!(
+            "{} is not supported by the built-in CEL subset; enable the cel-eval feature",
+            what
+        ),
+        indicator_id: None,
+    }
+}
+
+fn syntax(message: String) -> EvaluationError {
+    EvaluationError {
+        kind: EvaluationErrorKind::CelError,
+        message: format!("CEL compile error: {}", message),
+        indicator_id: None,
+    }
+}
+
+// ─── Parsing ────────────────────────────────────────────────────────────────
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Ident(String),
+    Int(i64),
+    Double(f64),
+    Str(String),
+    Punct(&'static str),
+}
+
+const PUNCTUATION: &[&str] = &[
+    "==", "!=", "<=", ">=", "&&", "||", "<", ">", "!", "(", ")", "[", "]", "{", "}", ".", ",", "+",
+    "-", "*", "/", "%", "?", ":",
+];
+
+fn tokenize(input: &str) -> Result<Vec<Token>, EvaluationError> {
+    let mut tokens = Vec::new();
+    let mut rest = input;
+    while let Some(c) = rest.chars().next() {
+        if c.is_whitespace() {
+            rest = &rest[c.len_utf8()..];
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            if matches!(rest[end..].chars().next(), Some('"' | '\'')) {
+                return Err(unsupported(format!("the '{}' string prefix", &rest[..end])));
+            }
+            tokens.push(Token::Ident(rest[..end].to_string()));
+            rest = &rest[end..];
+        } else if c.is_ascii_digit() {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '.'))
+                .unwrap_or(rest.len());
+            let number = &rest[..end];
+            let token = if let Ok(i) = number.parse() {
+                Token::Int(i)
+            } else if let Some(Ok(u)) = number.strip_suffix('u').map(str::parse::<u64>) {
+                Token::Int(i64::try_from(u).map_err(|_| syntax(format!("'{}' overflows", number)))?)
+            } else {
+                Token::Double(
+                    number
+                        .parse()
+                        .map_err(|_| syntax(format!("invalid number '{}'", number)))?,
+                )
+            };
+            tokens.push(token);
+            rest = &rest[end..];
+        } else if c == '"' || c == '\'' {
+            let (s, len) = string_literal(rest, c)?;
+            tokens.push(Token::Str(s));
+            rest = &rest[len..];
+        } else if let Some(p) = PUNCTUATION.iter().find(|p| rest.starts_with(**p)) {
+            tokens.push(Token::Punct(p));
+            rest = &rest[p.len()..];
+        } else {
+            return Err(syntax(format!("unexpected character '{}'", c)));
+        }
+    }
+    Ok(tokens)
+}
+
+/// The string literal at the start of `input`, and its length in bytes.
+fn string_literal(input: &str, quote: char) -> Result<(String, usize), EvaluationError> {
+    let triple: String = [quote; 3].iter().collect();
+    if input.starts_with(&triple) {
+        return Err(unsupported("a triple-quoted string"));
+    }
+    let mut value = String::new();
+    let mut chars = input.char_indices().skip(1);
+    while let Some((i, c)) = chars.next() {
+        match c {
+            c if c == quote => return Ok((value, i + 1)),
+            '\\' => match chars.next().map(|(_, c)| c) {
+                Some('n') => value.push('\n'),
+                Some('r') => value.push('\r'),
+                Some('t') => value.push('\t'),
+                Some(c @ ('\\' | '"' | '\'' | '`' | '?')) => value.push(c),
+                Some(c) => return Err(unsupported(format!("the '\\{}' escape", c))),
+                None => break,
+            },
+            '\n' => break,
+            c => value.push(c),
+        }
+    }
+    Err(syntax("unterminated string".to_string()))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum BinaryOp {
+    And,
+    Or,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Function {
+    Size,
+    Contains,
+    StartsWith,
+    EndsWith,
+}
+
+impl Function {
+    fn name(self) -> &'static str {
+        match self {
+            Function::Size => "size",
+            Function::Contains => "contains",
+            Function::StartsWith => "startsWith",
+            Function::EndsWith => "endsWith",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Expr {
+    Literal(Value),
+    Ident(String),
+    Member(Box<Expr>, String),
+    Index(Box<Expr>, Box<Expr>),
+    Call(Function, Vec<Expr>),
+    Not(Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn new(input: &str) -> Result<Self, EvaluationError> {
+        Ok(Parser {
+            tokens: tokenize(input)?,
+            pos: 0,
+        })
+    }
+
+    fn parse(mut self) -> Result<Expr, EvaluationError> {
+        let expr = self.or()?;
+        match self.tokens.get(self.pos) {
+            None => Ok(expr),
+            Some(Token::Punct("?")) => Err(unsupported("the '?:' operator")),
+            Some(token) => Err(syntax(format!("unexpected {}", describe(token)))),
+        }
+    }
+
+    fn peek_punct(&self) -> Option<&'static str> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Punct(p)) => Some(p),
+            _ => None,
+        }
+    }
+
+    fn eat(&mut self, punct: &str) -> bool {
+        let found = self.peek_punct() == Some(punct);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, punct: &str) -> Result<(), EvaluationError> {
+        if self.eat(punct) {
+            return Ok(());
+        }
+        Err(syntax(match self.tokens.get(self.pos) {
+            Some(token) => format!("expected '{}', found {}", punct, describe(token)),
+            None => format!("expected '{}' at end of expression", punct),
+        }))
+    }
+
+    fn or(&mut self) -> Result<Expr, EvaluationError> {
+        let mut left = self.and()?;
+        while self.eat("||") {
+            left = Expr::Binary(BinaryOp::Or, Box::new(left), Box::new(self.and()?));
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<Expr, EvaluationError> {
+        let mut left = self.relation()?;
+        while self.eat("&&") {
+            left = Expr::Binary(BinaryOp::And, Box::new(left), Box::new(self.relation()?));
+        }
+        Ok(left)
+    }
+
+    fn relation(&mut self) -> Result<Expr, EvaluationError> {
+        let mut left = self.unary()?;
+        loop {
+            let op = match self.peek_punct() {
+                Some("==") => BinaryOp::Eq,
+                Some("!=") => BinaryOp::Ne,
+                Some("<") => BinaryOp::Lt,
+                Some("<=") => BinaryOp::Le,
+                Some(">") => BinaryOp::Gt,
+                Some(">=") => BinaryOp::Ge,
+                Some(p @ ("+" | "-" | "*" | "/" | "%")) => {
+                    return Err(unsupported(format!("the '{}' operator", p)));
+                }
+                _ => {
+                    if let Some(Token::Ident(name)) = self.tokens.get(self.pos)
+                        && name == "in"
+                    {
+                        return Err(unsupported("the 'in' operator"));
+                    }
+                    return Ok(left);
+                }
+            };
+            self.pos += 1;
+            left = Expr::Binary(op, Box::new(left), Box::new(self.unary()?));
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expr, EvaluationError> {
+        if self.eat("!") {
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+        if self.eat("-") {
+            return match self.tokens.get(self.pos) {
+                Some(Token::Int(i)) => {
+                    let i = -*i;
+                    self.pos += 1;
+                    self.postfix(Expr::Literal(Value::from(i)))
+                }
+                Some(Token::Double(d)) => {
+                    let d = -*d;
+                    self.pos += 1;
+                    self.postfix(Expr::Literal(Value::from(d)))
+                }
+                _ => Err(unsupported("the '-' operator")),
+            };
+        }
+        let primary = self.primary()?;
+        self.postfix(primary)
+    }
+
+    fn postfix(&mut self, mut expr: Expr) -> Result<Expr, EvaluationError> {
+        loop {
+            if self.eat(".") {
+                let name = self.ident()?;
+                if self.eat("(") {
+                    let function = method(&name)?;
+                    let mut args = vec![expr];
+                    args.extend(self.arguments()?);
+                    expr = call(function, args)?;
+                } else {
+                    expr = Expr::Member(Box::new(expr), name);
+                }
+            } else if self.eat("[") {
+                let index = self.or()?;
+                self.expect("]")?;
+                expr = Expr::Index(Box::new(expr), Box::new(index));
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, EvaluationError> {
+        let Some(token) = self.tokens.get(self.pos).cloned() else {
+            return Err(syntax("unexpected end of expression".to_string()));
+        };
+        self.pos += 1;
+        match token {
+            Token::Int(i) => Ok(Expr::Literal(Value::from(i))),
+            Token::Double(d) => Ok(Expr::Literal(Value::from(d))),
+            Token::Str(s) => Ok(Expr::Literal(Value::String(s))),
+            Token::Ident(name) => match name.as_str() {
+                "true" => Ok(Expr::Literal(Value::Bool(true))),
+                "false" => Ok(Expr::Literal(Value::Bool(false))),
+                "null" => Ok(Expr::Literal(Value::Null)),
+                _ if self.eat("(") => {
+                    if name != "size" {
+                        return Err(unsupported(format!("the '{}' function", name)));
+                    }
+                    call(Function::Size, self.arguments()?)
+                }
+                _ => Ok(Expr::Ident(name)),
+            },
+            Token::Punct("(") => {
+                let expr = self.or()?;
+                self.expect(")")?;
+                Ok(expr)
+            }
+            Token::Punct("[") => Err(unsupported("a list literal")),
+            Token::Punct("{") => Err(unsupported("a map literal")),
+            token => Err(syntax(format!("unexpected {}", describe(&token)))),
+        }
+    }
+
+    fn ident(&mut self) -> Result<String, EvaluationError> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Ident(name)) => {
+                let name = name.clone();
+                self.pos += 1;
+                Ok(name)
+            }
+            Some(token) => Err(syntax(format!(
+                "expected a field name, found {}",
+                describe(token)
+            ))),
+            None => Err(syntax("expected a field name".to_string())),
+        }
+    }
+
+    /// Arguments after an opening parenthesis, through the closing one.
+    fn arguments(&mut self) -> Result<Vec<Expr>, EvaluationError> {
+        let mut args = Vec::new();
+        if self.eat(")") {
+            return Ok(args);
+        }
+        loop {
+            args.push(self.or()?);
+            if self.eat(")") {
+                return Ok(args);
+            }
+            self.expect(",")?;
+        }
+    }
+}
+
+fn method(name: &str) -> Result<Function, EvaluationError> {
+    match name {
+        "size" => Ok(Function::Size),
+        "contains" => Ok(Function::Contains),
+        "startsWith" => Ok(Function::StartsWith),
+        "endsWith" => Ok(Function::EndsWith),
+        _ => Err(unsupported(format!("the '{}' method", name))),
+    }
+}
+
+/// A call with its receiver, if any, as the first argument.
+fn call(function: Function, args: Vec<Expr>) -> Result<Expr, EvaluationError> {
+    let arity = match function {
+        Function::Size => 1,
+        _ => 2,
+    };
+    if args.len() != arity {
+        return Err(syntax(format!(
+            "wrong number of arguments to '{}'",
+            function.name()
+        )));
+    }
+    Ok(Expr::Call(function, args))
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Ident(name) => format!("'{}'", name),
+        Token::Int(i) => format!("'{}'", i),
+        Token::Double(d) => format!("'{}'", d),
+        Token::Str(s) => format!("{:?}", s),
+        Token::Punct(p) => format!("'{}'", p),
+    }
+}
+
+// ─── Evaluation ─────────────────────────────────────────────────────────────
+
+enum Failure {
+    /// A field or variable does not exist.
+    Missing,
+    Error(String),
+}
+
+fn eval(expr: &Expr, context: &Value) -> Result<Value, Failure> {
+    match expr {
+        Expr::Literal(value) => Ok(value.clone()),
+        Expr::Ident(name) => context.get(name).cloned().ok_or(Failure::Missing),
+        Expr::Member(target, name) => match eval(target, context)? {
+            Value::Object(map) => map.get(name).cloned().ok_or(Failure::Missing),
+            other => Err(Failure::Error(format!(
+                "no field '{}' on {}",
+                name,
+                type_name(&other)
+            ))),
+        },
+        Expr::Index(target, index) => {
+            let target = eval(target, context)?;
+            match (&target, eval(index, context)?) {
+                (Value::Object(map), Value::String(key)) => {
+                    map.get(&key).cloned().ok_or(Failure::Missing)
+                }
+                (Value::Array(items), Value::Number(n)) => n
+                    .as_i64()
+                    .and_then(|i| usize::try_from(i).ok())
+                    .and_then(|i| items.get(i))
+                    .cloned()
+                    .ok_or_else(|| Failure::Error(format!("index {} out of range", n))),
+                (_, index) => Err(Failure::Error(format!(
+                    "cannot index {} with {}",
+                    type_name(&target),
+                    type_name(&index)
+                ))),
+            }
+        }
+        Expr::Call(function, args) => {
+            let values = args
+                .iter()
+                .map(|a| eval(a, context))
+                .collect::<Result<Vec<_>, _>>()?;
+            apply(*function, &values)
+        }
+        Expr::Not(operand) => match eval(operand, context)? {
+            Value::Bool(b) => Ok(Value::Bool(!b)),
+            other => Err(Failure::Error(format!(
+                "cannot negate {}",
+                type_name(&other)
+            ))),
+        },
+        Expr::Binary(op @ (BinaryOp::And | BinaryOp::Or), left, right) => {
+            let decisive = *op == BinaryOp::Or;
+            if boolean(eval(left, context)?)? == decisive {
+                return Ok(Value::Bool(decisive));
+            }
+            Ok(Value::Bool(boolean(eval(right, context)?)?))
+        }
+        Expr::Binary(op, left, right) => {
+            let left = eval(left, context)?;
+            let right = eval(right, context)?;
+            compare(*op, &left, &right).map(Value::Bool)
+        }
+    }
+}
+
+fn boolean(value: Value) -> Result<bool, Failure> {
+    match value {
+        Value::Bool(b) => Ok(b),
+        other => Err(Failure::Error(format!(
+            "expected bool, found {}",
+            type_name(&other)
+        ))),
+    }
+}
+
+fn apply(function: Function, args: &[Value]) -> Result<Value, Failure> {
+    let text = |value: &Value| match value {
+        Value::String(s) => Ok(s.clone()),
+        other => Err(Failure::Error(format!(
+            "expected string, found {}",
+            type_name(other)
+        ))),
+    };
+    match function {
+        Function::Size => match &args[0] {
+            Value::String(s) => Ok(Value::from(s.chars().count())),
+            Value::Array(items) => Ok(Value::from(items.len())),
+            Value::Object(map) => Ok(Value::from(map.len())),
+            other => Err(Failure::Error(format!("no size for {}", type_name(other)))),
+        },
+        Function::Contains => Ok(Value::Bool(text(&args[0])?.contains(&text(&args[1])?))),
+        Function::StartsWith => Ok(Value::Bool(text(&args[0])?.starts_with(&text(&args[1])?))),
+        Function::EndsWith => Ok(Value::Bool(text(&args[0])?.ends_with(&text(&args[1])?))),
+    }
+}
+
+fn compare(op: BinaryOp, left: &Value, right: &Value) -> Result<bool, Failure> {
+    let ordering = match (left, right) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64().partial_cmp(&b.as_f64()),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+        _ => None,
+    };
+    match op {
+        BinaryOp::Eq => Ok(ordering.map_or(left == right, Ordering::is_eq)),
+        BinaryOp::Ne => Ok(ordering.map_or(left != right, Ordering::is_ne)),
+        _ => {
+            let ordering = ordering.ok_or_else(|| {
+                Failure::Error(format!(
+                    "cannot compare {} with {}",
+                    type_name(left),
+                    type_name(right)
+                ))
+            })?;
+            Ok(match op {
+                BinaryOp::Lt => ordering.is_lt(),
+                BinaryOp::Le => ordering.is_le(),
+                BinaryOp::Gt => ordering.is_gt(),
+                _ => ordering.is_ge(),
+            })
+        }
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "bool",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "list",
+        Value::Object(_) => "map",
+    }
+}
@@ -0,0 +1,416 @@
+//! Typed configuration for the evaluator stack.
+//!
+//! Tools that evaluate indicators usually need the same wiring: CEL on or
+//! off, a semantic backend, a generation provider, and some input limits.
+//! [`EvaluatorConfig`] captures that wiring in one struct that can be read
+//! from YAML ([`EvaluatorConfig::from_yaml`]) and overridden from
+//! `OATF_EVAL_*` environment variables ([`EvaluatorConfig::apply_env`]).
+//! [`EvaluatorConfig::build`] then produces an [`EvaluatorStack`].
+//!
+//! The SDK ships no semantic or generation backends (SDK spec §6.2, §6.3),
+//! so their construction is delegated to a caller-supplied
+//! [`BackendFactory`].
+
+use super::*;
+use crate::enums::RegexDialect;
+use crate::primitives::{check_regex_dialect, parse_duration};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Prefix of the environment variables read by [`EvaluatorConfig::apply_env`].
+pub const ENV_PREFIX: &str = "OATF_EVAL_";
+
+/// Configuration of the evaluator stack.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EvaluatorConfig {
+    /// Whether CEL expression indicators are evaluated. Defaults to `true`.
+    /// Without the `cel-eval` feature, only the [`CelSubsetEvaluator`]
+    /// subset is evaluated.
+    pub cel: bool,
+    /// Semantic evaluation backend, if any.
+    pub semantic: Option<BackendConfig>,
+    /// Generation provider backend, if any.
+    pub generation: Option<BackendConfig>,
+    /// Input limits applied before evaluation.
+    pub limits: Limits,
+    /// Regex dialect pattern indicators must stay within. Defaults to
+    /// [`RegexDialect::Native`]; use [`RegexDialect::Portable`] when the same
+    /// indicators are compiled for RE2-class engines.
+    pub regex_dialect: RegexDialect,
+}
+
+impl Default for EvaluatorConfig {
+    fn default() -> Self {
+        Self {
+            cel: true,
+            semantic: None,
+            generation: None,
+            limits: Limits::default(),
+            regex_dialect: RegexDialect::Native,
+        }
+    }
+}
+
+/// Connection settings for a model-backed evaluator.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BackendConfig {
+    /// HTTP(S) endpoint of the backend.
+    pub endpoint: String,
+    /// Model name passed to the backend.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Name of the environment variable holding the API key. The key itself
+    /// is never part of the configuration.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_env: Option<String>,
+    /// Request timeout as an OATF duration (e.g., `"30s"`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<String>,
+}
+
+impl BackendConfig {
+    /// Parsed request timeout, if one is configured and valid.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout.as_deref().and_then(|t| parse_duration(t).ok())
+    }
+
+    /// Value of the API key environment variable, if configured and set.
+    pub fn api_key(&self) -> Option<String> {
+        self.api_key_env
+            .as_deref()
+            .and_then(|name| std::env::var(name).ok())
+    }
+}
+
+/// Input limits enforced by [`EvaluatorStack::evaluate_indicator`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Limits {
+    /// Maximum size of a message, in bytes of compact JSON.
+    pub max_message_bytes: Option<usize>,
+    /// Maximum length of a CEL expression, in bytes.
+    pub max_expression_bytes: Option<usize>,
+}
+
+/// Constructs the model-backed evaluators named in an [`EvaluatorConfig`].
+///
+/// Both methods default to failing, so a factory only implements the
+/// backends it supports.
+pub trait BackendFactory {
+    /// Build a semantic evaluator for `config`.
+    fn semantic(&self, config: &BackendConfig) -> Result<Box<dyn SemanticEvaluator>, ConfigError> {
+        Err(backend_error("semantic", config))
+    }
+
+    /// Build a generation provider for `config`.
+    fn generation(
+        &self,
+        config: &BackendConfig,
+    ) -> Result<Box<dyn GenerationProvider>, ConfigError> {
+        Err(backend_error("generation", config))
+    }
+}
+
+/// A [`BackendFactory`] that supports no backends.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoBackends;
+
+impl BackendFactory for NoBackends {}
+
+fn backend_error(kind: &str, config: &BackendConfig) -> ConfigError {
+    ConfigError {
+        kind: ConfigErrorKind::Backend,
+        message: format!("no {} backend available for {}", kind, config.endpoint),
+    }
+}
+
+impl EvaluatorConfig {
+    /// Parse and check a YAML configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigErrorKind::Parse`] for malformed YAML or unknown keys
+    /// and [`ConfigErrorKind::Invalid`] when [`Self::check`] fails.
+    pub fn from_yaml(input: &str) -> Result<Self, ConfigError> {
+        let config: Self = serde_saphyr::from_str(input).map_err(|e| ConfigError {
+            kind: ConfigErrorKind::Parse,
+            message: format!("invalid evaluator configuration: {}", e),
+        })?;
+        config.check()?;
+        Ok(config)
+    }
+
+    /// Read the default configuration overridden by the process environment.
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::apply_env`].
+    pub fn from_env() -> Result<Self, ConfigError> {
+        let mut config = Self::default();
+        config.apply_env(std::env::vars())?;
+        Ok(config)
+    }
+
+    /// Override fields from `OATF_EVAL_*` variables in `vars`:
+    /// `CEL`, `MAX_MESSAGE_BYTES`, `MAX_EXPRESSION_BYTES`, `REGEX_DIALECT`
+    /// (`native` or `portable`), and
+    /// `SEMANTIC_*` / `GENERATION_*` with suffixes `ENDPOINT`, `MODEL`,
+    /// `API_KEY_ENV`, and `TIMEOUT`. Other variables are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigErrorKind::Parse`] for unparseable values and
+    /// [`ConfigErrorKind::Invalid`] when [`Self::check`] fails afterwards.
+    pub fn apply_env<I, K, V>(&mut self, vars: I) -> Result<(), ConfigError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        for (key, value) in vars {
+            let Some(name) = key.as_ref().strip_prefix(ENV_PREFIX) else {
+                continue;
+            };
+            let value = value.as_ref();
+            match name {
+                "CEL" => self.cel = parse_env(name, value)?,
+                "MAX_MESSAGE_BYTES" => {
+                    self.limits.max_message_bytes = Some(parse_env(name, value)?)
+                }
+                "MAX_EXPRESSION_BYTES" => {
+                    self.limits.max_expression_bytes = Some(parse_env(name, value)?)
+                }
+                "REGEX_DIALECT" => {
+                    self.regex_dialect = match value.trim() {
+                        "native" => RegexDialect::Native,
+                        "portable" => RegexDialect::Portable,
+                        _ => {
+                            return Err(ConfigError {
+                                kind: ConfigErrorKind::Parse,
+                                message: format!(
+                                    "{}{} has an invalid value: '{}'",
+                                    ENV_PREFIX, name, value
+                                ),
+                            });
+                        }
+                    }
+                }
+                _ => {
+                    if let Some(field) = name.strip_prefix("SEMANTIC_") {
+                        set_backend_field(&mut self.semantic, field, value);
+                    } else if let Some(field) = name.strip_prefix("GENERATION_") {
+                        set_backend_field(&mut self.generation, field, value);
+                    }
+                }
+            }
+        }
+        self.check()
+    }
+
+    /// Check endpoints, timeouts, and limits.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigErrorKind::Invalid`] describing the first problem.
+    pub fn check(&self) -> Result<(), ConfigError> {
+        for (name, backend) in [
+            ("semantic", &self.semantic),
+            ("generation", &self.generation),
+        ] {
+            let Some(backend) = backend else { continue };
+            if !(backend.endpoint.starts_with("http://")
+                || backend.endpoint.starts_with("https://"))
+            {
+                return Err(invalid(format!(
+                    "{}.endpoint must be an http(s) URL, got '{}'",
+                    name, backend.endpoint
+                )));
+            }
+            if let Some(timeout) = &backend.timeout
+                && parse_duration(timeout).is_err()
+            {
+                return Err(invalid(format!(
+                    "{}.timeout is not a valid duration: '{}'",
+                    name, timeout
+                )));
+            }
+        }
+        if self.limits.max_message_bytes == Some(0) || self.limits.max_expression_bytes == Some(0) {
+            return Err(invalid("limits must be positive".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Build the evaluator stack, asking `backends` for the configured
+    /// semantic and generation backends.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigErrorKind::Invalid`] when the configuration fails
+    /// [`Self::check`], and the factory's error when a backend cannot be
+    /// built.
+    pub fn build(&self, backends: &dyn BackendFactory) -> Result<EvaluatorStack, ConfigError> {
+        self.check()?;
+        let cel = if self.cel { Some(default_cel()) } else { None };
+        let semantic = self
+            .semantic
+            .as_ref()
+            .map(|c| backends.semantic(c))
+            .transpose()?;
+        let generation = self
+            .generation
+            .as_ref()
+            .map(|c| backends.generation(c))
+            .transpose()?;
+        Ok(EvaluatorStack {
+            cel,
+            semantic,
+            generation,
+            limits: self.limits,
+            regex_dialect: self.regex_dialect,
+        })
+    }
+}
+
+#[cfg(feature = "cel-eval")]
+fn default_cel() -> Box<dyn CelEvaluator> {
+    Box::new(DefaultCelEvaluator)
+}
+
+#[cfg(not(feature = "cel-eval"))]
+fn default_cel() -> Box<dyn CelEvaluator> {
+    Box::new(CelSubsetEvaluator)
+}
+
+fn invalid(message: String) -> ConfigError {
+    ConfigError {
+        kind: ConfigErrorKind::Invalid,
+        message,
+    }
+}
+
+fn parse_env<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, ConfigError> {
+    value.trim().parse().map_err(|_| ConfigError {
+        kind: ConfigErrorKind::Parse,
+        message: format!("{}{} has an invalid value: '{}'", ENV_PREFIX, name, value),
+    })
+}
+
+fn set_backend_field(backend: &mut Option<BackendConfig>, field: &str, value: &str) {
+    let backend = backend.get_or_insert_with(BackendConfig::default);
+    let value = value.to_string();
+    match field {
+        "ENDPOINT" => backend.endpoint = value,
+        "MODEL" => backend.model = Some(value),
+        "API_KEY_ENV" => backend.api_key_env = Some(value),
+        "TIMEOUT" => backend.timeout = Some(value),
+        _ => {}
+    }
+}
+
+/// The evaluators built from an [`EvaluatorConfig`].
+pub struct EvaluatorStack {
+    cel: Option<Box<dyn CelEvaluator>>,
+    semantic: Option<Box<dyn SemanticEvaluator>>,
+    generation: Option<Box<dyn GenerationProvider>>,
+    limits: Limits,
+    regex_dialect: RegexDialect,
+}
+
+impl EvaluatorStack {
+    /// The CEL evaluator, if enabled.
+    pub fn cel(&self) -> Option<&dyn CelEvaluator> {
+        self.cel.as_deref()
+    }
+
+    /// The indicators of a normalized document this stack cannot evaluate,
+    /// with the reason (see [`unsupported_indicators`]).
+    pub fn unsupported_indicators(&self, doc: &Document) -> Vec<(String, SkipReason)> {
+        unsupported_indicators(doc, self.cel(), self.semantic())
+    }
+
+    /// The semantic evaluator, if configured.
+    pub fn semantic(&self) -> Option<&dyn SemanticEvaluator> {
+        self.semantic.as_deref()
+    }
+
+    /// The generation provider, if configured.
+    pub fn generation(&self) -> Option<&dyn GenerationProvider> {
+        self.generation.as_deref()
+    }
+
+    /// The configured input limits.
+    pub fn limits(&self) -> Limits {
+        self.limits
+    }
+
+    /// The configured regex dialect.
+    pub fn regex_dialect(&self) -> RegexDialect {
+        self.regex_dialect
+    }
+
+    /// [`evaluate_indicator`] with this stack's evaluators. Messages and
+    /// expressions over the configured limits, and pattern regexes outside
+    /// the configured dialect, yield an `error` verdict without being
+    /// evaluated.
+    pub fn evaluate_indicator(&self, indicator: &Indicator, message: &Value) -> IndicatorVerdict {
+        if let Some(max) = self.limits.max_message_bytes {
+            let size = serde_json::to_vec(message).map_or(0, |b| b.len());
+            if size > max {
+                return limit_verdict(
+                    indicator,
+                    format!("message is {} bytes, limit {}", size, max),
+                );
+            }
+        }
+        if let (Some(max), Some(expr)) = (self.limits.max_expression_bytes, &indicator.expression)
+            && expr.cel.len() > max
+        {
+            return limit_verdict(
+                indicator,
+                format!("expression is {} bytes, limit {}", expr.cel.len(), max),
+            );
+        }
+        if let Some(pattern) = &indicator.pattern {
+            let condition_regex = match &pattern.condition {
+                Some(Condition::Operators(cond)) => cond.regex.as_deref(),
+                _ => None,
+            };
+            for regex in [pattern.regex.as_deref(), condition_regex]
+                .into_iter()
+                .flatten()
+            {
+                if let Err(reason) = check_regex_dialect(regex, self.regex_dialect) {
+                    return limit_verdict(indicator, reason);
+                }
+            }
+        }
+        evaluate_indicator(indicator, message, self.cel(), self.semantic())
+    }
+}
+
+impl std::fmt::Debug for EvaluatorStack {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EvaluatorStack")
+            .field("cel", &self.cel.is_some())
+            .field("semantic", &self.semantic.is_some())
+            .field("generation", &self.generation.is_some())
+            .field("limits", &self.limits)
+            .field("regex_dialect", &self.regex_dialect)
+            .finish()
+    }
+}
+
+fn limit_verdict(indicator: &Indicator, evidence: String) -> IndicatorVerdict {
+    IndicatorVerdict {
+        indicator_id: indicator.id.clone().unwrap_or_default(),
+        result: IndicatorResult::Error,
+        timestamp: None,
+        evidence: Some(evidence),
+        source: None,
+        captures: None,
+        skip_reason: None,
+    }
+}
@@ -0,0 +1,313 @@
+//! Multi-pattern pre-filtering for batch indicator evaluation (feature
+//! `prefilter`).
+//!
+//! Evaluating every indicator against every message compiles and runs one
+//! regex or substring search per indicator. [`CompiledIndicatorSet`] instead
+//! collects the literals (`contains`, `starts_with`, `ends_with`, string
+//! equality) and regexes that pattern indicators require, builds one
+//! Aho-Corasick automaton and one [`RegexSet`] from them, and scans each
+//! message's strings once. Only indicators whose required atoms all occurred
+//! are evaluated; the rest are `not_matched` without evaluation.
+//!
+//! The pre-filter never changes a verdict: an indicator is skipped only when
+//! a string its condition requires occurs in none of the message's string
+//! values, and its target can only resolve to those values. Indicators the
+//! pre-filter cannot reason about (expression and semantic indicators,
+//! numeric or `any_of` conditions, targets with `!json`/`!url` decoders) are
+//! always evaluated. If the regexes are too large to combine into one set,
+//! they are left to evaluation and indicators are filtered on their literals
+//! alone.
+//!
+//! Compiling a large library means parsing, normalizing, and analyzing every
+//! indicator. [`CompiledIndicatorSet::to_artifact`] saves the result — the
+//! normalized indicators with their resolved targets and CEL sources, the
+//! literal and regex tables, and each indicator's atoms — as a compact
+//! MessagePack artifact, and [`CompiledIndicatorSet::from_artifact`] loads it
+//! by rebuilding only the automaton and regex set, for deployments that load
+//! the same library on every cold start.
+
+use super::*;
+use crate::error::{ArtifactError, ArtifactErrorKind};
+use crate::spec::SPEC_VERSION;
+use aho_corasick::AhoCorasick;
+use regex::{Regex, RegexSet};
+use serde::{Deserialize, Serialize};
+
+/// Leading bytes of a compiled artifact: a magic string and the format
+/// version.
+const ARTIFACT_HEADER: &[u8] = b"OATFCIS\x01";
+
+/// Indicators compiled for batch evaluation behind a combined pre-filter.
+#[derive(Debug)]
+pub struct CompiledIndicatorSet {
+    indicators: Vec<Indicator>,
+    /// For each indicator, the atoms that must all occur; `None` when the
+    /// indicator is always evaluated.
+    requirements: Vec<Option<Vec<Atom>>>,
+    /// The patterns of `literals`, which the automaton does not retain.
+    literal_patterns: Vec<String>,
+    literals: AhoCorasick,
+    regexes: RegexSet,
+}
+
+/// A string a pattern indicator requires: an index into the literal
+/// automaton or the regex set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+enum Atom {
+    Literal(usize),
+    Regex(usize),
+}
+
+impl CompiledIndicatorSet {
+    /// Compile the indicators of `doc`, normalizing it first so shorthand
+    /// patterns are in standard form.
+    pub fn from_document(doc: &Document) -> Self {
+        let doc = crate::normalize::normalize(doc.clone());
+        Self::new(doc.attack.indicators.unwrap_or_default())
+    }
+
+    /// Compile normalized indicators (as [`evaluate_indicator`] expects them).
+    pub fn new(indicators: Vec<Indicator>) -> Self {
+        let mut literals: Vec<String> = Vec::new();
+        let mut regexes: Vec<String> = Vec::new();
+        let mut requirements: Vec<Option<Vec<Atom>>> = indicators
+            .iter()
+            .map(|indicator| {
+                let pattern = indicator.pattern.as_ref()?;
+                if pattern.target.as_deref().is_some_and(|t| t.contains('!')) {
+                    return None;
+                }
+                let (required_literals, regex) = match pattern.condition.as_ref()? {
+                    Condition::Equality(Value::String(s)) => (vec![s.as_str()], None),
+                    Condition::Equality(_) => return None,
+                    Condition::Operators(cond) => (
+                        [&cond.contains, &cond.starts_with, &cond.ends_with]
+                            .into_iter()
+                            .flatten()
+                            .map(String::as_str)
+                            .collect(),
+                        cond.regex.as_deref(),
+                    ),
+                };
+                let mut atoms: Vec<Atom> = required_literals
+                    .into_iter()
+                    .filter(|l| !l.is_empty())
+                    .map(|l| Atom::Literal(intern(&mut literals, l)))
+                    .collect();
+                if let Some(regex) = regex {
+                    // Invalid regexes never match; leave them to evaluation.
+                    Regex::new(regex).ok()?;
+                    atoms.push(Atom::Regex(intern(&mut regexes, regex)));
+                }
+                (!atoms.is_empty()).then_some(atoms)
+            })
+            .collect();
+        let regexes = RegexSet::new(&regexes).unwrap_or_else(|_| {
+            // Too large to combine: filter on literals alone.
+            for requirement in &mut requirements {
+                if let Some(atoms) = requirement {
+                    atoms.retain(|atom| matches!(atom, Atom::Literal(_)));
+                    if atoms.is_empty() {
+                        *requirement = None;
+                    }
+                }
+            }
+            RegexSet::empty()
+        });
+        CompiledIndicatorSet {
+            indicators,
+            requirements,
+            literals: AhoCorasick::new(&literals).expect("literal patterns are plain strings"),
+            literal_patterns: literals,
+            regexes,
+        }
+    }
+
+    /// Serialize the compiled set as a binary artifact for
+    /// [`from_artifact`](Self::from_artifact). Compiled regex programs are
+    /// not included; they are rebuilt on load.
+    pub fn to_artifact(&self) -> Vec<u8> {
+        let artifact = Artifact {
+            spec_version: SPEC_VERSION.to_string(),
+            indicators: self.indicators.clone(),
+            requirements: self.requirements.clone(),
+            literals: self.literal_patterns.clone(),
+            regexes: self.regexes.patterns().to_vec(),
+        };
+        let mut bytes = ARTIFACT_HEADER.to_vec();
+        rmp_serde::encode::write_named(&mut bytes, &artifact)
+            .expect("compiled indicator sets serialize to MessagePack");
+        bytes
+    }
+
+    /// Load a set saved by [`to_artifact`](Self::to_artifact), without
+    /// re-normalizing or re-analyzing its indicators.
+    ///
+    /// # Errors
+    ///
+    /// Returns `invalid_format` if `bytes` is not an intact artifact, or
+    /// `unsupported_version` if it was written by another artifact format or
+    /// for another spec version.
+    pub fn from_artifact(bytes: &[u8]) -> Result<Self, ArtifactError> {
+        let invalid = |message: String| ArtifactError {
+            kind: ArtifactErrorKind::InvalidFormat,
+            message,
+        };
+        let magic = &ARTIFACT_HEADER[..ARTIFACT_HEADER.len() - 1];
+        let body = match bytes.strip_prefix(ARTIFACT_HEADER) {
+            Some(body) => body,
+            None if bytes.starts_with(magic) => {
+                return Err(ArtifactError {
+                    kind: ArtifactErrorKind::UnsupportedVersion,
+                    message: format!(
+                        "unsupported artifact format version {}",
+                        bytes.get(magic.len()).copied().unwrap_or_default()
+                    ),
+                });
+            }
+            None => return Err(invalid("not a compiled indicator set artifact".to_string())),
+        };
+        let artifact: Artifact =
+            rmp_serde::from_slice(body).map_err(|e| invalid(format!("corrupt artifact: {}", e)))?;
+        if artifact.spec_version != SPEC_VERSION {
+            return Err(ArtifactError {
+                kind: ArtifactErrorKind::UnsupportedVersion,
+                message: format!(
+                    "artifact compiled for OATF {}, expected {}",
+                    artifact.spec_version, SPEC_VERSION
+                ),
+            });
+        }
+        let in_range = |atom: &Atom| match *atom {
+            Atom::Literal(i) => i < artifact.literals.len(),
+            Atom::Regex(i) => i < artifact.regexes.len(),
+        };
+        if artifact.requirements.len() != artifact.indicators.len()
+            || !artifact
+                .requirements
+                .iter()
+                .flatten()
+                .flatten()
+                .all(in_range)
+        {
+            return Err(invalid(
+                "artifact requirements do not match its indicators and patterns".to_string(),
+            ));
+        }
+        Ok(CompiledIndicatorSet {
+            literals: AhoCorasick::new(&artifact.literals)
+                .map_err(|e| invalid(format!("invalid literal table: {}", e)))?,
+            regexes: RegexSet::new(&artifact.regexes)
+                .map_err(|e| invalid(format!("invalid regex table: {}", e)))?,
+            indicators: artifact.indicators,
+            requirements: artifact.requirements,
+            literal_patterns: artifact.literals,
+        })
+    }
+
+    /// The compiled indicators, in input order.
+    pub fn indicators(&self) -> &[Indicator] {
+        &self.indicators
+    }
+
+    /// Number of indicators the pre-filter can skip (the rest are always
+    /// evaluated).
+    pub fn prefiltered(&self) -> usize {
+        self.requirements.iter().filter(|r| r.is_some()).count()
+    }
+
+    /// Indices of the indicators that must be evaluated against `message`:
+    /// those without pre-filter requirements and those whose required
+    /// literals and regexes all occur in one of the message's strings.
+    pub fn candidates(&self, message: &Value) -> Vec<usize> {
+        let mut literal_hits = vec![false; self.literals.patterns_len()];
+        let mut regex_hits = vec![false; self.regexes.len()];
+        for text in string_values(message) {
+            for m in self.literals.find_overlapping_iter(text) {
+                literal_hits[m.pattern().as_usize()] = true;
+            }
+            for i in self.regexes.matches(text).iter() {
+                regex_hits[i] = true;
+            }
+        }
+        self.requirements
+            .iter()
+            .enumerate()
+            .filter(|(_, atoms)| {
+                atoms.as_ref().is_none_or(|atoms| {
+                    atoms.iter().all(|atom| match *atom {
+                        Atom::Literal(i) => literal_hits[i],
+                        Atom::Regex(i) => regex_hits[i],
+                    })
+                })
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Evaluate every indicator against `message`, returning one verdict per
+    /// indicator in input order. Indicators ruled out by the pre-filter are
+    /// `not_matched`; the rest go through [`evaluate_indicator`].
+    pub fn evaluate(
+        &self,
+        message: &Value,
+        cel_evaluator: Option<&dyn CelEvaluator>,
+        semantic_evaluator: Option<&dyn SemanticEvaluator>,
+    ) -> Vec<IndicatorVerdict> {
+        let mut candidates = self.candidates(message).into_iter().peekable();
+        self.indicators
+            .iter()
+            .enumerate()
+            .map(|(i, indicator)| {
+                if candidates.next_if_eq(&i).is_some() {
+                    evaluate_indicator(indicator, message, cel_evaluator, semantic_evaluator)
+                } else {
+                    IndicatorVerdict {
+                        indicator_id: indicator.id.clone().unwrap_or_default(),
+                        result: IndicatorResult::NotMatched,
+                        timestamp: None,
+                        evidence: None,
+                        source: None,
+                        captures: None,
+                        skip_reason: None,
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+/// The serialized form of a [`CompiledIndicatorSet`].
+#[derive(Serialize, Deserialize)]
+struct Artifact {
+    spec_version: String,
+    indicators: Vec<Indicator>,
+    requirements: Vec<Option<Vec<Atom>>>,
+    literals: Vec<String>,
+    regexes: Vec<String>,
+}
+
+fn intern(patterns: &mut Vec<String>, pattern: &str) -> usize {
+    match patterns.iter().position(|p| p == pattern) {
+        Some(i) => i,
+        None => {
+            patterns.push(pattern.to_string());
+            patterns.len() - 1
+        }
+    }
+}
+
+/// Every string value in `value`, depth-first.
+fn string_values(value: &Value) -> Vec<&str> {
+    let mut out = Vec::new();
+    let mut stack = vec![value];
+    while let Some(v) = stack.pop() {
+        match v {
+            Value::String(s) => out.push(s.as_str()),
+            Value::Array(items) => stack.extend(items),
+            Value::Object(map) => stack.extend(map.values()),
+            _ => {}
+        }
+    }
+    out
+}
@@ -0,0 +1,214 @@
+//! Differential evaluation of two versions of a detection library.
+//!
+//! When indicators change, maintainers need to know which attacks the new
+//! library stops detecting before it ships. [`compare_runs`] evaluates an old
+//! and a new library over the same corpus of [`RecordedSession`]s and reports
+//! every session and attack whose detection changed: attacks the old library
+//! caught and the new one misses, and attacks only the new one catches.
+//!
+//! Each session's events are replayed through a [`StreamEvaluator`] per
+//! document, so frequency indicators see the whole session. An indicator's
+//! verdict for the session is its strongest result on any event, and an
+//! attack is detected when [`compute_verdict`] over those verdicts is
+//! `exploited`. Attacks are matched across versions by `attack.id`;
+//! documents without one are not compared.
+
+use super::stream::StreamEvaluator;
+use super::{CelEvaluator, SemanticEvaluator, compute_verdict};
+use crate::enums::{AttackResult, IndicatorResult};
+use crate::types::{Document, IndicatorVerdict, ProtocolEvent};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+/// The protocol events of one recorded agent session, in the order they
+/// were observed.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecordedSession {
+    /// Session identifier, reported with each change.
+    pub id: String,
+    /// The session's events, oldest first.
+    pub events: Vec<RecordedEvent>,
+}
+
+/// A protocol event and when it was observed.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    /// Milliseconds since the session started. Must not decrease within a
+    /// session.
+    pub at_ms: u64,
+    /// The event.
+    #[serde(flatten)]
+    pub event: ProtocolEvent,
+}
+
+/// Detections that differ between two library versions over a corpus.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegressionReport {
+    /// Number of sessions evaluated.
+    pub sessions: usize,
+    /// Session and attack pairs detected by both versions.
+    pub detected_by_both: usize,
+    /// Detected by the old version but not the new one, by session and
+    /// attack ID.
+    pub newly_missed: Vec<DetectionChange>,
+    /// Detected by the new version but not the old one, by session and
+    /// attack ID.
+    pub newly_caught: Vec<DetectionChange>,
+}
+
+impl RegressionReport {
+    /// Whether the new version misses a detection the old one made.
+    pub fn has_regressions(&self) -> bool {
+        !self.newly_missed.is_empty()
+    }
+}
+
+/// One session's verdicts for one attack under both versions.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DetectionChange {
+    /// The session.
+    pub session_id: String,
+    /// The attack.
+    pub attack_id: String,
+    /// The old version's verdict, or `None` if it has no such attack.
+    pub old: Option<AttackResult>,
+    /// The new version's verdict, or `None` if it has no such attack.
+    pub new: Option<AttackResult>,
+    /// IDs of the indicators that matched in the version that detected the
+    /// attack.
+    pub matched_indicators: Vec<String>,
+}
+
+/// Compare the detections of the `old` and `new` libraries over `corpus`.
+///
+/// Documents are normalized before evaluation. Expressions use
+/// [`DefaultCelEvaluator`](super::DefaultCelEvaluator) when the `cel-eval`
+/// feature is enabled and [`CelSubsetEvaluator`](super::CelSubsetEvaluator)
+/// otherwise; semantic indicators are skipped. Use [`compare_runs_with`] to
+/// supply evaluators.
+pub fn compare_runs(
+    old: &[Document],
+    new: &[Document],
+    corpus: &[RecordedSession],
+) -> RegressionReport {
+    #[cfg(feature = "cel-eval")]
+    let cel: Option<&dyn CelEvaluator> = Some(&super::DefaultCelEvaluator);
+    #[cfg(not(feature = "cel-eval"))]
+    let cel: Option<&dyn CelEvaluator> = Some(&super::CelSubsetEvaluator);
+    compare_runs_with(old, new, corpus, cel, None)
+}
+
+/// [`compare_runs`] with caller-supplied evaluators.
+pub fn compare_runs_with(
+    old: &[Document],
+    new: &[Document],
+    corpus: &[RecordedSession],
+    cel_evaluator: Option<&dyn CelEvaluator>,
+    semantic_evaluator: Option<&dyn SemanticEvaluator>,
+) -> RegressionReport {
+    let old = by_attack_id(old);
+    let new = by_attack_id(new);
+    let mut ids: Vec<&str> = old.keys().chain(new.keys()).copied().collect();
+    ids.sort_unstable();
+    ids.dedup();
+
+    let mut report = RegressionReport {
+        sessions: corpus.len(),
+        ..RegressionReport::default()
+    };
+    for session in corpus {
+        let run = |library: &BTreeMap<&str, Document>, id: &str| {
+            library
+                .get(id)
+                .map(|doc| evaluate_session(doc, session, cel_evaluator, semantic_evaluator))
+        };
+        for id in &ids {
+            let before = run(&old, id);
+            let after = run(&new, id);
+            let changes = match (detected(&before), detected(&after)) {
+                (true, true) => {
+                    report.detected_by_both += 1;
+                    continue;
+                }
+                (true, false) => &mut report.newly_missed,
+                (false, true) => &mut report.newly_caught,
+                (false, false) => continue,
+            };
+            let detecting = if detected(&before) { &before } else { &after };
+            changes.push(DetectionChange {
+                session_id: session.id.clone(),
+                attack_id: id.to_string(),
+                old: before.as_ref().map(|(result, _)| result.clone()),
+                new: after.as_ref().map(|(result, _)| result.clone()),
+                matched_indicators: detecting
+                    .as_ref()
+                    .map(|(_, matched)| matched.clone())
+                    .unwrap_or_default(),
+            });
+        }
+    }
+    report
+}
+
+/// Whether a run exists and detected the attack.
+fn detected(run: &Option<(AttackResult, Vec<String>)>) -> bool {
+    matches!(run, Some((AttackResult::Exploited, _)))
+}
+
+/// Normalized documents by attack ID; the first document wins for repeated
+/// IDs.
+fn by_attack_id(library: &[Document]) -> BTreeMap<&str, Document> {
+    let mut by_id = BTreeMap::new();
+    for doc in library {
+        if let Some(id) = doc.attack.id.as_deref() {
+            by_id
+                .entry(id)
+                .or_insert_with(|| crate::normalize::normalize(doc.clone()));
+        }
+    }
+    by_id
+}
+
+/// The attack's verdict for a session and the IDs of its matched
+/// indicators.
+fn evaluate_session(
+    doc: &Document,
+    session: &RecordedSession,
+    cel_evaluator: Option<&dyn CelEvaluator>,
+    semantic_evaluator: Option<&dyn SemanticEvaluator>,
+) -> (AttackResult, Vec<String>) {
+    let mut evaluator = StreamEvaluator::from_document(doc);
+    let mut strongest: HashMap<String, IndicatorVerdict> = HashMap::new();
+    for recorded in &session.events {
+        let at = Duration::from_millis(recorded.at_ms);
+        for verdict in evaluator.observe(&recorded.event, at, cel_evaluator, semantic_evaluator) {
+            match strongest.get(&verdict.indicator_id) {
+                Some(kept) if strength(&kept.result) >= strength(&verdict.result) => {}
+                _ => {
+                    strongest.insert(verdict.indicator_id.clone(), verdict);
+                }
+            }
+        }
+    }
+    let verdict = compute_verdict(&doc.attack, &strongest);
+    let matched = verdict
+        .indicator_verdicts
+        .iter()
+        .filter(|v| v.result == IndicatorResult::Matched)
+        .map(|v| v.indicator_id.clone())
+        .collect();
+    (verdict.result, matched)
+}
+
+/// Order of results when combining an indicator's verdicts over a session:
+/// a match on any event decides it.
+fn strength(result: &IndicatorResult) -> u8 {
+    match result {
+        IndicatorResult::Matched => 4,
+        IndicatorResult::MatchedLikelyBenign => 3,
+        IndicatorResult::Error => 2,
+        IndicatorResult::NotMatched => 1,
+        IndicatorResult::Skipped => 0,
+    }
+}
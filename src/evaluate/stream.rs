@@ -0,0 +1,215 @@
+//! Stateful evaluation over a session's stream of protocol events.
+//!
+//! Pattern, expression, and semantic indicators judge one message at a time;
+//! frequency indicators ("at least 10 `tools/call:read_file` within 60s")
+//! need the events that came before. [`StreamEvaluator`] keeps a sliding
+//! window per frequency indicator (and per `group_by` value) and evaluates
+//! every indicator against each event as it is observed. Times are offsets
+//! from any fixed origin, such as [`Clock::now`](crate::clock::Clock::now)
+//! or a recorded message's timestamp.
+
+use super::*;
+use crate::primitives::{evaluate_trigger, parse_duration};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Evaluates indicators against a session's events, keeping the state
+/// frequency indicators need. Use one evaluator per session.
+#[derive(Clone, Debug)]
+pub struct StreamEvaluator {
+    indicators: Vec<Indicator>,
+    /// For each indicator, its window state; `None` for stateless indicators.
+    windows: Vec<Option<Result<FrequencyWindow, String>>>,
+}
+
+/// Sliding-window counts for one frequency indicator.
+#[derive(Clone, Debug)]
+struct FrequencyWindow {
+    /// Trigger equivalent of the event filter, for event matching.
+    filter: Trigger,
+    protocol: String,
+    window: Duration,
+    /// `window` as written, for evidence.
+    window_text: String,
+    threshold: usize,
+    group_by: Option<String>,
+    /// Times of counted events within the window, oldest first, per group.
+    groups: HashMap<String, VecDeque<Duration>>,
+}
+
+impl StreamEvaluator {
+    /// An evaluator for a normalized document's indicators.
+    pub fn from_document(doc: &Document) -> Self {
+        Self::new(doc.attack.indicators.clone().unwrap_or_default())
+    }
+
+    /// An evaluator for normalized `indicators`.
+    pub fn new(indicators: Vec<Indicator>) -> Self {
+        let windows = indicators
+            .iter()
+            .map(|indicator| indicator.frequency.as_ref().map(|f| compile(indicator, f)))
+            .collect();
+        StreamEvaluator {
+            indicators,
+            windows,
+        }
+    }
+
+    /// The indicators, in input order.
+    pub fn indicators(&self) -> &[Indicator] {
+        &self.indicators
+    }
+
+    /// Evaluate every indicator against `event`, observed at `now`, and
+    /// return one verdict per indicator in input order.
+    ///
+    /// Frequency indicators count the event if it matches their `event`,
+    /// `match`, and the indicator's `origin`, then match when their window
+    /// (or the event's `group_by` group) holds at least `threshold` events;
+    /// events older than `window` no longer count; a match captures from the
+    /// event that completed it.
+    /// Other indicators are evaluated against the event with
+    /// [`evaluate_indicator_event`]. `now` must not go backwards.
+    pub fn observe(
+        &mut self,
+        event: &ProtocolEvent,
+        now: Duration,
+        cel_evaluator: Option<&dyn CelEvaluator>,
+        semantic_evaluator: Option<&dyn SemanticEvaluator>,
+    ) -> Vec<IndicatorVerdict> {
+        self.indicators
+            .iter()
+            .zip(&mut self.windows)
+            .map(|(indicator, window)| {
+                let indicator_id = indicator.id.clone().unwrap_or_default();
+                match window {
+                    None => evaluate_indicator_event(
+                        indicator,
+                        event,
+                        cel_evaluator,
+                        semantic_evaluator,
+                    ),
+                    Some(Err(message)) => IndicatorVerdict {
+                        indicator_id,
+                        result: IndicatorResult::Error,
+                        timestamp: None,
+                        evidence: Some(message.clone()),
+                        source: None,
+                        captures: None,
+                        skip_reason: None,
+                    },
+                    Some(Ok(window)) => {
+                        let mut verdict = window.observe(event, now, indicator_id);
+                        if verdict.result == IndicatorResult::Matched {
+                            verdict.captures = capture_values(indicator, &event.content);
+                        }
+                        verdict
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Forget all counted events, as at the start of a new session.
+    pub fn reset(&mut self) {
+        for window in self.windows.iter_mut().flatten().flatten() {
+            window.groups.clear();
+        }
+    }
+}
+
+fn compile(indicator: &Indicator, frequency: &FrequencyMatch) -> Result<FrequencyWindow, String> {
+    let window = parse_duration(&frequency.window)
+        .map_err(|e| format!("invalid frequency window: {}", e.message))?;
+    if window.is_zero() {
+        return Err("frequency window must be greater than zero".to_string());
+    }
+    if frequency.threshold < 1 {
+        return Err(format!(
+            "frequency threshold must be at least 1, got {}",
+            frequency.threshold
+        ));
+    }
+    let protocol = indicator
+        .protocol
+        .clone()
+        .or_else(|| {
+            crate::surface::lookup_surface(&indicator.surface).map(|s| s.protocol.to_string())
+        })
+        .unwrap_or_else(|| "mcp".to_string());
+    Ok(FrequencyWindow {
+        filter: Trigger {
+            event: Some(frequency.event.clone()),
+            count: None,
+            match_predicate: frequency.match_predicate.clone(),
+            origin: indicator.origin.clone(),
+            after: None,
+        },
+        protocol,
+        window,
+        window_text: frequency.window.clone(),
+        threshold: frequency.threshold as usize,
+        group_by: frequency.group_by.clone(),
+        groups: HashMap::new(),
+    })
+}
+
+impl FrequencyWindow {
+    fn observe(
+        &mut self,
+        event: &ProtocolEvent,
+        now: Duration,
+        indicator_id: String,
+    ) -> IndicatorVerdict {
+        let window = self.window;
+        let expired = |t: &Duration| now.saturating_sub(*t) >= window;
+        for times in self.groups.values_mut() {
+            while times.front().is_some_and(expired) {
+                times.pop_front();
+            }
+        }
+        self.groups.retain(|_, times| !times.is_empty());
+
+        let counted = matches!(
+            evaluate_trigger(
+                &self.filter,
+                Some(event),
+                Duration::ZERO,
+                &mut TriggerState::default(),
+                &self.protocol,
+            ),
+            TriggerResult::Advanced { .. }
+        );
+        let group = match &self.group_by {
+            None => Some(String::new()),
+            Some(path) => resolve_simple_path_ref(path, &event.content).map(|v| match v {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            }),
+        };
+
+        let mut result = IndicatorResult::NotMatched;
+        let mut evidence = None;
+        if counted && let Some(group) = group {
+            let times = self.groups.entry(group.clone()).or_default();
+            times.push_back(now);
+            if times.len() >= self.threshold {
+                result = IndicatorResult::Matched;
+                let mut text = format!("{} events within {}", times.len(), self.window_text);
+                if let Some(path) = &self.group_by {
+                    text.push_str(&format!(" for {} = {}", path, group));
+                }
+                evidence = Some(text);
+            }
+        }
+        IndicatorVerdict {
+            indicator_id,
+            result,
+            timestamp: None,
+            evidence,
+            source: None,
+            captures: None,
+            skip_reason: None,
+        }
+    }
+}
@@ -103,6 +103,14 @@ pub static EVENT_MODE_REGISTRY: &[EventModeEntry] = &[
         event: "notifications/tasks/status",
         valid_modes: &["mcp_client"],
     },
+    EventModeEntry {
+        event: "notifications/progress",
+        valid_modes: &["mcp_server", "mcp_client"],
+    },
+    EventModeEntry {
+        event: "notifications/elicitation/complete",
+        valid_modes: &["mcp_client"],
+    },
     // A2A events
     EventModeEntry {
         event: "message/send",
@@ -124,6 +132,22 @@ pub static EVENT_MODE_REGISTRY: &[EventModeEntry] = &[
         event: "tasks/pushNotification/get",
         valid_modes: &["a2a_server"],
     },
+    EventModeEntry {
+        event: "tasks/pushNotificationConfig/set",
+        valid_modes: &["a2a_server"],
+    },
+    EventModeEntry {
+        event: "tasks/pushNotificationConfig/get",
+        valid_modes: &["a2a_server"],
+    },
+    EventModeEntry {
+        event: "tasks/pushNotificationConfig/list",
+        valid_modes: &["a2a_server"],
+    },
+    EventModeEntry {
+        event: "tasks/pushNotificationConfig/delete",
+        valid_modes: &["a2a_server"],
+    },
     EventModeEntry {
         event: "agent_card/get",
         valid_modes: &["a2a_server", "a2a_client"],
@@ -169,14 +193,30 @@ pub static EVENT_MODE_REGISTRY: &[EventModeEntry] = &[
         event: "text_message_end",
         valid_modes: &["ag_ui_client"],
     },
+    EventModeEntry {
+        event: "text_message_chunk",
+        valid_modes: &["ag_ui_client"],
+    },
     EventModeEntry {
         event: "tool_call_start",
         valid_modes: &["ag_ui_client"],
     },
+    EventModeEntry {
+        event: "tool_call_args",
+        valid_modes: &["ag_ui_client"],
+    },
     EventModeEntry {
         event: "tool_call_end",
         valid_modes: &["ag_ui_client"],
     },
+    EventModeEntry {
+        event: "tool_call_result",
+        valid_modes: &["ag_ui_client"],
+    },
+    EventModeEntry {
+        event: "tool_call_chunk",
+        valid_modes: &["ag_ui_client"],
+    },
     EventModeEntry {
         event: "state_snapshot",
         valid_modes: &["ag_ui_client"],
@@ -197,6 +237,10 @@ pub static EVENT_MODE_REGISTRY: &[EventModeEntry] = &[
         event: "custom",
         valid_modes: &["ag_ui_client"],
     },
+    EventModeEntry {
+        event: "raw",
+        valid_modes: &["ag_ui_client"],
+    },
 ];
 
 /// Look up an event entry by its base event name (qualifier stripped).
@@ -242,6 +286,21 @@ static QUALIFIER_RESOLUTION_REGISTRY: &[QualifierResolutionEntry] = &[
         content_path: "status.state",
     },
     // AG-UI (§7.3.2)
+    QualifierResolutionEntry {
+        protocol: "ag_ui",
+        event: "run_error",
+        content_path: "code",
+    },
+    QualifierResolutionEntry {
+        protocol: "ag_ui",
+        event: "step_started",
+        content_path: "stepName",
+    },
+    QualifierResolutionEntry {
+        protocol: "ag_ui",
+        event: "step_finished",
+        content_path: "stepName",
+    },
     QualifierResolutionEntry {
         protocol: "ag_ui",
         event: "tool_call_start",
@@ -252,6 +311,11 @@ static QUALIFIER_RESOLUTION_REGISTRY: &[QualifierResolutionEntry] = &[
         event: "tool_call_end",
         content_path: "toolCallName",
     },
+    QualifierResolutionEntry {
+        protocol: "ag_ui",
+        event: "tool_call_chunk",
+        content_path: "toolCallName",
+    },
     QualifierResolutionEntry {
         protocol: "ag_ui",
         event: "custom",
@@ -282,8 +346,7 @@ pub fn resolve_event_qualifier(
     content: &Value,
 ) -> Option<String> {
     let path = lookup_qualifier_path(protocol, event_base)?;
-    let value = crate::primitives::resolve_simple_path(path, content)?;
-    match &value {
+    match crate::primitives::resolve_simple_path_ref(path, content)? {
         Value::String(s) => Some(s.clone()),
         Value::Number(n) => Some(n.to_string()),
         Value::Bool(b) => Some(b.to_string()),
@@ -292,8 +355,9 @@ pub fn resolve_event_qualifier(
 }
 
 /// Extract the protocol component from a mode string.
-/// "mcp_server" -> "mcp", "ag_ui_client" -> "ag_ui"
+/// "mcp_server" -> "mcp", "ag_ui_client" -> "ag_ui", "mcp_server@2025-03-26" -> "mcp"
 pub fn extract_protocol(mode: &str) -> &str {
+    let (mode, _) = crate::surface::split_mode_version(mode);
     if let Some(stripped) = mode.strip_suffix("_server") {
         stripped
     } else if let Some(stripped) = mode.strip_suffix("_client") {
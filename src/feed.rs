@@ -0,0 +1,284 @@
+//! Keeping a local copy of an OATF feed in sync with its upstream.
+//!
+//! A feed is an index served over HTTPS that lists document URLs:
+//!
+//! ```yaml
+//! documents:
+//!   - attacks/oatf-001.yaml
+//!   - https://mirror.example.com/oatf-002.yaml
+//! ```
+//!
+//! Relative URLs are resolved against the index URL. [`sync`] fetches the
+//! index and each document with `If-None-Match` / `If-Modified-Since`
+//! validators from the previous [`SyncState`], and yields only documents that
+//! are new, changed, or no longer listed. Fetched documents are validated
+//! through [`crate::load`].
+//!
+//! HTTP goes through a caller-supplied [`FeedTransport`], so the crate
+//! carries no HTTP client. Requires the `feed` feature.
+
+use crate::LoadResult;
+use crate::digest::sha256_digest;
+use crate::error::{FeedError, FeedErrorKind, OATFError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Cache validators from a previous response, sent back as conditional
+/// request headers.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Validators {
+    /// The `ETag` response header, sent as `If-None-Match`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    /// The `Last-Modified` response header, sent as `If-Modified-Since`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+}
+
+/// Outcome of a conditional GET.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FeedResponse {
+    /// `304 Not Modified`.
+    NotModified,
+    /// `200 OK` with the body and the response's validators.
+    Ok {
+        /// Response body.
+        body: Vec<u8>,
+        /// Validators to send on the next request.
+        validators: Validators,
+    },
+}
+
+/// Performs conditional HTTPS GETs. Implementations own the HTTP client,
+/// redirects, and authentication, and report non-success statuses other
+/// than 304 as errors of kind `transport`.
+pub trait FeedTransport {
+    /// GET `url`, sending `validators` as conditional request headers.
+    fn get(&self, url: &str, validators: &Validators) -> Result<FeedResponse, FeedError>;
+}
+
+/// What is known about a feed after a sync. Persist it (it is serializable)
+/// and pass it to the next [`sync`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncState {
+    /// Validators of the last fetched index.
+    #[serde(default)]
+    pub index: Validators,
+    /// Known documents by resolved URL.
+    #[serde(default)]
+    pub documents: BTreeMap<String, DocumentState>,
+}
+
+/// The last seen version of one feed document.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocumentState {
+    /// Validators of the last fetched response.
+    #[serde(default)]
+    pub validators: Validators,
+    /// `sha256:<hex>` digest of the last fetched body.
+    pub digest: String,
+}
+
+/// A document that differs from the previous sync.
+#[derive(Debug)]
+pub enum FeedUpdate {
+    /// A document not seen before.
+    New {
+        /// Resolved document URL.
+        url: String,
+        /// The outcome of [`crate::load`] on the document.
+        result: Result<LoadResult, Vec<OATFError>>,
+    },
+    /// A known document whose content changed.
+    Changed {
+        /// Resolved document URL.
+        url: String,
+        /// The outcome of [`crate::load`] on the document.
+        result: Result<LoadResult, Vec<OATFError>>,
+    },
+    /// A known document the index no longer lists.
+    Removed {
+        /// Resolved document URL.
+        url: String,
+    },
+}
+
+impl FeedUpdate {
+    /// The document URL.
+    pub fn url(&self) -> &str {
+        match self {
+            FeedUpdate::New { url, .. }
+            | FeedUpdate::Changed { url, .. }
+            | FeedUpdate::Removed { url } => url,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FeedIndex {
+    documents: Vec<String>,
+}
+
+/// Start syncing the feed whose index is at `url`.
+///
+/// The index is fetched immediately; if it is unchanged since `state` was
+/// recorded the returned iterator is empty. Otherwise each document is
+/// fetched lazily as the iterator advances, and `state` is updated after
+/// every yielded item, so stopping early leaves the remaining documents to
+/// the next sync. Documents whose body is unchanged are skipped even when
+/// the server does not honour the validators. Removals are yielded last.
+///
+/// # Errors
+///
+/// Returns `FeedError` if `url` is not an `https://` URL, the index cannot
+/// be fetched, or it is malformed. The iterator yields per-document fetch
+/// errors; such documents keep their previous state and are retried on the
+/// next sync.
+pub fn sync<'a>(
+    transport: &'a dyn FeedTransport,
+    url: &str,
+    state: &'a mut SyncState,
+) -> Result<SyncUpdates<'a>, FeedError> {
+    check_url(url)?;
+    let mut sync = SyncUpdates {
+        transport,
+        state,
+        pending: Vec::new(),
+        removed: Vec::new(),
+        index: None,
+    };
+    let (body, validators) = match transport.get(url, &sync.state.index)? {
+        FeedResponse::NotModified => return Ok(sync),
+        FeedResponse::Ok { body, validators } => (body, validators),
+    };
+
+    let invalid = |message: String| FeedError {
+        kind: FeedErrorKind::InvalidIndex,
+        url: url.to_string(),
+        message,
+    };
+    let text = std::str::from_utf8(&body).map_err(|e| invalid(e.to_string()))?;
+    let index: FeedIndex =
+        serde_saphyr::from_str(text).map_err(|e| invalid(format!("malformed index: {}", e)))?;
+
+    let mut pending = Vec::with_capacity(index.documents.len());
+    for document in &index.documents {
+        let resolved = resolve_url(url, document);
+        check_url(&resolved)?;
+        if !pending.contains(&resolved) {
+            pending.push(resolved);
+        }
+    }
+    sync.removed = sync
+        .state
+        .documents
+        .keys()
+        .filter(|known| !pending.contains(known))
+        .cloned()
+        .collect();
+    pending.reverse();
+    sync.pending = pending;
+    sync.index = Some(validators);
+    Ok(sync)
+}
+
+/// Iterator over the updates of one [`sync`].
+pub struct SyncUpdates<'a> {
+    transport: &'a dyn FeedTransport,
+    state: &'a mut SyncState,
+    /// Documents still to fetch, last first.
+    pending: Vec<String>,
+    removed: Vec<String>,
+    /// Validators of the fetched index, recorded once every document has
+    /// been processed.
+    index: Option<Validators>,
+}
+
+impl Iterator for SyncUpdates<'_> {
+    type Item = Result<FeedUpdate, FeedError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(url) = self.pending.pop() {
+            match self.fetch(url) {
+                Ok(None) => continue,
+                Ok(Some(update)) => return Some(Ok(update)),
+                Err(e) => {
+                    // Leave the index validators unrecorded so the failed
+                    // document is fetched again next time.
+                    self.index = None;
+                    return Some(Err(e));
+                }
+            }
+        }
+        if let Some(url) = self.removed.pop() {
+            self.state.documents.remove(&url);
+            return Some(Ok(FeedUpdate::Removed { url }));
+        }
+        if let Some(validators) = self.index.take() {
+            self.state.index = validators;
+        }
+        None
+    }
+}
+
+impl SyncUpdates<'_> {
+    fn fetch(&mut self, url: String) -> Result<Option<FeedUpdate>, FeedError> {
+        let known = self.state.documents.get(&url);
+        let validators = known.map(|d| d.validators.clone()).unwrap_or_default();
+        let (body, validators) = match self.transport.get(&url, &validators)? {
+            FeedResponse::NotModified => return Ok(None),
+            FeedResponse::Ok { body, validators } => (body, validators),
+        };
+
+        let digest = sha256_digest(&body);
+        let previous = self.state.documents.insert(
+            url.clone(),
+            DocumentState {
+                validators,
+                digest: digest.clone(),
+            },
+        );
+        if previous.as_ref().is_some_and(|p| p.digest == digest) {
+            return Ok(None);
+        }
+        let result = crate::bundle::load_entry(url.clone(), &body).result;
+        Ok(Some(match previous {
+            None => FeedUpdate::New { url, result },
+            Some(_) => FeedUpdate::Changed { url, result },
+        }))
+    }
+}
+
+fn check_url(url: &str) -> Result<(), FeedError> {
+    let host = url.strip_prefix("https://").unwrap_or("");
+    if host.is_empty() || host.starts_with('/') {
+        return Err(FeedError {
+            kind: FeedErrorKind::InvalidUrl,
+            url: url.to_string(),
+            message: "feed URLs must be absolute https:// URLs".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Resolve a document reference against the index URL: absolute URLs are
+/// kept, `/path` is resolved against the origin, anything else against the
+/// index's directory.
+fn resolve_url(base: &str, reference: &str) -> String {
+    if reference.contains("://") {
+        return reference.to_string();
+    }
+    let base = base.split(['?', '#']).next().unwrap_or(base);
+    let after_scheme = base.find("://").map_or(0, |i| i + 3);
+    let origin_end = base[after_scheme..]
+        .find('/')
+        .map_or(base.len(), |i| after_scheme + i);
+    if let Some(path) = reference.strip_prefix('/') {
+        return format!("{}/{}", &base[..origin_end], path);
+    }
+    let dir_end = base[origin_end..]
+        .rfind('/')
+        .map_or(origin_end, |i| origin_end + i);
+    format!("{}/{}", &base[..dir_end], reference)
+}
@@ -0,0 +1,271 @@
+//! Structural sharing for large batches of near-identical documents.
+//!
+//! Services that load thousands of template-derived attack variants hold
+//! mostly identical phase states and the same handful of surface, mode, and
+//! event names over and over. An [`InternPool`] deduplicates them: state
+//! subtrees become shared [`Arc<Value>`]s and names become shared
+//! [`Arc<str>`]s, so each distinct subtree or name is stored once per pool.
+//!
+//! [`parse_interned`] loads a document (parse → validate → normalize) and
+//! interns it; [`InternedDocument::to_document`] materializes an ordinary
+//! [`Document`] again when one is needed.
+
+#[cfg(all(feature = "validate", feature = "normalize"))]
+use crate::error::OATFError;
+use crate::types::Document;
+use serde_json::Value;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// Deduplicating store for strings and JSON subtrees.
+#[derive(Debug, Default)]
+pub struct InternPool {
+    strings: HashSet<Arc<str>>,
+    values: HashMap<u64, Vec<Arc<Value>>>,
+    hits: usize,
+}
+
+/// Counters describing how much an [`InternPool`] has shared.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct InternStats {
+    /// Distinct strings stored.
+    pub strings: usize,
+    /// Distinct JSON subtrees stored.
+    pub values: usize,
+    /// Strings and subtrees that were already present when interned.
+    pub hits: usize,
+}
+
+impl InternPool {
+    /// Create an empty pool.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the shared copy of `s`, storing it on first use.
+    pub fn intern_str(&mut self, s: &str) -> Arc<str> {
+        if let Some(shared) = self.strings.get(s) {
+            self.hits += 1;
+            return Arc::clone(shared);
+        }
+        let shared: Arc<str> = Arc::from(s);
+        self.strings.insert(Arc::clone(&shared));
+        shared
+    }
+
+    /// Return the shared copy of `value`, storing it on first use.
+    /// Values are compared structurally, including object key order.
+    pub fn intern_value(&mut self, value: Value) -> Arc<Value> {
+        let mut hasher = DefaultHasher::new();
+        serde_json::to_string(&value)
+            .unwrap_or_default()
+            .hash(&mut hasher);
+        let bucket = self.values.entry(hasher.finish()).or_default();
+        if let Some(shared) = bucket.iter().find(|v| ***v == value) {
+            self.hits += 1;
+            return Arc::clone(shared);
+        }
+        let shared = Arc::new(value);
+        bucket.push(Arc::clone(&shared));
+        shared
+    }
+
+    /// Current sharing counters.
+    pub fn stats(&self) -> InternStats {
+        InternStats {
+            strings: self.strings.len(),
+            values: self.values.values().map(Vec::len).sum(),
+            hits: self.hits,
+        }
+    }
+
+    /// Intern a normalized document.
+    ///
+    /// Phase states are split into shared subtrees: every top-level state
+    /// key is interned, array members (e.g., individual tools) are interned
+    /// one by one, and other values whole. Actor modes, trigger events, and
+    /// indicator surfaces are interned as strings. Documents that are not in
+    /// multi-actor form keep their top-level `execution` fields unshared.
+    pub fn intern_document(&mut self, mut document: Document) -> InternedDocument {
+        let mut actors = Vec::new();
+        for actor in document.attack.execution.actors.iter_mut().flatten() {
+            let mode = self.intern_str(&std::mem::take(&mut actor.mode));
+            let mut phases = Vec::new();
+            for phase in &mut actor.phases {
+                let state = phase.state.take().map(|s| self.share_state(s));
+                let event = phase
+                    .trigger
+                    .as_mut()
+                    .and_then(|t| t.event.take())
+                    .map(|e| self.intern_str(&e));
+                phases.push(InternedPhase { state, event });
+            }
+            actors.push(InternedActor { mode, phases });
+        }
+        let surfaces = document
+            .attack
+            .indicators
+            .iter_mut()
+            .flatten()
+            .map(|i| self.intern_str(&std::mem::take(&mut i.surface)))
+            .collect();
+
+        InternedDocument {
+            skeleton: document,
+            actors,
+            surfaces,
+        }
+    }
+
+    fn share_state(&mut self, state: Value) -> SharedState {
+        match state {
+            Value::Object(map) => SharedState::Object(
+                map.into_iter()
+                    .map(|(key, value)| {
+                        let node = match value {
+                            Value::Array(items) => SharedNode::List(
+                                items.into_iter().map(|v| self.intern_value(v)).collect(),
+                            ),
+                            other => SharedNode::Value(self.intern_value(other)),
+                        };
+                        (self.intern_str(&key), node)
+                    })
+                    .collect(),
+            ),
+            other => SharedState::Other(self.intern_value(other)),
+        }
+    }
+}
+
+/// A phase state made of shared subtrees.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SharedState {
+    /// An object state, key by key.
+    Object(Vec<(Arc<str>, SharedNode)>),
+    /// A non-object state, shared whole.
+    Other(Arc<Value>),
+}
+
+/// One top-level entry of a [`SharedState`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum SharedNode {
+    /// An array whose members are shared individually.
+    List(Vec<Arc<Value>>),
+    /// Any other value, shared whole.
+    Value(Arc<Value>),
+}
+
+impl SharedState {
+    /// Rebuild the state as an owned value.
+    pub fn to_value(&self) -> Value {
+        match self {
+            SharedState::Object(entries) => Value::Object(
+                entries
+                    .iter()
+                    .map(|(key, node)| {
+                        let value = match node {
+                            SharedNode::List(items) => {
+                                Value::Array(items.iter().map(|v| (**v).clone()).collect())
+                            }
+                            SharedNode::Value(v) => (**v).clone(),
+                        };
+                        (key.to_string(), value)
+                    })
+                    .collect(),
+            ),
+            SharedState::Other(v) => (**v).clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct InternedActor {
+    mode: Arc<str>,
+    phases: Vec<InternedPhase>,
+}
+
+#[derive(Clone, Debug)]
+struct InternedPhase {
+    state: Option<SharedState>,
+    event: Option<Arc<str>>,
+}
+
+/// A normalized document whose bulky and repetitive parts live in an
+/// [`InternPool`].
+#[derive(Clone, Debug)]
+pub struct InternedDocument {
+    /// The document with interned fields moved out.
+    skeleton: Document,
+    actors: Vec<InternedActor>,
+    surfaces: Vec<Arc<str>>,
+}
+
+impl InternedDocument {
+    /// Attack ID, if the document has one.
+    pub fn attack_id(&self) -> Option<&str> {
+        self.skeleton.attack.id.as_deref()
+    }
+
+    /// Mode of the actor at `actor`.
+    pub fn mode(&self, actor: usize) -> Option<&str> {
+        self.actors.get(actor).map(|a| &*a.mode)
+    }
+
+    /// Shared state of phase `phase` of actor `actor`.
+    pub fn state(&self, actor: usize, phase: usize) -> Option<&SharedState> {
+        self.actors.get(actor)?.phases.get(phase)?.state.as_ref()
+    }
+
+    /// Surface of the indicator at `indicator`.
+    pub fn surface(&self, indicator: usize) -> Option<&str> {
+        self.surfaces.get(indicator).map(|s| &**s)
+    }
+
+    /// Materialize an owned [`Document`] equal to the one that was interned.
+    pub fn to_document(&self) -> Document {
+        let mut document = self.skeleton.clone();
+        for (actor, interned) in document
+            .attack
+            .execution
+            .actors
+            .iter_mut()
+            .flatten()
+            .zip(&self.actors)
+        {
+            actor.mode = interned.mode.to_string();
+            for (phase, interned) in actor.phases.iter_mut().zip(&interned.phases) {
+                phase.state = interned.state.as_ref().map(SharedState::to_value);
+                if let Some(trigger) = &mut phase.trigger {
+                    trigger.event = interned.event.as_deref().map(str::to_string);
+                }
+            }
+        }
+        for (indicator, surface) in document
+            .attack
+            .indicators
+            .iter_mut()
+            .flatten()
+            .zip(&self.surfaces)
+        {
+            indicator.surface = surface.to_string();
+        }
+        document
+    }
+}
+
+/// Load `input` via [`crate::load`] and intern the normalized document into
+/// `pool`. Warnings are discarded.
+///
+/// # Errors
+///
+/// Returns the errors from [`crate::load`]; the pool is unchanged.
+#[cfg(all(feature = "validate", feature = "normalize"))]
+pub fn parse_interned(
+    input: &str,
+    pool: &mut InternPool,
+) -> Result<InternedDocument, Vec<OATFError>> {
+    let result = crate::load(input)?;
+    Ok(pool.intern_document(result.document))
+}
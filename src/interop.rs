@@ -0,0 +1,9 @@
+//! Interoperability with other security tooling.
+//!
+//! [`import`] turns red-team tool configurations and reports into skeleton
+//! OATF documents; [`export`] turns indicators into detection rules for
+//! SIEM platforms.
+
+#[cfg(feature = "normalize")]
+pub mod export;
+pub mod import;
@@ -0,0 +1,371 @@
+//! Converters from OATF indicators to detection rules for other security
+//! tooling.
+//!
+//! - [`yara_l`] writes YARA-L 2.0 rules for Google Security Operations
+//!   (Chronicle), one per pattern indicator. Where each surface appears in
+//!   the Unified Data Model (UDM) differs between deployments, so a
+//!   [`YaraLMapping`] names the field to match for every surface.
+//!
+//! Indicators with no rule equivalent — CEL expressions, semantic and
+//! frequency indicators, decoded targets, and `json_patch` conditions — are
+//! skipped and listed in [`Exported::notes`].
+
+use crate::enums::SeverityLevel;
+use crate::error::{ConfigError, ConfigErrorKind};
+use crate::normalize::normalize;
+use crate::types::{Condition, Document, Indicator, MatchCondition, Severity};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Detection rules converted from a document.
+#[derive(Clone, Debug, Default)]
+pub struct Exported {
+    /// One rule per converted indicator, in document order.
+    pub rules: Vec<ExportedRule>,
+    /// Indicators and predicates that were not converted, for the author to
+    /// review.
+    pub notes: Vec<String>,
+}
+
+impl Exported {
+    /// Every rule's source, separated by blank lines.
+    pub fn text(&self) -> String {
+        self.rules
+            .iter()
+            .map(|r| r.text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// A rule converted from one indicator.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExportedRule {
+    /// ID of the indicator the rule detects.
+    pub indicator_id: String,
+    /// Rule name.
+    pub name: String,
+    /// Rule source.
+    pub text: String,
+}
+
+// ─── YARA-L ─────────────────────────────────────────────────────────────────
+
+/// Where a Google SecOps deployment records OATF surfaces in UDM.
+///
+/// ```yaml
+/// fields:
+///   tool_description: security_result.description
+///   tool_call:arguments: target.process.command_line
+/// default_field: additional.fields["oatf_{surface}"]
+/// filters:
+///   mcp:
+///     - $e.metadata.product_name = "mcp-gateway"
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct YaraLMapping {
+    /// UDM field for each surface. A `surface:target` key applies to
+    /// indicators with that (normalized) target only and takes precedence.
+    pub fields: BTreeMap<String, String>,
+    /// UDM field for surfaces not in `fields`, with `{surface}` replaced by
+    /// the surface name. Without one, unmapped surfaces are skipped.
+    pub default_field: Option<String>,
+    /// Event conditions added to every rule, by indicator protocol.
+    pub filters: BTreeMap<String, Vec<String>>,
+}
+
+impl Default for YaraLMapping {
+    fn default() -> Self {
+        YaraLMapping {
+            fields: BTreeMap::new(),
+            default_field: Some("additional.fields[\"{surface}\"]".to_string()),
+            filters: BTreeMap::new(),
+        }
+    }
+}
+
+impl YaraLMapping {
+    /// Parse a mapping from YAML; omitted sections take their defaults.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigErrorKind::Parse`] for malformed YAML and
+    /// [`ConfigErrorKind::Invalid`] when a field name is empty.
+    pub fn from_yaml(input: &str) -> Result<Self, ConfigError> {
+        let mapping: Self = serde_saphyr::from_str(input).map_err(|e| ConfigError {
+            kind: ConfigErrorKind::Parse,
+            message: format!("invalid YARA-L mapping: {}", e),
+        })?;
+        let empty = mapping
+            .fields
+            .iter()
+            .find(|(_, field)| field.trim().is_empty())
+            .map(|(key, _)| format!("fields.{}", key))
+            .or_else(|| {
+                let default = mapping.default_field.as_deref()?;
+                default
+                    .trim()
+                    .is_empty()
+                    .then(|| "default_field".to_string())
+            });
+        if let Some(key) = empty {
+            return Err(ConfigError {
+                kind: ConfigErrorKind::Invalid,
+                message: format!("{} must name a UDM field", key),
+            });
+        }
+        Ok(mapping)
+    }
+
+    /// The UDM field for `surface` at `target`, if mapped.
+    pub fn field(&self, surface: &str, target: &str) -> Option<String> {
+        self.fields
+            .get(&format!("{}:{}", surface, target))
+            .or_else(|| self.fields.get(surface))
+            .cloned()
+            .or_else(|| {
+                let default = self.default_field.as_deref()?;
+                Some(default.replace("{surface}", surface))
+            })
+    }
+}
+
+/// Convert the pattern indicators of `doc` into YARA-L 2.0 rules.
+///
+/// The document is normalized first, so shorthand patterns and default
+/// targets convert like their expanded forms. Each rule matches a single
+/// event `$e` whose mapped field satisfies the indicator's condition, plus
+/// the mapping's filters for the indicator's protocol. Rule names are the
+/// indicator ID in lowercase with other characters replaced by `_`.
+///
+/// Origin predicates have no UDM counterpart; rules for indicators with one
+/// are emitted without it and noted.
+pub fn yara_l(doc: &Document, mapping: &YaraLMapping) -> Exported {
+    let doc = normalize(doc.clone());
+    let attack = &doc.attack;
+    let mut exported = Exported::default();
+    for indicator in attack.indicators.iter().flatten() {
+        let id = indicator.id.clone().unwrap_or_default();
+        match yara_l_events(indicator, mapping) {
+            Ok(events) => {
+                if indicator.origin.is_some() {
+                    exported
+                        .notes
+                        .push(format!("{}: origin predicate not converted", id));
+                }
+                let name = rule_name(&id);
+                let text = yara_l_rule(&doc, indicator, &name, &events);
+                exported.rules.push(ExportedRule {
+                    indicator_id: id,
+                    name,
+                    text,
+                });
+            }
+            Err(reason) => exported.notes.push(format!("{}: {}", id, reason)),
+        }
+    }
+    exported
+}
+
+/// The `events:` lines for `indicator`, or why it cannot be converted.
+fn yara_l_events(indicator: &Indicator, mapping: &YaraLMapping) -> Result<Vec<String>, String> {
+    let Some(pattern) = &indicator.pattern else {
+        let kind = if indicator.expression.is_some() {
+            "expression"
+        } else if indicator.semantic.is_some() {
+            "semantic"
+        } else {
+            "frequency"
+        };
+        return Err(format!("{} indicators have no YARA-L equivalent", kind));
+    };
+    let target = pattern.target.as_deref().unwrap_or_default();
+    if target.contains('!') {
+        return Err(format!("decoded target '{}' not converted", target));
+    }
+    let field = mapping
+        .field(&indicator.surface, target)
+        .ok_or_else(|| format!("no UDM field mapped for surface '{}'", indicator.surface))?;
+    let field = format!("$e.{}", field);
+
+    let protocol = indicator.protocol.as_deref().unwrap_or_default();
+    let mut events: Vec<String> = mapping.filters.get(protocol).cloned().unwrap_or_default();
+    match &pattern.condition {
+        Some(Condition::Equality(value)) => events.push(comparison(&field, "=", value)?),
+        Some(Condition::Operators(cond)) => events.extend(operator_terms(&field, cond)?),
+        None => return Err("pattern has no condition".to_string()),
+    }
+    Ok(events)
+}
+
+fn operator_terms(field: &str, cond: &MatchCondition) -> Result<Vec<String>, String> {
+    if cond.json_patch.is_some() {
+        return Err("json_patch conditions have no YARA-L equivalent".to_string());
+    }
+    let mut terms = Vec::new();
+    if let Some(s) = &cond.contains {
+        terms.push(format!(
+            "{} = /{}/",
+            field,
+            regex_literal(&regex::escape(s))
+        ));
+    }
+    if let Some(s) = &cond.starts_with {
+        terms.push(format!(
+            "{} = /^{}/",
+            field,
+            regex_literal(&regex::escape(s))
+        ));
+    }
+    if let Some(s) = &cond.ends_with {
+        terms.push(format!(
+            "{} = /{}$/",
+            field,
+            regex_literal(&regex::escape(s))
+        ));
+    }
+    if let Some(re) = &cond.regex {
+        terms.push(format!("{} = /{}/", field, regex_literal(re)));
+    }
+    if let Some(values) = &cond.any_of {
+        let alternatives = values
+            .iter()
+            .map(|v| comparison(field, "=", v))
+            .collect::<Result<Vec<_>, _>>()?;
+        match alternatives.len() {
+            0 => return Err("empty any_of never matches".to_string()),
+            1 => terms.extend(alternatives),
+            _ => terms.push(format!("({})", alternatives.join(" or "))),
+        }
+    }
+    let bounds = [
+        (">", cond.gt),
+        ("<", cond.lt),
+        (">=", cond.gte),
+        ("<=", cond.lte),
+    ];
+    for (op, bound) in bounds {
+        if let Some(bound) = bound {
+            terms.push(format!("{} {} {}", field, op, bound));
+        }
+    }
+    match cond.exists {
+        Some(true) => terms.push(format!("{} != \"\"", field)),
+        Some(false) => terms.push(format!("{} = \"\"", field)),
+        None => {}
+    }
+    Ok(terms)
+}
+
+fn comparison(field: &str, op: &str, value: &Value) -> Result<String, String> {
+    let literal = match value {
+        Value::String(s) => string_literal(s),
+        Value::Number(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        other => return Err(format!("cannot compare a UDM field to {}", other)),
+    };
+    Ok(format!("{} {} {}", field, op, literal))
+}
+
+fn yara_l_rule(doc: &Document, indicator: &Indicator, name: &str, events: &[String]) -> String {
+    let attack = &doc.attack;
+    let mut meta: Vec<(&str, String)> = Vec::new();
+    if let Some(author) = &attack.author {
+        meta.push(("author", author.clone()));
+    }
+    let description = indicator
+        .description
+        .as_ref()
+        .or(attack.name.as_ref())
+        .or(attack.description.as_ref());
+    if let Some(description) = description {
+        meta.push(("description", description.clone()));
+    }
+    let severity = indicator.severity.as_ref().or(match &attack.severity {
+        Some(Severity::Scalar(level)) | Some(Severity::Object { level, .. }) => Some(level),
+        None => None,
+    });
+    if let Some(severity) = severity {
+        meta.push(("severity", severity_name(severity).to_string()));
+    }
+    if let Some(id) = &attack.id {
+        meta.push(("oatf_attack_id", id.clone()));
+    }
+    meta.push((
+        "oatf_indicator_id",
+        indicator.id.clone().unwrap_or_default(),
+    ));
+    meta.push(("oatf_surface", indicator.surface.clone()));
+
+    let mut text = format!("rule {} {{\n  meta:\n", name);
+    for (key, value) in meta {
+        let _ = writeln!(text, "    {} = {}", key, string_literal(&value));
+    }
+    text.push_str("\n  events:\n");
+    for event in events {
+        let _ = writeln!(text, "    {}", event);
+    }
+    text.push_str("\n  condition:\n    $e\n}\n");
+    text
+}
+
+fn rule_name(indicator_id: &str) -> String {
+    let name: String = indicator_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name
+    } else {
+        format!("oatf_{}", name)
+    }
+}
+
+fn severity_name(level: &SeverityLevel) -> &'static str {
+    match level {
+        SeverityLevel::Informational => "Informational",
+        SeverityLevel::Low => "Low",
+        SeverityLevel::Medium => "Medium",
+        SeverityLevel::High => "High",
+        SeverityLevel::Critical => "Critical",
+    }
+}
+
+fn string_literal(s: &str) -> String {
+    let mut literal = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+/// `re` with unescaped slashes escaped, for a `/…/` literal.
+fn regex_literal(re: &str) -> String {
+    let mut literal = String::new();
+    let mut escaped = false;
+    for c in re.chars() {
+        if c == '/' && !escaped {
+            literal.push('\\');
+        }
+        escaped = c == '\\' && !escaped;
+        literal.push(c);
+    }
+    literal
+}
@@ -0,0 +1,432 @@
+//! Converters from LLM red-team tool content to skeleton OATF documents.
+//!
+//! The converters give teams a starting point for migrating existing content,
+//! not a finished attack: every document is a `draft` whose execution replays
+//! the original prompts or tool calls, with draft indicators derived from
+//! whatever the source says a successful attack looks like. Anything that has
+//! no OATF equivalent is listed in [`Imported::notes`] for the author to
+//! review.
+//!
+//! - [`promptfoo`] reads a promptfoo configuration: one document per test.
+//!   Suites targeting promptfoo's `mcp` provider become `mcp_client` tool
+//!   calls; all others become `ag_ui_client` user messages.
+//! - [`garak`] reads a garak report (`*.report.jsonl`): one document per probe
+//!   with a phase per distinct prompt, since garak configurations only name
+//!   probes and the prompts exist in the report.
+
+use crate::error::{ImportError, ImportErrorKind};
+use crate::types::Document;
+use regex::Regex;
+use serde_json::{Map, Value, json};
+use std::sync::LazyLock;
+
+/// A document converted from third-party content.
+#[derive(Clone, Debug)]
+pub struct Imported {
+    /// What the document was converted from (a promptfoo test description
+    /// or a garak probe class name).
+    pub source: String,
+    /// The skeleton document, unnormalized.
+    pub document: Document,
+    /// Source content that was not converted, for the author to review.
+    pub notes: Vec<String>,
+}
+
+static TEMPLATE_VAR_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{\s*([A-Za-z_][A-Za-z0-9_]*)\s*\}\}").unwrap());
+
+// ─── promptfoo ──────────────────────────────────────────────────────────────
+
+/// Convert a promptfoo configuration (`promptfooconfig.yaml`) into one
+/// document per entry in `tests`.
+///
+/// The first inline prompt is rendered with each test's `vars` (`{{ name }}`
+/// substitution only); without inline prompts, the `prompt` variable is
+/// used. When a provider has the id `mcp`, the rendered prompt must be the
+/// JSON tool call that provider expects (`{"tool": …, "args": …}`), and the
+/// test becomes an `mcp_client` `tools/call`; otherwise it becomes an
+/// `ag_ui_client` user message.
+///
+/// promptfoo assertions describe a safe response, so only negative
+/// assertions translate into indicators of a successful attack:
+/// `not-contains`, `not-icontains`, and `not-regex` become pattern
+/// indicators on the tool response (`tool_response`) or on the agent's
+/// streamed text (`agent_event`, `data.delta`). Assertions from
+/// `defaultTest` apply to every test. Other assertion types, `file://`
+/// prompts, and the `redteam` section are reported in the notes.
+///
+/// # Errors
+///
+/// Returns [`ImportErrorKind::Parse`] if the input is not a YAML mapping, and
+/// [`ImportErrorKind::Unsupported`] if it has no tests.
+pub fn promptfoo(config: &str) -> Result<Vec<Imported>, ImportError> {
+    let config: Value = serde_saphyr::from_str(config).map_err(|e| ImportError {
+        kind: ImportErrorKind::Parse,
+        message: format!("invalid promptfoo configuration: {}", e),
+    })?;
+    if !config.is_object() {
+        return Err(ImportError {
+            kind: ImportErrorKind::Parse,
+            message: "promptfoo configuration must be a mapping".to_string(),
+        });
+    }
+    let tests = config
+        .get("tests")
+        .and_then(Value::as_array)
+        .filter(|t| !t.is_empty())
+        .ok_or_else(|| ImportError {
+            kind: ImportErrorKind::Unsupported,
+            message: "promptfoo configuration has no inline tests".to_string(),
+        })?;
+
+    let mcp = list(config.get("providers")).iter().any(|provider| {
+        let id = provider.as_str().or_else(|| provider.get("id")?.as_str());
+        id == Some("mcp")
+    });
+    let prompt = list(config.get("prompts")).into_iter().find_map(|p| {
+        p.as_str()
+            .or_else(|| p.get("raw")?.as_str())
+            .map(str::to_string)
+    });
+    let default_asserts = config
+        .get("defaultTest")
+        .map(|d| list(d.get("assert")))
+        .unwrap_or_default();
+
+    let mut shared_notes = Vec::new();
+    if prompt.as_deref().is_some_and(|p| p.starts_with("file://")) {
+        shared_notes.push("prompts loaded from files were not converted".to_string());
+    }
+    if config.get("redteam").is_some() {
+        shared_notes.push(
+            "the redteam section generates tests at run time and was not converted".to_string(),
+        );
+    }
+    let prompt = prompt.filter(|p| !p.starts_with("file://"));
+
+    let mut imported = Vec::new();
+    for (i, test) in tests.iter().enumerate() {
+        let source = test
+            .get("description")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("test {}", i + 1));
+        let mut notes = shared_notes.clone();
+
+        let vars = test.get("vars").and_then(Value::as_object);
+        let rendered = match (&prompt, vars) {
+            (Some(template), _) => Some(render(template, vars)),
+            (None, Some(vars)) => vars.get("prompt").map(var_text),
+            (None, None) => None,
+        };
+        let Some(rendered) = rendered else {
+            notes.push("no prompt could be resolved; execution state is empty".to_string());
+            imported.push(promptfoo_document(source, json!({}), &[], mcp, notes));
+            continue;
+        };
+
+        let state = if mcp {
+            match serde_json::from_str::<Value>(&rendered)
+                .ok()
+                .filter(|call| call.get("tool").and_then(Value::as_str).is_some())
+            {
+                Some(call) => json!({"tools": [{
+                    "name": call["tool"],
+                    "arguments": call.get("args").cloned().unwrap_or_else(|| json!({})),
+                }]}),
+                None => {
+                    notes.push(
+                        "prompt is not an MCP tool call ({\"tool\": …, \"args\": …}); execution state is empty"
+                            .to_string(),
+                    );
+                    json!({})
+                }
+            }
+        } else {
+            json!({"run_agent_input": {"messages": [{"role": "user", "content": rendered}]}})
+        };
+
+        let mut asserts = default_asserts.clone();
+        asserts.extend(list(test.get("assert")));
+        imported.push(promptfoo_document(source, state, &asserts, mcp, notes));
+    }
+    Ok(imported)
+}
+
+fn promptfoo_document(
+    source: String,
+    state: Value,
+    asserts: &[Value],
+    mcp: bool,
+    mut notes: Vec<String>,
+) -> Imported {
+    let (mode, surface, target) = if mcp {
+        ("mcp_client", "tool_response", None)
+    } else {
+        ("ag_ui_client", "agent_event", Some("data.delta"))
+    };
+
+    let mut indicators = Vec::new();
+    for assertion in asserts {
+        let kind = assertion.get("type").and_then(Value::as_str).unwrap_or("");
+        let values = list(assertion.get("value"));
+        let condition: Vec<Value> = match kind {
+            "not-contains" => values
+                .iter()
+                .filter_map(Value::as_str)
+                .map(|v| json!({"contains": v}))
+                .collect(),
+            "not-icontains" => values
+                .iter()
+                .filter_map(Value::as_str)
+                .map(|v| json!({"regex": format!("(?i){}", regex::escape(v))}))
+                .collect(),
+            "not-regex" => values
+                .iter()
+                .filter_map(Value::as_str)
+                .map(|v| json!({"regex": v}))
+                .collect(),
+            _ => {
+                notes.push(format!("assertion '{}' was not converted", kind));
+                continue;
+            }
+        };
+        for condition in condition {
+            let mut pattern = condition;
+            if let Some(target) = target {
+                pattern["target"] = json!(target);
+            }
+            indicators.push(json!({
+                "surface": surface,
+                "description": format!("Converted from promptfoo '{}' assertion", kind),
+                "pattern": pattern,
+            }));
+        }
+    }
+
+    let document = skeleton(
+        &source,
+        "promptfoo",
+        json!({"mode": mode, "state": state}),
+        indicators,
+    );
+    Imported {
+        source,
+        document,
+        notes,
+    }
+}
+
+// ─── garak ──────────────────────────────────────────────────────────────────
+
+/// Convert a garak report (JSON Lines, `*.report.jsonl`) into one document
+/// per probe.
+///
+/// Each distinct prompt of the probe's `attempt` entries becomes an
+/// `ag_ui_client` phase that sends it as a user message and advances on
+/// `run_finished`. Detector trigger strings recorded with the attempts
+/// (`notes.triggers`) become pattern indicators on the agent's streamed
+/// text; the detectors themselves are listed in the notes. Other entry
+/// types (`start_run setup`, `init`, `eval`, …) are ignored.
+///
+/// # Errors
+///
+/// Returns [`ImportErrorKind::Parse`] for a line that is not valid JSON, and
+/// [`ImportErrorKind::Unsupported`] if the report has no attempts.
+pub fn garak(report: &str) -> Result<Vec<Imported>, ImportError> {
+    let mut probes: Vec<GarakProbe> = Vec::new();
+
+    for (line_no, line) in report.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry: Value = serde_json::from_str(line).map_err(|e| ImportError {
+            kind: ImportErrorKind::Parse,
+            message: format!("garak report line {}: {}", line_no + 1, e),
+        })?;
+        if entry.get("entry_type").and_then(Value::as_str) != Some("attempt") {
+            continue;
+        }
+        let probe = entry
+            .get("probe_classname")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown");
+        let index = match probes.iter().position(|p| p.name == probe) {
+            Some(index) => index,
+            None => {
+                probes.push(GarakProbe {
+                    name: probe.to_string(),
+                    ..GarakProbe::default()
+                });
+                probes.len() - 1
+            }
+        };
+        let GarakProbe {
+            prompts,
+            triggers,
+            detectors,
+            ..
+        } = &mut probes[index];
+
+        if let Some(prompt) = entry.get("prompt").and_then(garak_prompt_text)
+            && !prompts.contains(&prompt)
+        {
+            prompts.push(prompt);
+        }
+        for trigger in list(entry.pointer("/notes/triggers")) {
+            if let Some(trigger) = trigger.as_str().filter(|t| !t.is_empty())
+                && !triggers.iter().any(|t| t == trigger)
+            {
+                triggers.push(trigger.to_string());
+            }
+        }
+        if let Some(results) = entry.get("detector_results").and_then(Value::as_object) {
+            for detector in results.keys() {
+                if !detectors.contains(detector) {
+                    detectors.push(detector.clone());
+                }
+            }
+        }
+    }
+
+    if probes.is_empty() {
+        return Err(ImportError {
+            kind: ImportErrorKind::Unsupported,
+            message: "garak report has no attempt entries".to_string(),
+        });
+    }
+
+    let imported = probes
+        .into_iter()
+        .map(|probe| {
+            let GarakProbe {
+                name: probe,
+                prompts,
+                triggers,
+                detectors,
+            } = probe;
+            let count = prompts.len();
+            let phases: Vec<Value> = prompts
+                .into_iter()
+                .enumerate()
+                .map(|(i, prompt)| {
+                    let mut phase = json!({
+                        "name": format!("prompt_{}", i + 1),
+                        "state": {"run_agent_input": {"messages": [{"role": "user", "content": prompt}]}},
+                    });
+                    if i + 1 < count {
+                        phase["trigger"] = json!({"event": "run_finished"});
+                    }
+                    phase
+                })
+                .collect();
+            let indicators = triggers
+                .iter()
+                .map(|trigger| {
+                    json!({
+                        "surface": "agent_event",
+                        "description": "Converted from a garak detector trigger",
+                        "pattern": {"target": "data.delta", "contains": trigger},
+                    })
+                })
+                .collect();
+
+            let mut notes = Vec::new();
+            if count == 0 {
+                notes.push("attempts carry no prompt text; execution state is empty".to_string());
+            }
+            if !detectors.is_empty() {
+                notes.push(format!(
+                    "garak detectors were not converted: {}",
+                    detectors.join(", ")
+                ));
+            }
+            let execution = if count == 0 {
+                json!({"mode": "ag_ui_client", "state": {}})
+            } else {
+                json!({"mode": "ag_ui_client", "phases": phases})
+            };
+            Imported {
+                document: skeleton(&probe, "garak", execution, indicators),
+                source: probe,
+                notes,
+            }
+        })
+        .collect();
+    Ok(imported)
+}
+
+/// The attempts of one garak probe, in first-seen order.
+#[derive(Default)]
+struct GarakProbe {
+    name: String,
+    prompts: Vec<String>,
+    triggers: Vec<String>,
+    detectors: Vec<String>,
+}
+
+/// Prompt text of a garak attempt: a plain string in older reports, a
+/// conversation (`{"turns": [{"role", "content": {"text"}}]}`) in newer
+/// ones, of which the last user turn is used.
+fn garak_prompt_text(prompt: &Value) -> Option<String> {
+    if let Some(text) = prompt.as_str() {
+        return Some(text.to_string());
+    }
+    prompt
+        .get("turns")?
+        .as_array()?
+        .iter()
+        .rev()
+        .filter(|turn| turn.get("role").and_then(Value::as_str) != Some("assistant"))
+        .find_map(|turn| {
+            let content = turn.get("content")?;
+            content
+                .as_str()
+                .or_else(|| content.get("text")?.as_str())
+                .map(str::to_string)
+        })
+}
+
+// ─── Helpers ────────────────────────────────────────────────────────────────
+
+fn skeleton(name: &str, tool: &str, execution: Value, indicators: Vec<Value>) -> Document {
+    let mut attack = json!({
+        "name": name,
+        "status": "draft",
+        "description": format!("Imported from {}: {}", tool, name),
+        "classification": {"tags": ["imported", tool]},
+        "execution": execution,
+    });
+    if !indicators.is_empty() {
+        attack["indicators"] = Value::Array(indicators);
+    }
+    serde_json::from_value(json!({"oatf": "0.1", "attack": attack}))
+        .expect("imported document matches the document model")
+}
+
+/// A value that may be a single item or a list, as a list.
+fn list(value: Option<&Value>) -> Vec<Value> {
+    match value {
+        Some(Value::Array(items)) => items.clone(),
+        Some(Value::Null) | None => Vec::new(),
+        Some(item) => vec![item.clone()],
+    }
+}
+
+fn var_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn render(template: &str, vars: Option<&Map<String, Value>>) -> String {
+    TEMPLATE_VAR_RE
+        .replace_all(template, |caps: &regex::Captures| {
+            match vars.and_then(|v| v.get(&caps[1])) {
+                Some(value) => var_text(value),
+                None => caps[0].to_string(),
+            }
+        })
+        .into_owned()
+}
@@ -0,0 +1,132 @@
+//! The JSONPath engine behind `json_path` extractors and captures (feature
+//! `jsonpath`).
+//!
+//! Validation (V-015, V-054) and evaluation both go through the installed
+//! [`JsonPathEngine`], so a selector the validator accepts is one the
+//! evaluator can run. The default, [`SerdeJsonPath`], implements RFC 9535
+//! with the [`serde_json_path`](https://crates.io/crates/serde_json_path)
+//! crate; embedders that need a faster or differently-compliant engine
+//! install their own with [`set_engine`]:
+//!
+//! ```rust
+//! use oatf::jsonpath::{self, JsonPathEngine, JsonPathError};
+//! use serde_json::{Value, json};
+//!
+//! /// Only `$.a.b` member paths.
+//! struct Members;
+//!
+//! impl JsonPathEngine for Members {
+//!     fn check(&self, path: &str) -> Result<(), JsonPathError> {
+//!         match path.strip_prefix("$.") {
+//!             Some(rest) if rest.split('.').all(|s| {
+//!                 !s.is_empty() && s.chars().all(|c| c.is_alphanumeric() || c == '_')
+//!             }) => Ok(()),
+//!             _ => Err(JsonPathError {
+//!                 message: format!("not a member path: '{}'", path),
+//!             }),
+//!         }
+//!     }
+//!
+//!     fn query(&self, path: &str, value: &Value) -> Result<Vec<Value>, JsonPathError> {
+//!         self.check(path)?;
+//!         let rest = &path[2..];
+//!         Ok(oatf::primitives::resolve_simple_path(rest, value).into_iter().collect())
+//!     }
+//! }
+//!
+//! jsonpath::set_engine(Members);
+//! assert!(jsonpath::engine().check("$.tools[0]").is_err());
+//! assert_eq!(jsonpath::engine().query("$.a", &json!({"a": 1})).unwrap(), [json!(1)]);
+//! jsonpath::reset_engine();
+//! ```
+
+pub use crate::error::JsonPathError;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex, RwLock};
+
+/// A JSONPath implementation.
+pub trait JsonPathEngine: Send + Sync {
+    /// Check that `path` is a query this engine can evaluate.
+    fn check(&self, path: &str) -> Result<(), JsonPathError>;
+
+    /// The values `path` selects in `value`, in document order.
+    fn query(&self, path: &str, value: &Value) -> Result<Vec<Value>, JsonPathError>;
+}
+
+/// Compiled paths a [`SerdeJsonPath`] keeps before starting over.
+const COMPILED_LIMIT: usize = 1024;
+
+/// The default engine: RFC 9535 JSONPath via `serde_json_path`.
+///
+/// Paths are compiled once: [`check`](JsonPathEngine::check) keeps the
+/// compiled path, so evaluating a selector that validation already accepted
+/// does not parse it again.
+#[derive(Debug, Default)]
+pub struct SerdeJsonPath {
+    compiled: Mutex<HashMap<String, Arc<serde_json_path::JsonPath>>>,
+}
+
+impl SerdeJsonPath {
+    /// An engine with nothing compiled yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `path` compiled, from the cache when it was compiled before.
+    fn compile(&self, path: &str) -> Result<Arc<serde_json_path::JsonPath>, JsonPathError> {
+        let mut compiled = self.compiled.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(found) = compiled.get(path) {
+            return Ok(Arc::clone(found));
+        }
+        let parsed = serde_json_path::JsonPath::parse(path).map_err(|e| JsonPathError {
+            message: e.to_string(),
+        })?;
+        if compiled.len() >= COMPILED_LIMIT {
+            compiled.clear();
+        }
+        let parsed = Arc::new(parsed);
+        compiled.insert(path.to_string(), Arc::clone(&parsed));
+        Ok(parsed)
+    }
+}
+
+impl JsonPathEngine for SerdeJsonPath {
+    fn check(&self, path: &str) -> Result<(), JsonPathError> {
+        self.compile(path).map(|_| ())
+    }
+
+    fn query(&self, path: &str, value: &Value) -> Result<Vec<Value>, JsonPathError> {
+        Ok(self
+            .compile(path)?
+            .query(value)
+            .all()
+            .into_iter()
+            .cloned()
+            .collect())
+    }
+}
+
+static DEFAULT: LazyLock<Arc<dyn JsonPathEngine>> =
+    LazyLock::new(|| Arc::new(SerdeJsonPath::new()));
+
+static ENGINE: RwLock<Option<Arc<dyn JsonPathEngine>>> = RwLock::new(None);
+
+/// The installed engine, [`SerdeJsonPath`] unless [`set_engine`] replaced it.
+pub fn engine() -> Arc<dyn JsonPathEngine> {
+    match &*ENGINE.read().unwrap_or_else(|e| e.into_inner()) {
+        Some(engine) => Arc::clone(engine),
+        None => Arc::clone(&DEFAULT),
+    }
+}
+
+/// Install `engine` for every later validation and evaluation in the
+/// process.
+pub fn set_engine(engine: impl JsonPathEngine + 'static) {
+    *ENGINE.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(engine));
+}
+
+/// Go back to [`SerdeJsonPath`].
+pub fn reset_engine() {
+    *ENGINE.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
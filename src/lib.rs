@@ -12,6 +12,7 @@
 //! # Quick Start
 //!
 //! ```rust
+//! # #[cfg(all(feature = "validate", feature = "normalize"))] {
 //! let yaml = r#"
 //! oatf: "0.1"
 //! attack:
@@ -36,42 +37,172 @@
 //!
 //! let result = oatf::load(yaml).expect("valid document");
 //! println!("Loaded: {:?}", result.document.attack.name);
+//! # }
 //! ```
 //!
+//! [`prelude`] re-exports the stable core of the API for glob import.
+//!
 //! # Feature Flags
 //!
 //! | Feature    | Default | Description |
 //! |------------|---------|-------------|
-//! | `cel-eval` | yes     | CEL expression evaluation via the [`cel`] crate. Enables [`evaluate::DefaultCelEvaluator`]. |
+//! | `normalize` | yes    | [`normalize()`] and [`serialize_minimal`]. |
+//! | `validate` | yes     | Conformance rules ([`validate()`], [`validate::explain`]); enables `regex`, `jsonpath`, and `attachments`. With `normalize`, also [`load`], [`load_dir`], and [`catalog`]. |
+//! | `evaluate` | yes     | Indicator evaluation, verdicts, execution engines, and static analysis ([`evaluate`], [`engine`], [`analyze`]); enables `validate` and `normalize`. |
+//! | `regex`    | via `validate` | Regex-backed modules ([`references`], [`transform`], `interop`). |
+//! | `attachments` | via `validate` | Attachment decoding and `{{attachment:name}}` references ([`attachments`]); enables `regex`. |
+//! | `jsonpath` | via `validate` | JSONPath parsing for `json_path` extractors (V-015 and extraction). |
+//! | `cel-eval` | yes     | CEL expression evaluation via the [`cel`] crate. Enables [`evaluate::DefaultCelEvaluator`]; implies `evaluate`. Without it, [`evaluate::CelSubsetEvaluator`] evaluates a small CEL subset. |
+//! | `bundle`   | no      | Gzip-compressed documents and `.tar.gz` bundles in [`load_path`]; integrity-checked packs (`bundle::Pack`). |
+//! | `distribution` | no  | Push and pull bundles as OCI artifacts (`distribution` module). |
+//! | `feed`     | no      | Incremental sync of documents from HTTPS feeds (`feed` module). |
+//! | `library`  | no      | Curated canonical attack documents embedded in the crate (`library` module). |
+//! | `audit`    | no      | Structured, optionally hash-chained audit log of document lifecycle operations (`audit` module). |
+//! | `webhook`  | no      | HTTP webhook verdict sink (`sink::Webhook`). |
+//! | `otel`     | no      | OpenTelemetry span events and log records for verdicts (`otel` module). |
+//! | `store`    | no      | Embedded SQLite verdict history with queries and retention (`store` module). |
+//! | `registry-verify` | no | `spec::verify_registries` detects drift between the embedded registries and a specification checkout. |
+//!
+//! With `default-features = false` the crate is a parser only: [`parse()`],
+//! [`serialize()`], the document types, and the execution primitives that need
+//! no regex or JSONPath engine.
 
+#[cfg(feature = "evaluate")]
+pub mod analyze;
+#[cfg(feature = "attachments")]
+pub mod attachments;
+#[cfg(feature = "audit")]
+pub mod audit;
+pub mod authoring;
+#[cfg(all(feature = "validate", feature = "normalize"))]
+pub mod bundle;
+#[cfg(all(feature = "validate", feature = "normalize"))]
+pub mod calibrate;
+#[cfg(all(feature = "validate", feature = "normalize"))]
+pub mod catalog;
+pub mod clock;
+pub mod context;
+#[cfg(feature = "evaluate")]
+pub mod convert;
+pub mod defaults;
+#[cfg(feature = "distribution")]
+pub mod distribution;
+#[cfg(feature = "evaluate")]
+pub mod engine;
 pub mod enums;
 pub mod error;
+#[cfg(feature = "evaluate")]
 pub mod evaluate;
+#[cfg(feature = "feed")]
+pub mod feed;
+pub mod intern;
+#[cfg(feature = "regex")]
+pub mod interop;
+#[cfg(feature = "jsonpath")]
+pub mod jsonpath;
+#[cfg(feature = "library")]
+pub mod library;
+#[cfg(feature = "normalize")]
 pub mod normalize;
+#[cfg(feature = "otel")]
+pub mod otel;
 pub mod parse;
+pub mod prelude;
 pub mod primitives;
+#[cfg(feature = "regex")]
+pub mod references;
+#[cfg(feature = "evaluate")]
+pub mod report;
+pub mod select;
 pub mod serialize;
+pub mod sharing;
+pub mod sink;
+pub mod spec;
+#[cfg(feature = "store")]
+pub mod store;
+#[cfg(feature = "regex")]
+pub mod transform;
 pub mod types;
+#[cfg(feature = "validate")]
 pub mod validate;
 
+#[cfg(any(
+    feature = "audit",
+    feature = "bundle",
+    feature = "distribution",
+    feature = "feed"
+))]
+pub(crate) mod digest;
+#[cfg_attr(not(feature = "evaluate"), allow(dead_code))]
 pub(crate) mod event_registry;
+#[cfg(all(feature = "validate", feature = "normalize"))]
+pub(crate) mod parallel;
+#[cfg_attr(not(feature = "validate"), allow(dead_code))]
+pub(crate) mod projection;
+#[cfg(feature = "validate")]
+pub(crate) mod rule_docs;
+#[cfg_attr(not(feature = "evaluate"), allow(dead_code))]
 pub(crate) mod surface;
 
 pub use error::*;
 pub use types::*;
 
 // Re-export entry-point functions at the crate root for convenience.
-pub use normalize::normalize;
-pub use parse::parse;
-pub use serialize::serialize;
-pub use validate::validate;
+#[cfg(all(feature = "validate", feature = "normalize"))]
+pub use bundle::{load_dir, load_path};
+#[cfg(feature = "normalize")]
+pub use normalize::{minimize, normalize, normalize_indicator_set};
+pub use parse::{parse, parse_indicator_set};
+#[cfg(feature = "normalize")]
+pub use serialize::serialize_minimal;
+pub use serialize::{serialize, serialize_canonical, serialize_indicator_set};
+pub use spec::spec_info;
+#[cfg(feature = "registry-verify")]
+pub use spec::verify_registries;
+#[cfg(feature = "validate")]
+pub use validate::{
+    Dedupe, DiagnosticPolicy, ResourceLimits, validate, validate_indicator_set, validate_profile,
+    validate_regex_dialect, validate_resource_limits, validate_with_policy,
+};
 
 /// Result of the [`load`] convenience entry point.
+#[cfg(all(feature = "validate", feature = "normalize"))]
+#[derive(Debug)]
 pub struct LoadResult {
     /// The normalized document.
     pub document: Document,
+    /// The profile the document was validated under. [`load`] reports the
+    /// profile the document satisfies; [`load_with_profile`] the requested one.
+    pub profile: enums::Profile,
     /// Non-fatal warnings produced during validation.
     pub warnings: Vec<Diagnostic>,
+    /// Which fields of `document` normalization filled in, and which came
+    /// from organization defaults ([`load_with_defaults`]).
+    pub provenance: normalize::Provenance,
+}
+
+#[cfg(all(feature = "validate", feature = "normalize"))]
+impl LoadResult {
+    /// Whether the field at `path` (e.g. `attack.indicators[0].id`) of the
+    /// normalized document was written in the source or filled in by
+    /// normalization. `None` if the field is not populated.
+    pub fn provenance(&self, path: &str) -> Option<normalize::FieldProvenance> {
+        self.provenance.get(&self.document, path)
+    }
+
+    /// Serialize the document as its source was written, without the fields
+    /// normalization filled in (see
+    /// [`minimize_with_provenance`](normalize::minimize_with_provenance)).
+    ///
+    /// # Errors
+    ///
+    /// Returns `SerializeError` if the document cannot be converted to YAML.
+    pub fn serialize_minimal(&self) -> Result<String, SerializeError> {
+        serialize::serialize(&normalize::minimize_with_provenance(
+            self.document.clone(),
+            &self.provenance,
+        ))
+    }
 }
 
 /// Convenience entry point composing parse → validate → normalize.
@@ -113,10 +244,52 @@ pub struct LoadResult {
 ///     Err(errors) => eprintln!("{} errors", errors.len()),
 /// }
 /// ```
+#[cfg(all(feature = "validate", feature = "normalize"))]
 pub fn load(input: &str) -> Result<LoadResult, Vec<OATFError>> {
+    load_with_policy(input, &DiagnosticPolicy::default())
+}
+
+/// [`load`] with validation diagnostics — the returned errors or warnings —
+/// consolidated by `policy` (see [`validate_with_policy`]).
+///
+/// # Errors
+///
+/// Returns `Err(Vec<OATFError>)` if parsing fails or validation finds errors.
+///
+/// # Example
+///
+/// ```rust
+/// use oatf::{DiagnosticPolicy, OATFError};
+///
+/// let indicator = "    - surface: tool_description\n      pattern:\n        regex: \"(\"\n";
+/// let yaml = format!(
+///     "oatf: \"0.1\"\nattack:\n  execution:\n    mode: mcp_server\n    state:\n      tools: []\n  indicators:\n{}",
+///     indicator.repeat(5)
+/// );
+/// let policy = DiagnosticPolicy {
+///     max_per_rule: Some(2),
+///     ..DiagnosticPolicy::default()
+/// };
+///
+/// let errors = oatf::load_with_policy(&yaml, &policy).unwrap_err();
+/// let v013: Vec<_> = errors
+///     .iter()
+///     .filter_map(|e| match e {
+///         OATFError::Validation(v) if v.rule == "V-013" => Some(v.message.as_str()),
+///         _ => None,
+///     })
+///     .collect();
+/// assert_eq!(v013.len(), 3);
+/// assert_eq!(v013[2], "and 3 more V-013 errors");
+/// ```
+#[cfg(all(feature = "validate", feature = "normalize"))]
+pub fn load_with_policy(
+    input: &str,
+    policy: &DiagnosticPolicy,
+) -> Result<LoadResult, Vec<OATFError>> {
     let doc = parse::parse(input).map_err(|e| vec![OATFError::Parse(e)])?;
 
-    let result = validate::validate(&doc);
+    let result = validate::validate_with_policy(&doc, policy);
     if !result.errors.is_empty() {
         return Err(result
             .errors
@@ -125,10 +298,190 @@ pub fn load(input: &str) -> Result<LoadResult, Vec<OATFError>> {
             .collect());
     }
 
-    let normalized = normalize::normalize(doc);
+    let profile = doc.profile();
+    let (normalized, provenance) = normalize::normalize_with_provenance(doc);
 
     Ok(LoadResult {
         document: normalized,
+        profile,
+        warnings: result.warnings,
+        provenance,
+    })
+}
+
+/// [`load`] under an explicit [`Profile`](enums::Profile): validation also applies V-052, so a
+/// [`Profile::Full`](enums::Profile::Full) document must have indicators and a
+/// [`Profile::ExecutionOnly`](enums::Profile::ExecutionOnly) document must have neither indicators nor
+/// correlation.
+///
+/// # Errors
+///
+/// Returns `Err(Vec<OATFError>)` if parsing fails or validation finds errors.
+///
+/// # Example
+///
+/// ```rust
+/// use oatf::enums::Profile;
+///
+/// let yaml = r#"
+/// oatf: "0.1"
+/// attack:
+///   execution:
+///     mode: mcp_server
+///     state:
+///       tools:
+///         - name: test-tool
+///           description: "A test tool"
+///           inputSchema:
+///             type: object
+/// "#;
+///
+/// let result = oatf::load_with_profile(yaml, Profile::ExecutionOnly).expect("valid emulation");
+/// assert_eq!(result.profile, Profile::ExecutionOnly);
+/// assert!(oatf::load_with_profile(yaml, Profile::Full).is_err());
+/// ```
+#[cfg(all(feature = "validate", feature = "normalize"))]
+pub fn load_with_profile(
+    input: &str,
+    profile: enums::Profile,
+) -> Result<LoadResult, Vec<OATFError>> {
+    let doc = parse::parse(input).map_err(|e| vec![OATFError::Parse(e)])?;
+
+    let result = validate::validate_profile(&doc, profile);
+    if !result.errors.is_empty() {
+        return Err(result
+            .errors
+            .into_iter()
+            .map(OATFError::Validation)
+            .collect());
+    }
+
+    let (document, provenance) = normalize::normalize_with_provenance(doc);
+    Ok(LoadResult {
+        document,
+        profile,
+        warnings: result.warnings,
+        provenance,
+    })
+}
+
+/// [`load`] with organization defaults: `defaults` fill the fields the
+/// document leaves out (see [`defaults`] for the precedence rules) before
+/// validation. [`LoadResult::provenance`] reports the filled fields as
+/// [`Inherited`](normalize::FieldProvenance::Inherited).
+///
+/// # Errors
+///
+/// Returns `Err(Vec<OATFError>)` if parsing fails or validation of the merged
+/// document finds errors.
+///
+/// # Example
+///
+/// ```rust
+/// use oatf::defaults::OrgDefaults;
+/// use oatf::normalize::FieldProvenance;
+///
+/// let yaml = r#"
+/// oatf: "0.1"
+/// attack:
+///   severity: high
+///   execution:
+///     mode: mcp_server
+///     state:
+///       tools:
+///         - name: test-tool
+///           description: "A test tool"
+///           inputSchema:
+///             type: object
+/// "#;
+///
+/// let defaults = OrgDefaults::from_yaml("author: Red Team\nseverity:\n  confidence: 80\n")?;
+/// let result = oatf::load_with_defaults(yaml, &defaults).expect("valid document");
+/// assert_eq!(result.document.attack.author.as_deref(), Some("Red Team"));
+/// assert_eq!(result.provenance("attack.severity.confidence"), Some(FieldProvenance::Inherited));
+/// assert_eq!(result.provenance("attack.severity.level"), Some(FieldProvenance::Source));
+/// # Ok::<(), oatf::ParseError>(())
+/// ```
+#[cfg(all(feature = "validate", feature = "normalize"))]
+pub fn load_with_defaults(
+    input: &str,
+    defaults: &defaults::OrgDefaults,
+) -> Result<LoadResult, Vec<OATFError>> {
+    let mut doc = parse::parse(input).map_err(|e| vec![OATFError::Parse(e)])?;
+    let scalar_severity = matches!(doc.attack.severity, Some(types::Severity::Scalar(_)));
+    let inherited = defaults.apply(&mut doc);
+
+    let result = validate::validate_with_policy(&doc, &DiagnosticPolicy::default());
+    if !result.errors.is_empty() {
+        return Err(result
+            .errors
+            .into_iter()
+            .map(OATFError::Validation)
+            .collect());
+    }
+
+    let profile = doc.profile();
+    let (document, mut provenance) = normalize::normalize_with_provenance(doc);
+    for path in inherited {
+        if scalar_severity && path == "attack.severity.confidence" {
+            provenance.convert("attack.severity", "N-002");
+        }
+        provenance.inherit(path);
+    }
+    Ok(LoadResult {
+        document,
+        profile,
+        warnings: result.warnings,
+        provenance,
+    })
+}
+
+/// Result of the [`load_indicator_set`] convenience entry point.
+#[cfg(all(feature = "validate", feature = "normalize"))]
+#[derive(Debug)]
+pub struct IndicatorSetLoadResult {
+    /// The normalized indicator set.
+    pub indicator_set: IndicatorSet,
+    /// Non-fatal warnings produced during validation.
+    pub warnings: Vec<Diagnostic>,
+}
+
+/// [`load`] for indicator-only documents: parse → validate → normalize an
+/// [`IndicatorSet`].
+///
+/// # Errors
+///
+/// Returns `Err(Vec<OATFError>)` if parsing fails or validation finds errors.
+///
+/// # Example
+///
+/// ```rust
+/// let yaml = r#"
+/// oatf: "0.1"
+/// indicators:
+///   - surface: tool_description
+///     pattern:
+///       contains: "<IMPORTANT>"
+/// "#;
+///
+/// let result = oatf::load_indicator_set(yaml).expect("valid indicator set");
+/// assert_eq!(result.indicator_set.indicators[0].protocol.as_deref(), Some("mcp"));
+/// ```
+#[cfg(all(feature = "validate", feature = "normalize"))]
+pub fn load_indicator_set(input: &str) -> Result<IndicatorSetLoadResult, Vec<OATFError>> {
+    let set = parse::parse_indicator_set(input).map_err(|e| vec![OATFError::Parse(e)])?;
+
+    let result = validate::validate_indicator_set(&set);
+    if !result.errors.is_empty() {
+        return Err(result
+            .errors
+            .into_iter()
+            .map(OATFError::Validation)
+            .collect());
+    }
+
+    Ok(IndicatorSetLoadResult {
+        indicator_set: normalize::normalize_indicator_set(set),
         warnings: result.warnings,
     })
 }
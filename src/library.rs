@@ -0,0 +1,72 @@
+//! A curated library of canonical attack documents embedded in the crate
+//! (feature `library`).
+//!
+//! The library gives demos, tests, and quick starts realistic content
+//! without fetching an external repository: tool-description poisoning, a
+//! rug-pull tool swap, tool response injection, a destructive tool disguised
+//! as read-only, A2A agent card spoofing, and AG-UI system prompt
+//! extraction. Documents carry `LIB-` attack IDs, are loaded (validated and
+//! normalized) on first access, and are ready for evaluation.
+//!
+//! ```rust
+//! use oatf::enums::Category;
+//!
+//! let poisoning: Vec<_> = oatf::library::by_category(Category::CapabilityPoisoning).collect();
+//! assert_eq!(poisoning[0].attack.id.as_deref(), Some("LIB-001"));
+//! ```
+
+use crate::enums::Category;
+use crate::types::Document;
+use std::sync::LazyLock;
+
+/// The embedded YAML sources, in attack ID order.
+const SOURCES: &[&str] = &[
+    include_str!("library/tool-poisoning.yaml"),
+    include_str!("library/rug-pull.yaml"),
+    include_str!("library/response-injection.yaml"),
+    include_str!("library/oversight-bypass.yaml"),
+    include_str!("library/agent-card-spoofing.yaml"),
+    include_str!("library/ag-ui-prompt-injection.yaml"),
+];
+
+static LIBRARY: LazyLock<Vec<Document>> = LazyLock::new(|| {
+    SOURCES
+        .iter()
+        .map(|source| {
+            crate::load(source)
+                .expect("library documents are valid")
+                .document
+        })
+        .collect()
+});
+
+/// Every library document, normalized, in attack ID order.
+pub fn all() -> &'static [Document] {
+    &LIBRARY
+}
+
+/// The library documents classified under `category`.
+pub fn by_category(category: Category) -> impl Iterator<Item = &'static Document> {
+    all().iter().filter(move |doc| {
+        doc.attack
+            .classification
+            .as_ref()
+            .and_then(|c| c.category.as_ref())
+            == Some(&category)
+    })
+}
+
+/// The library document with attack ID `id`.
+pub fn get(id: &str) -> Option<&'static Document> {
+    all()
+        .iter()
+        .find(|doc| doc.attack.id.as_deref() == Some(id))
+}
+
+/// The YAML source of the library document with attack ID `id`, as authored.
+pub fn source(id: &str) -> Option<&'static str> {
+    let index = all()
+        .iter()
+        .position(|doc| doc.attack.id.as_deref() == Some(id))?;
+    Some(SOURCES[index])
+}
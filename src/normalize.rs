@@ -3,10 +3,13 @@
 //! Converts all execution forms to canonical multi-actor form, expands defaults,
 //! and resolves shorthand patterns. `normalize(normalize(doc)) == normalize(doc)`.
 
+use crate::context::ResolvedContext;
 use crate::enums::*;
-use crate::event_registry::extract_protocol;
 use crate::surface::lookup_surface;
 use crate::types::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
 
 /// Normalize a validated document into its canonical fully-expanded form.
 /// All defaults are materialized, all shorthand forms are expanded,
@@ -39,9 +42,609 @@ pub fn normalize(mut doc: Document) -> Document {
     doc
 }
 
+/// Normalize a validated [`IndicatorSet`] with the indicator steps of
+/// [`normalize`]: `protocol` defaults to the surface's protocol (N-001),
+/// IDs are generated (N-003), targets resolved (N-004), and pattern
+/// shorthand expanded (N-005). Idempotent.
+pub fn normalize_indicator_set(mut set: IndicatorSet) -> IndicatorSet {
+    let mut doc = set.to_document();
+    n003_auto_generate_indicator_ids(&mut doc);
+    n004_resolve_targets(&mut doc);
+    n005_expand_pattern_shorthand(&mut doc);
+    set.indicators = doc.attack.indicators.unwrap_or_default();
+    set
+}
+
+/// Reduce a document to its tersest equivalent form, undoing what
+/// [`normalize`] materializes: values equal to their defaults (N-001, N-002,
+/// N-004, N-008), generated phase names and indicator IDs (N-003), expanded
+/// pattern shorthand (N-005), and the canonical multi-actor form (N-006,
+/// N-007) are removed or collapsed.
+///
+/// Only values the reverse step restores exactly are removed, so
+/// `normalize(minimize(doc)) == normalize(doc)`.
+pub fn minimize(mut doc: Document) -> Document {
+    minimize_execution_form(&mut doc);
+
+    let default_protocol = ResolvedContext::new(&doc)
+        .default_protocol()
+        .map(str::to_string);
+    let attack = &mut doc.attack;
+
+    if attack.name.as_deref() == Some("Untitled") {
+        attack.name = None;
+    }
+    if attack.version == Some(1) {
+        attack.version = None;
+    }
+    if attack.status == Some(Status::Draft) {
+        attack.status = None;
+    }
+    if let Some(Severity::Object {
+        level,
+        confidence: None | Some(50),
+    }) = &attack.severity
+    {
+        attack.severity = Some(Severity::Scalar(level.clone()));
+    }
+
+    let phases = match (&mut attack.execution.phases, &mut attack.execution.actors) {
+        (Some(phases), _) => vec![phases],
+        (None, Some(actors)) => actors.iter_mut().map(|a| &mut a.phases).collect(),
+        (None, None) => Vec::new(),
+    };
+    for phases in phases {
+        for (i, phase) in phases.iter_mut().enumerate() {
+            if phase.name.as_deref() == Some(format!("phase-{}", i + 1).as_str()) {
+                phase.name = None;
+            }
+            if let Some(trigger) = &mut phase.trigger
+                && trigger.event.is_some()
+                && trigger.count == Some(1)
+            {
+                trigger.count = None;
+            }
+        }
+    }
+
+    let attack_id = attack.id.clone();
+    for (i, ind) in attack.indicators.iter_mut().flatten().enumerate() {
+        let generated = match &attack_id {
+            Some(attack_id) => format!("{}-{:02}", attack_id, i + 1),
+            None => format!("indicator-{:02}", i + 1),
+        };
+        if ind.id.as_deref() == Some(generated.as_str()) {
+            ind.id = None;
+        }
+        if ind.protocol.is_some() && ind.protocol == default_protocol {
+            ind.protocol = None;
+        }
+
+        let default_target = lookup_surface(&ind.surface).map(|entry| entry.default_target);
+        if let Some(pattern) = &mut ind.pattern {
+            if pattern.target.is_some() && pattern.target.as_deref() == default_target {
+                pattern.target = None;
+            }
+            collapse_pattern_shorthand(pattern);
+        }
+        if let Some(semantic) = &mut ind.semantic
+            && semantic.target.is_some()
+            && semantic.target.as_deref() == default_target
+        {
+            semantic.target = None;
+        }
+    }
+
+    if attack.indicators.is_some()
+        && let Some(correlation) = &mut attack.correlation
+    {
+        if correlation.logic == Some(CorrelationLogic::Any) {
+            correlation.logic = None;
+        }
+        if correlation.logic.is_none() && correlation.stages.is_none() && correlation.key.is_none()
+        {
+            attack.correlation = None;
+        }
+    }
+
+    if let Some(classification) = &mut attack.classification {
+        for mapping in classification.mappings.iter_mut().flatten() {
+            if mapping.relationship == Some(Relationship::Primary) {
+                mapping.relationship = None;
+            }
+        }
+    }
+
+    doc
+}
+
+/// Turn an operator condition back into shorthand fields (undoes N-005).
+fn collapse_pattern_shorthand(pattern: &mut PatternMatch) {
+    if let Some(Condition::Operators(cond)) = &pattern.condition
+        && cond.exists.is_none()
+        && cond.json_patch.is_none()
+        && !pattern.is_shorthand_fields_present()
+    {
+        let Some(Condition::Operators(cond)) = pattern.condition.take() else {
+            unreachable!();
+        };
+        pattern.contains = cond.contains;
+        pattern.starts_with = cond.starts_with;
+        pattern.ends_with = cond.ends_with;
+        pattern.regex = cond.regex;
+        pattern.any_of = cond.any_of;
+        pattern.gt = cond.gt;
+        pattern.lt = cond.lt;
+        pattern.gte = cond.gte;
+        pattern.lte = cond.lte;
+    }
+}
+
+/// Collapse a lone `default` actor to the multi-phase form, and a lone
+/// state-only phase to the single-phase form; drop MCP tool defaults.
+fn minimize_execution_form(doc: &mut Document) {
+    let exec = &mut doc.attack.execution;
+    let is_mcp_server = |mode: &str| crate::surface::split_mode_version(mode).0 == "mcp_server";
+    for actor in exec.actors.iter_mut().flatten() {
+        if is_mcp_server(&actor.mode) {
+            for state in actor.phases.iter_mut().filter_map(|p| p.state.as_mut()) {
+                remove_mcp_tool_defaults(state);
+            }
+        }
+    }
+    if exec.mode.as_deref().is_some_and(is_mcp_server) {
+        let phase_states = exec
+            .phases
+            .iter_mut()
+            .flatten()
+            .filter_map(|p| p.state.as_mut());
+        for state in exec.state.iter_mut().chain(phase_states) {
+            remove_mcp_tool_defaults(state);
+        }
+    }
+
+    if exec.mode.is_none()
+        && exec.phases.is_none()
+        && let Some([actor]) = exec.actors.as_deref()
+        && actor.name == "default"
+        && !actor.mode.is_empty()
+        && actor.description.is_none()
+        && actor.trust.is_none()
+        && actor.endpoint.is_none()
+        && actor.extensions.is_empty()
+    {
+        let actor = exec.actors.take().unwrap().pop().unwrap();
+        exec.mode = Some(actor.mode);
+        exec.phases = Some(actor.phases);
+    }
+
+    if exec.state.is_none()
+        && let Some([phase]) = exec.phases.as_deref()
+        && phase.name.as_deref().is_none_or(|name| name == "phase-1")
+        && (phase.mode.is_none() || phase.mode == exec.mode)
+        && phase.state.is_some()
+        && phase.description.is_none()
+        && phase.requires.is_none()
+        && phase.extractors.is_none()
+        && phase.on_enter.is_none()
+        && phase.pacing.is_none()
+        && phase.trigger.is_none()
+        && phase.on_timeout.is_none()
+        && phase.extensions.is_empty()
+    {
+        exec.state = exec.phases.take().unwrap().pop().unwrap().state;
+    }
+}
+
+fn remove_mcp_tool_defaults(state: &mut serde_json::Value) {
+    let Some(tools) = state.get_mut("tools").and_then(|t| t.as_array_mut()) else {
+        return;
+    };
+    for tool in tools.iter_mut().filter_map(|t| t.as_object_mut()) {
+        if tool.get("inputSchema") == Some(&serde_json::json!({"type": "object"})) {
+            tool.remove("inputSchema");
+        }
+        if tool.get("description") == Some(&serde_json::json!("")) {
+            tool.remove("description");
+        }
+    }
+}
+
+// ─── Provenance ──────────────────────────────────────────────────────────────
+
+/// Whether a field of a normalized document was written in the source
+/// document, taken from organization defaults, or filled in by normalization.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldProvenance {
+    /// The value was written in the source document, possibly in another
+    /// form (shorthand pattern, single- or multi-phase execution).
+    Source,
+    /// The value came from the [`OrgDefaults`](crate::defaults::OrgDefaults)
+    /// the document was loaded with.
+    Inherited,
+    /// The value was filled in by a normalization step (e.g. `"N-001"`).
+    Default {
+        /// The normalization step that produced the value.
+        step: &'static str,
+    },
+}
+
+/// Fields of a normalized document that normalization filled in, with the
+/// step that produced each, plus the form conversions it applied and the
+/// fields taken from organization defaults. Produced by
+/// [`normalize_with_provenance`] and [`load_with_defaults`](crate::load_with_defaults).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Provenance {
+    defaults: BTreeMap<String, &'static str>,
+    conversions: BTreeMap<String, &'static str>,
+    inherited: BTreeSet<String>,
+}
+
+impl Provenance {
+    /// Provenance of the field at `path` (e.g. `attack.indicators[0].id`)
+    /// in `doc`, the normalized document this provenance was produced with.
+    /// `None` if the field is not populated.
+    pub fn get(&self, doc: &Document, path: &str) -> Option<FieldProvenance> {
+        let value = serde_json::to_value(doc).ok()?;
+        value_at(&value, path)?;
+        if prefixes(path).any(|prefix| self.inherited.contains(prefix)) {
+            return Some(FieldProvenance::Inherited);
+        }
+        Some(match self.default_step(path) {
+            Some(step) => FieldProvenance::Default { step },
+            None => FieldProvenance::Source,
+        })
+    }
+
+    /// The step that filled in `path` or a field enclosing it.
+    pub fn default_step(&self, path: &str) -> Option<&'static str> {
+        prefixes(path).find_map(|prefix| self.defaults.get(prefix).copied())
+    }
+
+    /// Every field normalization filled in, by path, with its step. Fields
+    /// inside a filled-in field are not listed separately.
+    pub fn defaults(&self) -> impl Iterator<Item = (&str, &'static str)> {
+        self.defaults
+            .iter()
+            .map(|(path, step)| (path.as_str(), *step))
+    }
+
+    /// Every field taken from organization defaults, by path.
+    pub fn inherited(&self) -> impl Iterator<Item = &str> {
+        self.inherited.iter().map(String::as_str)
+    }
+
+    /// Record that `path` was taken from organization defaults.
+    #[cfg(feature = "validate")]
+    pub(crate) fn inherit(&mut self, path: String) {
+        self.inherited.insert(path);
+    }
+
+    /// Record that the field at `path` was converted from its source form.
+    #[cfg(feature = "validate")]
+    pub(crate) fn convert(&mut self, path: &str, step: &'static str) {
+        self.conversions.insert(path.to_string(), step);
+    }
+}
+
+/// `path` and the paths of the fields enclosing it, outermost first.
+fn prefixes(path: &str) -> impl Iterator<Item = &str> {
+    path.char_indices()
+        .filter(|&(_, c)| c == '.' || c == '[')
+        .map(|(i, _)| &path[..i])
+        .chain(std::iter::once(path))
+}
+
+/// [`normalize`] a document and record which fields of the result were
+/// filled in rather than written in the source.
+pub fn normalize_with_provenance(doc: Document) -> (Document, Provenance) {
+    let mut provenance = Provenance::default();
+    let source = convert_forms(doc.clone(), &mut provenance);
+    let normalized = normalize(doc);
+    if let (Ok(normalized), Ok(source)) = (
+        serde_json::to_value(&normalized),
+        serde_json::to_value(&source),
+    ) {
+        record_defaults(&normalized, Some(&source), String::new(), &mut provenance);
+    }
+    (normalized, provenance)
+}
+
+/// The source document a normalized document came from: every field
+/// `provenance` records as filled in or inherited is removed and every form
+/// conversion is undone. Unlike [`minimize`], values written in the source
+/// are kept even when they equal their defaults.
+pub fn minimize_with_provenance(doc: Document, provenance: &Provenance) -> Document {
+    let Ok(mut value) = serde_json::to_value(&doc) else {
+        return doc;
+    };
+    for path in provenance.inherited() {
+        remove_at(&mut value, path);
+    }
+    for (path, step) in provenance.defaults() {
+        // Generated actor and phase names go with the form conversion.
+        if step != "N-006" && step != "N-007" {
+            remove_at(&mut value, path);
+        }
+    }
+    let Ok(mut doc) = serde_json::from_value::<Document>(value) else {
+        return doc;
+    };
+
+    let attack = &mut doc.attack;
+    if provenance.conversions.contains_key("attack.severity")
+        && let Some(Severity::Object {
+            level,
+            confidence: None,
+        }) = &attack.severity
+    {
+        attack.severity = Some(Severity::Scalar(level.clone()));
+    }
+    for (i, ind) in attack.indicators.iter_mut().flatten().enumerate() {
+        let path = format!("attack.indicators[{}].pattern", i);
+        if provenance.conversions.contains_key(&path)
+            && let Some(pattern) = &mut ind.pattern
+        {
+            collapse_pattern_shorthand(pattern);
+        }
+    }
+
+    let exec = &mut attack.execution;
+    let conversion = provenance.conversions.get("attack.execution").copied();
+    if conversion.is_some()
+        && let Some(mut actor) = exec.actors.take().and_then(|mut actors| actors.pop())
+    {
+        if !provenance
+            .conversions
+            .contains_key("attack.execution.actors[0].mode")
+        {
+            exec.mode = Some(actor.mode);
+        }
+        if conversion == Some("N-006") {
+            exec.state = actor.phases.pop().and_then(|phase| phase.state);
+        } else {
+            exec.phases = Some(actor.phases);
+        }
+    }
+    doc
+}
+
+/// Apply the form conversions of [`normalize`] (N-002 severity expansion
+/// without its default, N-005, N-006, N-007) and record them, so the result
+/// differs from the normalized document only in filled-in values.
+fn convert_forms(mut doc: Document, provenance: &mut Provenance) -> Document {
+    let exec = &doc.attack.execution;
+    let conversion = if exec.state.is_some() && exec.phases.is_none() && exec.actors.is_none() {
+        provenance.defaults.insert(
+            "attack.execution.actors[0].phases[0].name".to_string(),
+            "N-006",
+        );
+        Some("N-006")
+    } else if exec.phases.is_some() && exec.actors.is_none() {
+        if exec.mode.is_none() {
+            provenance
+                .conversions
+                .insert("attack.execution.actors[0].mode".to_string(), "N-007");
+        }
+        Some("N-007")
+    } else {
+        None
+    };
+    if let Some(step) = conversion {
+        provenance
+            .conversions
+            .insert("attack.execution".to_string(), step);
+        provenance
+            .defaults
+            .insert("attack.execution.actors[0].name".to_string(), step);
+        n006_single_phase_to_multi_actor(&mut doc);
+        n007_multi_phase_to_multi_actor(&mut doc);
+    }
+
+    if let Some(Severity::Scalar(level)) = &doc.attack.severity {
+        provenance
+            .conversions
+            .insert("attack.severity".to_string(), "N-002");
+        doc.attack.severity = Some(Severity::Object {
+            level: level.clone(),
+            confidence: None,
+        });
+    }
+    for (i, ind) in doc.attack.indicators.iter().flatten().enumerate() {
+        if ind.pattern.as_ref().is_some_and(PatternMatch::is_shorthand) {
+            provenance
+                .conversions
+                .insert(format!("attack.indicators[{}].pattern", i), "N-005");
+        }
+    }
+    n005_expand_pattern_shorthand(&mut doc);
+    doc
+}
+
+/// Record every field of `normalized` that `source` lacks or holds a
+/// different value for.
+fn record_defaults(
+    normalized: &serde_json::Value,
+    source: Option<&serde_json::Value>,
+    path: String,
+    provenance: &mut Provenance,
+) {
+    use serde_json::Value;
+    match (normalized, source) {
+        (Value::Object(fields), Some(Value::Object(source))) => {
+            for (key, value) in fields {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                record_defaults(value, source.get(key), path, provenance);
+            }
+        }
+        (Value::Array(items), Some(Value::Array(source))) => {
+            for (i, item) in items.iter().enumerate() {
+                let path = format!("{}[{}]", path, i);
+                record_defaults(item, source.get(i), path, provenance);
+            }
+        }
+        (value, Some(source)) if value == source => {}
+        _ => {
+            let step = default_step_for(&path);
+            provenance.defaults.entry(path).or_insert(step);
+        }
+    }
+}
+
+/// The normalization step that fills in the field at `path`.
+fn default_step_for(path: &str) -> &'static str {
+    if path.starts_with("attack.indicators[") && path.ends_with("].id") {
+        "N-003"
+    } else if path.ends_with("pattern.target") || path.ends_with("semantic.target") {
+        "N-004"
+    } else if path.contains(".state.tools[") {
+        "N-008"
+    } else {
+        "N-001"
+    }
+}
+
+enum Segment<'a> {
+    Key(&'a str),
+    Index(usize),
+}
+
+/// Split `attack.indicators[0].id` into keys and indices.
+fn segments(path: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    for part in path.split('.') {
+        let (key, mut rest) = part.split_at(part.find('[').unwrap_or(part.len()));
+        if !key.is_empty() {
+            segments.push(Segment::Key(key));
+        }
+        while let Some(end) = rest.find(']') {
+            match rest[1..end].parse() {
+                Ok(index) => segments.push(Segment::Index(index)),
+                Err(_) => segments.push(Segment::Key(&rest[1..end])),
+            }
+            rest = &rest[end + 1..];
+        }
+    }
+    segments
+}
+
+fn value_at<'v>(value: &'v serde_json::Value, path: &str) -> Option<&'v serde_json::Value> {
+    segments(path)
+        .into_iter()
+        .try_fold(value, |value, segment| match segment {
+            Segment::Key(key) => value.get(key),
+            Segment::Index(index) => value.get(index),
+        })
+}
+
+fn remove_at(value: &mut serde_json::Value, path: &str) {
+    let mut segments = segments(path);
+    let Some(Segment::Key(last)) = segments.pop() else {
+        return;
+    };
+    let parent = segments
+        .into_iter()
+        .try_fold(value, |value, segment| match segment {
+            Segment::Key(key) => value.get_mut(key),
+            Segment::Index(index) => value.get_mut(index),
+        });
+    if let Some(serde_json::Value::Object(fields)) = parent {
+        fields.remove(last);
+    }
+}
+
+// ─── Diff ────────────────────────────────────────────────────────────────────
+
+/// One operation of an RFC 6902 JSON Patch, produced by [`normalize_diff`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JsonPatchOp {
+    /// `add`, `remove`, or `replace`.
+    pub op: PatchOp,
+    /// JSON Pointer to the changed location (e.g. `/attack/indicators/0/id`).
+    pub path: String,
+    /// The new value; absent for `remove`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<Value>,
+}
+
+/// The changes [`normalize`] would make to `doc`, as a JSON Patch over its
+/// JSON form. `doc` is not modified.
+///
+/// Each operation changes one location that no other operation touches, so
+/// any subset of the patch can be applied on its own; applying all of it
+/// gives the normalized document. Lists whose length normalization keeps are
+/// diffed item by item, other changed lists are replaced whole. The form
+/// conversions (N-006, N-007) remove `state` or `phases` and add `actors`,
+/// so they are only meaningful applied together.
+pub fn normalize_diff(doc: &Document) -> Vec<JsonPatchOp> {
+    let mut ops = Vec::new();
+    if let (Ok(source), Ok(normalized)) = (
+        serde_json::to_value(doc),
+        serde_json::to_value(normalize(doc.clone())),
+    ) {
+        diff_values(&source, &normalized, &mut String::new(), &mut ops);
+    }
+    ops
+}
+
+fn diff_values(source: &Value, target: &Value, pointer: &mut String, ops: &mut Vec<JsonPatchOp>) {
+    match (source, target) {
+        (Value::Object(source), Value::Object(target)) => {
+            for key in source.keys().filter(|k| !target.contains_key(*k)) {
+                ops.push(JsonPatchOp {
+                    op: PatchOp::Remove,
+                    path: child_pointer(pointer, key),
+                    value: None,
+                });
+            }
+            for (key, value) in target {
+                match source.get(key) {
+                    Some(old) => {
+                        let len = pointer.len();
+                        *pointer = child_pointer(pointer, key);
+                        diff_values(old, value, pointer, ops);
+                        pointer.truncate(len);
+                    }
+                    None => ops.push(JsonPatchOp {
+                        op: PatchOp::Add,
+                        path: child_pointer(pointer, key),
+                        value: Some(value.clone()),
+                    }),
+                }
+            }
+        }
+        (Value::Array(source), Value::Array(target)) if source.len() == target.len() => {
+            for (i, (old, value)) in source.iter().zip(target).enumerate() {
+                let len = pointer.len();
+                *pointer = child_pointer(pointer, &i.to_string());
+                diff_values(old, value, pointer, ops);
+                pointer.truncate(len);
+            }
+        }
+        (source, target) if source == target => {}
+        (_, target) => ops.push(JsonPatchOp {
+            op: PatchOp::Replace,
+            path: pointer.clone(),
+            value: Some(target.clone()),
+        }),
+    }
+}
+
+/// `pointer` extended by `key`, escaped per RFC 6901.
+fn child_pointer(pointer: &str, key: &str) -> String {
+    format!("{}/{}", pointer, key.replace('~', "~0").replace('/', "~1"))
+}
+
 // ─── N-001: Default values ───────────────────────────────────────────────────
 
 fn n001_defaults(doc: &mut Document) {
+    let default_protocol = ResolvedContext::new(doc)
+        .default_protocol()
+        .map(str::to_string);
     let attack = &mut doc.attack;
 
     // name → "Untitled"
@@ -95,25 +698,6 @@ fn n001_defaults(doc: &mut Document) {
 
     // indicator.protocol → protocol component of resolved mode
     if let Some(indicators) = &mut attack.indicators {
-        let exec_protocol = attack
-            .execution
-            .mode
-            .as_deref()
-            .map(extract_protocol)
-            .map(|s| s.to_string());
-
-        // In multi-actor form after normalization, we may not have execution.mode
-        // If actors exist, check for a single default actor
-        let actor_protocol = attack.execution.actors.as_ref().and_then(|actors| {
-            if actors.len() == 1 {
-                Some(extract_protocol(&actors[0].mode).to_string())
-            } else {
-                None
-            }
-        });
-
-        let default_protocol = exec_protocol.or(actor_protocol);
-
         for ind in indicators.iter_mut() {
             if ind.protocol.is_none()
                 && let Some(ref proto) = default_protocol
@@ -128,6 +712,8 @@ fn n001_defaults(doc: &mut Document) {
         if attack.correlation.is_none() {
             attack.correlation = Some(Correlation {
                 logic: Some(CorrelationLogic::Any),
+                stages: None,
+                key: None,
             });
         } else if let Some(ref mut corr) = attack.correlation
             && corr.logic.is_none()
@@ -234,6 +820,7 @@ fn n005_expand_pattern_shorthand(doc: &mut Document) {
                     gte: pattern.gte.take(),
                     lte: pattern.lte.take(),
                     exists: None,
+                    json_patch: None,
                 };
                 pattern.condition = Some(Condition::Operators(cond));
             }
@@ -253,16 +840,22 @@ fn n006_single_phase_to_multi_actor(doc: &mut Document) {
             name: Some("phase-1".to_string()),
             description: None,
             mode: None,
+            requires: None,
             state,
             extractors: None,
             on_enter: None,
+            pacing: None,
             trigger: None,
+            on_timeout: None,
             extensions: std::collections::HashMap::new(),
         };
 
         let actor = Actor {
             name: "default".to_string(),
+            description: None,
             mode: mode.clone(),
+            trust: None,
+            endpoint: None,
             phases: vec![phase],
             extensions: std::collections::HashMap::new(),
         };
@@ -290,7 +883,10 @@ fn n007_multi_phase_to_multi_actor(doc: &mut Document) {
 
         let actor = Actor {
             name: "default".to_string(),
+            description: None,
             mode,
+            trust: None,
+            endpoint: None,
             phases,
             extensions: std::collections::HashMap::new(),
         };
@@ -306,7 +902,7 @@ fn n007_multi_phase_to_multi_actor(doc: &mut Document) {
 fn n008_mcp_tool_defaults(doc: &mut Document) {
     if let Some(actors) = &mut doc.attack.execution.actors {
         for actor in actors.iter_mut() {
-            if actor.mode != "mcp_server" {
+            if crate::surface::split_mode_version(&actor.mode).0 != "mcp_server" {
                 continue;
             }
 
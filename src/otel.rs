@@ -0,0 +1,216 @@
+//! Exporting verdicts as OpenTelemetry span events and log records.
+//!
+//! Verdicts are described with a fixed set of attribute names so dashboards
+//! and alerts built on them keep working across releases:
+//!
+//! | Attribute | Type | On |
+//! |-----------|------|----|
+//! | [`ATTACK_ID`] `oatf.attack.id` | string | attack and indicator |
+//! | [`INDICATOR_ID`] `oatf.indicator.id` | string | indicator |
+//! | [`RESULT`] `oatf.result` | string (`exploited`, `matched`, …) | attack and indicator |
+//! | [`SEVERITY`] `oatf.severity` | string (`high`, …) | attack and indicator |
+//! | [`CATEGORY`] `oatf.attack.category` | string | attack |
+//! | [`SURFACE`] `oatf.indicator.surface` | string | indicator |
+//! | [`EVIDENCE`] `oatf.evidence` | string | indicator |
+//! | [`SOURCE`] `oatf.source` | string | attack and indicator |
+//! | `oatf.indicators.{matched,not_matched,error,skipped}` | int | attack |
+//!
+//! Attributes whose value is unknown are omitted. Attack-level severity,
+//! category, and indicator surfaces need the [`Attack`] the verdict was
+//! computed for; an indicator's severity override wins over the attack's.
+//!
+//! Requires the `otel` feature.
+
+use crate::enums::{AttackResult, SeverityLevel};
+use crate::types::{Attack, AttackVerdict, IndicatorVerdict, Severity};
+use opentelemetry::logs::{AnyValue, LogRecord, Logger, Severity as LogSeverity};
+use opentelemetry::trace::Span;
+use opentelemetry::{KeyValue, Value};
+use serde::Serialize;
+
+/// Attack identifier.
+pub const ATTACK_ID: &str = "oatf.attack.id";
+/// Indicator identifier.
+pub const INDICATOR_ID: &str = "oatf.indicator.id";
+/// Attack or indicator result.
+pub const RESULT: &str = "oatf.result";
+/// Effective severity level.
+pub const SEVERITY: &str = "oatf.severity";
+/// Attack taxonomy category.
+pub const CATEGORY: &str = "oatf.attack.category";
+/// Indicator surface.
+pub const SURFACE: &str = "oatf.indicator.surface";
+/// Indicator evidence.
+pub const EVIDENCE: &str = "oatf.evidence";
+/// Verdict source.
+pub const SOURCE: &str = "oatf.source";
+
+/// Event name of attack verdicts.
+pub const ATTACK_VERDICT_EVENT: &str = "oatf.attack.verdict";
+/// Event name of indicator verdicts.
+pub const INDICATOR_VERDICT_EVENT: &str = "oatf.indicator.verdict";
+
+/// The serialized name of an enum value, e.g. `not_exploited`.
+fn name(value: &impl Serialize) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(s)) => s,
+        _ => String::new(),
+    }
+}
+
+fn attack_severity(attack: &Attack) -> Option<&SeverityLevel> {
+    attack.severity.as_ref().map(|s| match s {
+        Severity::Scalar(level) | Severity::Object { level, .. } => level,
+    })
+}
+
+/// Attributes describing an attack verdict.
+pub fn attack_attributes(verdict: &AttackVerdict, attack: Option<&Attack>) -> Vec<KeyValue> {
+    let mut attributes = Vec::new();
+    let attack_id = verdict
+        .attack_id
+        .as_deref()
+        .or_else(|| attack.and_then(|a| a.id.as_deref()));
+    if let Some(id) = attack_id {
+        attributes.push(KeyValue::new(ATTACK_ID, id.to_string()));
+    }
+    attributes.push(KeyValue::new(RESULT, name(&verdict.result)));
+    if let Some(severity) = attack.and_then(attack_severity) {
+        attributes.push(KeyValue::new(SEVERITY, name(severity)));
+    }
+    if let Some(category) = attack
+        .and_then(|a| a.classification.as_ref())
+        .and_then(|c| c.category.as_ref())
+    {
+        attributes.push(KeyValue::new(CATEGORY, name(category)));
+    }
+    let summary = &verdict.evaluation_summary;
+    attributes.extend([
+        KeyValue::new("oatf.indicators.matched", summary.matched),
+        KeyValue::new("oatf.indicators.not_matched", summary.not_matched),
+        KeyValue::new("oatf.indicators.error", summary.error),
+        KeyValue::new("oatf.indicators.skipped", summary.skipped),
+    ]);
+    if let Some(source) = &verdict.source {
+        attributes.push(KeyValue::new(SOURCE, source.clone()));
+    }
+    attributes
+}
+
+/// Attributes describing an indicator verdict of `attack`.
+pub fn indicator_attributes(verdict: &IndicatorVerdict, attack: Option<&Attack>) -> Vec<KeyValue> {
+    let mut attributes = Vec::new();
+    if let Some(id) = attack.and_then(|a| a.id.as_deref()) {
+        attributes.push(KeyValue::new(ATTACK_ID, id.to_string()));
+    }
+    attributes.push(KeyValue::new(INDICATOR_ID, verdict.indicator_id.clone()));
+    attributes.push(KeyValue::new(RESULT, name(&verdict.result)));
+
+    let indicator = attack.and_then(|a| {
+        a.indicators
+            .iter()
+            .flatten()
+            .find(|i| i.id.as_deref() == Some(verdict.indicator_id.as_str()))
+    });
+    let severity = indicator
+        .and_then(|i| i.severity.as_ref())
+        .or_else(|| attack.and_then(attack_severity));
+    if let Some(severity) = severity {
+        attributes.push(KeyValue::new(SEVERITY, name(severity)));
+    }
+    if let Some(indicator) = indicator {
+        attributes.push(KeyValue::new(SURFACE, indicator.surface.clone()));
+    }
+    if let Some(evidence) = &verdict.evidence {
+        attributes.push(KeyValue::new(EVIDENCE, evidence.clone()));
+    }
+    if let Some(source) = &verdict.source {
+        attributes.push(KeyValue::new(SOURCE, source.clone()));
+    }
+    attributes
+}
+
+/// Record `verdict` on `span`: one [`ATTACK_VERDICT_EVENT`] followed by an
+/// [`INDICATOR_VERDICT_EVENT`] per indicator verdict.
+pub fn add_span_events(span: &mut impl Span, verdict: &AttackVerdict, attack: Option<&Attack>) {
+    span.add_event(ATTACK_VERDICT_EVENT, attack_attributes(verdict, attack));
+    for indicator in &verdict.indicator_verdicts {
+        span.add_event(
+            INDICATOR_VERDICT_EVENT,
+            indicator_attributes(indicator, attack),
+        );
+    }
+}
+
+/// Emit `verdict` through `logger`: one [`ATTACK_VERDICT_EVENT`] record
+/// followed by an [`INDICATOR_VERDICT_EVENT`] record per indicator verdict.
+///
+/// Exploited verdicts are logged at a level derived from the attack's
+/// severity (`critical` → FATAL, `high` → ERROR, `medium`/`low` or unknown →
+/// WARN, `informational` → INFO); partial verdicts at INFO, not-exploited at
+/// DEBUG, and errors at ERROR. Indicator records are logged at DEBUG, or
+/// INFO when matched.
+pub fn emit_log_records(logger: &impl Logger, verdict: &AttackVerdict, attack: Option<&Attack>) {
+    let level = match verdict.result {
+        AttackResult::Exploited => match attack.and_then(attack_severity) {
+            Some(SeverityLevel::Critical) => LogSeverity::Fatal,
+            Some(SeverityLevel::High) => LogSeverity::Error,
+            Some(SeverityLevel::Informational) => LogSeverity::Info,
+            _ => LogSeverity::Warn,
+        },
+        AttackResult::Partial => LogSeverity::Info,
+        AttackResult::NotExploited => LogSeverity::Debug,
+        AttackResult::Error => LogSeverity::Error,
+    };
+    let attributes = attack_attributes(verdict, attack);
+    let body = format!(
+        "attack {} {}",
+        verdict.attack_id.as_deref().unwrap_or("(unidentified)"),
+        name(&verdict.result)
+    );
+    emit(logger, ATTACK_VERDICT_EVENT, level, body, attributes);
+
+    for indicator in &verdict.indicator_verdicts {
+        let level = match indicator.result {
+            crate::enums::IndicatorResult::Matched => LogSeverity::Info,
+            _ => LogSeverity::Debug,
+        };
+        let body = format!(
+            "indicator {} {}",
+            indicator.indicator_id,
+            name(&indicator.result)
+        );
+        let attributes = indicator_attributes(indicator, attack);
+        emit(logger, INDICATOR_VERDICT_EVENT, level, body, attributes);
+    }
+}
+
+fn emit(
+    logger: &impl Logger,
+    event: &'static str,
+    level: LogSeverity,
+    body: String,
+    attributes: Vec<KeyValue>,
+) {
+    let mut record = logger.create_log_record();
+    record.set_event_name(event);
+    record.set_severity_number(level);
+    record.set_severity_text(level.name());
+    record.set_body(body.into());
+    record.add_attributes(
+        attributes
+            .into_iter()
+            .map(|kv| (kv.key, any_value(kv.value))),
+    );
+    logger.emit(record);
+}
+
+/// Attribute values produced by this module are scalars.
+fn any_value(value: Value) -> AnyValue {
+    match value {
+        Value::Bool(b) => AnyValue::Boolean(b),
+        Value::I64(i) => AnyValue::Int(i),
+        Value::F64(f) => AnyValue::Double(f),
+        other => AnyValue::String(other.as_str().into_owned().into()),
+    }
+}
@@ -0,0 +1,52 @@
+//! Bounded, order-preserving parallel map for corpus-level operations.
+//!
+//! Loading or validating many documents is independent per document, so
+//! [`map_ordered`] spreads the items over at most
+//! `std::thread::available_parallelism` scoped worker threads. Workers take
+//! the next unclaimed item from a shared counter, so one slow document does
+//! not hold up a whole chunk, and results are returned in input order
+//! regardless of which worker finished first. The validation rule registry
+//! and other registries are immutable statics, shared by every worker.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Apply `f` to every item on a bounded pool of scoped threads, returning
+/// the results in the order of `items`. Runs on the calling thread when
+/// there is one item or one available core.
+pub(crate) fn map_ordered<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let threads = std::thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(items.len());
+    if threads < 2 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<(usize, R)> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(i) else {
+                            break done;
+                        };
+                        done.push((i, f(item)));
+                    }
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().expect("parallel worker panicked"))
+            .collect()
+    });
+    results.sort_unstable_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, r)| r).collect()
+}
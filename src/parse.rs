@@ -1,16 +1,27 @@
-//! YAML → [`Document`] deserialization.
+//! YAML → [`Document`] and [`IndicatorSet`] deserialization.
 //!
 //! Two-step process: YAML → `serde_json::Value` → `Document`. Pre-parse rejects
 //! YAML anchors/aliases/merge keys, multi-document streams, and unknown top-level keys.
+//!
+//! [`Indicator::from_yaml`] and [`Phase::from_yaml`] parse a single indicator
+//! or phase with the same checks, for tools that edit fragments.
 
 use crate::error::{ParseError, ParseErrorKind};
-use crate::types::Document;
+use crate::types::{Document, Indicator, IndicatorSet, Phase};
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
 
 /// Parse a YAML string into an unvalidated Document.
 ///
 /// Performs YAML deserialization and type mapping only.
 /// Does NOT validate document conformance or apply normalization.
 pub fn parse(input: &str) -> Result<Document, ParseError> {
+    document_from_value(parse_yaml_value(input)?)
+}
+
+/// Pre-scan `input` and deserialize it to a YAML value: the steps shared by
+/// [`parse`] and [`parse_indicator_set`].
+pub(crate) fn parse_yaml_value(input: &str) -> Result<serde_json::Value, ParseError> {
     if input.trim().is_empty() {
         return Err(ParseError {
             kind: ParseErrorKind::Syntax,
@@ -29,7 +40,7 @@ pub fn parse(input: &str) -> Result<Document, ParseError> {
     check_multi_document(input)?;
 
     // Deserialize using serde-saphyr via serde_json Value as intermediate
-    // First parse YAML to serde_json::Value, then convert to Document
+    // First parse YAML to serde_json::Value, then convert to the target type
     let value: serde_json::Value = serde_saphyr::from_str(input).map_err(|e| {
         let msg = e.to_string();
         // Try to extract location info from the error message
@@ -42,6 +53,12 @@ pub fn parse(input: &str) -> Result<Document, ParseError> {
         }
     })?;
 
+    Ok(value)
+}
+
+/// Convert an already-deserialized YAML value into a Document, applying the
+/// root-shape, top-level key, and extension key checks of [`parse`].
+pub(crate) fn document_from_value(value: serde_json::Value) -> Result<Document, ParseError> {
     // Ensure root is a mapping/object
     if !value.is_object() {
         return Err(ParseError {
@@ -94,6 +111,135 @@ pub fn parse(input: &str) -> Result<Document, ParseError> {
     Ok(doc)
 }
 
+/// Parse a document read from `reader`.
+///
+/// The input is read into a single buffer and then parsed exactly as by
+/// [`parse`]; the anchor and multi-document checks need the full text, so
+/// the source is held once rather than streamed.
+///
+/// # Errors
+///
+/// Returns a `ParseError` of kind `io` if reading fails or the input is not
+/// valid UTF-8, and otherwise the errors of [`parse`].
+pub fn parse_from_reader(mut reader: impl std::io::Read) -> Result<Document, ParseError> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input).map_err(|e| ParseError {
+        kind: ParseErrorKind::Io,
+        message: format!("failed to read input: {}", e),
+        path: None,
+        line: None,
+        column: None,
+    })?;
+    parse(&input)
+}
+
+/// Parse a YAML string into an unvalidated [`IndicatorSet`]: a document with
+/// top-level `indicators` instead of `attack`.
+///
+/// Applies the same pre-parse checks as [`parse`]. Does NOT validate or
+/// normalize; see [`crate::validate::validate_indicator_set`].
+pub fn parse_indicator_set(input: &str) -> Result<IndicatorSet, ParseError> {
+    let value = parse_yaml_value(input)?;
+    let Some(obj) = value.as_object() else {
+        return Err(ParseError {
+            kind: ParseErrorKind::TypeMismatch,
+            message: "document root must be a YAML mapping".to_string(),
+            path: None,
+            line: None,
+            column: None,
+        });
+    };
+    for key in obj.keys() {
+        if !matches!(key.as_str(), "oatf" | "$schema" | "indicators") {
+            return Err(ParseError {
+                kind: ParseErrorKind::TypeMismatch,
+                message: format!("unknown top-level field: {}", key),
+                path: Some(key.to_string()),
+                line: None,
+                column: None,
+            });
+        }
+    }
+    let oatf_is_first_key = obj.keys().next().is_some_and(|k| k == "oatf");
+
+    let mut set: IndicatorSet = serde_json::from_value(value).map_err(|e| {
+        let msg = e.to_string();
+        ParseError {
+            kind: classify_json_error(&msg),
+            message: msg,
+            path: None,
+            line: None,
+            column: None,
+        }
+    })?;
+    set.oatf_is_first_key = oatf_is_first_key;
+
+    for (i, ind) in set.indicators.iter().enumerate() {
+        check_extensions(&ind.extensions, &format!("indicators[{}]", i))?;
+    }
+
+    Ok(set)
+}
+
+impl Indicator {
+    /// Parse a YAML mapping into an unvalidated indicator, as it would appear
+    /// in `attack.indicators`.
+    ///
+    /// Applies the pre-parse and extension key checks of [`parse`].
+    ///
+    /// ```rust
+    /// use oatf::types::Indicator;
+    ///
+    /// let indicator = Indicator::from_yaml("surface: tool_description\npattern:\n  contains: x\n")?;
+    /// assert_eq!(indicator.surface, "tool_description");
+    /// # Ok::<(), oatf::ParseError>(())
+    /// ```
+    pub fn from_yaml(input: &str) -> Result<Indicator, ParseError> {
+        let indicator: Indicator = fragment_from_yaml(input, "indicator")?;
+        check_extensions(&indicator.extensions, "indicator")?;
+        Ok(indicator)
+    }
+}
+
+impl Phase {
+    /// Parse a YAML mapping into an unvalidated phase, as it would appear in
+    /// `attack.execution.phases`.
+    ///
+    /// Applies the pre-parse and extension key checks of [`parse`].
+    pub fn from_yaml(input: &str) -> Result<Phase, ParseError> {
+        let phase: Phase = fragment_from_yaml(input, "phase")?;
+        check_extensions(&phase.extensions, "phase")?;
+        Ok(phase)
+    }
+}
+
+/// Pre-scan and deserialize a fragment whose root must be a mapping.
+pub(crate) fn fragment_from_yaml<T: DeserializeOwned>(
+    input: &str,
+    what: &str,
+) -> Result<T, ParseError> {
+    let value = parse_yaml_value(input)?;
+    if !value.is_object() {
+        return Err(ParseError {
+            kind: ParseErrorKind::TypeMismatch,
+            message: format!("{} must be a YAML mapping", what),
+            path: None,
+            line: None,
+            column: None,
+        });
+    }
+    serde_json::from_value(value).map_err(|e| {
+        let msg = e.to_string();
+        ParseError {
+            kind: classify_json_error(&msg),
+            message: msg,
+            path: None,
+            line: None,
+            column: None,
+        }
+    })
+}
+
 /// Validate that all extension (flatten) fields start with "x-".
 fn validate_extension_keys(doc: &Document) -> Result<(), ParseError> {
     check_extensions(&doc.attack.extensions, "attack")?;
@@ -133,7 +279,7 @@ fn validate_extension_keys(doc: &Document) -> Result<(), ParseError> {
 }
 
 fn check_extensions(
-    extensions: &std::collections::HashMap<String, serde_json::Value>,
+    extensions: &HashMap<String, serde_json::Value>,
     path: &str,
 ) -> Result<(), ParseError> {
     for key in extensions.keys() {
@@ -0,0 +1,45 @@
+//! The stable core of the API, for glob import.
+//!
+//! ```rust
+//! use oatf::prelude::*;
+//!
+//! let doc: Document = parse("oatf: \"0.1\"\nattack:\n  execution:\n    mode: mcp_server\n").unwrap();
+//! assert_eq!(doc.oatf, "0.1");
+//! ```
+//!
+//! Everything here follows semantic versioning strictly: pipeline entry
+//! points, the document and verdict types, their errors, and the extension
+//! traits integrations implement ([`CelEvaluator`], [`SemanticEvaluator`],
+//! [`Transport`], [`VerdictSink`]). Items outside the prelude may still
+//! change in minor releases while the format is pre-1.0; registry snapshots
+//! and runtime state such as [`TriggerState`] are `#[non_exhaustive]`, and
+//! [`Clock`] is sealed, so they can grow without a breaking change.
+
+pub use crate::clock::Clock;
+pub use crate::enums::{AttackResult, IndicatorResult, Profile};
+pub use crate::error::{
+    Diagnostic, DiagnosticSeverity, OATFError, ParseError, SerializeError, ValidationError,
+    ValidationResult,
+};
+pub use crate::parse::{parse, parse_indicator_set};
+pub use crate::serialize::{serialize, serialize_indicator_set};
+pub use crate::sink::VerdictSink;
+pub use crate::spec::spec_info;
+pub use crate::types::{
+    Actor, Attack, AttackVerdict, Document, Indicator, IndicatorSet, IndicatorVerdict, Phase,
+    Severity, Trigger, TriggerState,
+};
+
+#[cfg(feature = "normalize")]
+pub use crate::normalize::normalize;
+#[cfg(feature = "validate")]
+pub use crate::validate::validate;
+#[cfg(all(feature = "validate", feature = "normalize"))]
+pub use crate::{LoadResult, load, load_path};
+
+#[cfg(feature = "evaluate")]
+pub use crate::engine::Transport;
+#[cfg(feature = "evaluate")]
+pub use crate::evaluate::{
+    CelEvaluator, GenerationProvider, SemanticEvaluator, compute_verdict, evaluate_indicator,
+};
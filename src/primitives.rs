@@ -2,11 +2,17 @@
 //!
 //! Shared utility operations used by both entry points and evaluation.
 
+#[cfg(feature = "evaluate")]
+use crate::clock::Clock;
+#[cfg(feature = "evaluate")]
 use crate::enums::AdvanceReason;
+use crate::enums::RegexDialect;
 use crate::error::{Diagnostic, DiagnosticSeverity, ParseError, ParseErrorKind};
 use crate::types::*;
+#[cfg(feature = "evaluate")]
 use regex::Regex;
 use serde_json::Value;
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::time::Duration;
 
@@ -20,23 +26,24 @@ pub use crate::event_registry::resolve_event_qualifier;
 /// Resolves a simple dot-path against a value tree.
 ///
 /// Returns the single value at the path, or `None` if any segment fails to
-/// resolve. Empty path returns the root value.
+/// resolve. Empty path returns the root value. See
+/// [`resolve_simple_path_ref`] to borrow the value instead.
 pub fn resolve_simple_path(path: &str, value: &Value) -> Option<Value> {
+    resolve_simple_path_ref(path, value).cloned()
+}
+
+/// [`resolve_simple_path`] without copying: the resolved value is borrowed
+/// from `value`.
+pub fn resolve_simple_path_ref<'a>(path: &str, value: &'a Value) -> Option<&'a Value> {
     if path.is_empty() {
-        return Some(value.clone());
+        return Some(value);
     }
 
     let mut current = value;
     for segment in path.split('.') {
-        match current.as_object() {
-            Some(obj) => match obj.get(segment) {
-                Some(v) => current = v,
-                None => return None,
-            },
-            None => return None,
-        }
+        current = current.as_object()?.get(segment)?;
     }
-    Some(current.clone())
+    Some(current)
 }
 
 // ─── §5.1.2 resolve_wildcard_path ───────────────────────────────────────────
@@ -46,50 +53,179 @@ pub fn resolve_simple_path(path: &str, value: &Value) -> Option<Value> {
 /// Returns all values that match, potentially expanding across array elements
 /// via `[*]` wildcards. Returns an empty vec if the path does not match.
 /// Empty path returns the root value as a single-element list.
+///
+/// A segment may be followed by `!json` to parse a string value as embedded
+/// JSON and continue resolving inside it, or `!url` to percent-decode it
+/// (e.g. `arguments.payload!json.url`, `query!url!json.cmd`). Values that
+/// are not strings or fail to decode do not match.
+///
+/// See [`resolve_wildcard_path_ref`] to borrow the values instead.
 pub fn resolve_wildcard_path(path: &str, value: &Value) -> Vec<Value> {
+    resolve_wildcard_path_ref(path, value).into_owned()
+}
+
+/// [`resolve_wildcard_path`] without copying: values are borrowed from
+/// `value`, except those a decoder produced.
+pub fn resolve_wildcard_path_ref<'a>(path: &str, value: &'a Value) -> ResolvedValues<'a> {
     if path.is_empty() {
-        return vec![value.clone()];
+        return ResolvedValues {
+            values: vec![Cow::Borrowed(value)],
+        };
     }
 
-    let segments = match split_wildcard_segments(path) {
-        Some(s) => s,
-        None => return vec![],
+    let Some(segments) = split_wildcard_segments(path) else {
+        return ResolvedValues::default();
     };
 
-    let mut current = vec![value.clone()];
+    let mut current = vec![Cow::Borrowed(value)];
 
     for seg in &segments {
         if current.is_empty() {
             break;
         }
         let mut next = Vec::new();
-        for val in &current {
-            if seg.wildcard {
-                // First access the field name, then fan out
-                let target = if seg.name.is_empty() {
-                    val.clone()
-                } else {
-                    match val.as_object().and_then(|o| o.get(&seg.name)) {
-                        Some(v) => v.clone(),
-                        None => continue,
+        for val in current {
+            match val {
+                Cow::Borrowed(val) => {
+                    // First access the field name, then fan out
+                    let target = if seg.wildcard && seg.name.is_empty() {
+                        Some(val)
+                    } else {
+                        val.as_object().and_then(|o| o.get(&seg.name))
+                    };
+                    match target {
+                        Some(Value::Array(items)) if seg.wildcard => {
+                            next.extend(items.iter().map(Cow::Borrowed));
+                        }
+                        Some(target) if !seg.wildcard => next.push(Cow::Borrowed(target)),
+                        _ => {}
+                    }
+                }
+                // Decoded values are owned; take their parts apart instead
+                // of copying them.
+                Cow::Owned(val) => {
+                    let target = match val {
+                        val if seg.wildcard && seg.name.is_empty() => Some(val),
+                        Value::Object(mut obj) => obj.remove(&seg.name),
+                        _ => None,
+                    };
+                    match target {
+                        Some(Value::Array(items)) if seg.wildcard => {
+                            next.extend(items.into_iter().map(Cow::Owned));
+                        }
+                        Some(target) if !seg.wildcard => next.push(Cow::Owned(target)),
+                        _ => {}
                     }
-                };
-                if let Some(arr) = target.as_array() {
-                    next.extend(arr.iter().cloned());
                 }
-            } else if let Some(v) = val.as_object().and_then(|o| o.get(&seg.name)) {
-                next.push(v.clone());
             }
         }
+        for decoder in &seg.decoders {
+            next = next
+                .iter()
+                .filter_map(|v| decoder.decode(v))
+                .map(Cow::Owned)
+                .collect();
+        }
         current = next;
     }
 
-    current
+    ResolvedValues { values: current }
+}
+
+/// The values a wildcard path resolved to, from
+/// [`resolve_wildcard_path_ref`]. Values are borrowed from the resolved tree
+/// unless a `!json` or `!url` decoder produced them.
+#[must_use]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ResolvedValues<'a> {
+    values: Vec<Cow<'a, Value>>,
+}
+
+impl<'a> ResolvedValues<'a> {
+    /// Number of values.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Whether the path resolved to nothing.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// The value at `index`.
+    pub fn get(&self, index: usize) -> Option<&Value> {
+        self.values.get(index).map(|v| &**v)
+    }
+
+    /// The values, in resolution order.
+    pub fn iter(&self) -> impl Iterator<Item = &Value> {
+        self.values.iter().map(|v| &**v)
+    }
+
+    /// The values, borrowed or owned as resolved.
+    pub fn into_values(self) -> Vec<Cow<'a, Value>> {
+        self.values
+    }
+
+    /// Owned copies of the values.
+    pub fn into_owned(self) -> Vec<Value> {
+        self.values.into_iter().map(Cow::into_owned).collect()
+    }
+}
+
+/// Decoders that may follow a target path segment as `!<name>`.
+#[cfg(feature = "validate")]
+pub(crate) const TARGET_DECODERS: &[&str] = &["json", "url"];
+
+enum Decoder {
+    Json,
+    Url,
+}
+
+impl Decoder {
+    fn parse(name: &str) -> Option<Decoder> {
+        match name {
+            "json" => Some(Decoder::Json),
+            "url" => Some(Decoder::Url),
+            _ => None,
+        }
+    }
+
+    fn decode(&self, value: &Value) -> Option<Value> {
+        let text = value.as_str()?;
+        match self {
+            Decoder::Json => serde_json::from_str(text).ok(),
+            Decoder::Url => percent_decode(text).map(Value::String),
+        }
+    }
+}
+
+/// Decode `%XX` escapes. Returns `None` for malformed escapes or when the
+/// decoded bytes are not UTF-8.
+fn percent_decode(text: &str) -> Option<String> {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = bytes.get(i + 1..i + 3)?;
+            if !hex.iter().all(u8::is_ascii_hexdigit) {
+                return None;
+            }
+            out.push(u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()?);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).ok()
 }
 
 struct WildcardSegment {
     name: String,
     wildcard: bool,
+    decoders: Vec<Decoder>,
 }
 
 fn split_wildcard_segments(path: &str) -> Option<Vec<WildcardSegment>> {
@@ -108,6 +244,7 @@ fn split_wildcard_segments(path: &str) -> Option<Vec<WildcardSegment>> {
                     segments.push(WildcardSegment {
                         name: current.clone(),
                         wildcard: false,
+                        decoders: Vec::new(),
                     });
                     current.clear();
                 }
@@ -119,14 +256,15 @@ fn split_wildcard_segments(path: &str) -> Option<Vec<WildcardSegment>> {
                     segments.push(WildcardSegment {
                         name: current.clone(),
                         wildcard: true,
+                        decoders: Vec::new(),
                     });
                     current.clear();
                     i += 3;
-                    // After [*], must be . or end
+                    // After [*], must be ., a decoder, or end
                     if i < chars.len() {
                         if chars[i] == '.' {
                             i += 1;
-                        } else {
+                        } else if chars[i] != '!' {
                             return None;
                         }
                     }
@@ -134,6 +272,34 @@ fn split_wildcard_segments(path: &str) -> Option<Vec<WildcardSegment>> {
                     return None;
                 }
             }
+            '!' => {
+                // Decoders follow a named segment or a [*]
+                if !current.is_empty() {
+                    segments.push(WildcardSegment {
+                        name: current.clone(),
+                        wildcard: false,
+                        decoders: Vec::new(),
+                    });
+                    current.clear();
+                } else if i == 0 || chars[i - 1] == '.' {
+                    return None;
+                }
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|c| matches!(c, '.' | '!' | '['))
+                    .map_or(chars.len(), |p| i + 1 + p);
+                let name: String = chars[i + 1..end].iter().collect();
+                let decoder = Decoder::parse(&name)?;
+                segments.last_mut()?.decoders.push(decoder);
+                i = end;
+                if i < chars.len() {
+                    match chars[i] {
+                        '.' => i += 1,
+                        '!' => {}
+                        _ => return None,
+                    }
+                }
+            }
             c => {
                 current.push(c);
                 i += 1;
@@ -145,6 +311,7 @@ fn split_wildcard_segments(path: &str) -> Option<Vec<WildcardSegment>> {
         segments.push(WildcardSegment {
             name: current,
             wildcard: false,
+            decoders: Vec::new(),
         });
     }
 
@@ -312,6 +479,7 @@ fn duration_error(message: &str) -> ParseError {
 /// If `condition` is a bare value, performs deep equality comparison.
 /// If `condition` is a `MatchCondition` object, evaluates each present operator
 /// — all must match (AND logic).
+#[cfg(feature = "evaluate")]
 pub fn evaluate_condition(condition: &Condition, value: &Value) -> bool {
     match condition {
         Condition::Equality(expected) => values_deep_equal(value, expected),
@@ -320,6 +488,7 @@ pub fn evaluate_condition(condition: &Condition, value: &Value) -> bool {
 }
 
 /// Evaluate a MatchCondition (set of operators) against a value with AND logic.
+#[cfg(feature = "evaluate")]
 pub fn evaluate_match_condition(cond: &MatchCondition, value: &Value) -> bool {
     // Each present operator must pass (AND logic)
     if let Some(ref s) = cond.contains {
@@ -404,14 +573,63 @@ pub fn evaluate_match_condition(cond: &MatchCondition, value: &Value) -> bool {
         }
     }
 
+    if let Some(ref patch) = cond.json_patch {
+        let matched = match value {
+            Value::Array(ops) => ops.iter().any(|op| patch_op_matches(patch, op)),
+            op => patch_op_matches(patch, op),
+        };
+        if !matched {
+            return false;
+        }
+    }
+
     // exists is handled by evaluate_predicate, not here
     true
 }
 
+/// Whether the RFC 6902 operation `op` has the `patch` op, a path matching
+/// its pattern, and a value satisfying its value condition.
+#[cfg(feature = "evaluate")]
+fn patch_op_matches(patch: &PatchMatch, op: &Value) -> bool {
+    if let Some(expected) = patch.op
+        && op.get("op").and_then(Value::as_str) != Some(expected.as_str())
+    {
+        return false;
+    }
+    if let Some(ref pattern) = patch.path {
+        match op.get("path").and_then(Value::as_str) {
+            Some(path) if pointer_matches(pattern, path) => {}
+            _ => return false,
+        }
+    }
+    match patch.value {
+        Some(ref entry) => entry_matches(entry, resolve_simple_path_ref("value", op)),
+        None => true,
+    }
+}
+
+/// Whether JSON Pointer `pointer` matches `pattern`, in which a `*` segment
+/// matches any one segment and a final `**` matches any remainder, including
+/// none.
+#[cfg(feature = "evaluate")]
+fn pointer_matches(pattern: &str, pointer: &str) -> bool {
+    let mut pattern = pattern.split('/');
+    let mut pointer = pointer.split('/');
+    loop {
+        match (pattern.next(), pointer.next()) {
+            (Some("**"), _) if pattern.clone().next().is_none() => return true,
+            (Some(p), Some(s)) if p == "*" || p == s => {}
+            (None, None) => return true,
+            _ => return false,
+        }
+    }
+}
+
 /// Deep equality comparison per SDK spec §5.3.
 ///
 /// Integer 42 equals float 42.0; object key order is irrelevant;
 /// arrays compare element-wise by position and length.
+#[cfg(feature = "evaluate")]
 fn values_deep_equal(a: &Value, b: &Value) -> bool {
     match (a, b) {
         (Value::Null, Value::Null) => true,
@@ -444,92 +662,57 @@ fn values_deep_equal(a: &Value, b: &Value) -> bool {
 /// - Handle `exists` operator at the path-resolution level
 /// - Evaluate remaining condition operators against resolved value
 ///
+/// Resolved values are borrowed from `value`, never copied.
+///
 /// Empty predicate → true.
+#[cfg(feature = "evaluate")]
 pub fn evaluate_predicate(predicate: &MatchPredicate, value: &Value) -> bool {
-    for (path, entry) in predicate {
-        let resolved = resolve_simple_path(path, value);
+    predicate
+        .iter()
+        .all(|(path, entry)| entry_matches(entry, resolve_simple_path_ref(path, value)))
+}
 
-        match entry {
-            MatchEntry::Scalar(expected) => match &resolved {
-                Some(val) => {
-                    if !values_deep_equal(val, expected) {
-                        return false;
-                    }
-                }
-                None => return false,
-            },
-            MatchEntry::Condition(cond) => {
-                match cond {
-                    MatchCondition {
-                        exists: Some(false),
-                        ..
-                    } => {
-                        // exists: false — path should NOT resolve
-                        if resolved.is_some() {
-                            return false;
-                        }
-                        // §5.4: exists: false with no other operators → true;
-                        // exists: false with other operators → false
-                        let has_other_ops = cond.contains.is_some()
-                            || cond.starts_with.is_some()
-                            || cond.ends_with.is_some()
-                            || cond.regex.is_some()
-                            || cond.any_of.is_some()
-                            || cond.gt.is_some()
-                            || cond.lt.is_some()
-                            || cond.gte.is_some()
-                            || cond.lte.is_some();
-                        if has_other_ops {
-                            return false;
-                        }
-                    }
-                    MatchCondition {
-                        exists: Some(true), ..
-                    } => {
-                        // exists: true — path MUST resolve
-                        if resolved.is_none() {
-                            return false;
-                        }
-                        // Evaluate remaining operators
-                        let val = resolved.as_ref().unwrap();
-                        if !evaluate_match_condition_excluding_exists(cond, val) {
-                            return false;
-                        }
-                    }
-                    _ => {
-                        // No exists operator
-                        match &resolved {
-                            Some(val) => {
-                                if !evaluate_match_condition(cond, val) {
-                                    return false;
-                                }
-                            }
-                            None => return false,
-                        }
-                    }
-                }
-            }
+/// Whether one predicate entry holds for the value its path resolved to.
+#[cfg(feature = "evaluate")]
+fn entry_matches(entry: &MatchEntry, resolved: Option<&Value>) -> bool {
+    let cond = match entry {
+        MatchEntry::Scalar(expected) => {
+            return resolved.is_some_and(|val| values_deep_equal(val, expected));
         }
+        MatchEntry::Condition(cond) => cond,
+    };
+    match cond.exists {
+        // exists: false — path should NOT resolve. §5.4: exists: false with
+        // no other operators → true; with other operators → false
+        Some(false) => {
+            resolved.is_none()
+                && cond.contains.is_none()
+                && cond.starts_with.is_none()
+                && cond.ends_with.is_none()
+                && cond.regex.is_none()
+                && cond.any_of.is_none()
+                && cond.gt.is_none()
+                && cond.lt.is_none()
+                && cond.gte.is_none()
+                && cond.lte.is_none()
+                && cond.json_patch.is_none()
+        }
+        // exists: true, or no exists operator — path MUST resolve and the
+        // remaining operators hold (evaluate_match_condition ignores exists)
+        _ => resolved.is_some_and(|val| evaluate_match_condition(cond, val)),
     }
-    true
 }
 
-/// Evaluate all operators in a MatchCondition except `exists`.
-fn evaluate_match_condition_excluding_exists(cond: &MatchCondition, value: &Value) -> bool {
-    // Build a temporary MatchCondition without exists
-    let without_exists = MatchCondition {
-        contains: cond.contains.clone(),
-        starts_with: cond.starts_with.clone(),
-        ends_with: cond.ends_with.clone(),
-        regex: cond.regex.clone(),
-        any_of: cond.any_of.clone(),
-        gt: cond.gt,
-        lt: cond.lt,
-        gte: cond.gte,
-        lte: cond.lte,
-        exists: None,
-    };
-    evaluate_match_condition(&without_exists, value)
+/// Evaluates an `origin` predicate against the event's [`Origin`], with the
+/// same semantics as [`evaluate_predicate`] over the object
+/// `{role, endpoint, trusted}`. An event of unknown origin satisfies only
+/// predicates whose every entry is `exists: false`.
+#[cfg(feature = "evaluate")]
+pub fn evaluate_origin(predicate: &MatchPredicate, origin: Option<&Origin>) -> bool {
+    let value = origin
+        .and_then(|o| serde_json::to_value(o).ok())
+        .unwrap_or(Value::Null);
+    evaluate_predicate(predicate, &value)
 }
 
 // ─── §5.5 interpolate_template ──────────────────────────────────────────────
@@ -569,8 +752,8 @@ pub fn interpolate_template(
             // b. If starts with "request." and request is Some
             else if let Some(rest) = expr.strip_prefix("request.") {
                 if let Some(req) = request {
-                    match resolve_simple_path(rest, req) {
-                        Some(v) => result.push_str(&value_to_string(&v)),
+                    match resolve_simple_path_ref(rest, req) {
+                        Some(v) => result.push_str(&value_to_string(v)),
                         None => {
                             diagnostics.push(w004_diagnostic(expr));
                         }
@@ -582,8 +765,8 @@ pub fn interpolate_template(
             // c. If starts with "response." and response is Some
             else if let Some(rest) = expr.strip_prefix("response.") {
                 if let Some(resp) = response {
-                    match resolve_simple_path(rest, resp) {
-                        Some(v) => result.push_str(&value_to_string(&v)),
+                    match resolve_simple_path_ref(rest, resp) {
+                        Some(v) => result.push_str(&value_to_string(v)),
                         None => {
                             diagnostics.push(w004_diagnostic(expr));
                         }
@@ -629,6 +812,7 @@ fn w004_diagnostic(expr: &str) -> Diagnostic {
         code: "W-004".to_string(),
         path: None,
         message: format!("unresolvable template reference: '{}'", expr),
+        suggestion: None,
     }
 }
 
@@ -689,11 +873,42 @@ fn interpolate_value_inner(
     }
 }
 
+/// Interpolates the template expressions in a trigger's `match` predicate —
+/// scalar values and condition operands such as `contains` or `regex` — so
+/// phase advancement can depend on values extracted earlier in the run
+/// (e.g. `arguments.token: "{{session_token}}"`).
+///
+/// Only `extractors` are in scope; `request.*` and `response.*` references
+/// resolve to empty strings with a W-004 warning. Interpolated values are
+/// inserted verbatim, including into regular expressions.
+pub fn interpolate_predicate(
+    predicate: &MatchPredicate,
+    extractors: &HashMap<String, String>,
+) -> (MatchPredicate, Vec<Diagnostic>) {
+    let mut diagnostics = Vec::new();
+    let interpolated = predicate
+        .iter()
+        .map(|(path, entry)| {
+            let value = serde_json::to_value(entry).unwrap_or_default();
+            if !value.to_string().contains("{{") {
+                return (path.clone(), entry.clone());
+            }
+            let (value, diags) = interpolate_value(&value, extractors, None, None);
+            diagnostics.extend(diags);
+            let entry = serde_json::from_value(value).unwrap_or_else(|_| entry.clone());
+            (path.clone(), entry)
+        })
+        .collect();
+    (interpolated, diagnostics)
+}
+
 // ─── §5.6 evaluate_extractor ────────────────────────────────────────────────
 
 /// Applies an extractor to a message, capturing a value.
 ///
-/// - `json_path`: Evaluate JSONPath; return first match serialized to compact JSON.
+/// - `json_path`: Evaluate JSONPath with the installed
+///   [`JsonPathEngine`](crate::jsonpath::JsonPathEngine); return the first
+///   match serialized to compact JSON.
 /// - `regex`: Evaluate regex; return first capture group value.
 ///
 /// The `direction` parameter indicates whether the message is a request or
@@ -701,6 +916,7 @@ fn interpolate_value_inner(
 /// returned immediately (the extractor does not apply to this direction).
 ///
 /// Returns `None` for no match. `Some("")` is a valid result.
+#[cfg(feature = "evaluate")]
 pub fn evaluate_extractor(
     extractor: &Extractor,
     message: &Value,
@@ -719,10 +935,22 @@ pub fn evaluate_extractor(
     }
 }
 
+/// Applies an indicator's capture to the message it matched, with the same
+/// selector semantics as [`evaluate_extractor`].
+#[cfg(feature = "evaluate")]
+pub fn evaluate_capture(capture: &Capture, message: &Value) -> Option<String> {
+    match capture.capture_type {
+        crate::enums::ExtractorType::JsonPath => {
+            evaluate_extractor_jsonpath(&capture.selector, message)
+        }
+        crate::enums::ExtractorType::Regex => evaluate_extractor_regex(&capture.selector, message),
+    }
+}
+
+#[cfg(feature = "evaluate")]
 fn evaluate_extractor_jsonpath(selector: &str, message: &Value) -> Option<String> {
-    let path = serde_json_path::JsonPath::parse(selector).ok()?;
-    let node_list = path.query(message);
-    let first = node_list.first()?;
+    let nodes = crate::jsonpath::engine().query(selector, message).ok()?;
+    let first = nodes.first()?;
 
     // Serialize: scalars to their natural representation, non-scalars to compact JSON
     match first {
@@ -734,6 +962,7 @@ fn evaluate_extractor_jsonpath(selector: &str, message: &Value) -> Option<String
     }
 }
 
+#[cfg(feature = "evaluate")]
 fn evaluate_extractor_regex(selector: &str, message: &Value) -> Option<String> {
     let text = match message {
         Value::String(s) => s.clone(),
@@ -756,6 +985,8 @@ fn evaluate_extractor_regex(selector: &str, message: &Value) -> Option<String> {
 ///
 /// First-match-wins for entries with `when` predicates. Falls back to
 /// the default entry (no `when`) if no predicate-bearing entry matches.
+/// The request is only borrowed; predicates read it in place.
+#[cfg(feature = "evaluate")]
 pub fn select_response<'a>(
     entries: &'a [ResponseEntry],
     request: &Value,
@@ -789,7 +1020,8 @@ pub fn select_response<'a>(
 ///
 /// `state` is a mutable reference to per-trigger state that persists across
 /// calls. The SDK increments `state.event_count` only when the incoming event
-/// fully matches (base type + qualifier + predicate).
+/// fully matches (base type + qualifier + predicate + origin).
+#[cfg(feature = "evaluate")]
 pub fn evaluate_trigger(
     trigger: &Trigger,
     event: Option<&ProtocolEvent>,
@@ -828,12 +1060,17 @@ pub fn evaluate_trigger(
             }
         }
 
-        // 4. Check match predicate if present
+        // 4. Check match and origin predicates if present
         if let Some(predicate) = &trigger.match_predicate
             && !evaluate_predicate(predicate, &ev.content)
         {
             return TriggerResult::NotAdvanced;
         }
+        if let Some(origin) = &trigger.origin
+            && !evaluate_origin(origin, ev.origin.as_ref())
+        {
+            return TriggerResult::NotAdvanced;
+        }
 
         // 5. Full match — increment count, then check threshold
         state.event_count += 1;
@@ -848,6 +1085,28 @@ pub fn evaluate_trigger(
     TriggerResult::NotAdvanced
 }
 
+/// [`evaluate_trigger`] for a phase that started at `phase_started`, with the
+/// elapsed time read from `clock`.
+#[cfg(feature = "evaluate")]
+pub fn evaluate_trigger_at(
+    trigger: &Trigger,
+    event: Option<&ProtocolEvent>,
+    phase_started: Duration,
+    clock: &dyn Clock,
+    state: &mut TriggerState,
+    protocol: &str,
+) -> TriggerResult {
+    let elapsed = clock.now().saturating_sub(phase_started);
+    evaluate_trigger(trigger, event, elapsed, state, protocol)
+}
+
+/// Clock time at which `trigger`'s `after` timeout expires for a phase that
+/// started at `phase_started`, or `None` without a valid `after`.
+pub fn trigger_deadline(trigger: &Trigger, phase_started: Duration) -> Option<Duration> {
+    let after = parse_duration(trigger.after.as_deref()?).ok()?;
+    Some(phase_started + after)
+}
+
 // ─── §5.9 parse_event_qualifier ─────────────────────────────────────────────
 
 /// Splits an event type string on the first `:` separator.
@@ -880,3 +1139,158 @@ pub fn compute_effective_state(phases: &[Phase], phase_index: usize) -> Value {
 
     effective
 }
+
+// ─── Phase preconditions ────────────────────────────────────────────────────
+
+/// Checks a phase's `requires` against the values extracted so far, keyed by
+/// extractor name or `actor.name` for values shared by other actors. A value
+/// that was never extracted is absent, so only `exists: false` holds for it.
+/// A phase without `requires` may always be entered.
+#[cfg(feature = "evaluate")]
+pub fn evaluate_requires(
+    requires: Option<&MatchPredicate>,
+    values: &HashMap<String, String>,
+) -> bool {
+    requires.into_iter().flatten().all(|(name, entry)| {
+        let value = values.get(name).map(|v| Value::String(v.clone()));
+        entry_matches(entry, value.as_ref())
+    })
+}
+
+// ─── Regex dialects ─────────────────────────────────────────────────────────
+
+/// Largest repetition bound RE2-class engines accept.
+const PORTABLE_MAX_REPETITION: u64 = 1000;
+
+/// Checks that `pattern` stays within `dialect`, returning a description of
+/// the first construct outside it.
+///
+/// [`RegexDialect::Native`] accepts everything; whether the pattern compiles
+/// is a separate question (V-013). [`RegexDialect::Portable`] rejects
+/// constructs that RE2-class engines do not support, including ones the
+/// native engine also rejects (backreferences, lookaround), so the reason is
+/// reported in dialect terms.
+pub fn check_regex_dialect(pattern: &str, dialect: RegexDialect) -> Result<(), String> {
+    if dialect == RegexDialect::Native {
+        return Ok(());
+    }
+    let unsupported = |what: &str| {
+        Err(format!(
+            "{} not supported by the portable regex dialect",
+            what
+        ))
+    };
+    let chars: Vec<char> = pattern.chars().collect();
+    let at = |i: usize| chars.get(i).copied();
+    // Depth of nested character classes; 0 outside a class.
+    let mut class_depth = 0usize;
+    // Whether the previous token was a quantifier (for possessive `*+`).
+    let mut after_quantifier = false;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\\' {
+            match at(i + 1) {
+                Some('1'..='9') if class_depth == 0 => return unsupported("backreferences are"),
+                Some('k' | 'g') if class_depth == 0 => return unsupported("backreferences are"),
+                Some('<' | '>') if class_depth == 0 => {
+                    return unsupported("'\\<' and '\\>' word boundaries are");
+                }
+                Some('b' | 'B') if class_depth == 0 && at(i + 2) == Some('{') => {
+                    return unsupported("'\\b{…}' word boundaries are");
+                }
+                Some('x' | 'u' | 'U' | 'p' | 'P') if at(i + 2) == Some('{') => {
+                    // Braced code point or property name, not a repetition.
+                    after_quantifier = false;
+                    i += chars[i..]
+                        .iter()
+                        .position(|b| *b == '}')
+                        .map_or(2, |end| end + 1);
+                    continue;
+                }
+                _ => {}
+            }
+            after_quantifier = false;
+            i += 2;
+            continue;
+        }
+        if class_depth > 0 {
+            match c {
+                ']' => class_depth -= 1,
+                '[' if at(i + 1) == Some(':') => {
+                    // ASCII class such as [:alpha:], shared by all dialects.
+                    let rest: String = chars[i..].iter().collect();
+                    i += rest.find(":]").map_or(1, |end| end + 2);
+                    continue;
+                }
+                '[' => return unsupported("nested character classes are"),
+                '&' | '-' | '~' if at(i + 1) == Some(c) => {
+                    return unsupported("character class set operations are");
+                }
+                _ => {}
+            }
+            i += 1;
+            continue;
+        }
+        match c {
+            '[' => {
+                class_depth = 1;
+                i += 1;
+                // A leading `^` and a leading `]` are part of the class.
+                if at(i) == Some('^') {
+                    i += 1;
+                }
+                if at(i) == Some(']') {
+                    i += 1;
+                }
+                after_quantifier = false;
+                continue;
+            }
+            '(' if at(i + 1) == Some('?') => match (at(i + 2), at(i + 3)) {
+                (Some('=' | '!'), _) | (Some('<'), Some('=' | '!')) => {
+                    return unsupported("lookaround assertions are");
+                }
+                (Some('>'), _) => return unsupported("atomic groups are"),
+                (Some('('), _) => return unsupported("conditional groups are"),
+                (Some('0'..='9' | '&'), _) | (Some('+' | '-'), Some('0'..='9')) => {
+                    return unsupported("recursive patterns are");
+                }
+                _ => {
+                    let flags = chars[i + 2..]
+                        .iter()
+                        .take_while(|f| f.is_ascii_alphabetic() || **f == '-');
+                    if flags.clone().any(|f| *f == 'R') {
+                        return unsupported("the CRLF flag 'R' is");
+                    }
+                }
+            },
+            '+' if after_quantifier => return unsupported("possessive quantifiers are"),
+            '{' => {
+                let rest: String = chars[i + 1..].iter().collect();
+                if let Some(end) = rest.find('}') {
+                    let bounds = &rest[..end];
+                    if !bounds.is_empty() && bounds.chars().all(|b| b.is_ascii_digit() || b == ',')
+                    {
+                        if bounds
+                            .split(',')
+                            .filter_map(|n| n.parse::<u64>().ok())
+                            .any(|n| n > PORTABLE_MAX_REPETITION)
+                        {
+                            return Err(format!(
+                                "repetition bound {{{}}} exceeds the portable regex dialect's limit of {}",
+                                bounds, PORTABLE_MAX_REPETITION
+                            ));
+                        }
+                        i += end + 2;
+                        after_quantifier = true;
+                        continue;
+                    }
+                }
+            }
+            _ => {}
+        }
+        after_quantifier = matches!(c, '*' | '+' | '?') && !after_quantifier;
+        i += 1;
+    }
+    Ok(())
+}
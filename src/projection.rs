@@ -0,0 +1,211 @@
+//! Projection of a document's execution state into protocol messages.
+//!
+//! Shared by document scanning
+//! ([`evaluate_indicator_on_document`](crate::evaluate::evaluate_indicator_on_document))
+//! and validation, which checks that indicators can see the state they
+//! target; [`project_document`] is re-exported from `evaluate`.
+
+use crate::types::Document;
+use serde_json::Value;
+
+/// Surfaces read from a phase state's listings (tools, prompts, resources).
+const LISTING_SURFACES: &[&str] = &[
+    "tool_description",
+    "tool_input_schema",
+    "tool_name",
+    "tool_annotations",
+    "tool_output_schema",
+    "resource_uri",
+    "resource_description",
+    "prompt_description",
+];
+/// Surfaces read from an A2A agent card.
+const CARD_SURFACES: &[&str] = &[
+    "agent_card",
+    "card_name",
+    "card_description",
+    "skill_description",
+    "skill_name",
+];
+/// Surfaces read from a `tools/call` result.
+const TOOL_RESPONSE_SURFACES: &[&str] = &["tool_response", "tool_structured_response"];
+/// Surfaces read from a `prompts/get` result.
+const PROMPT_RESPONSE_SURFACES: &[&str] = &["prompt_content"];
+/// Surfaces read from an A2A task.
+const TASK_SURFACES: &[&str] = &["task_message", "task_artifact", "task_status"];
+
+/// Whether [`project_document`] projects messages for `surface`.
+pub(crate) fn is_projected_surface(surface: &str) -> bool {
+    [
+        LISTING_SURFACES,
+        CARD_SURFACES,
+        TOOL_RESPONSE_SURFACES,
+        PROMPT_RESPONSE_SURFACES,
+        TASK_SURFACES,
+    ]
+    .iter()
+    .any(|surfaces| surfaces.contains(&surface))
+}
+
+/// A protocol message reconstructed from a document's execution state.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProjectedMessage {
+    /// Dot-path of the state fragment the message was built from.
+    pub path: String,
+    /// Surfaces whose targets are relative to this message.
+    pub surfaces: &'static [&'static str],
+    /// The message as the attack would put it on the wire.
+    pub message: Value,
+}
+
+/// Project a document's execution state into the messages its actors would
+/// serve, so indicators can be evaluated against the document itself.
+///
+/// For every phase state (and a single-phase `execution.state`):
+///
+/// - the state itself serves as the MCP listing results (tools, resources,
+///   prompts);
+/// - `agent_card` serves as the A2A agent card;
+/// - each `tools[].responses[]` entry becomes a `tools/call` result, each
+///   `prompts[].responses[]` entry a `prompts/get` result, and each
+///   `task_responses[]` entry an A2A task, as is each of the entry's
+///   streamed `updates[]`.
+///
+/// A response entry's `synthesize.prompt` stands in for the content it would
+/// generate: it is projected as a single text content item, prompt message,
+/// or agent message part, so attacker instructions in generation prompts are
+/// visible to indicators.
+pub fn project_document(doc: &Document) -> Vec<ProjectedMessage> {
+    let mut states: Vec<(String, &Value)> = Vec::new();
+    if let Some(state) = &doc.attack.execution.state {
+        states.push(("attack.execution.state".to_string(), state));
+    }
+    for actor_info in crate::context::ResolvedContext::new(doc).actors() {
+        for (pi, phase) in actor_info.phases.iter().enumerate() {
+            if let Some(state) = &phase.state {
+                let path = format!("{}.phases[{}].state", actor_info.path_prefix, pi);
+                states.push((path, state));
+            }
+        }
+    }
+
+    let mut messages = Vec::new();
+    for (path, state) in states {
+        let Some(obj) = state.as_object() else {
+            continue;
+        };
+        messages.push(ProjectedMessage {
+            path: path.clone(),
+            surfaces: LISTING_SURFACES,
+            message: state.clone(),
+        });
+        if let Some(card) = obj.get("agent_card") {
+            messages.push(ProjectedMessage {
+                path: format!("{}.agent_card", path),
+                surfaces: CARD_SURFACES,
+                message: card.clone(),
+            });
+        }
+
+        for (list, surfaces) in [
+            ("tools", TOOL_RESPONSE_SURFACES),
+            ("prompts", PROMPT_RESPONSE_SURFACES),
+        ] {
+            for (i, item) in obj
+                .get(list)
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .enumerate()
+            {
+                for (j, entry) in item
+                    .get("responses")
+                    .and_then(|v| v.as_array())
+                    .into_iter()
+                    .flatten()
+                    .enumerate()
+                {
+                    messages.push(ProjectedMessage {
+                        path: format!("{}.{}[{}].responses[{}]", path, list, i, j),
+                        surfaces,
+                        message: project_response(entry, list),
+                    });
+                }
+            }
+        }
+        for (j, entry) in obj
+            .get("task_responses")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .enumerate()
+        {
+            messages.push(ProjectedMessage {
+                path: format!("{}.task_responses[{}]", path, j),
+                surfaces: TASK_SURFACES,
+                message: project_response(entry, "task_responses"),
+            });
+            for (k, update) in entry
+                .get("updates")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .enumerate()
+            {
+                messages.push(ProjectedMessage {
+                    path: format!("{}.task_responses[{}].updates[{}]", path, j, k),
+                    surfaces: TASK_SURFACES,
+                    message: project_update(update),
+                });
+            }
+        }
+    }
+    messages
+}
+
+/// A streamed task update as a task: a `status-update` event's `status`, or
+/// an `artifact-update` event's `artifact` as the task's only artifact.
+fn project_update(update: &Value) -> Value {
+    let mut task = serde_json::Map::new();
+    if let Some(status) = update.get("status") {
+        task.insert("status".to_string(), status.clone());
+        if let Some(message) = status.get("message") {
+            task.insert("messages".to_string(), Value::Array(vec![message.clone()]));
+        }
+    }
+    if let Some(artifact) = update.get("artifact") {
+        task.insert(
+            "artifacts".to_string(),
+            Value::Array(vec![artifact.clone()]),
+        );
+    }
+    Value::Object(task)
+}
+
+/// The wire message served for a response entry: the entry without `when`,
+/// with any `synthesize.prompt` substituted as text content.
+fn project_response(entry: &Value, list: &str) -> Value {
+    let mut message = entry.as_object().cloned().unwrap_or_default();
+    message.remove("when");
+    if let Some(prompt) = message
+        .remove("synthesize")
+        .and_then(|s| s.get("prompt").cloned())
+    {
+        let (key, item) = match list {
+            "tools" => (
+                "content",
+                serde_json::json!({"type": "text", "text": prompt}),
+            ),
+            "prompts" => (
+                "messages",
+                serde_json::json!({"role": "assistant", "content": {"type": "text", "text": prompt}}),
+            ),
+            _ => (
+                "messages",
+                serde_json::json!({"role": "agent", "parts": [{"kind": "text", "text": prompt}]}),
+            ),
+        };
+        message.insert(key.to_string(), Value::Array(vec![item]));
+    }
+    Value::Object(message)
+}
@@ -0,0 +1,165 @@
+//! Structured identifiers in `attack.references`.
+//!
+//! References are free-form URLs, but correlating attacks with vulnerability
+//! data needs the identifiers inside them. [`Reference::kind`] classifies a
+//! reference as a CVE record, a GitHub security advisory, or a CSAF advisory,
+//! [`Attack::cves`] collects the CVE IDs an attack cites, and
+//! [`enrich_references`] fills in and normalizes reference titles from those
+//! identifiers. Malformed CVE IDs are reported by W-008 during validation.
+
+use crate::types::{Attack, Document, Reference};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Candidate CVE identifiers, well-formed or not.
+static CVE_TOKEN_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\bCVE-[A-Za-z0-9-]*[A-Za-z0-9]").unwrap());
+
+static CVE_ID_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)^CVE-(\d{4})-\d{4,}$").unwrap());
+
+static GHSA_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\bGHSA(-[23456789cfghjmpqrvwx]{4}){3}\b").unwrap());
+
+/// What an [`attack.references`](Attack::references) entry points to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReferenceKind {
+    /// A CSAF advisory document (`.json` under a `csaf` path).
+    Csaf,
+    /// A CVE record; carries the canonical (uppercase) CVE ID.
+    Cve(String),
+    /// A GitHub security advisory; carries the canonical GHSA ID.
+    Ghsa(String),
+    /// Any other reference.
+    Other,
+}
+
+impl Reference {
+    /// Classify the reference by its URL, falling back to its title for CVE
+    /// and GHSA IDs. A CSAF document that names a CVE is still
+    /// [`ReferenceKind::Csaf`]; use [`Reference::cve_ids`] for the CVEs it
+    /// mentions.
+    pub fn kind(&self) -> ReferenceKind {
+        if is_csaf_url(&self.url) {
+            return ReferenceKind::Csaf;
+        }
+        let texts = [Some(self.url.as_str()), self.title.as_deref()];
+        if let Some(cve) = texts.iter().flatten().find_map(|t| cve_ids(t).next()) {
+            return ReferenceKind::Cve(cve);
+        }
+        if let Some(ghsa) = texts.iter().flatten().find_map(|t| GHSA_RE.find(t)) {
+            return ReferenceKind::Ghsa(canonical_ghsa(ghsa.as_str()));
+        }
+        ReferenceKind::Other
+    }
+
+    /// Well-formed CVE IDs in the reference's URL and title, canonicalized
+    /// and deduplicated in order of appearance.
+    pub fn cve_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = Vec::new();
+        for text in [Some(self.url.as_str()), self.title.as_deref()]
+            .into_iter()
+            .flatten()
+        {
+            for id in cve_ids(text) {
+                if !ids.contains(&id) {
+                    ids.push(id);
+                }
+            }
+        }
+        ids
+    }
+}
+
+impl Attack {
+    /// CVE IDs cited by the attack's references, canonicalized and
+    /// deduplicated in order of appearance.
+    pub fn cves(&self) -> Vec<String> {
+        let mut ids: Vec<String> = Vec::new();
+        for reference in self.references.iter().flatten() {
+            for id in reference.cve_ids() {
+                if !ids.contains(&id) {
+                    ids.push(id);
+                }
+            }
+        }
+        ids
+    }
+}
+
+/// Fill in and normalize reference titles from the identifiers they carry:
+/// CVE and GHSA references without a title are titled with their ID, CSAF
+/// references with `"CSAF advisory"` (plus the CVEs they name), and CVE IDs
+/// inside existing titles are uppercased. Idempotent; not part of
+/// [`normalize`](crate::normalize::normalize).
+pub fn enrich_references(mut doc: Document) -> Document {
+    for reference in doc.attack.references.iter_mut().flatten() {
+        match &mut reference.title {
+            Some(title) => {
+                *title = CVE_TOKEN_RE
+                    .replace_all(title.trim(), |caps: &regex::Captures| {
+                        let token = &caps[0];
+                        if is_cve_id(token) {
+                            token.to_ascii_uppercase()
+                        } else {
+                            token.to_string()
+                        }
+                    })
+                    .into_owned();
+            }
+            None => {
+                reference.title = match reference.kind() {
+                    ReferenceKind::Cve(id) | ReferenceKind::Ghsa(id) => Some(id),
+                    ReferenceKind::Csaf => {
+                        let cves = reference.cve_ids();
+                        Some(if cves.is_empty() {
+                            "CSAF advisory".to_string()
+                        } else {
+                            format!("CSAF advisory for {}", cves.join(", "))
+                        })
+                    }
+                    ReferenceKind::Other => None,
+                };
+            }
+        }
+    }
+    doc
+}
+
+/// CVE-like tokens in `text` that are not well-formed CVE IDs (W-008).
+#[cfg(feature = "validate")]
+pub(crate) fn malformed_cve_ids(text: &str) -> Vec<&str> {
+    CVE_TOKEN_RE
+        .find_iter(text)
+        .map(|m| m.as_str())
+        .filter(|token| !is_cve_id(token))
+        .collect()
+}
+
+fn is_cve_id(token: &str) -> bool {
+    CVE_ID_RE
+        .captures(token)
+        .and_then(|caps| caps[1].parse::<u32>().ok())
+        .is_some_and(|year| year >= 1999)
+}
+
+fn cve_ids(text: &str) -> impl Iterator<Item = String> + '_ {
+    CVE_TOKEN_RE
+        .find_iter(text)
+        .map(|m| m.as_str())
+        .filter(|token| is_cve_id(token))
+        .map(str::to_ascii_uppercase)
+}
+
+fn canonical_ghsa(id: &str) -> String {
+    format!("GHSA{}", id[4..].to_ascii_lowercase())
+}
+
+fn is_csaf_url(url: &str) -> bool {
+    let path = url
+        .split(['?', '#'])
+        .next()
+        .unwrap_or(url)
+        .to_ascii_lowercase();
+    path.ends_with(".json") && path.split('/').any(|segment| segment == "csaf")
+}
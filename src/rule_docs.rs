@@ -0,0 +1,464 @@
+/// Extended documentation for a conformance rule.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RuleDocEntry {
+    pub id: &'static str,
+    pub rationale: &'static str,
+    pub failing: &'static str,
+    pub passing: &'static str,
+    pub fix: &'static str,
+}
+
+const fn entry(
+    id: &'static str,
+    rationale: &'static str,
+    failing: &'static str,
+    passing: &'static str,
+    fix: &'static str,
+) -> RuleDocEntry {
+    RuleDocEntry {
+        id,
+        rationale,
+        failing,
+        passing,
+        fix,
+    }
+}
+
+/// Rationale, examples, and fix guidance for every rule in `spec::RULES`.
+/// Examples are YAML fragments showing only the relevant part of a document.
+pub static RULE_DOCS: &[RuleDocEntry] = &[
+    entry(
+        "V-001",
+        "Consumers dispatch on the format version; an unknown version cannot be interpreted safely.",
+        "oatf: \"0.2\"\n",
+        "oatf: \"0.1\"\n",
+        "Set `oatf` to a version this implementation supports (see `spec_info().spec_version`).",
+    ),
+    entry(
+        "V-002",
+        "A document that is not well-formed YAML cannot be parsed into the document model.",
+        "oatf: \"0.1\"\nattack: [unclosed\n",
+        "oatf: \"0.1\"\nattack:\n  execution: {}\n",
+        "Fix the YAML syntax error reported at the given line and column.",
+    ),
+    entry(
+        "V-003",
+        "Every document describes exactly one attack; without `attack` there is nothing to execute or detect.",
+        "oatf: \"0.1\"\n",
+        "oatf: \"0.1\"\nattack:\n  execution:\n    mode: mcp_server\n    state: {}\n",
+        "Add a top-level `attack` mapping.",
+    ),
+    entry(
+        "V-004",
+        "Required fields carry the information every consumer relies on, such as `attack.execution` and `indicators[].surface`.",
+        "indicators:\n  - pattern:\n      contains: \"x\"\n",
+        "indicators:\n  - surface: tool_description\n    pattern:\n      contains: \"x\"\n",
+        "Add the missing field named in the error.",
+    ),
+    entry(
+        "V-005",
+        "Enumerated fields are interpreted by value; an unknown value has no defined meaning.",
+        "severity: catastrophic\n",
+        "severity: critical\n",
+        "Use one of the values listed in the specification for the field.",
+    ),
+    entry(
+        "V-006",
+        "An empty indicator list claims detection coverage that does not exist.",
+        "indicators: []\n",
+        "indicators:\n  - surface: tool_description\n    pattern:\n      contains: \"x\"\n",
+        "Add at least one indicator or remove the `indicators` key.",
+    ),
+    entry(
+        "V-007",
+        "An empty phase list describes an execution that does nothing.",
+        "execution:\n  mode: mcp_server\n  phases: []\n",
+        "execution:\n  mode: mcp_server\n  phases:\n    - name: serve\n      state: {}\n",
+        "Add at least one phase or use the single-phase `state` form.",
+    ),
+    entry(
+        "V-008",
+        "A phase without a trigger never advances, so any phase after it is unreachable.",
+        "phases:\n  - name: a\n    state: {}\n  - name: b\n",
+        "phases:\n  - name: a\n    state: {}\n    trigger:\n      event: tools/call\n  - name: b\n",
+        "Give every phase except the last a `trigger`, and keep the terminal phase last.",
+    ),
+    entry(
+        "V-009",
+        "Later phases inherit state from earlier ones; the first phase has nothing to inherit from.",
+        "phases:\n  - name: start\n    trigger:\n      event: tools/call\n  - name: end\n",
+        "phases:\n  - name: start\n    state:\n      tools: []\n    trigger:\n      event: tools/call\n  - name: end\n",
+        "Add `state` to the first phase of each actor.",
+    ),
+    entry(
+        "V-010",
+        "Verdicts report results per indicator ID; duplicates make results ambiguous.",
+        "indicators:\n  - id: OATF-001-01\n    ...\n  - id: OATF-001-01\n    ...\n",
+        "indicators:\n  - id: OATF-001-01\n    ...\n  - id: OATF-001-02\n    ...\n",
+        "Give each indicator a distinct `id`, or omit IDs and let normalization assign them.",
+    ),
+    entry(
+        "V-011",
+        "Phase names identify phases in traces and reports; duplicates make them ambiguous.",
+        "phases:\n  - name: probe\n    ...\n  - name: probe\n",
+        "phases:\n  - name: probe\n    ...\n  - name: exploit\n",
+        "Rename one of the phases.",
+    ),
+    entry(
+        "V-012",
+        "An indicator is evaluated by exactly one method; zero or several leave the method undefined.",
+        "- surface: tool_description\n  pattern:\n    contains: \"x\"\n  expression:\n    cel: \"true\"\n",
+        "- surface: tool_description\n  pattern:\n    contains: \"x\"\n",
+        "Keep exactly one of `pattern`, `expression`, `semantic`, or `frequency`, and do not mix shorthand operators with `condition`.",
+    ),
+    entry(
+        "V-013",
+        "A regular expression that does not compile can never match.",
+        "pattern:\n  regex: \"(unclosed\"\n",
+        "pattern:\n  regex: \"(closed)\"\n",
+        "Fix the regular expression syntax; escape literal metacharacters. Under the portable dialect, replace backreferences and lookaround with separate indicators or a CEL expression.",
+    ),
+    entry(
+        "V-014",
+        "A CEL expression that does not parse cannot be evaluated.",
+        "expression:\n  cel: \"message.name ==\"\n",
+        "expression:\n  cel: \"message.name == 'x'\"\n",
+        "Fix the CEL syntax at the reported position.",
+    ),
+    entry(
+        "V-015",
+        "A JSONPath selector that does not parse cannot extract anything.",
+        "extractors:\n  - name: token\n    source: response\n    type: json_path\n    selector: \"$..[\"\n",
+        "extractors:\n  - name: token\n    source: response\n    type: json_path\n    selector: \"$.content[0].text\"\n",
+        "Fix the JSONPath syntax in the selector.",
+    ),
+    entry(
+        "V-016",
+        "Malformed template expressions are sent verbatim instead of being interpolated.",
+        "text: \"token={{token\"\n",
+        "text: \"token={{token}}\"\n",
+        "Close every `{{` with `}}` and use only identifier characters and dots inside.",
+    ),
+    entry(
+        "V-017",
+        "Confidence is a percentage.",
+        "severity:\n  level: high\n  confidence: 150\n",
+        "severity:\n  level: high\n  confidence: 90\n",
+        "Use an integer between 0 and 100.",
+    ),
+    entry(
+        "V-018",
+        "A surface names where in a specific protocol the indicator looks; it cannot apply to another protocol.",
+        "- protocol: a2a\n  surface: tool_description\n",
+        "- protocol: mcp\n  surface: tool_description\n",
+        "Pick a surface registered for the indicator's protocol, or correct `protocol`.",
+    ),
+    entry(
+        "V-019",
+        "`count` and `match` qualify an event; without one they have nothing to count or match.",
+        "trigger:\n  count: 3\n",
+        "trigger:\n  event: tools/call\n  count: 3\n",
+        "Add `event`, or remove `count` and `match`.",
+    ),
+    entry(
+        "V-020",
+        "Anchors, aliases, and merge keys make documents hard to review and can hide content.",
+        "base: &base\n  name: a\ncopy: *base\n",
+        "base:\n  name: a\ncopy:\n  name: a\n",
+        "Expand anchors and aliases inline.",
+    ),
+    entry(
+        "V-021",
+        "Targets are resolved as wildcard dot-paths; a malformed path resolves to nothing.",
+        "pattern:\n  target: \"tools[*.description\"\n  condition:\n    contains: \"x\"\n",
+        "pattern:\n  target: \"tools[*].description\"\n  condition:\n    contains: \"x\"\n",
+        "Use dot-separated segments with optional `[*]` wildcards, and `!json` or `!url` after a segment to decode an embedded string.",
+    ),
+    entry(
+        "V-022",
+        "Semantic thresholds are similarity scores between 0 and 1.",
+        "semantic:\n  intent: \"exfiltration\"\n  threshold: 70\n",
+        "semantic:\n  intent: \"exfiltration\"\n  threshold: 0.7\n",
+        "Use a number between 0.0 and 1.0.",
+    ),
+    entry(
+        "V-023",
+        "Attack IDs are referenced across tools and catalogs and must follow one format.",
+        "id: oatf-1\n",
+        "id: OATF-001\n",
+        "Use an uppercase prefix followed by a dash and at least three digits.",
+    ),
+    entry(
+        "V-024",
+        "Indicator IDs are derived from the attack ID so results can be traced back to their attack.",
+        "id: OATF-001\nindicators:\n  - id: OATF-002-01\n",
+        "id: OATF-001\nindicators:\n  - id: OATF-001-01\n",
+        "Use the attack ID followed by a dash and at least two digits.",
+    ),
+    entry(
+        "V-025",
+        "Confidence is a percentage.",
+        "- surface: tool_description\n  confidence: 120\n",
+        "- surface: tool_description\n  confidence: 80\n",
+        "Use an integer between 0 and 100.",
+    ),
+    entry(
+        "V-026",
+        "Expression variables are bound by resolving their path; an invalid path binds nothing.",
+        "expression:\n  cel: \"desc.size() > 100\"\n  variables:\n    desc: \"tools[*.description\"\n",
+        "expression:\n  cel: \"desc.size() > 100\"\n  variables:\n    desc: \"tools[*].description\"\n",
+        "Use a valid wildcard dot-path for every variable.",
+    ),
+    entry(
+        "V-027",
+        "Trigger match keys are resolved against the event content as simple dot-paths.",
+        "match:\n  \"params..name\": read_file\n",
+        "match:\n  params.name: read_file\n",
+        "Use dot-separated identifiers without wildcards or empty segments.",
+    ),
+    entry(
+        "V-028",
+        "Mode and protocol decide how a phase executes and how an indicator is evaluated; they must be resolvable.",
+        "execution:\n  phases:\n    - name: serve\n      state: {}\n",
+        "execution:\n  mode: mcp_server\n  phases:\n    - name: serve\n      state: {}\n",
+        "Set `execution.mode`, or set `mode` on every phase (and `protocol` on indicators when it cannot be inferred).",
+    ),
+    entry(
+        "V-029",
+        "An event that a mode never observes can never fire a trigger.",
+        "mode: mcp_server\n...\ntrigger:\n  event: message/send\n",
+        "mode: mcp_server\n...\ntrigger:\n  event: tools/call\n",
+        "Use an event registered for the phase's mode (see `spec_info().events`).",
+    ),
+    entry(
+        "V-030",
+        "The single-phase, multi-phase, and multi-actor forms are alternatives; combining them is ambiguous.",
+        "execution:\n  mode: mcp_server\n  state: {}\n  phases:\n    - name: a\n",
+        "execution:\n  mode: mcp_server\n  state: {}\n",
+        "Keep exactly one of `state`, `phases`, or `actors`.",
+    ),
+    entry(
+        "V-031",
+        "Actors are referenced by name and each runs its own phases under its own mode.",
+        "actors:\n  - name: Server\n    mode: mcp_server\n    phases: []\n",
+        "actors:\n  - name: server\n    mode: mcp_server\n    phases:\n      - name: serve\n        state: {}\n",
+        "Give every actor a unique snake_case name, a mode, and at least one phase.",
+    ),
+    entry(
+        "V-032",
+        "A cross-actor reference reads an extractor of another actor; an unknown actor yields nothing.",
+        "text: \"{{clinet.token}}\"\n",
+        "text: \"{{client.token}}\"\n",
+        "Reference an actor declared in `execution.actors`.",
+    ),
+    entry(
+        "V-033",
+        "A response is either static or synthesized; with both, the served content is ambiguous.",
+        "responses:\n  - content: [{type: text, text: \"x\"}]\n    synthesize:\n      prompt: \"y\"\n",
+        "responses:\n  - synthesize:\n      prompt: \"y\"\n",
+        "Keep either the static content or `synthesize`.",
+    ),
+    entry(
+        "V-034",
+        "Response entries are first-match-wins; a second catch-all can never be selected.",
+        "responses:\n  - content: [{type: text, text: \"a\"}]\n  - content: [{type: text, text: \"b\"}]\n",
+        "responses:\n  - when:\n      arguments.path: /etc/passwd\n    content: [{type: text, text: \"a\"}]\n  - content: [{type: text, text: \"b\"}]\n",
+        "Add `when` to all but one entry, and keep the catch-all last.",
+    ),
+    entry(
+        "V-035",
+        "An empty prompt gives the generation provider nothing to work from.",
+        "synthesize:\n  prompt: \"\"\n",
+        "synthesize:\n  prompt: \"Return a file listing that includes .ssh/id_rsa\"\n",
+        "Write a non-empty prompt.",
+    ),
+    entry(
+        "V-036",
+        "The protocol is derived from the mode string, so the mode must follow `<protocol>_<role>`.",
+        "mode: McpServer\n",
+        "mode: mcp_server\n",
+        "Use a lowercase protocol followed by `_server` or `_client`, optionally with `@version`.",
+    ),
+    entry(
+        "V-037",
+        "Document versions count revisions starting at 1.",
+        "version: 0\n",
+        "version: 1\n",
+        "Use a positive integer.",
+    ),
+    entry(
+        "V-038",
+        "A timeout that cannot be parsed never elapses.",
+        "trigger:\n  after: \"30 seconds\"\n",
+        "trigger:\n  after: 30s\n",
+        "Use a shorthand duration (`30s`, `5m`, `1h`, `2d`) or ISO 8601 (`PT30S`).",
+    ),
+    entry(
+        "V-039",
+        "Extractor names are referenced from templates as `{{name}}`.",
+        "extractors:\n  - name: Session-Token\n",
+        "extractors:\n  - name: session_token\n",
+        "Use a snake_case name.",
+    ),
+    entry(
+        "V-040",
+        "An empty extractor list captures nothing.",
+        "extractors: []\n",
+        "extractors:\n  - name: token\n    source: response\n    type: regex\n    selector: \"token=(\\\\w+)\"\n",
+        "Add at least one extractor or remove the `extractors` key.",
+    ),
+    entry(
+        "V-041",
+        "Variable keys become identifiers in the CEL environment.",
+        "variables:\n  tool-desc: \"tools[*].description\"\n",
+        "variables:\n  tool_desc: \"tools[*].description\"\n",
+        "Use a valid CEL identifier (letters, digits, underscores; not starting with a digit).",
+    ),
+    entry(
+        "V-042",
+        "A trigger with neither an event nor a timeout can never fire.",
+        "trigger:\n  count: 1\n",
+        "trigger:\n  after: 30s\n",
+        "Add `event`, `after`, or both.",
+    ),
+    entry(
+        "V-043",
+        "A binding-specific action is identified by its single key.",
+        "on_enter:\n  - x-foo: {}\n    x-bar: {}\n",
+        "on_enter:\n  - x-foo: {}\n  - x-bar: {}\n",
+        "Split the action so each entry has exactly one non-extension key.",
+    ),
+    entry(
+        "V-044",
+        "Regex extractors capture the first group; without one there is nothing to capture.",
+        "selector: \"token=\\\\w+\"\n",
+        "selector: \"token=(\\\\w+)\"\n",
+        "Wrap the part to capture in parentheses.",
+    ),
+    entry(
+        "V-045",
+        "An empty action list does nothing.",
+        "on_enter: []\n",
+        "on_enter:\n  - log:\n      message: \"entered\"\n",
+        "Add at least one action or remove the `on_enter` key.",
+    ),
+    entry(
+        "V-046",
+        "A pinned protocol version selects wire behavior; an unknown version cannot be honored.",
+        "mode: mcp_server@2023-01-01\n",
+        "mode: mcp_server@2025-06-18\n",
+        "Pin a known revision (see `spec_info().protocols`) or drop the `@version` pin.",
+    ),
+    entry(
+        "V-047",
+        "Pacing waits between messages; an unparseable duration cannot be waited out.",
+        "pacing:\n  delay: \"two seconds\"\n",
+        "pacing:\n  delay: 2s\n  jitter: 1s\n",
+        "Use a shorthand or ISO 8601 duration for `delay` and `jitter`.",
+    ),
+    entry(
+        "V-048",
+        "Safety limits stop a run before it does harm; a malformed limit would silently not apply.",
+        "safety:\n  max_messages: 0\n  forbidden_hosts: [\"https://prod.example.com/\"]\n",
+        "safety:\n  max_messages: 100\n  forbidden_hosts: [\"*.prod.example.com\"]\n",
+        "Use a duration for `max_runtime`, a positive `max_messages`, and bare host names or `*.` patterns.",
+    ),
+    entry(
+        "V-049",
+        "Lifecycle links let catalogs route users from a deprecated attack to its replacement; a link that cannot name another attack leads nowhere.",
+        "id: OATF-010\nsuperseded_by: OATF-010\nsupersedes: [\"oatf 9\"]\n",
+        "id: OATF-010\nstatus: deprecated\nsuperseded_by: OATF-012\n",
+        "Reference other attacks by their attack ID, and omit `supersedes` rather than leaving it empty.",
+    ),
+    entry(
+        "V-050",
+        "Evaluators downgrade matches that fit a false positive's `when`; an empty or malformed predicate would mark every match, or none, as benign.",
+        "false_positives:\n  - description: \"\"\n    when: {}\n",
+        "false_positives:\n  - Security scanners quoting the phrase\n  - description: Internal audit tool\n    when:\n      name: audit_log\n",
+        "Give each entry a description and key `when` conditions by simple dot paths.",
+    ),
+    entry(
+        "V-051",
+        "Stage correlation requires a match in every listed stage; a stage no indicator belongs to can never match, so the attack could never be exploited.",
+        "indicators:\n  - stage: Delivery\n    surface: tool_description\n    pattern: {contains: x}\ncorrelation:\n  stages: [delivery, exfiltration]\n",
+        "indicators:\n  - stage: delivery\n    surface: tool_description\n    pattern: {contains: x}\n  - stage: exfiltration\n    surface: tool_arguments\n    pattern: {contains: x}\ncorrelation:\n  stages: [delivery, exfiltration]\n",
+        "Use snake_case stage names and list only stages that some indicator declares.",
+    ),
+    entry(
+        "V-052",
+        "A consumer that asked for a profile relies on it: detection pipelines need indicators to evaluate, and emulation libraries must not ship detection content that is never checked.",
+        "# validated as execution_only\nexecution:\n  mode: mcp_server\n  state: {tools: []}\nindicators:\n  - surface: tool_description\n    pattern: {contains: x}\n",
+        "# validated as execution_only\nexecution:\n  mode: mcp_server\n  state: {tools: []}\n",
+        "Add indicators for the full profile, or drop indicators and correlation for execution_only.",
+    ),
+    entry(
+        "V-053",
+        "A frequency indicator matches once enough events fall within its window; a zero window or threshold would match never or on every event, and an unresolvable `group_by` would count nothing.",
+        "- surface: tool_arguments\n  frequency:\n    event: tools/call\n    window: 0s\n    threshold: 0\n",
+        "- surface: tool_arguments\n  frequency:\n    event: tools/call:read_file\n    window: 60s\n    threshold: 10\n    group_by: arguments.path\n",
+        "Give `window` a positive duration and `threshold` a count of at least 1, and key `group_by` and `match` by simple dot paths.",
+    ),
+    entry(
+        "V-054",
+        "Correlation keys only constrain indicators that capture them: a key no indicator captures silently correlates unrelated matches, and a selector that can never capture keeps its indicator's matches out of every verdict.",
+        "indicators:\n  - surface: tool_arguments\n    pattern: {contains: x}\n    capture:\n      - {name: Session, type: regex, selector: \"session=\\\\w+\"}\ncorrelation:\n  key: [session_id]\n",
+        "indicators:\n  - surface: tool_arguments\n    pattern: {contains: x}\n    capture:\n      - {name: session_id, type: json_path, selector: \"$.session\"}\ncorrelation:\n  key: [session_id]\n",
+        "Name captures in snake_case with a JSONPath or a regex with a capture group, and list only captured names in `correlation.key`.",
+    ),
+    entry(
+        "V-055",
+        "An origin predicate is checked against the event's `{role, endpoint, trusted}` only; any other key, an unknown role, or a role that never sends the indicator's surface makes the predicate unsatisfiable.",
+        "- surface: tool_arguments\n  origin:\n    role: server\n    host: evil.example\n  pattern: {contains: x}\n",
+        "- surface: tool_arguments\n  origin:\n    role: client\n    trusted: false\n  pattern: {contains: x}\n",
+        "Key `origin` by `role`, `endpoint`, or `trusted`, use `client`, `server`, or `third_party` for the role, and pick a role that can send the surface.",
+    ),
+    entry(
+        "V-056",
+        "A trigger's `match` predicate is interpolated with the values extracted so far before it is evaluated. A bare `{{name}}` must be captured by an extractor of an earlier phase of the same actor, and `{{actor.name}}` by an extractor of that actor; `request` and `response` are not in scope.",
+        "phases:\n  - name: login\n    trigger:\n      event: tools/call\n      match:\n        arguments.token: \"{{session_token}}\"\n",
+        "phases:\n  - name: login\n    extractors:\n      - name: session_token\n        source: response\n        type: json_path\n        selector: \"$.content[0].text\"\n    trigger:\n      event: tools/call\n  - name: replay\n    trigger:\n      event: tools/call\n      match:\n        arguments.token: \"{{session_token}}\"\n",
+        "Declare an extractor for the value in an earlier phase, or reference the actor that captures it as `{{actor.name}}`.",
+    ),
+    entry(
+        "V-057",
+        "`on_timeout` actions run only when a trigger advances by its `after` timeout; an empty list does nothing, and without `after` the trigger can never time out.",
+        "trigger:\n  event: tools/call\non_timeout:\n  - log:\n      message: target never called the tool\n",
+        "trigger:\n  event: tools/call\n  after: 30s\non_timeout:\n  - log:\n      message: target never called the tool\n",
+        "Give the trigger an `after` duration, or remove `on_timeout`.",
+    ),
+    entry(
+        "V-058",
+        "Engines decode attachments on demand and substitute them for `{{attachment:name}}` references; a name used twice is ambiguous, data that does not decode or exceeds `attachments::MAX_ATTACHMENT_SIZE` decoded bytes cannot be served, and a reference to an undeclared attachment cannot be resolved.",
+        "attachments:\n  - name: note\n    content_type: text/plain\n    encoding: gzip\n    data: aGVsbG8=\nexecution:\n  state:\n    resources:\n      - uri: file:///note.txt\n        text: \"{{attachment:notes}}\"\n",
+        "attachments:\n  - name: note\n    content_type: text/plain\n    encoding: base64\n    data: aGVsbG8=\nexecution:\n  state:\n    resources:\n      - uri: file:///note.txt\n        text: \"{{attachment:note}}\"\n",
+        "Give each attachment a unique snake_case name and a `type/subtype` content type, make `data` match its `encoding`, and reference attachments by their declared names.",
+    ),
+    entry(
+        "V-059",
+        "Harnesses and gateways load each phase's state into memory and serve it to agents; a state with thousands of tools, megabyte descriptions, or deep nesting, whether malicious or accidental, can exhaust them before the attack under test even runs.",
+        "# validated with max_tools: 2\nexecution:\n  mode: mcp_server\n  state:\n    tools:\n      - {name: a, description: A, inputSchema: {type: object}}\n      - {name: b, description: B, inputSchema: {type: object}}\n      - {name: c, description: C, inputSchema: {type: object}}\n",
+        "# validated with max_tools: 2\nexecution:\n  mode: mcp_server\n  state:\n    tools:\n      - {name: a, description: A, inputSchema: {type: object}}\n      - {name: b, description: B, inputSchema: {type: object}}\n",
+        "Trim the state to what the attack needs, move large content into attachments, or raise the limits deliberately for trusted documents.",
+    ),
+    entry(
+        "V-060",
+        "Harnesses bind each actor's transport from its endpoint; an endpoint with both a URL and a command, neither, a URL a harness cannot connect to, or an empty command line leaves the binding ambiguous or impossible.",
+        "execution:\n  actors:\n    - name: attacker\n      mode: mcp_server\n      endpoint:\n        url: ftp://127.0.0.1/mcp\n      phases:\n        - state:\n            tools: []\n",
+        "execution:\n  actors:\n    - name: attacker\n      mode: mcp_server\n      endpoint:\n        url: http://127.0.0.1:8080/mcp\n      phases:\n        - state:\n            tools: []\n",
+        "Set either `url` to an `http`, `https`, `ws`, or `wss` URL, or `command` to the program and arguments of a stdio process.",
+    ),
+    entry(
+        "V-061",
+        "Engines check a phase's `requires` before entering it, with the values extracted so far; a name that no earlier phase or other actor extracts never has a value, so its condition is decided before the attack runs.",
+        "execution:\n  mode: mcp_client\n  phases:\n    - name: login\n      extractors:\n        - name: token\n          source: response\n          type: json_path\n          selector: \"$.content[0].text\"\n      trigger:\n        event: tools/call:login\n    - name: exploit\n      requires:\n        session: {exists: true}\n",
+        "execution:\n  mode: mcp_client\n  phases:\n    - name: login\n      extractors:\n        - name: token\n          source: response\n          type: json_path\n          selector: \"$.content[0].text\"\n      trigger:\n        event: tools/call:login\n    - name: exploit\n      requires:\n        token: {exists: true}\n",
+        "Name an extractor of an earlier phase of the same actor, or `actor.name` for a value another actor extracts.",
+    ),
+    entry(
+        "V-062",
+        "Consumers decide whether they may reuse, modify, or redistribute a document from its license; an expression tools cannot parse leaves the terms unclear.",
+        "license: CC-BY-4.0 or (Apache-2.0\n",
+        "license: CC-BY-4.0 OR Apache-2.0\n",
+        "Write an SPDX license expression: license IDs or `LicenseRef-` references joined with uppercase `AND`, `OR`, and `WITH`.",
+    ),
+];
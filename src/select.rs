@@ -0,0 +1,364 @@
+//! Field selection for serialized verdicts.
+//!
+//! High-volume pipelines rarely need every part of an [`AttackVerdict`]. A
+//! [`FieldSelection`] names the fields to keep in a GraphQL-like syntax —
+//! nested fields in braces or as dotted paths, separated by whitespace or
+//! commas:
+//!
+//! ```rust
+//! use oatf::select::FieldSelection;
+//!
+//! let fields: FieldSelection = "attack_id result indicator_verdicts { indicator_id result }"
+//!     .parse()
+//!     .unwrap();
+//! assert_eq!(
+//!     fields,
+//!     "attack_id, result, indicator_verdicts.indicator_id, indicator_verdicts.result"
+//!         .parse()
+//!         .unwrap()
+//! );
+//! ```
+//!
+//! [`AttackVerdict::to_json_with`] keeps only the selected fields, and
+//! [`AttackVerdict::to_compact_line`] renders them as a single
+//! `key=value` line for SIEM ingestion. Selecting a field without a nested
+//! selection keeps all of it; a selected field the verdict omits (such as an
+//! absent `timestamp`) is left out.
+
+use crate::error::{SelectionError, SelectionErrorKind};
+use crate::types::AttackVerdict;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::str::FromStr;
+
+/// A field of a verdict type and the fields nested under it.
+struct Field {
+    name: &'static str,
+    children: &'static [Field],
+}
+
+const fn leaf(name: &'static str) -> Field {
+    Field {
+        name,
+        children: &[],
+    }
+}
+
+const SUMMARY: &[Field] = &[
+    leaf("matched"),
+    leaf("not_matched"),
+    leaf("error"),
+    leaf("skipped"),
+];
+
+const INDICATOR_VERDICT: &[Field] = &[
+    leaf("indicator_id"),
+    leaf("result"),
+    leaf("timestamp"),
+    leaf("evidence"),
+    leaf("source"),
+    leaf("captures"),
+    leaf("skip_reason"),
+];
+
+const STAGE_SUMMARY: &[Field] = &[
+    leaf("stage"),
+    Field {
+        name: "summary",
+        children: SUMMARY,
+    },
+];
+
+const ATTACK_VERDICT: &[Field] = &[
+    leaf("attack_id"),
+    leaf("result"),
+    Field {
+        name: "indicator_verdicts",
+        children: INDICATOR_VERDICT,
+    },
+    Field {
+        name: "evaluation_summary",
+        children: SUMMARY,
+    },
+    Field {
+        name: "stages",
+        children: STAGE_SUMMARY,
+    },
+    leaf("timestamp"),
+    leaf("source"),
+];
+
+/// The fields of an [`AttackVerdict`] to serialize.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldSelection {
+    /// Selected field names, each with its nested selection (`None` keeps the
+    /// whole field).
+    fields: BTreeMap<String, Option<FieldSelection>>,
+}
+
+impl FieldSelection {
+    /// Parse a selection of [`AttackVerdict`] fields.
+    ///
+    /// # Errors
+    ///
+    /// Returns `syntax` for an empty or malformed selection and
+    /// `unknown_field` for a name that is not a field at its position.
+    pub fn parse(input: &str) -> Result<Self, SelectionError> {
+        let tokens = tokenize(input)?;
+        let mut pos = 0;
+        let selection = parse_fields(&tokens, &mut pos, ATTACK_VERDICT, "")?;
+        if let Some(token) = tokens.get(pos) {
+            return Err(syntax(format!("unexpected '{}'", token)));
+        }
+        Ok(selection)
+    }
+
+    /// Every field of the verdict.
+    pub fn all() -> Self {
+        FieldSelection {
+            fields: ATTACK_VERDICT
+                .iter()
+                .map(|f| (f.name.to_string(), None))
+                .collect(),
+        }
+    }
+
+    /// Whether the top-level field `name` is selected.
+    pub fn contains(&self, name: &str) -> bool {
+        self.fields.contains_key(name)
+    }
+
+    fn insert(&mut self, name: &str, nested: Option<FieldSelection>) {
+        match self.fields.get_mut(name) {
+            None => {
+                self.fields.insert(name.to_string(), nested);
+            }
+            Some(existing) => {
+                *existing = match (existing.take(), nested) {
+                    (Some(mut a), Some(b)) => {
+                        for (name, nested) in b.fields {
+                            a.insert(&name, nested);
+                        }
+                        Some(a)
+                    }
+                    _ => None,
+                };
+            }
+        }
+    }
+
+    fn project(&self, value: &Value) -> Value {
+        match value {
+            Value::Object(object) => Value::Object(
+                object
+                    .iter()
+                    .filter_map(|(key, value)| {
+                        let nested = self.fields.get(key)?;
+                        let value = match nested {
+                            Some(nested) => nested.project(value),
+                            None => value.clone(),
+                        };
+                        Some((key.clone(), value))
+                    })
+                    .collect::<Map<_, _>>(),
+            ),
+            Value::Array(items) => Value::Array(items.iter().map(|v| self.project(v)).collect()),
+            other => other.clone(),
+        }
+    }
+}
+
+impl FromStr for FieldSelection {
+    type Err = SelectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+fn syntax(message: String) -> SelectionError {
+    SelectionError {
+        kind: SelectionErrorKind::Syntax,
+        message,
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<&str>, SelectionError> {
+    let mut tokens = Vec::new();
+    let mut rest = input;
+    while let Some(c) = rest.chars().next() {
+        if c.is_whitespace() || c == ',' {
+            rest = &rest[c.len_utf8()..];
+        } else if matches!(c, '{' | '}' | '.') {
+            tokens.push(&rest[..1]);
+            rest = &rest[1..];
+        } else if c.is_ascii_alphanumeric() || c == '_' {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            tokens.push(&rest[..end]);
+            rest = &rest[end..];
+        } else {
+            return Err(syntax(format!("unexpected character '{}'", c)));
+        }
+    }
+    Ok(tokens)
+}
+
+/// Parse field names until a closing brace or the end of input.
+fn parse_fields(
+    tokens: &[&str],
+    pos: &mut usize,
+    schema: &'static [Field],
+    parent: &str,
+) -> Result<FieldSelection, SelectionError> {
+    let mut selection = FieldSelection {
+        fields: BTreeMap::new(),
+    };
+    while let Some(&token) = tokens.get(*pos) {
+        if token == "}" {
+            break;
+        }
+        let (name, nested) = parse_field(tokens, pos, schema, parent)?;
+        selection.insert(name, nested);
+    }
+    if selection.fields.is_empty() {
+        return Err(syntax(match parent {
+            "" => "no fields selected".to_string(),
+            _ => format!("empty selection under '{}'", parent),
+        }));
+    }
+    Ok(selection)
+}
+
+/// Parse one field with its optional `.child` or `{ ... }` selection.
+fn parse_field(
+    tokens: &[&str],
+    pos: &mut usize,
+    schema: &'static [Field],
+    parent: &str,
+) -> Result<(&'static str, Option<FieldSelection>), SelectionError> {
+    let token = tokens[*pos];
+    let field = schema.iter().find(|f| f.name == token).ok_or_else(|| {
+        if matches!(token, "{" | ".") {
+            return syntax(format!("expected a field name before '{}'", token));
+        }
+        let path = match parent {
+            "" => token.to_string(),
+            _ => format!("{}.{}", parent, token),
+        };
+        SelectionError {
+            kind: SelectionErrorKind::UnknownField,
+            message: format!("unknown verdict field '{}'", path),
+        }
+    })?;
+    *pos += 1;
+    let path = match parent {
+        "" => field.name.to_string(),
+        _ => format!("{}.{}", parent, field.name),
+    };
+    let nested = match tokens.get(*pos).copied() {
+        Some(".") => {
+            *pos += 1;
+            if tokens
+                .get(*pos)
+                .is_none_or(|t| matches!(*t, "{" | "}" | "."))
+            {
+                return Err(syntax(format!("expected a field name after '{}.'", path)));
+            }
+            let mut nested = FieldSelection {
+                fields: BTreeMap::new(),
+            };
+            let (name, inner) = parse_field(tokens, pos, field.children, &path)?;
+            nested.insert(name, inner);
+            Some(nested)
+        }
+        Some("{") => {
+            *pos += 1;
+            let nested = parse_fields(tokens, pos, field.children, &path)?;
+            if tokens.get(*pos) != Some(&"}") {
+                return Err(syntax(format!("unclosed '{{' after '{}'", path)));
+            }
+            *pos += 1;
+            Some(nested)
+        }
+        _ => None,
+    };
+    Ok((field.name, nested))
+}
+
+// ─── Verdict serialization ──────────────────────────────────────────────────
+
+impl AttackVerdict {
+    /// The verdict as JSON, keeping only the `fields` selected.
+    pub fn to_json_with(&self, fields: &FieldSelection) -> Value {
+        // Verdicts contain only strings, numbers, and maps with string keys,
+        // so conversion cannot fail.
+        let full = serde_json::to_value(self).unwrap_or(Value::Null);
+        fields.project(&full)
+    }
+
+    /// The selected `fields` as a single line of space-separated `key=value`
+    /// pairs (logfmt), for SIEM ingestion.
+    ///
+    /// Nested fields are flattened into dotted keys and list items are
+    /// indexed, e.g. `indicator_verdicts[0].result=matched`. Values are
+    /// quoted when they contain spaces, quotes, `=`, or control characters.
+    pub fn to_compact_line(&self, fields: &FieldSelection) -> String {
+        let mut line = String::new();
+        flatten(&self.to_json_with(fields), "", &mut line);
+        line
+    }
+}
+
+fn flatten(value: &Value, key: &str, line: &mut String) {
+    match value {
+        Value::Object(object) => {
+            for (name, value) in object {
+                let key = match key {
+                    "" => name.clone(),
+                    _ => format!("{}.{}", key, name),
+                };
+                flatten(value, &key, line);
+            }
+        }
+        Value::Array(items) => {
+            for (i, value) in items.iter().enumerate() {
+                flatten(value, &format!("{}[{}]", key, i), line);
+            }
+        }
+        Value::Null => {}
+        Value::String(s) => pair(key, s, line),
+        other => pair(key, &other.to_string(), line),
+    }
+}
+
+fn pair(key: &str, value: &str, line: &mut String) {
+    if !line.is_empty() {
+        line.push(' ');
+    }
+    let quote = value.is_empty()
+        || value
+            .chars()
+            .any(|c| c == ' ' || c == '"' || c == '=' || c.is_control());
+    if !quote {
+        let _ = write!(line, "{}={}", key, value);
+        return;
+    }
+    let _ = write!(line, "{}=\"", key);
+    for c in value.chars() {
+        match c {
+            '"' => line.push_str("\\\""),
+            '\\' => line.push_str("\\\\"),
+            '\n' => line.push_str("\\n"),
+            '\r' => line.push_str("\\r"),
+            '\t' => line.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(line, "\\u{:04x}", c as u32);
+            }
+            c => line.push(c),
+        }
+    }
+    line.push('"');
+}
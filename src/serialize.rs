@@ -1,7 +1,98 @@
 //! [`Document`] → YAML serialization.
 
 use crate::error::SerializeError;
-use crate::types::Document;
+use crate::sharing::SharingPolicy;
+use crate::types::{Document, IndicatorSet};
+use serde::Serialize;
+use serde_json::Value;
+use std::io::{BufWriter, Write};
+
+/// Layout of YAML sequences in serialized output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SequenceStyle {
+    /// Every non-empty sequence is emitted in block style (`- item`).
+    #[default]
+    Block,
+    /// Sequences containing only scalars are emitted in flow style (`[a, b]`);
+    /// sequences containing mappings or nested sequences stay in block style.
+    FlowScalars,
+}
+
+/// Quoting policy for string scalars.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum QuoteStyle {
+    /// Quote only strings that would otherwise be misread (special characters,
+    /// reserved words, numeric-looking values). Multi-line strings may use
+    /// block scalars.
+    #[default]
+    Auto,
+    /// Quote every string scalar. Single quotes are preferred; double quotes
+    /// are used when the string needs escape sequences. Disables block scalars.
+    Always,
+}
+
+/// Formatting options for [`serialize_with`].
+///
+/// The defaults reproduce the output of [`serialize`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SerializeOptions {
+    /// Spaces per nesting level. Must be at least 2: with one space, block
+    /// sequences and mappings nested in them do not read back as written.
+    pub indent: usize,
+    /// Block or flow layout for sequences.
+    pub sequence_style: SequenceStyle,
+    /// Quoting policy for string scalars.
+    pub quote_style: QuoteStyle,
+    /// Maximum line width for folded block scalars. Long strings are wrapped
+    /// at whitespace only, so a single long token may exceed this width.
+    pub line_width: usize,
+    /// Emit keys in a canonical order: document and `attack` keys in
+    /// specification order, then any other keys, and `x-*` extension keys
+    /// last in every mapping, sorted by name. Predicate keys (`match`,
+    /// `origin`, `requires`, `when`) and expression `variables` are sorted by
+    /// name. Execution state is protocol content and keeps its order. Gives
+    /// stable, diff-friendly output for documents whose extensions were added
+    /// in any order.
+    pub canonical_keys: bool,
+    /// Refuse to serialize documents this policy does not permit, for
+    /// output that will be shared. `None` serializes any document.
+    pub sharing: Option<SharingPolicy>,
+}
+
+impl SerializeOptions {
+    /// The options of [`serialize_canonical`]: the defaults with
+    /// `canonical_keys` set.
+    pub fn canonical() -> Self {
+        SerializeOptions {
+            canonical_keys: true,
+            ..SerializeOptions::default()
+        }
+    }
+}
+
+impl Default for SerializeOptions {
+    fn default() -> Self {
+        SerializeOptions {
+            indent: 2,
+            sequence_style: SequenceStyle::Block,
+            quote_style: QuoteStyle::Auto,
+            line_width: 80,
+            canonical_keys: false,
+            sharing: None,
+        }
+    }
+}
+
+/// Version of the canonical serialization format produced by
+/// [`serialize_canonical`].
+///
+/// For a normalized document, releases with the same format version produce
+/// byte-identical canonical output, so it can be hashed or signed. Any change
+/// to that output (key order, quoting, layout, or the underlying YAML
+/// emitter) increments this version and is recorded in the changelog, and
+/// never happens within a minor release. The output of [`serialize`] and
+/// [`serialize_with`] with other options is not covered.
+pub const STABLE_FORMAT_VERSION: u32 = 1;
 
 /// Serialize a Document to a YAML string.
 ///
@@ -9,15 +100,286 @@ use crate::types::Document;
 /// The `oatf` field is emitted first, followed by `$schema` (if present),
 /// then `attack` fields in specification order.
 pub fn serialize(doc: &Document) -> Result<String, SerializeError> {
-    // Convert to serde_json::Value first for consistent field ordering
-    let value = serde_json::to_value(doc).map_err(|e| SerializeError {
-        message: format!("failed to convert document to JSON value: {}", e),
-    })?;
+    serialize_with(doc, &SerializeOptions::default())
+}
 
-    // Serialize to YAML using serde-saphyr
-    let yaml = serde_saphyr::to_string(&value).map_err(|e| SerializeError {
+/// Serialize a normalized Document in the canonical format, stable for a
+/// given [`STABLE_FORMAT_VERSION`]. Use this for output that is hashed,
+/// signed, or compared byte for byte.
+///
+/// # Errors
+///
+/// Returns `SerializeError` if the document cannot be converted to YAML.
+pub fn serialize_canonical(doc: &Document) -> Result<String, SerializeError> {
+    serialize_with(doc, &SerializeOptions::canonical())
+}
+
+/// Serialize the tersest equivalent of a document: values equal to their
+/// normalization defaults, generated phase names and indicator IDs, and the
+/// canonical multi-actor form are left out (see
+/// [`minimize`](crate::normalize::minimize)). Use this for documents
+/// maintained by hand; normalizing the output gives back `normalize(doc)`.
+///
+/// # Errors
+///
+/// Returns `SerializeError` if the document cannot be converted to YAML.
+#[cfg(feature = "normalize")]
+pub fn serialize_minimal(doc: &Document) -> Result<String, SerializeError> {
+    serialize(&crate::normalize::minimize(doc.clone()))
+}
+
+/// Serialize an [`IndicatorSet`] to a YAML string, `oatf` first.
+///
+/// # Errors
+///
+/// Returns `SerializeError` if the set cannot be converted to YAML.
+pub fn serialize_indicator_set(set: &IndicatorSet) -> Result<String, SerializeError> {
+    let value = serde_json::to_value(set).map_err(|e| SerializeError {
+        message: format!("failed to convert indicator set to JSON value: {}", e),
+    })?;
+    serde_saphyr::to_string(&value).map_err(|e| SerializeError {
         message: format!("failed to serialize to YAML: {}", e),
+    })
+}
+
+/// Serialize a Document to a YAML string using the given formatting options.
+///
+/// Field ordering is identical to [`serialize`] unless
+/// `options.canonical_keys` is set; otherwise only layout, indentation, and
+/// quoting are affected.
+///
+/// # Errors
+///
+/// Returns `SerializeError` if `options.indent` is less than 2 or
+/// `options.line_width` is zero, if `options.sharing` does not permit the
+/// document, or if the document cannot be converted to YAML.
+pub fn serialize_with(
+    doc: &Document,
+    options: &SerializeOptions,
+) -> Result<String, SerializeError> {
+    check_sharing(doc, options)?;
+    let mut value = document_to_value(doc)?;
+    let saphyr_options = saphyr_options(options)?;
+    if options.canonical_keys {
+        canonicalize(&mut value, DOCUMENT_KEY_ORDER);
+    }
+
+    let styled = Styled {
+        value: &value,
+        sequence_style: options.sequence_style,
+    };
+    let yaml = serde_saphyr::to_string_with_options(&styled, saphyr_options).map_err(|e| {
+        SerializeError {
+            message: format!("failed to serialize to YAML: {}", e),
+        }
     })?;
 
     Ok(yaml)
 }
+
+/// Serialize a Document as YAML directly into `writer`.
+///
+/// Output is identical to [`serialize`], but it is emitted through a buffer
+/// in chunks as it is produced, so large documents (for example synthesized
+/// corpora embedded in state) never exist as a complete YAML string. The
+/// writer is flushed before returning.
+///
+/// # Errors
+///
+/// Returns `SerializeError` if the document cannot be converted to YAML or
+/// if writing fails.
+pub fn serialize_to_writer(doc: &Document, writer: impl Write) -> Result<(), SerializeError> {
+    serialize_to_writer_with(doc, writer, &SerializeOptions::default())
+}
+
+/// [`serialize_to_writer`] with the given formatting options.
+///
+/// # Errors
+///
+/// As [`serialize_with`], plus any error from writing.
+pub fn serialize_to_writer_with(
+    doc: &Document,
+    writer: impl Write,
+    options: &SerializeOptions,
+) -> Result<(), SerializeError> {
+    check_sharing(doc, options)?;
+    let mut value = document_to_value(doc)?;
+    let saphyr_options = saphyr_options(options)?;
+    if options.canonical_keys {
+        canonicalize(&mut value, DOCUMENT_KEY_ORDER);
+    }
+
+    let styled = Styled {
+        value: &value,
+        sequence_style: options.sequence_style,
+    };
+    let mut out = BufWriter::with_capacity(WRITE_CHUNK_BYTES, writer);
+    serde_saphyr::to_io_writer_with_options(&mut out, &styled, saphyr_options).map_err(|e| {
+        SerializeError {
+            message: format!("failed to serialize to YAML: {}", e),
+        }
+    })?;
+    out.flush().map_err(|e| SerializeError {
+        message: format!("failed to write YAML: {}", e),
+    })
+}
+
+/// Size of the buffer [`serialize_to_writer`] fills before each write.
+const WRITE_CHUNK_BYTES: usize = 64 * 1024;
+
+/// Convert to `serde_json::Value` first for consistent field ordering.
+fn document_to_value(doc: &Document) -> Result<Value, SerializeError> {
+    serde_json::to_value(doc).map_err(|e| SerializeError {
+        message: format!("failed to convert document to JSON value: {}", e),
+    })
+}
+
+/// Document keys in specification order.
+const DOCUMENT_KEY_ORDER: &[&str] = &["oatf", "$schema", "attack"];
+
+/// `attack` keys in specification order.
+const ATTACK_KEY_ORDER: &[&str] = &[
+    "id",
+    "name",
+    "version",
+    "status",
+    "created",
+    "modified",
+    "author",
+    "description",
+    "grace_period",
+    "license",
+    "distribution",
+    "severity",
+    "impact",
+    "classification",
+    "references",
+    "execution",
+    "attachments",
+    "indicators",
+    "correlation",
+    "safety",
+    "supersedes",
+    "superseded_by",
+];
+
+/// Mapping fields whose keys are sorted by name, so canonical output does
+/// not depend on authoring order (predicates) or the process (`variables`).
+const UNORDERED_MAP_KEYS: &[&str] = &["match", "origin", "requires", "when", "variables"];
+
+/// Reorder the keys of `value` (a mapping whose known keys are `order`) and
+/// of the mappings nested in it, for [`SerializeOptions::canonical_keys`].
+fn canonicalize(value: &mut Value, order: &[&str]) {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> = std::mem::take(map).into_iter().collect();
+            // Stable, so other keys keep their relative order.
+            entries.sort_by(|(a, _), (b, _)| key_rank(order, a).cmp(&key_rank(order, b)));
+            for (key, mut item) in entries {
+                match key.as_str() {
+                    "state" => {}
+                    "attack" if order == DOCUMENT_KEY_ORDER => {
+                        canonicalize(&mut item, ATTACK_KEY_ORDER)
+                    }
+                    key if UNORDERED_MAP_KEYS.contains(&key) => {
+                        if let Value::Object(entries) = &mut item {
+                            entries.sort_keys();
+                        }
+                        canonicalize(&mut item, &[])
+                    }
+                    _ => canonicalize(&mut item, &[]),
+                }
+                map.insert(key, item);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                canonicalize(item, &[]);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Sort key placing known keys in `order` first, other keys next, and
+/// extension keys last by name.
+fn key_rank<'a>(order: &[&str], key: &'a str) -> (u8, usize, &'a str) {
+    match order.iter().position(|k| *k == key) {
+        Some(i) => (0, i, ""),
+        None if key.starts_with("x-") => (2, 0, key),
+        None => (1, 0, ""),
+    }
+}
+
+fn check_sharing(doc: &Document, options: &SerializeOptions) -> Result<(), SerializeError> {
+    match &options.sharing {
+        Some(policy) => policy
+            .check(doc)
+            .map_err(|e| SerializeError { message: e.message }),
+        None => Ok(()),
+    }
+}
+
+fn saphyr_options(
+    options: &SerializeOptions,
+) -> Result<serde_saphyr::SerializerOptions, SerializeError> {
+    if options.indent < 2 {
+        return Err(SerializeError {
+            message: "indent must be at least 2".to_string(),
+        });
+    }
+    if options.line_width == 0 {
+        return Err(SerializeError {
+            message: "line_width must be at least 1".to_string(),
+        });
+    }
+    Ok(serde_saphyr::ser_options! {
+        indent_step: options.indent,
+        folded_wrap_chars: options.line_width,
+        quote_all: options.quote_style == QuoteStyle::Always,
+    })
+}
+
+/// A value wrapper that applies [`SequenceStyle`] while serializing.
+struct Styled<'a> {
+    value: &'a Value,
+    sequence_style: SequenceStyle,
+}
+
+impl Serialize for Styled<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::{SerializeMap, SerializeSeq};
+        match self.value {
+            Value::Array(items) => {
+                if self.sequence_style == SequenceStyle::FlowScalars
+                    && !items.is_empty()
+                    && items.iter().all(|v| !v.is_array() && !v.is_object())
+                {
+                    return serde_saphyr::FlowSeq(items).serialize(serializer);
+                }
+                let mut seq = serializer.serialize_seq(Some(items.len()))?;
+                for item in items {
+                    seq.serialize_element(&Styled {
+                        value: item,
+                        sequence_style: self.sequence_style,
+                    })?;
+                }
+                seq.end()
+            }
+            Value::Object(map) => {
+                let mut out = serializer.serialize_map(Some(map.len()))?;
+                for (k, v) in map {
+                    out.serialize_entry(
+                        k,
+                        &Styled {
+                            value: v,
+                            sequence_style: self.sequence_style,
+                        },
+                    )?;
+                }
+                out.end()
+            }
+            scalar => scalar.serialize(serializer),
+        }
+    }
+}
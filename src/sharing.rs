@@ -0,0 +1,134 @@
+//! Traffic Light Protocol (TLP) enforcement for sharing documents.
+//!
+//! An attack's `distribution` marks who its document may be shared with. A
+//! [`SharingPolicy`] names the most restricted marking a sharing channel
+//! accepts, and the sharing-oriented APIs refuse documents marked above it:
+//!
+//! - [`SerializeOptions::sharing`](crate::serialize::SerializeOptions::sharing)
+//!   for YAML output,
+//! - `Pack::with_sharing` (`bundle` feature) for packs,
+//! - `distribution::push_with_policy` (`distribution` feature) for OCI
+//!   registries.
+//!
+//! `Catalog::shareable` filters a catalog by the same policy.
+
+use crate::enums::Tlp;
+use crate::error::{SharingError, SharingErrorKind};
+use crate::types::Document;
+use serde::Deserialize;
+
+/// The most restricted TLP marking a sharing channel accepts.
+///
+/// ```rust
+/// use oatf::enums::Tlp;
+/// use oatf::sharing::SharingPolicy;
+///
+/// let policy = SharingPolicy::new(Tlp::Green).unmarked_as(Tlp::Amber);
+/// assert!(policy.permits_marking(Some(Tlp::Clear)));
+/// assert!(!policy.permits_marking(Some(Tlp::Red)));
+/// assert!(!policy.permits_marking(None));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SharingPolicy {
+    /// Most restricted marking that may be shared.
+    pub limit: Tlp,
+    /// Marking assumed for documents without a `distribution`.
+    pub unmarked: Tlp,
+}
+
+impl SharingPolicy {
+    /// Share documents marked `limit` or less restricted. Documents without
+    /// a `distribution` count as TLP:CLEAR.
+    pub fn new(limit: Tlp) -> Self {
+        SharingPolicy {
+            limit,
+            unmarked: Tlp::Clear,
+        }
+    }
+
+    /// Treat documents without a `distribution` as marked `marking`.
+    pub fn unmarked_as(mut self, marking: Tlp) -> Self {
+        self.unmarked = marking;
+        self
+    }
+
+    /// Whether a document with the given `distribution` may be shared.
+    pub fn permits_marking(&self, distribution: Option<Tlp>) -> bool {
+        distribution.unwrap_or(self.unmarked) <= self.limit
+    }
+
+    /// Whether `doc` may be shared.
+    pub fn permits(&self, doc: &Document) -> bool {
+        self.permits_marking(doc.attack.distribution)
+    }
+
+    /// Check that `doc` may be shared.
+    ///
+    /// # Errors
+    ///
+    /// Returns `restricted` if the document is marked above the limit.
+    pub fn check(&self, doc: &Document) -> Result<(), SharingError> {
+        self.check_marking(doc.attack.id.as_deref(), doc.attack.distribution)
+    }
+
+    /// Check that a document's source (YAML or JSON) may be shared, reading
+    /// only its `attack.id` and `attack.distribution`. Sources without an
+    /// `attack`, such as indicator sets, count as unmarked.
+    ///
+    /// # Errors
+    ///
+    /// Returns `unreadable` if the source is not a YAML mapping or its
+    /// marking is not a TLP level, and `restricted` if it is marked above the
+    /// limit.
+    pub fn check_source(&self, content: &[u8]) -> Result<(), SharingError> {
+        #[derive(Deserialize)]
+        struct Source {
+            attack: Option<Marking>,
+        }
+        #[derive(Deserialize)]
+        struct Marking {
+            id: Option<String>,
+            distribution: Option<Tlp>,
+        }
+
+        let unreadable = |message: String| SharingError {
+            kind: SharingErrorKind::Unreadable,
+            message: format!("cannot read the document's marking: {}", message),
+        };
+        let input = std::str::from_utf8(content).map_err(|e| unreadable(e.to_string()))?;
+        let value = crate::parse::parse_yaml_value(input).map_err(|e| unreadable(e.message))?;
+        let source: Source =
+            serde_json::from_value(value).map_err(|e| unreadable(e.to_string()))?;
+        match source.attack {
+            Some(attack) => self.check_marking(attack.id.as_deref(), attack.distribution),
+            None => self.check_marking(None, None),
+        }
+    }
+
+    fn check_marking(
+        &self,
+        id: Option<&str>,
+        distribution: Option<Tlp>,
+    ) -> Result<(), SharingError> {
+        if self.permits_marking(distribution) {
+            return Ok(());
+        }
+        let marking = distribution.unwrap_or(self.unmarked);
+        let subject = match id {
+            Some(id) => format!("attack '{}'", id),
+            None => "document".to_string(),
+        };
+        let how = if distribution.is_some() {
+            "is marked"
+        } else {
+            "is unmarked, treated as"
+        };
+        Err(SharingError {
+            kind: SharingErrorKind::Restricted,
+            message: format!(
+                "{} {} {}, above the sharing limit {}",
+                subject, how, marking, self.limit
+            ),
+        })
+    }
+}
@@ -0,0 +1,452 @@
+//! Publishing attack verdicts out of the process.
+//!
+//! A [`VerdictSink`] delivers [`AttackVerdict`]s to a destination:
+//!
+//! - [`Stdout`] writes one JSON line per verdict to stdout or any writer,
+//!   optionally only [selected fields](crate::select) or as compact
+//!   `key=value` lines;
+//! - [`Kafka`] sends JSON records keyed by attack ID through a
+//!   caller-supplied [`KafkaProducer`], so any Kafka client can be plugged in;
+//! - `Webhook` POSTs JSON to an HTTP endpoint (requires the `webhook`
+//!   feature).
+//!
+//! Delivery policies wrap any sink: [`Retrying`] retries `unavailable`
+//! failures with exponential backoff, and [`Buffered`] moves delivery to a
+//! worker thread behind a bounded queue, so a slow destination blocks
+//! publishers (or fails [`Buffered::try_publish`] with `full`) instead of
+//! growing memory without limit.
+
+use crate::error::{SinkError, SinkErrorKind};
+use crate::select::FieldSelection;
+use crate::types::AttackVerdict;
+use std::io::Write;
+use std::sync::mpsc::{Receiver, SyncSender, TrySendError, sync_channel};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// A destination for attack verdicts.
+pub trait VerdictSink {
+    /// Deliver one verdict.
+    fn publish(&mut self, verdict: &AttackVerdict) -> Result<(), SinkError>;
+
+    /// Deliver anything the sink has buffered. The default does nothing.
+    fn flush(&mut self) -> Result<(), SinkError> {
+        Ok(())
+    }
+}
+
+impl<S: VerdictSink + ?Sized> VerdictSink for Box<S> {
+    fn publish(&mut self, verdict: &AttackVerdict) -> Result<(), SinkError> {
+        (**self).publish(verdict)
+    }
+
+    fn flush(&mut self) -> Result<(), SinkError> {
+        (**self).flush()
+    }
+}
+
+fn encode(verdict: &AttackVerdict) -> Result<Vec<u8>, SinkError> {
+    serde_json::to_vec(verdict).map_err(|e| SinkError {
+        kind: SinkErrorKind::Rejected,
+        message: format!("failed to encode verdict: {}", e),
+    })
+}
+
+// ─── Stdout ─────────────────────────────────────────────────────────────────
+
+/// Writes each verdict as a line of JSON.
+pub struct Stdout<W: Write = std::io::Stdout> {
+    writer: W,
+    fields: Option<FieldSelection>,
+    compact: bool,
+}
+
+impl Stdout {
+    /// A sink writing to the process's standard output.
+    pub fn new() -> Self {
+        Self::to_writer(std::io::stdout())
+    }
+}
+
+impl Default for Stdout {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<W: Write> Stdout<W> {
+    /// A sink writing JSON lines to `writer`.
+    pub fn to_writer(writer: W) -> Self {
+        Stdout {
+            writer,
+            fields: None,
+            compact: false,
+        }
+    }
+
+    /// Write only the selected fields of each verdict.
+    pub fn select(mut self, fields: FieldSelection) -> Self {
+        self.fields = Some(fields);
+        self
+    }
+
+    /// Write compact `key=value` lines
+    /// ([`AttackVerdict::to_compact_line`]) instead of JSON.
+    pub fn compact(mut self) -> Self {
+        self.compact = true;
+        self
+    }
+
+    /// Consume the sink and return the writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> VerdictSink for Stdout<W> {
+    fn publish(&mut self, verdict: &AttackVerdict) -> Result<(), SinkError> {
+        let mut line = match (&self.fields, self.compact) {
+            (None, false) => encode(verdict)?,
+            (Some(fields), false) => verdict.to_json_with(fields).to_string().into_bytes(),
+            (fields, true) => {
+                let all;
+                let fields = match fields {
+                    Some(fields) => fields,
+                    None => {
+                        all = FieldSelection::all();
+                        &all
+                    }
+                };
+                verdict.to_compact_line(fields).into_bytes()
+            }
+        };
+        line.push(b'\n');
+        self.writer.write_all(&line).map_err(io_error)?;
+        self.writer.flush().map_err(io_error)
+    }
+
+    fn flush(&mut self) -> Result<(), SinkError> {
+        self.writer.flush().map_err(io_error)
+    }
+}
+
+fn io_error(e: std::io::Error) -> SinkError {
+    SinkError {
+        kind: SinkErrorKind::Unavailable,
+        message: format!("failed to write verdict: {}", e),
+    }
+}
+
+// ─── Kafka ──────────────────────────────────────────────────────────────────
+
+/// The producer side of a Kafka client. Implementations report transient
+/// broker failures as `unavailable` so [`Retrying`] can retry them.
+pub trait KafkaProducer {
+    /// Send one record.
+    fn send(&mut self, topic: &str, key: Option<&str>, payload: &[u8]) -> Result<(), SinkError>;
+
+    /// Wait for outstanding records to be acknowledged.
+    fn flush(&mut self) -> Result<(), SinkError> {
+        Ok(())
+    }
+}
+
+/// Sends each verdict as a JSON record to a Kafka topic, keyed by attack ID
+/// so verdicts for one attack stay in order within a partition.
+pub struct Kafka<P: KafkaProducer> {
+    producer: P,
+    topic: String,
+}
+
+impl<P: KafkaProducer> Kafka<P> {
+    /// A sink sending to `topic` through `producer`.
+    pub fn new(producer: P, topic: impl Into<String>) -> Self {
+        Kafka {
+            producer,
+            topic: topic.into(),
+        }
+    }
+
+    /// Consume the sink and return the producer.
+    pub fn into_inner(self) -> P {
+        self.producer
+    }
+}
+
+impl<P: KafkaProducer> VerdictSink for Kafka<P> {
+    fn publish(&mut self, verdict: &AttackVerdict) -> Result<(), SinkError> {
+        let payload = encode(verdict)?;
+        self.producer
+            .send(&self.topic, verdict.attack_id.as_deref(), &payload)
+    }
+
+    fn flush(&mut self) -> Result<(), SinkError> {
+        self.producer.flush()
+    }
+}
+
+// ─── Webhook ────────────────────────────────────────────────────────────────
+
+/// POSTs each verdict as JSON to an HTTP endpoint.
+///
+/// Connection failures, timeouts, `429`, and `5xx` responses are
+/// `unavailable`; other non-success statuses are `rejected`.
+#[cfg(feature = "webhook")]
+pub struct Webhook {
+    agent: ureq::Agent,
+    url: String,
+    headers: Vec<(String, String)>,
+}
+
+#[cfg(feature = "webhook")]
+impl Webhook {
+    /// A webhook posting to `url` with a 10 second timeout.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::with_timeout(url, Duration::from_secs(10))
+    }
+
+    /// A webhook posting to `url` with the given request timeout.
+    pub fn with_timeout(url: impl Into<String>, timeout: Duration) -> Self {
+        let config = ureq::Agent::config_builder()
+            .timeout_global(Some(timeout))
+            .http_status_as_error(false)
+            .build();
+        Webhook {
+            agent: ureq::Agent::new_with_config(config),
+            url: url.into(),
+            headers: Vec::new(),
+        }
+    }
+
+    /// Add a header sent with every request, e.g. `Authorization`.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+}
+
+#[cfg(feature = "webhook")]
+impl VerdictSink for Webhook {
+    fn publish(&mut self, verdict: &AttackVerdict) -> Result<(), SinkError> {
+        let body = encode(verdict)?;
+        let mut request = self.agent.post(&self.url).content_type("application/json");
+        for (name, value) in &self.headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        let response = request.send(&body[..]).map_err(|e| SinkError {
+            kind: SinkErrorKind::Unavailable,
+            message: format!("webhook request failed: {}", e),
+        })?;
+        let status = response.status().as_u16();
+        match status {
+            200..=299 => Ok(()),
+            429 | 500..=599 => Err(SinkError {
+                kind: SinkErrorKind::Unavailable,
+                message: format!("webhook returned {}", status),
+            }),
+            _ => Err(SinkError {
+                kind: SinkErrorKind::Rejected,
+                message: format!("webhook returned {}", status),
+            }),
+        }
+    }
+}
+
+// ─── Retrying ───────────────────────────────────────────────────────────────
+
+/// Retries `unavailable` failures of the inner sink with exponential backoff.
+/// Other failures are returned immediately.
+pub struct Retrying<S: VerdictSink> {
+    inner: S,
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl<S: VerdictSink> Retrying<S> {
+    /// Retry up to 5 attempts in total, backing off from 100ms up to 5s.
+    pub fn new(inner: S) -> Self {
+        Retrying {
+            inner,
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+
+    /// Set the total number of attempts per publish (at least 1).
+    pub fn max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+
+    /// Set the first backoff delay and its cap; each retry doubles the delay.
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Consume the wrapper and return the inner sink.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    fn retry(
+        &mut self,
+        mut op: impl FnMut(&mut S) -> Result<(), SinkError>,
+    ) -> Result<(), SinkError> {
+        let mut delay = self.initial_backoff;
+        let mut attempt = 1;
+        loop {
+            match op(&mut self.inner) {
+                Err(e) if e.kind == SinkErrorKind::Unavailable && attempt < self.max_attempts => {
+                    std::thread::sleep(delay);
+                    delay = (delay * 2).min(self.max_backoff);
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl<S: VerdictSink> VerdictSink for Retrying<S> {
+    fn publish(&mut self, verdict: &AttackVerdict) -> Result<(), SinkError> {
+        self.retry(|inner| inner.publish(verdict))
+    }
+
+    fn flush(&mut self) -> Result<(), SinkError> {
+        self.retry(|inner| inner.flush())
+    }
+}
+
+// ─── Buffered ───────────────────────────────────────────────────────────────
+
+enum Message {
+    Verdict(Box<AttackVerdict>),
+    Flush(SyncSender<()>),
+}
+
+/// Delivers verdicts to the inner sink on a worker thread through a bounded
+/// queue.
+///
+/// [`publish`](VerdictSink::publish) blocks while the queue is full;
+/// [`try_publish`](Buffered::try_publish) fails with `full` instead.
+/// Delivery failures on the worker are not returned by `publish`; they are
+/// counted and the most recent one is reported by the next
+/// [`flush`](VerdictSink::flush). Dropping the sink delivers what is queued
+/// and stops the worker.
+pub struct Buffered {
+    sender: Option<SyncSender<Message>>,
+    worker: Option<JoinHandle<()>>,
+    failures: Arc<Mutex<(u64, Option<SinkError>)>>,
+}
+
+impl Buffered {
+    /// Start a worker delivering to `inner` with room for `capacity` queued
+    /// verdicts (at least 1).
+    pub fn new(inner: impl VerdictSink + Send + 'static, capacity: usize) -> Self {
+        let (sender, receiver) = sync_channel(capacity.max(1));
+        let failures = Arc::new(Mutex::new((0, None)));
+        let worker_failures = Arc::clone(&failures);
+        let worker = std::thread::spawn(move || deliver(inner, receiver, worker_failures));
+        Buffered {
+            sender: Some(sender),
+            worker: Some(worker),
+            failures,
+        }
+    }
+
+    /// Queue `verdict` without blocking.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SinkError` of kind `full` when the queue is full and `closed`
+    /// if the worker has stopped.
+    pub fn try_publish(&mut self, verdict: &AttackVerdict) -> Result<(), SinkError> {
+        let message = Message::Verdict(Box::new(verdict.clone()));
+        match self.sender()?.try_send(message) {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Full(_)) => Err(SinkError {
+                kind: SinkErrorKind::Full,
+                message: "verdict queue is full".to_string(),
+            }),
+            Err(TrySendError::Disconnected(_)) => Err(closed()),
+        }
+    }
+
+    /// Number of verdicts the worker has failed to deliver so far.
+    pub fn failed(&self) -> u64 {
+        self.failures.lock().map(|f| f.0).unwrap_or(0)
+    }
+
+    fn sender(&self) -> Result<&SyncSender<Message>, SinkError> {
+        self.sender.as_ref().ok_or_else(closed)
+    }
+}
+
+fn closed() -> SinkError {
+    SinkError {
+        kind: SinkErrorKind::Closed,
+        message: "verdict sink worker has stopped".to_string(),
+    }
+}
+
+fn deliver(
+    mut inner: impl VerdictSink,
+    receiver: Receiver<Message>,
+    failures: Arc<Mutex<(u64, Option<SinkError>)>>,
+) {
+    let record = |result: Result<(), SinkError>| {
+        if let Err(e) = result
+            && let Ok(mut failures) = failures.lock()
+        {
+            failures.0 += 1;
+            failures.1 = Some(e);
+        }
+    };
+    for message in receiver {
+        match message {
+            Message::Verdict(verdict) => record(inner.publish(&verdict)),
+            Message::Flush(done) => {
+                record(inner.flush());
+                let _ = done.send(());
+            }
+        }
+    }
+    record(inner.flush());
+}
+
+impl VerdictSink for Buffered {
+    fn publish(&mut self, verdict: &AttackVerdict) -> Result<(), SinkError> {
+        self.sender()?
+            .send(Message::Verdict(Box::new(verdict.clone())))
+            .map_err(|_| closed())
+    }
+
+    /// Wait until every queued verdict has been delivered, then report the
+    /// most recent delivery failure since the last flush, if any.
+    fn flush(&mut self) -> Result<(), SinkError> {
+        let (done, wait) = sync_channel(1);
+        self.sender()?
+            .send(Message::Flush(done))
+            .map_err(|_| closed())?;
+        wait.recv().map_err(|_| closed())?;
+        let last = self.failures.lock().ok().and_then(|mut f| f.1.take());
+        match last {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for Buffered {
+    fn drop(&mut self) {
+        self.sender.take();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
@@ -0,0 +1,590 @@
+//! Runtime access to the specification metadata embedded in this crate.
+//!
+//! Tools that display "validated against OATF 0.1, registry snapshot …" can
+//! query [`spec_info`] instead of hard-coding what this build understands.
+
+use crate::enums::OriginRole;
+use crate::event_registry::EVENT_MODE_REGISTRY;
+use crate::surface::{KNOWN_MODES, KNOWN_PROTOCOL_VERSIONS, KNOWN_PROTOCOLS, SURFACE_REGISTRY};
+use serde::Serialize;
+
+/// OATF format version implemented by this crate (the accepted `oatf` value).
+pub const SPEC_VERSION: &str = "0.1";
+
+/// A conformance rule with the specification section that defines it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub struct RuleInfo {
+    /// Rule identifier (e.g., `"V-001"`).
+    pub id: &'static str,
+    /// Specification section reference (e.g., `"§11.1.1"`).
+    pub spec_ref: &'static str,
+    /// One-line summary of what the rule requires.
+    pub description: &'static str,
+}
+
+/// The conformance rules checked during parse and validation, in ID order.
+pub static RULES: &[RuleInfo] = &[
+    rule(
+        "V-001",
+        "§11.1.1",
+        "oatf must be a supported format version",
+    ),
+    rule("V-002", "§11.1.2", "Document must be well-formed YAML"),
+    rule("V-003", "§11.1.3", "attack must be present"),
+    rule("V-004", "§11.1.4", "Required fields must be present"),
+    rule(
+        "V-005",
+        "§11.1.5",
+        "Enumerated fields must use known values",
+    ),
+    rule(
+        "V-006",
+        "§11.1.6",
+        "indicators, when present, must not be empty",
+    ),
+    rule(
+        "V-007",
+        "§11.1.8",
+        "phases, when present, must not be empty",
+    ),
+    rule("V-008", "§11.1.8", "Only the last phase may omit a trigger"),
+    rule("V-009", "§11.1.8", "The first phase must define state"),
+    rule("V-010", "§11.1.10", "Indicator IDs must be unique"),
+    rule(
+        "V-011",
+        "§11.1.8",
+        "Phase names must be unique within an actor",
+    ),
+    rule(
+        "V-012",
+        "§11.1.11",
+        "Indicators must have exactly one detection key",
+    ),
+    rule("V-013", "§5.7", "Regular expressions must compile"),
+    rule("V-014", "§5.7", "CEL expressions must parse"),
+    rule("V-015", "§5.7", "JSONPath selectors must parse"),
+    rule("V-016", "§5.7", "Template expressions must be well-formed"),
+    rule("V-017", "§4.3", "Severity confidence must be within 0–100"),
+    rule(
+        "V-018",
+        "§7",
+        "Indicator surfaces must belong to the indicator's protocol",
+    ),
+    rule("V-019", "§5.3", "Trigger count and match require an event"),
+    rule(
+        "V-020",
+        "§11.1.1",
+        "YAML anchors, aliases, and merge keys are not allowed",
+    ),
+    rule(
+        "V-021",
+        "§6.2",
+        "Pattern targets must be valid target paths",
+    ),
+    rule(
+        "V-022",
+        "§6.4",
+        "Semantic thresholds must be within 0.0–1.0",
+    ),
+    rule("V-023", "§4.2", "Attack IDs must follow the ID format"),
+    rule(
+        "V-024",
+        "§6.1",
+        "Indicator IDs must follow the ID format and attack prefix",
+    ),
+    rule("V-025", "§6.1", "Indicator confidence must be within 0–100"),
+    rule("V-026", "§6.3", "Expression variables must be valid paths"),
+    rule("V-027", "§5.4", "Trigger match keys must be valid paths"),
+    rule(
+        "V-028",
+        "§5.1",
+        "Phase modes and indicator protocols must be resolvable",
+    ),
+    rule(
+        "V-029",
+        "§7",
+        "Trigger events must be valid for the phase's mode",
+    ),
+    rule("V-030", "§5.1", "Execution forms are mutually exclusive"),
+    rule(
+        "V-031",
+        "§5.1",
+        "Actor names must be unique and well-formed",
+    ),
+    rule(
+        "V-032",
+        "§5.5",
+        "Cross-actor references must name known actors",
+    ),
+    rule(
+        "V-033",
+        "§11.1.14",
+        "Static content and synthesize are mutually exclusive",
+    ),
+    rule(
+        "V-034",
+        "§11.1.15",
+        "At most one response entry may be a catch-all",
+    ),
+    rule("V-035", "§11.1.16", "synthesize.prompt must not be empty"),
+    rule(
+        "V-036",
+        "§5.1",
+        "Modes must follow the <protocol>_<role> pattern",
+    ),
+    rule("V-037", "§4.2", "attack.version must be positive"),
+    rule("V-038", "§5.3", "trigger.after must be a valid duration"),
+    rule("V-039", "§5.5", "Extractor names must be snake_case"),
+    rule(
+        "V-040",
+        "§11.1.8",
+        "extractors, when present, must not be empty",
+    ),
+    rule(
+        "V-041",
+        "§11.1.17",
+        "Expression variable names must be valid identifiers",
+    ),
+    rule("V-042", "§5.2", "Triggers must have an event or after"),
+    rule(
+        "V-043",
+        "§5.2",
+        "Binding-specific actions must have exactly one key",
+    ),
+    rule(
+        "V-044",
+        "§5.5",
+        "Regex extractors must have a capture group",
+    ),
+    rule("V-045", "§5.2", "on_enter, when present, must not be empty"),
+    rule("V-046", "§5.1", "Pinned protocol versions must be known"),
+    rule(
+        "V-047",
+        "§5.3",
+        "Pacing delay and jitter must be valid durations",
+    ),
+    rule("V-048", "§4.2", "Safety limits must be well-formed"),
+    rule(
+        "V-049",
+        "§4.2",
+        "Lifecycle references must be valid attack IDs other than the attack's own",
+    ),
+    rule(
+        "V-050",
+        "§6.1",
+        "False positive entries must have a description and valid conditions",
+    ),
+    rule(
+        "V-051",
+        "§6.1",
+        "Stages must be snake_case, and correlated stages must have indicators",
+    ),
+    rule(
+        "V-052",
+        "§4.2",
+        "Documents must carry the content their profile requires",
+    ),
+    rule(
+        "V-053",
+        "§6.5",
+        "Frequency indicators must have a positive window and threshold",
+    ),
+    rule(
+        "V-054",
+        "§6.1",
+        "Captures must be valid, and correlation keys must name captured values",
+    ),
+    rule(
+        "V-055",
+        "§5.3",
+        "Origin predicates must name known origin fields and a role that can send the surface",
+    ),
+    rule(
+        "V-056",
+        "§5.3",
+        "Trigger match templates must reference values extracted by earlier phases",
+    ),
+    rule(
+        "V-057",
+        "§5.2",
+        "on_timeout, when present, must not be empty and requires trigger.after",
+    ),
+    rule(
+        "V-058",
+        "§4.2",
+        "Attachments must be uniquely named, decodable within the size limit, and referenced by declared name",
+    ),
+    rule(
+        "V-059",
+        "§11.1.8",
+        "Execution state must stay within the configured resource limits",
+    ),
+    rule(
+        "V-060",
+        "§2.6a",
+        "Actor endpoints must set exactly one of an http, https, ws, or wss url and a stdio command",
+    ),
+    rule(
+        "V-061",
+        "§5.2",
+        "Phase requires must name values extracted by earlier phases or by other actors",
+    ),
+    rule(
+        "V-062",
+        "§4.2",
+        "License must be a well-formed SPDX license expression",
+    ),
+];
+
+const fn rule(id: &'static str, spec_ref: &'static str, description: &'static str) -> RuleInfo {
+    RuleInfo {
+        id,
+        spec_ref,
+        description,
+    }
+}
+
+/// Look up a conformance rule by ID.
+pub fn rule_info(id: &str) -> Option<&'static RuleInfo> {
+    RULES.iter().find(|r| r.id == id)
+}
+
+/// A protocol binding and the revisions a mode may pin.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub struct ProtocolInfo {
+    /// Protocol identifier (e.g., `"mcp"`).
+    pub name: &'static str,
+    /// Known modes of this protocol.
+    pub modes: Vec<&'static str>,
+    /// Known revisions for `@version` pins; empty if any version is accepted.
+    pub versions: Vec<&'static str>,
+}
+
+/// An entry of the event-mode validity registry.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub struct EventInfo {
+    /// Base event name (e.g., `"tools/call"`).
+    pub name: &'static str,
+    /// Modes for which the event is valid.
+    pub modes: Vec<&'static str>,
+}
+
+/// An entry of the surface registry.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub struct SurfaceInfo {
+    /// Surface name (e.g., `"tool_description"`).
+    pub name: &'static str,
+    /// Protocol the surface belongs to.
+    pub protocol: &'static str,
+    /// Default pattern target for the surface.
+    pub default_target: &'static str,
+    /// Party that produces the surface's content; absent if either may.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin: Option<OriginRole>,
+}
+
+/// Specification metadata embedded in this build.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub struct SpecInfo {
+    /// OATF format version ([`SPEC_VERSION`]).
+    pub spec_version: &'static str,
+    /// Version of this crate.
+    pub crate_version: &'static str,
+    /// Fingerprint of the embedded registries and rules. It changes whenever
+    /// any of them changes, so two builds with the same snapshot accept the
+    /// same modes, events, surfaces, and rules.
+    pub registry_snapshot: String,
+    /// Known protocols.
+    pub protocols: Vec<ProtocolInfo>,
+    /// Known modes.
+    pub modes: Vec<&'static str>,
+    /// Event-mode validity registry.
+    pub events: Vec<EventInfo>,
+    /// Surface registry.
+    pub surfaces: Vec<SurfaceInfo>,
+    /// Conformance rules.
+    pub rules: Vec<RuleInfo>,
+}
+
+/// Return the specification metadata embedded in this build.
+pub fn spec_info() -> SpecInfo {
+    let protocols = KNOWN_PROTOCOLS
+        .iter()
+        .map(|&name| ProtocolInfo {
+            name,
+            modes: KNOWN_MODES
+                .iter()
+                .copied()
+                .filter(|m| {
+                    m.strip_prefix(name)
+                        .is_some_and(|rest| rest == "_server" || rest == "_client")
+                })
+                .collect(),
+            versions: KNOWN_PROTOCOL_VERSIONS
+                .iter()
+                .find(|(p, _)| *p == name)
+                .map(|(_, versions)| versions.to_vec())
+                .unwrap_or_default(),
+        })
+        .collect();
+    let events = EVENT_MODE_REGISTRY
+        .iter()
+        .map(|e| EventInfo {
+            name: e.event,
+            modes: e.valid_modes.to_vec(),
+        })
+        .collect();
+    let surfaces = SURFACE_REGISTRY
+        .iter()
+        .map(|s| SurfaceInfo {
+            name: s.surface,
+            protocol: s.protocol,
+            default_target: s.default_target,
+            origin: s.origin,
+        })
+        .collect();
+
+    let mut info = SpecInfo {
+        spec_version: SPEC_VERSION,
+        crate_version: env!("CARGO_PKG_VERSION"),
+        registry_snapshot: String::new(),
+        protocols,
+        modes: KNOWN_MODES.to_vec(),
+        events,
+        surfaces,
+        rules: RULES.to_vec(),
+    };
+    info.registry_snapshot = fingerprint(&info);
+    info
+}
+
+/// 64-bit FNV-1a over the registries and rules, as 16 hex digits.
+fn fingerprint(info: &SpecInfo) -> String {
+    let content = serde_json::to_string(&(
+        info.spec_version,
+        &info.protocols,
+        &info.events,
+        &info.surfaces,
+        &info.rules,
+    ))
+    .expect("spec metadata serializes");
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in content.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
+
+/// A difference between a registry compiled into this build and the
+/// specification's copy of it.
+#[cfg(feature = "registry-verify")]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub struct RegistryDrift {
+    /// Registry name: `"modes"`, `"events"`, or `"surfaces"`.
+    pub registry: &'static str,
+    /// The mode, event, or surface that differs.
+    pub entry: String,
+    /// Human-readable description of the difference.
+    pub message: String,
+}
+
+/// Result of [`verify_registries`].
+#[cfg(feature = "registry-verify")]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub struct RegistryReport {
+    /// Every difference found, grouped by registry.
+    pub drift: Vec<RegistryDrift>,
+}
+
+#[cfg(feature = "registry-verify")]
+impl RegistryReport {
+    /// True when the build's registries match the specification's.
+    pub fn is_consistent(&self) -> bool {
+        self.drift.is_empty()
+    }
+}
+
+/// Cross-check the mode, event-mode validity, and surface registries
+/// compiled into this build against the YAML registries of a specification
+/// checkout (such as the `spec` submodule), to detect drift between the
+/// crate and the specification a consumer vendors.
+///
+/// Reads `registries/modes.yaml`, `registries/events.yaml`, and
+/// `registries/surfaces.yaml` under `spec_dir`. Each holds a list, either at
+/// the top level or as the only value of a mapping. Modes are strings or
+/// objects with a `mode` (or `name`); events are objects with an `event` (or
+/// `name`) and `modes` (or `valid_modes`); surfaces are objects with a
+/// `surface` (or `name`), `protocol`, `default_target`, and optional
+/// `origin`. Entry order does not matter.
+///
+/// # Errors
+///
+/// Returns a [`RegistryError`](crate::error::RegistryError) if a file cannot
+/// be read or is not in this shape. Differences are not errors; they are
+/// reported in the [`RegistryReport`].
+#[cfg(feature = "registry-verify")]
+pub fn verify_registries(
+    spec_dir: impl AsRef<std::path::Path>,
+) -> Result<RegistryReport, crate::error::RegistryError> {
+    let dir = spec_dir.as_ref().join("registries");
+    let info = spec_info();
+    let mut drift = Vec::new();
+
+    let modes = registry_entries(&dir.join("modes.yaml"))?
+        .iter()
+        .map(|entry| match entry {
+            serde_json::Value::String(mode) => Ok(mode.clone()),
+            _ => required_str(entry, &["mode", "name"], "modes.yaml"),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let built: Vec<String> = info.modes.iter().map(|m| m.to_string()).collect();
+    compare_sets("modes", &built, &modes, &mut drift);
+
+    let mut events = std::collections::BTreeMap::new();
+    for entry in registry_entries(&dir.join("events.yaml"))? {
+        let name = required_str(&entry, &["event", "name"], "events.yaml")?;
+        let modes = ["modes", "valid_modes"]
+            .iter()
+            .find_map(|key| entry.get(*key))
+            .and_then(|v| serde_json::from_value::<Vec<String>>(v.clone()).ok())
+            .ok_or_else(|| registry_parse_error("events.yaml", &name, "modes"))?;
+        events.insert(name, modes);
+    }
+    let built: Vec<String> = info.events.iter().map(|e| e.name.to_string()).collect();
+    let spec: Vec<String> = events.keys().cloned().collect();
+    compare_sets("events", &built, &spec, &mut drift);
+    for event in &info.events {
+        if let Some(spec_modes) = events.get(event.name) {
+            let mut built: Vec<&str> = event.modes.clone();
+            let mut spec: Vec<&str> = spec_modes.iter().map(String::as_str).collect();
+            built.sort_unstable();
+            spec.sort_unstable();
+            if built != spec {
+                drift.push(RegistryDrift {
+                    registry: "events",
+                    entry: event.name.to_string(),
+                    message: format!(
+                        "valid modes differ: build [{}], specification [{}]",
+                        built.join(", "),
+                        spec.join(", ")
+                    ),
+                });
+            }
+        }
+    }
+
+    let mut surfaces = std::collections::BTreeMap::new();
+    for entry in registry_entries(&dir.join("surfaces.yaml"))? {
+        let name = required_str(&entry, &["surface", "name"], "surfaces.yaml")?;
+        surfaces.insert(name, entry);
+    }
+    let built: Vec<String> = info.surfaces.iter().map(|s| s.name.to_string()).collect();
+    let spec: Vec<String> = surfaces.keys().cloned().collect();
+    compare_sets("surfaces", &built, &spec, &mut drift);
+    for surface in &info.surfaces {
+        let Some(entry) = surfaces.get(surface.name) else {
+            continue;
+        };
+        let origin = surface.origin.map(|o| o.as_str());
+        let fields = [
+            ("protocol", Some(surface.protocol)),
+            ("default_target", Some(surface.default_target)),
+            ("origin", origin),
+        ];
+        for (field, built) in fields {
+            let spec = entry.get(field).and_then(|v| v.as_str());
+            if (field != "origin" || spec.is_some()) && spec != built {
+                drift.push(RegistryDrift {
+                    registry: "surfaces",
+                    entry: surface.name.to_string(),
+                    message: format!(
+                        "{} differs: build '{}', specification '{}'",
+                        field,
+                        built.unwrap_or_default(),
+                        spec.unwrap_or_default()
+                    ),
+                });
+            }
+        }
+    }
+
+    Ok(RegistryReport { drift })
+}
+
+/// The entries of a registry file.
+#[cfg(feature = "registry-verify")]
+fn registry_entries(
+    path: &std::path::Path,
+) -> Result<Vec<serde_json::Value>, crate::error::RegistryError> {
+    use crate::error::{RegistryError, RegistryErrorKind};
+    let text = std::fs::read_to_string(path).map_err(|e| RegistryError {
+        kind: RegistryErrorKind::Io,
+        message: format!("failed to read {}: {}", path.display(), e),
+    })?;
+    let parse_error = |message: String| RegistryError {
+        kind: RegistryErrorKind::Parse,
+        message: format!("{}: {}", path.display(), message),
+    };
+    let value: serde_json::Value =
+        serde_saphyr::from_str(&text).map_err(|e| parse_error(e.to_string()))?;
+    match value {
+        serde_json::Value::Array(entries) => Ok(entries),
+        serde_json::Value::Object(map) if map.len() == 1 => match map.into_iter().next() {
+            Some((_, serde_json::Value::Array(entries))) => Ok(entries),
+            _ => Err(parse_error("expected a list of entries".to_string())),
+        },
+        _ => Err(parse_error("expected a list of entries".to_string())),
+    }
+}
+
+#[cfg(feature = "registry-verify")]
+fn required_str(
+    entry: &serde_json::Value,
+    keys: &[&str],
+    file: &str,
+) -> Result<String, crate::error::RegistryError> {
+    keys.iter()
+        .find_map(|key| entry.get(*key).and_then(|v| v.as_str()))
+        .map(str::to_string)
+        .ok_or_else(|| registry_parse_error(file, &entry.to_string(), keys[0]))
+}
+
+#[cfg(feature = "registry-verify")]
+fn registry_parse_error(file: &str, entry: &str, field: &str) -> crate::error::RegistryError {
+    crate::error::RegistryError {
+        kind: crate::error::RegistryErrorKind::Parse,
+        message: format!("{}: entry {} has no valid '{}'", file, entry, field),
+    }
+}
+
+/// Report entries present in only one of `built` and `spec`.
+#[cfg(feature = "registry-verify")]
+fn compare_sets(
+    registry: &'static str,
+    built: &[String],
+    spec: &[String],
+    drift: &mut Vec<RegistryDrift>,
+) {
+    for name in spec.iter().filter(|name| !built.contains(name)) {
+        drift.push(RegistryDrift {
+            registry,
+            entry: name.clone(),
+            message: "in the specification but not this build".to_string(),
+        });
+    }
+    for name in built.iter().filter(|name| !spec.contains(name)) {
+        drift.push(RegistryDrift {
+            registry,
+            entry: name.clone(),
+            message: "in this build but not the specification".to_string(),
+        });
+    }
+}
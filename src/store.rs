@@ -0,0 +1,419 @@
+//! Durable verdict history in an embedded SQLite database.
+//!
+//! A [`VerdictStore`] keeps [`AttackVerdict`]s, optionally grouped into
+//! [`Session`]s, in a single database file, so a long-running monitor can
+//! answer "what happened to attack X last week" without external
+//! infrastructure:
+//!
+//! - [`VerdictStore::insert`] records a verdict (the store is also a
+//!   [`VerdictSink`], so it plugs into [`Buffered`](crate::sink::Buffered)
+//!   and friends);
+//! - [`VerdictStore::query`] filters by attack ID, session, result, and time
+//!   range;
+//! - [`VerdictStore::apply_retention`] prunes history by age and count.
+//!
+//! A verdict's time is its ISO 8601 `timestamp` when present and parseable,
+//! otherwise the time it was inserted. Requires the `store` feature; SQLite
+//! is compiled in, so no system library is needed.
+
+use crate::enums::AttackResult;
+use crate::error::{SinkError, SinkErrorKind, StoreError, StoreErrorKind};
+use crate::sink::VerdictSink;
+use crate::types::AttackVerdict;
+use rusqlite::types::Value as SqlValue;
+use rusqlite::{Connection, OptionalExtension, params, params_from_iter};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS sessions (
+    id          TEXT PRIMARY KEY,
+    started_at  TEXT,
+    ended_at    TEXT,
+    metadata    TEXT NOT NULL,
+    recorded_at INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS verdicts (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    attack_id   TEXT,
+    session_id  TEXT,
+    result      TEXT NOT NULL,
+    recorded_at INTEGER NOT NULL,
+    body        TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS verdicts_attack ON verdicts (attack_id, recorded_at);
+CREATE INDEX IF NOT EXISTS verdicts_time ON verdicts (recorded_at);
+CREATE INDEX IF NOT EXISTS verdicts_session ON verdicts (session_id);
+";
+
+/// A group of verdicts from one monitoring or test run.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Session {
+    /// Caller-chosen session identifier.
+    pub id: String,
+    /// ISO 8601 start time.
+    pub started_at: Option<String>,
+    /// ISO 8601 end time.
+    pub ended_at: Option<String>,
+    /// Free-form labels, such as the target or the operator.
+    pub metadata: BTreeMap<String, String>,
+}
+
+/// A verdict as stored.
+#[derive(Clone, Debug)]
+pub struct StoredVerdict {
+    /// Row ID, increasing in insertion order.
+    pub id: i64,
+    /// Session the verdict was recorded under.
+    pub session_id: Option<String>,
+    /// Time used for range queries and retention.
+    pub recorded_at: SystemTime,
+    /// The verdict itself.
+    pub verdict: AttackVerdict,
+}
+
+/// Filters for [`VerdictStore::query`]. Unset fields match everything;
+/// results are ordered oldest first.
+#[derive(Clone, Debug, Default)]
+pub struct VerdictQuery {
+    /// Only verdicts for this attack.
+    pub attack_id: Option<String>,
+    /// Only verdicts recorded under this session.
+    pub session_id: Option<String>,
+    /// Only verdicts with this result.
+    pub result: Option<AttackResult>,
+    /// Only verdicts at or after this time.
+    pub since: Option<SystemTime>,
+    /// Only verdicts before this time.
+    pub until: Option<SystemTime>,
+    /// Return at most this many verdicts (the most recent ones).
+    pub limit: Option<usize>,
+}
+
+/// Limits enforced by [`VerdictStore::apply_retention`]. Unset fields are
+/// not enforced.
+#[derive(Clone, Debug, Default)]
+pub struct RetentionPolicy {
+    /// Delete verdicts older than this.
+    pub max_age: Option<Duration>,
+    /// Keep only the most recent verdicts overall.
+    pub max_verdicts: Option<usize>,
+    /// Keep only the most recent verdicts of each attack.
+    pub max_per_attack: Option<usize>,
+}
+
+/// An embedded, file-backed verdict store.
+pub struct VerdictStore {
+    conn: Connection,
+}
+
+impl std::fmt::Debug for VerdictStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VerdictStore")
+            .field("path", &self.conn.path())
+            .finish()
+    }
+}
+
+impl VerdictStore {
+    /// Open the store at `path`, creating the file and schema if needed.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, StoreError> {
+        Self::init(Connection::open(path).map_err(database)?)
+    }
+
+    /// A store that lives only as long as the value, for tests and
+    /// short-lived tools.
+    pub fn open_in_memory() -> Result<Self, StoreError> {
+        Self::init(Connection::open_in_memory().map_err(database)?)
+    }
+
+    fn init(conn: Connection) -> Result<Self, StoreError> {
+        conn.execute_batch(SCHEMA).map_err(database)?;
+        Ok(VerdictStore { conn })
+    }
+
+    /// Record a verdict, optionally under a session, and return its row ID.
+    /// The session does not need to have been [`put`](Self::put_session)
+    /// first.
+    pub fn insert(
+        &mut self,
+        verdict: &AttackVerdict,
+        session_id: Option<&str>,
+    ) -> Result<i64, StoreError> {
+        let body = serde_json::to_string(verdict).map_err(|e| StoreError {
+            kind: StoreErrorKind::Encoding,
+            message: format!("failed to encode verdict: {}", e),
+        })?;
+        // SQLite parses the ISO 8601 timestamp; NULL falls back to now.
+        self.conn
+            .execute(
+                "INSERT INTO verdicts (attack_id, session_id, result, recorded_at, body)
+                 VALUES (?1, ?2, ?3,
+                         COALESCE(CAST(unixepoch(?4, 'subsec') * 1000 AS INTEGER), ?5), ?6)",
+                params![
+                    verdict.attack_id,
+                    session_id,
+                    result_name(&verdict.result),
+                    verdict.timestamp,
+                    to_millis(SystemTime::now()),
+                    body,
+                ],
+            )
+            .map_err(database)?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Create or replace a session record.
+    pub fn put_session(&mut self, session: &Session) -> Result<(), StoreError> {
+        let metadata = serde_json::to_string(&session.metadata).map_err(|e| StoreError {
+            kind: StoreErrorKind::Encoding,
+            message: format!("failed to encode session metadata: {}", e),
+        })?;
+        self.conn
+            .execute(
+                "INSERT OR REPLACE INTO sessions (id, started_at, ended_at, metadata, recorded_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    session.id,
+                    session.started_at,
+                    session.ended_at,
+                    metadata,
+                    to_millis(SystemTime::now()),
+                ],
+            )
+            .map_err(database)?;
+        Ok(())
+    }
+
+    /// The session with ID `id`, if recorded.
+    pub fn session(&self, id: &str) -> Result<Option<Session>, StoreError> {
+        self.conn
+            .query_row(
+                "SELECT id, started_at, ended_at, metadata FROM sessions WHERE id = ?1",
+                [id],
+                session_row,
+            )
+            .optional()
+            .map_err(database)?
+            .map(decode_session)
+            .transpose()
+    }
+
+    /// All recorded sessions, ordered by ID.
+    pub fn sessions(&self) -> Result<Vec<Session>, StoreError> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, started_at, ended_at, metadata FROM sessions ORDER BY id")
+            .map_err(database)?;
+        let rows = stmt
+            .query_map([], session_row)
+            .map_err(database)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(database)?;
+        rows.into_iter().map(decode_session).collect()
+    }
+
+    /// Verdicts matching `query`, oldest first.
+    pub fn query(&self, query: &VerdictQuery) -> Result<Vec<StoredVerdict>, StoreError> {
+        let mut clauses = Vec::new();
+        let mut args: Vec<SqlValue> = Vec::new();
+        if let Some(attack_id) = &query.attack_id {
+            args.push(SqlValue::Text(attack_id.clone()));
+            clauses.push(format!("attack_id = ?{}", args.len()));
+        }
+        if let Some(session_id) = &query.session_id {
+            args.push(SqlValue::Text(session_id.clone()));
+            clauses.push(format!("session_id = ?{}", args.len()));
+        }
+        if let Some(result) = &query.result {
+            args.push(SqlValue::Text(result_name(result)));
+            clauses.push(format!("result = ?{}", args.len()));
+        }
+        if let Some(since) = query.since {
+            args.push(SqlValue::Integer(to_millis(since)));
+            clauses.push(format!("recorded_at >= ?{}", args.len()));
+        }
+        if let Some(until) = query.until {
+            args.push(SqlValue::Integer(to_millis(until)));
+            clauses.push(format!("recorded_at < ?{}", args.len()));
+        }
+        let filter = if clauses.is_empty() {
+            String::new()
+        } else {
+            format!(" WHERE {}", clauses.join(" AND "))
+        };
+        let limit = match query.limit {
+            Some(limit) => format!(" LIMIT {}", limit),
+            None => String::new(),
+        };
+        let sql = format!(
+            "SELECT id, session_id, recorded_at, body FROM
+               (SELECT * FROM verdicts{} ORDER BY recorded_at DESC, id DESC{})
+             ORDER BY recorded_at, id",
+            filter, limit
+        );
+
+        let mut stmt = self.conn.prepare(&sql).map_err(database)?;
+        let rows = stmt
+            .query_map(params_from_iter(args), |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, i64>(2)?,
+                    row.get::<_, String>(3)?,
+                ))
+            })
+            .map_err(database)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(database)?;
+        rows.into_iter()
+            .map(|(id, session_id, recorded_at, body)| {
+                let verdict = serde_json::from_str(&body).map_err(|e| StoreError {
+                    kind: StoreErrorKind::Encoding,
+                    message: format!("stored verdict {} is not a valid verdict: {}", id, e),
+                })?;
+                Ok(StoredVerdict {
+                    id,
+                    session_id,
+                    recorded_at: from_millis(recorded_at),
+                    verdict,
+                })
+            })
+            .collect()
+    }
+
+    /// Number of stored verdicts.
+    pub fn len(&self) -> Result<usize, StoreError> {
+        self.conn
+            .query_row("SELECT COUNT(*) FROM verdicts", [], |row| {
+                row.get::<_, i64>(0)
+            })
+            .map(|n| n as usize)
+            .map_err(database)
+    }
+
+    /// Whether the store holds no verdicts.
+    pub fn is_empty(&self) -> Result<bool, StoreError> {
+        self.len().map(|n| n == 0)
+    }
+
+    /// Enforce `policy` relative to the current time. See
+    /// [`apply_retention_at`](Self::apply_retention_at).
+    pub fn apply_retention(&mut self, policy: &RetentionPolicy) -> Result<usize, StoreError> {
+        self.apply_retention_at(policy, SystemTime::now())
+    }
+
+    /// Enforce `policy` as of `now` and return the number of verdicts
+    /// deleted. Sessions left without verdicts are deleted once they are
+    /// older than `max_age`.
+    pub fn apply_retention_at(
+        &mut self,
+        policy: &RetentionPolicy,
+        now: SystemTime,
+    ) -> Result<usize, StoreError> {
+        let tx = self.conn.transaction().map_err(database)?;
+        let mut deleted = 0;
+        if let Some(max_age) = policy.max_age {
+            let cutoff = to_millis(now.checked_sub(max_age).unwrap_or(SystemTime::UNIX_EPOCH));
+            deleted += tx
+                .execute("DELETE FROM verdicts WHERE recorded_at < ?1", [cutoff])
+                .map_err(database)?;
+            tx.execute(
+                "DELETE FROM sessions WHERE recorded_at < ?1
+                   AND id NOT IN (SELECT session_id FROM verdicts WHERE session_id IS NOT NULL)",
+                [cutoff],
+            )
+            .map_err(database)?;
+        }
+        if let Some(max_per_attack) = policy.max_per_attack {
+            deleted += tx
+                .execute(
+                    "DELETE FROM verdicts WHERE id IN (
+                       SELECT id FROM (
+                         SELECT id, ROW_NUMBER() OVER (
+                           PARTITION BY attack_id ORDER BY recorded_at DESC, id DESC) AS rank
+                         FROM verdicts)
+                       WHERE rank > ?1)",
+                    [max_per_attack as i64],
+                )
+                .map_err(database)?;
+        }
+        if let Some(max_verdicts) = policy.max_verdicts {
+            deleted += tx
+                .execute(
+                    "DELETE FROM verdicts WHERE id NOT IN (
+                       SELECT id FROM verdicts ORDER BY recorded_at DESC, id DESC LIMIT ?1)",
+                    [max_verdicts as i64],
+                )
+                .map_err(database)?;
+        }
+        tx.commit().map_err(database)?;
+        Ok(deleted)
+    }
+}
+
+impl VerdictSink for VerdictStore {
+    fn publish(&mut self, verdict: &AttackVerdict) -> Result<(), SinkError> {
+        self.insert(verdict, None)
+            .map(|_| ())
+            .map_err(|e| SinkError {
+                kind: match e.kind {
+                    StoreErrorKind::Database => SinkErrorKind::Unavailable,
+                    StoreErrorKind::Encoding => SinkErrorKind::Rejected,
+                },
+                message: e.message,
+            })
+    }
+}
+
+type SessionRow = (String, Option<String>, Option<String>, String);
+
+fn session_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<SessionRow> {
+    Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+}
+
+fn decode_session((id, started_at, ended_at, metadata): SessionRow) -> Result<Session, StoreError> {
+    let metadata = serde_json::from_str(&metadata).map_err(|e| StoreError {
+        kind: StoreErrorKind::Encoding,
+        message: format!("stored metadata of session '{}' is invalid: {}", id, e),
+    })?;
+    Ok(Session {
+        id,
+        started_at,
+        ended_at,
+        metadata,
+    })
+}
+
+fn result_name(result: &AttackResult) -> String {
+    match result {
+        AttackResult::Exploited => "exploited",
+        AttackResult::NotExploited => "not_exploited",
+        AttackResult::Partial => "partial",
+        AttackResult::Error => "error",
+    }
+    .to_string()
+}
+
+fn to_millis(time: SystemTime) -> i64 {
+    match time.duration_since(SystemTime::UNIX_EPOCH) {
+        Ok(d) => d.as_millis() as i64,
+        Err(e) => -(e.duration().as_millis() as i64),
+    }
+}
+
+fn from_millis(millis: i64) -> SystemTime {
+    if millis >= 0 {
+        SystemTime::UNIX_EPOCH + Duration::from_millis(millis as u64)
+    } else {
+        SystemTime::UNIX_EPOCH - Duration::from_millis(millis.unsigned_abs())
+    }
+}
+
+fn database(e: rusqlite::Error) -> StoreError {
+    StoreError {
+        kind: StoreErrorKind::Database,
+        message: e.to_string(),
+    }
+}
@@ -1,9 +1,14 @@
-/// A surface registry entry mapping surface name to protocol and default target path.
+use crate::enums::{Category, OriginRole};
+
+/// A surface registry entry mapping surface name to protocol, default target
+/// path, and the party that produces the surface's content.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SurfaceEntry {
     pub surface: &'static str,
     pub protocol: &'static str,
     pub default_target: &'static str,
+    /// `None` for surfaces either party may send (e.g., A2A task messages).
+    pub origin: Option<OriginRole>,
 }
 
 /// The v0.1 surface registry as a compile-time constant.
@@ -13,193 +18,249 @@ pub static SURFACE_REGISTRY: &[SurfaceEntry] = &[
         surface: "tool_description",
         protocol: "mcp",
         default_target: "tools[*].description",
+        origin: Some(OriginRole::Server),
     },
     SurfaceEntry {
         surface: "tool_input_schema",
         protocol: "mcp",
         default_target: "tools[*].inputSchema",
+        origin: Some(OriginRole::Server),
     },
     SurfaceEntry {
         surface: "tool_name",
         protocol: "mcp",
         default_target: "tools[*].name",
+        origin: Some(OriginRole::Server),
     },
     SurfaceEntry {
         surface: "tool_annotations",
         protocol: "mcp",
         default_target: "tools[*].annotations",
+        origin: Some(OriginRole::Server),
     },
     SurfaceEntry {
         surface: "tool_output_schema",
         protocol: "mcp",
         default_target: "tools[*].outputSchema",
+        origin: Some(OriginRole::Server),
     },
     SurfaceEntry {
         surface: "tool_response",
         protocol: "mcp",
         default_target: "content[*]",
+        origin: Some(OriginRole::Server),
     },
     SurfaceEntry {
         surface: "tool_structured_response",
         protocol: "mcp",
         default_target: "structuredContent",
+        origin: Some(OriginRole::Server),
     },
     SurfaceEntry {
         surface: "tool_arguments",
         protocol: "mcp",
         default_target: "arguments",
+        origin: Some(OriginRole::Client),
     },
     SurfaceEntry {
         surface: "resource_content",
         protocol: "mcp",
         default_target: "contents[*]",
+        origin: Some(OriginRole::Server),
     },
     SurfaceEntry {
         surface: "resource_uri",
         protocol: "mcp",
         default_target: "resources[*].uri",
+        origin: Some(OriginRole::Server),
     },
     SurfaceEntry {
         surface: "resource_description",
         protocol: "mcp",
         default_target: "resources[*].description",
+        origin: Some(OriginRole::Server),
     },
     SurfaceEntry {
         surface: "prompt_content",
         protocol: "mcp",
         default_target: "messages[*].content",
+        origin: Some(OriginRole::Server),
     },
     SurfaceEntry {
         surface: "prompt_arguments",
         protocol: "mcp",
         default_target: "arguments",
+        origin: Some(OriginRole::Client),
     },
     SurfaceEntry {
         surface: "prompt_description",
         protocol: "mcp",
         default_target: "prompts[*].description",
+        origin: Some(OriginRole::Server),
     },
     SurfaceEntry {
         surface: "server_notification",
         protocol: "mcp",
         default_target: "params",
+        origin: Some(OriginRole::Server),
     },
     SurfaceEntry {
         surface: "server_capability",
         protocol: "mcp",
         default_target: "capabilities",
+        origin: Some(OriginRole::Server),
     },
     SurfaceEntry {
         surface: "server_info",
         protocol: "mcp",
         default_target: "serverInfo",
+        origin: Some(OriginRole::Server),
     },
     SurfaceEntry {
         surface: "sampling_request",
         protocol: "mcp",
         default_target: "params",
+        origin: Some(OriginRole::Server),
     },
     SurfaceEntry {
         surface: "elicitation_request",
         protocol: "mcp",
         default_target: "params",
+        origin: Some(OriginRole::Server),
+    },
+    SurfaceEntry {
+        surface: "sampling_response",
+        protocol: "mcp",
+        default_target: "result.content",
+        origin: Some(OriginRole::Client),
     },
     SurfaceEntry {
         surface: "elicitation_response",
         protocol: "mcp",
         default_target: "result",
+        origin: Some(OriginRole::Client),
+    },
+    SurfaceEntry {
+        surface: "progress_notification",
+        protocol: "mcp",
+        default_target: "params.message",
+        origin: None,
+    },
+    SurfaceEntry {
+        surface: "resource_subscription",
+        protocol: "mcp",
+        default_target: "params.uri",
+        origin: Some(OriginRole::Client),
     },
     SurfaceEntry {
         surface: "mcp_task_status",
         protocol: "mcp",
         default_target: "task",
+        origin: Some(OriginRole::Server),
     },
     SurfaceEntry {
         surface: "mcp_task_result",
         protocol: "mcp",
         default_target: "result",
+        origin: Some(OriginRole::Server),
     },
     SurfaceEntry {
         surface: "roots_response",
         protocol: "mcp",
         default_target: "roots[*]",
+        origin: Some(OriginRole::Client),
     },
     // A2A surfaces
     SurfaceEntry {
         surface: "agent_card",
         protocol: "a2a",
         default_target: "",
+        origin: Some(OriginRole::Server),
     },
     SurfaceEntry {
         surface: "card_name",
         protocol: "a2a",
         default_target: "name",
+        origin: Some(OriginRole::Server),
     },
     SurfaceEntry {
         surface: "card_description",
         protocol: "a2a",
         default_target: "description",
+        origin: Some(OriginRole::Server),
     },
     SurfaceEntry {
         surface: "skill_description",
         protocol: "a2a",
         default_target: "skills[*].description",
+        origin: Some(OriginRole::Server),
     },
     SurfaceEntry {
         surface: "skill_name",
         protocol: "a2a",
         default_target: "skills[*].name",
+        origin: Some(OriginRole::Server),
     },
     SurfaceEntry {
         surface: "task_message",
         protocol: "a2a",
         default_target: "messages[*]",
+        origin: None,
     },
     SurfaceEntry {
         surface: "task_artifact",
         protocol: "a2a",
         default_target: "artifacts[*]",
+        origin: Some(OriginRole::Server),
     },
     SurfaceEntry {
         surface: "task_status",
         protocol: "a2a",
         default_target: "status.state",
+        origin: Some(OriginRole::Server),
     },
     // AG-UI surfaces
     SurfaceEntry {
         surface: "message_history",
         protocol: "ag_ui",
         default_target: "messages[*]",
+        origin: Some(OriginRole::Client),
     },
     SurfaceEntry {
         surface: "tool_definition",
         protocol: "ag_ui",
         default_target: "tools[*]",
+        origin: Some(OriginRole::Client),
     },
     SurfaceEntry {
         surface: "tool_result",
         protocol: "ag_ui",
         default_target: "messages[*]",
+        origin: Some(OriginRole::Client),
     },
     SurfaceEntry {
         surface: "agent_state",
         protocol: "ag_ui",
         default_target: "state",
+        origin: Some(OriginRole::Client),
     },
     SurfaceEntry {
         surface: "forwarded_props",
         protocol: "ag_ui",
         default_target: "forwardedProps",
+        origin: Some(OriginRole::Client),
     },
     SurfaceEntry {
         surface: "agent_event",
         protocol: "ag_ui",
         default_target: "data",
+        origin: Some(OriginRole::Server),
     },
     SurfaceEntry {
         surface: "agent_tool_call",
         protocol: "ag_ui",
         default_target: "data",
+        origin: Some(OriginRole::Server),
     },
 ];
 
@@ -208,6 +269,74 @@ pub fn lookup_surface(surface: &str) -> Option<&'static SurfaceEntry> {
     SURFACE_REGISTRY.iter().find(|e| e.surface == surface)
 }
 
+/// Surfaces on which each taxonomy category can be observed, for W-010.
+///
+/// Categories absent from the table (context manipulation, temporal
+/// manipulation, availability disruption) are not tied to particular
+/// surfaces. A cross-protocol chain is checked by protocol count instead.
+pub static CATEGORY_SURFACES: &[(Category, &[&str])] = &[
+    (
+        Category::CapabilityPoisoning,
+        &[
+            "tool_description",
+            "tool_input_schema",
+            "tool_name",
+            "tool_annotations",
+            "tool_output_schema",
+            "resource_uri",
+            "resource_description",
+            "prompt_description",
+            "prompt_arguments",
+            "server_capability",
+            "server_info",
+            "agent_card",
+            "card_name",
+            "card_description",
+            "skill_description",
+            "skill_name",
+            "tool_definition",
+        ],
+    ),
+    (
+        Category::ResponseFabrication,
+        &[
+            "tool_response",
+            "tool_structured_response",
+            "resource_content",
+            "prompt_content",
+            "sampling_request",
+            "sampling_response",
+            "elicitation_request",
+            "progress_notification",
+            "mcp_task_status",
+            "mcp_task_result",
+            "roots_response",
+            "task_message",
+            "task_artifact",
+            "task_status",
+            "message_history",
+            "tool_result",
+            "agent_event",
+        ],
+    ),
+    (
+        Category::OversightBypass,
+        &[
+            "tool_description",
+            "tool_annotations",
+            "tool_arguments",
+            "sampling_request",
+            "sampling_response",
+            "elicitation_request",
+            "elicitation_response",
+            "skill_description",
+            "task_status",
+            "tool_definition",
+            "agent_tool_call",
+        ],
+    ),
+];
+
 /// Known protocol identifiers for v0.1.
 pub static KNOWN_PROTOCOLS: &[&str] = &["mcp", "a2a", "ag_ui"];
 
@@ -219,3 +348,32 @@ pub static KNOWN_MODES: &[&str] = &[
     "a2a_client",
     "ag_ui_client",
 ];
+
+/// Known protocol revisions that a mode may pin with `@version`.
+///
+/// Protocols absent from this table accept any version string.
+pub static KNOWN_PROTOCOL_VERSIONS: &[(&str, &[&str])] = &[
+    (
+        "mcp",
+        &["2024-11-05", "2025-03-26", "2025-06-18", "2025-11-25"],
+    ),
+    ("a2a", &["0.2.5", "0.2.6", "0.3.0"]),
+];
+
+/// Split a mode string into its base mode and optional version pin:
+/// `"mcp_server@2025-03-26"` → `("mcp_server", Some("2025-03-26"))`.
+pub fn split_mode_version(mode: &str) -> (&str, Option<&str>) {
+    match mode.split_once('@') {
+        Some((base, version)) => (base, Some(version)),
+        None => (mode, None),
+    }
+}
+
+/// Check whether `version` is a known revision of `protocol`.
+/// Returns None if the protocol has no version registry.
+pub fn is_known_protocol_version(protocol: &str, version: &str) -> Option<bool> {
+    KNOWN_PROTOCOL_VERSIONS
+        .iter()
+        .find(|(p, _)| *p == protocol)
+        .map(|(_, versions)| versions.contains(&version))
+}
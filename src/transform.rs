@@ -0,0 +1,712 @@
+//! Transformations that prepare documents for sharing outside the
+//! organization that wrote them.
+//!
+//! [`anonymize`] removes or pseudonymizes what a document reveals about its
+//! authors: author names, internal host names, organization-specific
+//! identifiers (ticket numbers, asset tags), and free-text descriptions.
+//! Pseudonyms are recorded in an [`AnonymizationMapping`] that the author
+//! keeps, so [`deanonymize`] can restore a shared document (for example, one
+//! returned with improvements) to its original names. Stripped content is
+//! not recorded and cannot be restored.
+//!
+//! [`merge`] combines two edits of the same document structurally, so
+//! edits to different indicators or phases merge cleanly where a line-based
+//! merge of the YAML would conflict.
+//!
+//! [`renumber_indicators`] and [`rename_attack_id`] change identifiers while
+//! keeping indicator IDs prefixed by the attack ID (V-024), and report the
+//! IDs that changed.
+
+use crate::error::{MergeConflict, MergeConflicts, TransformError, TransformErrorKind};
+use crate::types::*;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::LazyLock;
+
+/// Replacement for stripped host names, kept a valid host so URLs parse.
+const REDACTED_HOST: &str = "redacted.invalid";
+/// Replacement for stripped text that the format requires.
+const REDACTED: &str = "[redacted]";
+
+/// What [`anonymize`] does with one kind of sensitive content.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Treatment {
+    /// Leave the content as written.
+    #[default]
+    Keep,
+    /// Remove the content (optional fields) or replace it with a redaction
+    /// marker (required fields and text inside other strings).
+    Strip,
+    /// Replace each distinct value with a stable pseudonym recorded in the
+    /// mapping.
+    Pseudonymize,
+}
+
+/// Which content [`anonymize`] strips or pseudonymizes.
+///
+/// The default policy keeps everything.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnonymizationPolicy {
+    /// `attack.author`.
+    pub author: Treatment,
+    /// Free-text descriptions of the attack, its phases, indicators,
+    /// references, and false positives. Tool and prompt descriptions inside
+    /// execution state are attack content and are not affected.
+    pub descriptions: Treatment,
+    /// Internal domains (e.g., `corp.example.com`); the domain and its
+    /// subdomains are treated wherever they appear in the document's text.
+    pub internal_domains: Vec<String>,
+    /// Treatment of host names under `internal_domains`.
+    pub hosts: Treatment,
+    /// Regular expressions matching organization-specific identifiers
+    /// (e.g., `ACME-[0-9]+`), treated wherever they appear in the document's
+    /// text.
+    pub identifier_patterns: Vec<String>,
+    /// Treatment of text matched by `identifier_patterns`.
+    pub identifiers: Treatment,
+    /// Remove `x-*` extension fields, which often carry internal tracking
+    /// data.
+    pub strip_extensions: bool,
+}
+
+/// Pseudonyms assigned by [`anonymize`], for reversing them with
+/// [`deanonymize`]. Serialize it to keep it alongside the original.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnonymizationMapping {
+    /// Original text, keyed by the pseudonym that replaced it.
+    pub pseudonyms: BTreeMap<String, String>,
+}
+
+/// A share-safe document and the mapping that reverses its pseudonyms.
+#[derive(Clone, Debug)]
+pub struct Anonymized {
+    /// The anonymized document.
+    pub document: Document,
+    /// Pseudonym assignments; keep it private.
+    pub mapping: AnonymizationMapping,
+}
+
+/// Produce a share-safe variant of `doc` according to `policy`.
+///
+/// Each distinct value gets one pseudonym (`anon-author-1`,
+/// `anon-host-1.example`, `anon-id-1`, `anon-description-1`), so
+/// relationships within the document survive: every mention of the same
+/// internal host becomes the same pseudonymous host. Host names match
+/// case-insensitively and share a pseudonym regardless of case.
+///
+/// # Errors
+///
+/// Returns a [`TransformError`] of kind `invalid_policy` if an internal
+/// domain is empty or an identifier pattern is not a valid regex.
+pub fn anonymize(
+    doc: &Document,
+    policy: &AnonymizationPolicy,
+) -> Result<Anonymized, TransformError> {
+    let hosts = host_pattern(&policy.internal_domains)?;
+    let identifiers = policy
+        .identifier_patterns
+        .iter()
+        .map(|pattern| {
+            Regex::new(pattern).map_err(|e| TransformError {
+                kind: TransformErrorKind::InvalidPolicy,
+                message: format!("invalid identifier pattern '{}': {}", pattern, e),
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut pseudonyms = Pseudonyms::default();
+    let mut doc = doc.clone();
+    let attack = &mut doc.attack;
+
+    if let Some(author) = &mut attack.author {
+        match policy.author {
+            Treatment::Keep => {}
+            Treatment::Strip => attack.author = None,
+            Treatment::Pseudonymize => *author = pseudonyms.assign("author", author),
+        }
+    }
+
+    let mut descriptions: Vec<&mut Option<String>> = vec![&mut attack.description];
+    for phase in attack.execution.phases.iter_mut().flatten() {
+        descriptions.push(&mut phase.description);
+    }
+    for actor in attack.execution.actors.iter_mut().flatten() {
+        for phase in &mut actor.phases {
+            descriptions.push(&mut phase.description);
+        }
+    }
+    let mut required = Vec::new();
+    for indicator in attack.indicators.iter_mut().flatten() {
+        descriptions.push(&mut indicator.description);
+        for fp in indicator.false_positives.iter_mut().flatten() {
+            required.push(&mut fp.description);
+        }
+    }
+    for reference in attack.references.iter_mut().flatten() {
+        descriptions.push(&mut reference.description);
+    }
+    for description in descriptions {
+        if let Some(text) = description {
+            match policy.descriptions {
+                Treatment::Keep => {}
+                Treatment::Strip => *description = None,
+                Treatment::Pseudonymize => *text = pseudonyms.assign("description", text),
+            }
+        }
+    }
+    for text in required {
+        match policy.descriptions {
+            Treatment::Keep => {}
+            Treatment::Strip => *text = REDACTED.to_string(),
+            Treatment::Pseudonymize => *text = pseudonyms.assign("description", text),
+        }
+    }
+
+    if policy.strip_extensions {
+        strip_extensions(&mut doc);
+    }
+
+    let treat_hosts = policy.hosts != Treatment::Keep && hosts.is_some();
+    let treat_identifiers = policy.identifiers != Treatment::Keep && !identifiers.is_empty();
+    if treat_hosts || treat_identifiers {
+        doc = map_strings(&doc, |text| {
+            let mut text = text.to_string();
+            if treat_hosts && let Some(hosts) = &hosts {
+                text = hosts
+                    .replace_all(&text, |caps: &regex::Captures| match policy.hosts {
+                        Treatment::Strip => REDACTED_HOST.to_string(),
+                        _ => pseudonyms.assign_host(&caps[0]),
+                    })
+                    .into_owned();
+            }
+            if treat_identifiers {
+                for pattern in &identifiers {
+                    text = pattern
+                        .replace_all(&text, |caps: &regex::Captures| match policy.identifiers {
+                            Treatment::Strip => REDACTED.to_string(),
+                            _ => pseudonyms.assign("id", &caps[0]),
+                        })
+                        .into_owned();
+                }
+            }
+            text
+        })?;
+    }
+
+    Ok(Anonymized {
+        document: doc,
+        mapping: AnonymizationMapping {
+            pseudonyms: pseudonyms.originals,
+        },
+    })
+}
+
+/// Replace the pseudonyms in `doc` with the originals recorded in `mapping`.
+///
+/// Content that [`anonymize`] stripped stays stripped.
+///
+/// # Errors
+///
+/// Returns a [`TransformError`] of kind `encoding` if the restored document
+/// cannot be rebuilt.
+pub fn deanonymize(
+    doc: &Document,
+    mapping: &AnonymizationMapping,
+) -> Result<Document, TransformError> {
+    if mapping.pseudonyms.is_empty() {
+        return Ok(doc.clone());
+    }
+    // Longest first, so `anon-id-12` is not read as `anon-id-1` followed by `2`.
+    let mut names: Vec<&String> = mapping.pseudonyms.keys().collect();
+    names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+    let alternation = names
+        .iter()
+        .map(|name| regex::escape(name))
+        .collect::<Vec<_>>()
+        .join("|");
+    let pattern = Regex::new(&format!(r"\b(?:{})\b", alternation)).map_err(|e| TransformError {
+        kind: TransformErrorKind::InvalidPolicy,
+        message: format!("mapping has too many pseudonyms to match: {}", e),
+    })?;
+    map_strings(doc, |text| {
+        pattern
+            .replace_all(text, |caps: &regex::Captures| {
+                mapping.pseudonyms[&caps[0]].clone()
+            })
+            .into_owned()
+    })
+}
+
+/// Pseudonym assignment, one per distinct original per kind.
+#[derive(Default)]
+struct Pseudonyms {
+    assigned: HashMap<(&'static str, String), String>,
+    counts: HashMap<&'static str, usize>,
+    originals: BTreeMap<String, String>,
+}
+
+impl Pseudonyms {
+    fn assign(&mut self, kind: &'static str, original: &str) -> String {
+        self.assign_keyed(kind, original.to_string(), original, "")
+    }
+
+    fn assign_host(&mut self, host: &str) -> String {
+        self.assign_keyed("host", host.to_ascii_lowercase(), host, ".example")
+    }
+
+    fn assign_keyed(
+        &mut self,
+        kind: &'static str,
+        key: String,
+        original: &str,
+        suffix: &str,
+    ) -> String {
+        if let Some(pseudonym) = self.assigned.get(&(kind, key.clone())) {
+            return pseudonym.clone();
+        }
+        let count = self.counts.entry(kind).or_default();
+        *count += 1;
+        let pseudonym = format!("anon-{}-{}{}", kind, count, suffix);
+        self.originals
+            .insert(pseudonym.clone(), original.to_string());
+        self.assigned.insert((kind, key), pseudonym.clone());
+        pseudonym
+    }
+}
+
+/// One case-insensitive pattern matching every internal domain and its
+/// subdomains, or `None` without domains.
+fn host_pattern(domains: &[String]) -> Result<Option<Regex>, TransformError> {
+    if domains.is_empty() {
+        return Ok(None);
+    }
+    let mut alternatives = Vec::new();
+    for domain in domains {
+        let domain = domain.trim().trim_start_matches('.');
+        if domain.is_empty() {
+            return Err(TransformError {
+                kind: TransformErrorKind::InvalidPolicy,
+                message: "internal domains must not be empty".to_string(),
+            });
+        }
+        alternatives.push(regex::escape(domain));
+    }
+    let pattern = format!(
+        r"(?i)\b(?:[a-z0-9](?:[a-z0-9-]*[a-z0-9])?\.)*(?:{})\b",
+        alternatives.join("|")
+    );
+    Regex::new(&pattern).map(Some).map_err(|e| TransformError {
+        kind: TransformErrorKind::InvalidPolicy,
+        message: format!("invalid internal domain: {}", e),
+    })
+}
+
+/// Rebuild `doc` with `f` applied to every string value.
+fn map_strings(
+    doc: &Document,
+    mut f: impl FnMut(&str) -> String,
+) -> Result<Document, TransformError> {
+    let encoding = |e: serde_json::Error| TransformError {
+        kind: TransformErrorKind::Encoding,
+        message: e.to_string(),
+    };
+    let mut value = serde_json::to_value(doc).map_err(encoding)?;
+    map_value_strings(&mut value, &mut f);
+    let mut mapped: Document = serde_json::from_value(value).map_err(encoding)?;
+    mapped.oatf_is_first_key = doc.oatf_is_first_key;
+    Ok(mapped)
+}
+
+fn map_value_strings(value: &mut Value, f: &mut impl FnMut(&str) -> String) {
+    match value {
+        Value::String(text) => *text = f(text),
+        Value::Array(items) => items.iter_mut().for_each(|v| map_value_strings(v, f)),
+        Value::Object(map) => map.values_mut().for_each(|v| map_value_strings(v, f)),
+        _ => {}
+    }
+}
+
+/// Remove `x-*` fields from every structure that carries them.
+fn strip_extensions(doc: &mut Document) {
+    let attack = &mut doc.attack;
+    attack.extensions.clear();
+    attack.execution.extensions.clear();
+    for phase in attack.execution.phases.iter_mut().flatten() {
+        phase.extensions.clear();
+    }
+    for actor in attack.execution.actors.iter_mut().flatten() {
+        actor.extensions.clear();
+        for phase in &mut actor.phases {
+            phase.extensions.clear();
+        }
+    }
+    for indicator in attack.indicators.iter_mut().flatten() {
+        indicator.extensions.clear();
+    }
+}
+
+/// Three-way merge of two edits, `ours` and `theirs`, of a common ancestor
+/// `base`.
+///
+/// Values are merged field by field: a field changed on one side only takes
+/// that side's value, and a field both sides changed must end up equal or
+/// it conflicts. Indicators are matched by `id`, phases and actors by
+/// `name`, and references by `url`, so reordering, adding, and removing
+/// entries on either side merges like any other edit; entries added on
+/// both sides with the same key are merged as if added empty in `base`.
+/// Other lists (including lists inside execution state) are values: they
+/// conflict when both sides change them differently. Removing an entry or
+/// field that the other side changed is a conflict.
+///
+/// The merged document keeps `ours`'s field and entry order, with entries
+/// only `theirs` added after the entry they follow in `theirs`.
+///
+/// # Errors
+///
+/// Returns every conflict, with its document path and the three values.
+pub fn merge(
+    base: &Document,
+    ours: &Document,
+    theirs: &Document,
+) -> Result<Document, MergeConflicts> {
+    let encode = |doc: &Document| serde_json::to_value(doc).unwrap_or(Value::Null);
+    let (base_value, ours_value, theirs_value) = (encode(base), encode(ours), encode(theirs));
+    let mut conflicts = Vec::new();
+    let merged = merge_value(
+        Some(&base_value),
+        Some(&ours_value),
+        Some(&theirs_value),
+        "",
+        None,
+        &mut conflicts,
+    )
+    .unwrap_or(Value::Null);
+    if !conflicts.is_empty() {
+        return Err(MergeConflicts { conflicts });
+    }
+    match serde_json::from_value::<Document>(merged.clone()) {
+        Ok(mut doc) => {
+            doc.oatf_is_first_key = ours.oatf_is_first_key;
+            Ok(doc)
+        }
+        Err(e) => Err(MergeConflicts {
+            conflicts: vec![MergeConflict {
+                path: "attack".to_string(),
+                base: Some(base_value),
+                ours: Some(ours_value),
+                theirs: Some(merged),
+                message: format!("the combined edits do not form a document: {}", e),
+            }],
+        }),
+    }
+}
+
+/// Field identifying entries of the list stored under `field`, for lists
+/// merged entry by entry.
+fn list_key(field: Option<&str>) -> Option<&'static str> {
+    match field? {
+        "indicators" => Some("id"),
+        "phases" | "actors" => Some("name"),
+        "references" => Some("url"),
+        _ => None,
+    }
+}
+
+fn child_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+/// Merge one value; `None` means absent (or removed). `field` is the key the
+/// value is stored under, which selects keyed list merging.
+fn merge_value(
+    base: Option<&Value>,
+    ours: Option<&Value>,
+    theirs: Option<&Value>,
+    path: &str,
+    field: Option<&str>,
+    conflicts: &mut Vec<MergeConflict>,
+) -> Option<Value> {
+    if ours == theirs || theirs == base {
+        return ours.cloned();
+    }
+    if ours == base {
+        return theirs.cloned();
+    }
+    let empty = Value::Object(Default::default());
+    match (ours, theirs) {
+        (Some(Value::Object(o)), Some(Value::Object(t))) => {
+            let b = match base {
+                Some(Value::Object(b)) => b,
+                None => empty.as_object().unwrap(),
+                Some(_) => {
+                    return conflict(
+                        base,
+                        ours,
+                        theirs,
+                        path,
+                        "changed to different values",
+                        conflicts,
+                    );
+                }
+            };
+            let mut merged = serde_json::Map::new();
+            let keys = o.keys().chain(t.keys().filter(|k| !o.contains_key(*k)));
+            for key in keys {
+                if let Some(value) = merge_value(
+                    b.get(key),
+                    o.get(key),
+                    t.get(key),
+                    &child_path(path, key),
+                    Some(key),
+                    conflicts,
+                ) {
+                    merged.insert(key.clone(), value);
+                }
+            }
+            Some(Value::Object(merged))
+        }
+        (Some(Value::Array(o)), Some(Value::Array(t))) => {
+            let empty_list = Vec::new();
+            let b = match base {
+                Some(Value::Array(b)) => b,
+                None => &empty_list,
+                Some(_) => {
+                    return conflict(
+                        base,
+                        ours,
+                        theirs,
+                        path,
+                        "changed to different values",
+                        conflicts,
+                    );
+                }
+            };
+            match list_key(field).filter(|key| [b, o, t].iter().all(|list| keyed(list, key))) {
+                Some(key) => Some(Value::Array(merge_keyed(b, o, t, key, path, conflicts))),
+                None => conflict(
+                    base,
+                    ours,
+                    theirs,
+                    path,
+                    "changed to different values",
+                    conflicts,
+                ),
+            }
+        }
+        (None, _) => conflict(
+            base,
+            ours,
+            theirs,
+            path,
+            "removed by us but changed by them",
+            conflicts,
+        ),
+        (_, None) => conflict(
+            base,
+            ours,
+            theirs,
+            path,
+            "changed by us but removed by them",
+            conflicts,
+        ),
+        _ if base.is_none() => conflict(
+            base,
+            ours,
+            theirs,
+            path,
+            "added with different values",
+            conflicts,
+        ),
+        _ => conflict(
+            base,
+            ours,
+            theirs,
+            path,
+            "changed to different values",
+            conflicts,
+        ),
+    }
+}
+
+/// Whether every entry of `list` is an object with a distinct string `key`.
+fn keyed(list: &[Value], key: &str) -> bool {
+    let mut seen = std::collections::HashSet::new();
+    list.iter().all(|entry| {
+        entry
+            .get(key)
+            .and_then(Value::as_str)
+            .is_some_and(|k| seen.insert(k))
+    })
+}
+
+fn entry_key<'a>(entry: &'a Value, key: &str) -> &'a str {
+    entry.get(key).and_then(Value::as_str).unwrap_or_default()
+}
+
+fn merge_keyed(
+    base: &[Value],
+    ours: &[Value],
+    theirs: &[Value],
+    key: &str,
+    path: &str,
+    conflicts: &mut Vec<MergeConflict>,
+) -> Vec<Value> {
+    let find = |list: &'_ [Value], k: &str| -> Option<usize> {
+        list.iter().position(|entry| entry_key(entry, key) == k)
+    };
+
+    // Our order, with entries only they added placed after the entry that
+    // precedes them in their list.
+    let mut order: Vec<&str> = ours.iter().map(|entry| entry_key(entry, key)).collect();
+    for (i, entry) in theirs.iter().enumerate() {
+        let k = entry_key(entry, key);
+        if order.contains(&k) || find(base, k).is_some() {
+            continue;
+        }
+        let at = theirs[..i]
+            .iter()
+            .rev()
+            .find_map(|prev| order.iter().position(|o| *o == entry_key(prev, key)))
+            .map_or(0, |p| p + 1);
+        order.insert(at, k);
+    }
+    for entry in base {
+        let k = entry_key(entry, key);
+        if !order.contains(&k) && find(theirs, k).is_some() {
+            // Removed by us; keep its place for a possible conflict.
+            order.push(k);
+        }
+    }
+
+    let mut merged = Vec::new();
+    for k in order {
+        let index = merged.len();
+        let entry = merge_value(
+            find(base, k).map(|i| &base[i]),
+            find(ours, k).map(|i| &ours[i]),
+            find(theirs, k).map(|i| &theirs[i]),
+            &format!("{}[{}]", path, index),
+            None,
+            conflicts,
+        );
+        merged.extend(entry);
+    }
+    merged
+}
+
+fn conflict(
+    base: Option<&Value>,
+    ours: Option<&Value>,
+    theirs: Option<&Value>,
+    path: &str,
+    message: &str,
+    conflicts: &mut Vec<MergeConflict>,
+) -> Option<Value> {
+    conflicts.push(MergeConflict {
+        path: path.to_string(),
+        base: base.cloned(),
+        ours: ours.cloned(),
+        theirs: theirs.cloned(),
+        message: message.to_string(),
+    });
+    ours.cloned()
+}
+
+// ─── Identifiers ────────────────────────────────────────────────────────────
+
+static ATTACK_ID_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[A-Z][A-Z0-9-]*-[0-9]{3,}$").unwrap());
+
+/// Give every indicator the ID its position implies: `<attack.id>-NN`
+/// numbered from `01`, or `indicator-NN` when the attack has no ID (the IDs
+/// N-003 would generate).
+///
+/// Returns the IDs that changed, old to new, so references kept outside the
+/// document (stored verdicts, calibration patches, suppressions) can follow.
+/// Indicators that had no ID are given one and are not listed.
+pub fn renumber_indicators(doc: &mut Document) -> BTreeMap<String, String> {
+    let prefix = doc.attack.id.as_deref().unwrap_or("indicator").to_string();
+    let mut changes = BTreeMap::new();
+    for (i, ind) in doc.attack.indicators.iter_mut().flatten().enumerate() {
+        let id = format!("{}-{:02}", prefix, i + 1);
+        if let Some(old) = ind.id.replace(id.clone())
+            && old != id
+        {
+            changes.insert(old, id);
+        }
+    }
+    changes
+}
+
+/// Change `attack.id` to `new_id` and move every indicator ID under it, so
+/// the V-024 prefix rule keeps holding.
+///
+/// Indicators keep their numeric suffix (`OATF-001-03` becomes
+/// `OATF-042-03`). One whose ID has no numeric suffix, or whose suffix
+/// another indicator already claimed, gets the next number after the highest
+/// in use. Indicators without an ID are left for normalization to number.
+///
+/// Returns the indicator IDs that changed, old to new, as
+/// [`renumber_indicators`] does.
+///
+/// # Errors
+///
+/// Returns a [`TransformError`] of kind `invalid_id` if `new_id` does not
+/// satisfy the attack ID format (V-023); the document is left unchanged.
+pub fn rename_attack_id(
+    doc: &mut Document,
+    new_id: &str,
+) -> Result<BTreeMap<String, String>, TransformError> {
+    if !ATTACK_ID_RE.is_match(new_id) {
+        return Err(TransformError {
+            kind: TransformErrorKind::InvalidId,
+            message: format!(
+                "attack ID '{}' must match ^[A-Z][A-Z0-9-]*-[0-9]{{3,}}$",
+                new_id
+            ),
+        });
+    }
+    doc.attack.id = Some(new_id.to_string());
+
+    let indicators = doc.attack.indicators.as_deref_mut().unwrap_or_default();
+    let suffix = |id: &str| {
+        let (_, digits) = id.rsplit_once('-')?;
+        (digits.len() >= 2 && digits.bytes().all(|b| b.is_ascii_digit()))
+            .then(|| digits.to_string())
+    };
+    let mut next = indicators
+        .iter()
+        .filter_map(|ind| suffix(ind.id.as_deref()?)?.parse::<u64>().ok())
+        .max()
+        .unwrap_or(0);
+
+    let mut changes = BTreeMap::new();
+    let mut claimed = Vec::new();
+    for ind in indicators.iter_mut() {
+        let Some(old) = ind.id.take() else {
+            continue;
+        };
+        let digits = match suffix(&old) {
+            Some(digits) if !claimed.contains(&digits) => digits,
+            _ => {
+                next += 1;
+                format!("{:02}", next)
+            }
+        };
+        claimed.push(digits.clone());
+        let id = format!("{}-{}", new_id, digits);
+        if old != id {
+            changes.insert(old, id.clone());
+        }
+        ind.id = Some(id);
+    }
+    Ok(changes)
+}
@@ -3,9 +3,10 @@
 //! All struct fields follow the specification naming. Extension fields (`x-*` prefixed)
 //! are captured via `#[serde(flatten)] HashMap<String, Value>` on types that support them.
 
+use indexmap::IndexMap;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use crate::enums::*;
 
@@ -26,6 +27,97 @@ pub struct Document {
     pub oatf_is_first_key: bool,
 }
 
+impl Document {
+    /// The profile this document satisfies: [`Profile::ExecutionOnly`] when it
+    /// has no `indicators`, otherwise [`Profile::Full`].
+    pub fn profile(&self) -> Profile {
+        if self.attack.indicators.is_none() {
+            Profile::ExecutionOnly
+        } else {
+            Profile::Full
+        }
+    }
+}
+
+// ─── §2.2a IndicatorSet ─────────────────────────────────────────────────────
+
+/// A standalone collection of detection indicators, for detection content
+/// that does not describe how to execute an attack.
+///
+/// ```yaml
+/// oatf: "0.1"
+/// indicators:
+///   - surface: tool_description
+///     pattern:
+///       contains: "<IMPORTANT>"
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IndicatorSet {
+    /// OATF format version string (e.g., `"0.1"`).
+    pub oatf: String,
+    /// Optional JSON Schema URI for editor validation.
+    #[serde(rename = "$schema", skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    /// The detection indicators.
+    pub indicators: Vec<Indicator>,
+    /// Whether `oatf` was the first key in the original YAML (for W-001).
+    #[serde(skip)]
+    pub oatf_is_first_key: bool,
+}
+
+impl IndicatorSet {
+    /// An attack document carrying these indicators and an empty execution,
+    /// so document rules and normalization steps apply to them. Indicators
+    /// without a `protocol` get their surface's protocol.
+    #[cfg(any(feature = "validate", feature = "normalize"))]
+    pub(crate) fn to_document(&self) -> Document {
+        let mut indicators = self.indicators.clone();
+        for indicator in &mut indicators {
+            if indicator.protocol.is_none()
+                && let Some(entry) = crate::surface::lookup_surface(&indicator.surface)
+            {
+                indicator.protocol = Some(entry.protocol.to_string());
+            }
+        }
+        Document {
+            oatf: self.oatf.clone(),
+            schema: self.schema.clone(),
+            attack: Attack {
+                id: None,
+                name: None,
+                version: None,
+                status: None,
+                created: None,
+                modified: None,
+                author: None,
+                description: None,
+                grace_period: None,
+                license: None,
+                distribution: None,
+                severity: None,
+                impact: None,
+                classification: None,
+                references: None,
+                execution: Execution {
+                    mode: None,
+                    state: None,
+                    phases: None,
+                    actors: None,
+                    extensions: HashMap::new(),
+                },
+                attachments: None,
+                indicators: Some(indicators),
+                correlation: None,
+                safety: None,
+                supersedes: None,
+                superseded_by: None,
+                extensions: HashMap::new(),
+            },
+            oatf_is_first_key: self.oatf_is_first_key,
+        }
+    }
+}
+
 // ─── §2.3 Attack ─────────────────────────────────────────────────────────────
 
 /// The attack envelope containing metadata, execution, and indicators.
@@ -58,6 +150,13 @@ pub struct Attack {
     /// Grace period duration string (e.g., `"30d"`) for responsible disclosure.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub grace_period: Option<String>,
+    /// SPDX license expression for the document's content (e.g.,
+    /// `"CC-BY-4.0 OR Apache-2.0"`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// TLP marking limiting who the document may be shared with.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distribution: Option<Tlp>,
     /// Attack severity (scalar string or object with level + confidence).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub severity: Option<Severity>,
@@ -72,12 +171,25 @@ pub struct Attack {
     pub references: Option<Vec<Reference>>,
     /// Execution plan describing the attack phases and actors.
     pub execution: Execution,
+    /// Named binary or text blobs that state references as
+    /// `{{attachment:name}}`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attachments: Option<Vec<Attachment>>,
     /// Detection indicators for this attack.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub indicators: Option<Vec<Indicator>>,
     /// Verdict correlation configuration.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub correlation: Option<Correlation>,
+    /// Limits an execution engine must enforce while running this attack.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub safety: Option<Safety>,
+    /// IDs of the attacks this attack replaces.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supersedes: Option<Vec<String>>,
+    /// ID of the attack that replaces this one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub superseded_by: Option<String>,
     /// Extension fields (`x-*` prefixed).
     #[serde(flatten)]
     pub extensions: HashMap<String, Value>,
@@ -91,6 +203,48 @@ pub struct Correlation {
     /// Correlation logic (`any` or `all`). Defaults to `any` at evaluation time.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub logic: Option<CorrelationLogic>,
+    /// Stages that must each have a matched indicator for an `exploited`
+    /// verdict. When present, it takes precedence over `logic`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stages: Option<Vec<String>>,
+    /// Capture names whose values matched indicators must agree on before
+    /// their verdicts are combined (e.g., `[session_id]`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<Vec<String>>,
+}
+
+// ─── §2.3b Safety ────────────────────────────────────────────────────────────
+
+/// Guardrails enforced by execution engines. Exceeding any limit stops the
+/// run with a safety violation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Safety {
+    /// Maximum wall-clock duration of a run (duration string, e.g. `"5m"`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_runtime: Option<String>,
+    /// Maximum number of protocol messages the engine may send.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_messages: Option<i64>,
+    /// Hosts the engine must never connect to. A leading `*.` matches any
+    /// subdomain (e.g., `"*.prod.example.com"`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forbidden_hosts: Option<Vec<String>>,
+}
+
+// ─── §2.3c Attachment ────────────────────────────────────────────────────────
+
+/// A named blob carried inline in the document, decoded on demand (see
+/// [`attachments`](crate::attachments)).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attachment {
+    /// Attachment name, referenced from state as `{{attachment:name}}`.
+    pub name: String,
+    /// MIME type of the decoded content (e.g. `image/png`).
+    pub content_type: String,
+    /// How `data` is encoded.
+    pub encoding: AttachmentEncoding,
+    /// The encoded content.
+    pub data: String,
 }
 
 // ─── §2.4 Severity ───────────────────────────────────────────────────────────
@@ -210,8 +364,18 @@ pub struct Execution {
 pub struct Actor {
     /// Actor name identifier (e.g., `"attacker"`, `"victim"`).
     pub name: String,
+    /// Human-readable description of the actor's role.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
     /// Protocol mode (e.g., `"mcp/sse"`, `"a2a"`).
     pub mode: String,
+    /// Whether the target treats this actor as trusted. Absent means
+    /// [`ActorTrust::Untrusted`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trust: Option<ActorTrust>,
+    /// Where a harness binds the actor's transport.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<Endpoint>,
     /// Ordered list of execution phases for this actor.
     pub phases: Vec<Phase>,
     /// Extension fields (`x-*` prefixed).
@@ -219,6 +383,88 @@ pub struct Actor {
     pub extensions: HashMap<String, Value>,
 }
 
+impl Actor {
+    /// The actor's trust level, [`ActorTrust::Untrusted`] when absent.
+    pub fn trust(&self) -> ActorTrust {
+        self.trust.unwrap_or_default()
+    }
+}
+
+/// Transport binding hint for an [`Actor`]: a URL for network transports
+/// or a command line for stdio. Exactly one is set (V-060).
+///
+/// Server actors are served at the endpoint; client actors connect to it.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Endpoint {
+    /// `http`, `https`, `ws`, or `wss` URL (e.g. `http://127.0.0.1:8080/mcp`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Program and arguments of a process spoken to over stdio.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<Vec<String>>,
+}
+
+impl Endpoint {
+    /// The transport the endpoint implies, or `None` when neither or both
+    /// of `url` and `command` are set, or the URL scheme is not recognized.
+    pub fn transport(&self) -> Option<EndpointTransport> {
+        match (&self.url, &self.command) {
+            (Some(url), None) => {
+                let (scheme, _) = url.split_once("://")?;
+                match scheme.to_ascii_lowercase().as_str() {
+                    "http" | "https" => Some(EndpointTransport::Http),
+                    "ws" | "wss" => Some(EndpointTransport::WebSocket),
+                    _ => None,
+                }
+            }
+            (None, Some(_)) => Some(EndpointTransport::Stdio),
+            _ => None,
+        }
+    }
+}
+
+// ─── §2.6b Mode ──────────────────────────────────────────────────────────────
+
+/// A mode string split into its base mode and optional protocol version pin.
+///
+/// Modes may pin a protocol revision with an `@` suffix (e.g.,
+/// `"mcp_server@2025-03-26"`) so harnesses can negotiate the matching
+/// protocol version. Mode fields on [`Execution`], [`Actor`], and [`Phase`]
+/// keep the raw string; use [`Mode::parse`] to inspect it.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Mode {
+    /// Base mode without the version pin (e.g., `"mcp_server"`).
+    pub name: String,
+    /// Pinned protocol version, if any (e.g., `"2025-03-26"`).
+    pub version: Option<String>,
+}
+
+impl Mode {
+    /// Parse a mode string. Never fails; validation (V-005, V-036, V-046)
+    /// reports malformed or unknown modes.
+    pub fn parse(mode: &str) -> Self {
+        let (name, version) = crate::surface::split_mode_version(mode);
+        Mode {
+            name: name.to_string(),
+            version: version.map(str::to_string),
+        }
+    }
+
+    /// Protocol component of the mode (e.g., `"mcp"` for `"mcp_server"`).
+    pub fn protocol(&self) -> &str {
+        crate::event_registry::extract_protocol(&self.name)
+    }
+}
+
+impl std::fmt::Display for Mode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.version {
+            Some(version) => write!(f, "{}@{}", self.name, version),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
 // ─── §2.7 Phase ──────────────────────────────────────────────────────────────
 
 /// An execution phase within an actor's plan.
@@ -233,6 +479,11 @@ pub struct Phase {
     /// Protocol mode override for this phase.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mode: Option<String>,
+    /// Conditions on extracted values, keyed by extractor name (or
+    /// `actor.name` for another actor's), that must hold for the phase to be
+    /// entered. A phase whose conditions fail is skipped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requires: Option<MatchPredicate>,
     /// Phase execution state (JSON object describing protocol messages).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub state: Option<Value>,
@@ -242,9 +493,16 @@ pub struct Phase {
     /// Actions executed when this phase begins.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub on_enter: Option<Vec<Action>>,
+    /// Pacing applied between consecutive messages sent during this phase.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pacing: Option<Pacing>,
     /// Trigger condition that advances to the next phase.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub trigger: Option<Trigger>,
+    /// Actions executed when the trigger advances by its `after` timeout
+    /// rather than an event match, before the next phase begins.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_timeout: Option<Vec<Action>>,
     /// Extension fields (`x-*` prefixed).
     #[serde(flatten)]
     pub extensions: HashMap<String, Value>,
@@ -498,6 +756,20 @@ impl<'de> Deserialize<'de> for Action {
     }
 }
 
+// ─── §2.7b Pacing ───────────────────────────────────────────────────────────
+
+/// Delay between consecutive messages an execution engine sends in a phase,
+/// so simulations can mimic adversary tempo instead of flooding the target.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Pacing {
+    /// Fixed delay before each message after the first (duration string, e.g. `"2s"`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delay: Option<String>,
+    /// Upper bound of a random extra delay added to `delay` (duration string).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jitter: Option<String>,
+}
+
 // ─── §2.8 Trigger ────────────────────────────────────────────────────────────
 
 /// Condition that advances execution to the next phase.
@@ -512,6 +784,9 @@ pub struct Trigger {
     /// Predicate that the event payload must satisfy.
     #[serde(rename = "match", skip_serializing_if = "Option::is_none")]
     pub match_predicate: Option<MatchPredicate>,
+    /// Predicate that the event's [`Origin`] must satisfy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin: Option<MatchPredicate>,
     /// Duration string (e.g., `"5s"`) after which the trigger times out.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub after: Option<String>,
@@ -529,6 +804,26 @@ pub struct ProtocolEvent {
     pub qualifier: Option<String>,
     /// Event payload content.
     pub content: Value,
+    /// Who sent the event, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<Origin>,
+}
+
+/// Where a protocol event came from: the sending party and, optionally, its
+/// endpoint identity and whether the harness trusts it.
+///
+/// `origin` predicates on triggers and indicators match against this object,
+/// so the same payload can be told apart by its sender.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Origin {
+    /// Party that sent the event.
+    pub role: OriginRole,
+    /// Endpoint identity (e.g., a server URL or agent name).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    /// Whether the sender is inside the deployment's trust boundary.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trusted: Option<bool>,
 }
 
 // ─── §2.8b TriggerResult ────────────────────────────────────────────────────
@@ -553,12 +848,23 @@ pub enum TriggerResult {
 /// mutable reference on every evaluation. The SDK increments `event_count`
 /// only when the incoming event fully matches (base type + qualifier +
 /// predicate), which prevents the over-count bug inherent in external counting.
+///
+/// More tracking may be added, so the struct is `#[non_exhaustive]`; create it
+/// with [`Default`] or [`TriggerState::with_event_count`].
 #[derive(Clone, Debug, Default)]
+#[non_exhaustive]
 pub struct TriggerState {
     /// Number of events that have fully matched so far.
     pub event_count: u64,
 }
 
+impl TriggerState {
+    /// State resuming a trigger that has already matched `event_count` events.
+    pub fn with_event_count(event_count: u64) -> Self {
+        TriggerState { event_count }
+    }
+}
+
 // ─── §2.9 Extractor ─────────────────────────────────────────────────────────
 
 /// A data extractor that captures values from protocol messages.
@@ -578,7 +884,10 @@ pub struct Extractor {
 // ─── §2.10 MatchPredicate ───────────────────────────────────────────────────
 
 /// A match predicate is a map from dot-path field references to conditions.
-pub type MatchPredicate = HashMap<String, MatchEntry>;
+///
+/// Entries keep their document order, so evaluation, serialization, and
+/// diagnostics visit them in the order they were written.
+pub type MatchPredicate = IndexMap<String, MatchEntry>;
 
 /// Either a scalar Value (equality check) or a MatchCondition object.
 #[derive(Clone, Debug)]
@@ -615,6 +924,7 @@ impl<'de> Deserialize<'de> for MatchEntry {
                     "gte",
                     "lte",
                     "exists",
+                    "json_patch",
                 ];
                 if map.keys().any(|k| operator_keys.contains(&k.as_str())) {
                     let cond: MatchCondition =
@@ -664,13 +974,36 @@ pub struct MatchCondition {
     /// Field existence check.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub exists: Option<bool>,
+    /// JSON Patch operation match, for patch documents such as the `delta`
+    /// of an AG-UI `STATE_DELTA` event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json_patch: Option<PatchMatch>,
+}
+
+/// Matches a JSON Patch (RFC 6902) document — a list of operations, or a
+/// single operation — when any one operation satisfies every present field.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PatchMatch {
+    /// The operation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub op: Option<PatchOp>,
+    /// JSON Pointer the operation targets. A `*` segment matches any one
+    /// segment and a final `**` segment any number of segments, e.g.
+    /// `/users/*/role` or `/permissions/**`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Condition on the operation's `value`, with [`MatchPredicate`]
+    /// semantics (`exists: false` matches operations without a value).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<Box<MatchEntry>>,
 }
 
 // ─── §2.12 Indicator ────────────────────────────────────────────────────────
 
 /// A detection indicator that matches against protocol messages.
 ///
-/// Exactly one of `pattern`, `expression`, or `semantic` should be present.
+/// Exactly one of `pattern`, `expression`, `semantic`, or `frequency` should
+/// be present.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Indicator {
     /// Unique indicator identifier (used in verdict reporting).
@@ -693,20 +1026,111 @@ pub struct Indicator {
     /// Semantic/intent-based detection.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub semantic: Option<SemanticMatch>,
+    /// Count-based detection over a window of protocol events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency: Option<FrequencyMatch>,
+    /// Predicate that the evaluated event's [`Origin`] must satisfy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin: Option<MatchPredicate>,
+    /// Values to capture from matching messages, for `correlation.key`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capture: Option<Vec<Capture>>,
     /// Confidence percentage (0–100) for this indicator.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub confidence: Option<i64>,
     /// Indicator-level severity override.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub severity: Option<SeverityLevel>,
-    /// Known false-positive descriptions.
+    /// Kill-chain stage the indicator detects (e.g., `delivery`,
+    /// `exfiltration`).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub false_positives: Option<Vec<String>>,
+    pub stage: Option<String>,
+    /// Known benign situations in which the indicator matches.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub false_positives: Option<Vec<FalsePositive>>,
     /// Extension fields (`x-*` prefixed).
     #[serde(flatten)]
     pub extensions: HashMap<String, Value>,
 }
 
+// ─── §2.12a FalsePositive ───────────────────────────────────────────────────
+
+/// A known benign situation in which an indicator matches.
+///
+/// Written either as a bare description string or as an object with a
+/// `description` and a `when` predicate identifying the benign case. A
+/// match whose message satisfies `when` evaluates to
+/// [`IndicatorResult::MatchedLikelyBenign`].
+#[derive(Clone, Debug)]
+pub struct FalsePositive {
+    /// Human-readable description of the benign case.
+    pub description: String,
+    /// Predicate over the evaluated message identifying the benign case.
+    pub when: Option<MatchPredicate>,
+}
+
+impl Serialize for FalsePositive {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        match &self.when {
+            None => serializer.serialize_str(&self.description),
+            Some(when) => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("description", &self.description)?;
+                map.serialize_entry("when", when)?;
+                map.end()
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for FalsePositive {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Structured {
+            description: String,
+            when: Option<MatchPredicate>,
+        }
+
+        let value = Value::deserialize(deserializer)?;
+        match value {
+            Value::String(description) => Ok(FalsePositive {
+                description,
+                when: None,
+            }),
+            Value::Object(_) => {
+                let s: Structured =
+                    serde_json::from_value(value).map_err(serde::de::Error::custom)?;
+                Ok(FalsePositive {
+                    description: s.description,
+                    when: s.when,
+                })
+            }
+            _ => Err(serde::de::Error::custom(
+                "false_positives entries must be a string or an object",
+            )),
+        }
+    }
+}
+
+// ─── §2.12b Capture ─────────────────────────────────────────────────────────
+
+/// A value an indicator captures from the message it matched, such as a
+/// session ID or tool name, so correlation can tell related matches from
+/// unrelated traffic.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Capture {
+    /// Name of the captured value, referenced by `correlation.key`.
+    pub name: String,
+    /// Extraction method (JSONPath or regex).
+    #[serde(rename = "type")]
+    pub capture_type: ExtractorType,
+    /// JSONPath expression or regex pattern with a capture group, applied to
+    /// the surface's payload.
+    pub selector: String,
+}
+
 // ─── §2.13 PatternMatch ─────────────────────────────────────────────────────
 
 /// A pattern match indicator. Supports standard and shorthand form.
@@ -880,6 +1304,7 @@ impl Condition {
                     "gte",
                     "lte",
                     "exists",
+                    "json_patch",
                 ];
                 if map.keys().any(|k| operator_keys.contains(&k.as_str()))
                     && let Ok(cond) = serde_json::from_value::<MatchCondition>(v.clone())
@@ -935,6 +1360,32 @@ pub struct SemanticMatch {
     pub examples: Option<SemanticExamples>,
 }
 
+// ─── §2.15a FrequencyMatch ───────────────────────────────────────────────────
+
+/// A count-based detection indicator: matches when at least `threshold`
+/// matching events occur within `window`.
+///
+/// Events are matched like trigger events: by base type, optional
+/// `:qualifier`, and `match` predicate. With `group_by`, events are counted
+/// separately per value at that path in the event content. Frequency
+/// indicators are stateful and are evaluated by
+/// [`StreamEvaluator`](crate::evaluate::stream::StreamEvaluator).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FrequencyMatch {
+    /// Protocol event to count (e.g., `"tools/call:read_file"`).
+    pub event: String,
+    /// Predicate that the event content must satisfy to be counted.
+    #[serde(rename = "match", skip_serializing_if = "Option::is_none")]
+    pub match_predicate: Option<MatchPredicate>,
+    /// Duration string (e.g., `"60s"`) of the sliding window.
+    pub window: String,
+    /// Number of events within the window at which the indicator matches.
+    pub threshold: i64,
+    /// Simple dot path into the event content to count by.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_by: Option<String>,
+}
+
 // ─── §2.16 SemanticExamples ─────────────────────────────────────────────────
 
 /// Positive and negative examples for semantic matching guidance.
@@ -1001,6 +1452,78 @@ pub struct IndicatorVerdict {
     /// Source that produced this verdict.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source: Option<String>,
+    /// Values captured by the indicator's `capture` extractors, by name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub captures: Option<BTreeMap<String, String>>,
+    /// Why a `skipped` indicator was not evaluated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skip_reason: Option<SkipReason>,
+}
+
+/// Why an indicator was skipped, in machine-readable form.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum SkipReason {
+    /// No evaluator of the kind the indicator needs is available.
+    MissingEvaluator {
+        /// The evaluator the indicator needs.
+        evaluator: EvaluatorKind,
+    },
+    /// The expression calls functions the CEL evaluator does not support.
+    UnsupportedFunctions {
+        /// The unsupported functions and methods, sorted.
+        functions: Vec<String>,
+    },
+    /// The indicator's language (its `x-language` extension) is not one the
+    /// semantic evaluator handles.
+    UnsupportedLanguage {
+        /// The indicator's language tag.
+        language: String,
+    },
+    /// The input is larger than the evaluator accepts.
+    InputTooLarge {
+        /// Input size in bytes.
+        size: usize,
+        /// The evaluator's limit in bytes.
+        limit: usize,
+    },
+    /// The evaluator rejected the expression as outside what it supports.
+    UnsupportedExpression,
+    /// No verdict was provided for the indicator.
+    NotEvaluated,
+}
+
+impl std::fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SkipReason::MissingEvaluator { evaluator } => match evaluator {
+                EvaluatorKind::Cel => write!(f, "CEL evaluator not available"),
+                EvaluatorKind::Semantic => write!(f, "Semantic evaluator not available"),
+                EvaluatorKind::Stream => {
+                    write!(f, "Frequency indicators require a stream evaluator")
+                }
+            },
+            SkipReason::UnsupportedFunctions { functions } => write!(
+                f,
+                "CEL evaluator does not support: {}",
+                functions.join(", ")
+            ),
+            SkipReason::UnsupportedLanguage { language } => write!(
+                f,
+                "semantic evaluator does not support language '{}'",
+                language
+            ),
+            SkipReason::InputTooLarge { size, limit } => write!(
+                f,
+                "input of {} bytes exceeds the evaluator's {}-byte limit",
+                size, limit
+            ),
+            SkipReason::UnsupportedExpression => {
+                write!(f, "expression not supported by the evaluator")
+            }
+            SkipReason::NotEvaluated => write!(f, "No evaluation result provided"),
+        }
+    }
 }
 
 /// Attack-level verdict computed from indicator verdicts.
@@ -1015,6 +1538,9 @@ pub struct AttackVerdict {
     pub indicator_verdicts: Vec<IndicatorVerdict>,
     /// Summary counts of indicator results.
     pub evaluation_summary: EvaluationSummary,
+    /// Summary counts per indicator stage, when any indicator has a stage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stages: Option<Vec<StageSummary>>,
     /// ISO 8601 timestamp of the verdict.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timestamp: Option<String>,
@@ -1023,6 +1549,15 @@ pub struct AttackVerdict {
     pub source: Option<String>,
 }
 
+/// Indicator result counts for one stage.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StageSummary {
+    /// Stage name.
+    pub stage: String,
+    /// Counts for the indicators of this stage.
+    pub summary: EvaluationSummary,
+}
+
 /// Summary counts of indicator evaluation results.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct EvaluationSummary {
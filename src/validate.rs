@@ -1,15 +1,30 @@
-//! Document validation against conformance rules V-001 through V-045.
+//! Document validation against conformance rules V-001 through V-062.
 //!
 //! Returns **all** errors and warnings, not just the first. Validation does not
 //! modify the document.
 
+use crate::context::ResolvedContext;
+use crate::enums::{Category, OriginRole, Profile, RegexDialect, Status};
 use crate::error::*;
-use crate::event_registry::{extract_protocol, is_event_valid_for_mode, strip_event_qualifier};
-use crate::surface::{KNOWN_MODES, KNOWN_PROTOCOLS, lookup_surface};
+use crate::event_registry::{
+    EVENT_MODE_REGISTRY, extract_protocol, is_event_valid_for_mode, lookup_event,
+    strip_event_qualifier,
+};
+use crate::primitives::{
+    TARGET_DECODERS, check_regex_dialect, parse_duration, resolve_wildcard_path_ref,
+};
+use crate::rule_docs::RULE_DOCS;
+use crate::spec::rule_info;
+use crate::surface::{
+    CATEGORY_SURFACES, KNOWN_MODES, KNOWN_PROTOCOLS, SURFACE_REGISTRY, SurfaceEntry,
+    is_known_protocol_version, lookup_surface, split_mode_version,
+};
 use crate::types::*;
 use regex::Regex;
 use std::sync::LazyLock;
 
+pub mod rules;
+
 // ─── Helper: construct ValidationError with auto-populated spec_ref ─────────
 
 fn verr(rule: &str, path: impl Into<String>, message: impl Into<String>) -> ValidationError {
@@ -18,70 +33,130 @@ fn verr(rule: &str, path: impl Into<String>, message: impl Into<String>) -> Vali
         spec_ref: spec_ref_for_rule(rule).to_string(),
         path: path.into(),
         message: message.into(),
+        suggestion: None,
     }
 }
 
 fn spec_ref_for_rule(rule: &str) -> &'static str {
-    match rule {
-        "V-001" => "§11.1.1",
-        "V-002" => "§11.1.2",
-        "V-003" => "§11.1.3",
-        "V-004" => "§11.1.4",
-        "V-005" => "§11.1.5",
-        "V-006" => "§11.1.6",
-        "V-007" => "§11.1.8",
-        "V-008" => "§11.1.8",
-        "V-009" => "§11.1.8",
-        "V-010" => "§11.1.10",
-        "V-011" => "§11.1.8",
-        "V-012" => "§11.1.11",
-        "V-013" => "§5.7",
-        "V-014" => "§5.7",
-        "V-015" => "§5.7",
-        "V-016" => "§5.7",
-        "V-017" => "§4.3",
-        "V-018" => "§7",
-        "V-019" => "§5.3",
-        "V-020" => "§11.1.1",
-        "V-021" => "§6.2",
-        "V-022" => "§6.4",
-        "V-023" => "§4.2",
-        "V-024" => "§6.1",
-        "V-025" => "§6.1",
-        "V-026" => "§6.3",
-        "V-027" => "§5.4",
-        "V-028" => "§5.1",
-        "V-029" => "§7",
-        "V-030" => "§5.1",
-        "V-031" => "§5.1",
-        "V-032" => "§5.5",
-        "V-033" => "§11.1.14",
-        "V-034" => "§11.1.15",
-        "V-035" => "§11.1.16",
-        "V-036" => "§5.1",
-        "V-037" => "§4.2",
-        "V-038" => "§5.3",
-        "V-039" => "§5.5",
-        "V-040" => "§11.1.8",
-        "V-041" => "§11.1.17",
-        "V-042" => "§5.2",
-        "V-043" => "§5.2",
-        "V-044" => "§5.5",
-        "V-045" => "§5.2",
-        _ => "",
-    }
+    rule_info(rule).map_or("", |r| r.spec_ref)
+}
+
+// ─── Helper: "did you mean" suggestions ─────────────────────────────────────
+
+/// Action keys defined by the format; any other key is binding-specific.
+const ACTION_KEYS: &[&str] = &["send_notification", "log", "send_elicitation"];
+
+/// The candidate closest to `value` by case-insensitive Levenshtein
+/// distance, if it is within a third of `value`'s length (at least one
+/// edit). Ties go to the earliest candidate.
+fn closest<'a>(value: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let lower = value.to_lowercase();
+    let limit = (value.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .filter(|c| *c != value)
+        .map(|c| (levenshtein(&lower, c), c))
+        .filter(|(distance, _)| *distance <= limit)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, c)| c)
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// The known mode closest to `mode`, keeping its `@version` suffix.
+fn suggest_mode(mode: &str) -> Option<String> {
+    let (base, version) = split_mode_version(mode);
+    let known = closest(base, KNOWN_MODES.iter().copied())?;
+    Some(match version {
+        Some(version) => format!("{}@{}", known, version),
+        None => known.to_string(),
+    })
+}
+
+/// The registered event valid for `mode` closest to `event`, keeping its
+/// `:qualifier` suffix.
+fn suggest_event(event: &str, mode: &str) -> Option<String> {
+    let base = strip_event_qualifier(event);
+    let valid = EVENT_MODE_REGISTRY
+        .iter()
+        .filter(|e| e.valid_modes.contains(&mode))
+        .map(|e| e.event);
+    let known = closest(base, valid)?;
+    Some(format!("{}{}", known, &event[base.len()..]))
+}
+
+// ─── Rule documentation ─────────────────────────────────────────────────────
+
+/// Documentation for a conformance rule, as returned by [`explain`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RuleDoc {
+    /// Rule identifier (e.g., `"V-018"`).
+    pub id: &'static str,
+    /// Specification section reference (e.g., `"§7"`).
+    pub spec_ref: &'static str,
+    /// One-line summary of what the rule requires.
+    pub description: &'static str,
+    /// Why the rule exists.
+    pub rationale: &'static str,
+    /// YAML fragment that violates the rule.
+    pub failing_example: &'static str,
+    /// The same fragment, corrected.
+    pub passing_example: &'static str,
+    /// How to resolve a violation.
+    pub fix: &'static str,
+}
+
+/// Explain a conformance rule by ID (e.g., `"V-018"`).
+///
+/// Returns `None` for IDs that are not conformance rules. Examples are YAML
+/// fragments showing only the part of a document the rule inspects.
+pub fn explain(rule_id: &str) -> Option<RuleDoc> {
+    let info = rule_info(rule_id)?;
+    let doc = RULE_DOCS.iter().find(|d| d.id == info.id)?;
+    Some(RuleDoc {
+        id: info.id,
+        spec_ref: info.spec_ref,
+        description: info.description,
+        rationale: doc.rationale,
+        failing_example: doc.failing,
+        passing_example: doc.passing,
+        fix: doc.fix,
+    })
 }
 
 // ─── Cached regexes ─────────────────────────────────────────────────────────
 
-static MODE_RE: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"^[a-z][a-z0-9_]*_(server|client)$").unwrap());
+static MODE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^[a-z][a-z0-9_]*_(server|client)(@[A-Za-z0-9][A-Za-z0-9._-]*)?$").unwrap()
+});
 
 static SNAKE_CASE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[a-z][a-z0-9_]*$").unwrap());
 
 static ATTACK_ID_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"^[A-Z][A-Z0-9-]*-[0-9]{3,}$").unwrap());
 
+/// An SPDX license ID (optionally `+`) or `LicenseRef-` reference.
+static LICENSE_ID_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:(?:DocumentRef-[A-Za-z0-9.-]+:)?LicenseRef-[A-Za-z0-9.-]+|[A-Za-z0-9.-]+\+?)$")
+        .unwrap()
+});
+
+static LICENSE_EXCEPTION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[A-Za-z0-9.-]+$").unwrap());
+
 static INDICATOR_ID_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"^[A-Z][A-Z0-9-]*-[0-9]{3,}-[0-9]{2,}$").unwrap());
 
@@ -98,121 +173,310 @@ static SHORTHAND_DURATION_RE: LazyLock<Regex> =
 static ISO_DURATION_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"^P([0-9]+D)?(T([0-9]+H)?([0-9]+M)?([0-9]+S)?)?$").unwrap());
 
+static CONTENT_TYPE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^[a-zA-Z0-9][a-zA-Z0-9!#$&^_.+-]*/[a-zA-Z0-9][a-zA-Z0-9!#$&^_.+-]*(\s*;.*)?$")
+        .unwrap()
+});
+
 static PROTOCOL_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[a-z][a-z0-9_]*$").unwrap());
 
-/// Validate a parsed document against all 45 conformance rules (V-001..V-045).
+/// Validate a parsed document against conformance rules V-001..V-051 and
+/// V-053..V-058 and V-060..V-062, running every rule of [`RuleEngine::builtin`](rules::RuleEngine::builtin).
 /// Returns a ValidationResult containing all errors and warnings found.
+///
+/// Diagnostics are consolidated with the default [`DiagnosticPolicy`], so
+/// identical diagnostics are reported once; [`RuleEngine::run`](rules::RuleEngine::run)
+/// returns them as the rules produce them.
 pub fn validate(doc: &Document) -> ValidationResult {
-    let mut errors = Vec::new();
-    let mut warnings = Vec::new();
-
-    w001_oatf_key_ordering(doc, &mut warnings);
-    v001_oatf_version(doc, &mut errors);
-    // V-003 (attack present) and V-004 (required fields) are enforced by
-    // serde deserialization during parse — no runtime check needed here.
-    v005_enum_values(doc, &mut errors);
-    v006_indicators_non_empty(doc, &mut errors);
-    v007_phases_non_empty(doc, &mut errors);
-    v008_terminal_phase(doc, &mut errors);
-    v009_first_phase_state(doc, &mut errors);
-    v010_unique_indicator_ids(doc, &mut errors);
-    v011_unique_phase_names(doc, &mut errors);
-    v012_exactly_one_detection_key(doc, &mut errors);
-    v012_pattern_form_ambiguity(doc, &mut errors);
-    v013_regex_valid(doc, &mut errors);
-    v014_cel_valid(doc, &mut errors);
-    v015_jsonpath_valid(doc, &mut errors);
-    v016_template_syntax(doc, &mut errors);
-    v017_severity_confidence(doc, &mut errors);
-    v018_surface_protocol(doc, &mut errors, &mut warnings);
-    v019_count_match_require_event(doc, &mut errors);
-    v021_target_path_syntax(doc, &mut errors);
-    v022_semantic_threshold(doc, &mut errors);
-    v023_attack_id_format(doc, &mut errors);
-    v024_indicator_id_format(doc, &mut errors);
-    v025_indicator_confidence(doc, &mut errors);
-    v026_expression_variables_paths(doc, &mut errors);
-    v027_match_predicate_paths(doc, &mut errors);
-    v028_conditional_requiredness(doc, &mut errors);
-    v029_event_mode_validity(doc, &mut errors, &mut warnings);
-    v030_mutual_exclusion(doc, &mut errors);
-    v031_multi_actor_constraints(doc, &mut errors);
-    v032_cross_actor_refs(doc, &mut errors);
-    v033_content_synthesize_exclusivity(doc, &mut errors);
-    v034_catch_all_constraints(doc, &mut errors);
-    v035_synthesize_prompt(doc, &mut errors);
-    v036_mode_protocol_pattern(doc, &mut errors, &mut warnings);
-    v037_version_positive(doc, &mut errors);
-    v038_trigger_after_duration(doc, &mut errors);
-    v039_extractor_name_pattern(doc, &mut errors);
-    v040_extractors_non_empty(doc, &mut errors);
-    v041_expression_variable_keys(doc, &mut errors);
-    v042_trigger_event_or_after(doc, &mut errors);
-    v043_binding_specific_action_keys(doc, &mut errors);
-    v044_regex_extractor_capture_group(doc, &mut errors);
-    v045_on_enter_non_empty(doc, &mut errors);
-
-    w004_undeclared_extractor_refs(doc, &mut warnings);
-    w005_indicator_protocol_mismatch(doc, &mut warnings);
-
-    ValidationResult { errors, warnings }
+    validate_with_policy(doc, &DiagnosticPolicy::default())
 }
 
-static TEMPLATE_VAR_RE: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"\{\{([a-zA-Z_][a-zA-Z0-9_.]*)\}\}").unwrap());
-
-// ─── Helper: collect all phases from all execution forms ─────────────────────
-
-struct ActorInfo<'a> {
-    #[allow(dead_code)]
-    name: String,
-    mode: Option<&'a str>,
-    phases: &'a [Phase],
-    path_prefix: String,
+/// [`validate`] with diagnostics consolidated by `policy` instead of the
+/// default.
+pub fn validate_with_policy(doc: &Document, policy: &DiagnosticPolicy) -> ValidationResult {
+    rules::BUILTIN
+        .run(doc, &rules::RunOptions::default())
+        .consolidate(policy)
 }
 
-fn collect_actors(doc: &Document) -> Vec<ActorInfo<'_>> {
-    let exec = &doc.attack.execution;
-    if let Some(actors) = &exec.actors {
-        actors
+/// Re-run the rules of [`validate`] that a change at any of `changed_paths`
+/// can affect, for editors revalidating after each edit.
+///
+/// Paths are document paths as diagnostics report them (e.g.
+/// `attack.indicators[2].pattern.regex`); a path covers everything under it,
+/// and `""` is the whole document. The result holds the findings of the
+/// affected rules only: callers replace the previous findings of those rules
+/// ([`Rule::is_affected_by`](rules::Rule::is_affected_by)) and keep the rest.
+pub fn revalidate<S: AsRef<str>>(doc: &Document, changed_paths: &[S]) -> ValidationResult {
+    let options = rules::RunOptions {
+        changed_paths: Some(
+            changed_paths
+                .iter()
+                .map(|path| path.as_ref().to_string())
+                .collect(),
+        ),
+        ..rules::RunOptions::default()
+    };
+    rules::BUILTIN
+        .run(doc, &options)
+        .consolidate(&DiagnosticPolicy::default())
+}
+
+/// [`validate`] a document against a [`Profile`]: every rule of
+/// [`validate`], plus V-052 checking that the document carries the content
+/// the profile requires.
+pub fn validate_profile(doc: &Document, profile: Profile) -> ValidationResult {
+    let mut result = validate(doc);
+    v052_profile_content(doc, profile, &mut result.errors);
+    result.consolidate(&DiagnosticPolicy::default())
+}
+
+/// [`validate`] a document for deployment on engines limited to `dialect`:
+/// every rule of [`validate`], with V-013 also reporting regular expressions
+/// that use constructs outside the dialect (see
+/// [`check_regex_dialect`](crate::primitives::check_regex_dialect)).
+pub fn validate_regex_dialect(doc: &Document, dialect: RegexDialect) -> ValidationResult {
+    let mut result = validate(doc);
+    v013_regex_dialect(doc, dialect, &mut result.errors);
+    result.consolidate(&DiagnosticPolicy::default())
+}
+
+/// Size and complexity budgets for execution state, checked by
+/// [`validate_resource_limits`] for `execution.state` and every phase state.
+/// A limit of `None` is not checked.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResourceLimits {
+    /// Largest state, in bytes of compact JSON.
+    pub max_state_bytes: Option<usize>,
+    /// Most entries in a state's `tools` list.
+    pub max_tools: Option<usize>,
+    /// Longest string anywhere in a state, such as a tool description or
+    /// prompt text, in bytes.
+    pub max_string_length: Option<usize>,
+    /// Deepest nesting of objects and arrays in a state.
+    pub max_depth: Option<usize>,
+    /// How an exceeded limit is reported: as a V-059 error, or as a W-009
+    /// warning that leaves the document valid.
+    pub severity: DiagnosticSeverity,
+}
+
+impl Default for ResourceLimits {
+    /// 1 MiB of state, 100 tools, 64 KiB strings, 32 levels of nesting,
+    /// reported as errors.
+    fn default() -> Self {
+        ResourceLimits {
+            max_state_bytes: Some(1024 * 1024),
+            max_tools: Some(100),
+            max_string_length: Some(64 * 1024),
+            max_depth: Some(32),
+            severity: DiagnosticSeverity::Error,
+        }
+    }
+}
+
+/// [`validate`] a document against resource budgets before handing it to a
+/// harness or gateway: every rule of [`validate`], plus V-059 (or W-009,
+/// per [`ResourceLimits::severity`]) for each state exceeding `limits`.
+pub fn validate_resource_limits(doc: &Document, limits: &ResourceLimits) -> ValidationResult {
+    let mut result = validate(doc);
+    for (path, message) in v059_resource_limits(doc, limits) {
+        match limits.severity {
+            DiagnosticSeverity::Error => result.errors.push(verr("V-059", path, message)),
+            DiagnosticSeverity::Warning => result.warnings.push(Diagnostic {
+                severity: DiagnosticSeverity::Warning,
+                code: "W-009".to_string(),
+                path: Some(path),
+                message,
+                suggestion: None,
+            }),
+        }
+    }
+    result.consolidate(&DiagnosticPolicy::default())
+}
+
+// ─── Diagnostic consolidation ───────────────────────────────────────────────
+
+/// Which diagnostics [`DiagnosticPolicy`] treats as duplicates.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Dedupe {
+    /// Keep every diagnostic.
+    Off,
+    /// Keep one of each diagnostic with the same rule, path, and message.
+    #[default]
+    Identical,
+    /// Keep one diagnostic per rule and path, whatever its message.
+    /// Diagnostics without a path are kept once per rule and message.
+    ByPath,
+}
+
+/// How [`ValidationResult::consolidate`] trims the diagnostics of a
+/// pathological document, where one rule can fire thousands of times.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DiagnosticPolicy {
+    /// Which diagnostics to report once.
+    pub dedupe: Dedupe,
+    /// Order diagnostics by rule, in the order each rule first reported,
+    /// keeping document order within each rule.
+    pub group: bool,
+    /// Most diagnostics kept per rule. The rest are replaced by one
+    /// diagnostic of the same rule, at the first path left out, saying how
+    /// many more there were. `None` keeps every diagnostic.
+    pub max_per_rule: Option<usize>,
+}
+
+impl DiagnosticPolicy {
+    /// Keep every diagnostic, in the order the rules report them.
+    pub fn keep_all() -> Self {
+        DiagnosticPolicy {
+            dedupe: Dedupe::Off,
+            group: false,
+            max_per_rule: None,
+        }
+    }
+}
+
+impl ValidationResult {
+    /// Deduplicate, group, and truncate the errors and warnings per
+    /// `policy`. Truncation never drops all of a rule's errors, so a result
+    /// stays invalid: with `max_per_rule: Some(0)`, each rule's errors
+    /// become a single summary error.
+    pub fn consolidate(self, policy: &DiagnosticPolicy) -> Self {
+        let errors = consolidate(
+            self.errors,
+            policy,
+            |e| (e.rule.as_str(), Some(e.path.as_str()), e.message.as_str()),
+            |first, more| ValidationError {
+                rule: first.rule.clone(),
+                spec_ref: first.spec_ref.clone(),
+                path: first.path.clone(),
+                message: format!("and {} more {} errors", more, first.rule),
+                suggestion: None,
+            },
+        );
+        let warnings = consolidate(
+            self.warnings,
+            policy,
+            |w| (w.code.as_str(), w.path.as_deref(), w.message.as_str()),
+            |first, more| Diagnostic {
+                severity: first.severity.clone(),
+                code: first.code.clone(),
+                path: first.path.clone(),
+                message: format!("and {} more {} warnings", more, first.code),
+                suggestion: None,
+            },
+        );
+        ValidationResult { errors, warnings }
+    }
+}
+
+/// Apply `policy` to diagnostics with the rule, path, and message `key`
+/// gives; `summary` stands in for the `more` left out, starting at the one
+/// it is given.
+fn consolidate<T>(
+    items: Vec<T>,
+    policy: &DiagnosticPolicy,
+    key: impl Fn(&T) -> (&str, Option<&str>, &str),
+    summary: impl Fn(&T, usize) -> T,
+) -> Vec<T> {
+    let mut items = items;
+    if policy.dedupe != Dedupe::Off {
+        let mut seen = std::collections::HashSet::new();
+        let first: Vec<bool> = items
             .iter()
-            .enumerate()
-            .map(|(i, a)| ActorInfo {
-                name: a.name.clone(),
-                mode: Some(a.mode.as_str()),
-                phases: &a.phases,
-                path_prefix: format!("attack.execution.actors[{}]", i),
+            .map(|item| match (policy.dedupe, key(item)) {
+                (Dedupe::ByPath, (rule, Some(path), _)) => seen.insert((rule, Some(path), "")),
+                (_, key) => seen.insert(key),
             })
-            .collect()
-    } else if let Some(phases) = &exec.phases {
-        vec![ActorInfo {
-            name: "default".to_string(),
-            mode: exec.mode.as_deref(),
-            phases: phases.as_slice(),
-            path_prefix: "attack.execution".to_string(),
-        }]
-    } else if exec.state.is_some() {
-        // Single-phase form: we represent as a virtual single phase
-        vec![]
-    } else {
-        vec![]
-    }
+            .collect();
+        let mut first = first.into_iter();
+        items.retain(|_| first.next().unwrap_or(true));
+    }
+    if policy.group {
+        let mut rules: Vec<String> = Vec::new();
+        for item in &items {
+            let rule = key(item).0;
+            if !rules.iter().any(|r| r == rule) {
+                rules.push(rule.to_string());
+            }
+        }
+        // Stable, so document order is kept within each rule.
+        items.sort_by_key(|item| rules.iter().position(|r| r == key(item).0));
+    }
+    let Some(max) = policy.max_per_rule else {
+        return items;
+    };
+    let mut totals: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+    for item in &items {
+        *totals.entry(key(item).0.to_string()).or_default() += 1;
+    }
+    let mut seen: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+    let mut out = Vec::new();
+    for item in items {
+        let rule = key(&item).0.to_string();
+        let total = totals[&rule];
+        let n = seen.entry(rule).or_default();
+        *n += 1;
+        if total <= max || *n <= max {
+            out.push(item);
+        } else if *n == max + 1 {
+            out.push(summary(&item, total - max));
+        }
+    }
+    out
+}
+
+const INDICATOR_SET_RULES: &[&str] = &[
+    "W-001", "V-001", "V-005", "V-006", "V-010", "V-012", "V-013", "V-014", "V-018", "V-021",
+    "V-022", "V-024", "V-025", "V-026", "V-028", "V-036", "V-041", "V-050", "V-051", "V-053",
+    "V-054", "V-055",
+];
+
+/// Validate a parsed [`IndicatorSet`] against the rules that apply to
+/// indicators outside an attack: V-001, V-005, V-006, V-010, V-012, V-013,
+/// V-014, V-018, V-021, V-022, V-024, V-025, V-026, V-028, V-036, V-041,
+/// V-050, V-051, V-053, V-054, V-055, and W-001.
+///
+/// An indicator's protocol defaults to its surface's, so `protocol` is only
+/// required (V-028) for surfaces outside the registry. Paths are relative to
+/// the set (e.g. `indicators[0].pattern.target`).
+pub fn validate_indicator_set(set: &IndicatorSet) -> ValidationResult {
+    let doc = set.to_document();
+    let options = rules::RunOptions {
+        rules: Some(
+            INDICATOR_SET_RULES
+                .iter()
+                .map(|id| id.to_string())
+                .collect(),
+        ),
+        ..rules::RunOptions::default()
+    };
+    let ValidationResult {
+        mut errors,
+        mut warnings,
+    } = rules::BUILTIN.run(&doc, &options);
+
+    let relative = |path: &str| path.strip_prefix("attack.").unwrap_or(path).to_string();
+    for error in &mut errors {
+        error.path = relative(&error.path);
+        if error.rule == "V-028" {
+            error.message =
+                "indicator.protocol is required when the surface has no registered protocol"
+                    .to_string();
+        }
+    }
+    for warning in &mut warnings {
+        warning.path = warning.path.as_deref().map(relative);
+    }
+    ValidationResult { errors, warnings }.consolidate(&DiagnosticPolicy::default())
 }
 
-fn resolve_mode(
-    doc: &Document,
-    actor_mode: Option<&str>,
-    phase_mode: Option<&str>,
-) -> Option<String> {
-    phase_mode
-        .or(actor_mode)
-        .or(doc.attack.execution.mode.as_deref())
-        .map(|s| s.to_string())
-}
+static TEMPLATE_VAR_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{([a-zA-Z_][a-zA-Z0-9_.]*)\}\}").unwrap());
 
 // ─── V-001 ──────────────────────────────────────────────────────────────────
 
-fn v001_oatf_version(doc: &Document, errors: &mut Vec<ValidationError>) {
+fn v001_oatf_version(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
     if doc.oatf != "0.1" {
         errors.push(verr(
             "V-001",
@@ -224,29 +488,36 @@ fn v001_oatf_version(doc: &Document, errors: &mut Vec<ValidationError>) {
 
 // ─── V-005 ──────────────────────────────────────────────────────────────────
 
-fn v005_enum_values(doc: &Document, errors: &mut Vec<ValidationError>) {
+fn v005_enum_values(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
     // V-005 validates execution.mode pattern; V-036 validates actor/phase modes.
     if let Some(mode) = &doc.attack.execution.mode
         && !MODE_RE.is_match(mode)
     {
-        errors.push(verr(
+        let mut error = verr(
             "V-005",
             "attack.execution.mode",
             format!(
-                "mode must match [a-z][a-z0-9_]*_(server|client), got '{}'",
+                "mode must match [a-z][a-z0-9_]*_(server|client)[@version], got '{}'",
                 mode
             ),
-        ));
+        );
+        error.suggestion = suggest_mode(mode);
+        errors.push(error);
     }
 
     if let Some(indicators) = &doc.attack.indicators {
         for (i, ind) in indicators.iter().enumerate() {
             if lookup_surface(&ind.surface).is_none() {
-                errors.push(verr(
+                let mut error = verr(
                     "V-005",
                     format!("attack.indicators[{}].surface", i),
                     format!("unknown surface: '{}'", ind.surface),
-                ));
+                );
+                error.suggestion =
+                    closest(&ind.surface, SURFACE_REGISTRY.iter().map(|e| e.surface))
+                        .map(str::to_string);
+                errors.push(error);
             }
         }
     }
@@ -254,7 +525,8 @@ fn v005_enum_values(doc: &Document, errors: &mut Vec<ValidationError>) {
 
 // ─── V-006 ──────────────────────────────────────────────────────────────────
 
-fn v006_indicators_non_empty(doc: &Document, errors: &mut Vec<ValidationError>) {
+fn v006_indicators_non_empty(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
     if let Some(indicators) = &doc.attack.indicators
         && indicators.is_empty()
     {
@@ -268,7 +540,8 @@ fn v006_indicators_non_empty(doc: &Document, errors: &mut Vec<ValidationError>)
 
 // ─── V-007 ──────────────────────────────────────────────────────────────────
 
-fn v007_phases_non_empty(doc: &Document, errors: &mut Vec<ValidationError>) {
+fn v007_phases_non_empty(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
     let exec = &doc.attack.execution;
     if let Some(phases) = &exec.phases
         && phases.is_empty()
@@ -294,8 +567,8 @@ fn v007_phases_non_empty(doc: &Document, errors: &mut Vec<ValidationError>) {
 
 // ─── V-008 ──────────────────────────────────────────────────────────────────
 
-fn v008_terminal_phase(doc: &Document, errors: &mut Vec<ValidationError>) {
-    for actor_info in collect_actors(doc) {
+fn v008_terminal_phase(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    for actor_info in cx.actors() {
         let mut terminal_count = 0;
         let mut last_terminal_idx = None;
         for (i, phase) in actor_info.phases.iter().enumerate() {
@@ -328,7 +601,8 @@ fn v008_terminal_phase(doc: &Document, errors: &mut Vec<ValidationError>) {
 
 // ─── V-009 ──────────────────────────────────────────────────────────────────
 
-fn v009_first_phase_state(doc: &Document, errors: &mut Vec<ValidationError>) {
+fn v009_first_phase_state(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
     let exec = &doc.attack.execution;
     // Single-phase form: execution.state must be present (handled by V-030)
     if let Some(phases) = &exec.phases
@@ -356,7 +630,8 @@ fn v009_first_phase_state(doc: &Document, errors: &mut Vec<ValidationError>) {
 
 // ─── V-010 ──────────────────────────────────────────────────────────────────
 
-fn v010_unique_indicator_ids(doc: &Document, errors: &mut Vec<ValidationError>) {
+fn v010_unique_indicator_ids(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
     if let Some(indicators) = &doc.attack.indicators {
         let mut seen = std::collections::HashSet::new();
         for (i, ind) in indicators.iter().enumerate() {
@@ -375,8 +650,8 @@ fn v010_unique_indicator_ids(doc: &Document, errors: &mut Vec<ValidationError>)
 
 // ─── V-011 ──────────────────────────────────────────────────────────────────
 
-fn v011_unique_phase_names(doc: &Document, errors: &mut Vec<ValidationError>) {
-    for actor_info in collect_actors(doc) {
+fn v011_unique_phase_names(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    for actor_info in cx.actors() {
         let mut seen = std::collections::HashSet::new();
         for (i, phase) in actor_info.phases.iter().enumerate() {
             if let Some(name) = &phase.name
@@ -394,13 +669,15 @@ fn v011_unique_phase_names(doc: &Document, errors: &mut Vec<ValidationError>) {
 
 // ─── V-012 ──────────────────────────────────────────────────────────────────
 
-fn v012_exactly_one_detection_key(doc: &Document, errors: &mut Vec<ValidationError>) {
+fn v012_exactly_one_detection_key(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
     if let Some(indicators) = &doc.attack.indicators {
         for (i, ind) in indicators.iter().enumerate() {
             let count = [
                 ind.pattern.is_some(),
                 ind.expression.is_some(),
                 ind.semantic.is_some(),
+                ind.frequency.is_some(),
             ]
             .iter()
             .filter(|&&b| b)
@@ -410,7 +687,7 @@ fn v012_exactly_one_detection_key(doc: &Document, errors: &mut Vec<ValidationErr
                     "V-012",
                     format!("attack.indicators[{}]", i),
                     format!(
-                        "each indicator must have exactly one detection key (pattern, expression, or semantic), found {}",
+                        "each indicator must have exactly one detection key (pattern, expression, semantic, or frequency), found {}",
                         count
                     ),
                 ));
@@ -420,7 +697,8 @@ fn v012_exactly_one_detection_key(doc: &Document, errors: &mut Vec<ValidationErr
 }
 
 /// Reject patterns that have both `condition` and shorthand operator fields.
-fn v012_pattern_form_ambiguity(doc: &Document, errors: &mut Vec<ValidationError>) {
+fn v012_pattern_form_ambiguity(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
     if let Some(indicators) = &doc.attack.indicators {
         for (i, ind) in indicators.iter().enumerate() {
             if let Some(pattern) = &ind.pattern
@@ -439,69 +717,136 @@ fn v012_pattern_form_ambiguity(doc: &Document, errors: &mut Vec<ValidationError>
 
 // ─── V-013 ──────────────────────────────────────────────────────────────────
 
-fn v013_regex_valid(doc: &Document, errors: &mut Vec<ValidationError>) {
-    if let Some(indicators) = &doc.attack.indicators {
-        for (i, ind) in indicators.iter().enumerate() {
-            if let Some(pattern) = &ind.pattern {
-                // Check regex in shorthand form
-                if let Some(re) = &pattern.regex
-                    && let Err(e) = Regex::new(re)
-                {
-                    errors.push(verr(
-                        "V-013",
-                        format!("attack.indicators[{}].pattern.regex", i),
-                        format!("invalid regex: {}", e),
-                    ));
-                }
-                // Check regex in condition form
-                if let Some(Condition::Operators(cond)) = &pattern.condition
-                    && let Some(re) = &cond.regex
-                    && let Err(e) = Regex::new(re)
-                {
-                    errors.push(verr(
-                        "V-013",
-                        format!("attack.indicators[{}].pattern.condition.regex", i),
-                        format!("invalid regex: {}", e),
-                    ));
-                }
-            }
+fn v013_regex_valid(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    for (path, re) in regex_sites(cx) {
+        if let Err(e) = Regex::new(re) {
+            errors.push(verr("V-013", path, format!("invalid regex: {}", e)));
+        }
+    }
+}
+
+/// V-013 under a [`RegexDialect`]: a violation replaces the generic compile
+/// error at the same path, if any, so the reason is stated in dialect terms.
+fn v013_regex_dialect(doc: &Document, dialect: RegexDialect, errors: &mut Vec<ValidationError>) {
+    for (path, re) in regex_sites(&ResolvedContext::new(doc)) {
+        let Err(reason) = check_regex_dialect(re, dialect) else {
+            continue;
+        };
+        let message = format!(
+            "regex outside the {} dialect: {}",
+            dialect_name(dialect),
+            reason
+        );
+        match errors
+            .iter_mut()
+            .find(|e| e.rule == "V-013" && e.path == path)
+        {
+            Some(existing) => existing.message = message,
+            None => errors.push(verr("V-013", path, message)),
         }
     }
-    // Also check regex in match predicates (triggers and response entries)
-    validate_regex_in_phases(doc, errors);
 }
 
-fn validate_regex_in_phases(doc: &Document, errors: &mut Vec<ValidationError>) {
-    for actor_info in collect_actors(doc) {
+fn dialect_name(dialect: RegexDialect) -> &'static str {
+    match dialect {
+        RegexDialect::Native => "native",
+        RegexDialect::Portable => "portable",
+    }
+}
+
+/// Every regular expression V-013 checks, with its document path: indicator
+/// patterns (shorthand and condition form), trigger match predicates, and
+/// origin predicates.
+fn regex_sites<'a>(cx: &ResolvedContext<'a>) -> Vec<(String, &'a str)> {
+    let doc = cx.document();
+    let mut sites = Vec::new();
+    for (i, ind) in doc.attack.indicators.iter().flatten().enumerate() {
+        if let Some(pattern) = &ind.pattern {
+            if let Some(re) = &pattern.regex {
+                sites.push((
+                    format!("attack.indicators[{}].pattern.regex", i),
+                    re.as_str(),
+                ));
+            }
+            if let Some(Condition::Operators(cond)) = &pattern.condition {
+                condition_regex_sites(
+                    cond,
+                    &format!("attack.indicators[{}].pattern.condition", i),
+                    &mut sites,
+                );
+            }
+        }
+    }
+    for (i, ind) in doc.attack.indicators.iter().flatten().enumerate() {
+        if let Some(origin) = &ind.origin {
+            predicate_regex_sites(
+                origin,
+                &format!("attack.indicators[{}].origin", i),
+                &mut sites,
+            );
+        }
+    }
+    for actor_info in cx.actors() {
         for (pi, phase) in actor_info.phases.iter().enumerate() {
-            if let Some(trigger) = &phase.trigger
-                && let Some(pred) = &trigger.match_predicate
-            {
-                for (key, entry) in pred {
-                    if let MatchEntry::Condition(cond) = entry
-                        && let Some(re) = &cond.regex
-                        && let Err(e) = Regex::new(re)
-                    {
-                        errors.push(verr(
-                            "V-013",
-                            format!(
-                                "{}.phases[{}].trigger.match.{}.regex",
-                                actor_info.path_prefix, pi, key
-                            ),
-                            format!("invalid regex: {}", e),
-                        ));
-                    }
-                }
+            if let Some(requires) = &phase.requires {
+                predicate_regex_sites(
+                    requires,
+                    &format!("{}.phases[{}].requires", actor_info.path_prefix, pi),
+                    &mut sites,
+                );
             }
+            let Some(trigger) = &phase.trigger else {
+                continue;
+            };
+            let path = format!("{}.phases[{}].trigger", actor_info.path_prefix, pi);
+            if let Some(pred) = &trigger.match_predicate {
+                predicate_regex_sites(pred, &format!("{}.match", path), &mut sites);
+            }
+            if let Some(origin) = &trigger.origin {
+                predicate_regex_sites(origin, &format!("{}.origin", path), &mut sites);
+            }
+        }
+    }
+    sites
+}
+
+fn predicate_regex_sites<'a>(
+    predicate: &'a MatchPredicate,
+    path: &str,
+    sites: &mut Vec<(String, &'a str)>,
+) {
+    for (key, entry) in predicate {
+        if let MatchEntry::Condition(cond) = entry {
+            condition_regex_sites(cond, &format!("{}.{}", path, key), sites);
         }
     }
 }
 
+/// The `regex` of `cond` and of its `json_patch` value condition.
+fn condition_regex_sites<'a>(
+    cond: &'a MatchCondition,
+    path: &str,
+    sites: &mut Vec<(String, &'a str)>,
+) {
+    if let Some(re) = &cond.regex {
+        sites.push((format!("{}.regex", path), re.as_str()));
+    }
+    if let Some(MatchEntry::Condition(value)) =
+        cond.json_patch.as_ref().and_then(|p| p.value.as_deref())
+    {
+        condition_regex_sites(value, &format!("{}.json_patch.value", path), sites);
+    }
+}
+
 // ─── V-014 ──────────────────────────────────────────────────────────────────
 
-fn v014_cel_valid(doc: &Document, errors: &mut Vec<ValidationError>) {
+fn v014_cel_valid(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    // Without a CEL parser, expressions are not checked.
+    #[cfg(not(feature = "cel-eval"))]
+    let _ = (cx, errors);
     #[cfg(feature = "cel-eval")]
     {
+        let doc = cx.document();
         if let Some(indicators) = &doc.attack.indicators {
             for (i, ind) in indicators.iter().enumerate() {
                 if let Some(expr) = &ind.expression {
@@ -521,22 +866,23 @@ fn v014_cel_valid(doc: &Document, errors: &mut Vec<ValidationError>) {
 
 // ─── V-015 ──────────────────────────────────────────────────────────────────
 
-fn v015_jsonpath_valid(doc: &Document, errors: &mut Vec<ValidationError>) {
-    // collect_actors() already handles actors, phases, and single-phase forms
-    for actor_info in collect_actors(doc) {
+fn v015_jsonpath_valid(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    // cx.actors() already handles the actor and phase forms
+    for actor_info in cx.actors() {
         for (pi, phase) in actor_info.phases.iter().enumerate() {
             if let Some(extractors) = &phase.extractors {
                 for (ei, ext) in extractors.iter().enumerate() {
-                    if matches!(ext.extractor_type, crate::enums::ExtractorType::JsonPath)
-                        && !is_valid_jsonpath_syntax(&ext.selector)
-                    {
+                    if !matches!(ext.extractor_type, crate::enums::ExtractorType::JsonPath) {
+                        continue;
+                    }
+                    if let Some(message) = jsonpath_error(&ext.selector) {
                         errors.push(verr(
                             "V-015",
                             format!(
                                 "{}.phases[{}].extractors[{}].selector",
                                 actor_info.path_prefix, pi, ei
                             ),
-                            format!("invalid JSONPath syntax: '{}'", ext.selector),
+                            message,
                         ));
                     }
                 }
@@ -545,21 +891,28 @@ fn v015_jsonpath_valid(doc: &Document, errors: &mut Vec<ValidationError>) {
     }
 }
 
-/// JSONPath syntax validation using the serde_json_path parser (RFC 9535).
-fn is_valid_jsonpath_syntax(path: &str) -> bool {
-    serde_json_path::JsonPath::parse(path).is_ok()
+/// Compile `path` with the installed
+/// [`JsonPathEngine`](crate::jsonpath::JsonPathEngine), so validation accepts
+/// exactly the selectors evaluation can run. Returns the V-015 message for a
+/// selector the engine rejects.
+fn jsonpath_error(path: &str) -> Option<String> {
+    crate::jsonpath::engine()
+        .check(path)
+        .err()
+        .map(|e| format!("invalid JSONPath syntax: '{}': {}", path, e))
 }
 
 // ─── V-016 ──────────────────────────────────────────────────────────────────
 
-fn v016_template_syntax(doc: &Document, errors: &mut Vec<ValidationError>) {
+fn v016_template_syntax(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
     // Check for unclosed {{ in template strings throughout the document
-    // Handle single-phase form directly (collect_actors returns empty for it)
+    // Handle single-phase form directly (it has no actors)
     if let Some(state) = &doc.attack.execution.state {
         check_templates_in_value(state, "attack.execution.state", errors);
     }
     // We check state values and on_enter action message fields
-    for actor_info in collect_actors(doc) {
+    for actor_info in cx.actors() {
         for (pi, phase) in actor_info.phases.iter().enumerate() {
             if let Some(state) = &phase.state {
                 check_templates_in_value(
@@ -632,7 +985,8 @@ fn check_template_string(s: &str, path: &str, errors: &mut Vec<ValidationError>)
 
 // ─── V-017 ──────────────────────────────────────────────────────────────────
 
-fn v017_severity_confidence(doc: &Document, errors: &mut Vec<ValidationError>) {
+fn v017_severity_confidence(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
     if let Some(severity) = &doc.attack.severity
         && let Severity::Object {
             confidence: Some(c),
@@ -651,27 +1005,14 @@ fn v017_severity_confidence(doc: &Document, errors: &mut Vec<ValidationError>) {
 // ─── V-018 ──────────────────────────────────────────────────────────────────
 
 fn v018_surface_protocol(
-    doc: &Document,
+    cx: &ResolvedContext<'_>,
     errors: &mut Vec<ValidationError>,
     _warnings: &mut Vec<Diagnostic>,
 ) {
+    let doc = cx.document();
     if let Some(indicators) = &doc.attack.indicators {
         for (i, ind) in indicators.iter().enumerate() {
-            let protocol = ind
-                .protocol
-                .as_deref()
-                .or_else(|| doc.attack.execution.mode.as_deref().map(extract_protocol))
-                .or_else(|| {
-                    // Multi-actor form: infer from single actor's mode
-                    doc.attack.execution.actors.as_ref().and_then(|actors| {
-                        if actors.len() == 1 {
-                            Some(extract_protocol(&actors[0].mode))
-                        } else {
-                            None
-                        }
-                    })
-                });
-            if let Some(proto) = protocol
+            if let Some(proto) = cx.indicator_protocol(i)
                 && KNOWN_PROTOCOLS.contains(&proto)
                 && let Some(entry) = lookup_surface(&ind.surface)
                 && entry.protocol != proto
@@ -691,8 +1032,8 @@ fn v018_surface_protocol(
 
 // ─── V-019 ──────────────────────────────────────────────────────────────────
 
-fn v019_count_match_require_event(doc: &Document, errors: &mut Vec<ValidationError>) {
-    for actor_info in collect_actors(doc) {
+fn v019_count_match_require_event(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    for actor_info in cx.actors() {
         for (pi, phase) in actor_info.phases.iter().enumerate() {
             if let Some(trigger) = &phase.trigger
                 && trigger.event.is_none()
@@ -710,7 +1051,8 @@ fn v019_count_match_require_event(doc: &Document, errors: &mut Vec<ValidationErr
 
 // ─── V-021 ──────────────────────────────────────────────────────────────────
 
-fn v021_target_path_syntax(doc: &Document, errors: &mut Vec<ValidationError>) {
+fn v021_target_path_syntax(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
     if let Some(indicators) = &doc.attack.indicators {
         for (i, ind) in indicators.iter().enumerate() {
             if let Some(pattern) = &ind.pattern
@@ -740,11 +1082,12 @@ fn v021_target_path_syntax(doc: &Document, errors: &mut Vec<ValidationError>) {
 /// Validate wildcard dot-path syntax per §5.1.2.
 ///
 /// Valid: `tools[*].description`, `content[*]`, `arguments`, `""`, `status.state`,
-///        `2xx-status`, `0foo.bar`
+///        `2xx-status`, `0foo.bar`, `arguments.payload!json.url`, `items[*]!url`
 ///
 /// Invalid: `tools[*.description` (missing bracket), `tools..name` (double dot),
 ///          `[*]tools` (leading bracket), `tools[*].[*]` (bracket after dot-bracket),
-///          `tools[-1]` (negative index)
+///          `tools[-1]` (negative index), `payload!yaml` (unknown decoder),
+///          `!json.url` (decoder without a segment)
 pub fn is_valid_wildcard_dot_path(path: &str) -> bool {
     if path.is_empty() {
         return true; // Empty string targets root
@@ -798,13 +1141,13 @@ fn split_wildcard_path(path: &str) -> Option<Vec<String>> {
                 if i + 2 < chars.len() && chars[i + 1] == '*' && chars[i + 2] == ']' {
                     current.push_str("[*]");
                     i += 3;
-                    // After [*], must be followed by . or end
+                    // After [*], must be followed by ., a decoder, or end
                     if i < chars.len() {
                         if chars[i] == '.' {
                             segments.push(current.clone());
                             current.clear();
                             i += 1;
-                        } else {
+                        } else if chars[i] != '!' {
                             return None; // Invalid char after [*]
                         }
                     }
@@ -814,6 +1157,34 @@ fn split_wildcard_path(path: &str) -> Option<Vec<String>> {
                     return None; // Invalid bracket content
                 }
             }
+            '!' => {
+                if current.is_empty() {
+                    return None; // Decoder without a segment
+                }
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|c| !c.is_ascii_lowercase())
+                    .map_or(chars.len(), |p| i + 1 + p);
+                let name: String = chars[i + 1..end].iter().collect();
+                if !TARGET_DECODERS.contains(&name.as_str()) {
+                    return None; // Unknown decoder
+                }
+                current.push('!');
+                current.push_str(&name);
+                i = end;
+                // After a decoder, must be followed by ., another decoder, or end
+                if i < chars.len() {
+                    match chars[i] {
+                        '.' => {
+                            segments.push(current.clone());
+                            current.clear();
+                            i += 1;
+                        }
+                        '!' => {}
+                        _ => return None,
+                    }
+                }
+            }
             c if is_path_segment_char(c) => {
                 current.push(c);
                 i += 1;
@@ -873,7 +1244,8 @@ pub fn is_valid_simple_dot_path(path: &str) -> bool {
 
 // ─── V-022 ──────────────────────────────────────────────────────────────────
 
-fn v022_semantic_threshold(doc: &Document, errors: &mut Vec<ValidationError>) {
+fn v022_semantic_threshold(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
     if let Some(indicators) = &doc.attack.indicators {
         for (i, ind) in indicators.iter().enumerate() {
             if let Some(semantic) = &ind.semantic
@@ -895,7 +1267,8 @@ fn v022_semantic_threshold(doc: &Document, errors: &mut Vec<ValidationError>) {
 
 // ─── V-023 ──────────────────────────────────────────────────────────────────
 
-fn v023_attack_id_format(doc: &Document, errors: &mut Vec<ValidationError>) {
+fn v023_attack_id_format(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
     if let Some(id) = &doc.attack.id
         && !ATTACK_ID_RE.is_match(id)
     {
@@ -912,7 +1285,8 @@ fn v023_attack_id_format(doc: &Document, errors: &mut Vec<ValidationError>) {
 
 // ─── V-024 ──────────────────────────────────────────────────────────────────
 
-fn v024_indicator_id_format(doc: &Document, errors: &mut Vec<ValidationError>) {
+fn v024_indicator_id_format(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
     if let Some(indicators) = &doc.attack.indicators {
         for (i, ind) in indicators.iter().enumerate() {
             if let Some(ind_id) = &ind.id
@@ -952,7 +1326,8 @@ fn v024_indicator_id_format(doc: &Document, errors: &mut Vec<ValidationError>) {
 
 // ─── V-025 ──────────────────────────────────────────────────────────────────
 
-fn v025_indicator_confidence(doc: &Document, errors: &mut Vec<ValidationError>) {
+fn v025_indicator_confidence(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
     if let Some(indicators) = &doc.attack.indicators {
         for (i, ind) in indicators.iter().enumerate() {
             if let Some(conf) = ind.confidence
@@ -970,7 +1345,8 @@ fn v025_indicator_confidence(doc: &Document, errors: &mut Vec<ValidationError>)
 
 // ─── V-026 ──────────────────────────────────────────────────────────────────
 
-fn v026_expression_variables_paths(doc: &Document, errors: &mut Vec<ValidationError>) {
+fn v026_expression_variables_paths(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
     if let Some(indicators) = &doc.attack.indicators {
         for (i, ind) in indicators.iter().enumerate() {
             if let Some(expr) = &ind.expression
@@ -998,9 +1374,9 @@ fn v026_expression_variables_paths(doc: &Document, errors: &mut Vec<ValidationEr
 
 // ─── V-027 ──────────────────────────────────────────────────────────────────
 
-fn v027_match_predicate_paths(doc: &Document, errors: &mut Vec<ValidationError>) {
+fn v027_match_predicate_paths(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
     // Check trigger.match keys
-    for actor_info in collect_actors(doc) {
+    for actor_info in cx.actors() {
         for (pi, phase) in actor_info.phases.iter().enumerate() {
             if let Some(trigger) = &phase.trigger
                 && let Some(pred) = &trigger.match_predicate
@@ -1026,15 +1402,16 @@ fn v027_match_predicate_paths(doc: &Document, errors: &mut Vec<ValidationError>)
 
     // Check response entry `when` predicate keys in state values
     // This is a deep check into state values which we do best-effort
-    check_when_predicates_in_state(doc, errors);
+    check_when_predicates_in_state(cx, errors);
 }
 
-fn check_when_predicates_in_state(doc: &Document, errors: &mut Vec<ValidationError>) {
+fn check_when_predicates_in_state(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
     // Handle single-phase form directly
     if let Some(state) = &doc.attack.execution.state {
         scan_when_predicates(state, "attack.execution.state", errors);
     }
-    for actor_info in collect_actors(doc) {
+    for actor_info in cx.actors() {
         for (pi, phase) in actor_info.phases.iter().enumerate() {
             if let Some(state) = &phase.state {
                 scan_when_predicates(
@@ -1085,7 +1462,8 @@ fn scan_when_predicates(value: &serde_json::Value, path: &str, errors: &mut Vec<
 
 // ─── V-028 ──────────────────────────────────────────────────────────────────
 
-fn v028_conditional_requiredness(doc: &Document, errors: &mut Vec<ValidationError>) {
+fn v028_conditional_requiredness(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
     let exec = &doc.attack.execution;
 
     // When execution.mode is absent and execution.actors is absent (mode-less multi-phase form)
@@ -1123,13 +1501,13 @@ fn v028_conditional_requiredness(doc: &Document, errors: &mut Vec<ValidationErro
 // ─── V-029 ──────────────────────────────────────────────────────────────────
 
 fn v029_event_mode_validity(
-    doc: &Document,
+    cx: &ResolvedContext<'_>,
     errors: &mut Vec<ValidationError>,
     _warnings: &mut Vec<Diagnostic>,
 ) {
-    for actor_info in collect_actors(doc) {
+    for actor_info in cx.actors() {
         let mode = match actor_info.mode {
-            Some(m) => m,
+            Some(m) => split_mode_version(m).0,
             None => continue,
         };
 
@@ -1139,7 +1517,9 @@ fn v029_event_mode_validity(
         }
 
         for (pi, phase) in actor_info.phases.iter().enumerate() {
-            let resolved_mode = phase.mode.as_deref().unwrap_or(mode);
+            let resolved_mode = actor_info
+                .phase_mode(pi)
+                .map_or(mode, |m| split_mode_version(m).0);
 
             if !KNOWN_MODES.contains(&resolved_mode) {
                 continue;
@@ -1152,14 +1532,16 @@ fn v029_event_mode_validity(
                 if let Some(valid) = is_event_valid_for_mode(base_event, resolved_mode)
                     && !valid
                 {
-                    errors.push(verr(
+                    let mut error = verr(
                         "V-029",
                         format!("{}.phases[{}].trigger.event", actor_info.path_prefix, pi),
                         format!(
                             "event '{}' is not valid for mode '{}'",
                             event, resolved_mode
                         ),
-                    ));
+                    );
+                    error.suggestion = suggest_event(event, resolved_mode);
+                    errors.push(error);
                 }
                 // If event not in registry, skip (unrecognized binding event)
             }
@@ -1169,7 +1551,8 @@ fn v029_event_mode_validity(
 
 // ─── V-030 ──────────────────────────────────────────────────────────────────
 
-fn v030_mutual_exclusion(doc: &Document, errors: &mut Vec<ValidationError>) {
+fn v030_mutual_exclusion(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
     let exec = &doc.attack.execution;
     let has_state = exec.state.is_some();
     let has_phases = exec.phases.is_some();
@@ -1206,7 +1589,8 @@ fn v030_mutual_exclusion(doc: &Document, errors: &mut Vec<ValidationError>) {
 
 // ─── V-031 ──────────────────────────────────────────────────────────────────
 
-fn v031_multi_actor_constraints(doc: &Document, errors: &mut Vec<ValidationError>) {
+fn v031_multi_actor_constraints(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
     if let Some(actors) = &doc.attack.execution.actors {
         let mut seen_names = std::collections::HashSet::new();
 
@@ -1272,7 +1656,8 @@ fn v031_multi_actor_constraints(doc: &Document, errors: &mut Vec<ValidationError
 
 // ─── V-032 ──────────────────────────────────────────────────────────────────
 
-fn v032_cross_actor_refs(doc: &Document, errors: &mut Vec<ValidationError>) {
+fn v032_cross_actor_refs(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
     let actor_names: std::collections::HashSet<String> =
         if let Some(actors) = &doc.attack.execution.actors {
             actors.iter().map(|a| a.name.clone()).collect()
@@ -1284,7 +1669,7 @@ fn v032_cross_actor_refs(doc: &Document, errors: &mut Vec<ValidationError>) {
         };
 
     // Scan all template strings in the document for {{actor_name.extractor_name}} references
-    for actor_info in collect_actors(doc) {
+    for actor_info in cx.actors() {
         for (pi, phase) in actor_info.phases.iter().enumerate() {
             if let Some(state) = &phase.state {
                 check_cross_actor_refs_in_value(
@@ -1355,19 +1740,23 @@ fn check_cross_actor_refs_in_string(
 
 // ─── V-033 ──────────────────────────────────────────────────────────────────
 
-fn v033_content_synthesize_exclusivity(doc: &Document, errors: &mut Vec<ValidationError>) {
+fn v033_content_synthesize_exclusivity(
+    cx: &ResolvedContext<'_>,
+    errors: &mut Vec<ValidationError>,
+) {
+    let doc = cx.document();
     // Check state values for response entries with both content/messages and synthesize
     // Handle single-phase form directly
     if let Some(state) = &doc.attack.execution.state {
         let mode = doc.attack.execution.mode.as_deref().unwrap_or_default();
         check_response_exclusivity(state, mode, "attack.execution.state", errors);
     }
-    for actor_info in collect_actors(doc) {
+    for actor_info in cx.actors() {
         for (pi, phase) in actor_info.phases.iter().enumerate() {
             if let Some(state) = &phase.state {
-                let mode = resolve_mode(doc, actor_info.mode, phase.mode.as_deref());
+                let mode = actor_info.phase_mode(pi).unwrap_or_default();
                 let path = format!("{}.phases[{}].state", actor_info.path_prefix, pi);
-                check_response_exclusivity(state, &mode.unwrap_or_default(), &path, errors);
+                check_response_exclusivity(state, mode, &path, errors);
             }
         }
     }
@@ -1464,12 +1853,13 @@ fn check_response_exclusivity(
 
 // ─── V-034 ──────────────────────────────────────────────────────────────────
 
-fn v034_catch_all_constraints(doc: &Document, errors: &mut Vec<ValidationError>) {
+fn v034_catch_all_constraints(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
     // Handle single-phase form directly
     if let Some(state) = &doc.attack.execution.state {
         check_catch_all_in_state(state, "attack.execution.state", errors);
     }
-    for actor_info in collect_actors(doc) {
+    for actor_info in cx.actors() {
         for (pi, phase) in actor_info.phases.iter().enumerate() {
             if let Some(state) = &phase.state {
                 let path = format!("{}.phases[{}].state", actor_info.path_prefix, pi);
@@ -1545,12 +1935,13 @@ fn check_catch_all_list(
 
 // ─── V-035 ──────────────────────────────────────────────────────────────────
 
-fn v035_synthesize_prompt(doc: &Document, errors: &mut Vec<ValidationError>) {
+fn v035_synthesize_prompt(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
     // Handle single-phase form directly
     if let Some(state) = &doc.attack.execution.state {
         check_synthesize_prompts(state, "attack.execution.state", errors);
     }
-    for actor_info in collect_actors(doc) {
+    for actor_info in cx.actors() {
         for (pi, phase) in actor_info.phases.iter().enumerate() {
             if let Some(state) = &phase.state {
                 let path = format!("{}.phases[{}].state", actor_info.path_prefix, pi);
@@ -1606,21 +1997,23 @@ fn check_synthesize_prompts(
 // ─── V-036 ──────────────────────────────────────────────────────────────────
 
 fn v036_mode_protocol_pattern(
-    doc: &Document,
+    cx: &ResolvedContext<'_>,
     errors: &mut Vec<ValidationError>,
     warnings: &mut Vec<Diagnostic>,
 ) {
+    let doc = cx.document();
     // execution.mode pattern is validated by V-005; V-036 handles actor/phase modes.
     // Check for W-002 warning on execution.mode (unrecognized but valid pattern)
     if let Some(mode) = &doc.attack.execution.mode
         && MODE_RE.is_match(mode)
-        && !KNOWN_MODES.contains(&mode.as_str())
+        && !KNOWN_MODES.contains(&split_mode_version(mode).0)
     {
         warnings.push(Diagnostic {
             severity: DiagnosticSeverity::Warning,
             code: "W-002".to_string(),
             path: Some("attack.execution.mode".to_string()),
             message: format!("unrecognized mode: '{}'", mode),
+            suggestion: suggest_mode(mode),
         });
     }
 
@@ -1628,39 +2021,44 @@ fn v036_mode_protocol_pattern(
     if let Some(actors) = &doc.attack.execution.actors {
         for (i, actor) in actors.iter().enumerate() {
             if !MODE_RE.is_match(&actor.mode) {
-                errors.push(verr(
+                let mut error = verr(
                     "V-036",
                     format!("attack.execution.actors[{}].mode", i),
                     format!(
-                        "mode must match [a-z][a-z0-9_]*_(server|client), got '{}'",
+                        "mode must match [a-z][a-z0-9_]*_(server|client)[@version], got '{}'",
                         actor.mode
                     ),
-                ));
-            } else if !KNOWN_MODES.contains(&actor.mode.as_str()) {
+                );
+                error.suggestion = suggest_mode(&actor.mode);
+                errors.push(error);
+            } else if !KNOWN_MODES.contains(&split_mode_version(&actor.mode).0) {
                 warnings.push(Diagnostic {
                     severity: DiagnosticSeverity::Warning,
                     code: "W-002".to_string(),
                     path: Some(format!("attack.execution.actors[{}].mode", i)),
                     message: format!("unrecognized mode: '{}'", actor.mode),
+                    suggestion: suggest_mode(&actor.mode),
                 });
             }
         }
     }
 
     // Check phase modes
-    for actor_info in collect_actors(doc) {
+    for actor_info in cx.actors() {
         for (pi, phase) in actor_info.phases.iter().enumerate() {
             if let Some(mode) = &phase.mode
                 && !MODE_RE.is_match(mode)
             {
-                errors.push(verr(
+                let mut error = verr(
                     "V-036",
                     format!("{}.phases[{}].mode", actor_info.path_prefix, pi),
                     format!(
-                        "mode must match [a-z][a-z0-9_]*_(server|client), got '{}'",
+                        "mode must match [a-z][a-z0-9_]*_(server|client)[@version], got '{}'",
                         mode
                     ),
-                ));
+                );
+                error.suggestion = suggest_mode(mode);
+                errors.push(error);
             }
         }
     }
@@ -1681,6 +2079,8 @@ fn v036_mode_protocol_pattern(
                         code: "W-003".to_string(),
                         path: Some(format!("attack.indicators[{}].protocol", i)),
                         message: format!("unrecognized protocol: '{}'", protocol),
+                        suggestion: closest(protocol, KNOWN_PROTOCOLS.iter().copied())
+                            .map(str::to_string),
                     });
                 }
             }
@@ -1690,7 +2090,8 @@ fn v036_mode_protocol_pattern(
 
 // ─── V-037 ──────────────────────────────────────────────────────────────────
 
-fn v037_version_positive(doc: &Document, errors: &mut Vec<ValidationError>) {
+fn v037_version_positive(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
     if let Some(version) = doc.attack.version
         && version < 1
     {
@@ -1707,9 +2108,10 @@ fn v037_version_positive(doc: &Document, errors: &mut Vec<ValidationError>) {
 
 // ─── V-038 ──────────────────────────────────────────────────────────────────
 
-fn v038_trigger_after_duration(doc: &Document, errors: &mut Vec<ValidationError>) {
+fn v038_trigger_after_duration(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
     // Validate trigger.after durations
-    for actor_info in collect_actors(doc) {
+    for actor_info in cx.actors() {
         for (pi, phase) in actor_info.phases.iter().enumerate() {
             if let Some(trigger) = &phase.trigger
                 && let Some(after) = &trigger.after
@@ -1760,8 +2162,8 @@ pub fn is_valid_duration(s: &str) -> bool {
 
 // ─── V-039 ──────────────────────────────────────────────────────────────────
 
-fn v039_extractor_name_pattern(doc: &Document, errors: &mut Vec<ValidationError>) {
-    for actor_info in collect_actors(doc) {
+fn v039_extractor_name_pattern(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    for actor_info in cx.actors() {
         for (pi, phase) in actor_info.phases.iter().enumerate() {
             if let Some(extractors) = &phase.extractors {
                 for (ei, ext) in extractors.iter().enumerate() {
@@ -1786,8 +2188,8 @@ fn v039_extractor_name_pattern(doc: &Document, errors: &mut Vec<ValidationError>
 
 // ─── V-040 ──────────────────────────────────────────────────────────────────
 
-fn v040_extractors_non_empty(doc: &Document, errors: &mut Vec<ValidationError>) {
-    for actor_info in collect_actors(doc) {
+fn v040_extractors_non_empty(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    for actor_info in cx.actors() {
         for (pi, phase) in actor_info.phases.iter().enumerate() {
             if let Some(extractors) = &phase.extractors
                 && extractors.is_empty()
@@ -1804,7 +2206,8 @@ fn v040_extractors_non_empty(doc: &Document, errors: &mut Vec<ValidationError>)
 
 // ─── V-041 ──────────────────────────────────────────────────────────────────
 
-fn v041_expression_variable_keys(doc: &Document, errors: &mut Vec<ValidationError>) {
+fn v041_expression_variable_keys(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
     if let Some(indicators) = &doc.attack.indicators {
         for (i, ind) in indicators.iter().enumerate() {
             if let Some(expr) = &ind.expression
@@ -1829,8 +2232,8 @@ fn v041_expression_variable_keys(doc: &Document, errors: &mut Vec<ValidationErro
 
 // ─── V-042 ──────────────────────────────────────────────────────────────────
 
-fn v042_trigger_event_or_after(doc: &Document, errors: &mut Vec<ValidationError>) {
-    for actor_info in collect_actors(doc) {
+fn v042_trigger_event_or_after(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    for actor_info in cx.actors() {
         for (pi, phase) in actor_info.phases.iter().enumerate() {
             if let Some(trigger) = &phase.trigger
                 && trigger.event.is_none()
@@ -1848,11 +2251,14 @@ fn v042_trigger_event_or_after(doc: &Document, errors: &mut Vec<ValidationError>
 
 // ─── V-043 ──────────────────────────────────────────────────────────────────
 
-fn v043_binding_specific_action_keys(doc: &Document, errors: &mut Vec<ValidationError>) {
-    for actor_info in collect_actors(doc) {
+fn v043_binding_specific_action_keys(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    for actor_info in cx.actors() {
         for (pi, phase) in actor_info.phases.iter().enumerate() {
-            if let Some(actions) = &phase.on_enter {
-                for (ai, action) in actions.iter().enumerate() {
+            for (key, actions) in [
+                ("on_enter", &phase.on_enter),
+                ("on_timeout", &phase.on_timeout),
+            ] {
+                for (ai, action) in actions.iter().flatten().enumerate() {
                     let count = match action {
                         Action::SendNotification {
                             non_ext_key_count, ..
@@ -1870,7 +2276,7 @@ fn v043_binding_specific_action_keys(doc: &Document, errors: &mut Vec<Validation
                     if count != 1 {
                         errors.push(verr(
                             "V-043",
-                            format!("{}.phases[{}].on_enter[{}]", actor_info.path_prefix, pi, ai),
+                            format!("{}.phases[{}].{}[{}]", actor_info.path_prefix, pi, key, ai),
                             format!(
                                 "action must have exactly one non-extension key, found {}",
                                 count
@@ -1885,8 +2291,8 @@ fn v043_binding_specific_action_keys(doc: &Document, errors: &mut Vec<Validation
 
 // ─── V-044 ──────────────────────────────────────────────────────────────────
 
-fn v044_regex_extractor_capture_group(doc: &Document, errors: &mut Vec<ValidationError>) {
-    for actor_info in collect_actors(doc) {
+fn v044_regex_extractor_capture_group(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    for actor_info in cx.actors() {
         for (pi, phase) in actor_info.phases.iter().enumerate() {
             if let Some(extractors) = &phase.extractors {
                 for (ei, ext) in extractors.iter().enumerate() {
@@ -1928,8 +2334,8 @@ fn has_capture_group(pattern: &str) -> bool {
 
 // ─── V-045 ──────────────────────────────────────────────────────────────────
 
-fn v045_on_enter_non_empty(doc: &Document, errors: &mut Vec<ValidationError>) {
-    for actor_info in collect_actors(doc) {
+fn v045_on_enter_non_empty(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    for actor_info in cx.actors() {
         for (pi, phase) in actor_info.phases.iter().enumerate() {
             if let Some(actions) = &phase.on_enter
                 && actions.is_empty()
@@ -1944,128 +2350,1125 @@ fn v045_on_enter_non_empty(doc: &Document, errors: &mut Vec<ValidationError>) {
     }
 }
 
-// ─── W-001 ──────────────────────────────────────────────────────────────────
+// ─── V-046 ──────────────────────────────────────────────────────────────────
 
-fn w001_oatf_key_ordering(doc: &Document, warnings: &mut Vec<Diagnostic>) {
-    if !doc.oatf_is_first_key {
-        warnings.push(Diagnostic {
-            severity: DiagnosticSeverity::Warning,
-            code: "W-001".to_string(),
-            path: Some("oatf".to_string()),
-            message: "oatf key should be the first key in the document".to_string(),
-        });
+fn v046_mode_version_known(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
+    let mut modes: Vec<(String, &str)> = Vec::new();
+    if let Some(mode) = &doc.attack.execution.mode {
+        modes.push(("attack.execution.mode".to_string(), mode));
     }
-}
-
-// ─── W-004 ──────────────────────────────────────────────────────────────────
-
-fn w004_undeclared_extractor_refs(doc: &Document, warnings: &mut Vec<Diagnostic>) {
-    // Collect actor names so cross-actor references ({{actor.extractor}}) are not flagged
-    let actor_names: std::collections::HashSet<String> =
-        if let Some(actors) = &doc.attack.execution.actors {
-            actors.iter().map(|a| a.name.clone()).collect()
-        } else {
-            let mut set = std::collections::HashSet::new();
-            set.insert("default".to_string());
-            set
-        };
-
-    for actor_info in collect_actors(doc) {
-        for phase in actor_info.phases.iter() {
-            let declared: std::collections::HashSet<String> = phase
-                .extractors
-                .as_ref()
-                .map(|exts| exts.iter().map(|e| e.name.clone()).collect())
-                .unwrap_or_default();
-
-            let mut has_undeclared = false;
-
-            // Check state for template references
-            if let Some(state) = &phase.state {
-                has_undeclared |= check_undeclared_refs_in_value(state, &declared, &actor_names);
-            }
-
-            // Check on_enter actions for template references
-            if let Some(actions) = &phase.on_enter {
-                for action in actions {
-                    let action_value = serde_json::to_value(action).unwrap_or_default();
-                    has_undeclared |=
-                        check_undeclared_refs_in_value(&action_value, &declared, &actor_names);
-                }
+    if let Some(actors) = &doc.attack.execution.actors {
+        for (i, actor) in actors.iter().enumerate() {
+            modes.push((format!("attack.execution.actors[{}].mode", i), &actor.mode));
+        }
+    }
+    for actor_info in cx.actors() {
+        for (pi, phase) in actor_info.phases.iter().enumerate() {
+            if let Some(mode) = &phase.mode {
+                modes.push((
+                    format!("{}.phases[{}].mode", actor_info.path_prefix, pi),
+                    mode,
+                ));
             }
+        }
+    }
 
-            if has_undeclared {
-                warnings.push(Diagnostic {
-                    severity: DiagnosticSeverity::Warning,
-                    code: "W-004".to_string(),
-                    path: None,
-                    message: "template references undeclared extractor".to_string(),
-                });
-                return; // Emit once per document
+    for (path, mode) in modes {
+        // Malformed modes are reported by V-005 / V-036
+        if !MODE_RE.is_match(mode) {
+            continue;
+        }
+        if let (_, Some(version)) = split_mode_version(mode) {
+            let protocol = extract_protocol(mode);
+            if is_known_protocol_version(protocol, version) == Some(false) {
+                errors.push(verr(
+                    "V-046",
+                    path,
+                    format!(
+                        "unknown {} protocol version '{}' in mode '{}'",
+                        protocol, version, mode
+                    ),
+                ));
             }
         }
     }
 }
 
-fn check_undeclared_refs_in_value(
-    value: &serde_json::Value,
-    declared: &std::collections::HashSet<String>,
-    actor_names: &std::collections::HashSet<String>,
-) -> bool {
-    match value {
-        serde_json::Value::String(s) => {
-            for cap in TEMPLATE_VAR_RE.captures_iter(s) {
-                let var_name = &cap[1];
-                // Get the root (before any dot)
-                let root = var_name.split('.').next().unwrap_or(var_name);
-                // Skip request/response builtins and cross-actor references
-                if root == "request" || root == "response" || actor_names.contains(root) {
-                    continue;
-                }
-                if !declared.contains(root) {
-                    return true;
+// ─── V-047 ──────────────────────────────────────────────────────────────────
+
+fn v047_pacing_durations(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    for actor_info in cx.actors() {
+        for (pi, phase) in actor_info.phases.iter().enumerate() {
+            let Some(pacing) = &phase.pacing else {
+                continue;
+            };
+            for (field, value) in [("delay", &pacing.delay), ("jitter", &pacing.jitter)] {
+                if let Some(duration) = value
+                    && !is_valid_duration(duration)
+                {
+                    errors.push(verr(
+                        "V-047",
+                        format!("{}.phases[{}].pacing.{}", actor_info.path_prefix, pi, field),
+                        format!("invalid duration: '{}'", duration),
+                    ));
                 }
             }
-            false
         }
-        serde_json::Value::Array(arr) => arr
-            .iter()
-            .any(|v| check_undeclared_refs_in_value(v, declared, actor_names)),
-        serde_json::Value::Object(map) => map
-            .values()
-            .any(|v| check_undeclared_refs_in_value(v, declared, actor_names)),
-        _ => false,
     }
 }
 
-// ─── W-005 ──────────────────────────────────────────────────────────────────
+// ─── V-048 ──────────────────────────────────────────────────────────────────
 
-fn w005_indicator_protocol_mismatch(doc: &Document, warnings: &mut Vec<Diagnostic>) {
-    // Collect all protocols used by actors
-    let mut actor_protocols: std::collections::HashSet<String> = std::collections::HashSet::new();
+fn v048_safety_limits(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
+    let Some(safety) = &doc.attack.safety else {
+        return;
+    };
+    if let Some(runtime) = &safety.max_runtime
+        && !is_valid_duration(runtime)
+    {
+        errors.push(verr(
+            "V-048",
+            "attack.safety.max_runtime",
+            format!("invalid duration: '{}'", runtime),
+        ));
+    }
+    if let Some(max) = safety.max_messages
+        && max < 1
+    {
+        errors.push(verr(
+            "V-048",
+            "attack.safety.max_messages",
+            format!("max_messages must be at least 1, got {}", max),
+        ));
+    }
+    for (i, host) in safety.forbidden_hosts.iter().flatten().enumerate() {
+        let name = host.strip_prefix("*.").unwrap_or(host);
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || ".-:[]".contains(c))
+        {
+            errors.push(verr(
+                "V-048",
+                format!("attack.safety.forbidden_hosts[{}]", i),
+                format!(
+                    "forbidden host must be a host name or '*.' suffix pattern, got '{}'",
+                    host
+                ),
+            ));
+        }
+    }
+}
 
-    if let Some(mode) = &doc.attack.execution.mode {
-        actor_protocols.insert(extract_protocol(mode).to_string());
+// ─── V-049 ──────────────────────────────────────────────────────────────────
+
+fn v049_lifecycle_references(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
+    let attack = &doc.attack;
+    if attack.supersedes.as_ref().is_some_and(Vec::is_empty) {
+        errors.push(verr(
+            "V-049",
+            "attack.supersedes",
+            "supersedes, when present, must not be empty",
+        ));
     }
-    if let Some(actors) = &doc.attack.execution.actors {
-        for actor in actors {
-            actor_protocols.insert(extract_protocol(&actor.mode).to_string());
+    let references = attack
+        .supersedes
+        .iter()
+        .flatten()
+        .enumerate()
+        .map(|(i, id)| (format!("attack.supersedes[{}]", i), id))
+        .chain(
+            attack
+                .superseded_by
+                .iter()
+                .map(|id| ("attack.superseded_by".to_string(), id)),
+        );
+    for (path, id) in references {
+        if !ATTACK_ID_RE.is_match(id) {
+            errors.push(verr(
+                "V-049",
+                path,
+                format!("'{}' is not a valid attack ID", id),
+            ));
+        } else if attack.id.as_deref() == Some(id.as_str()) {
+            errors.push(verr(
+                "V-049",
+                path,
+                format!("attack '{}' cannot reference itself", id),
+            ));
         }
     }
-    // Also check phase modes
-    for actor_info in collect_actors(doc) {
-        for phase in actor_info.phases {
-            if let Some(mode) = &phase.mode {
-                actor_protocols.insert(extract_protocol(mode).to_string());
+}
+
+// ─── V-050 ──────────────────────────────────────────────────────────────────
+
+fn v050_false_positive_entries(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
+    for (i, ind) in doc.attack.indicators.iter().flatten().enumerate() {
+        for (j, fp) in ind.false_positives.iter().flatten().enumerate() {
+            let path = format!("attack.indicators[{}].false_positives[{}]", i, j);
+            if fp.description.trim().is_empty() {
+                errors.push(verr(
+                    "V-050",
+                    format!("{}.description", path),
+                    "false positive description must not be empty",
+                ));
+            }
+            let Some(when) = &fp.when else {
+                continue;
+            };
+            if when.is_empty() {
+                errors.push(verr(
+                    "V-050",
+                    format!("{}.when", path),
+                    "when, when present, must not be empty",
+                ));
+            }
+            for key in when.keys() {
+                if !is_valid_simple_dot_path(key) {
+                    errors.push(verr(
+                        "V-050",
+                        format!("{}.when.{}", path, key),
+                        format!("when key must be a valid simple dot-path, got '{}'", key),
+                    ));
+                }
             }
         }
     }
+}
 
-    if actor_protocols.is_empty() {
-        return;
-    }
+// ─── V-051 ──────────────────────────────────────────────────────────────────
 
-    if let Some(indicators) = &doc.attack.indicators {
+fn v051_indicator_stages(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
+    let mut used = std::collections::HashSet::new();
+    for (i, ind) in doc.attack.indicators.iter().flatten().enumerate() {
+        if let Some(stage) = &ind.stage {
+            used.insert(stage.as_str());
+            if !SNAKE_CASE_RE.is_match(stage) {
+                errors.push(verr(
+                    "V-051",
+                    format!("attack.indicators[{}].stage", i),
+                    format!("stage must match [a-z][a-z0-9_]*, got '{}'", stage),
+                ));
+            }
+        }
+    }
+
+    let Some(stages) = doc
+        .attack
+        .correlation
+        .as_ref()
+        .and_then(|c| c.stages.as_ref())
+    else {
+        return;
+    };
+    if stages.is_empty() {
+        errors.push(verr(
+            "V-051",
+            "attack.correlation.stages",
+            "stages, when present, must not be empty",
+        ));
+    }
+    let mut seen = std::collections::HashSet::new();
+    for (i, stage) in stages.iter().enumerate() {
+        let path = format!("attack.correlation.stages[{}]", i);
+        if !seen.insert(stage) {
+            errors.push(verr("V-051", path, format!("duplicate stage: {}", stage)));
+        } else if !used.contains(stage.as_str()) {
+            errors.push(verr(
+                "V-051",
+                path,
+                format!("no indicator has stage '{}'", stage),
+            ));
+        }
+    }
+}
+
+// ─── V-052 ──────────────────────────────────────────────────────────────────
+
+fn v052_profile_content(doc: &Document, profile: Profile, errors: &mut Vec<ValidationError>) {
+    match profile {
+        Profile::Full => {
+            if doc.attack.indicators.is_none() {
+                errors.push(verr(
+                    "V-052",
+                    "attack.indicators",
+                    "indicators are required by the full profile",
+                ));
+            }
+        }
+        Profile::ExecutionOnly => {
+            if doc.attack.indicators.is_some() {
+                errors.push(verr(
+                    "V-052",
+                    "attack.indicators",
+                    "indicators must be omitted in the execution_only profile",
+                ));
+            }
+            if doc.attack.correlation.is_some() {
+                errors.push(verr(
+                    "V-052",
+                    "attack.correlation",
+                    "correlation must be omitted in the execution_only profile",
+                ));
+            }
+        }
+    }
+}
+
+// ─── V-053 ──────────────────────────────────────────────────────────────────
+
+fn v053_frequency(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
+    for (i, ind) in doc.attack.indicators.iter().flatten().enumerate() {
+        let Some(frequency) = &ind.frequency else {
+            continue;
+        };
+        let path = format!("attack.indicators[{}].frequency", i);
+        if frequency.event.is_empty() {
+            errors.push(verr(
+                "V-053",
+                format!("{}.event", path),
+                "frequency.event must not be empty",
+            ));
+        }
+        if !is_valid_duration(&frequency.window) {
+            errors.push(verr(
+                "V-053",
+                format!("{}.window", path),
+                format!("invalid duration: '{}'", frequency.window),
+            ));
+        } else if parse_duration(&frequency.window).is_ok_and(|d| d.is_zero()) {
+            errors.push(verr(
+                "V-053",
+                format!("{}.window", path),
+                "frequency.window must be greater than zero",
+            ));
+        }
+        if frequency.threshold < 1 {
+            errors.push(verr(
+                "V-053",
+                format!("{}.threshold", path),
+                format!(
+                    "frequency.threshold must be at least 1, got {}",
+                    frequency.threshold
+                ),
+            ));
+        }
+        if let Some(group_by) = &frequency.group_by
+            && !is_valid_simple_dot_path(group_by)
+        {
+            errors.push(verr(
+                "V-053",
+                format!("{}.group_by", path),
+                format!(
+                    "group_by must be a valid simple dot-path, got '{}'",
+                    group_by
+                ),
+            ));
+        }
+        for key in frequency.match_predicate.iter().flat_map(|p| p.keys()) {
+            if !is_valid_simple_dot_path(key) {
+                errors.push(verr(
+                    "V-053",
+                    format!("{}.match.{}", path, key),
+                    format!(
+                        "match predicate key must be a valid simple dot-path, got '{}'",
+                        key
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+// ─── V-054 ──────────────────────────────────────────────────────────────────
+
+fn v054_captures(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
+    let mut declared = std::collections::HashSet::new();
+    for (i, ind) in doc.attack.indicators.iter().flatten().enumerate() {
+        let Some(captures) = &ind.capture else {
+            continue;
+        };
+        if captures.is_empty() {
+            errors.push(verr(
+                "V-054",
+                format!("attack.indicators[{}].capture", i),
+                "capture, when present, must contain at least one entry",
+            ));
+        }
+        let mut seen = std::collections::HashSet::new();
+        for (ci, capture) in captures.iter().enumerate() {
+            let path = format!("attack.indicators[{}].capture[{}]", i, ci);
+            if !SNAKE_CASE_RE.is_match(&capture.name) {
+                errors.push(verr(
+                    "V-054",
+                    format!("{}.name", path),
+                    format!(
+                        "capture name must match [a-z][a-z0-9_]*, got '{}'",
+                        capture.name
+                    ),
+                ));
+            } else if !seen.insert(capture.name.as_str()) {
+                errors.push(verr(
+                    "V-054",
+                    format!("{}.name", path),
+                    format!("duplicate capture name: {}", capture.name),
+                ));
+            }
+            declared.insert(capture.name.as_str());
+            let selector_error = match capture.capture_type {
+                crate::enums::ExtractorType::JsonPath => jsonpath_error(&capture.selector),
+                crate::enums::ExtractorType::Regex => match Regex::new(&capture.selector) {
+                    Err(e) => Some(format!("invalid regex: {}", e)),
+                    Ok(_) if !has_capture_group(&capture.selector) => Some(
+                        "regex capture selector must contain at least one capture group"
+                            .to_string(),
+                    ),
+                    Ok(_) => None,
+                },
+            };
+            if let Some(message) = selector_error {
+                errors.push(verr("V-054", format!("{}.selector", path), message));
+            }
+        }
+    }
+
+    let Some(key) = doc.attack.correlation.as_ref().and_then(|c| c.key.as_ref()) else {
+        return;
+    };
+    if key.is_empty() {
+        errors.push(verr(
+            "V-054",
+            "attack.correlation.key",
+            "key, when present, must not be empty",
+        ));
+    }
+    for (i, name) in key.iter().enumerate() {
+        if !declared.contains(name.as_str()) {
+            errors.push(verr(
+                "V-054",
+                format!("attack.correlation.key[{}]", i),
+                format!("no indicator captures '{}'", name),
+            ));
+        }
+    }
+}
+
+// ─── V-055 ──────────────────────────────────────────────────────────────────
+
+fn v055_origin_predicates(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
+    for (i, ind) in doc.attack.indicators.iter().flatten().enumerate() {
+        if let Some(origin) = &ind.origin {
+            let sender = lookup_surface(&ind.surface).and_then(|s| s.origin);
+            check_origin_predicate(
+                origin,
+                sender,
+                &format!("attack.indicators[{}].origin", i),
+                errors,
+            );
+        }
+    }
+    for actor_info in cx.actors() {
+        for (pi, phase) in actor_info.phases.iter().enumerate() {
+            if let Some(origin) = phase.trigger.as_ref().and_then(|t| t.origin.as_ref()) {
+                let path = format!("{}.phases[{}].trigger.origin", actor_info.path_prefix, pi);
+                check_origin_predicate(origin, None, &path, errors);
+            }
+        }
+    }
+}
+
+/// Check an origin predicate's keys and values. `sender` is the party the
+/// surface registry says produces the content; third-party content may be
+/// relayed through any surface.
+fn check_origin_predicate(
+    predicate: &MatchPredicate,
+    sender: Option<OriginRole>,
+    path: &str,
+    errors: &mut Vec<ValidationError>,
+) {
+    for (key, entry) in predicate {
+        let entry_path = format!("{}.{}", path, key);
+        match key.as_str() {
+            "role" => {
+                let roles = match entry {
+                    MatchEntry::Scalar(value) => vec![value],
+                    MatchEntry::Condition(cond) => cond.any_of.iter().flatten().collect(),
+                };
+                for role in roles {
+                    let parsed = serde_json::from_value::<OriginRole>(role.clone()).ok();
+                    match parsed {
+                        None => errors.push(verr(
+                            "V-055",
+                            entry_path.clone(),
+                            format!("role must be client, server, or third_party, got {}", role),
+                        )),
+                        Some(role)
+                            if sender.is_some_and(|s| s != role)
+                                && role != OriginRole::ThirdParty =>
+                        {
+                            errors.push(verr(
+                                "V-055",
+                                entry_path.clone(),
+                                format!(
+                                    "the surface's content is sent by the {}, never the {}",
+                                    sender.map_or("", |s| s.as_str()),
+                                    role.as_str()
+                                ),
+                            ))
+                        }
+                        Some(_) => {}
+                    }
+                }
+            }
+            "trusted" => {
+                if let MatchEntry::Scalar(value) = entry
+                    && !value.is_boolean()
+                {
+                    errors.push(verr(
+                        "V-055",
+                        entry_path,
+                        format!("trusted must be a boolean, got {}", value),
+                    ));
+                }
+            }
+            "endpoint" => {}
+            _ => errors.push(verr(
+                "V-055",
+                entry_path,
+                format!(
+                    "origin predicate keys must be role, endpoint, or trusted, got '{}'",
+                    key
+                ),
+            )),
+        }
+    }
+}
+
+// ─── V-056 ──────────────────────────────────────────────────────────────────
+
+fn v056_trigger_match_refs(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let extracted_by: std::collections::HashMap<&str, std::collections::HashSet<&str>> = cx
+        .actors()
+        .iter()
+        .map(|actor| (actor.name, extractor_names(actor.phases.iter())))
+        .collect();
+
+    for actor_info in cx.actors() {
+        for (pi, phase) in actor_info.phases.iter().enumerate() {
+            let Some(predicate) = phase
+                .trigger
+                .as_ref()
+                .and_then(|t| t.match_predicate.as_ref())
+            else {
+                continue;
+            };
+            // The phase's own extractors run on the message being matched, so
+            // a predicate referencing them would compare the message with
+            // itself.
+            let earlier = extractor_names(actor_info.phases[..pi].iter());
+            let mut keys: Vec<&String> = predicate.keys().collect();
+            keys.sort();
+            for key in keys {
+                let path = format!(
+                    "{}.phases[{}].trigger.match.{}",
+                    actor_info.path_prefix, pi, key
+                );
+                let value = serde_json::to_value(&predicate[key]).unwrap_or_default();
+                let mut references = Vec::new();
+                collect_template_refs(&value, &mut references);
+                for reference in references {
+                    let message = match reference.split_once('.') {
+                        None if !earlier.contains(reference.as_str()) => format!(
+                            "'{{{{{}}}}}' is not extracted by an earlier phase of actor '{}'",
+                            reference, actor_info.name
+                        ),
+                        Some((root, _)) if root == "request" || root == "response" => format!(
+                            "'{{{{{}}}}}' cannot be resolved: trigger predicates only see extracted values",
+                            reference
+                        ),
+                        Some((actor, name))
+                            if !extracted_by
+                                .get(actor)
+                                .is_some_and(|names| names.contains(name)) =>
+                        {
+                            format!(
+                                "'{{{{{}}}}}' is not extracted by actor '{}'",
+                                reference, actor
+                            )
+                        }
+                        _ => continue,
+                    };
+                    errors.push(verr("V-056", path.clone(), message));
+                }
+            }
+        }
+    }
+}
+
+fn extractor_names<'a>(
+    phases: impl Iterator<Item = &'a Phase>,
+) -> std::collections::HashSet<&'a str> {
+    phases
+        .flat_map(|phase| phase.extractors.iter().flatten())
+        .map(|extractor| extractor.name.as_str())
+        .collect()
+}
+
+fn collect_template_refs(value: &serde_json::Value, references: &mut Vec<String>) {
+    match value {
+        serde_json::Value::String(s) => references.extend(
+            TEMPLATE_VAR_RE
+                .captures_iter(s)
+                .map(|cap| cap[1].to_string()),
+        ),
+        serde_json::Value::Array(arr) => arr
+            .iter()
+            .for_each(|v| collect_template_refs(v, references)),
+        serde_json::Value::Object(map) => map
+            .values()
+            .for_each(|v| collect_template_refs(v, references)),
+        _ => {}
+    }
+}
+
+// ─── V-057 ──────────────────────────────────────────────────────────────────
+
+fn v057_on_timeout(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    for actor_info in cx.actors() {
+        for (pi, phase) in actor_info.phases.iter().enumerate() {
+            let Some(actions) = &phase.on_timeout else {
+                continue;
+            };
+            let path = format!("{}.phases[{}].on_timeout", actor_info.path_prefix, pi);
+            if actions.is_empty() {
+                errors.push(verr(
+                    "V-057",
+                    path.clone(),
+                    "on_timeout, when present, must contain at least one action",
+                ));
+            }
+            if phase.trigger.as_ref().is_none_or(|t| t.after.is_none()) {
+                errors.push(verr(
+                    "V-057",
+                    path,
+                    "on_timeout requires a trigger with an after timeout",
+                ));
+            }
+        }
+    }
+}
+
+// ─── V-058 ──────────────────────────────────────────────────────────────────
+
+fn v058_attachments(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let doc = cx.document();
+    let attachments = doc.attack.attachments.as_deref().unwrap_or_default();
+    let mut seen = std::collections::HashSet::new();
+    for (i, attachment) in attachments.iter().enumerate() {
+        let path = format!("attack.attachments[{}]", i);
+        if !SNAKE_CASE_RE.is_match(&attachment.name) {
+            errors.push(verr(
+                "V-058",
+                format!("{}.name", path),
+                format!(
+                    "attachment name must match [a-z][a-z0-9_]*, got '{}'",
+                    attachment.name
+                ),
+            ));
+        } else if !seen.insert(attachment.name.as_str()) {
+            errors.push(verr(
+                "V-058",
+                format!("{}.name", path),
+                format!("duplicate attachment name '{}'", attachment.name),
+            ));
+        }
+        if !CONTENT_TYPE_RE.is_match(&attachment.content_type) {
+            errors.push(verr(
+                "V-058",
+                format!("{}.content_type", path),
+                format!(
+                    "content_type must be a MIME type (type/subtype), got '{}'",
+                    attachment.content_type
+                ),
+            ));
+        }
+        if let Err(e) = attachment.decode() {
+            errors.push(verr("V-058", format!("{}.data", path), e.message));
+        }
+    }
+
+    let mut states = Vec::new();
+    if let Some(state) = &doc.attack.execution.state {
+        states.push((state, "attack.execution.state".to_string()));
+    }
+    for actor_info in cx.actors() {
+        for (pi, phase) in actor_info.phases.iter().enumerate() {
+            if let Some(state) = &phase.state {
+                states.push((
+                    state,
+                    format!("{}.phases[{}].state", actor_info.path_prefix, pi),
+                ));
+            }
+        }
+    }
+    for (state, path) in states {
+        check_attachment_refs(state, &path, &seen, errors);
+    }
+}
+
+fn check_attachment_refs(
+    value: &serde_json::Value,
+    path: &str,
+    declared: &std::collections::HashSet<&str>,
+    errors: &mut Vec<ValidationError>,
+) {
+    match value {
+        serde_json::Value::String(s) => {
+            for name in crate::attachments::references(s) {
+                if !declared.contains(name) {
+                    errors.push(verr(
+                        "V-058",
+                        path.to_string(),
+                        format!("reference to undeclared attachment '{}'", name),
+                    ));
+                }
+            }
+        }
+        serde_json::Value::Array(arr) => {
+            for (i, v) in arr.iter().enumerate() {
+                check_attachment_refs(v, &format!("{}[{}]", path, i), declared, errors);
+            }
+        }
+        serde_json::Value::Object(map) => {
+            for (k, v) in map {
+                check_attachment_refs(v, &format!("{}.{}", path, k), declared, errors);
+            }
+        }
+        _ => {}
+    }
+}
+
+// ─── V-059 ──────────────────────────────────────────────────────────────────
+
+/// Counts the bytes written to it, to measure serialized state without
+/// buffering it.
+struct ByteCount(usize);
+
+impl std::io::Write for ByteCount {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn v059_resource_limits(doc: &Document, limits: &ResourceLimits) -> Vec<(String, String)> {
+    let cx = ResolvedContext::new(doc);
+    let mut states = Vec::new();
+    if let Some(state) = &doc.attack.execution.state {
+        states.push((state, "attack.execution.state".to_string()));
+    }
+    for actor_info in cx.actors() {
+        for (pi, phase) in actor_info.phases.iter().enumerate() {
+            if let Some(state) = &phase.state {
+                states.push((
+                    state,
+                    format!("{}.phases[{}].state", actor_info.path_prefix, pi),
+                ));
+            }
+        }
+    }
+
+    let mut violations = Vec::new();
+    for (state, path) in states {
+        if let Some(max) = limits.max_state_bytes {
+            let mut count = ByteCount(0);
+            if serde_json::to_writer(&mut count, state).is_ok() && count.0 > max {
+                violations.push((
+                    path.clone(),
+                    format!("state is {} bytes, over the limit of {}", count.0, max),
+                ));
+            }
+        }
+        if let (Some(max), Some(tools)) = (limits.max_tools, state["tools"].as_array())
+            && tools.len() > max
+        {
+            violations.push((
+                format!("{}.tools", path),
+                format!("state has {} tools, over the limit of {}", tools.len(), max),
+            ));
+        }
+        let depth = check_state_strings(state, &path, 0, limits, &mut violations);
+        if let Some(max) = limits.max_depth
+            && depth > max
+        {
+            violations.push((
+                path,
+                format!(
+                    "state nests {} levels deep, over the limit of {}",
+                    depth, max
+                ),
+            ));
+        }
+    }
+    violations
+}
+
+/// Reports strings of `value` longer than `max_string_length` and returns
+/// the nesting depth of `value` below `depth`.
+fn check_state_strings(
+    value: &serde_json::Value,
+    path: &str,
+    depth: usize,
+    limits: &ResourceLimits,
+    violations: &mut Vec<(String, String)>,
+) -> usize {
+    match value {
+        serde_json::Value::String(s) => {
+            if let Some(max) = limits.max_string_length
+                && s.len() > max
+            {
+                violations.push((
+                    path.to_string(),
+                    format!("string is {} bytes, over the limit of {}", s.len(), max),
+                ));
+            }
+            depth
+        }
+        serde_json::Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                check_state_strings(
+                    v,
+                    &format!("{}[{}]", path, i),
+                    depth + 1,
+                    limits,
+                    violations,
+                )
+            })
+            .fold(depth + 1, usize::max),
+        serde_json::Value::Object(map) => map
+            .iter()
+            .map(|(k, v)| {
+                check_state_strings(v, &format!("{}.{}", path, k), depth + 1, limits, violations)
+            })
+            .fold(depth + 1, usize::max),
+        _ => depth,
+    }
+}
+
+// ─── V-060 ──────────────────────────────────────────────────────────────────
+
+fn v060_actor_endpoints(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let actors = cx.document().attack.execution.actors.as_deref();
+    for (i, actor) in actors.unwrap_or_default().iter().enumerate() {
+        let Some(endpoint) = &actor.endpoint else {
+            continue;
+        };
+        let path = format!("attack.execution.actors[{}].endpoint", i);
+        match (&endpoint.url, &endpoint.command) {
+            (Some(url), None) => {
+                let host = url.split_once("://").map_or("", |(_, rest)| rest);
+                if endpoint.transport().is_none() || host.is_empty() || host.starts_with('/') {
+                    errors.push(verr(
+                        "V-060",
+                        format!("{}.url", path),
+                        format!(
+                            "endpoint url must be an http, https, ws, or wss URL with a host, got '{}'",
+                            url
+                        ),
+                    ));
+                }
+            }
+            (None, Some(command)) => {
+                if command.first().is_none_or(|program| program.is_empty()) {
+                    errors.push(verr(
+                        "V-060",
+                        format!("{}.command", path),
+                        "endpoint command must start with a program",
+                    ));
+                }
+            }
+            _ => errors.push(verr(
+                "V-060",
+                path,
+                "endpoint must set exactly one of url or command",
+            )),
+        }
+    }
+}
+
+// ─── V-061 ──────────────────────────────────────────────────────────────────
+
+fn v061_phase_requires(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let extracted_by: std::collections::HashMap<&str, std::collections::HashSet<&str>> = cx
+        .actors()
+        .iter()
+        .map(|actor| (actor.name, extractor_names(actor.phases.iter())))
+        .collect();
+
+    for actor_info in cx.actors() {
+        for (pi, phase) in actor_info.phases.iter().enumerate() {
+            let Some(requires) = &phase.requires else {
+                continue;
+            };
+            let path = format!("{}.phases[{}].requires", actor_info.path_prefix, pi);
+            if requires.is_empty() {
+                errors.push(verr("V-061", path, "requires must not be empty"));
+                continue;
+            }
+            // Preconditions are checked before the phase is entered, so only
+            // earlier phases can have extracted the value.
+            let earlier = extractor_names(actor_info.phases[..pi].iter());
+            let mut keys: Vec<&String> = requires.keys().collect();
+            keys.sort();
+            for key in keys {
+                let message = match key.split_once('.') {
+                    None if !earlier.contains(key.as_str()) => format!(
+                        "'{}' is not extracted by an earlier phase of actor '{}'",
+                        key, actor_info.name
+                    ),
+                    Some((actor, name)) if actor == actor_info.name => {
+                        format!("'{}' refers to the phase's own actor; use '{}'", key, name)
+                    }
+                    Some((actor, name))
+                        if !extracted_by
+                            .get(actor)
+                            .is_some_and(|names| names.contains(name)) =>
+                    {
+                        format!("'{}' is not extracted by actor '{}'", key, actor)
+                    }
+                    _ => continue,
+                };
+                errors.push(verr("V-061", format!("{}.{}", path, key), message));
+            }
+        }
+    }
+}
+
+// ─── V-062 ──────────────────────────────────────────────────────────────────
+
+fn v062_license_expression(cx: &ResolvedContext<'_>, errors: &mut Vec<ValidationError>) {
+    let Some(license) = &cx.document().attack.license else {
+        return;
+    };
+    if let Err(reason) = check_license_expression(license) {
+        errors.push(verr(
+            "V-062",
+            "attack.license",
+            format!(
+                "'{}' is not a valid SPDX license expression: {}",
+                license, reason
+            ),
+        ));
+    }
+}
+
+/// Check `expression` against the SPDX license expression grammar: license
+/// IDs and `LicenseRef-` references combined with `AND`, `OR`, and
+/// `WITH <exception>`, and grouped with parentheses. IDs are not checked
+/// against the SPDX license list.
+fn check_license_expression(expression: &str) -> Result<(), String> {
+    let spaced = expression.replace('(', " ( ").replace(')', " ) ");
+    let tokens: Vec<&str> = spaced.split_whitespace().collect();
+    if tokens.is_empty() {
+        return Err("it is empty".to_string());
+    }
+    let mut pos = 0;
+    license_compound(&tokens, &mut pos)?;
+    match tokens.get(pos) {
+        None => Ok(()),
+        Some(token) => Err(unexpected_license_token(token)),
+    }
+}
+
+/// `compound := term (("AND" | "OR") term)*`. Operator precedence does not
+/// affect whether an expression is well formed.
+fn license_compound(tokens: &[&str], pos: &mut usize) -> Result<(), String> {
+    license_term(tokens, pos)?;
+    while let Some(&op) = tokens.get(*pos)
+        && (op == "AND" || op == "OR")
+    {
+        *pos += 1;
+        license_term(tokens, pos)?;
+    }
+    Ok(())
+}
+
+/// `term := "(" compound ")" | license ["WITH" exception]`.
+fn license_term(tokens: &[&str], pos: &mut usize) -> Result<(), String> {
+    let Some(&token) = tokens.get(*pos) else {
+        return Err("it ends where a license was expected".to_string());
+    };
+    *pos += 1;
+    if token == "(" {
+        license_compound(tokens, pos)?;
+        if tokens.get(*pos) != Some(&")") {
+            return Err("'(' is not closed".to_string());
+        }
+        *pos += 1;
+        return Ok(());
+    }
+    if is_license_operator(token) || token == ")" {
+        return Err(unexpected_license_token(token));
+    }
+    if !LICENSE_ID_RE.is_match(token) {
+        return Err(format!("'{}' is not a license ID", token));
+    }
+    if tokens.get(*pos) == Some(&"WITH") {
+        *pos += 1;
+        match tokens.get(*pos) {
+            Some(exception)
+                if !is_license_operator(exception) && LICENSE_EXCEPTION_RE.is_match(exception) =>
+            {
+                *pos += 1
+            }
+            _ => return Err("WITH must be followed by an exception ID".to_string()),
+        }
+    }
+    Ok(())
+}
+
+fn is_license_operator(token: &str) -> bool {
+    ["AND", "OR", "WITH"]
+        .iter()
+        .any(|op| op.eq_ignore_ascii_case(token))
+}
+
+fn unexpected_license_token(token: &str) -> String {
+    if is_license_operator(token) && token != token.to_ascii_uppercase() {
+        format!(
+            "operators are uppercase; use '{}'",
+            token.to_ascii_uppercase()
+        )
+    } else {
+        format!("unexpected '{}'", token)
+    }
+}
+
+// ─── W-001 ──────────────────────────────────────────────────────────────────
+
+fn w001_oatf_key_ordering(cx: &ResolvedContext<'_>, warnings: &mut Vec<Diagnostic>) {
+    let doc = cx.document();
+    if !doc.oatf_is_first_key {
+        warnings.push(Diagnostic {
+            severity: DiagnosticSeverity::Warning,
+            code: "W-001".to_string(),
+            path: Some("oatf".to_string()),
+            message: "oatf key should be the first key in the document".to_string(),
+            suggestion: None,
+        });
+    }
+}
+
+// ─── W-004 ──────────────────────────────────────────────────────────────────
+
+fn w004_undeclared_extractor_refs(cx: &ResolvedContext<'_>, warnings: &mut Vec<Diagnostic>) {
+    let doc = cx.document();
+    // Collect actor names so cross-actor references ({{actor.extractor}}) are not flagged
+    let actor_names: std::collections::HashSet<String> =
+        if let Some(actors) = &doc.attack.execution.actors {
+            actors.iter().map(|a| a.name.clone()).collect()
+        } else {
+            let mut set = std::collections::HashSet::new();
+            set.insert("default".to_string());
+            set
+        };
+
+    for actor_info in cx.actors() {
+        for phase in actor_info.phases.iter() {
+            let declared: std::collections::HashSet<String> = phase
+                .extractors
+                .as_ref()
+                .map(|exts| exts.iter().map(|e| e.name.clone()).collect())
+                .unwrap_or_default();
+
+            let mut has_undeclared = false;
+
+            // Check state for template references
+            if let Some(state) = &phase.state {
+                has_undeclared |= check_undeclared_refs_in_value(state, &declared, &actor_names);
+            }
+
+            // Check on_enter and on_timeout actions for template references
+            for actions in [&phase.on_enter, &phase.on_timeout] {
+                for action in actions.iter().flatten() {
+                    let action_value = serde_json::to_value(action).unwrap_or_default();
+                    has_undeclared |=
+                        check_undeclared_refs_in_value(&action_value, &declared, &actor_names);
+                }
+            }
+
+            if has_undeclared {
+                warnings.push(Diagnostic {
+                    severity: DiagnosticSeverity::Warning,
+                    code: "W-004".to_string(),
+                    path: None,
+                    message: "template references undeclared extractor".to_string(),
+                    suggestion: None,
+                });
+                return; // Emit once per document
+            }
+        }
+    }
+}
+
+fn check_undeclared_refs_in_value(
+    value: &serde_json::Value,
+    declared: &std::collections::HashSet<String>,
+    actor_names: &std::collections::HashSet<String>,
+) -> bool {
+    match value {
+        serde_json::Value::String(s) => {
+            for cap in TEMPLATE_VAR_RE.captures_iter(s) {
+                let var_name = &cap[1];
+                // Get the root (before any dot)
+                let root = var_name.split('.').next().unwrap_or(var_name);
+                // Skip request/response builtins and cross-actor references
+                if root == "request" || root == "response" || actor_names.contains(root) {
+                    continue;
+                }
+                if !declared.contains(root) {
+                    return true;
+                }
+            }
+            false
+        }
+        serde_json::Value::Array(arr) => arr
+            .iter()
+            .any(|v| check_undeclared_refs_in_value(v, declared, actor_names)),
+        serde_json::Value::Object(map) => map
+            .values()
+            .any(|v| check_undeclared_refs_in_value(v, declared, actor_names)),
+        _ => false,
+    }
+}
+
+// ─── W-005 ──────────────────────────────────────────────────────────────────
+
+fn w005_indicator_protocol_mismatch(cx: &ResolvedContext<'_>, warnings: &mut Vec<Diagnostic>) {
+    let doc = cx.document();
+    // Collect all protocols used by actors
+    let actor_protocols: std::collections::HashSet<&str> =
+        cx.modes().map(extract_protocol).collect();
+
+    if actor_protocols.is_empty() {
+        return;
+    }
+
+    if let Some(indicators) = &doc.attack.indicators {
         for ind in indicators {
             if let Some(protocol) = &ind.protocol
                 && !actor_protocols.contains(protocol.as_str())
@@ -2078,9 +3481,236 @@ fn w005_indicator_protocol_mismatch(doc: &Document, warnings: &mut Vec<Diagnosti
                         "indicator protocol '{}' does not match any actor protocol",
                         protocol
                     ),
+                    suggestion: None,
                 });
                 return; // Emit once per document
             }
         }
     }
 }
+
+// ─── W-006 ──────────────────────────────────────────────────────────────────
+
+fn w006_superseded_not_deprecated(cx: &ResolvedContext<'_>, warnings: &mut Vec<Diagnostic>) {
+    let doc = cx.document();
+    if let Some(successor) = &doc.attack.superseded_by
+        && doc.attack.status != Some(Status::Deprecated)
+    {
+        warnings.push(Diagnostic {
+            severity: DiagnosticSeverity::Warning,
+            code: "W-006".to_string(),
+            path: Some("attack.status".to_string()),
+            message: format!(
+                "attack is superseded by '{}' but its status is not deprecated",
+                successor
+            ),
+            suggestion: None,
+        });
+    }
+}
+
+// ─── W-007 ──────────────────────────────────────────────────────────────────
+
+/// Indicators on a surface the execution state never creates (e.g.
+/// `prompt_content` with no prompt responses in any state) cannot fire in a
+/// document-driven simulation. Only checked for surfaces of protocols that
+/// every actor serves, since client-mode state does not describe what the
+/// remote server exposes.
+fn w007_indicator_surface_never_served(cx: &ResolvedContext<'_>, warnings: &mut Vec<Diagnostic>) {
+    let doc = cx.document();
+    let Some(indicators) = &doc.attack.indicators else {
+        return;
+    };
+
+    let modes: Vec<&str> = cx.modes().collect();
+    let served = |protocol: &str| {
+        let mut protocol_modes = modes
+            .iter()
+            .map(|m| split_mode_version(m).0)
+            .filter(|m| extract_protocol(m) == protocol)
+            .peekable();
+        protocol_modes.peek().is_some() && protocol_modes.all(|m| m.ends_with("_server"))
+    };
+
+    let messages = crate::projection::project_document(doc);
+    for (i, ind) in indicators.iter().enumerate() {
+        let Some(entry) = lookup_surface(&ind.surface) else {
+            continue;
+        };
+        if !crate::projection::is_projected_surface(entry.surface) || !served(entry.protocol) {
+            continue;
+        }
+        let reachable = messages
+            .iter()
+            .filter(|m| m.surfaces.contains(&entry.surface))
+            .any(|m| !resolve_wildcard_path_ref(entry.default_target, &m.message).is_empty());
+        if !reachable {
+            warnings.push(Diagnostic {
+                severity: DiagnosticSeverity::Warning,
+                code: "W-007".to_string(),
+                path: Some(format!("attack.indicators[{}].surface", i)),
+                message: format!(
+                    "no execution state creates the '{}' surface, so this indicator can never match",
+                    ind.surface
+                ),
+                suggestion: None,
+            });
+        }
+    }
+}
+
+// ─── W-008 ──────────────────────────────────────────────────────────────────
+
+fn w008_malformed_cve_ids(cx: &ResolvedContext<'_>, warnings: &mut Vec<Diagnostic>) {
+    let doc = cx.document();
+    for (i, reference) in doc.attack.references.iter().flatten().enumerate() {
+        let fields = [
+            ("url", Some(&reference.url)),
+            ("title", reference.title.as_ref()),
+        ];
+        for (field, text) in fields {
+            let Some(text) = text else {
+                continue;
+            };
+            for token in crate::references::malformed_cve_ids(text) {
+                warnings.push(Diagnostic {
+                    severity: DiagnosticSeverity::Warning,
+                    code: "W-008".to_string(),
+                    path: Some(format!("attack.references[{}].{}", i, field)),
+                    message: format!(
+                        "'{}' is not a well-formed CVE ID (CVE-YYYY-NNNN with a year from 1999 and at least four digits)",
+                        token
+                    ),
+                    suggestion: None,
+                });
+            }
+        }
+    }
+}
+
+// ─── W-010 ──────────────────────────────────────────────────────────────────
+
+/// A category that cannot be observed on any of the indicator surfaces is
+/// usually a classification copied from another document. Only documents
+/// whose indicator surfaces are all registered are checked.
+fn w010_category_surface_mismatch(cx: &ResolvedContext<'_>, warnings: &mut Vec<Diagnostic>) {
+    let doc = cx.document();
+    let Some(category) = doc
+        .attack
+        .classification
+        .as_ref()
+        .and_then(|c| c.category.as_ref())
+    else {
+        return;
+    };
+    let surfaces: Option<Vec<&SurfaceEntry>> = doc
+        .attack
+        .indicators
+        .iter()
+        .flatten()
+        .map(|ind| lookup_surface(&ind.surface))
+        .collect();
+    let Some(surfaces) = surfaces.filter(|s| !s.is_empty()) else {
+        return;
+    };
+    let name = serde_json::to_value(category)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default();
+
+    let message = if *category == Category::CrossProtocolChain {
+        let mut protocols: Vec<&str> = cx
+            .modes()
+            .map(|m| extract_protocol(split_mode_version(m).0))
+            .chain(surfaces.iter().map(|s| s.protocol))
+            .collect();
+        protocols.sort_unstable();
+        protocols.dedup();
+        if protocols.len() > 1 {
+            return;
+        }
+        format!(
+            "classification.category '{}' but the modes and indicator surfaces only use the '{}' protocol",
+            name,
+            protocols.join("', '")
+        )
+    } else {
+        let Some((_, compatible)) = CATEGORY_SURFACES.iter().find(|(c, _)| c == category) else {
+            return;
+        };
+        if surfaces.iter().any(|s| compatible.contains(&s.surface)) {
+            return;
+        }
+        let mut used: Vec<&str> = surfaces.iter().map(|s| s.surface).collect();
+        used.dedup();
+        format!(
+            "classification.category '{}' is not observable on the indicator surfaces ({}); expected a surface such as {}",
+            name,
+            used.join(", "),
+            compatible[..3].join(", ")
+        )
+    };
+    warnings.push(Diagnostic {
+        severity: DiagnosticSeverity::Warning,
+        code: "W-010".to_string(),
+        path: Some("attack.classification.category".to_string()),
+        message,
+        suggestion: None,
+    });
+}
+
+// ─── W-011 ──────────────────────────────────────────────────────────────────
+
+/// Trigger events and action keys that are undefined for their mode but close
+/// to a defined name, and so are likely misspelled. Names with no close match
+/// are taken to be binding-specific and are not reported.
+fn w011_likely_misspellings(cx: &ResolvedContext<'_>, warnings: &mut Vec<Diagnostic>) {
+    let mut warn = |path: String, message: String, suggestion: String| {
+        warnings.push(Diagnostic {
+            severity: DiagnosticSeverity::Warning,
+            code: "W-011".to_string(),
+            path: Some(path),
+            message,
+            suggestion: Some(suggestion),
+        })
+    };
+
+    for actor_info in cx.actors() {
+        for (pi, phase) in actor_info.phases.iter().enumerate() {
+            let prefix = format!("{}.phases[{}]", actor_info.path_prefix, pi);
+            let mode = actor_info
+                .phase_mode(pi)
+                .or(actor_info.mode)
+                .map(|m| split_mode_version(m).0);
+            if let Some(mode) = mode
+                && KNOWN_MODES.contains(&mode)
+                && let Some(event) = phase.trigger.as_ref().and_then(|t| t.event.as_deref())
+                && lookup_event(strip_event_qualifier(event)).is_none()
+                && let Some(suggestion) = suggest_event(event, mode)
+            {
+                warn(
+                    format!("{}.trigger.event", prefix),
+                    format!("unrecognized event '{}' for mode '{}'", event, mode),
+                    suggestion,
+                );
+            }
+
+            for (key, actions) in [
+                ("on_enter", &phase.on_enter),
+                ("on_timeout", &phase.on_timeout),
+            ] {
+                for (ai, action) in actions.iter().flatten().enumerate() {
+                    if let Action::BindingSpecific { key: name, .. } = action
+                        && let Some(suggestion) = closest(name, ACTION_KEYS.iter().copied())
+                    {
+                        warn(
+                            format!("{}.{}[{}].{}", prefix, key, ai, name),
+                            format!("unrecognized action key '{}'", name),
+                            suggestion.to_string(),
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,666 @@
+//! The validation rule engine.
+//!
+//! Every check [`validate`](super::validate) performs is a [`Rule`]: an ID,
+//! a severity, the rules it depends on, the kinds of document nodes it
+//! inspects, and the check itself. A [`RuleEngine`] orders its rules so
+//! prerequisites run first, skips a rule when a prerequisite reported errors
+//! (an invalid `attack.id` makes every indicator-ID prefix check noise), and
+//! runs either every rule or a selection ([`RunOptions`]), sequentially or in
+//! parallel. Findings are always reported in rule order, so the result is the
+//! same either way.
+//!
+//! Each rule also declares the document paths it reads: the paths of its
+//! node kinds ([`NodeKind::paths`]) plus any it lists itself
+//! ([`Rule::watching`]). [`RunOptions::changed_paths`] uses them to run only
+//! the rules an edit can affect.
+//!
+//! Tools add organization-specific checks by registering their own rules on
+//! [`RuleEngine::builtin`]:
+//!
+//! ```rust
+//! use oatf::validate::rules::{NodeKind, Rule, RuleEngine, RunOptions};
+//! use oatf::{DiagnosticSeverity, ValidationError};
+//!
+//! let mut engine = RuleEngine::builtin();
+//! engine
+//!     .register(Rule::new(
+//!         "X-ACME-001",
+//!         DiagnosticSeverity::Error,
+//!         &[NodeKind::Attack],
+//!         |cx, out| {
+//!             if cx.document().attack.author.is_none() {
+//!                 out.errors.push(ValidationError {
+//!                     rule: "X-ACME-001".to_string(),
+//!                     spec_ref: String::new(),
+//!                     path: "attack.author".to_string(),
+//!                     message: "attack.author is required".to_string(),
+//!                     suggestion: None,
+//!                 });
+//!             }
+//!         },
+//!     ))
+//!     .unwrap();
+//! let doc = oatf::parse("oatf: \"0.1\"\nattack:\n  execution:\n    mode: mcp_server\n    state: {tools: []}\n").unwrap();
+//! let result = engine.run(&doc, &RunOptions::default());
+//! assert_eq!(result.errors[0].rule, "X-ACME-001");
+//! ```
+
+use super::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Kinds of document nodes a rule inspects, for selecting rules by the part
+/// of the document they cover.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeKind {
+    /// The document root (`oatf`, key order).
+    Document,
+    /// Attack metadata: identity, severity, lifecycle, references, safety.
+    Attack,
+    /// The execution form, modes, and actors.
+    Execution,
+    /// Phases.
+    Phase,
+    /// Phase triggers.
+    Trigger,
+    /// Phase extractors.
+    Extractor,
+    /// `on_enter` and `on_timeout` actions.
+    Action,
+    /// Execution state (tools, responses, templates).
+    State,
+    /// Indicators.
+    Indicator,
+    /// `attack.correlation`.
+    Correlation,
+}
+
+/// Every place an effective phase mode comes from, for rules that infer
+/// protocols or check events against modes.
+const MODES: &[&str] = &[
+    "attack.execution.mode",
+    "attack.execution.actors[*].mode",
+    "attack.execution.phases[*].mode",
+    "attack.execution.actors[*].phases[*].mode",
+];
+
+impl NodeKind {
+    /// Path patterns of the nodes of this kind. Patterns are dotted paths
+    /// in the form diagnostics use, where `[*]` matches any index.
+    pub fn paths(self) -> &'static [&'static str] {
+        match self {
+            NodeKind::Document => &["oatf"],
+            NodeKind::Attack => &[
+                "attack.id",
+                "attack.name",
+                "attack.version",
+                "attack.status",
+                "attack.created",
+                "attack.modified",
+                "attack.author",
+                "attack.description",
+                "attack.grace_period",
+                "attack.license",
+                "attack.distribution",
+                "attack.severity",
+                "attack.impact",
+                "attack.classification",
+                "attack.references",
+                "attack.attachments",
+                "attack.safety",
+                "attack.supersedes",
+                "attack.superseded_by",
+            ],
+            NodeKind::Execution => &[
+                "attack.execution.mode",
+                "attack.execution.actors[*].name",
+                "attack.execution.actors[*].mode",
+                "attack.execution.actors[*].trust",
+                "attack.execution.actors[*].endpoint",
+            ],
+            NodeKind::Phase => &[
+                "attack.execution.state",
+                "attack.execution.phases[*]",
+                "attack.execution.actors[*].phases[*]",
+            ],
+            NodeKind::Trigger => &[
+                "attack.execution.phases[*].trigger",
+                "attack.execution.actors[*].phases[*].trigger",
+            ],
+            NodeKind::Extractor => &[
+                "attack.execution.phases[*].extractors",
+                "attack.execution.actors[*].phases[*].extractors",
+            ],
+            NodeKind::Action => &[
+                "attack.execution.phases[*].on_enter",
+                "attack.execution.phases[*].on_timeout",
+                "attack.execution.actors[*].phases[*].on_enter",
+                "attack.execution.actors[*].phases[*].on_timeout",
+            ],
+            NodeKind::State => &[
+                "attack.execution.state",
+                "attack.execution.phases[*].state",
+                "attack.execution.actors[*].phases[*].state",
+            ],
+            NodeKind::Indicator => &["attack.indicators"],
+            NodeKind::Correlation => &["attack.correlation"],
+        }
+    }
+}
+
+/// Whether a change at `path` can affect a node matching `pattern`: one of
+/// them is an ancestor of, or the same node as, the other.
+fn overlaps(pattern: &str, path: &str) -> bool {
+    let pattern = segments(pattern);
+    let path = segments(path);
+    pattern.iter().zip(&path).all(|(p, c)| {
+        p == c || (p.starts_with('[') && c.starts_with('[') && (*p == "[*]" || *c == "[*]"))
+    })
+}
+
+/// `attack.phases[0].name` as `attack`, `phases`, `[0]`, `name`.
+fn segments(path: &str) -> Vec<&str> {
+    let mut out = Vec::new();
+    for part in path.split('.').filter(|p| !p.is_empty()) {
+        let mut rest = part;
+        while let Some(open) = rest[1..].find('[').map(|i| i + 1) {
+            out.push(&rest[..open]);
+            rest = &rest[open..];
+        }
+        out.push(rest);
+    }
+    out
+}
+
+/// Errors and warnings a rule reports.
+#[derive(Clone, Debug, Default)]
+pub struct RuleOutput {
+    /// Conformance errors.
+    pub errors: Vec<ValidationError>,
+    /// Warnings.
+    pub warnings: Vec<Diagnostic>,
+}
+
+type Check = Arc<dyn Fn(&ResolvedContext<'_>, &mut RuleOutput) + Send + Sync>;
+
+/// A validation rule.
+#[derive(Clone)]
+pub struct Rule {
+    /// Rule identifier (e.g., `"V-024"`).
+    pub id: &'static str,
+    /// Severity of the rule's primary findings. Some error rules also
+    /// report related warnings.
+    pub severity: DiagnosticSeverity,
+    /// Rules that must run first and report no errors for this rule to run.
+    pub prerequisites: &'static [&'static str],
+    /// Node kinds the rule inspects.
+    pub targets: &'static [NodeKind],
+    /// Path patterns the rule reads outside its node kinds (see
+    /// [`NodeKind::paths`]).
+    pub paths: &'static [&'static str],
+    check: Check,
+}
+
+impl std::fmt::Debug for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Rule")
+            .field("id", &self.id)
+            .field("severity", &self.severity)
+            .field("prerequisites", &self.prerequisites)
+            .field("targets", &self.targets)
+            .field("paths", &self.paths)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Rule {
+    /// A rule without prerequisites that runs `check`.
+    pub fn new(
+        id: &'static str,
+        severity: DiagnosticSeverity,
+        targets: &'static [NodeKind],
+        check: impl Fn(&ResolvedContext<'_>, &mut RuleOutput) + Send + Sync + 'static,
+    ) -> Self {
+        Rule {
+            id,
+            severity,
+            prerequisites: &[],
+            targets,
+            paths: &[],
+            check: Arc::new(check),
+        }
+    }
+
+    /// The rule, run only after `prerequisites` have reported no errors.
+    pub fn requires(mut self, prerequisites: &'static [&'static str]) -> Self {
+        self.prerequisites = prerequisites;
+        self
+    }
+
+    /// The rule, also rerun for changes at `paths` outside its node kinds.
+    pub fn watching(mut self, paths: &'static [&'static str]) -> Self {
+        self.paths = paths;
+        self
+    }
+
+    /// Whether a change at the document path `path` (e.g.
+    /// `attack.indicators[2].pattern`) can change the rule's findings.
+    /// `""` is the whole document.
+    pub fn is_affected_by(&self, path: &str) -> bool {
+        self.targets
+            .iter()
+            .flat_map(|kind| kind.paths())
+            .chain(self.paths)
+            .any(|pattern| overlaps(pattern, path))
+    }
+
+    /// Run the rule alone against `doc`.
+    pub fn check(&self, doc: &Document) -> RuleOutput {
+        self.check_resolved(&ResolvedContext::new(doc))
+    }
+
+    fn check_resolved(&self, cx: &ResolvedContext<'_>) -> RuleOutput {
+        let mut out = RuleOutput::default();
+        (self.check)(cx, &mut out);
+        out
+    }
+}
+
+/// Which rules [`RuleEngine::run`] reports, and how it runs them.
+#[derive(Clone, Debug, Default)]
+pub struct RunOptions {
+    /// Report only these rule IDs; all rules when `None`.
+    pub rules: Option<Vec<String>>,
+    /// Report only rules inspecting at least one of these node kinds; all
+    /// rules when `None`.
+    pub targets: Option<Vec<NodeKind>>,
+    /// Report only rules affected by a change at one of these document
+    /// paths (see [`Rule::is_affected_by`]); all rules when `None`.
+    pub changed_paths: Option<Vec<String>>,
+    /// Rule IDs never to report.
+    pub skip: Vec<String>,
+    /// Stop after the first reported rule that finds errors.
+    pub fail_fast: bool,
+    /// Run independent rules on multiple threads.
+    pub parallel: bool,
+}
+
+impl RunOptions {
+    fn selects(&self, rule: &Rule) -> bool {
+        self.rules
+            .as_ref()
+            .is_none_or(|ids| ids.iter().any(|id| id == rule.id))
+            && self
+                .targets
+                .as_ref()
+                .is_none_or(|kinds| rule.targets.iter().any(|t| kinds.contains(t)))
+            && self
+                .changed_paths
+                .as_ref()
+                .is_none_or(|paths| paths.iter().any(|p| rule.is_affected_by(p)))
+            && !self.skip.iter().any(|id| id == rule.id)
+    }
+}
+
+/// An ordered set of validation rules.
+#[derive(Clone, Debug, Default)]
+pub struct RuleEngine {
+    rules: Vec<Rule>,
+}
+
+pub(super) static BUILTIN: LazyLock<RuleEngine> = LazyLock::new(builtin_rules);
+
+impl RuleEngine {
+    /// An engine without rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An engine with the rules of [`validate`](super::validate), in the
+    /// order they report.
+    pub fn builtin() -> Self {
+        BUILTIN.clone()
+    }
+
+    /// The registered rules, in registration order.
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Add a rule. Its findings are reported after those of every rule
+    /// registered before it that it does not depend on. Prerequisites that
+    /// are never registered are treated as passed.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RuleEngineError`] if a rule with the same ID is
+    /// registered, or if the rule's prerequisites depend on it.
+    pub fn register(&mut self, rule: Rule) -> Result<(), RuleEngineError> {
+        if self.rules.iter().any(|r| r.id == rule.id) {
+            return Err(RuleEngineError {
+                kind: RuleEngineErrorKind::DuplicateRule,
+                rule: rule.id.to_string(),
+                message: "a rule with this ID is already registered".to_string(),
+            });
+        }
+        let mut pending: Vec<&str> = rule.prerequisites.to_vec();
+        let mut seen = HashSet::new();
+        while let Some(id) = pending.pop() {
+            if id == rule.id {
+                return Err(RuleEngineError {
+                    kind: RuleEngineErrorKind::PrerequisiteCycle,
+                    rule: rule.id.to_string(),
+                    message: "the rule's prerequisites depend on it".to_string(),
+                });
+            }
+            if seen.insert(id)
+                && let Some(prerequisite) = self.rules.iter().find(|r| r.id == id)
+            {
+                pending.extend(prerequisite.prerequisites);
+            }
+        }
+        self.rules.push(rule);
+        Ok(())
+    }
+
+    /// Validate `doc` with the rules `options` selects.
+    ///
+    /// Prerequisites of selected rules run even when not selected, but only
+    /// selected rules are reported. A rule whose prerequisite reported errors
+    /// (or was itself skipped) is skipped.
+    pub fn run(&self, doc: &Document, options: &RunOptions) -> ValidationResult {
+        let cx = ResolvedContext::new(doc);
+        let order = self.order();
+        let index_of = |id: &str| self.rules.iter().position(|r| r.id == id);
+        let prerequisites: Vec<Vec<usize>> = self
+            .rules
+            .iter()
+            .map(|r| {
+                r.prerequisites
+                    .iter()
+                    .filter_map(|id| index_of(id))
+                    .collect()
+            })
+            .collect();
+
+        let selected: Vec<bool> = self.rules.iter().map(|r| options.selects(r)).collect();
+        let mut needed = selected.clone();
+        for &i in order.iter().rev() {
+            if needed[i] {
+                for &p in &prerequisites[i] {
+                    needed[p] = true;
+                }
+            }
+        }
+
+        // Rules grouped into waves that only depend on earlier waves; run
+        // sequentially, every rule is its own wave.
+        let mut waves: Vec<Vec<usize>> = Vec::new();
+        if options.parallel {
+            let mut level = vec![0; self.rules.len()];
+            for &i in &order {
+                level[i] = prerequisites[i]
+                    .iter()
+                    .map(|&p| level[p] + 1)
+                    .max()
+                    .unwrap_or(0);
+                if waves.len() <= level[i] {
+                    waves.resize(level[i] + 1, Vec::new());
+                }
+                waves[level[i]].push(i);
+            }
+        } else {
+            waves = order.iter().map(|&i| vec![i]).collect();
+        }
+
+        let mut outputs: Vec<Option<RuleOutput>> = vec![None; self.rules.len()];
+        let mut failed = vec![false; self.rules.len()];
+        for wave in waves {
+            let mut runnable = Vec::new();
+            for i in wave.into_iter().filter(|&i| needed[i]) {
+                if prerequisites[i].iter().any(|&p| failed[p]) {
+                    failed[i] = true;
+                } else {
+                    runnable.push(i);
+                }
+            }
+            for (i, out) in self.run_wave(&cx, &runnable, options.parallel) {
+                failed[i] = !out.errors.is_empty();
+                outputs[i] = Some(out);
+            }
+            if options.fail_fast && runnable.iter().any(|&i| selected[i] && failed[i]) {
+                break;
+            }
+        }
+
+        let mut result = ValidationResult {
+            errors: Vec::new(),
+            warnings: Vec::new(),
+        };
+        for i in order.into_iter().filter(|&i| selected[i]) {
+            let Some(out) = outputs[i].take() else {
+                continue;
+            };
+            let stop = options.fail_fast && !out.errors.is_empty();
+            result.errors.extend(out.errors);
+            result.warnings.extend(out.warnings);
+            if stop {
+                break;
+            }
+        }
+        result
+    }
+
+    /// Registration order, with each rule moved after its prerequisites.
+    fn order(&self) -> Vec<usize> {
+        let mut order = Vec::with_capacity(self.rules.len());
+        let mut placed = vec![false; self.rules.len()];
+        while order.len() < self.rules.len() {
+            for (i, rule) in self.rules.iter().enumerate() {
+                let ready = !placed[i]
+                    && rule.prerequisites.iter().all(|id| {
+                        self.rules
+                            .iter()
+                            .position(|r| r.id == *id)
+                            .is_none_or(|p| placed[p])
+                    });
+                if ready {
+                    placed[i] = true;
+                    order.push(i);
+                    break;
+                }
+            }
+        }
+        order
+    }
+
+    fn run_wave(
+        &self,
+        cx: &ResolvedContext<'_>,
+        wave: &[usize],
+        parallel: bool,
+    ) -> Vec<(usize, RuleOutput)> {
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        if !parallel || wave.len() < 2 || threads < 2 {
+            return wave
+                .iter()
+                .map(|&i| (i, self.rules[i].check_resolved(cx)))
+                .collect();
+        }
+        let chunk = wave.len().div_ceil(threads);
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = wave
+                .chunks(chunk)
+                .map(|rules| {
+                    scope.spawn(move || {
+                        rules
+                            .iter()
+                            .map(|&i| (i, self.rules[i].check_resolved(cx)))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|h| h.join().expect("validation rule panicked"))
+                .collect()
+        })
+    }
+}
+
+fn error_rule(
+    id: &'static str,
+    targets: &'static [NodeKind],
+    check: fn(&ResolvedContext<'_>, &mut Vec<ValidationError>),
+) -> Rule {
+    Rule::new(id, DiagnosticSeverity::Error, targets, move |cx, out| {
+        check(cx, &mut out.errors)
+    })
+}
+
+fn error_rule_with_warnings(
+    id: &'static str,
+    targets: &'static [NodeKind],
+    check: fn(&ResolvedContext<'_>, &mut Vec<ValidationError>, &mut Vec<Diagnostic>),
+) -> Rule {
+    Rule::new(id, DiagnosticSeverity::Error, targets, move |cx, out| {
+        check(cx, &mut out.errors, &mut out.warnings)
+    })
+}
+
+fn warning_rule(
+    id: &'static str,
+    targets: &'static [NodeKind],
+    check: fn(&ResolvedContext<'_>, &mut Vec<Diagnostic>),
+) -> Rule {
+    Rule::new(id, DiagnosticSeverity::Warning, targets, move |cx, out| {
+        check(cx, &mut out.warnings)
+    })
+}
+
+fn builtin_rules() -> RuleEngine {
+    use NodeKind::*;
+    // V-003 (attack present) and V-004 (required fields) are enforced by
+    // serde deserialization during parse — no rule needed here.
+    let rules = [
+        warning_rule("W-001", &[Document], w001_oatf_key_ordering),
+        error_rule("V-001", &[Document], v001_oatf_version),
+        error_rule("V-005", &[Attack, Indicator], v005_enum_values).watching(MODES),
+        error_rule("V-006", &[Indicator], v006_indicators_non_empty),
+        error_rule("V-007", &[Execution, Phase], v007_phases_non_empty),
+        error_rule("V-008", &[Phase, Trigger], v008_terminal_phase),
+        error_rule("V-009", &[Phase, State], v009_first_phase_state),
+        error_rule("V-010", &[Indicator], v010_unique_indicator_ids),
+        error_rule("V-011", &[Phase], v011_unique_phase_names),
+        Rule::new(
+            "V-012",
+            DiagnosticSeverity::Error,
+            &[Indicator],
+            |cx, out| {
+                v012_exactly_one_detection_key(cx, &mut out.errors);
+                v012_pattern_form_ambiguity(cx, &mut out.errors);
+            },
+        ),
+        error_rule("V-013", &[Indicator, Trigger], v013_regex_valid),
+        error_rule("V-014", &[Indicator], v014_cel_valid),
+        error_rule("V-015", &[Extractor], v015_jsonpath_valid),
+        error_rule("V-016", &[State, Action], v016_template_syntax),
+        error_rule("V-017", &[Attack], v017_severity_confidence),
+        error_rule_with_warnings("V-018", &[Indicator], v018_surface_protocol).watching(MODES),
+        error_rule("V-019", &[Trigger], v019_count_match_require_event),
+        error_rule("V-021", &[Indicator], v021_target_path_syntax),
+        error_rule("V-022", &[Indicator], v022_semantic_threshold),
+        error_rule("V-023", &[Attack], v023_attack_id_format),
+        error_rule("V-024", &[Indicator], v024_indicator_id_format)
+            .requires(&["V-023"])
+            .watching(&["attack.id"]),
+        error_rule("V-025", &[Indicator], v025_indicator_confidence),
+        error_rule("V-026", &[Indicator], v026_expression_variables_paths),
+        error_rule("V-027", &[Trigger], v027_match_predicate_paths),
+        error_rule(
+            "V-028",
+            &[Execution, Phase, Indicator],
+            v028_conditional_requiredness,
+        )
+        .watching(MODES),
+        error_rule_with_warnings("V-029", &[Trigger], v029_event_mode_validity).watching(MODES),
+        error_rule("V-030", &[Execution], v030_mutual_exclusion).watching(&[
+            "attack.execution.state",
+            "attack.execution.phases",
+            "attack.execution.actors",
+        ]),
+        error_rule("V-031", &[Execution], v031_multi_actor_constraints)
+            .watching(&["attack.execution.actors[*].phases[*].name"]),
+        error_rule("V-032", &[State, Action], v032_cross_actor_refs)
+            .watching(&["attack.execution.actors[*].name"]),
+        error_rule("V-033", &[State], v033_content_synthesize_exclusivity).watching(MODES),
+        error_rule("V-034", &[State], v034_catch_all_constraints),
+        error_rule("V-035", &[State], v035_synthesize_prompt),
+        error_rule_with_warnings(
+            "V-036",
+            &[Execution, Phase, Indicator],
+            v036_mode_protocol_pattern,
+        )
+        .watching(MODES),
+        error_rule("V-037", &[Attack], v037_version_positive),
+        error_rule("V-038", &[Trigger], v038_trigger_after_duration)
+            .watching(&["attack.grace_period"]),
+        error_rule("V-039", &[Extractor], v039_extractor_name_pattern),
+        error_rule("V-040", &[Extractor], v040_extractors_non_empty),
+        error_rule("V-041", &[Indicator], v041_expression_variable_keys),
+        error_rule("V-042", &[Trigger], v042_trigger_event_or_after),
+        error_rule("V-043", &[Action], v043_binding_specific_action_keys),
+        error_rule("V-044", &[Extractor], v044_regex_extractor_capture_group),
+        error_rule("V-045", &[Action], v045_on_enter_non_empty),
+        error_rule("V-046", &[Execution, Phase], v046_mode_version_known),
+        error_rule("V-047", &[Phase], v047_pacing_durations),
+        error_rule("V-048", &[Attack], v048_safety_limits),
+        error_rule("V-049", &[Attack], v049_lifecycle_references),
+        error_rule("V-050", &[Indicator], v050_false_positive_entries),
+        error_rule("V-051", &[Indicator, Correlation], v051_indicator_stages),
+        error_rule("V-053", &[Indicator], v053_frequency),
+        error_rule("V-054", &[Indicator, Correlation], v054_captures),
+        error_rule("V-055", &[Indicator, Trigger], v055_origin_predicates),
+        error_rule("V-056", &[Trigger, Extractor], v056_trigger_match_refs),
+        error_rule("V-057", &[Trigger, Action], v057_on_timeout),
+        error_rule("V-058", &[Attack, State], v058_attachments),
+        error_rule("V-060", &[Execution], v060_actor_endpoints),
+        error_rule("V-061", &[Phase, Extractor], v061_phase_requires),
+        error_rule("V-062", &[Attack], v062_license_expression),
+        warning_rule("W-004", &[State, Action], w004_undeclared_extractor_refs).watching(&[
+            "attack.execution.actors[*].name",
+            "attack.execution.phases[*].extractors",
+            "attack.execution.actors[*].phases[*].extractors",
+        ]),
+        warning_rule(
+            "W-005",
+            &[Indicator, Execution],
+            w005_indicator_protocol_mismatch,
+        )
+        .watching(MODES),
+        warning_rule("W-006", &[Attack], w006_superseded_not_deprecated),
+        warning_rule(
+            "W-007",
+            &[Indicator, State],
+            w007_indicator_surface_never_served,
+        )
+        .watching(MODES),
+        warning_rule("W-008", &[Attack], w008_malformed_cve_ids),
+        warning_rule(
+            "W-010",
+            &[Attack, Indicator],
+            w010_category_surface_mismatch,
+        )
+        .watching(MODES),
+        warning_rule("W-011", &[Trigger, Action], w011_likely_misspellings).watching(MODES),
+    ];
+    let mut engine = RuleEngine::new();
+    for rule in rules {
+        engine
+            .register(rule)
+            .expect("built-in rules are consistent");
+    }
+    engine
+}
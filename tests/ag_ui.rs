@@ -0,0 +1,231 @@
+#![cfg(feature = "evaluate")]
+
+use oatf::convert::predicate_to_cel;
+use oatf::enums::IndicatorResult;
+use oatf::evaluate::evaluate_indicator;
+use oatf::primitives::{evaluate_predicate, resolve_event_qualifier};
+use oatf::types::MatchPredicate;
+use serde_json::{Value, json};
+
+fn predicate(yaml: &str) -> MatchPredicate {
+    serde_saphyr::from_str(yaml).unwrap()
+}
+
+const ESCALATION: &str = r#"
+delta:
+  json_patch:
+    op: replace
+    path: /users/*/role
+    value: admin
+"#;
+
+const STATE_DELTA_DOC: &str = r#"
+oatf: "0.1"
+attack:
+  execution:
+    mode: ag_ui_client
+    state: {}
+  indicators:
+    - surface: agent_event
+      pattern:
+        target: data.delta
+        condition:
+          json_patch:
+            path: /permissions/**
+            value:
+              any_of: [write, admin]
+"#;
+
+// ─── Event taxonomy ─────────────────────────────────────────────────────────
+
+#[test]
+fn ag_ui_events_are_valid_for_the_client_mode() {
+    for event in [
+        "text_message_chunk",
+        "tool_call_args",
+        "tool_call_result",
+        "tool_call_chunk",
+        "raw",
+        "state_delta",
+    ] {
+        let input = format!(
+            r#"
+oatf: "0.1"
+attack:
+  execution:
+    mode: ag_ui_client
+    phases:
+      - name: phase-1
+        state: {{}}
+        trigger:
+          event: {}
+      - name: phase-2
+  indicators:
+    - surface: agent_event
+      pattern:
+        contains: "test"
+"#,
+            event
+        );
+        let doc = oatf::parse(&input).unwrap();
+        let errors = oatf::validate(&doc).errors;
+        assert!(
+            errors.iter().all(|e| e.rule != "V-029"),
+            "{}: {:?}",
+            event,
+            errors
+        );
+    }
+}
+
+#[test]
+fn ag_ui_qualifiers_resolve() {
+    let cases = [
+        ("step_started", json!({"stepName": "plan"}), "plan"),
+        ("step_finished", json!({"stepName": "plan"}), "plan"),
+        (
+            "run_error",
+            json!({"message": "boom", "code": "E42"}),
+            "E42",
+        ),
+        (
+            "tool_call_chunk",
+            json!({"toolCallId": "c1", "toolCallName": "shell", "delta": "{"}),
+            "shell",
+        ),
+    ];
+    for (event, content, expected) in cases {
+        assert_eq!(
+            resolve_event_qualifier("ag_ui", event, &content).as_deref(),
+            Some(expected),
+            "{}",
+            event
+        );
+    }
+    assert_eq!(
+        resolve_event_qualifier("ag_ui", "text_message_chunk", &json!({"delta": "hi"})),
+        None
+    );
+}
+
+// ─── JSON Patch matching ────────────────────────────────────────────────────
+
+#[test]
+fn json_patch_matches_any_operation() {
+    let pred = predicate(ESCALATION);
+    let patch = |ops: Value| json!({ "delta": ops });
+
+    assert!(evaluate_predicate(
+        &pred,
+        &patch(json!([
+            {"op": "add", "path": "/users/1/name", "value": "eve"},
+            {"op": "replace", "path": "/users/1/role", "value": "admin"},
+        ]))
+    ));
+    assert!(evaluate_predicate(
+        &pred,
+        &patch(json!({"op": "replace", "path": "/users/7/role", "value": "admin"}))
+    ));
+    for ops in [
+        json!([{"op": "add", "path": "/users/1/role", "value": "admin"}]),
+        json!([{"op": "replace", "path": "/users/1/role", "value": "viewer"}]),
+        json!([{"op": "replace", "path": "/users/1/profile/role", "value": "admin"}]),
+        json!([{"op": "replace", "path": "/users/role", "value": "admin"}]),
+        json!([]),
+        json!("replace /users/1/role"),
+    ] {
+        assert!(!evaluate_predicate(&pred, &patch(ops.clone())), "{}", ops);
+    }
+}
+
+#[test]
+fn json_patch_path_remainder_and_value_conditions() {
+    let pred = predicate(
+        r#"
+delta:
+  json_patch:
+    path: /permissions/**
+    value:
+      exists: false
+"#,
+    );
+    for path in ["/permissions", "/permissions/admin", "/permissions/a/b"] {
+        let ops = json!({"delta": [{"op": "remove", "path": path}]});
+        assert!(evaluate_predicate(&pred, &ops), "{}", path);
+    }
+    let ops = json!({"delta": [{"op": "add", "path": "/permissions/admin", "value": true}]});
+    assert!(!evaluate_predicate(&pred, &ops));
+    let ops = json!({"delta": [{"op": "remove", "path": "/permissionsx"}]});
+    assert!(!evaluate_predicate(&pred, &ops));
+
+    let pred = predicate("delta: {json_patch: {value: {contains: ignore}}}\n");
+    let ops =
+        json!({"delta": [{"op": "add", "path": "/notes/-", "value": "please ignore prior rules"}]});
+    assert!(evaluate_predicate(&pred, &ops));
+}
+
+#[test]
+fn state_delta_indicator_detects_permission_grant() {
+    let doc = oatf::load(STATE_DELTA_DOC).unwrap().document;
+    let indicator = &doc.attack.indicators.as_ref().unwrap()[0];
+    let event = |ops: Value| json!({"type": "STATE_DELTA", "data": {"delta": ops}});
+
+    let granted = event(json!([
+        {"op": "add", "path": "/permissions/files", "value": "write"},
+    ]));
+    assert_eq!(
+        evaluate_indicator(indicator, &granted, None, None).result,
+        IndicatorResult::Matched
+    );
+    let benign = event(json!([
+        {"op": "add", "path": "/permissions/files", "value": "read"},
+        {"op": "replace", "path": "/theme", "value": "admin"},
+    ]));
+    assert_eq!(
+        evaluate_indicator(indicator, &benign, None, None).result,
+        IndicatorResult::NotMatched
+    );
+
+    let examples = oatf::authoring::examples_for(indicator);
+    assert_eq!(
+        examples.matching,
+        Some(json!({"data": {"delta": [{"op": "add", "path": "/permissions", "value": "write"}]}}))
+    );
+}
+
+#[test]
+fn json_patch_renders_as_cel() {
+    assert_eq!(
+        predicate_to_cel(&predicate(ESCALATION)),
+        r#"message.delta.exists(op, op.op == "replace" && op.path.matches("^/users/[^/]*/role$") && op.value == "admin")"#
+    );
+}
+
+#[cfg(feature = "cel-eval")]
+#[test]
+fn json_patch_cel_agrees_with_predicate() {
+    use oatf::evaluate::{CelEvaluator, default_cel_evaluator};
+
+    let evaluator = default_cel_evaluator();
+    for yaml in [
+        ESCALATION,
+        "delta: {json_patch: {path: /permissions/**, value: {any_of: [write, admin]}}}\n",
+    ] {
+        let pred = predicate(yaml);
+        let cel = predicate_to_cel(&pred);
+        for ops in [
+            json!([{"op": "replace", "path": "/users/1/role", "value": "admin"}]),
+            json!([{"op": "replace", "path": "/users/1/role", "value": "viewer"}]),
+            json!([{"op": "add", "path": "/permissions", "value": "write"}]),
+            json!([{"op": "add", "path": "/permissions/x/y", "value": "admin"}]),
+            json!([{"op": "add", "path": "/permissionsx", "value": "admin"}]),
+        ] {
+            let message = json!({ "delta": ops });
+            let expected = evaluate_predicate(&pred, &message);
+            let actual = evaluator
+                .evaluate(&cel, &json!({ "message": message }))
+                .unwrap();
+            assert_eq!(actual, Value::Bool(expected), "{} on {}", cel, message);
+        }
+    }
+}
@@ -0,0 +1,201 @@
+#![cfg(feature = "evaluate")]
+
+use oatf::authoring::{
+    FieldType, RequirementKind, ScaffoldKind, completion_model, examples_for, scaffold,
+};
+use oatf::enums::IndicatorResult;
+use serde_json::json;
+
+#[test]
+fn model_references_only_known_objects() {
+    let model = completion_model();
+    assert!(model.object(model.root).is_some());
+
+    fn referenced(ty: &FieldType, out: &mut Vec<&'static str>) {
+        match ty {
+            FieldType::Object { name } => out.push(name),
+            FieldType::List { items } => referenced(items, out),
+            FieldType::Map { values } => referenced(values, out),
+            FieldType::OneOf { options } => options.iter().for_each(|o| referenced(o, out)),
+            _ => {}
+        }
+    }
+    let mut names = Vec::new();
+    for object in &model.objects {
+        for field in &object.fields {
+            referenced(&field.ty, &mut names);
+        }
+    }
+    for name in names {
+        assert!(model.object(name).is_some(), "unknown object {name}");
+    }
+    for requirement in &model.requirements {
+        let object = model.object(requirement.object).unwrap();
+        for field in &requirement.fields {
+            assert!(object.field(field).is_some(), "{}.{field}", object.name);
+        }
+    }
+}
+
+#[test]
+fn enum_values_come_from_the_types() {
+    let model = completion_model();
+    let attack = model.object("Attack").unwrap();
+    assert_eq!(
+        attack.field("status").unwrap().ty,
+        FieldType::Enum {
+            values: vec!["draft", "experimental", "stable", "deprecated"]
+        }
+    );
+    let extractor = model.object("Extractor").unwrap();
+    assert_eq!(
+        extractor.field("type").unwrap().ty,
+        FieldType::Enum {
+            values: vec!["json_path", "regex"]
+        }
+    );
+    assert!(model.object("Trigger").unwrap().field("match").is_some());
+    assert!(model.object("Document").unwrap().field("$schema").is_some());
+
+    let surface = &model
+        .object("Indicator")
+        .unwrap()
+        .field("surface")
+        .unwrap()
+        .ty;
+    let FieldType::OpenEnum { values } = surface else {
+        panic!("surface should be an open enum");
+    };
+    assert!(values.contains(&"tool_description"));
+}
+
+#[test]
+fn conditional_requirements_are_exported() {
+    let model = completion_model();
+    let v030 = model
+        .requirements
+        .iter()
+        .find(|r| r.rule == "V-030" && r.kind == RequirementKind::ExactlyOne)
+        .unwrap();
+    assert_eq!(v030.object, "Execution");
+    assert_eq!(v030.fields, ["state", "phases", "actors"]);
+    assert!(
+        model
+            .requirements
+            .iter()
+            .any(|r| r.rule == "V-028" && r.object == "Indicator" && r.fields == ["protocol"])
+    );
+
+    let serialized = serde_json::to_value(&model).unwrap();
+    assert_eq!(serialized["requirements"][0]["kind"], json!("exactly_one"));
+    assert_eq!(
+        serialized["objects"][0]["fields"][0]["type"],
+        json!({"kind": "enum", "values": ["0.1"]})
+    );
+}
+
+fn indicator(yaml: &str) -> oatf::Indicator {
+    oatf::parse_indicator_set(&format!("oatf: \"0.1\"\nindicators:\n{}", yaml))
+        .unwrap()
+        .indicators
+        .remove(0)
+}
+
+fn normalized(yaml: &str) -> oatf::Indicator {
+    oatf::load_indicator_set(&format!("oatf: \"0.1\"\nindicators:\n{}", yaml))
+        .unwrap()
+        .indicator_set
+        .indicators
+        .remove(0)
+}
+
+#[test]
+fn examples_place_values_at_the_surface_default_target() {
+    let examples = examples_for(&indicator(
+        "  - surface: tool_description\n    pattern:\n      contains: ignore previous\n",
+    ));
+    assert_eq!(
+        examples.matching,
+        Some(json!({"tools": [{"description": "ignore previous"}]}))
+    );
+    assert_eq!(
+        examples.non_matching,
+        Some(json!({"tools": [{"description": "benign example"}]}))
+    );
+}
+
+#[test]
+fn examples_satisfy_regex_numeric_and_equality_conditions() {
+    let regex = "  - surface: tool_description\n    pattern:\n      regex: '(?i)(ignore|disregard)\\s+(all )?previous\\b'\n";
+    let numeric = "  - surface: tool_arguments\n    pattern:\n      target: arguments.amount\n      condition:\n        gt: 1000\n        lte: 5000\n";
+    let equality = "  - surface: tool_arguments\n    pattern:\n      target: arguments.path\n      condition: /etc/passwd\n";
+    for yaml in [regex, numeric, equality] {
+        let examples = examples_for(&indicator(yaml));
+        let matching = examples.matching.expect("matching example");
+        let non_matching = examples.non_matching.expect("non-matching example");
+        let ind = normalized(yaml);
+        let result = |m| oatf::evaluate::evaluate_indicator(&ind, m, None, None).result;
+        assert_eq!(result(&matching), IndicatorResult::Matched, "{}", matching);
+        assert_eq!(result(&non_matching), IndicatorResult::NotMatched);
+    }
+    assert_eq!(
+        examples_for(&indicator(regex)).matching,
+        Some(json!({"tools": [{"description": "ignore previous"}]}))
+    );
+    let examples = examples_for(&indicator(numeric));
+    assert_eq!(
+        examples.matching,
+        Some(json!({"arguments": {"amount": 3000}}))
+    );
+    assert_eq!(
+        examples.non_matching,
+        Some(json!({"arguments": {"amount": 999}}))
+    );
+}
+
+#[test]
+fn examples_are_omitted_when_no_message_can_be_synthesized() {
+    let expression = examples_for(&indicator(
+        "  - surface: tool_description\n    expression:\n      cel: \"message.tools.size() > 100\"\n",
+    ));
+    assert_eq!(expression.matching, None);
+    assert_eq!(expression.non_matching, None);
+
+    let decoded = examples_for(&indicator(
+        "  - surface: tool_arguments\n    pattern:\n      target: arguments.query!url\n      contains: \"' OR 1=1\"\n",
+    ));
+    assert_eq!(decoded.matching, None);
+    assert_eq!(decoded.non_matching, None);
+}
+
+#[test]
+fn scaffolds_validate_and_detect_their_own_payloads() {
+    for kind in ScaffoldKind::ALL {
+        let doc = scaffold(kind);
+        let result = oatf::validate(&doc);
+        assert!(result.errors.is_empty(), "{:?}: {:?}", kind, result.errors);
+        assert!(
+            result.warnings.is_empty(),
+            "{:?}: {:?}",
+            kind,
+            result.warnings
+        );
+
+        let loaded = oatf::load(&oatf::serialize(&doc).unwrap())
+            .unwrap()
+            .document;
+        let indicator = &loaded.attack.indicators.as_ref().unwrap()[0];
+        let verdict =
+            oatf::evaluate::evaluate_indicator_on_document(indicator, &loaded, None, None);
+        let expected = match kind {
+            // The agent's reply is not part of the document.
+            ScaffoldKind::AgUiPromptInjection => IndicatorResult::NotMatched,
+            _ => IndicatorResult::Matched,
+        };
+        assert_eq!(verdict.result, expected, "{:?}", kind);
+    }
+    assert_eq!(
+        serde_json::to_value(ScaffoldKind::AgUiPromptInjection).unwrap(),
+        "ag_ui_prompt_injection"
+    );
+}
@@ -0,0 +1,154 @@
+#![cfg(all(feature = "validate", feature = "normalize"))]
+
+use oatf::calibrate::{CalibratedField, CalibrationOptions, LabeledOutcome, calibrate, tally};
+use oatf::catalog::Catalog;
+use serde_json::json;
+
+const DOC: &str = r#"
+oatf: "0.1"
+attack:
+  id: OATF-101
+  execution:
+    mode: mcp_server
+    state:
+      tools:
+        - name: calc
+          description: "Adds numbers"
+          inputSchema:
+            type: object
+  indicators:
+    - id: OATF-101-01
+      surface: tool_description
+      confidence: 90
+      pattern:
+        contains: "IMPORTANT"
+    - id: OATF-101-02
+      surface: tool_description
+      semantic:
+        intent: "hidden instructions to the model"
+        threshold: 0.6
+"#;
+
+fn outcomes(id: &str, tp: usize, fp: usize, tn: usize, fn_: usize) -> Vec<LabeledOutcome> {
+    let make = |matched, malicious, n| {
+        std::iter::repeat_n(
+            LabeledOutcome {
+                indicator_id: id.to_string(),
+                matched,
+                malicious,
+            },
+            n,
+        )
+    };
+    make(true, true, tp)
+        .chain(make(true, false, fp))
+        .chain(make(false, false, tn))
+        .chain(make(false, true, fn_))
+        .collect()
+}
+
+fn catalog() -> Catalog {
+    let mut catalog = Catalog::new();
+    catalog.load_str("attacks/oatf-101.yaml", DOC).unwrap();
+    catalog
+}
+
+#[test]
+fn tally_counts_confusion_matrix() {
+    let stats = tally(&outcomes("a", 3, 1, 4, 2));
+    let a = stats["a"];
+    assert_eq!(
+        (
+            a.true_positives,
+            a.false_positives,
+            a.true_negatives,
+            a.false_negatives
+        ),
+        (3, 1, 4, 2)
+    );
+    assert_eq!(a.precision(), Some(0.75));
+    assert_eq!(a.recall(), Some(0.6));
+}
+
+#[test]
+fn calibrate_lowers_overstated_confidence_and_raises_noisy_threshold() {
+    let catalog = catalog();
+    let mut labeled = outcomes("OATF-101-01", 6, 4, 10, 0);
+    labeled.extend(outcomes("OATF-101-02", 10, 10, 0, 0));
+    let patches = calibrate(catalog.entries(), &labeled, &CalibrationOptions::default());
+
+    assert_eq!(patches.len(), 1);
+    assert_eq!(patches[0].source, "attacks/oatf-101.yaml");
+    assert_eq!(patches[0].attack_id.as_deref(), Some("OATF-101"));
+    let changes: Vec<_> = patches[0]
+        .adjustments
+        .iter()
+        .map(|a| (a.path.as_str(), a.field, a.recommended.clone()))
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            (
+                "attack.indicators[0].confidence",
+                CalibratedField::Confidence,
+                json!(58)
+            ),
+            (
+                "attack.indicators[1].confidence",
+                CalibratedField::Confidence,
+                json!(50)
+            ),
+            (
+                "attack.indicators[1].semantic.threshold",
+                CalibratedField::Threshold,
+                json!(0.65)
+            ),
+        ]
+    );
+
+    let mut doc = catalog.entries()[0].document.clone();
+    patches[0].apply(&mut doc);
+    let indicators = doc.attack.indicators.as_ref().unwrap();
+    assert_eq!(indicators[0].confidence, Some(58));
+    assert_eq!(
+        indicators[1].semantic.as_ref().unwrap().threshold,
+        Some(0.65)
+    );
+    assert!(oatf::validate(&doc).errors.is_empty());
+}
+
+#[test]
+fn calibrate_lowers_threshold_for_precise_but_insensitive_indicator() {
+    let mut labeled = outcomes("OATF-101-02", 10, 0, 5, 10);
+    labeled.extend(outcomes("OATF-101-01", 9, 1, 0, 0));
+    let patches = calibrate(
+        catalog().entries(),
+        &labeled,
+        &CalibrationOptions::default(),
+    );
+    let threshold = patches[0]
+        .adjustments
+        .iter()
+        .find(|a| a.field == CalibratedField::Threshold)
+        .unwrap();
+    assert_eq!(threshold.current, Some(json!(0.6)));
+    assert_eq!(threshold.recommended, json!(0.55));
+    // 9 of 10 → (9 + 1) / 12 ≈ 83, more than 5 points below 90
+    assert!(
+        patches[0]
+            .adjustments
+            .iter()
+            .any(|a| a.indicator_id == "OATF-101-01")
+    );
+}
+
+#[test]
+fn calibrate_needs_enough_samples_and_a_real_difference() {
+    let catalog = catalog();
+    let few = outcomes("OATF-101-01", 2, 2, 0, 0);
+    assert!(calibrate(catalog.entries(), &few, &CalibrationOptions::default()).is_empty());
+
+    // 18 of 20 → (18 + 1) / 22 ≈ 86, within 5 points of 90
+    let close = outcomes("OATF-101-01", 18, 2, 0, 0);
+    assert!(calibrate(catalog.entries(), &close, &CalibrationOptions::default()).is_empty());
+}
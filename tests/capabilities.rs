@@ -0,0 +1,273 @@
+#![cfg(feature = "evaluate")]
+
+use oatf::EvaluationError;
+use oatf::enums::{EvaluatorKind, IndicatorResult, SemanticIntentClass};
+use oatf::evaluate::capabilities::cel_functions;
+use oatf::evaluate::stream::StreamEvaluator;
+use oatf::evaluate::{
+    CapabilitySet, CelEvaluator, CelSubsetEvaluator, SemanticEvaluator, evaluate_indicator,
+    unsupported_indicators,
+};
+use oatf::types::{ProtocolEvent, SemanticExamples, SkipReason};
+use serde_json::{Value, json};
+use std::time::Duration;
+
+const DOC: &str = r#"
+oatf: "0.1"
+attack:
+  execution:
+    mode: mcp_server
+    state:
+      tools: []
+  indicators:
+    - surface: tool_arguments
+      pattern:
+        target: arguments.path
+        contains: passwd
+    - surface: tool_arguments
+      expression:
+        cel: 'message.arguments.path.startsWith("/etc/")'
+    - surface: tool_arguments
+      expression:
+        cel: 'message.arguments.path.matches("^/etc/") && size(message.arguments) > 0'
+    - surface: tool_arguments
+      x-language: en-GB
+      semantic:
+        intent: read credentials
+    - surface: tool_arguments
+      x-language: fr
+      semantic:
+        intent: lire les identifiants
+"#;
+
+/// Evaluates every expression to `true`, within the given capabilities.
+struct Limited(CapabilitySet);
+
+impl CelEvaluator for Limited {
+    fn evaluate(&self, _expression: &str, _context: &Value) -> Result<Value, EvaluationError> {
+        Ok(Value::Bool(true))
+    }
+
+    fn capabilities(&self) -> &CapabilitySet {
+        &self.0
+    }
+}
+
+impl SemanticEvaluator for Limited {
+    fn evaluate(
+        &self,
+        _text: &str,
+        _intent: &str,
+        _intent_class: Option<&SemanticIntentClass>,
+        _threshold: Option<f64>,
+        _examples: Option<&SemanticExamples>,
+    ) -> Result<f64, EvaluationError> {
+        Ok(1.0)
+    }
+
+    fn capabilities(&self) -> &CapabilitySet {
+        &self.0
+    }
+}
+
+fn english() -> Limited {
+    Limited(CapabilitySet {
+        languages: Some(["en".to_string()].into()),
+        ..CapabilitySet::default()
+    })
+}
+
+fn doc() -> oatf::Document {
+    oatf::load(DOC).unwrap().document
+}
+
+fn message() -> Value {
+    json!({"name": "read_file", "arguments": {"path": "/etc/passwd"}})
+}
+
+// ─── Requirements ───────────────────────────────────────────────────────────
+
+#[test]
+fn cel_functions_lists_calls_outside_strings() {
+    let functions = cel_functions(
+        r#"size(message.items) > 1u && message.items.exists(i, i.name.matches("x(y)")) && 'f(' != "g()""#,
+    );
+    assert_eq!(
+        functions.into_iter().collect::<Vec<_>>(),
+        ["exists", "matches", "size"]
+    );
+}
+
+#[test]
+fn language_tags_cover_their_subtags() {
+    let caps = english().0;
+    assert!(caps.supports_language("en"));
+    assert!(caps.supports_language("EN-gb"));
+    assert!(!caps.supports_language("eng"));
+    assert!(!caps.supports_language("fr"));
+    assert!(CapabilitySet::UNRESTRICTED.supports_language("fr"));
+}
+
+// ─── Evaluation ─────────────────────────────────────────────────────────────
+
+#[test]
+fn unmet_requirements_skip_with_a_reason() {
+    let doc = doc();
+    let indicators = doc.attack.indicators.as_ref().unwrap();
+    let subset = CelSubsetEvaluator;
+    let semantic = english();
+
+    let verdicts: Vec<_> = indicators
+        .iter()
+        .map(|i| evaluate_indicator(i, &message(), Some(&subset), Some(&semantic)))
+        .collect();
+    let results: Vec<_> = verdicts.iter().map(|v| v.result.clone()).collect();
+    assert_eq!(
+        results,
+        [
+            IndicatorResult::Matched,
+            IndicatorResult::Matched,
+            IndicatorResult::Skipped,
+            IndicatorResult::Matched,
+            IndicatorResult::Skipped,
+        ]
+    );
+    assert_eq!(
+        verdicts[2].skip_reason,
+        Some(SkipReason::UnsupportedFunctions {
+            functions: vec!["matches".to_string()]
+        })
+    );
+    assert_eq!(
+        verdicts[2].evidence.as_deref(),
+        Some("CEL evaluator does not support: matches")
+    );
+    assert_eq!(
+        verdicts[4].skip_reason,
+        Some(SkipReason::UnsupportedLanguage {
+            language: "fr".to_string()
+        })
+    );
+    assert!(verdicts[0].skip_reason.is_none());
+
+    let none = evaluate_indicator(&indicators[1], &message(), None, None);
+    assert_eq!(
+        serde_json::to_value(&none).unwrap()["skip_reason"],
+        json!({"reason": "missing_evaluator", "evaluator": "cel"})
+    );
+    assert_eq!(
+        none.evidence.as_deref(),
+        Some("CEL evaluator not available")
+    );
+}
+
+#[test]
+fn oversized_inputs_are_skipped() {
+    let doc = doc();
+    let indicators = doc.attack.indicators.as_ref().unwrap();
+    let small = Limited(CapabilitySet {
+        max_input_bytes: Some(16),
+        ..CapabilitySet::default()
+    });
+    let size = serde_json::to_string(&message()["arguments"])
+        .unwrap()
+        .len();
+
+    let verdict = evaluate_indicator(&indicators[1], &message(), Some(&small), None);
+    assert_eq!(verdict.result, IndicatorResult::Skipped);
+    assert!(matches!(
+        verdict.skip_reason,
+        Some(SkipReason::InputTooLarge { limit: 16, .. })
+    ));
+
+    // Semantic limits apply to each extracted text.
+    let verdict = evaluate_indicator(&indicators[3], &message(), None, Some(&small));
+    assert_eq!(
+        verdict.skip_reason,
+        Some(SkipReason::InputTooLarge { size, limit: 16 })
+    );
+    let roomy = Limited(CapabilitySet {
+        max_input_bytes: Some(size),
+        ..CapabilitySet::default()
+    });
+    let verdict = evaluate_indicator(&indicators[3], &message(), None, Some(&roomy));
+    assert_eq!(verdict.result, IndicatorResult::Matched);
+}
+
+#[test]
+fn stream_evaluator_reports_skip_reasons() {
+    let mut stream = StreamEvaluator::from_document(&doc());
+    let event = ProtocolEvent {
+        event_type: "tools/call".to_string(),
+        qualifier: Some("read_file".to_string()),
+        content: message(),
+        origin: None,
+    };
+    let verdicts = stream.observe(&event, Duration::ZERO, Some(&CelSubsetEvaluator), None);
+    let reasons: Vec<_> = verdicts.iter().map(|v| v.skip_reason.clone()).collect();
+    let missing_semantic = Some(SkipReason::MissingEvaluator {
+        evaluator: EvaluatorKind::Semantic,
+    });
+    assert_eq!(
+        reasons,
+        [
+            None,
+            None,
+            Some(SkipReason::UnsupportedFunctions {
+                functions: vec!["matches".to_string()]
+            }),
+            missing_semantic.clone(),
+            missing_semantic,
+        ]
+    );
+}
+
+// ─── Summary ────────────────────────────────────────────────────────────────
+
+#[test]
+fn unsupported_indicators_lists_what_a_deployment_cannot_evaluate() {
+    let doc = doc();
+    let semantic = english();
+    assert_eq!(
+        unsupported_indicators(&doc, Some(&CelSubsetEvaluator), Some(&semantic)),
+        [
+            (
+                "indicator-03".to_string(),
+                SkipReason::UnsupportedFunctions {
+                    functions: vec!["matches".to_string()]
+                }
+            ),
+            (
+                "indicator-05".to_string(),
+                SkipReason::UnsupportedLanguage {
+                    language: "fr".to_string()
+                }
+            ),
+        ]
+    );
+
+    let unrestricted = Limited(CapabilitySet::default());
+    let unsupported = unsupported_indicators(&doc, Some(&unrestricted), None);
+    let ids: Vec<&str> = unsupported.iter().map(|(id, _)| id.as_str()).collect();
+    assert_eq!(ids, ["indicator-04", "indicator-05"]);
+    assert!(unsupported_indicators(&doc, Some(&unrestricted), Some(&unrestricted)).is_empty());
+}
+
+#[test]
+fn evaluator_stack_reports_unsupported_indicators() {
+    use oatf::evaluate::config::{EvaluatorConfig, NoBackends};
+
+    let stack = EvaluatorConfig::default().build(&NoBackends).unwrap();
+    let unsupported = stack.unsupported_indicators(&doc());
+    assert!(unsupported.iter().all(|(_, reason)| {
+        *reason
+            == SkipReason::MissingEvaluator {
+                evaluator: EvaluatorKind::Semantic,
+            }
+            || cfg!(not(feature = "cel-eval"))
+    }));
+    assert_eq!(
+        unsupported.len(),
+        if cfg!(feature = "cel-eval") { 2 } else { 3 }
+    );
+}
@@ -0,0 +1,210 @@
+#![cfg(feature = "evaluate")]
+
+use oatf::EvaluationErrorKind;
+use oatf::enums::IndicatorResult;
+use oatf::evaluate::{CelEvaluator, CelSubsetEvaluator, evaluate_indicator};
+use oatf::types::SkipReason;
+use serde_json::{Value, json};
+
+fn context() -> Value {
+    json!({
+        "message": {
+            "method": "tools/call",
+            "params": {
+                "name": "read_file",
+                "arguments": {"path": "/home/user/.ssh/id_rsa"},
+            },
+            "content": [{"type": "text", "text": "ok"}, {"type": "image"}],
+            "headers": {"content-type": "text/plain"},
+            "score": 0.75,
+            "count": 3,
+        },
+        "tool": "read_file",
+    })
+}
+
+fn eval(expression: &str) -> Result<Value, oatf::EvaluationError> {
+    CelSubsetEvaluator.evaluate(expression, &context())
+}
+
+const EXPRESSIONS: &[(&str, bool)] = &[
+    (r#"message.method == "tools/call""#, true),
+    (r#"message.params.name != 'read_file'"#, false),
+    (r#"message.params.arguments.path.contains(".ssh")"#, true),
+    (
+        r#"message.params.arguments.path.startsWith("/home/")"#,
+        true,
+    ),
+    (
+        r#"message.params.arguments.path.endsWith("id_rsa.pub")"#,
+        false,
+    ),
+    (
+        "size(message.content) == 2 && message.content.size() >= 2",
+        true,
+    ),
+    ("size(message.params.name) > 8", true),
+    (r#"message.content[0].text == "ok""#, true),
+    (r#"message.headers["content-type"] == "text/plain""#, true),
+    ("message.score < 1 && message.count >= 3.0", true),
+    ("!(message.count > 5) || false", true),
+    (
+        r#"tool == message.params.name && !tool.contains("write")"#,
+        true,
+    ),
+    ("message.missing == 1", false),
+    ("message.missing.deeper.contains('x')", false),
+    ("undeclared", false),
+    ("message.count == 3 || message.missing == 1", true),
+    ("message.count == 4 && message.missing == 1", false),
+    ("-1 < message.count", true),
+];
+
+// ─── Subset ─────────────────────────────────────────────────────────────────
+
+#[test]
+fn subset_expressions_evaluate() {
+    for (expression, expected) in EXPRESSIONS {
+        assert_eq!(
+            eval(expression).unwrap(),
+            Value::Bool(*expected),
+            "{}",
+            expression
+        );
+    }
+}
+
+#[test]
+fn constructs_outside_the_subset_are_unsupported() {
+    for (expression, construct) in [
+        (
+            r#"message.method.matches("^tools/")"#,
+            "the 'matches' method",
+        ),
+        (
+            "message.content.exists(c, c.type == 'image')",
+            "the 'exists' method",
+        ),
+        ("message.count + 1 > 3", "the '+' operator"),
+        ("message.count > 1 ? true : false", "the '?:' operator"),
+        ("message.method in ['tools/call']", "the 'in' operator"),
+        ("int(message.score) == 0", "the 'int' function"),
+        ("[1, 2] == message.content", "a list literal"),
+        // Checked before evaluation, even where it would not be reached.
+        (
+            "false && message.method.matches('x')",
+            "the 'matches' method",
+        ),
+    ] {
+        let err = eval(expression).unwrap_err();
+        assert_eq!(err.kind, EvaluationErrorKind::Unsupported, "{}", expression);
+        assert_eq!(
+            err.message,
+            format!(
+                "{} is not supported by the built-in CEL subset; enable the cel-eval feature",
+                construct
+            )
+        );
+    }
+}
+
+#[test]
+fn malformed_and_mistyped_expressions_are_errors() {
+    for expression in [
+        "message.method ==",
+        "(message.count > 1",
+        "message.method.contains()",
+        "'unterminated",
+        "message.count.contains('3')",
+        "message.method < 3",
+        "message.count && true",
+    ] {
+        let err = eval(expression).unwrap_err();
+        assert_eq!(err.kind, EvaluationErrorKind::CelError, "{}", expression);
+    }
+}
+
+// ─── Indicator evaluation ───────────────────────────────────────────────────
+
+#[test]
+fn unsupported_expressions_skip_the_indicator() {
+    let indicator = |cel: &str| {
+        let doc = format!(
+            r#"
+oatf: "0.1"
+attack:
+  execution:
+    mode: mcp_server
+    state:
+      tools: []
+  indicators:
+    - surface: tool_arguments
+      expression:
+        cel: '{}'
+"#,
+            cel
+        );
+        oatf::load(&doc)
+            .unwrap()
+            .document
+            .attack
+            .indicators
+            .unwrap()[0]
+            .clone()
+    };
+    let message = json!({"name": "read_file", "arguments": {"path": "/etc/passwd"}});
+
+    let verdict = evaluate_indicator(
+        &indicator(r#"message.arguments.path.startsWith("/etc/")"#),
+        &message,
+        Some(&CelSubsetEvaluator),
+        None,
+    );
+    assert_eq!(verdict.result, IndicatorResult::Matched);
+
+    let verdict = evaluate_indicator(
+        &indicator(r#"message.arguments.path.matches("^/etc/")"#),
+        &message,
+        Some(&CelSubsetEvaluator),
+        None,
+    );
+    assert_eq!(verdict.result, IndicatorResult::Skipped);
+    assert_eq!(
+        verdict.skip_reason,
+        Some(SkipReason::UnsupportedFunctions {
+            functions: vec!["matches".to_string()]
+        })
+    );
+
+    let verdict = evaluate_indicator(
+        &indicator(r#"message.arguments.path + "x" == "/etc/passwdx""#),
+        &message,
+        Some(&CelSubsetEvaluator),
+        None,
+    );
+    assert_eq!(verdict.result, IndicatorResult::Skipped);
+    assert_eq!(verdict.skip_reason, Some(SkipReason::UnsupportedExpression));
+    assert_eq!(
+        verdict.evidence.as_deref(),
+        Some(
+            "the '+' operator is not supported by the built-in CEL subset; enable the cel-eval feature"
+        )
+    );
+}
+
+#[cfg(feature = "cel-eval")]
+#[test]
+fn subset_agrees_with_the_default_evaluator() {
+    use oatf::evaluate::DefaultCelEvaluator;
+
+    for (expression, _) in EXPRESSIONS {
+        assert_eq!(
+            eval(expression).unwrap(),
+            DefaultCelEvaluator
+                .evaluate(expression, &context())
+                .unwrap(),
+            "{}",
+            expression
+        );
+    }
+}
@@ -0,0 +1,140 @@
+#![cfg(feature = "evaluate")]
+
+use oatf::clock::{Clock, MockClock, SystemClock};
+use oatf::engine::actors::{ActorInput, ActorOutput, ActorSystem};
+use oatf::enums::AdvanceReason;
+use oatf::primitives::{evaluate_trigger_at, trigger_deadline};
+use oatf::{ProtocolEvent, Trigger, TriggerResult, TriggerState};
+use serde_json::json;
+use std::time::Duration;
+
+fn trigger(yaml: &str) -> Trigger {
+    serde_saphyr::from_str(yaml).unwrap()
+}
+
+#[test]
+fn mock_clock_moves_only_when_told() {
+    let clock = MockClock::new(Duration::from_secs(10));
+    let handle = clock.clone();
+    assert_eq!(clock.now(), Duration::from_secs(10));
+    handle.advance(Duration::from_millis(500));
+    clock.sleep(Duration::from_secs(2));
+    assert_eq!(handle.now(), Duration::from_millis(12_500));
+    handle.set(Duration::from_secs(1));
+    assert_eq!(clock.now(), Duration::from_secs(1));
+
+    let system = SystemClock::new();
+    assert!(system.now() < Duration::from_secs(60));
+}
+
+#[test]
+fn after_timeouts_fire_at_the_deadline() {
+    let t = trigger("event: tools/call\nafter: 30s\n");
+    let started = Duration::from_secs(5);
+    assert_eq!(trigger_deadline(&t, started), Some(Duration::from_secs(35)));
+    assert_eq!(
+        trigger_deadline(&trigger("event: tools/call\n"), started),
+        None
+    );
+
+    let clock = MockClock::new(started);
+    let mut state = TriggerState::default();
+    clock.advance(Duration::from_secs(29));
+    assert_eq!(
+        evaluate_trigger_at(&t, None, started, &clock, &mut state, "mcp"),
+        TriggerResult::NotAdvanced
+    );
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(
+        evaluate_trigger_at(&t, None, started, &clock, &mut state, "mcp"),
+        TriggerResult::Advanced {
+            reason: AdvanceReason::Timeout
+        }
+    );
+
+    let event = ProtocolEvent {
+        event_type: "tools/call".to_string(),
+        qualifier: None,
+        content: json!({}),
+        origin: None,
+    };
+    let mut state = TriggerState::default();
+    clock.set(started);
+    assert_eq!(
+        evaluate_trigger_at(&t, Some(&event), started, &clock, &mut state, "mcp"),
+        TriggerResult::Advanced {
+            reason: AdvanceReason::EventMatched
+        }
+    );
+}
+
+#[test]
+fn recorded_trace_replays_with_original_timings() {
+    let doc = oatf::load(
+        r#"
+oatf: "0.1"
+attack:
+  execution:
+    mode: mcp_server
+    phases:
+      - name: wait
+        state:
+          tools:
+            - name: ping
+              description: "Ping"
+              inputSchema: {type: object}
+        trigger:
+          event: tools/call
+          count: 2
+          after: 10s
+      - name: done
+  indicators:
+    - surface: tool_description
+      pattern:
+        contains: "x"
+"#,
+    )
+    .unwrap()
+    .document;
+    let ping = || ActorInput::Request {
+        method: "tools/call".to_string(),
+        params: json!({"name": "ping"}),
+    };
+
+    // Two pings 12s apart: the timeout fires between them.
+    let trace = [Duration::from_secs(1), Duration::from_secs(13)];
+    let clock = MockClock::default();
+    let mut system = ActorSystem::with_clock(&doc, clock.clone()).unwrap();
+    system.start();
+    let name = system.machines()[0].name().to_string();
+    let mut advanced = Vec::new();
+    for at in trace {
+        clock.set(at);
+        let mut outputs = system.tick();
+        outputs.extend(system.deliver(&name, ping()).unwrap());
+        for output in outputs {
+            if let ActorOutput::Advanced { reason } = output.output {
+                advanced.push((system.now(), reason));
+            }
+        }
+    }
+    assert_eq!(
+        advanced,
+        [(Duration::from_secs(13), AdvanceReason::Timeout)]
+    );
+
+    // Replayed 5s apart, both pings land before the timeout.
+    let clock = MockClock::default();
+    let mut system = ActorSystem::with_clock(&doc, clock.clone()).unwrap();
+    system.start();
+    clock.set(Duration::from_secs(1));
+    system.deliver(&name, ping()).unwrap();
+    clock.set(Duration::from_secs(6));
+    let outputs = system.deliver(&name, ping()).unwrap();
+    assert_eq!(
+        outputs[1].output,
+        ActorOutput::Advanced {
+            reason: AdvanceReason::EventMatched
+        }
+    );
+}
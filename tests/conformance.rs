@@ -1,3 +1,5 @@
+#![cfg(feature = "evaluate")]
+
 mod conformance {
     pub mod common;
     mod evaluate;
@@ -58,8 +58,12 @@ fn evaluate_pattern_suite() {
             pattern: Some(pattern),
             expression: None,
             semantic: None,
+            frequency: None,
+            origin: None,
+            capture: None,
             confidence: None,
             severity: None,
+            stage: None,
             false_positives: None,
             extensions: HashMap::new(),
         };
@@ -68,6 +72,7 @@ fn evaluate_pattern_suite() {
 
         let result_str = match verdict.result {
             IndicatorResult::Matched => "matched",
+            IndicatorResult::MatchedLikelyBenign => "matched_likely_benign",
             IndicatorResult::NotMatched => "not_matched",
             IndicatorResult::Error => "error",
             IndicatorResult::Skipped => "skipped",
@@ -163,8 +168,12 @@ fn evaluate_expression_suite() {
             pattern: None,
             expression: Some(expr),
             semantic: None,
+            frequency: None,
+            origin: None,
+            capture: None,
             confidence: None,
             severity: None,
+            stage: None,
             false_positives: None,
             extensions: HashMap::new(),
         };
@@ -199,6 +208,7 @@ fn evaluate_expression_suite() {
 
         let result_str = match verdict.result {
             IndicatorResult::Matched => "matched",
+            IndicatorResult::MatchedLikelyBenign => "matched_likely_benign",
             IndicatorResult::NotMatched => "not_matched",
             IndicatorResult::Error => "error",
             IndicatorResult::Skipped => "skipped",
@@ -314,8 +324,12 @@ fn evaluate_semantic_suite() {
             pattern: None,
             expression: None,
             semantic: Some(semantic),
+            frequency: None,
+            origin: None,
+            capture: None,
             confidence: None,
             severity: None,
+            stage: None,
             false_positives: None,
             extensions: HashMap::new(),
         };
@@ -340,6 +354,7 @@ fn evaluate_semantic_suite() {
 
         let result_str = match verdict.result {
             IndicatorResult::Matched => "matched",
+            IndicatorResult::MatchedLikelyBenign => "matched_likely_benign",
             IndicatorResult::NotMatched => "not_matched",
             IndicatorResult::Error => "error",
             IndicatorResult::Skipped => "skipped",
@@ -82,8 +82,12 @@ fn run_verdict_suite(filename: &str) {
                 pattern: None,
                 expression: None,
                 semantic: None,
+                frequency: None,
+                origin: None,
+                capture: None,
                 confidence: None,
                 severity: None,
+                stage: None,
                 false_positives: None,
                 extensions: HashMap::new(),
             })
@@ -99,6 +103,8 @@ fn run_verdict_suite(filename: &str) {
             author: None,
             description: None,
             grace_period: None,
+            license: None,
+            distribution: None,
             severity: None,
             impact: None,
             classification: None,
@@ -110,8 +116,16 @@ fn run_verdict_suite(filename: &str) {
                 actors: Some(vec![]),
                 extensions: HashMap::new(),
             },
+            attachments: None,
             indicators: Some(indicators),
-            correlation: Some(Correlation { logic: Some(logic) }),
+            correlation: Some(Correlation {
+                logic: Some(logic),
+                stages: None,
+                key: None,
+            }),
+            safety: None,
+            supersedes: None,
+            superseded_by: None,
             extensions: HashMap::new(),
         };
 
@@ -140,6 +154,8 @@ fn run_verdict_suite(filename: &str) {
                     timestamp: entry.timestamp.clone(),
                     evidence: None,
                     source: None,
+                    captures: None,
+                    skip_reason: None,
                 },
             );
         }
@@ -0,0 +1,117 @@
+#![cfg(feature = "normalize")]
+
+use oatf::context::ResolvedContext;
+
+const MULTI_PHASE: &str = r#"
+oatf: "0.1"
+attack:
+  execution:
+    mode: mcp_server
+    phases:
+      - name: setup
+        state:
+          tools: []
+        trigger:
+          event: tools/call
+      - name: switch
+        mode: a2a_server
+  indicators:
+    - surface: tool_description
+      pattern:
+        contains: ignore
+    - surface: agent_card
+      protocol: a2a
+      pattern:
+        contains: ignore
+"#;
+
+const MULTI_ACTOR: &str = r#"
+oatf: "0.1"
+attack:
+  execution:
+    actors:
+      - name: server
+        mode: mcp_server
+        phases:
+          - name: serve
+            state:
+              tools: []
+      - name: client
+        mode: mcp_client
+        phases:
+          - name: call
+            mode: ag_ui_client
+            state: {}
+  indicators:
+    - surface: tool_description
+      pattern:
+        contains: ignore
+"#;
+
+#[test]
+fn multi_phase_form_has_a_default_actor() {
+    let doc = oatf::parse(MULTI_PHASE).unwrap();
+    let cx = ResolvedContext::new(&doc);
+    let [actor] = cx.actors() else {
+        panic!("expected one actor");
+    };
+    assert_eq!(actor.name, "default");
+    assert_eq!(actor.path_prefix, "attack.execution");
+    assert_eq!(actor.phase_mode(0), Some("mcp_server"));
+    assert_eq!(actor.phase_mode(1), Some("a2a_server"));
+    assert_eq!(actor.phase_mode(2), None);
+
+    assert_eq!(cx.default_protocol(), Some("mcp"));
+    assert_eq!(cx.indicator_protocol(0), Some("mcp"));
+    assert_eq!(cx.indicator_protocol(1), Some("a2a"));
+    assert_eq!(
+        cx.modes().collect::<Vec<_>>(),
+        vec!["mcp_server", "a2a_server"]
+    );
+}
+
+#[test]
+fn multi_actor_form_resolves_each_actor() {
+    let doc = oatf::parse(MULTI_ACTOR).unwrap();
+    let cx = ResolvedContext::new(&doc);
+    let client = cx.actor("client").unwrap();
+    assert_eq!(client.mode, Some("mcp_client"));
+    assert_eq!(client.phase_mode(0), Some("ag_ui_client"));
+    assert_eq!(client.path_prefix, "attack.execution.actors[1]");
+    assert!(cx.actor("default").is_none());
+
+    // Several actors: no protocol to default indicators to.
+    assert_eq!(cx.default_protocol(), None);
+    assert_eq!(cx.indicator_protocol(0), None);
+}
+
+#[test]
+fn normalization_agrees_with_the_context() {
+    let doc = oatf::parse(MULTI_PHASE).unwrap();
+    let expected: Vec<_> = {
+        let cx = ResolvedContext::new(&doc);
+        (0..2)
+            .map(|i| cx.indicator_protocol(i).map(str::to_string))
+            .collect()
+    };
+    let normalized = oatf::normalize(doc);
+    let protocols: Vec<_> = normalized
+        .attack
+        .indicators
+        .unwrap()
+        .into_iter()
+        .map(|i| i.protocol)
+        .collect();
+    assert_eq!(protocols, expected);
+}
+
+#[test]
+fn single_phase_form_has_no_actors() {
+    let doc = oatf::parse(
+        "oatf: \"0.1\"\nattack:\n  execution:\n    mode: a2a_server\n    state: {}\n  indicators:\n    - surface: agent_card\n      pattern:\n        contains: x\n",
+    )
+    .unwrap();
+    let cx = ResolvedContext::new(&doc);
+    assert!(cx.actors().is_empty());
+    assert_eq!(cx.indicator_protocol(0), Some("a2a"));
+}
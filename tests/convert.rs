@@ -0,0 +1,126 @@
+#![cfg(feature = "evaluate")]
+
+use oatf::convert::{cel_to_predicate, predicate_to_cel};
+use oatf::types::MatchPredicate;
+use serde_json::Value;
+
+fn predicate(yaml: &str) -> MatchPredicate {
+    serde_saphyr::from_str(yaml).unwrap()
+}
+
+fn canonical(predicate: &MatchPredicate) -> Value {
+    let mut sorted: Vec<_> = predicate.iter().collect();
+    sorted.sort_by_key(|(k, _)| k.as_str());
+    serde_json::to_value(sorted).unwrap()
+}
+
+const PREDICATE: &str = r#"
+name: read_file
+arguments.path:
+  starts_with: "/etc/"
+  regex: "passwd|shadow"
+arguments.retries:
+  gte: 1
+  lt: 3.5
+arguments.mode:
+  any_of: [r, rw]
+arguments.x-trace:
+  exists: false
+"#;
+
+#[test]
+fn predicate_renders_as_cel_conjunction() {
+    let cel = predicate_to_cel(&predicate(PREDICATE));
+    assert_eq!(
+        cel,
+        concat!(
+            r#"message.name == "read_file" && "#,
+            r#"message.arguments.path.startsWith("/etc/") && message.arguments.path.matches("passwd|shadow") && "#,
+            r#"double(message.arguments.retries) < 3.5 && double(message.arguments.retries) >= 1.0 && "#,
+            r#"message.arguments.mode in ["r","rw"] && "#,
+            r#"!("x-trace" in message.arguments)"#,
+        )
+    );
+    assert_eq!(predicate_to_cel(&MatchPredicate::new()), "true");
+}
+
+#[test]
+fn round_trip_preserves_predicate() {
+    for yaml in [
+        PREDICATE,
+        "params.name: {contains: secret, exists: true}\n",
+        "count: {gt: 2}\nenabled: true\nmeta: {a: [1, 2]}\n",
+    ] {
+        let original = predicate(yaml);
+        let cel = predicate_to_cel(&original);
+        let back = cel_to_predicate(&cel).unwrap_or_else(|| panic!("{cel}"));
+        assert_eq!(canonical(&back), canonical(&original), "{cel}");
+    }
+}
+
+#[test]
+fn predicates_keep_document_order() {
+    let original = predicate("zeta: 1\nalpha: 2\nmid.path: {exists: true}\n");
+    let keys = |p: &MatchPredicate| p.keys().cloned().collect::<Vec<_>>();
+    assert_eq!(keys(&original), ["zeta", "alpha", "mid.path"]);
+
+    let back = cel_to_predicate(&predicate_to_cel(&original)).unwrap();
+    assert_eq!(keys(&back), ["zeta", "alpha", "mid.path"]);
+    assert_eq!(
+        serde_json::to_string(&back).unwrap(),
+        r#"{"zeta":1,"alpha":2,"mid.path":{"exists":true}}"#
+    );
+}
+
+#[test]
+fn hand_written_conjunctions_convert() {
+    let back = cel_to_predicate(
+        r#"has(message.params.uri) && message.params.uri.endsWith(".env") && message["tool-name"] == "fetch""#,
+    )
+    .unwrap();
+    assert_eq!(
+        canonical(&back),
+        canonical(&predicate(
+            "params.uri: {exists: true, ends_with: .env}\ntool-name: fetch\n"
+        ))
+    );
+}
+
+#[test]
+fn unsupported_expressions_are_rejected() {
+    for cel in [
+        r#"message.a == 1 || message.b == 2"#,
+        r#"message.a == 1 && message.a == 2"#,
+        r#"size(message.items) > 3"#,
+        r#"other.a == 1"#,
+        r#"message.a.contains('x')"#,
+        r#"message.a != 1"#,
+    ] {
+        assert!(cel_to_predicate(cel).is_none(), "{cel}");
+    }
+}
+
+#[cfg(feature = "cel-eval")]
+#[test]
+fn both_forms_agree_on_messages() {
+    use oatf::evaluate::{CelEvaluator, default_cel_evaluator};
+    use oatf::primitives::evaluate_predicate;
+    use serde_json::json;
+
+    let original = predicate(PREDICATE);
+    let cel = predicate_to_cel(&original);
+    let evaluator = default_cel_evaluator();
+    for message in [
+        json!({"name": "read_file", "arguments": {"path": "/etc/passwd", "retries": 1, "mode": "r"}}),
+        json!({"name": "read_file", "arguments": {"path": "/etc/hosts", "retries": 1, "mode": "r"}}),
+        json!({"name": "read_file", "arguments": {"path": "/etc/shadow", "retries": 3, "mode": "rw", "x-trace": 1}}),
+        json!({"name": "write_file", "arguments": {"path": "/etc/passwd", "retries": 2, "mode": "rw"}}),
+        json!({"name": "read_file", "arguments": {"path": "/etc/passwd", "retries": 4, "mode": "rw"}}),
+    ] {
+        let expected = evaluate_predicate(&original, &message);
+        let actual = evaluator
+            .evaluate(&cel, &json!({ "message": message }))
+            .unwrap();
+        assert_eq!(actual, Value::Bool(expected), "{message}");
+    }
+}
@@ -0,0 +1,196 @@
+#![cfg(feature = "distribution")]
+
+use oatf::distribution::{
+    ArtifactFile, Reference, RegistryTransport, SIGNATURE_MEDIA_TYPE, load_documents, pull, push,
+    push_with_policy, sha256_digest,
+};
+use oatf::enums::Tlp;
+use oatf::sharing::SharingPolicy;
+use oatf::{DistributionError, DistributionErrorKind};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+const DOC: &str = r#"
+oatf: "0.1"
+attack:
+  id: OATF-050
+  execution:
+    mode: mcp_server
+    state:
+      tools:
+        - name: t
+          description: d
+          inputSchema: {type: object}
+"#;
+
+/// An in-memory registry keyed by (repository, digest or tag).
+#[derive(Default)]
+struct MemoryRegistry {
+    blobs: RefCell<HashMap<(String, String), Vec<u8>>>,
+    manifests: RefCell<HashMap<(String, String), Vec<u8>>>,
+}
+
+fn not_found(what: &str) -> DistributionError {
+    DistributionError {
+        kind: DistributionErrorKind::Transport,
+        message: format!("{what} not found"),
+    }
+}
+
+impl RegistryTransport for MemoryRegistry {
+    fn put_blob(&self, repo: &str, digest: &str, content: &[u8]) -> Result<(), DistributionError> {
+        let key = (repo.to_string(), digest.to_string());
+        self.blobs.borrow_mut().insert(key, content.to_vec());
+        Ok(())
+    }
+
+    fn get_blob(&self, repo: &str, digest: &str) -> Result<Vec<u8>, DistributionError> {
+        let key = (repo.to_string(), digest.to_string());
+        self.blobs
+            .borrow()
+            .get(&key)
+            .cloned()
+            .ok_or_else(|| not_found(digest))
+    }
+
+    fn put_manifest(
+        &self,
+        repo: &str,
+        reference: &str,
+        _media_type: &str,
+        content: &[u8],
+    ) -> Result<(), DistributionError> {
+        let mut manifests = self.manifests.borrow_mut();
+        manifests.insert((repo.to_string(), reference.to_string()), content.to_vec());
+        manifests.insert((repo.to_string(), sha256_digest(content)), content.to_vec());
+        Ok(())
+    }
+
+    fn get_manifest(&self, repo: &str, reference: &str) -> Result<Vec<u8>, DistributionError> {
+        let key = (repo.to_string(), reference.to_string());
+        self.manifests
+            .borrow()
+            .get(&key)
+            .cloned()
+            .ok_or_else(|| not_found(reference))
+    }
+}
+
+fn files() -> Vec<ArtifactFile> {
+    vec![
+        ArtifactFile::document("oatf-050.yaml", DOC),
+        ArtifactFile::signature("oatf-050.yaml.sig", b"sig".to_vec()),
+    ]
+}
+
+#[test]
+fn references_parse() {
+    let tagged = Reference::parse("ghcr.io/acme/attacks:v1").unwrap();
+    assert_eq!(tagged.registry, "ghcr.io");
+    assert_eq!(tagged.repository, "acme/attacks");
+    assert_eq!(tagged.manifest_reference(), "v1");
+
+    let digest = format!("sha256:{}", "a".repeat(64));
+    let pinned = Reference::parse(&format!("localhost:5000/attacks@{digest}")).unwrap();
+    assert_eq!(pinned.registry, "localhost:5000");
+    assert_eq!(pinned.tag, None);
+    assert_eq!(pinned.manifest_reference(), digest);
+
+    let latest = Reference::parse("registry.example.com/attacks").unwrap();
+    assert_eq!(latest.manifest_reference(), "latest");
+
+    for bad in [
+        "acme/attacks:v1",
+        "ghcr.io/",
+        "ghcr.io/a@sha256:xyz",
+        "ghcr.io/a:",
+    ] {
+        let err = Reference::parse(bad).unwrap_err();
+        assert_eq!(err.kind, DistributionErrorKind::InvalidReference, "{bad}");
+    }
+}
+
+#[test]
+fn push_then_pull_round_trips() {
+    let registry = MemoryRegistry::default();
+    let reference = Reference::parse("ghcr.io/acme/attacks:v1").unwrap();
+    let digest = push(&registry, &reference, &files()).unwrap();
+
+    let pulled = pull(&registry, &reference).unwrap();
+    assert_eq!(pulled, files());
+    assert_eq!(pulled[1].media_type, SIGNATURE_MEDIA_TYPE);
+
+    let pinned = Reference::parse(&format!("ghcr.io/acme/attacks@{digest}")).unwrap();
+    assert_eq!(pull(&registry, &pinned).unwrap(), files());
+
+    let entries = load_documents("ghcr.io/acme/attacks:v1", &pulled);
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].source, "ghcr.io/acme/attacks:v1!oatf-050.yaml");
+    assert!(entries[0].result.is_ok());
+
+    let err = push(&registry, &pinned, &files()).unwrap_err();
+    assert_eq!(err.kind, DistributionErrorKind::InvalidReference);
+}
+
+#[test]
+fn push_with_policy_refuses_restricted_documents() {
+    let registry = MemoryRegistry::default();
+    let reference = Reference::parse("ghcr.io/acme/attacks:v1").unwrap();
+    let red = DOC.replace("  id: OATF-050\n", "  id: OATF-050\n  distribution: red\n");
+    let mut restricted = files();
+    restricted.push(ArtifactFile::document("oatf-051.yaml", red));
+
+    let err = push_with_policy(
+        &registry,
+        &reference,
+        &restricted,
+        SharingPolicy::new(Tlp::Amber),
+    )
+    .unwrap_err();
+    assert_eq!(err.kind, DistributionErrorKind::Restricted);
+    assert!(
+        err.message.starts_with("oatf-051.yaml: "),
+        "{}",
+        err.message
+    );
+    assert!(registry.blobs.borrow().is_empty());
+    assert!(registry.manifests.borrow().is_empty());
+
+    // Signatures are not documents, so their content is not read.
+    push_with_policy(
+        &registry,
+        &reference,
+        &files(),
+        SharingPolicy::new(Tlp::Clear),
+    )
+    .unwrap();
+    assert_eq!(pull(&registry, &reference).unwrap(), files());
+}
+
+#[test]
+fn tampered_content_is_rejected() {
+    let registry = MemoryRegistry::default();
+    let reference = Reference::parse("ghcr.io/acme/attacks:v1").unwrap();
+    let digest = push(&registry, &reference, &files()).unwrap();
+
+    let layer_digest = sha256_digest(DOC.as_bytes());
+    let key = ("acme/attacks".to_string(), layer_digest);
+    registry
+        .blobs
+        .borrow_mut()
+        .insert(key, b"tampered".to_vec());
+    let err = pull(&registry, &reference).unwrap_err();
+    assert_eq!(err.kind, DistributionErrorKind::DigestMismatch);
+
+    let key = ("acme/attacks".to_string(), digest.clone());
+    registry.manifests.borrow_mut().insert(key, b"{}".to_vec());
+    let pinned = Reference::parse(&format!("ghcr.io/acme/attacks@{digest}")).unwrap();
+    let err = pull(&registry, &pinned).unwrap_err();
+    assert_eq!(err.kind, DistributionErrorKind::DigestMismatch);
+
+    let key = ("acme/attacks".to_string(), "v1".to_string());
+    let image = br#"{"schemaVersion":2,"mediaType":"application/vnd.oci.image.manifest.v1+json","config":{"mediaType":"application/vnd.oci.image.config.v1+json","digest":"sha256:00","size":2},"layers":[]}"#;
+    registry.manifests.borrow_mut().insert(key, image.to_vec());
+    let err = pull(&registry, &reference).unwrap_err();
+    assert_eq!(err.kind, DistributionErrorKind::InvalidManifest);
+}
@@ -0,0 +1,88 @@
+#![cfg(feature = "evaluate")]
+
+use oatf::OATFError;
+use oatf::enums::IndicatorResult;
+use oatf::evaluate::{dry_run, dry_run_with};
+use serde_json::json;
+
+const CALL: &str = r#"{
+    "jsonrpc": "2.0",
+    "id": 7,
+    "method": "tools/call",
+    "params": {"name": "read", "arguments": {"path": "/home/u/.ssh/id_rsa"}}
+}"#;
+
+#[test]
+fn shorthand_pattern_matches_with_trace() {
+    let report = dry_run(
+        "surface: tool_arguments\npattern:\n  target: arguments.path\n  contains: .ssh\n",
+        CALL,
+    );
+    assert!(report.errors.is_empty(), "{:?}", report.errors);
+    assert!(report.matched());
+
+    let indicator = report.indicator.unwrap();
+    assert_eq!(indicator.protocol.as_deref(), Some("mcp"));
+    assert!(indicator.pattern.as_ref().unwrap().condition.is_some());
+    assert_eq!(report.payload.unwrap()["name"], "read");
+    assert_eq!(report.resolved, [json!("/home/u/.ssh/id_rsa")]);
+
+    // Without a target, the surface's default target is used.
+    let report = dry_run("surface: tool_arguments\npattern:\n  exists: true\n", CALL);
+    assert_eq!(report.target.as_deref(), Some("arguments"));
+    assert_eq!(report.resolved, [json!({"path": "/home/u/.ssh/id_rsa"})]);
+}
+
+#[test]
+fn explicit_target_that_misses_reports_not_matched() {
+    let report = dry_run(
+        "surface: tool_arguments\npattern:\n  target: arguments.url\n  contains: .ssh\n",
+        CALL,
+    );
+    assert!(!report.matched());
+    assert_eq!(report.verdict.unwrap().result, IndicatorResult::NotMatched);
+    assert!(report.resolved.is_empty());
+}
+
+#[test]
+fn invalid_fragment_reports_relative_paths() {
+    let report = dry_run(
+        "surface: tool_arguments\npattern:\n  target: \"arguments..path\"\n  contains: x\n",
+        CALL,
+    );
+    assert!(report.verdict.is_none());
+    assert!(report.indicator.is_none());
+    assert!(report.errors.iter().any(|e| matches!(
+        e,
+        OATFError::Validation(v) if v.rule == "V-021" && v.path == "pattern.target"
+    )));
+}
+
+#[test]
+fn malformed_inputs_are_reported_together() {
+    let report = dry_run("- not a mapping\n", "{not json");
+    assert_eq!(report.errors.len(), 2);
+    assert!(
+        report
+            .errors
+            .iter()
+            .all(|e| matches!(e, OATFError::Parse(_)))
+    );
+    assert!(report.verdict.is_none());
+}
+
+#[test]
+fn semantic_indicators_skip_without_an_evaluator() {
+    let fragment = "surface: tool_response\nsemantic:\n  intent: leak credentials\n";
+    let response = r#"{"jsonrpc": "2.0", "id": 1, "result": {"content": []}}"#;
+    let report = dry_run_with(fragment, response, None, None);
+    assert_eq!(report.verdict.unwrap().result, IndicatorResult::Skipped);
+}
+
+#[cfg(feature = "cel-eval")]
+#[test]
+fn expressions_use_the_default_cel_evaluator() {
+    let fragment = "surface: tool_arguments\nexpression:\n  cel: 'message.arguments.path.contains(\".ssh\")'\n";
+    let report = dry_run(fragment, CALL);
+    assert!(report.matched(), "{:?}", report.verdict);
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "evaluate")]
+
+use oatf::enums::IndicatorResult;
+use oatf::evaluate::evaluate_indicator;
+use oatf::primitives::resolve_wildcard_path;
+use serde_json::json;
+
+#[test]
+fn json_decoder_descends_into_embedded_json() {
+    let message = json!({
+        "arguments": {"payload": "{\"url\": \"https://evil.example\", \"hops\": [1, 2]}"}
+    });
+    assert_eq!(
+        resolve_wildcard_path("arguments.payload!json.url", &message),
+        vec![json!("https://evil.example")]
+    );
+    assert_eq!(
+        resolve_wildcard_path("arguments.payload!json.hops[*]", &message),
+        vec![json!(1), json!(2)]
+    );
+    assert_eq!(
+        resolve_wildcard_path("arguments.payload!json", &message)[0]["hops"],
+        json!([1, 2])
+    );
+}
+
+#[test]
+fn decoders_apply_to_wildcard_elements_and_chain() {
+    let message = json!({
+        "items": ["{\"a\": 1}", "not json", 7, "{\"a\": 2}"],
+        "query": "%7B%22cmd%22%3A%22rm%20-rf%22%7D"
+    });
+    assert_eq!(
+        resolve_wildcard_path("items[*]!json.a", &message),
+        vec![json!(1), json!(2)]
+    );
+    assert_eq!(
+        resolve_wildcard_path("query!url", &message),
+        vec![json!("{\"cmd\":\"rm -rf\"}")]
+    );
+    assert_eq!(
+        resolve_wildcard_path("query!url!json.cmd", &message),
+        vec![json!("rm -rf")]
+    );
+}
+
+#[test]
+fn undecodable_values_do_not_match() {
+    let message = json!({"a": "%zz", "b": "%C3%28", "c": {"d": 1}});
+    assert!(resolve_wildcard_path("a!url", &message).is_empty());
+    assert!(resolve_wildcard_path("b!url", &message).is_empty());
+    assert!(resolve_wildcard_path("c!json", &message).is_empty());
+    assert!(resolve_wildcard_path("c!yaml", &message).is_empty());
+}
+
+#[test]
+fn indicators_match_smuggled_arguments() {
+    let doc = oatf::load(
+        r#"
+oatf: "0.1"
+attack:
+  execution:
+    mode: mcp_client
+    state:
+      actions: []
+  indicators:
+    - surface: tool_arguments
+      pattern:
+        target: arguments.payload!json.url
+        contains: "evil.example"
+"#,
+    );
+    let doc = match doc {
+        Ok(result) => result.document,
+        Err(errors) => panic!("{:?}", errors),
+    };
+    let indicator = &doc.attack.indicators.unwrap()[0];
+    let call = json!({
+        "jsonrpc": "2.0",
+        "id": 3,
+        "method": "tools/call",
+        "params": {
+            "name": "fetch",
+            "arguments": {"payload": "{\"url\": \"https://evil.example/x\"}"}
+        }
+    });
+    assert_eq!(
+        evaluate_indicator(indicator, &call, None, None).result,
+        IndicatorResult::Matched
+    );
+}
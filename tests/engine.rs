@@ -0,0 +1,481 @@
+#![cfg(feature = "evaluate")]
+
+use oatf::engine::actors::{ActorInput, ActorSystem};
+use oatf::engine::{McpClient, Transport, record};
+use oatf::enums::AdvanceReason;
+use oatf::{EngineError, EngineErrorKind};
+use serde_json::{Value, json};
+
+/// In-memory MCP server: answers `initialize`, echoes tool calls, and records traffic.
+#[derive(Default)]
+struct MockServer {
+    requests: Vec<(String, Value)>,
+    notifications: Vec<(String, Value)>,
+}
+
+impl Transport for MockServer {
+    fn request(&mut self, method: &str, params: Value) -> Result<Value, EngineError> {
+        self.requests.push((method.to_string(), params.clone()));
+        match method {
+            "initialize" => Ok(json!({
+                "protocolVersion": params["protocolVersion"],
+                "serverInfo": {"name": "mock"},
+            })),
+            "tools/call" => {
+                let name = params["name"].as_str().unwrap_or_default();
+                let text = match name {
+                    "login" => "session=abc123".to_string(),
+                    other => format!("called {} with {}", other, params["arguments"]),
+                };
+                Ok(json!({"content": [{"type": "text", "text": text}]}))
+            }
+            "tools/list" => Ok(json!({"tools": [
+                {"name": "read_file", "description": "Reads a file", "inputSchema": {"type": "object"}},
+            ]})),
+            "prompts/get" => Ok(json!({"messages": []})),
+            _ => Err(EngineError {
+                kind: EngineErrorKind::Protocol,
+                message: format!("method not found: {}", method),
+                phase_name: None,
+            }),
+        }
+    }
+
+    fn notify(&mut self, method: &str, params: Value) -> Result<(), EngineError> {
+        self.notifications.push((method.to_string(), params));
+        Ok(())
+    }
+}
+
+const CLIENT_DOC: &str = r#"
+oatf: "0.1"
+attack:
+  execution:
+    mode: mcp_client@2025-03-26
+    phases:
+      - name: login
+        state:
+          tools:
+            - name: login
+              arguments:
+                user: admin
+        extractors:
+          - name: token
+            source: response
+            type: regex
+            selector: "session=([a-z0-9]+)"
+        trigger:
+          event: tools/call:login
+      - name: exfiltrate
+        on_enter:
+          - log:
+              message: "token captured"
+        state:
+          tools:
+            - name: read_file
+              arguments:
+                path: "/secrets?token={{token}}"
+  indicators:
+    - surface: tool_response
+      pattern:
+        contains: "secret"
+"#;
+
+fn load(input: &str) -> oatf::Document {
+    oatf::load(input).expect("valid document").document
+}
+
+// ─── McpClient ──────────────────────────────────────────────────────────────
+
+#[test]
+fn mcp_client_runs_phases_and_propagates_extractors() {
+    let doc = load(CLIENT_DOC);
+    let mut client = McpClient::new(MockServer::default());
+    let report = client.run(&doc).expect("run should succeed");
+
+    assert!(report.completed);
+    assert_eq!(report.phases.len(), 2);
+    assert_eq!(report.phases[0].advance, Some(AdvanceReason::EventMatched));
+    assert_eq!(
+        report.extracted.get("token").map(String::as_str),
+        Some("abc123")
+    );
+    assert_eq!(report.phases[1].logs, vec!["token captured"]);
+
+    let server = client.into_transport();
+    let methods: Vec<_> = server.requests.iter().map(|(m, _)| m.as_str()).collect();
+    assert_eq!(methods, vec!["initialize", "tools/call", "tools/call"]);
+    assert_eq!(server.requests[0].1["protocolVersion"], "2025-03-26");
+    assert_eq!(
+        server.requests[2].1["arguments"]["path"],
+        "/secrets?token=abc123"
+    );
+    assert_eq!(server.notifications[0].0, "notifications/initialized");
+}
+
+#[test]
+fn mcp_client_stops_when_trigger_never_fires() {
+    let input = r#"
+oatf: "0.1"
+attack:
+  execution:
+    mode: mcp_client
+    phases:
+      - name: probe
+        state:
+          requests:
+            - method: prompts/get
+              params:
+                name: summarize
+        trigger:
+          event: tools/call
+      - name: terminal
+  indicators:
+    - surface: tool_response
+      pattern:
+        contains: "x"
+"#;
+    let mut client = McpClient::new(MockServer::default());
+    let report = client.run(&load(input)).unwrap();
+    assert!(!report.completed);
+    assert_eq!(report.phases.len(), 1);
+    assert_eq!(report.phases[0].exchanges[0].method, "prompts/get");
+    assert_eq!(report.phases[0].advance, None);
+}
+
+#[test]
+fn mcp_client_requires_client_actor() {
+    let input = r#"
+oatf: "0.1"
+attack:
+  execution:
+    mode: mcp_server
+    state:
+      tools: []
+  indicators:
+    - surface: tool_description
+      pattern:
+        contains: "x"
+"#;
+    let err = McpClient::new(MockServer::default())
+        .run(&load(input))
+        .unwrap_err();
+    assert_eq!(err.kind, EngineErrorKind::Configuration);
+}
+
+#[test]
+fn mcp_client_reports_protocol_errors_with_phase() {
+    let input = r#"
+oatf: "0.1"
+attack:
+  execution:
+    mode: mcp_client
+    phases:
+      - name: bogus
+        state:
+          requests:
+            - method: resources/templates/list
+        trigger:
+          event: tools/call
+      - name: terminal
+  indicators:
+    - surface: tool_response
+      pattern:
+        contains: "x"
+"#;
+    let err = McpClient::new(MockServer::default())
+        .run(&load(input))
+        .unwrap_err();
+    assert_eq!(err.kind, EngineErrorKind::Protocol);
+    assert_eq!(err.phase_name.as_deref(), Some("bogus"));
+}
+
+#[test]
+fn mcp_client_honors_phase_pacing() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::time::Duration;
+
+    let input = r#"
+oatf: "0.1"
+attack:
+  execution:
+    mode: mcp_client
+    phases:
+      - name: slow
+        pacing:
+          delay: 2s
+          jitter: 1s
+        on_enter:
+          - send_notification:
+              method: notifications/roots/list_changed
+        state:
+          tools:
+            - name: a
+            - name: b
+        trigger:
+          event: tools/call:b
+      - name: terminal
+  indicators:
+    - surface: tool_response
+      pattern:
+        contains: "x"
+"#;
+    let waits = Rc::new(RefCell::new(Vec::new()));
+    let recorded = Rc::clone(&waits);
+    let mut client = McpClient::new(MockServer::default())
+        .with_seed(7)
+        .with_sleep(move |d| recorded.borrow_mut().push(d));
+    let report = client.run(&load(input)).unwrap();
+    assert!(report.completed);
+
+    // Notification, tools/call a, tools/call b: two paced gaps
+    let waits = waits.borrow();
+    assert_eq!(waits.len(), 2);
+    for wait in waits.iter() {
+        assert!(*wait >= Duration::from_secs(2) && *wait <= Duration::from_secs(3));
+    }
+}
+
+// ─── Safety rails ───────────────────────────────────────────────────────────
+
+fn safety_doc(safety: &str) -> String {
+    format!(
+        r#"
+oatf: "0.1"
+attack:
+  safety:
+{}
+  execution:
+    mode: mcp_client
+    phases:
+      - name: spray
+        pacing:
+          delay: 1m
+        state:
+          tools:
+            - name: a
+            - name: b
+            - name: c
+        trigger:
+          event: tools/call:c
+      - name: terminal
+  indicators:
+    - surface: tool_response
+      pattern:
+        contains: "x"
+"#,
+        safety
+    )
+}
+
+/// Mock server reachable at a fixed URL.
+struct RemoteServer(MockServer, &'static str);
+
+impl Transport for RemoteServer {
+    fn request(&mut self, method: &str, params: Value) -> Result<Value, EngineError> {
+        self.0.request(method, params)
+    }
+    fn notify(&mut self, method: &str, params: Value) -> Result<(), EngineError> {
+        self.0.notify(method, params)
+    }
+    fn target(&self) -> Option<String> {
+        Some(self.1.to_string())
+    }
+}
+
+#[test]
+fn safety_max_messages_stops_run() {
+    let doc = load(&safety_doc("    max_messages: 3"));
+    let mut client = McpClient::new(MockServer::default()).with_sleep(|_| {});
+    let err = client.run(&doc).unwrap_err();
+    assert_eq!(err.kind, EngineErrorKind::SafetyViolation);
+    assert_eq!(err.phase_name.as_deref(), Some("spray"));
+    // initialize, notifications/initialized, tools/call a — then stop
+    assert_eq!(client.into_transport().requests.len(), 2);
+}
+
+#[test]
+fn safety_max_runtime_stops_before_waiting() {
+    let doc = load(&safety_doc("    max_runtime: 30s"));
+    let mut client = McpClient::new(MockServer::default()).with_sleep(|_| {
+        panic!("must not sleep past max_runtime");
+    });
+    let err = client.run(&doc).unwrap_err();
+    assert_eq!(err.kind, EngineErrorKind::SafetyViolation);
+    assert!(err.message.contains("max_runtime"));
+}
+
+#[test]
+fn safety_forbidden_host_blocks_before_handshake() {
+    let doc = load(&safety_doc("    forbidden_hosts: [\"*.prod.example.com\"]"));
+    let transport = RemoteServer(
+        MockServer::default(),
+        "https://api.prod.example.com:8443/mcp",
+    );
+    let mut client = McpClient::new(transport);
+    let err = client.run(&doc).unwrap_err();
+    assert_eq!(err.kind, EngineErrorKind::SafetyViolation);
+    assert!(client.into_transport().0.requests.is_empty());
+
+    let transport = RemoteServer(MockServer::default(), "https://staging.example.com/mcp");
+    let report = McpClient::new(transport)
+        .with_sleep(|_| {})
+        .run(&doc)
+        .unwrap();
+    assert!(report.completed);
+}
+
+// ─── Clock ──────────────────────────────────────────────────────────────────
+
+#[test]
+fn mcp_client_on_mock_clock_times_timeouts_and_pacing() {
+    use oatf::clock::MockClock;
+    use std::time::Duration;
+
+    let input = r#"
+oatf: "0.1"
+attack:
+  execution:
+    mode: mcp_client
+    phases:
+      - name: probe
+        pacing:
+          delay: 5s
+        state:
+          tools:
+            - name: a
+            - name: b
+        trigger:
+          event: tools/list
+          after: 1m
+      - name: terminal
+        state:
+          tools:
+            - name: c
+  indicators:
+    - surface: tool_response
+      pattern:
+        contains: "x"
+"#;
+    let clock = MockClock::default();
+    let mut client = McpClient::new(MockServer::default()).with_clock(clock.clone());
+    let report = client.run(&load(input)).unwrap();
+
+    assert!(report.completed);
+    assert_eq!(report.phases[0].advance, Some(AdvanceReason::Timeout));
+    let at: Vec<_> = report.phases[0].exchanges.iter().map(|e| e.at).collect();
+    assert_eq!(at, [Duration::ZERO, Duration::from_secs(5)]);
+    // The timeout waits out the rest of the minute on the mock clock.
+    assert_eq!(report.phases[1].entered_at, Duration::from_secs(60));
+    assert_eq!(report.phases[1].exchanges[0].at, Duration::from_secs(60));
+    assert_eq!(oatf::clock::Clock::now(&clock), Duration::from_secs(60));
+}
+
+#[test]
+fn safety_max_runtime_uses_the_clock() {
+    use oatf::clock::MockClock;
+    use std::time::Duration;
+
+    let doc = load(&safety_doc("    max_runtime: 90s"));
+    let clock = MockClock::default();
+    let err = McpClient::new(MockServer::default())
+        .with_clock(clock.clone())
+        .run(&doc)
+        .unwrap_err();
+    assert_eq!(err.kind, EngineErrorKind::SafetyViolation);
+    // One paced minute fits; the second would exceed 90s.
+    assert_eq!(oatf::clock::Clock::now(&clock), Duration::from_secs(60));
+}
+
+// ─── Recording ──────────────────────────────────────────────────────────────
+
+#[test]
+fn record_drafts_replay_server_from_client_run() {
+    let input = CLIENT_DOC.replace(
+        "        state:\n          tools:\n            - name: login",
+        "        state:\n          requests:\n            - method: tools/list\n          tools:\n            - name: login",
+    );
+    let report = McpClient::new(MockServer::default())
+        .run(&load(&input))
+        .unwrap();
+    let draft = record(&report);
+
+    let actors = draft.attack.execution.actors.as_ref().unwrap();
+    assert_eq!(actors[0].mode, "mcp_server@2025-03-26");
+    let phases = &actors[0].phases;
+    assert_eq!(phases.len(), 2);
+    assert_eq!(
+        phases[0].trigger.as_ref().unwrap().event.as_deref(),
+        Some("tools/call:login")
+    );
+    assert!(phases[1].trigger.is_none());
+
+    let login = &phases[0].state.as_ref().unwrap()["tools"][0];
+    assert_eq!(login["name"], "login");
+    assert_eq!(login["responses"][0]["when"]["arguments.user"], "admin");
+    assert_eq!(
+        login["responses"][0]["content"][0]["text"],
+        "session=abc123"
+    );
+
+    // Listed definition copied; the extracted token becomes a server extractor
+    let read_file = &phases[1].state.as_ref().unwrap()["tools"][0];
+    assert_eq!(read_file["description"], "Reads a file");
+    assert_eq!(phases[1].extractors.as_ref().unwrap()[0].name, "token");
+
+    assert!(oatf::validate(&draft).errors.is_empty());
+    let replay = load(&oatf::serialize(&draft).unwrap());
+    assert_eq!(
+        replay_read_file(&replay, json!({"path": "/secrets?token=abc123"})),
+        "/secrets?token=abc123"
+    );
+}
+
+#[test]
+fn record_quotes_argument_names_outside_path_syntax() {
+    let input = CLIENT_DOC.replace(
+        "                path: \"/secrets?token={{token}}\"",
+        "                file-path: x\n                it's.path: \"/secrets?token={{token}}\"",
+    );
+    let report = McpClient::new(MockServer::default())
+        .run(&load(&input))
+        .unwrap();
+    let draft = record(&report);
+
+    // The dotted name cannot be a match key and is left out of the predicate.
+    let phases = &draft.attack.execution.actors.as_ref().unwrap()[0].phases;
+    let read_file = &phases[1].state.as_ref().unwrap()["tools"][0];
+    assert_eq!(
+        read_file["responses"][0]["when"],
+        json!({"arguments.file-path": "x"})
+    );
+
+    assert!(oatf::validate(&draft).errors.is_empty());
+    let replay = load(&oatf::serialize(&draft).unwrap());
+    assert_eq!(
+        replay_read_file(
+            &replay,
+            json!({"file-path": "x", "it's.path": "/secrets?token=abc123"})
+        ),
+        "/secrets?token=abc123"
+    );
+}
+
+/// Run a recorded draft through the login phase, call `read_file` with
+/// `arguments`, and return the token its extractor captured.
+fn replay_read_file(draft: &oatf::Document, arguments: Value) -> String {
+    let call = |name: &str, arguments: Value| ActorInput::Request {
+        method: "tools/call".to_string(),
+        params: json!({"name": name, "arguments": arguments}),
+    };
+    let mut system = ActorSystem::new(draft).unwrap();
+    system.start();
+    system
+        .deliver("recorded_server", call("login", json!({"user": "admin"})))
+        .unwrap();
+    system
+        .deliver("recorded_server", call("read_file", arguments))
+        .unwrap();
+    system.actor("recorded_server").unwrap().extracted()["token"].clone()
+}
@@ -0,0 +1,162 @@
+#![cfg(feature = "evaluate")]
+
+use oatf::ConfigErrorKind;
+use oatf::enums::{IndicatorResult, RegexDialect};
+use oatf::evaluate::config::{EvaluatorConfig, NoBackends};
+use serde_json::json;
+use std::time::Duration;
+
+#[cfg(feature = "cel-eval")]
+mod backends {
+    use oatf::enums::SemanticIntentClass;
+    use oatf::evaluate::SemanticEvaluator;
+    use oatf::evaluate::config::{BackendConfig, BackendFactory};
+    use oatf::{ConfigError, EvaluationError, SemanticExamples};
+
+    pub struct FixedScore(pub f64);
+
+    impl SemanticEvaluator for FixedScore {
+        fn evaluate(
+            &self,
+            _text: &str,
+            _intent: &str,
+            _intent_class: Option<&SemanticIntentClass>,
+            _threshold: Option<f64>,
+            _examples: Option<&SemanticExamples>,
+        ) -> Result<f64, EvaluationError> {
+            Ok(self.0)
+        }
+    }
+
+    pub struct Factory;
+
+    impl BackendFactory for Factory {
+        fn semantic(
+            &self,
+            _config: &BackendConfig,
+        ) -> Result<Box<dyn SemanticEvaluator>, ConfigError> {
+            Ok(Box::new(FixedScore(0.9)))
+        }
+    }
+}
+
+fn indicator(yaml: &str) -> oatf::Indicator {
+    serde_saphyr::from_str(yaml).unwrap()
+}
+
+#[cfg(feature = "cel-eval")]
+#[test]
+fn yaml_config_builds_stack_with_backends() {
+    let config = EvaluatorConfig::from_yaml(
+        r#"
+semantic:
+  endpoint: https://models.example.com/v1
+  model: embed-small
+  api_key_env: EMBED_KEY
+  timeout: 30s
+limits:
+  max_message_bytes: 64
+"#,
+    )
+    .unwrap();
+    assert!(config.cel);
+    assert_eq!(
+        config.semantic.as_ref().unwrap().timeout(),
+        Some(Duration::from_secs(30))
+    );
+
+    let stack = config.build(&backends::Factory).unwrap();
+    assert!(stack.cel().is_some());
+    assert!(stack.generation().is_none());
+
+    let semantic = indicator(
+        "id: X-01\nsurface: tool_description\nsemantic:\n  intent: exfiltrate\n  threshold: 0.7\n",
+    );
+    let verdict = stack.evaluate_indicator(&semantic, &json!({"description": "hi"}));
+    assert_eq!(verdict.result, IndicatorResult::Matched);
+
+    let large = json!({"description": "x".repeat(100)});
+    let verdict = stack.evaluate_indicator(&semantic, &large);
+    assert_eq!(verdict.result, IndicatorResult::Error);
+    assert!(verdict.evidence.unwrap().contains("limit 64"));
+}
+
+#[test]
+fn env_overrides_yaml_values() {
+    let mut config = EvaluatorConfig::from_yaml("cel: true\n").unwrap();
+    config
+        .apply_env([
+            ("OATF_EVAL_CEL", "false"),
+            ("OATF_EVAL_GENERATION_ENDPOINT", "http://localhost:8080"),
+            ("OATF_EVAL_GENERATION_TIMEOUT", "2m"),
+            ("OATF_EVAL_MAX_EXPRESSION_BYTES", "256"),
+            ("UNRELATED", "ignored"),
+        ])
+        .unwrap();
+    assert!(!config.cel);
+    let generation = config.generation.as_ref().unwrap();
+    assert_eq!(generation.endpoint, "http://localhost:8080");
+    assert_eq!(generation.timeout(), Some(Duration::from_secs(120)));
+    assert_eq!(config.limits.max_expression_bytes, Some(256));
+
+    let err = config.build(&NoBackends).unwrap_err();
+    assert_eq!(err.kind, ConfigErrorKind::Backend);
+}
+
+#[test]
+fn invalid_configs_are_rejected() {
+    let unknown = EvaluatorConfig::from_yaml("cell: true\n").unwrap_err();
+    assert_eq!(unknown.kind, ConfigErrorKind::Parse);
+
+    let endpoint = EvaluatorConfig::from_yaml("semantic:\n  endpoint: localhost\n").unwrap_err();
+    assert_eq!(endpoint.kind, ConfigErrorKind::Invalid);
+
+    let timeout = EvaluatorConfig::from_yaml("semantic:\n  endpoint: http://x\n  timeout: 5ms\n")
+        .unwrap_err();
+    assert_eq!(timeout.kind, ConfigErrorKind::Invalid);
+
+    let mut config = EvaluatorConfig::default();
+    let err = config
+        .apply_env([("OATF_EVAL_MAX_MESSAGE_BYTES", "lots")])
+        .unwrap_err();
+    assert_eq!(err.kind, ConfigErrorKind::Parse);
+}
+
+#[test]
+fn disabled_cel_skips_expression_indicators() {
+    let config = EvaluatorConfig::from_yaml("cel: false\n").unwrap();
+    let stack = config.build(&NoBackends).unwrap();
+    let expr = indicator("id: X-02\nsurface: tool_description\nexpression:\n  cel: \"true\"\n");
+    let verdict = stack.evaluate_indicator(&expr, &json!({}));
+    assert_eq!(verdict.result, IndicatorResult::Skipped);
+}
+
+#[test]
+fn portable_dialect_rejects_non_portable_patterns() {
+    let config = EvaluatorConfig::from_yaml("regex_dialect: portable\n").unwrap();
+    let stack = config.build(&NoBackends).unwrap();
+    assert_eq!(stack.regex_dialect(), RegexDialect::Portable);
+
+    let portable = indicator(
+        "id: X-03\nsurface: tool_description\npattern:\n  target: description\n  condition:\n    regex: \"ignore (all|previous)\"\n",
+    );
+    let verdict = stack.evaluate_indicator(&portable, &json!({"description": "ignore all"}));
+    assert_eq!(verdict.result, IndicatorResult::Matched);
+
+    let nested = indicator(
+        "id: X-04\nsurface: tool_description\npattern:\n  condition:\n    regex: \"[a-z&&[^aeiou]]+\"\n",
+    );
+    let verdict = stack.evaluate_indicator(&nested, &json!({"description": "xyz"}));
+    assert_eq!(verdict.result, IndicatorResult::Error);
+    assert!(verdict.evidence.unwrap().contains("portable"));
+
+    let mut config = EvaluatorConfig::default();
+    config
+        .apply_env([("OATF_EVAL_REGEX_DIALECT", "portable")])
+        .unwrap();
+    assert_eq!(config.regex_dialect, RegexDialect::Portable);
+    let err = config
+        .apply_env([("OATF_EVAL_REGEX_DIALECT", "pcre")])
+        .unwrap_err();
+    assert_eq!(err.kind, ConfigErrorKind::Parse);
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "evaluate")]
+
+use oatf::enums::{AttackResult, IndicatorResult};
+use oatf::evaluate::{compute_verdict, evaluate_indicator};
+use serde_json::json;
+use std::collections::HashMap;
+
+const DOC: &str = r#"
+oatf: "0.1"
+attack:
+  id: OATF-020
+  execution:
+    mode: mcp_client
+    phases:
+      - name: call
+        state:
+          tools: []
+  indicators:
+    - id: OATF-020-01
+      surface: tool_arguments
+      pattern:
+        target: arguments.path
+        contains: "/etc/"
+      false_positives:
+        - Configuration management agents read /etc routinely
+        - description: Health checks read /etc/hostname
+          when:
+            arguments.path: /etc/hostname
+            name:
+              any_of: [health, status]
+"#;
+
+#[test]
+fn structured_false_positives_round_trip() {
+    let loaded = oatf::load(DOC).unwrap();
+    let fps = loaded.document.attack.indicators.as_ref().unwrap()[0]
+        .false_positives
+        .as_ref()
+        .unwrap();
+    assert_eq!(fps.len(), 2);
+    assert!(fps[0].when.is_none());
+    assert_eq!(fps[1].when.as_ref().unwrap().len(), 2);
+
+    let yaml = oatf::serialize(&loaded.document).unwrap();
+    assert!(yaml.contains("- Configuration management agents read /etc routinely"));
+    let reloaded = oatf::load(&yaml).unwrap();
+    let fps = reloaded.document.attack.indicators.as_ref().unwrap()[0]
+        .false_positives
+        .as_ref()
+        .unwrap();
+    assert_eq!(fps[1].description, "Health checks read /etc/hostname");
+}
+
+#[test]
+fn matches_fitting_a_false_positive_are_likely_benign() {
+    let loaded = oatf::load(DOC).unwrap();
+    let attack = &loaded.document.attack;
+    let indicator = &attack.indicators.as_ref().unwrap()[0];
+
+    let benign = json!({"name": "health", "arguments": {"path": "/etc/hostname"}});
+    let verdict = evaluate_indicator(indicator, &benign, None, None);
+    assert_eq!(verdict.result, IndicatorResult::MatchedLikelyBenign);
+    assert_eq!(
+        verdict.evidence.as_deref(),
+        Some("likely benign: Health checks read /etc/hostname")
+    );
+
+    let malicious = json!({"name": "read", "arguments": {"path": "/etc/hostname"}});
+    let verdict = evaluate_indicator(indicator, &malicious, None, None);
+    assert_eq!(verdict.result, IndicatorResult::Matched);
+
+    let verdicts = HashMap::from([(
+        "OATF-020-01".to_string(),
+        evaluate_indicator(indicator, &benign, None, None),
+    )]);
+    let attack_verdict = compute_verdict(attack, &verdicts);
+    assert_eq!(attack_verdict.result, AttackResult::NotExploited);
+    assert_eq!(attack_verdict.evaluation_summary.not_matched, 1);
+}
@@ -0,0 +1,191 @@
+#![cfg(feature = "feed")]
+
+use oatf::feed::{FeedResponse, FeedTransport, FeedUpdate, SyncState, Validators, sync};
+use oatf::{FeedError, FeedErrorKind};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+const INDEX_URL: &str = "https://feeds.example.com/oatf/index.yaml";
+
+fn doc(id: &str, description: &str) -> String {
+    format!(
+        r#"
+oatf: "0.1"
+attack:
+  id: {id}
+  execution:
+    mode: mcp_server
+    state:
+      tools:
+        - name: t
+          description: "{description}"
+          inputSchema: {{type: object}}
+"#
+    )
+}
+
+/// Serves bodies with an ETag derived from a per-URL version counter and
+/// records the requests it receives.
+#[derive(Default)]
+struct Server {
+    bodies: RefCell<HashMap<String, (u32, String)>>,
+    requests: RefCell<Vec<(String, Option<String>)>>,
+}
+
+impl Server {
+    fn publish(&self, url: &str, body: &str) {
+        let mut bodies = self.bodies.borrow_mut();
+        let version = bodies.get(url).map_or(1, |(v, _)| v + 1);
+        bodies.insert(url.to_string(), (version, body.to_string()));
+    }
+
+    fn fetched(&self) -> Vec<String> {
+        let requests = self.requests.borrow();
+        requests.iter().map(|(url, _)| url.clone()).collect()
+    }
+}
+
+impl FeedTransport for Server {
+    fn get(&self, url: &str, validators: &Validators) -> Result<FeedResponse, FeedError> {
+        let etag = validators.etag.clone();
+        self.requests
+            .borrow_mut()
+            .push((url.to_string(), etag.clone()));
+        let bodies = self.bodies.borrow();
+        let Some((version, body)) = bodies.get(url) else {
+            return Err(FeedError {
+                kind: FeedErrorKind::Transport,
+                url: url.to_string(),
+                message: "404 Not Found".to_string(),
+            });
+        };
+        let current = format!("\"v{version}\"");
+        if etag.as_deref() == Some(current.as_str()) {
+            return Ok(FeedResponse::NotModified);
+        }
+        Ok(FeedResponse::Ok {
+            body: body.as_bytes().to_vec(),
+            validators: Validators {
+                etag: Some(current),
+                last_modified: None,
+            },
+        })
+    }
+}
+
+fn summarize(server: &Server, state: &mut SyncState) -> Vec<String> {
+    sync(server, INDEX_URL, state)
+        .unwrap()
+        .map(|update| match update.unwrap() {
+            FeedUpdate::New { url, result } => format!("new {url} {}", result.is_ok()),
+            FeedUpdate::Changed { url, result } => format!("changed {url} {}", result.is_ok()),
+            FeedUpdate::Removed { url } => format!("removed {url}"),
+        })
+        .collect()
+}
+
+#[test]
+fn sync_yields_only_new_changed_and_removed_documents() {
+    let server = Server::default();
+    server.publish(
+        INDEX_URL,
+        "documents:\n  - a.yaml\n  - /shared/b.yaml\n  - https://mirror.example.com/c.yaml\n",
+    );
+    server.publish(
+        "https://feeds.example.com/oatf/a.yaml",
+        &doc("OATF-001", "a"),
+    );
+    server.publish(
+        "https://feeds.example.com/shared/b.yaml",
+        &doc("OATF-002", "b"),
+    );
+    server.publish("https://mirror.example.com/c.yaml", "oatf: \"0.1\"\n");
+
+    let mut state = SyncState::default();
+    assert_eq!(
+        summarize(&server, &mut state),
+        [
+            "new https://feeds.example.com/oatf/a.yaml true",
+            "new https://feeds.example.com/shared/b.yaml true",
+            "new https://mirror.example.com/c.yaml false",
+        ]
+    );
+    assert_eq!(state.documents.len(), 3);
+
+    // Nothing changed: only the index is requested, conditionally.
+    server.requests.borrow_mut().clear();
+    assert!(summarize(&server, &mut state).is_empty());
+    assert_eq!(server.fetched(), [INDEX_URL]);
+    assert_eq!(server.requests.borrow()[0].1.as_deref(), Some("\"v1\""));
+
+    // One document changes, one is republished unchanged, one is dropped.
+    server.publish(INDEX_URL, "documents: [a.yaml, /shared/b.yaml]\n");
+    server.publish(
+        "https://feeds.example.com/oatf/a.yaml",
+        &doc("OATF-001", "a2"),
+    );
+    server.publish(
+        "https://feeds.example.com/shared/b.yaml",
+        &doc("OATF-002", "b"),
+    );
+    assert_eq!(
+        summarize(&server, &mut state),
+        [
+            "changed https://feeds.example.com/oatf/a.yaml true",
+            "removed https://mirror.example.com/c.yaml",
+        ]
+    );
+    assert_eq!(state.documents.len(), 2);
+
+    let persisted = serde_json::to_string(&state).unwrap();
+    let restored: SyncState = serde_json::from_str(&persisted).unwrap();
+    assert_eq!(restored, state);
+}
+
+#[test]
+fn failed_documents_are_retried_on_next_sync() {
+    let server = Server::default();
+    server.publish(INDEX_URL, "documents: [a.yaml, missing.yaml]\n");
+    server.publish(
+        "https://feeds.example.com/oatf/a.yaml",
+        &doc("OATF-001", "a"),
+    );
+
+    let mut state = SyncState::default();
+    let results: Vec<_> = sync(&server, INDEX_URL, &mut state).unwrap().collect();
+    assert!(results[0].is_ok());
+    assert_eq!(
+        results[1].as_ref().unwrap_err().kind,
+        FeedErrorKind::Transport
+    );
+
+    server.publish(
+        "https://feeds.example.com/oatf/missing.yaml",
+        &doc("OATF-003", "m"),
+    );
+    assert_eq!(
+        summarize(&server, &mut state),
+        ["new https://feeds.example.com/oatf/missing.yaml true"]
+    );
+}
+
+#[test]
+fn invalid_urls_and_indexes_are_rejected() {
+    let server = Server::default();
+    let mut state = SyncState::default();
+    let err = sync(&server, "http://feeds.example.com/index.yaml", &mut state)
+        .err()
+        .unwrap();
+    assert_eq!(err.kind, FeedErrorKind::InvalidUrl);
+
+    server.publish(INDEX_URL, "docs: []\n");
+    let err = sync(&server, INDEX_URL, &mut state).err().unwrap();
+    assert_eq!(err.kind, FeedErrorKind::InvalidIndex);
+
+    server.publish(
+        INDEX_URL,
+        "documents: [\"http://plain.example.com/a.yaml\"]\n",
+    );
+    let err = sync(&server, INDEX_URL, &mut state).err().unwrap();
+    assert_eq!(err.kind, FeedErrorKind::InvalidUrl);
+}
@@ -0,0 +1,85 @@
+use oatf::ParseErrorKind;
+use oatf::types::{Indicator, Phase};
+
+// ─── Indicators ─────────────────────────────────────────────────────────────
+
+#[test]
+fn indicator_parses_from_a_fragment() {
+    let indicator = Indicator::from_yaml(
+        r#"
+id: ssh-key-read
+surface: tool_arguments
+x-team: detection
+pattern:
+  target: arguments.path
+  regex: "\\.ssh/"
+"#,
+    )
+    .unwrap();
+    assert_eq!(indicator.id.as_deref(), Some("ssh-key-read"));
+    assert_eq!(indicator.surface, "tool_arguments");
+    assert!(indicator.pattern.is_some());
+    assert!(indicator.extensions.contains_key("x-team"));
+}
+
+#[test]
+fn indicator_fragments_get_document_checks() {
+    let unknown = Indicator::from_yaml("surface: tool_description\nconfidense: 80\n").unwrap_err();
+    assert_eq!(unknown.kind, ParseErrorKind::TypeMismatch);
+    assert_eq!(unknown.path.as_deref(), Some("indicator.confidense"));
+
+    let anchor = Indicator::from_yaml("surface: &s tool_description\nprotocol: *s\n").unwrap_err();
+    assert_eq!(anchor.kind, ParseErrorKind::Syntax);
+
+    let variant = Indicator::from_yaml(
+        "surface: tool_description\nsemantic:\n  intent: x\n  intent_class: mind_control\n",
+    )
+    .unwrap_err();
+    assert_eq!(variant.kind, ParseErrorKind::UnknownVariant);
+
+    let root = Indicator::from_yaml("- surface: tool_description\n").unwrap_err();
+    assert_eq!(root.kind, ParseErrorKind::TypeMismatch);
+    assert_eq!(root.message, "indicator must be a YAML mapping");
+
+    assert_eq!(
+        Indicator::from_yaml("  \n").unwrap_err().kind,
+        ParseErrorKind::Syntax
+    );
+}
+
+// ─── Phases ─────────────────────────────────────────────────────────────────
+
+#[test]
+fn phase_parses_from_a_fragment() {
+    let phase = Phase::from_yaml(
+        r#"
+name: trust_building
+state:
+  tools:
+    - name: read_file
+      description: Reads a file.
+trigger:
+  event: tools/call
+  count: 3
+x-notes: first phase
+"#,
+    )
+    .unwrap();
+    assert_eq!(phase.name.as_deref(), Some("trust_building"));
+    assert_eq!(phase.trigger.as_ref().unwrap().count, Some(3));
+    assert!(phase.extensions.contains_key("x-notes"));
+}
+
+#[test]
+fn phase_fragments_get_document_checks() {
+    let unknown = Phase::from_yaml("name: p\ntriger:\n  event: tools/call\n").unwrap_err();
+    assert_eq!(unknown.path.as_deref(), Some("phase.triger"));
+
+    let log =
+        Phase::from_yaml("name: p\non_enter:\n  - log:\n      message: hi\n      level: loud\n")
+            .unwrap_err();
+    assert_eq!(log.kind, ParseErrorKind::UnknownVariant);
+
+    let multi = Phase::from_yaml("---\nname: a\n---\nname: b\n").unwrap_err();
+    assert_eq!(multi.kind, ParseErrorKind::Syntax);
+}
@@ -0,0 +1,248 @@
+#![cfg(feature = "evaluate")]
+
+use oatf::enums::IndicatorResult;
+use oatf::evaluate::evaluate_indicator;
+use oatf::evaluate::stream::StreamEvaluator;
+use oatf::types::ProtocolEvent;
+use serde_json::{Value, json};
+use std::time::Duration;
+
+const DOC: &str = r#"
+oatf: "0.1"
+attack:
+  id: OATF-040
+  execution:
+    mode: mcp_server
+    state:
+      tools: []
+  indicators:
+    - id: OATF-040-01
+      surface: tool_arguments
+      frequency:
+        event: tools/call:read_file
+        window: 60s
+        threshold: 3
+    - id: OATF-040-02
+      surface: tool_arguments
+      frequency:
+        event: tools/call
+        match:
+          arguments.path:
+            starts_with: /etc/
+        window: 10s
+        threshold: 2
+        group_by: arguments.path
+    - id: OATF-040-03
+      surface: tool_arguments
+      pattern:
+        target: arguments.path
+        condition:
+          contains: passwd
+"#;
+
+fn evaluator() -> StreamEvaluator {
+    StreamEvaluator::from_document(&oatf::load(DOC).unwrap().document)
+}
+
+fn call(name: &str, path: &str) -> ProtocolEvent {
+    ProtocolEvent {
+        event_type: "tools/call".to_string(),
+        qualifier: Some(name.to_string()),
+        content: json!({"name": name, "arguments": {"path": path}}),
+        origin: None,
+    }
+}
+
+fn observe(stream: &mut StreamEvaluator, event: &ProtocolEvent, secs: u64) -> Vec<IndicatorResult> {
+    stream
+        .observe(event, Duration::from_secs(secs), None, None)
+        .into_iter()
+        .map(|v| v.result)
+        .collect()
+}
+
+#[test]
+fn threshold_within_window_matches() {
+    let mut stream = evaluator();
+    let read = call("read_file", "/tmp/a");
+    assert_eq!(
+        observe(&mut stream, &read, 0)[0],
+        IndicatorResult::NotMatched
+    );
+    assert_eq!(
+        observe(&mut stream, &read, 20)[0],
+        IndicatorResult::NotMatched
+    );
+    let verdicts = stream.observe(&read, Duration::from_secs(40), None, None);
+    assert_eq!(verdicts[0].indicator_id, "OATF-040-01");
+    assert_eq!(verdicts[0].result, IndicatorResult::Matched);
+    assert_eq!(verdicts[0].evidence.as_deref(), Some("3 events within 60s"));
+}
+
+#[test]
+fn events_leave_the_window() {
+    let mut stream = evaluator();
+    let read = call("read_file", "/tmp/a");
+    observe(&mut stream, &read, 0);
+    observe(&mut stream, &read, 30);
+    // The first call is exactly one window old and no longer counts.
+    assert_eq!(
+        observe(&mut stream, &read, 60)[0],
+        IndicatorResult::NotMatched
+    );
+    assert_eq!(observe(&mut stream, &read, 61)[0], IndicatorResult::Matched);
+}
+
+#[test]
+fn qualifier_and_match_filter_counted_events() {
+    let mut stream = evaluator();
+    for t in 0..5 {
+        let results = observe(&mut stream, &call("write_file", "/tmp/a"), t);
+        assert_eq!(results[0], IndicatorResult::NotMatched);
+        assert_eq!(results[1], IndicatorResult::NotMatched);
+    }
+}
+
+#[test]
+fn group_by_counts_each_value_separately() {
+    let mut stream = evaluator();
+    assert_eq!(
+        observe(&mut stream, &call("read_file", "/etc/passwd"), 0)[1],
+        IndicatorResult::NotMatched
+    );
+    assert_eq!(
+        observe(&mut stream, &call("read_file", "/etc/shadow"), 1)[1],
+        IndicatorResult::NotMatched
+    );
+    let verdicts = stream.observe(
+        &call("read_file", "/etc/passwd"),
+        Duration::from_secs(2),
+        None,
+        None,
+    );
+    assert_eq!(verdicts[1].result, IndicatorResult::Matched);
+    assert_eq!(
+        verdicts[1].evidence.as_deref(),
+        Some("2 events within 10s for arguments.path = /etc/passwd")
+    );
+}
+
+#[test]
+fn stateless_indicators_evaluate_each_event() {
+    let mut stream = evaluator();
+    assert_eq!(
+        observe(&mut stream, &call("read_file", "/etc/passwd"), 0)[2],
+        IndicatorResult::Matched
+    );
+    assert_eq!(
+        observe(&mut stream, &call("read_file", "/tmp/a"), 1)[2],
+        IndicatorResult::NotMatched
+    );
+}
+
+#[test]
+fn reset_forgets_counts() {
+    let mut stream = evaluator();
+    let read = call("read_file", "/tmp/a");
+    observe(&mut stream, &read, 0);
+    observe(&mut stream, &read, 1);
+    stream.reset();
+    assert_eq!(
+        observe(&mut stream, &read, 2)[0],
+        IndicatorResult::NotMatched
+    );
+}
+
+#[test]
+fn single_message_evaluation_skips_frequency_indicators() {
+    let doc = oatf::load(DOC).unwrap().document;
+    let indicator = &doc.attack.indicators.as_ref().unwrap()[0];
+    let verdict = evaluate_indicator(indicator, &Value::Null, None, None);
+    assert_eq!(verdict.result, IndicatorResult::Skipped);
+}
+
+fn errors_for(yaml: &str, rule: &str) -> Vec<String> {
+    let doc = oatf::parse(yaml).unwrap();
+    oatf::validate(&doc)
+        .errors
+        .into_iter()
+        .filter(|e| e.rule == rule)
+        .map(|e| e.path)
+        .collect()
+}
+
+#[test]
+fn v053_rejects_invalid_frequency() {
+    let yaml = r#"
+oatf: "0.1"
+attack:
+  id: OATF-041
+  execution:
+    mode: mcp_server
+    state:
+      tools: []
+  indicators:
+    - id: OATF-041-01
+      surface: tool_arguments
+      frequency:
+        event: tools/call
+        match:
+          "bad path!": {exists: true}
+        window: 0s
+        threshold: 0
+        group_by: "params..name"
+    - id: OATF-041-02
+      surface: tool_arguments
+      frequency:
+        event: tools/call
+        window: soon
+        threshold: 1
+"#;
+    assert_eq!(
+        errors_for(yaml, "V-053"),
+        vec![
+            "attack.indicators[0].frequency.window",
+            "attack.indicators[0].frequency.threshold",
+            "attack.indicators[0].frequency.group_by",
+            "attack.indicators[0].frequency.match.bad path!",
+            "attack.indicators[1].frequency.window",
+        ]
+    );
+    assert!(errors_for(DOC, "V-053").is_empty());
+}
+
+#[test]
+fn v012_counts_frequency_as_detection_key() {
+    let yaml = r#"
+oatf: "0.1"
+attack:
+  id: OATF-042
+  execution:
+    mode: mcp_server
+    state:
+      tools: []
+  indicators:
+    - id: OATF-042-01
+      surface: tool_arguments
+      pattern: {contains: x}
+      frequency:
+        event: tools/call
+        window: 60s
+        threshold: 5
+"#;
+    assert_eq!(errors_for(yaml, "V-012"), vec!["attack.indicators[0]"]);
+}
+
+#[test]
+fn frequency_round_trips_through_serialize() {
+    let doc = oatf::load(DOC).unwrap().document;
+    let yaml = oatf::serialize(&doc).unwrap();
+    assert!(yaml.contains("group_by: arguments.path"));
+    let reparsed = oatf::load(&yaml).unwrap().document;
+    let frequency = reparsed.attack.indicators.unwrap()[1]
+        .frequency
+        .clone()
+        .unwrap();
+    assert_eq!(frequency.threshold, 2);
+    assert!(frequency.match_predicate.is_some());
+}
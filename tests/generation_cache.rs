@@ -0,0 +1,188 @@
+#![cfg(feature = "evaluate")]
+
+use oatf::clock::MockClock;
+use oatf::evaluate::{CacheMetrics, CacheStats, CachedGenerationProvider, GenerationProvider};
+use oatf::{GenerationError, GenerationErrorKind};
+use serde_json::{Value, json};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Numbers its outputs; prompts containing "fail" fail.
+#[derive(Default)]
+struct Counter {
+    calls: AtomicUsize,
+}
+
+impl GenerationProvider for Counter {
+    fn generate(
+        &self,
+        prompt: &str,
+        protocol: &str,
+        _response_context: &Value,
+    ) -> Result<Value, GenerationError> {
+        let n = self.calls.fetch_add(1, Ordering::SeqCst);
+        if prompt.contains("fail") {
+            return Err(GenerationError {
+                kind: GenerationErrorKind::ModelError,
+                message: "model error".to_string(),
+                phase_name: None,
+                prompt_preview: None,
+            });
+        }
+        Ok(json!({"protocol": protocol, "n": n}))
+    }
+}
+
+fn calls(cache: &CachedGenerationProvider<Counter>) -> usize {
+    cache.inner().calls.load(Ordering::SeqCst)
+}
+
+/// Records hook calls.
+#[derive(Clone, Default)]
+struct Events(Arc<Mutex<Vec<String>>>);
+
+impl CacheMetrics for Events {
+    fn hit(&self, protocol: &str) {
+        self.0.lock().unwrap().push(format!("hit {protocol}"));
+    }
+
+    fn miss(&self, protocol: &str) {
+        self.0.lock().unwrap().push(format!("miss {protocol}"));
+    }
+
+    fn evicted(&self) {
+        self.0.lock().unwrap().push("evicted".to_string());
+    }
+}
+
+// ─── Keys ───────────────────────────────────────────────────────────────────
+
+#[test]
+fn repeated_calls_are_answered_from_the_cache() {
+    let cache = CachedGenerationProvider::new(Counter::default());
+    let context = json!({"tool": "read_file"});
+
+    let first = cache.generate("write a lure", "mcp", &context).unwrap();
+    let second = cache.generate("write a lure", "mcp", &context).unwrap();
+    assert_eq!(first, second);
+    assert_eq!(calls(&cache), 1);
+
+    // Any part of the key differing is a different call.
+    cache.generate("write a lure", "a2a", &context).unwrap();
+    cache.generate("write a decoy", "mcp", &context).unwrap();
+    cache
+        .generate("write a lure", "mcp", &json!({"tool": "fetch"}))
+        .unwrap();
+    assert_eq!(calls(&cache), 4);
+    assert_eq!(
+        cache.stats(),
+        CacheStats {
+            hits: 1,
+            misses: 4,
+            evictions: 0,
+            entries: 4
+        }
+    );
+}
+
+#[test]
+fn failures_are_not_cached() {
+    let cache = CachedGenerationProvider::new(Counter::default());
+    for _ in 0..2 {
+        let error = cache.generate("fail", "mcp", &Value::Null).unwrap_err();
+        assert_eq!(error.kind, GenerationErrorKind::ModelError);
+    }
+    assert_eq!(calls(&cache), 2);
+    assert_eq!(cache.stats().entries, 0);
+}
+
+// ─── Limits ─────────────────────────────────────────────────────────────────
+
+#[test]
+fn entries_expire_after_their_ttl() {
+    let clock = MockClock::default();
+    let cache = CachedGenerationProvider::new(Counter::default())
+        .with_ttl(Duration::from_secs(60))
+        .with_clock(clock.clone());
+
+    cache.generate("lure", "mcp", &Value::Null).unwrap();
+    clock.advance(Duration::from_secs(59));
+    cache.generate("lure", "mcp", &Value::Null).unwrap();
+    assert_eq!(calls(&cache), 1);
+
+    clock.advance(Duration::from_secs(1));
+    let fresh = cache.generate("lure", "mcp", &Value::Null).unwrap();
+    assert_eq!(fresh["n"], 1);
+    assert_eq!(calls(&cache), 2);
+}
+
+#[test]
+fn least_recently_used_entries_are_evicted() {
+    let events = Events::default();
+    let cache = CachedGenerationProvider::new(Counter::default())
+        .with_max_entries(2)
+        .with_metrics(events.clone());
+
+    cache.generate("a", "mcp", &Value::Null).unwrap();
+    cache.generate("b", "mcp", &Value::Null).unwrap();
+    cache.generate("a", "mcp", &Value::Null).unwrap();
+    cache.generate("c", "mcp", &Value::Null).unwrap();
+    assert_eq!(cache.stats().evictions, 1);
+
+    // "b" was evicted; "a" was used more recently and is kept.
+    cache.generate("a", "mcp", &Value::Null).unwrap();
+    assert_eq!(calls(&cache), 3);
+    cache.generate("b", "a2a", &Value::Null).unwrap();
+    assert_eq!(calls(&cache), 4);
+
+    assert_eq!(
+        *events.0.lock().unwrap(),
+        [
+            "miss mcp", "miss mcp", "hit mcp", "miss mcp", "evicted", "hit mcp", "miss a2a",
+            "evicted",
+        ]
+    );
+}
+
+#[test]
+fn zero_capacity_disables_caching() {
+    let cache = CachedGenerationProvider::new(Counter::default()).with_max_entries(0);
+    cache.generate("a", "mcp", &Value::Null).unwrap();
+    cache.generate("a", "mcp", &Value::Null).unwrap();
+    assert_eq!(calls(&cache), 2);
+
+    let cache = CachedGenerationProvider::new(Counter::default());
+    cache.generate("a", "mcp", &Value::Null).unwrap();
+    cache.clear();
+    cache.generate("a", "mcp", &Value::Null).unwrap();
+    assert_eq!(calls(&cache), 2);
+}
+
+// ─── Sharing ────────────────────────────────────────────────────────────────
+
+#[test]
+fn cache_is_shared_between_threads() {
+    let cache = Arc::new(CachedGenerationProvider::new(Counter::default()));
+    cache.generate("lure", "mcp", &Value::Null).unwrap();
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let cache = Arc::clone(&cache);
+            std::thread::spawn(move || cache.generate("lure", "mcp", &Value::Null).unwrap())
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap()["n"], 0);
+    }
+    assert_eq!(calls(&cache), 1);
+    assert_eq!(cache.stats().hits, 4);
+}
+
+#[test]
+fn boxed_providers_can_be_wrapped() {
+    let boxed: Box<dyn GenerationProvider> = Box::new(Counter::default());
+    let cache = CachedGenerationProvider::new(boxed);
+    cache.generate("a", "mcp", &Value::Null).unwrap();
+    cache.generate("a", "mcp", &Value::Null).unwrap();
+    assert_eq!(cache.stats().hits, 1);
+}
@@ -0,0 +1,110 @@
+#![cfg(feature = "evaluate")]
+
+use oatf::enums::IndicatorResult;
+use oatf::evaluate::evaluate_indicator;
+use oatf::{
+    OATFError, ParseErrorKind, load_indicator_set, parse_indicator_set, serialize_indicator_set,
+    validate_indicator_set,
+};
+use serde_json::json;
+
+const SET: &str = r#"
+oatf: "0.1"
+indicators:
+  - surface: tool_description
+    pattern:
+      contains: "<IMPORTANT>"
+  - id: ssh-key-read
+    surface: tool_arguments
+    pattern:
+      target: arguments.path
+      regex: "\\.ssh/"
+  - surface: task_message
+    protocol: a2a
+    expression:
+      cel: 'size(message.parts) > 10'
+"#;
+
+#[test]
+fn loads_without_execution() {
+    let result = load_indicator_set(SET).unwrap();
+    assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+    let indicators = &result.indicator_set.indicators;
+    assert_eq!(indicators.len(), 3);
+
+    // Protocols default to the surface's, IDs are generated, targets resolved.
+    assert_eq!(indicators[0].protocol.as_deref(), Some("mcp"));
+    assert_eq!(indicators[0].id.as_deref(), Some("indicator-01"));
+    assert_eq!(indicators[1].id.as_deref(), Some("ssh-key-read"));
+    let pattern = indicators[0].pattern.as_ref().unwrap();
+    assert_eq!(pattern.target.as_deref(), Some("tools[*].description"));
+    assert!(pattern.condition.is_some());
+    assert_eq!(indicators[2].protocol.as_deref(), Some("a2a"));
+
+    let call = json!({"name": "read", "arguments": {"path": "/home/u/.ssh/id_rsa"}});
+    assert_eq!(
+        evaluate_indicator(&indicators[1], &call, None, None).result,
+        IndicatorResult::Matched
+    );
+}
+
+#[test]
+fn validation_paths_are_relative_to_the_set() {
+    let input = r#"
+oatf: "0.1"
+indicators:
+  - surface: tool_description
+    pattern:
+      target: "tools..description"
+      contains: x
+  - surface: tool_description
+    protocol: a2a
+    pattern:
+      contains: y
+"#;
+    let set = parse_indicator_set(input).unwrap();
+    let result = validate_indicator_set(&set);
+    let found: Vec<(&str, &str)> = result
+        .errors
+        .iter()
+        .map(|e| (e.rule.as_str(), e.path.as_str()))
+        .collect();
+    assert!(found.contains(&("V-021", "indicators[0].pattern.target")));
+    assert!(found.contains(&("V-018", "indicators[1].surface")));
+}
+
+#[test]
+fn empty_and_attack_documents_are_rejected() {
+    let errors = load_indicator_set("oatf: \"0.1\"\nindicators: []\n").unwrap_err();
+    assert!(
+        errors
+            .iter()
+            .any(|e| matches!(e, OATFError::Validation(v) if v.rule == "V-006"))
+    );
+
+    let err = parse_indicator_set(
+        "oatf: \"0.1\"\nattack:\n  execution:\n    mode: mcp_server\n    state: {}\n",
+    )
+    .unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::TypeMismatch);
+    assert_eq!(err.path.as_deref(), Some("attack"));
+
+    let err = parse_indicator_set(
+        "oatf: \"0.1\"\nindicators:\n  - surface: tool_description\n    note: x\n    pattern:\n      contains: a\n",
+    )
+    .unwrap_err();
+    assert_eq!(err.path.as_deref(), Some("indicators[0].note"));
+}
+
+#[test]
+fn round_trips_through_serialization() {
+    let set = load_indicator_set(SET).unwrap().indicator_set;
+    let yaml = serialize_indicator_set(&set).unwrap();
+    assert!(yaml.starts_with("oatf:"), "{}", yaml);
+    let reloaded = load_indicator_set(&yaml).unwrap().indicator_set;
+    assert_eq!(
+        serialize_indicator_set(&reloaded).unwrap(),
+        yaml,
+        "normalization is idempotent"
+    );
+}
@@ -0,0 +1,78 @@
+#![cfg(all(feature = "validate", feature = "normalize"))]
+
+use oatf::intern::{InternPool, SharedNode, SharedState, parse_interned};
+use std::sync::Arc;
+
+fn variant(n: usize) -> String {
+    format!(
+        r#"
+oatf: "0.1"
+attack:
+  id: OATF-{:03}
+  execution:
+    mode: mcp_server
+    phases:
+      - name: trust
+        state:
+          tools:
+            - name: calculator
+              description: "Adds numbers"
+              inputSchema:
+                type: object
+            - name: notes
+              description: "Variant {}"
+        trigger:
+          event: tools/call:calculator
+      - name: terminal
+  indicators:
+    - surface: tool_description
+      pattern:
+        contains: "Variant"
+"#,
+        n + 100,
+        n
+    )
+}
+
+#[test]
+fn parse_interned_shares_state_subtrees_across_variants() {
+    let mut pool = InternPool::new();
+    let docs: Vec<_> = (0..50)
+        .map(|n| parse_interned(&variant(n), &mut pool).unwrap())
+        .collect();
+
+    let shared_tool = |i: usize| match docs[i].state(0, 0) {
+        Some(SharedState::Object(entries)) => match &entries[0].1 {
+            SharedNode::List(items) => Arc::clone(&items[0]),
+            other => panic!("unexpected node {:?}", other),
+        },
+        other => panic!("unexpected state {:?}", other),
+    };
+    assert!(Arc::ptr_eq(&shared_tool(0), &shared_tool(49)));
+
+    // 50 distinct "notes" tools plus one shared calculator
+    let stats = pool.stats();
+    assert_eq!(stats.values, 51);
+    assert!(stats.hits > 0);
+    assert_eq!(docs[7].mode(0), Some("mcp_server"));
+    assert_eq!(docs[7].surface(0), Some("tool_description"));
+    assert_eq!(docs[7].attack_id(), Some("OATF-107"));
+}
+
+#[test]
+fn interned_document_round_trips() {
+    let mut pool = InternPool::new();
+    let loaded = oatf::load(&variant(3)).unwrap().document;
+    let interned = pool.intern_document(loaded.clone());
+    assert_eq!(
+        oatf::serialize(&interned.to_document()).unwrap(),
+        oatf::serialize(&loaded).unwrap()
+    );
+}
+
+#[test]
+fn parse_interned_reports_load_errors() {
+    let mut pool = InternPool::new();
+    assert!(parse_interned("oatf: \"0.1\"\n", &mut pool).is_err());
+    assert_eq!(pool.stats().values, 0);
+}
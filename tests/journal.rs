@@ -0,0 +1,195 @@
+#![cfg(feature = "evaluate")]
+
+use oatf::TraceErrorKind;
+use oatf::engine::actors::{ActorInput, ActorOutput, ActorSystem};
+use oatf::engine::journal::{Journal, JournalAction};
+use oatf::enums::AdvanceReason;
+use serde_json::json;
+use std::time::Duration;
+
+const DOC: &str = r#"
+oatf: "0.1"
+attack:
+  execution:
+    actors:
+      - name: server
+        mode: mcp_server
+        phases:
+          - name: probe
+            state:
+              tools:
+                - name: read_file
+                  description: "Reads a file"
+                  inputSchema: {type: object}
+                  responses:
+                    - when:
+                        arguments.path:
+                          contains: ssh
+                      content:
+                        - type: text
+                          text: "-----BEGIN KEY-----"
+                    - content:
+                        - type: text
+                          text: "ok"
+            extractors:
+              - name: path
+                source: request
+                type: json_path
+                selector: "$.params.arguments.path"
+            trigger:
+              event: tools/call:read_file
+              count: 2
+          - name: wait
+            trigger:
+              event: tools/list
+              after: 10s
+          - name: done
+"#;
+
+fn read(path: &str) -> ActorInput {
+    ActorInput::Request {
+        method: "tools/call".to_string(),
+        params: json!({"name": "read_file", "arguments": {"path": path}}),
+    }
+}
+
+/// Start, two reads (the second advances to `wait`), and a timeout into
+/// `done`: five steps.
+fn run() -> (oatf::Document, ActorSystem) {
+    let doc = oatf::load(DOC).unwrap().document;
+    let mut system = ActorSystem::new(&doc).unwrap().with_journal();
+    system.start();
+    system.deliver("server", read("notes.txt")).unwrap();
+    system.advance(Duration::from_secs(1));
+    system.deliver("server", read("~/.ssh/id_rsa")).unwrap();
+    system.advance(Duration::from_secs(10));
+    (doc, system)
+}
+
+// ─── Recording ──────────────────────────────────────────────────────────────
+
+#[test]
+fn journal_records_each_step_and_its_effect() {
+    let (_, system) = run();
+    let steps = &system.journal().unwrap().steps;
+    let actions: Vec<_> = steps
+        .iter()
+        .map(|s| match &s.action {
+            JournalAction::Start => "start",
+            JournalAction::Deliver { .. } => "deliver",
+            JournalAction::Tick => "tick",
+        })
+        .collect();
+    assert_eq!(actions, ["start", "deliver", "tick", "deliver", "tick"]);
+
+    // Why each reply was chosen: the default entry, then the `when` match.
+    assert_eq!(steps[1].responses[0].entry, Some(1));
+    assert_eq!(steps[3].responses[0].entry, Some(0));
+    assert_eq!(steps[3].responses[0].phase, 0);
+    assert!(steps[2].responses.is_empty());
+
+    // When and why the phase advanced.
+    assert_eq!(steps[1].actors[0].phase_name.as_deref(), Some("probe"));
+    assert!(steps[3].outputs.iter().any(|o| o.output
+        == ActorOutput::Advanced {
+            reason: AdvanceReason::EventMatched
+        }));
+    assert_eq!(steps[3].actors[0].phase_name.as_deref(), Some("wait"));
+    assert_eq!(steps[3].actors[0].extracted["path"], "~/.ssh/id_rsa");
+    assert_eq!(steps[4].at, Duration::from_secs(11));
+    assert!(steps[4].actors[0].completed);
+}
+
+// ─── Rewinding ──────────────────────────────────────────────────────────────
+
+#[test]
+fn rewind_restores_an_earlier_step() {
+    let (_, mut system) = run();
+    let original = system.journal().unwrap().clone();
+
+    system.rewind_to(2).unwrap();
+    assert_eq!(system.journal().unwrap().steps, original.steps[..=2]);
+    let server = system.actor("server").unwrap();
+    assert_eq!(server.phase_name(), Some("probe"));
+    assert_eq!(server.extracted()["path"], "notes.txt");
+    assert_eq!(system.now(), Duration::from_secs(1));
+
+    // Continuing differently from the rewound step.
+    let outputs = system.deliver("server", read("/etc/passwd")).unwrap();
+    assert!(outputs.iter().any(|o| matches!(
+        &o.output,
+        ActorOutput::PhaseEntered { name: Some(name), .. } if name == "wait"
+    )));
+    assert_eq!(system.journal().unwrap().steps.len(), 4);
+    assert_eq!(
+        system.journal().unwrap().steps[3].responses[0].entry,
+        Some(1)
+    );
+
+    system.rewind_to(0).unwrap();
+    assert_eq!(system.actor("server").unwrap().phase(), 0);
+    assert!(system.actor("server").unwrap().extracted().is_empty());
+}
+
+#[test]
+fn rewind_errors() {
+    let (doc, mut system) = run();
+    let error = system.rewind_to(5).unwrap_err();
+    assert_eq!(error.kind, oatf::EngineErrorKind::Configuration);
+    assert_eq!(system.journal().unwrap().steps.len(), 5);
+
+    let mut unjournaled = ActorSystem::new(&doc).unwrap();
+    unjournaled.start();
+    assert!(unjournaled.rewind_to(0).is_err());
+}
+
+// ─── Replay ─────────────────────────────────────────────────────────────────
+
+#[test]
+fn saved_journals_replay_deterministically() {
+    let (doc, system) = run();
+    let saved = serde_json::to_string(system.journal().unwrap()).unwrap();
+    let journal: Journal = serde_json::from_str(&saved).unwrap();
+    assert_eq!(&journal, system.journal().unwrap());
+
+    let mut replayed = ActorSystem::replay(&doc, &journal).unwrap();
+    assert_eq!(replayed.journal(), system.journal());
+    assert!(replayed.is_complete());
+    replayed.rewind_to(1).unwrap();
+    assert_eq!(
+        replayed.actor("server").unwrap().phase_name(),
+        Some("probe")
+    );
+}
+
+#[test]
+fn replay_reports_divergence() {
+    let (doc, system) = run();
+    let journal = system.journal().unwrap();
+
+    // The document changed: the second read no longer gets the key.
+    let edited = oatf::load(&DOC.replace("contains: ssh", "contains: aws"))
+        .unwrap()
+        .document;
+    let error = ActorSystem::replay(&edited, journal).unwrap_err();
+    assert_eq!(error.kind, TraceErrorKind::Diverged);
+    assert_eq!(error.entry, Some(3));
+
+    let mut reordered = journal.clone();
+    reordered.steps[3].at = Duration::ZERO;
+    let error = ActorSystem::replay(&doc, &reordered).unwrap_err();
+    assert_eq!(
+        (error.kind, error.entry),
+        (TraceErrorKind::OutOfOrder, Some(3))
+    );
+
+    let mut unknown = journal.clone();
+    if let JournalAction::Deliver { actor, .. } = &mut unknown.steps[1].action {
+        *actor = "client".to_string();
+    }
+    let error = ActorSystem::replay(&doc, &unknown).unwrap_err();
+    assert_eq!(
+        (error.kind, error.entry),
+        (TraceErrorKind::UnknownActor, Some(1))
+    );
+}
@@ -0,0 +1,162 @@
+#![cfg(feature = "evaluate")]
+
+use oatf::enums::{ExtractorSource, ExtractorType};
+use oatf::jsonpath::{self, JsonPathEngine, JsonPathError, SerdeJsonPath};
+use oatf::primitives::evaluate_extractor;
+use oatf::types::Extractor;
+use serde_json::{Value, json};
+use std::sync::Mutex;
+
+/// Held by tests that depend on the process-wide engine.
+static INSTALLED: Mutex<()> = Mutex::new(());
+
+const DOC: &str = r#"
+oatf: "0.1"
+attack:
+  execution:
+    mode: mcp_server
+    phases:
+      - name: recon
+        state:
+          tools: []
+        extractors:
+          - name: first_tool
+            source: response
+            type: json_path
+            selector: "$.tools[0].name"
+        trigger:
+          event: tools/list
+      - name: exploit
+        state:
+          tools: []
+"#;
+
+/// Accepts only `$.a.b` member paths, and reports every match twice.
+struct Members;
+
+impl JsonPathEngine for Members {
+    fn check(&self, path: &str) -> Result<(), JsonPathError> {
+        match path.strip_prefix("$.") {
+            Some(rest)
+                if rest.split('.').all(|s| {
+                    !s.is_empty() && s.chars().all(|c| c.is_alphanumeric() || c == '_')
+                }) =>
+            {
+                Ok(())
+            }
+            _ => Err(JsonPathError {
+                message: format!("not a member path: '{}'", path),
+            }),
+        }
+    }
+
+    fn query(&self, path: &str, value: &Value) -> Result<Vec<Value>, JsonPathError> {
+        self.check(path)?;
+        let found = oatf::primitives::resolve_simple_path(&path[2..], value);
+        Ok(found
+            .into_iter()
+            .map(|v| json!(format!("{}{}", v, v)))
+            .collect())
+    }
+}
+
+fn extractor(selector: &str) -> Extractor {
+    Extractor {
+        name: "x".to_string(),
+        source: ExtractorSource::Response,
+        extractor_type: ExtractorType::JsonPath,
+        selector: selector.to_string(),
+    }
+}
+
+fn v015(doc: &oatf::Document) -> usize {
+    oatf::validate(doc)
+        .errors
+        .iter()
+        .filter(|e| e.rule == "V-015")
+        .count()
+}
+
+// ─── Default engine ─────────────────────────────────────────────────────────
+
+#[test]
+fn serde_json_path_queries_in_document_order() {
+    let engine = SerdeJsonPath::new();
+    let value = json!({"tools": [{"name": "a"}, {"name": "b"}]});
+    assert_eq!(
+        engine.query("$.tools[*].name", &value).unwrap(),
+        [json!("a"), json!("b")]
+    );
+    assert!(engine.check("$[?").is_err());
+    assert!(engine.query("$[?", &value).is_err());
+}
+
+#[test]
+fn selectors_that_fail_validation_never_extract() {
+    let _installed = INSTALLED.lock().unwrap_or_else(|e| e.into_inner());
+    let response = json!({"tools": [{"name": "calc"}]});
+    for selector in [
+        "$.tools[0",
+        "tools[0].name",
+        "$.tools[0]]",
+        "$..",
+        "$.tools[?@.name ==]",
+        "$['tools'",
+    ] {
+        let doc = oatf::parse(&DOC.replace("$.tools[0].name", selector)).unwrap();
+        let errors = oatf::validate(&doc).errors;
+        let v015 = errors.iter().find(|e| e.rule == "V-015");
+        let message = &v015
+            .unwrap_or_else(|| panic!("{} passed V-015", selector))
+            .message;
+        assert!(
+            message.starts_with(&format!("invalid JSONPath syntax: '{}': ", selector)),
+            "{}",
+            message
+        );
+        let extracted =
+            evaluate_extractor(&extractor(selector), &response, ExtractorSource::Response);
+        assert_eq!(extracted, None, "{}", selector);
+    }
+}
+
+// ─── Installed engine ───────────────────────────────────────────────────────
+
+#[test]
+fn validation_and_evaluation_use_the_installed_engine() {
+    let _installed = INSTALLED.lock().unwrap_or_else(|e| e.into_inner());
+    let doc = oatf::parse(DOC).unwrap();
+    let response = json!({"tools": [{"name": "calc"}], "server": {"name": "srv"}});
+
+    assert_eq!(v015(&doc), 0);
+    assert_eq!(
+        evaluate_extractor(
+            &extractor("$.tools[0].name"),
+            &response,
+            ExtractorSource::Response
+        ),
+        Some("calc".to_string())
+    );
+
+    jsonpath::set_engine(Members);
+    assert_eq!(v015(&doc), 1);
+    assert_eq!(
+        evaluate_extractor(
+            &extractor("$.tools[0].name"),
+            &response,
+            ExtractorSource::Response
+        ),
+        None
+    );
+    assert_eq!(
+        evaluate_extractor(
+            &extractor("$.server.name"),
+            &response,
+            ExtractorSource::Response
+        ),
+        Some("\"srv\"\"srv\"".to_string())
+    );
+
+    jsonpath::reset_engine();
+    assert_eq!(v015(&doc), 0);
+}
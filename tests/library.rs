@@ -0,0 +1,62 @@
+#![cfg(all(feature = "library", feature = "evaluate"))]
+
+use oatf::enums::{Category, IndicatorResult};
+use oatf::evaluate::evaluate_indicator_on_document;
+use oatf::library;
+
+#[test]
+fn library_sources_are_clean_and_ordered() {
+    let ids: Vec<_> = library::all()
+        .iter()
+        .map(|doc| doc.attack.id.clone().unwrap())
+        .collect();
+    let expected: Vec<_> = (1..=ids.len()).map(|n| format!("LIB-{:03}", n)).collect();
+    assert_eq!(ids, expected);
+
+    for id in &ids {
+        let source = library::source(id).unwrap();
+        let result = oatf::validate(&oatf::parse(source).unwrap());
+        assert!(result.errors.is_empty(), "{}: {:?}", id, result.errors);
+        assert!(result.warnings.is_empty(), "{}: {:?}", id, result.warnings);
+        assert!(source.contains(&format!("id: {}", id)));
+    }
+    assert!(library::get("LIB-999").is_none());
+}
+
+#[test]
+fn library_documents_survive_serialization() {
+    for doc in library::all() {
+        let yaml = oatf::serialize(doc).unwrap();
+        let reparsed = oatf::parse(&yaml).unwrap();
+        assert_eq!(
+            serde_json::to_value(&reparsed).unwrap(),
+            serde_json::to_value(doc).unwrap(),
+            "{}",
+            yaml
+        );
+    }
+}
+
+#[test]
+fn by_category_filters_on_classification() {
+    let ids = |category| -> Vec<_> {
+        library::by_category(category)
+            .map(|doc| doc.attack.id.as_deref().unwrap())
+            .collect()
+    };
+    assert_eq!(ids(Category::CapabilityPoisoning), ["LIB-001"]);
+    assert_eq!(ids(Category::ContextManipulation), ["LIB-005", "LIB-006"]);
+    assert!(ids(Category::AvailabilityDisruption).is_empty());
+}
+
+#[test]
+fn server_documents_detect_their_own_payloads() {
+    for doc in library::all() {
+        let id = doc.attack.id.as_deref().unwrap();
+        let matched = doc.attack.indicators.iter().flatten().any(|ind| {
+            evaluate_indicator_on_document(ind, doc, None, None).result == IndicatorResult::Matched
+        });
+        // The AG-UI document's indicator inspects the agent's reply.
+        assert_eq!(matched, id != "LIB-006", "{}", id);
+    }
+}
@@ -1,3 +1,5 @@
+#![cfg(feature = "evaluate")]
+
 //! Tests targeting gaps identified by mutation testing.
 
 use oatf::enums::*;
@@ -70,6 +72,7 @@ fn is_valid_returns_false_when_errors_present() {
             spec_ref: "§11.1.1".to_string(),
             path: "attack".to_string(),
             message: "test error".to_string(),
+            suggestion: None,
         }],
         warnings: vec![],
     };
@@ -109,8 +112,12 @@ fn make_semantic_indicator(id: &str, threshold: f64) -> Indicator {
             threshold: Some(threshold),
             examples: None,
         }),
+        frequency: None,
+        origin: None,
+        capture: None,
         confidence: None,
         severity: None,
+        stage: None,
         false_positives: None,
         extensions: HashMap::new(),
     }
@@ -162,8 +169,12 @@ fn make_attack(indicator_ids: &[&str], logic: CorrelationLogic) -> Attack {
             pattern: None,
             expression: None,
             semantic: None,
+            frequency: None,
+            origin: None,
+            capture: None,
             confidence: None,
             severity: None,
+            stage: None,
             false_positives: None,
             extensions: HashMap::new(),
         })
@@ -179,6 +190,8 @@ fn make_attack(indicator_ids: &[&str], logic: CorrelationLogic) -> Attack {
         author: None,
         description: None,
         grace_period: None,
+        license: None,
+        distribution: None,
         severity: None,
         impact: None,
         classification: None,
@@ -190,8 +203,16 @@ fn make_attack(indicator_ids: &[&str], logic: CorrelationLogic) -> Attack {
             actors: None,
             extensions: HashMap::new(),
         },
+        attachments: None,
         indicators: Some(indicators),
-        correlation: Some(Correlation { logic: Some(logic) }),
+        correlation: Some(Correlation {
+            logic: Some(logic),
+            stages: None,
+            key: None,
+        }),
+        safety: None,
+        supersedes: None,
+        superseded_by: None,
         extensions: HashMap::new(),
     }
 }
@@ -203,6 +224,8 @@ fn make_verdict(id: &str, result: IndicatorResult) -> IndicatorVerdict {
         timestamp: None,
         evidence: None,
         source: None,
+        captures: None,
+        skip_reason: None,
     }
 }
 
@@ -0,0 +1,226 @@
+#![cfg(feature = "evaluate")]
+
+use oatf::clock::MockClock;
+use oatf::engine::actors::{ActorInput, ActorOutput, ActorSystem};
+use oatf::engine::{McpClient, Transport};
+use oatf::enums::AdvanceReason;
+use oatf::{EngineError, Phase};
+use serde_json::{Value, json};
+use std::time::Duration;
+
+const CLIENT_DOC: &str = r#"
+oatf: "0.1"
+attack:
+  execution:
+    mode: mcp_client
+    phases:
+      - name: probe
+        state:
+          tools:
+            - name: probe
+        trigger:
+          event: tools/call:probe
+          match:
+            status: ready
+          after: 30s
+        on_timeout:
+          - log:
+              message: target never became ready
+          - send_notification:
+              method: notifications/cancelled
+              params:
+                reason: timeout
+      - name: done
+  indicators:
+    - surface: tool_response
+      pattern:
+        contains: ready
+"#;
+
+/// Answers every tool call with `{"status": status}`.
+struct StatusServer {
+    status: &'static str,
+    notifications: Vec<String>,
+}
+
+impl Transport for StatusServer {
+    fn request(&mut self, _method: &str, _params: Value) -> Result<Value, EngineError> {
+        Ok(json!({"status": self.status}))
+    }
+
+    fn notify(&mut self, method: &str, _params: Value) -> Result<(), EngineError> {
+        self.notifications.push(method.to_string());
+        Ok(())
+    }
+}
+
+fn run_client(status: &'static str) -> (oatf::engine::ExecutionReport, StatusServer) {
+    let doc = oatf::load(CLIENT_DOC).unwrap().document;
+    let server = StatusServer {
+        status,
+        notifications: Vec::new(),
+    };
+    let mut client = McpClient::new(server).with_clock(MockClock::default());
+    let report = client.run(&doc).unwrap();
+    (report, client.into_transport())
+}
+
+fn v057(phase: &str) -> Vec<String> {
+    let input = format!(
+        r#"
+oatf: "0.1"
+attack:
+  execution:
+    mode: mcp_client
+    phases:
+{}
+      - name: done
+"#,
+        phase
+    );
+    oatf::validate(&oatf::parse(&input).unwrap())
+        .errors
+        .into_iter()
+        .filter(|e| e.rule == "V-057" || e.rule == "V-043")
+        .map(|e| format!("{} {}", e.rule, e.path))
+        .collect()
+}
+
+#[test]
+fn client_runs_on_timeout_actions_only_when_the_trigger_times_out() {
+    let (report, server) = run_client("busy");
+    assert!(report.completed);
+    assert_eq!(report.phases[0].advance, Some(AdvanceReason::Timeout));
+    assert_eq!(report.phases[0].logs, ["target never became ready"]);
+    assert_eq!(
+        report.phases[0].notifications,
+        [(
+            "notifications/cancelled".to_string(),
+            json!({"reason": "timeout"})
+        )]
+    );
+    assert!(
+        server
+            .notifications
+            .contains(&"notifications/cancelled".to_string())
+    );
+
+    let (report, server) = run_client("ready");
+    assert_eq!(report.phases[0].advance, Some(AdvanceReason::EventMatched));
+    assert!(report.phases[0].logs.is_empty());
+    assert!(report.phases[0].notifications.is_empty());
+    assert!(
+        !server
+            .notifications
+            .contains(&"notifications/cancelled".to_string())
+    );
+}
+
+#[test]
+fn actor_emits_on_timeout_actions_between_advancing_and_the_next_phase() {
+    let doc = oatf::load(CLIENT_DOC).unwrap().document;
+    let mut system = ActorSystem::new(&doc).unwrap();
+    system.start();
+
+    let fired: Vec<_> = system
+        .advance(Duration::from_secs(30))
+        .into_iter()
+        .map(|o| o.output)
+        .collect();
+    assert_eq!(
+        fired[..3],
+        [
+            ActorOutput::Advanced {
+                reason: AdvanceReason::Timeout
+            },
+            ActorOutput::Log("target never became ready".to_string()),
+            ActorOutput::Notify {
+                method: "notifications/cancelled".to_string(),
+                params: json!({"reason": "timeout"}),
+            },
+        ]
+    );
+    assert!(matches!(
+        fired[3],
+        ActorOutput::PhaseEntered { index: 1, .. }
+    ));
+
+    // An event match advances without the on_timeout actions.
+    let mut system = ActorSystem::new(&doc).unwrap();
+    system.start();
+    let matched: Vec<_> = system
+        .deliver(
+            "default",
+            ActorInput::Response {
+                method: "tools/call".to_string(),
+                params: json!({"name": "probe"}),
+                result: json!({"status": "ready"}),
+            },
+        )
+        .unwrap()
+        .into_iter()
+        .map(|o| o.output)
+        .collect();
+    assert!(matched.contains(&ActorOutput::Advanced {
+        reason: AdvanceReason::EventMatched
+    }));
+    assert!(
+        !matched
+            .iter()
+            .any(|o| matches!(o, ActorOutput::Log(_) | ActorOutput::Notify { .. }))
+    );
+}
+
+#[test]
+fn on_timeout_round_trips() {
+    let doc = oatf::load(CLIENT_DOC).unwrap().document;
+    let phase: &Phase = &doc.attack.execution.actors.as_ref().unwrap()[0].phases[0];
+    assert_eq!(phase.on_timeout.as_ref().map(Vec::len), Some(2));
+
+    let yaml = oatf::serialize(&doc).unwrap();
+    assert!(yaml.contains("on_timeout:"));
+    let reparsed = oatf::parse(&yaml).unwrap();
+    assert_eq!(oatf::serialize(&reparsed).unwrap(), yaml);
+}
+
+#[test]
+fn v057_requires_non_empty_on_timeout_with_an_after_timeout() {
+    assert!(
+        v057(
+            r#"      - name: wait
+        trigger:
+          event: tools/call
+          after: 10s
+        on_timeout:
+          - log:
+              message: gave up"#
+        )
+        .is_empty()
+    );
+    assert_eq!(
+        v057(
+            r#"      - name: wait
+        trigger:
+          event: tools/call
+          after: 10s
+        on_timeout: []"#
+        ),
+        ["V-057 attack.execution.phases[0].on_timeout"]
+    );
+    assert_eq!(
+        v057(
+            r#"      - name: wait
+        trigger:
+          event: tools/call
+        on_timeout:
+          - log:
+              message: gave up
+            send_notification:
+              method: notifications/cancelled"#
+        ),
+        [
+            "V-043 attack.execution.phases[0].on_timeout[0]",
+            "V-057 attack.execution.phases[0].on_timeout",
+        ]
+    );
+}
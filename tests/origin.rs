@@ -0,0 +1,231 @@
+#![cfg(feature = "evaluate")]
+
+use oatf::enums::{IndicatorResult, OriginRole};
+use oatf::evaluate::evaluate_indicator_event;
+use oatf::evaluate::stream::StreamEvaluator;
+use oatf::primitives::evaluate_trigger;
+use oatf::types::{Origin, ProtocolEvent, TriggerResult, TriggerState};
+use serde_json::json;
+use std::time::Duration;
+
+const DOC: &str = r#"
+oatf: "0.1"
+attack:
+  id: OATF-060
+  execution:
+    mode: mcp_server
+    phases:
+      - name: wait
+        state:
+          tools: []
+        trigger:
+          event: tools/call
+          origin:
+            trusted: false
+      - name: done
+  indicators:
+    - id: OATF-060-01
+      surface: tool_arguments
+      origin:
+        role: client
+        trusted: false
+      pattern:
+        target: arguments.path
+        condition:
+          contains: .ssh
+    - id: OATF-060-02
+      surface: tool_arguments
+      origin:
+        endpoint:
+          starts_with: "https://untrusted."
+      frequency:
+        event: tools/call
+        window: 60s
+        threshold: 2
+"#;
+
+fn event(origin: Option<Origin>) -> ProtocolEvent {
+    ProtocolEvent {
+        event_type: "tools/call".to_string(),
+        qualifier: Some("read_file".to_string()),
+        content: json!({"name": "read_file", "arguments": {"path": "~/.ssh/id_rsa"}}),
+        origin,
+    }
+}
+
+fn client(endpoint: &str, trusted: bool) -> Option<Origin> {
+    Some(Origin {
+        role: OriginRole::Client,
+        endpoint: Some(endpoint.to_string()),
+        trusted: Some(trusted),
+    })
+}
+
+fn document() -> oatf::Document {
+    oatf::load(DOC).unwrap().document
+}
+
+#[test]
+fn origin_round_trips_on_events() {
+    let event: ProtocolEvent = serde_json::from_value(json!({
+        "event_type": "tools/call",
+        "content": {},
+        "origin": {"role": "third_party", "endpoint": "https://example.com"}
+    }))
+    .unwrap();
+    let origin = event.origin.as_ref().unwrap();
+    assert_eq!(origin.role, OriginRole::ThirdParty);
+    assert_eq!(origin.trusted, None);
+
+    let plain: ProtocolEvent =
+        serde_json::from_value(json!({"event_type": "tools/call", "content": {}})).unwrap();
+    assert!(plain.origin.is_none());
+    assert!(
+        serde_json::to_value(&plain)
+            .unwrap()
+            .get("origin")
+            .is_none()
+    );
+}
+
+#[test]
+fn indicator_distinguishes_the_same_payload_by_origin() {
+    let doc = document();
+    let indicator = &doc.attack.indicators.as_ref().unwrap()[0];
+
+    let untrusted = evaluate_indicator_event(
+        indicator,
+        &event(client("https://untrusted.example", false)),
+        None,
+        None,
+    );
+    assert_eq!(untrusted.result, IndicatorResult::Matched);
+
+    let trusted = evaluate_indicator_event(
+        indicator,
+        &event(client("https://host.internal", true)),
+        None,
+        None,
+    );
+    assert_eq!(trusted.result, IndicatorResult::NotMatched);
+    assert_eq!(
+        trusted.evidence.as_deref(),
+        Some("event origin client (https://host.internal) does not satisfy the indicator's origin")
+    );
+
+    let unknown = evaluate_indicator_event(indicator, &event(None), None, None);
+    assert_eq!(unknown.result, IndicatorResult::NotMatched);
+}
+
+#[test]
+fn trigger_origin_gates_advancement() {
+    let doc = oatf::parse(DOC).unwrap();
+    let phases = doc.attack.execution.phases.as_ref().unwrap();
+    let trigger = phases[0].trigger.as_ref().unwrap();
+    let mut state = TriggerState::default();
+
+    let trusted = event(client("https://host.internal", true));
+    assert_eq!(
+        evaluate_trigger(trigger, Some(&trusted), Duration::ZERO, &mut state, "mcp"),
+        TriggerResult::NotAdvanced
+    );
+    assert_eq!(state.event_count, 0);
+
+    let untrusted = event(client("https://untrusted.example", false));
+    assert!(matches!(
+        evaluate_trigger(trigger, Some(&untrusted), Duration::ZERO, &mut state, "mcp"),
+        TriggerResult::Advanced { .. }
+    ));
+}
+
+#[test]
+fn frequency_counts_only_matching_origins() {
+    let mut stream = StreamEvaluator::from_document(&document());
+    let untrusted = event(client("https://untrusted.example", false));
+    let trusted = event(client("https://host.internal", true));
+
+    let results = |verdicts: Vec<oatf::IndicatorVerdict>| {
+        verdicts.into_iter().map(|v| v.result).collect::<Vec<_>>()
+    };
+    assert_eq!(
+        results(stream.observe(&untrusted, Duration::from_secs(0), None, None)),
+        vec![IndicatorResult::Matched, IndicatorResult::NotMatched]
+    );
+    assert_eq!(
+        results(stream.observe(&trusted, Duration::from_secs(1), None, None)),
+        vec![IndicatorResult::NotMatched, IndicatorResult::NotMatched]
+    );
+    assert_eq!(
+        results(stream.observe(&untrusted, Duration::from_secs(2), None, None))[1],
+        IndicatorResult::Matched
+    );
+}
+
+#[test]
+fn v055_checks_origin_predicates() {
+    let errors = |yaml: &str| -> Vec<String> {
+        oatf::validate(&oatf::parse(yaml).unwrap())
+            .errors
+            .into_iter()
+            .filter(|e| e.rule == "V-055" || e.rule == "V-013")
+            .map(|e| format!("{} {}", e.rule, e.path))
+            .collect()
+    };
+    assert!(errors(DOC).is_empty());
+
+    let yaml = r#"
+oatf: "0.1"
+attack:
+  id: OATF-061
+  execution:
+    mode: mcp_server
+    phases:
+      - name: wait
+        state:
+          tools: []
+        trigger:
+          event: tools/call
+          origin:
+            role: attacker
+            endpoint: {regex: "("}
+      - name: done
+  indicators:
+    - id: OATF-061-01
+      surface: tool_arguments
+      origin:
+        role: server
+        host: evil.example
+        trusted: "no"
+      pattern: {contains: x}
+    - id: OATF-061-02
+      surface: tool_response
+      origin:
+        role: {any_of: [server, third_party]}
+      pattern: {contains: x}
+"#;
+    assert_eq!(
+        errors(yaml),
+        vec![
+            "V-013 attack.execution.phases[0].trigger.origin.endpoint.regex",
+            "V-055 attack.indicators[0].origin.role",
+            "V-055 attack.indicators[0].origin.host",
+            "V-055 attack.indicators[0].origin.trusted",
+            "V-055 attack.execution.phases[0].trigger.origin.role",
+        ]
+    );
+}
+
+#[test]
+fn surface_registry_records_senders() {
+    let info = oatf::spec::spec_info();
+    let sender = |name: &str| {
+        info.surfaces
+            .iter()
+            .find(|s| s.name == name)
+            .unwrap()
+            .origin
+    };
+    assert_eq!(sender("tool_description"), Some(OriginRole::Server));
+    assert_eq!(sender("tool_arguments"), Some(OriginRole::Client));
+    assert_eq!(sender("task_message"), None);
+}
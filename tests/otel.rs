@@ -0,0 +1,265 @@
+#![cfg(all(feature = "otel", feature = "validate", feature = "normalize"))]
+
+use oatf::otel::{self, ATTACK_VERDICT_EVENT, INDICATOR_VERDICT_EVENT};
+use oatf::{Attack, AttackVerdict};
+use opentelemetry::logs::{AnyValue, LogRecord, Logger, Severity};
+use opentelemetry::trace::{Span, SpanContext, Status};
+use opentelemetry::{Key, KeyValue};
+use serde_json::json;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::time::SystemTime;
+
+const ATTACK: &str = r#"
+oatf: "0.1"
+attack:
+  id: OATF-042
+  severity: high
+  classification:
+    category: capability_poisoning
+  execution:
+    mode: mcp_server
+    state:
+      tools:
+        - name: lookup
+          description: Look things up.
+          inputSchema: { type: object }
+  indicators:
+    - id: OATF-042-01
+      surface: tool_description
+      pattern: { contains: secret }
+    - id: OATF-042-02
+      surface: tool_description
+      severity: critical
+      pattern: { contains: token }
+"#;
+
+fn attack() -> Attack {
+    oatf::load(ATTACK).unwrap().document.attack
+}
+
+fn verdict() -> AttackVerdict {
+    serde_json::from_value(json!({
+        "attack_id": "OATF-042",
+        "result": "exploited",
+        "indicator_verdicts": [
+            {"indicator_id": "OATF-042-01", "result": "matched", "evidence": "secret"},
+            {"indicator_id": "OATF-042-02", "result": "not_matched"}
+        ],
+        "evaluation_summary": {"matched": 1, "not_matched": 1, "error": 0, "skipped": 0},
+        "source": "probe"
+    }))
+    .unwrap()
+}
+
+fn attribute(attributes: &[KeyValue], key: &str) -> Option<String> {
+    attributes
+        .iter()
+        .find(|kv| kv.key.as_str() == key)
+        .map(|kv| kv.value.as_str().into_owned())
+}
+
+#[derive(Default)]
+struct RecordingSpan {
+    events: Vec<(String, Vec<KeyValue>)>,
+}
+
+impl Span for RecordingSpan {
+    fn add_event_with_timestamp<T>(&mut self, name: T, _: SystemTime, attributes: Vec<KeyValue>)
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.events.push((name.into().into_owned(), attributes));
+    }
+    fn span_context(&self) -> &SpanContext {
+        &SpanContext::NONE
+    }
+    fn is_recording(&self) -> bool {
+        true
+    }
+    fn set_attribute(&mut self, _: KeyValue) {}
+    fn set_status(&mut self, _: Status) {}
+    fn update_name<T>(&mut self, _: T)
+    where
+        T: Into<Cow<'static, str>>,
+    {
+    }
+    fn add_link(&mut self, _: SpanContext, _: Vec<KeyValue>) {}
+    fn end_with_timestamp(&mut self, _: SystemTime) {}
+}
+
+#[derive(Default)]
+struct Record {
+    event: Option<&'static str>,
+    severity: Option<Severity>,
+    body: Option<AnyValue>,
+    attributes: Vec<(Key, AnyValue)>,
+}
+
+impl Record {
+    fn attribute(&self, key: &str) -> Option<&AnyValue> {
+        self.attributes
+            .iter()
+            .find(|(k, _)| k.as_str() == key)
+            .map(|(_, v)| v)
+    }
+}
+
+impl LogRecord for Record {
+    fn set_event_name(&mut self, name: &'static str) {
+        self.event = Some(name);
+    }
+    fn set_target<T>(&mut self, _: T)
+    where
+        T: Into<Cow<'static, str>>,
+    {
+    }
+    fn set_timestamp(&mut self, _: SystemTime) {}
+    fn set_observed_timestamp(&mut self, _: SystemTime) {}
+    fn set_severity_text(&mut self, _: &'static str) {}
+    fn set_severity_number(&mut self, number: Severity) {
+        self.severity = Some(number);
+    }
+    fn set_body(&mut self, body: AnyValue) {
+        self.body = Some(body);
+    }
+    fn add_attributes<I, K, V>(&mut self, attributes: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<Key>,
+        V: Into<AnyValue>,
+    {
+        for (k, v) in attributes {
+            self.add_attribute(k, v);
+        }
+    }
+    fn add_attribute<K, V>(&mut self, key: K, value: V)
+    where
+        K: Into<Key>,
+        V: Into<AnyValue>,
+    {
+        self.attributes.push((key.into(), value.into()));
+    }
+}
+
+#[derive(Default)]
+struct RecordingLogger {
+    records: RefCell<Vec<Record>>,
+}
+
+impl Logger for RecordingLogger {
+    type LogRecord = Record;
+    fn create_log_record(&self) -> Record {
+        Record::default()
+    }
+    fn emit(&self, record: Record) {
+        self.records.borrow_mut().push(record);
+    }
+}
+
+#[test]
+fn span_events_carry_stable_attributes() {
+    let attack = attack();
+    let mut span = RecordingSpan::default();
+    otel::add_span_events(&mut span, &verdict(), Some(&attack));
+
+    let names: Vec<&str> = span.events.iter().map(|(n, _)| n.as_str()).collect();
+    assert_eq!(
+        names,
+        [
+            ATTACK_VERDICT_EVENT,
+            INDICATOR_VERDICT_EVENT,
+            INDICATOR_VERDICT_EVENT
+        ]
+    );
+
+    let attack_event = &span.events[0].1;
+    assert_eq!(
+        attribute(attack_event, "oatf.attack.id").unwrap(),
+        "OATF-042"
+    );
+    assert_eq!(attribute(attack_event, "oatf.result").unwrap(), "exploited");
+    assert_eq!(attribute(attack_event, "oatf.severity").unwrap(), "high");
+    assert_eq!(
+        attribute(attack_event, "oatf.attack.category").unwrap(),
+        "capability_poisoning"
+    );
+    assert_eq!(
+        attribute(attack_event, "oatf.indicators.matched").unwrap(),
+        "1"
+    );
+    assert_eq!(attribute(attack_event, "oatf.source").unwrap(), "probe");
+
+    let first = &span.events[1].1;
+    assert_eq!(attribute(first, "oatf.attack.id").unwrap(), "OATF-042");
+    assert_eq!(
+        attribute(first, "oatf.indicator.id").unwrap(),
+        "OATF-042-01"
+    );
+    assert_eq!(attribute(first, "oatf.result").unwrap(), "matched");
+    assert_eq!(attribute(first, "oatf.severity").unwrap(), "high");
+    assert_eq!(
+        attribute(first, "oatf.indicator.surface").unwrap(),
+        "tool_description"
+    );
+    assert_eq!(attribute(first, "oatf.evidence").unwrap(), "secret");
+
+    // The indicator's own severity overrides the attack's.
+    let second = &span.events[2].1;
+    assert_eq!(attribute(second, "oatf.result").unwrap(), "not_matched");
+    assert_eq!(attribute(second, "oatf.severity").unwrap(), "critical");
+    assert!(attribute(second, "oatf.evidence").is_none());
+}
+
+#[test]
+fn attributes_without_attack_omit_document_fields() {
+    let verdict = verdict();
+    let attributes = otel::attack_attributes(&verdict, None);
+    assert_eq!(
+        attribute(&attributes, "oatf.attack.id").unwrap(),
+        "OATF-042"
+    );
+    assert!(attribute(&attributes, "oatf.severity").is_none());
+    assert!(attribute(&attributes, "oatf.attack.category").is_none());
+
+    let indicator = otel::indicator_attributes(&verdict.indicator_verdicts[0], None);
+    assert_eq!(
+        attribute(&indicator, "oatf.indicator.id").unwrap(),
+        "OATF-042-01"
+    );
+    assert!(attribute(&indicator, "oatf.attack.id").is_none());
+    assert!(attribute(&indicator, "oatf.indicator.surface").is_none());
+}
+
+#[test]
+fn log_records_map_results_to_levels() {
+    let attack = attack();
+    let logger = RecordingLogger::default();
+    otel::emit_log_records(&logger, &verdict(), Some(&attack));
+
+    let records = logger.records.borrow();
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[0].event, Some(ATTACK_VERDICT_EVENT));
+    assert_eq!(records[0].severity, Some(Severity::Error));
+    assert_eq!(
+        records[0].attribute("oatf.attack.id"),
+        Some(&AnyValue::String("OATF-042".into()))
+    );
+    assert_eq!(
+        records[0].attribute("oatf.indicators.not_matched"),
+        Some(&AnyValue::Int(1))
+    );
+    assert_eq!(
+        records[0].body,
+        Some(AnyValue::String("attack OATF-042 exploited".into()))
+    );
+    assert_eq!(records[1].event, Some(INDICATOR_VERDICT_EVENT));
+    assert_eq!(records[1].severity, Some(Severity::Info));
+    assert_eq!(records[2].severity, Some(Severity::Debug));
+
+    let mut not_exploited = verdict();
+    not_exploited.result = oatf::enums::AttackResult::NotExploited;
+    let logger = RecordingLogger::default();
+    otel::emit_log_records(&logger, &not_exploited, Some(&attack));
+    assert_eq!(logger.records.borrow()[0].severity, Some(Severity::Debug));
+}
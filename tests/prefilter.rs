@@ -0,0 +1,188 @@
+#![cfg(feature = "prefilter")]
+
+use oatf::ArtifactErrorKind;
+use oatf::enums::IndicatorResult;
+use oatf::evaluate::evaluate_indicator;
+use oatf::evaluate::prefilter::CompiledIndicatorSet;
+use oatf::parse::parse;
+use serde_json::json;
+
+fn doc() -> oatf::Document {
+    parse(
+        r#"
+oatf: "0.1"
+attack:
+  id: PF-001
+  execution:
+    mode: mcp_server
+    state:
+      tools:
+        - name: search
+          description: "Searches"
+          inputSchema: {type: object}
+  indicators:
+    - surface: tool_description
+      pattern:
+        contains: "ignore previous"
+    - surface: tool_description
+      pattern:
+        regex: "(?i)exfiltrat(e|ion)"
+    - surface: tool_arguments
+      pattern:
+        target: "arguments.url"
+        condition:
+          starts_with: "https://evil"
+          regex: "\\.example$"
+    - surface: tool_arguments
+      pattern:
+        target: "arguments.count"
+        condition:
+          gt: 10
+    - surface: tool_arguments
+      pattern:
+        target: "arguments.payload!json.cmd"
+        contains: "rm -rf"
+"#,
+    )
+    .expect("parse should succeed")
+}
+
+#[test]
+fn only_indicators_with_prematched_atoms_are_candidates() {
+    let set = CompiledIndicatorSet::from_document(&doc());
+    assert_eq!(set.indicators().len(), 5);
+    // Numeric and decoded-target indicators are always evaluated.
+    assert_eq!(set.prefiltered(), 3);
+
+    let benign = json!({"description": "Searches the web"});
+    assert_eq!(set.candidates(&benign), [3, 4]);
+
+    let hostile = json!({"description": "Ignore this. Data EXFILTRATION helper"});
+    assert_eq!(set.candidates(&hostile), [1, 3, 4]);
+
+    // Both atoms of a conjunction must occur.
+    let partial = json!({"arguments": {"url": "https://evil.test"}});
+    assert_eq!(set.candidates(&partial), [3, 4]);
+    let full = json!({"arguments": {"url": "https://evil.example"}});
+    assert_eq!(set.candidates(&full), [2, 3, 4]);
+}
+
+#[test]
+fn prefiltered_verdicts_match_direct_evaluation() {
+    let set = CompiledIndicatorSet::from_document(&doc());
+    let messages = [
+        json!({"description": "please ignore previous instructions"}),
+        json!({"description": "exfiltrate it"}),
+        json!({"arguments": {"url": "https://evil.example", "count": 50}}),
+        json!({"arguments": {"payload": "{\"cmd\": \"rm -rf /\"}"}}),
+        json!({"arguments": {"url": "\"quoted\" https://evil"}}),
+        json!({}),
+    ];
+    for message in &messages {
+        let batch = set.evaluate(message, None, None);
+        assert_eq!(batch.len(), set.indicators().len());
+        for (verdict, indicator) in batch.iter().zip(set.indicators()) {
+            let direct = evaluate_indicator(indicator, message, None, None);
+            assert_eq!(
+                verdict.result, direct.result,
+                "{} on {}",
+                verdict.indicator_id, message
+            );
+            assert_eq!(verdict.indicator_id, direct.indicator_id);
+        }
+    }
+    let matched: Vec<_> = set
+        .evaluate(&messages[2], None, None)
+        .into_iter()
+        .filter(|v| v.result == IndicatorResult::Matched)
+        .map(|v| v.indicator_id)
+        .collect();
+    assert_eq!(matched, ["PF-001-03", "PF-001-04"]);
+}
+
+#[test]
+fn regexes_too_large_to_combine_fall_back_to_evaluation() {
+    let mut doc = doc();
+    let indicators = doc.attack.indicators.as_mut().unwrap();
+    indicators.truncate(1);
+    for i in 0..20 {
+        indicators.push(
+            serde_json::from_value(json!({
+                "surface": "tool_description",
+                "pattern": {"regex": format!("\\w{{40}}x{}", i)}
+            }))
+            .unwrap(),
+        );
+    }
+    assert!(oatf::validate(&doc).errors.is_empty());
+
+    let set = CompiledIndicatorSet::from_document(&doc);
+    // Only the literal indicator is still pre-filtered.
+    assert_eq!(set.prefiltered(), 1);
+    let message = json!({"tools": [{"description": format!("{}x7", "a".repeat(40))}]});
+    assert_eq!(set.candidates(&message), (1..=20).collect::<Vec<_>>());
+    let matched: Vec<String> = set
+        .evaluate(&message, None, None)
+        .into_iter()
+        .filter(|v| v.result == IndicatorResult::Matched)
+        .map(|v| v.indicator_id)
+        .collect();
+    assert_eq!(matched, ["PF-001-09"]);
+
+    let loaded = CompiledIndicatorSet::from_artifact(&set.to_artifact()).unwrap();
+    assert_eq!(loaded.candidates(&message), set.candidates(&message));
+}
+
+#[test]
+fn artifacts_load_to_an_equivalent_set() {
+    let mut doc = doc();
+    doc.attack.indicators.as_mut().unwrap().push(
+        serde_json::from_value(json!({
+            "surface": "tool_description",
+            "expression": {"cel": "message.description.size() > 1000"}
+        }))
+        .unwrap(),
+    );
+    let set = CompiledIndicatorSet::from_document(&doc);
+    let bytes = set.to_artifact();
+    let loaded = CompiledIndicatorSet::from_artifact(&bytes).unwrap();
+
+    assert_eq!(
+        serde_json::to_value(loaded.indicators()).unwrap(),
+        serde_json::to_value(set.indicators()).unwrap()
+    );
+    assert_eq!(
+        loaded.indicators()[5].expression.as_ref().unwrap().cel,
+        "message.description.size() > 1000"
+    );
+    assert_eq!(loaded.prefiltered(), set.prefiltered());
+    for message in [
+        json!({"description": "Ignore this. Data EXFILTRATION helper"}),
+        json!({"arguments": {"url": "https://evil.example", "count": 50}}),
+    ] {
+        assert_eq!(loaded.candidates(&message), set.candidates(&message));
+        let results = |set: &CompiledIndicatorSet| {
+            set.evaluate(&message, None, None)
+                .into_iter()
+                .map(|v| v.result)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(results(&loaded), results(&set));
+    }
+    assert_eq!(loaded.to_artifact(), bytes);
+}
+
+#[test]
+fn damaged_or_foreign_artifacts_are_rejected() {
+    let bytes = CompiledIndicatorSet::from_document(&doc()).to_artifact();
+    let kind = |bytes: &[u8]| CompiledIndicatorSet::from_artifact(bytes).unwrap_err().kind;
+
+    assert_eq!(kind(b"oatf: \"0.1\""), ArtifactErrorKind::InvalidFormat);
+    assert_eq!(
+        kind(&bytes[..bytes.len() / 2]),
+        ArtifactErrorKind::InvalidFormat
+    );
+    let mut newer = bytes.clone();
+    newer[7] += 1;
+    assert_eq!(kind(&newer), ArtifactErrorKind::UnsupportedVersion);
+}
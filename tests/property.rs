@@ -1,3 +1,5 @@
+#![cfg(feature = "evaluate")]
+
 mod property {
     mod actors;
     mod condition;
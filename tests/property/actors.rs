@@ -124,8 +124,7 @@ attack:
         // Escape YAML special characters
         let safe_name = name.replace('\\', "\\\\")
             .replace('"', "\\\"")
-            .replace('\n', " ")
-            .replace('\r', " ");
+            .replace(['\n', '\r'], " ");
         let yaml = format!(
             r#"oatf: "0.1"
 attack:
@@ -15,6 +15,7 @@ fn empty_match_condition() -> MatchCondition {
         gte: None,
         lte: None,
         exists: None,
+        json_patch: None,
     }
 }
 
@@ -1,8 +1,7 @@
 use oatf::primitives::{evaluate_condition, evaluate_predicate};
-use oatf::types::{Condition, MatchCondition, MatchEntry};
+use oatf::types::{Condition, MatchCondition, MatchEntry, MatchPredicate};
 use proptest::prelude::*;
 use serde_json::{Value, json};
-use std::collections::HashMap;
 
 fn empty_match_condition() -> MatchCondition {
     MatchCondition {
@@ -16,6 +15,7 @@ fn empty_match_condition() -> MatchCondition {
         gte: None,
         lte: None,
         exists: None,
+        json_patch: None,
     }
 }
 
@@ -25,7 +25,7 @@ proptest! {
     // Empty predicate always returns true
     #[test]
     fn empty_predicate_is_true(n in -100i64..100) {
-        let predicate = HashMap::new();
+        let predicate = MatchPredicate::new();
         let value = json!(n);
         prop_assert!(evaluate_predicate(&predicate, &value));
     }
@@ -40,7 +40,7 @@ proptest! {
         obj.insert(key.clone(), json!(val));
         let value = Value::Object(obj);
 
-        let mut predicate = HashMap::new();
+        let mut predicate = MatchPredicate::new();
         predicate.insert(key, MatchEntry::Scalar(json!(val)));
         prop_assert!(evaluate_predicate(&predicate, &value));
     }
@@ -52,7 +52,7 @@ proptest! {
         val in -100i64..100,
     ) {
         let value = json!({"other_key": val});
-        let mut predicate = HashMap::new();
+        let mut predicate = MatchPredicate::new();
         predicate.insert(key.clone(), MatchEntry::Scalar(json!(val)));
         // If key happens to be "other_key", it will match; otherwise false
         let expected = key == "other_key";
@@ -69,7 +69,7 @@ proptest! {
         obj.insert(key.clone(), json!(val));
         let value = Value::Object(obj);
 
-        let mut predicate = HashMap::new();
+        let mut predicate = MatchPredicate::new();
         predicate.insert(key, MatchEntry::Condition(MatchCondition {
             exists: Some(true),
             ..empty_match_condition()
@@ -83,7 +83,7 @@ proptest! {
         key in "[a-z]{1,6}",
     ) {
         let value = json!({});
-        let mut predicate = HashMap::new();
+        let mut predicate = MatchPredicate::new();
         predicate.insert(key, MatchEntry::Condition(MatchCondition {
             exists: Some(false),
             ..empty_match_condition()
@@ -97,7 +97,7 @@ proptest! {
         key in "[a-z]{1,6}",
     ) {
         let value = json!({});
-        let mut predicate = HashMap::new();
+        let mut predicate = MatchPredicate::new();
         predicate.insert(key, MatchEntry::Condition(MatchCondition {
             exists: Some(true),
             ..empty_match_condition()
@@ -115,7 +115,7 @@ proptest! {
         obj.insert(key.clone(), json!(val));
         let value = Value::Object(obj);
 
-        let mut predicate = HashMap::new();
+        let mut predicate = MatchPredicate::new();
         predicate.insert(key, MatchEntry::Condition(MatchCondition {
             exists: Some(false),
             ..empty_match_condition()
@@ -129,13 +129,13 @@ proptest! {
         let value = json!({"x": a, "y": b});
 
         // Both match
-        let mut pred_match = HashMap::new();
+        let mut pred_match = MatchPredicate::new();
         pred_match.insert("x".to_string(), MatchEntry::Scalar(json!(a)));
         pred_match.insert("y".to_string(), MatchEntry::Scalar(json!(b)));
         prop_assert!(evaluate_predicate(&pred_match, &value));
 
         // One wrong → false
-        let mut pred_wrong = HashMap::new();
+        let mut pred_wrong = MatchPredicate::new();
         pred_wrong.insert("x".to_string(), MatchEntry::Scalar(json!(a)));
         pred_wrong.insert("y".to_string(), MatchEntry::Scalar(json!(b + 1)));
         let expected = false; // b != b + 1 for any b in -50..50
@@ -153,7 +153,7 @@ proptest! {
             contains: Some(substring.clone()),
             ..empty_match_condition()
         };
-        let mut predicate = HashMap::new();
+        let mut predicate = MatchPredicate::new();
         predicate.insert("field".to_string(), MatchEntry::Condition(cond.clone()));
 
         let cond_result = evaluate_condition(
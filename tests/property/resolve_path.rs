@@ -8,7 +8,7 @@ fn arb_json(depth: u32) -> impl Strategy<Value = Value> {
         Just(Value::Null),
         any::<bool>().prop_map(Value::Bool),
         any::<i64>().prop_map(|i| json!(i)),
-        "[a-z]{1,8}".prop_map(|s| Value::String(s)),
+        "[a-z]{1,8}".prop_map(Value::String),
     ];
 
     leaf.prop_recursive(depth, 64, 8, |inner| {
@@ -59,10 +59,10 @@ fn extract_wildcard_paths(value: &Value, prefix: &str, paths: &mut Vec<String>,
                 let wildcard_path = format!("{}[*]", path);
                 paths.push(wildcard_path.clone());
                 // Recurse into array elements for deeper paths
-                if let Some(arr) = child.as_array() {
-                    if let Some(first) = arr.first() {
-                        extract_wildcard_paths(first, &wildcard_path, paths, max_depth - 1);
-                    }
+                if let Some(arr) = child.as_array()
+                    && let Some(first) = arr.first()
+                {
+                    extract_wildcard_paths(first, &wildcard_path, paths, max_depth - 1);
                 }
             } else {
                 extract_wildcard_paths(child, &path, paths, max_depth - 1);
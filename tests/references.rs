@@ -0,0 +1,103 @@
+#![cfg(feature = "validate")]
+
+use oatf::references::{ReferenceKind, enrich_references};
+use oatf::{Reference, parse, validate};
+
+const DOC: &str = r#"
+oatf: "0.1"
+attack:
+  references:
+    - url: https://nvd.nist.gov/vuln/detail/cve-2025-49596
+    - url: https://www.cve.org/CVERecord?id=CVE-2025-6514
+      title: "  mcp-remote OS command injection (cve-2025-6514) "
+    - url: https://github.com/advisories/GHSA-8Q2F-Q5G9-5X7R
+    - url: https://security.example.com/.well-known/csaf/2025/cve-2025-49596.json
+    - url: https://example.com/blog/tool-poisoning
+      title: Tool poisoning writeup
+    - url: https://example.com/advisory
+      title: See CVE-25-123 and CVE-2025-12
+  execution:
+    mode: mcp_server
+    state: {}
+"#;
+
+fn reference(url: &str) -> Reference {
+    Reference {
+        url: url.to_string(),
+        title: None,
+        description: None,
+    }
+}
+
+#[test]
+fn classifies_references() {
+    assert_eq!(
+        reference("https://nvd.nist.gov/vuln/detail/cve-2025-49596").kind(),
+        ReferenceKind::Cve("CVE-2025-49596".to_string())
+    );
+    assert_eq!(
+        reference("https://github.com/advisories/GHSA-8Q2F-Q5G9-5X7R").kind(),
+        ReferenceKind::Ghsa("GHSA-8q2f-q5g9-5x7r".to_string())
+    );
+    let csaf =
+        reference("https://access.example.com/data/csaf/v2/advisories/2025/rhsa-2025_1.json");
+    assert_eq!(csaf.kind(), ReferenceKind::Csaf);
+    assert_eq!(
+        reference("https://example.com/csaf").kind(),
+        ReferenceKind::Other
+    );
+    assert_eq!(
+        reference("https://example.com/CVE-1990-1234").kind(),
+        ReferenceKind::Other
+    );
+}
+
+#[test]
+fn collects_cves_and_enriches_titles() {
+    let doc = parse(DOC).unwrap();
+    assert_eq!(doc.attack.cves(), ["CVE-2025-49596", "CVE-2025-6514"]);
+
+    let doc = enrich_references(doc);
+    let titles: Vec<_> = doc
+        .attack
+        .references
+        .as_ref()
+        .unwrap()
+        .iter()
+        .map(|r| r.title.as_deref())
+        .collect();
+    assert_eq!(
+        titles,
+        [
+            Some("CVE-2025-49596"),
+            Some("mcp-remote OS command injection (CVE-2025-6514)"),
+            Some("GHSA-8q2f-q5g9-5x7r"),
+            Some("CSAF advisory for CVE-2025-49596"),
+            Some("Tool poisoning writeup"),
+            Some("See CVE-25-123 and CVE-2025-12"),
+        ]
+    );
+    let again = enrich_references(doc.clone());
+    assert_eq!(
+        serde_json::to_value(&again.attack.references).unwrap(),
+        serde_json::to_value(&doc.attack.references).unwrap()
+    );
+}
+
+#[test]
+fn w008_malformed_cve_ids() {
+    let doc = parse(DOC).unwrap();
+    let warnings: Vec<_> = validate(&doc)
+        .warnings
+        .into_iter()
+        .filter(|w| w.code == "W-008")
+        .collect();
+    assert_eq!(warnings.len(), 2);
+    assert!(
+        warnings
+            .iter()
+            .all(|w| w.path.as_deref() == Some("attack.references[5].title"))
+    );
+    assert!(warnings[0].message.contains("'CVE-25-123'"));
+    assert!(warnings[1].message.contains("'CVE-2025-12'"));
+}
@@ -0,0 +1,116 @@
+#![cfg(feature = "validate")]
+
+use oatf::enums::RegexDialect;
+use oatf::parse::parse;
+use oatf::primitives::check_regex_dialect;
+use oatf::{validate, validate_regex_dialect};
+
+fn portable(pattern: &str) -> Result<(), String> {
+    check_regex_dialect(pattern, RegexDialect::Portable)
+}
+
+#[test]
+fn native_dialect_accepts_everything() {
+    assert!(check_regex_dialect(r"(a)\1(?=b)[a-z&&[^x]]", RegexDialect::Native).is_ok());
+}
+
+#[test]
+fn portable_dialect_accepts_shared_syntax() {
+    for pattern in [
+        r"(?i)ignore\s+(all|previous)\s+instructions",
+        r"^[[:alpha:]_][\w-]*$",
+        r"[^]a-z\-]+",
+        r"(?P<host>[a-z0-9.-]+)\.(?:com|net){1,3}",
+        r"\bsecret\b|\p{Greek}+|\x{263a}",
+        r"a*?b+?c??d{2,1000}",
+        r"\{literal\}|a{,}",
+        r"\x{2000}|\u{2028}|\U{1F600}|[\x{2000}-\x{200b}]",
+        r"\x{41}+\p{Lu}*?\P{Greek}?",
+    ] {
+        assert_eq!(portable(pattern), Ok(()), "{}", pattern);
+    }
+}
+
+#[test]
+fn portable_dialect_rejects_engine_specific_syntax() {
+    for (pattern, reason) in [
+        (r"(a)\1", "backreferences"),
+        (r"(?<q>')\k<q>", "backreferences"),
+        (r"foo(?=bar)", "lookaround"),
+        (r"(?<!https:)//", "lookaround"),
+        (r"(?>a+)b", "atomic groups"),
+        (r"a++b", "possessive quantifiers"),
+        (r"a{2}+", "possessive quantifiers"),
+        (r"\x{41}++", "possessive quantifiers"),
+        (r"[a-z&&[^aeiou]]", "set operations"),
+        (r"[a-z--b]", "set operations"),
+        (r"[a[bc]]", "nested character classes"),
+        (r"\b{start}word", "word boundaries"),
+        (r"\<word\>", "word boundaries"),
+        (r"(?mR)^line$", "CRLF flag"),
+        (r"(a|(?1))", "recursive"),
+        (r"x{1001}", "limit of 1000"),
+    ] {
+        let err = portable(pattern).expect_err(pattern);
+        assert!(err.contains(reason), "{}: {}", pattern, err);
+    }
+}
+
+fn doc_with_regexes(indicator_regex: &str, trigger_regex: &str) -> oatf::Document {
+    parse(&format!(
+        r#"
+oatf: "0.1"
+attack:
+  execution:
+    mode: mcp_server
+    phases:
+      - name: wait
+        state:
+          tools:
+            - name: search
+              description: "Searches"
+              inputSchema: {{type: object}}
+        trigger:
+          event: tools/call
+          match:
+            params.name:
+              regex: '{}'
+      - name: done
+  indicators:
+    - surface: tool_description
+      pattern:
+        regex: '{}'
+"#,
+        trigger_regex, indicator_regex
+    ))
+    .expect("parse should succeed")
+}
+
+#[test]
+fn v013_reports_dialect_violations_under_portable_profile() {
+    let doc = doc_with_regexes(r"[a-z&&[^x]]+", r"^search$");
+    assert!(validate(&doc).is_valid());
+    assert!(validate_regex_dialect(&doc, RegexDialect::Native).is_valid());
+
+    let result = validate_regex_dialect(&doc, RegexDialect::Portable);
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].rule, "V-013");
+    assert_eq!(result.errors[0].path, "attack.indicators[0].pattern.regex");
+    assert!(result.errors[0].message.contains("portable dialect"));
+}
+
+#[test]
+fn dialect_reason_replaces_generic_compile_error() {
+    let doc = doc_with_regexes(r"x", r"^(?!admin)");
+    let generic = validate(&doc);
+    assert_eq!(generic.errors.len(), 1);
+    assert!(generic.errors[0].message.starts_with("invalid regex"));
+
+    let result = validate_regex_dialect(&doc, RegexDialect::Portable);
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(
+        result.errors[0].path,
+        "attack.execution.phases[0].trigger.match.params.name.regex"
+    );
+    assert!(result.errors[0].message.contains("lookaround"));
+}
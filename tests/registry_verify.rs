@@ -0,0 +1,104 @@
+#![cfg(feature = "registry-verify")]
+
+use oatf::RegistryErrorKind;
+use oatf::spec::spec_info;
+use oatf::verify_registries;
+use serde_json::{Value, json};
+use std::fs;
+use std::path::PathBuf;
+
+/// A specification checkout whose registries mirror this build, with
+/// `edit` applied to (modes, events, surfaces).
+fn spec_dir(name: &str, edit: impl FnOnce(&mut Value, &mut Value, &mut Value)) -> PathBuf {
+    let info = spec_info();
+    let mut modes = json!(info.modes);
+    let mut events = json!({"events": info.events.iter().map(|e| json!({
+        "event": e.name,
+        "modes": e.modes,
+    })).collect::<Vec<_>>()});
+    // Surfaces in reverse order, keyed by `name`: order and key spelling do
+    // not matter.
+    let mut surfaces = Value::Array(
+        info.surfaces
+            .iter()
+            .rev()
+            .map(|s| json!({"name": s.name, "protocol": s.protocol, "default_target": s.default_target}))
+            .collect(),
+    );
+    edit(&mut modes, &mut events, &mut surfaces);
+
+    let dir = std::env::temp_dir().join(format!("oatf-registries-{}-{}", name, std::process::id()));
+    let registries = dir.join("registries");
+    fs::create_dir_all(&registries).unwrap();
+    for (file, value) in [("modes", modes), ("events", events), ("surfaces", surfaces)] {
+        fs::write(
+            registries.join(format!("{}.yaml", file)),
+            serde_json::to_string_pretty(&value).unwrap(),
+        )
+        .unwrap();
+    }
+    dir
+}
+
+#[test]
+fn matching_registries_are_consistent() {
+    let dir = spec_dir("match", |_, _, _| {});
+    let report = verify_registries(&dir).unwrap();
+    assert!(report.is_consistent(), "{:?}", report.drift);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn drift_is_reported_per_entry() {
+    let dir = spec_dir("drift", |modes, events, surfaces| {
+        modes.as_array_mut().unwrap().push(json!("acp_server"));
+        let events = events["events"].as_array_mut().unwrap();
+        let call = events
+            .iter_mut()
+            .find(|e| e["event"] == "tools/call")
+            .unwrap();
+        call["modes"] = json!(["mcp_server"]);
+        events.retain(|e| e["event"] != "initialize");
+        let surfaces = surfaces.as_array_mut().unwrap();
+        let description = surfaces
+            .iter_mut()
+            .find(|s| s["name"] == "tool_description")
+            .unwrap();
+        description["default_target"] = json!("tools[*].desc");
+        description["origin"] = json!("client");
+    });
+    let report = verify_registries(&dir).unwrap();
+    let drift: Vec<_> = report
+        .drift
+        .iter()
+        .map(|d| format!("{} {}: {}", d.registry, d.entry, d.message))
+        .collect();
+    assert_eq!(
+        drift,
+        vec![
+            "modes acp_server: in the specification but not this build",
+            "events initialize: in this build but not the specification",
+            "events tools/call: valid modes differ: build [mcp_client, mcp_server], specification [mcp_server]",
+            "surfaces tool_description: default_target differs: build 'tools[*].description', specification 'tools[*].desc'",
+            "surfaces tool_description: origin differs: build 'server', specification 'client'",
+        ]
+    );
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn unreadable_registries_are_errors() {
+    let missing = std::env::temp_dir().join("oatf-registries-missing");
+    assert_eq!(
+        verify_registries(&missing).unwrap_err().kind,
+        RegistryErrorKind::Io
+    );
+
+    let dir = spec_dir("malformed", |_, events, _| {
+        *events = json!([{"event": "tools/call"}]);
+    });
+    let err = verify_registries(&dir).unwrap_err();
+    assert_eq!(err.kind, RegistryErrorKind::Parse);
+    assert!(err.message.contains("'modes'"));
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,184 @@
+#![cfg(feature = "evaluate")]
+
+use oatf::Document;
+use oatf::enums::AttackResult;
+use oatf::evaluate::compare_runs;
+use oatf::evaluate::regression::{DetectionChange, RecordedEvent, RecordedSession};
+use oatf::types::ProtocolEvent;
+use serde_json::json;
+
+fn attack(id: &str, indicator: &str) -> Document {
+    let yaml = format!(
+        r#"
+oatf: "0.1"
+attack:
+  id: {}
+  execution:
+    mode: mcp_server
+    state:
+      tools: []
+  indicators:
+    - surface: tool_arguments
+{}
+"#,
+        id, indicator
+    );
+    oatf::load(&yaml).unwrap().document
+}
+
+fn contains(id: &str, text: &str) -> Document {
+    attack(
+        id,
+        &format!(
+            "      pattern:\n        target: arguments.path\n        condition:\n          contains: \"{}\"",
+            text
+        ),
+    )
+}
+
+fn repeated_reads(id: &str, threshold: u32) -> Document {
+    attack(
+        id,
+        &format!(
+            "      frequency:\n        event: tools/call:read_file\n        window: 60s\n        threshold: {}",
+            threshold
+        ),
+    )
+}
+
+/// A session reading `paths`, one second apart.
+fn session(id: &str, paths: &[&str]) -> RecordedSession {
+    RecordedSession {
+        id: id.to_string(),
+        events: paths
+            .iter()
+            .enumerate()
+            .map(|(i, path)| RecordedEvent {
+                at_ms: i as u64 * 1000,
+                event: ProtocolEvent {
+                    event_type: "tools/call".to_string(),
+                    qualifier: Some("read_file".to_string()),
+                    content: json!({"name": "read_file", "arguments": {"path": path}}),
+                    origin: None,
+                },
+            })
+            .collect(),
+    }
+}
+
+fn change(
+    session_id: &str,
+    attack_id: &str,
+    old: Option<AttackResult>,
+    new: Option<AttackResult>,
+    matched: &[&str],
+) -> DetectionChange {
+    DetectionChange {
+        session_id: session_id.to_string(),
+        attack_id: attack_id.to_string(),
+        old,
+        new,
+        matched_indicators: matched.iter().map(|s| s.to_string()).collect(),
+    }
+}
+
+// ─── Differences ────────────────────────────────────────────────────────────
+
+#[test]
+fn reports_newly_missed_and_newly_caught_detections() {
+    let old = [
+        contains("OATF-060", "passwd"),
+        repeated_reads("OATF-061", 3),
+        contains("OATF-062", "shadow"),
+    ];
+    let new = [
+        contains("OATF-060", "passwd.bak"),
+        repeated_reads("OATF-061", 2),
+        contains("OATF-063", "id_rsa"),
+    ];
+    let corpus = [
+        session("passwd", &["/etc/passwd"]),
+        session("two-reads", &["a", "b"]),
+        session("three-reads", &["a", "b", "c"]),
+        session("shadow", &["/etc/shadow"]),
+        session("ssh", &["~/.ssh/id_rsa"]),
+    ];
+
+    let report = compare_runs(&old, &new, &corpus);
+    assert_eq!(report.sessions, 5);
+    assert_eq!(report.detected_by_both, 1);
+    assert!(report.has_regressions());
+    use AttackResult::{Exploited, NotExploited};
+    assert_eq!(
+        report.newly_missed,
+        [
+            change(
+                "passwd",
+                "OATF-060",
+                Some(Exploited),
+                Some(NotExploited),
+                &["OATF-060-01"]
+            ),
+            change(
+                "shadow",
+                "OATF-062",
+                Some(Exploited),
+                None,
+                &["OATF-062-01"]
+            ),
+        ]
+    );
+    assert_eq!(
+        report.newly_caught,
+        [
+            change(
+                "two-reads",
+                "OATF-061",
+                Some(NotExploited),
+                Some(Exploited),
+                &["OATF-061-01"]
+            ),
+            change("ssh", "OATF-063", None, Some(Exploited), &["OATF-063-01"]),
+        ]
+    );
+}
+
+#[test]
+fn identical_libraries_have_no_differences() {
+    let library = [
+        contains("OATF-060", "passwd"),
+        repeated_reads("OATF-061", 2),
+    ];
+    let corpus = [
+        session("passwd", &["/etc/passwd", "/etc/passwd"]),
+        session("benign", &["notes.txt"]),
+        session("empty", &[]),
+    ];
+    let report = compare_runs(&library, &library, &corpus);
+    assert_eq!(report.detected_by_both, 2);
+    assert!(report.newly_missed.is_empty());
+    assert!(report.newly_caught.is_empty());
+    assert!(!report.has_regressions());
+}
+
+// ─── Corpus ─────────────────────────────────────────────────────────────────
+
+#[test]
+fn sessions_load_from_json() {
+    let json = r#"{
+        "id": "recorded-1",
+        "events": [
+            {"at_ms": 0, "event_type": "tools/call", "qualifier": "read_file",
+             "content": {"name": "read_file", "arguments": {"path": "/etc/passwd"}}}
+        ]
+    }"#;
+    let recorded: RecordedSession = serde_json::from_str(json).unwrap();
+    assert_eq!(
+        recorded.events[0].event.qualifier.as_deref(),
+        Some("read_file")
+    );
+
+    let report = compare_runs(&[contains("OATF-060", "passwd")], &[], &[recorded]);
+    assert_eq!(report.newly_missed.len(), 1);
+    assert_eq!(report.newly_missed[0].session_id, "recorded-1");
+}
@@ -0,0 +1,250 @@
+#![cfg(feature = "evaluate")]
+
+use oatf::clock::MockClock;
+use oatf::engine::actors::{ActorInput, ActorOutput, ActorSystem};
+use oatf::engine::{McpClient, Transport};
+use oatf::primitives::evaluate_requires;
+use oatf::{EngineError, MatchPredicate};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+
+const CLIENT_DOC: &str = r#"
+oatf: "0.1"
+attack:
+  execution:
+    mode: mcp_client
+    phases:
+      - name: login
+        state:
+          tools:
+            - name: login
+        extractors:
+          - name: token
+            source: response
+            type: regex
+            selector: "session=([a-z0-9]+)"
+        trigger:
+          event: tools/call:login
+      - name: exploit
+        requires:
+          token: {exists: true}
+        state:
+          tools:
+            - name: use_token
+              arguments:
+                token: "{{token}}"
+        trigger:
+          event: tools/call:use_token
+      - name: retry
+        requires:
+          token: {exists: false}
+        state:
+          tools:
+            - name: probe
+        trigger:
+          event: tools/call:probe
+      - name: done
+"#;
+
+/// Answers `login` with `text`, and every other tool call with "ok".
+struct LoginServer {
+    text: &'static str,
+    calls: Vec<String>,
+}
+
+impl Transport for LoginServer {
+    fn request(&mut self, method: &str, params: Value) -> Result<Value, EngineError> {
+        let name = params["name"].as_str().unwrap_or_default().to_string();
+        let text = if name == "login" { self.text } else { "ok" };
+        if method == "tools/call" {
+            self.calls.push(name);
+        }
+        Ok(json!({"content": [{"type": "text", "text": text}]}))
+    }
+
+    fn notify(&mut self, _method: &str, _params: Value) -> Result<(), EngineError> {
+        Ok(())
+    }
+}
+
+fn run_client(text: &'static str) -> (oatf::engine::ExecutionReport, Vec<String>) {
+    let doc = oatf::load(CLIENT_DOC).unwrap().document;
+    let server = LoginServer {
+        text,
+        calls: Vec::new(),
+    };
+    let mut client = McpClient::new(server).with_clock(MockClock::default());
+    let report = client.run(&doc).unwrap();
+    (report, client.into_transport().calls)
+}
+
+fn v061(phases: &str) -> Vec<String> {
+    let input = format!(
+        r#"
+oatf: "0.1"
+attack:
+  execution:
+    actors:
+      - name: client
+        mode: mcp_client
+        phases:
+          - name: login
+            extractors:
+              - name: token
+                source: response
+                type: json_path
+                selector: "$.token"
+            trigger:
+              event: tools/call:login
+{}
+          - name: done
+      - name: server
+        mode: mcp_server
+        phases:
+          - state:
+              tools: []
+"#,
+        phases
+    );
+    oatf::validate(&oatf::parse(&input).unwrap())
+        .errors
+        .into_iter()
+        .filter(|e| e.rule == "V-061" || e.rule == "V-013")
+        .map(|e| format!("{} {}: {}", e.rule, e.path, e.message))
+        .collect()
+}
+
+// ─── Evaluation ─────────────────────────────────────────────────────────────
+
+#[test]
+fn requires_checks_extracted_values() {
+    let requires: MatchPredicate = serde_json::from_value(json!({
+        "token": {"exists": true},
+        "harvester.role": {"starts_with": "adm"},
+    }))
+    .unwrap();
+    let values = |pairs: &[(&str, &str)]| -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    };
+
+    assert!(evaluate_requires(None, &HashMap::new()));
+    assert!(evaluate_requires(
+        Some(&requires),
+        &values(&[("token", "abc"), ("harvester.role", "admin")])
+    ));
+    assert!(!evaluate_requires(
+        Some(&requires),
+        &values(&[("token", "abc"), ("harvester.role", "user")])
+    ));
+    assert!(!evaluate_requires(
+        Some(&requires),
+        &values(&[("harvester.role", "admin")])
+    ));
+}
+
+// ─── Engine ─────────────────────────────────────────────────────────────────
+
+#[test]
+fn client_branches_on_whether_a_value_was_extracted() {
+    let (report, calls) = run_client("session=abc123");
+    assert!(report.completed);
+    assert_eq!(calls, ["login", "use_token"]);
+    assert_eq!(report.skipped, [2]);
+    let entered: Vec<_> = report.phases.iter().map(|p| p.name.as_deref()).collect();
+    assert_eq!(entered, [Some("login"), Some("exploit"), Some("done")]);
+
+    let (report, calls) = run_client("denied");
+    assert!(report.completed);
+    assert_eq!(calls, ["login", "probe"]);
+    assert_eq!(report.skipped, [1]);
+}
+
+#[test]
+fn actor_skips_phases_whose_requires_fail() {
+    let doc = oatf::load(CLIENT_DOC).unwrap().document;
+    let mut system = ActorSystem::new(&doc).unwrap();
+    system.start();
+    let outputs = system
+        .deliver(
+            "default",
+            ActorInput::Response {
+                method: "tools/call".to_string(),
+                params: json!({"name": "login"}),
+                result: json!({"content": [{"type": "text", "text": "denied"}]}),
+            },
+        )
+        .unwrap();
+    let outputs: Vec<_> = outputs.into_iter().map(|o| o.output).collect();
+    assert!(outputs.contains(&ActorOutput::PhaseSkipped {
+        index: 1,
+        name: Some("exploit".to_string())
+    }));
+    assert!(outputs.contains(&ActorOutput::PhaseEntered {
+        index: 2,
+        name: Some("retry".to_string())
+    }));
+    assert_eq!(system.actor("default").unwrap().phase(), 2);
+}
+
+#[test]
+fn skipping_the_last_phase_completes_the_actor() {
+    let doc = oatf::load(&CLIENT_DOC.replace(
+        "      - name: done\n",
+        "      - name: done\n        requires:\n          token: {exists: true}\n",
+    ))
+    .unwrap()
+    .document;
+    let mut system = ActorSystem::new(&doc).unwrap();
+    system.start();
+    for tool in ["login", "probe"] {
+        system
+            .deliver(
+                "default",
+                ActorInput::Response {
+                    method: "tools/call".to_string(),
+                    params: json!({"name": tool}),
+                    result: json!({"content": []}),
+                },
+            )
+            .unwrap();
+    }
+    assert!(system.is_complete());
+}
+
+// ─── Validation ─────────────────────────────────────────────────────────────
+
+#[test]
+fn requires_must_name_earlier_extractors() {
+    assert!(
+        v061("          - name: use\n            requires:\n              token: {exists: true}\n            trigger:\n              event: tools/call")
+            .is_empty()
+    );
+    assert_eq!(
+        v061(
+            "          - name: use\n            requires:\n              session: {exists: true}\n              server.token: abc\n              client.token: abc\n            trigger:\n              event: tools/call"
+        ),
+        [
+            "V-061 attack.execution.actors[0].phases[1].requires.client.token: 'client.token' refers to the phase's own actor; use 'token'",
+            "V-061 attack.execution.actors[0].phases[1].requires.server.token: 'server.token' is not extracted by actor 'server'",
+            "V-061 attack.execution.actors[0].phases[1].requires.session: 'session' is not extracted by an earlier phase of actor 'client'",
+        ]
+    );
+    assert_eq!(
+        v061(
+            "          - name: use\n            requires: {}\n            trigger:\n              event: tools/call"
+        ),
+        ["V-061 attack.execution.actors[0].phases[1].requires: requires must not be empty"]
+    );
+    let errors = v061(
+        "          - name: use\n            requires:\n              token: {regex: \"(\"}\n            trigger:\n              event: tools/call",
+    );
+    assert_eq!(errors.len(), 1);
+    assert!(
+        errors[0].starts_with("V-013 attack.execution.actors[0].phases[1].requires.token.regex"),
+        "{:?}",
+        errors
+    );
+}
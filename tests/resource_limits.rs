@@ -0,0 +1,123 @@
+#![cfg(feature = "validate")]
+
+use oatf::{DiagnosticSeverity, ResourceLimits, validate_resource_limits};
+
+fn document(tools: usize, description: &str) -> oatf::Document {
+    let tools: String = (0..tools)
+        .map(|i| {
+            format!(
+                "            - name: tool_{}\n              description: \"{}\"\n              inputSchema: {{type: object}}\n",
+                i, description
+            )
+        })
+        .collect();
+    oatf::parse(&format!(
+        r#"
+oatf: "0.1"
+attack:
+  execution:
+    mode: mcp_server
+    phases:
+      - name: lure
+        state:
+          tools:
+{}        trigger:
+          event: tools/call
+      - name: payload
+        state:
+          tools:
+            - name: nested
+              description: Nested schema
+              inputSchema: {{type: object, properties: {{a: {{type: object, properties: {{b: {{type: string}}}}}}}}}}
+"#,
+        tools
+    ))
+    .unwrap()
+}
+
+fn v059(doc: &oatf::Document, limits: &ResourceLimits) -> Vec<String> {
+    validate_resource_limits(doc, limits)
+        .errors
+        .into_iter()
+        .filter(|e| e.rule == "V-059")
+        .map(|e| e.path)
+        .collect()
+}
+
+#[test]
+fn default_limits_accept_ordinary_documents() {
+    let doc = document(3, "Reads a file");
+    let result = validate_resource_limits(&doc, &ResourceLimits::default());
+    assert!(result.is_valid(), "{:?}", result.errors);
+    assert_eq!(result.errors.len(), oatf::validate(&doc).errors.len());
+}
+
+#[test]
+fn each_limit_reports_the_offending_state() {
+    let doc = document(3, &"x".repeat(40));
+    let unlimited = ResourceLimits {
+        max_state_bytes: None,
+        max_tools: None,
+        max_string_length: None,
+        max_depth: None,
+        severity: DiagnosticSeverity::Error,
+    };
+    assert!(v059(&doc, &unlimited).is_empty());
+
+    let tools = ResourceLimits {
+        max_tools: Some(2),
+        ..unlimited.clone()
+    };
+    assert_eq!(
+        v059(&doc, &tools),
+        ["attack.execution.phases[0].state.tools"]
+    );
+
+    let strings = ResourceLimits {
+        max_string_length: Some(32),
+        ..unlimited.clone()
+    };
+    assert_eq!(
+        v059(&doc, &strings),
+        [
+            "attack.execution.phases[0].state.tools[0].description",
+            "attack.execution.phases[0].state.tools[1].description",
+            "attack.execution.phases[0].state.tools[2].description",
+        ]
+    );
+
+    let bytes = ResourceLimits {
+        max_state_bytes: Some(200),
+        ..unlimited.clone()
+    };
+    assert_eq!(v059(&doc, &bytes), ["attack.execution.phases[0].state"]);
+
+    let depth = ResourceLimits {
+        max_depth: Some(6),
+        ..unlimited
+    };
+    assert_eq!(v059(&doc, &depth), ["attack.execution.phases[1].state"]);
+}
+
+#[test]
+fn warning_severity_reports_w009_and_keeps_the_document_valid() {
+    let doc = document(5, "Reads a file");
+    let limits = ResourceLimits {
+        max_tools: Some(4),
+        severity: DiagnosticSeverity::Warning,
+        ..ResourceLimits::default()
+    };
+    let result = validate_resource_limits(&doc, &limits);
+    assert!(result.is_valid());
+    let warnings: Vec<_> = result
+        .warnings
+        .iter()
+        .filter(|w| w.code == "W-009")
+        .collect();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(
+        warnings[0].path.as_deref(),
+        Some("attack.execution.phases[0].state.tools")
+    );
+    assert!(warnings[0].message.contains("5 tools"));
+}
@@ -0,0 +1,263 @@
+#![cfg(feature = "validate")]
+
+use oatf::validate::rules::{NodeKind, Rule, RuleEngine, RunOptions};
+use oatf::{DiagnosticSeverity, RuleEngineErrorKind, ValidationError, ValidationResult};
+
+const DOC: &str = r#"
+oatf: "0.1"
+attack:
+  id: bad-id
+  severity: high
+  execution:
+    mode: mcp_server
+    phases:
+      - name: setup
+        state:
+          tools: []
+        trigger:
+          event: tools/call
+      - name: setup
+  indicators:
+    - id: OTHER-001-01
+      surface: tool_description
+      pattern:
+        regex: "("
+    - id: nope
+      surface: tool_arguments
+      expression:
+        cel: "1 +"
+"#;
+
+fn rules_of(result: &ValidationResult) -> Vec<&str> {
+    result.errors.iter().map(|e| e.rule.as_str()).collect()
+}
+
+fn author_rule(id: &'static str) -> Rule {
+    Rule::new(
+        id,
+        DiagnosticSeverity::Error,
+        &[NodeKind::Attack],
+        move |cx, out| {
+            if cx.document().attack.author.is_none() {
+                out.errors.push(ValidationError {
+                    rule: id.to_string(),
+                    spec_ref: String::new(),
+                    path: "attack.author".to_string(),
+                    message: "attack.author is required".to_string(),
+                    suggestion: None,
+                });
+            }
+        },
+    )
+}
+
+#[test]
+fn validate_runs_the_builtin_rules() {
+    let doc = oatf::parse(DOC).unwrap();
+    let result = oatf::validate(&doc);
+    let engine = RuleEngine::builtin().run(&doc, &RunOptions::default());
+    assert_eq!(result.errors, engine.errors);
+    assert_eq!(result.warnings, engine.warnings);
+    assert_eq!(RuleEngine::builtin().rules()[0].id, "W-001");
+    assert!(
+        RuleEngine::builtin()
+            .rules()
+            .iter()
+            .any(|rule| rule.id == "V-055")
+    );
+}
+
+#[test]
+fn indicator_ids_are_not_checked_against_an_invalid_attack_id() {
+    let doc = oatf::parse(DOC).unwrap();
+    let result = oatf::validate(&doc);
+    let rules = rules_of(&result);
+    assert!(rules.contains(&"V-023"));
+    assert!(!rules.contains(&"V-024"));
+
+    let fixed = oatf::parse(&DOC.replace("bad-id", "OATF-001")).unwrap();
+    let rules: Vec<_> = oatf::validate(&fixed)
+        .errors
+        .into_iter()
+        .filter(|e| e.rule == "V-024")
+        .collect();
+    assert_eq!(rules.len(), 2);
+}
+
+#[test]
+fn selective_runs() {
+    let doc = oatf::parse(DOC).unwrap();
+    let engine = RuleEngine::builtin();
+
+    let only = engine.run(
+        &doc,
+        &RunOptions {
+            rules: Some(vec!["V-011".to_string(), "V-013".to_string()]),
+            ..RunOptions::default()
+        },
+    );
+    assert_eq!(rules_of(&only), vec!["V-011", "V-013"]);
+
+    let phases = engine.run(
+        &doc,
+        &RunOptions {
+            targets: Some(vec![NodeKind::Phase]),
+            skip: vec!["V-008".to_string()],
+            ..RunOptions::default()
+        },
+    );
+    assert_eq!(rules_of(&phases), vec!["V-011"]);
+
+    // V-023 runs as V-024's prerequisite but is not reported.
+    let v024 = engine.run(
+        &doc,
+        &RunOptions {
+            rules: Some(vec!["V-024".to_string()]),
+            ..RunOptions::default()
+        },
+    );
+    assert!(v024.errors.is_empty());
+}
+
+#[test]
+fn fail_fast_stops_at_the_first_failing_rule() {
+    let doc = oatf::parse(DOC).unwrap();
+    let result = RuleEngine::builtin().run(
+        &doc,
+        &RunOptions {
+            fail_fast: true,
+            ..RunOptions::default()
+        },
+    );
+    assert_eq!(rules_of(&result), vec!["V-011"]);
+}
+
+#[test]
+fn parallel_runs_report_in_rule_order() {
+    let doc = oatf::parse(DOC).unwrap();
+    let engine = RuleEngine::builtin();
+    let sequential = engine.run(&doc, &RunOptions::default());
+    let parallel = engine.run(
+        &doc,
+        &RunOptions {
+            parallel: true,
+            ..RunOptions::default()
+        },
+    );
+    assert_eq!(sequential.errors, parallel.errors);
+    assert_eq!(sequential.warnings, parallel.warnings);
+}
+
+#[test]
+fn registered_rules_run_after_their_prerequisites() {
+    let doc = oatf::parse(DOC).unwrap();
+    let mut engine = RuleEngine::new();
+    engine
+        .register(author_rule("X-002").requires(&["X-001"]))
+        .unwrap();
+    engine.register(author_rule("X-001")).unwrap();
+    assert_eq!(
+        rules_of(&engine.run(&doc, &RunOptions::default())),
+        vec!["X-001"]
+    );
+
+    let mut builtin = RuleEngine::builtin();
+    builtin.register(author_rule("X-001")).unwrap();
+    let result = builtin.run(&doc, &RunOptions::default());
+    assert_eq!(result.errors.last().unwrap().rule, "X-001");
+}
+
+#[test]
+fn registration_errors() {
+    let mut engine = RuleEngine::builtin();
+    let duplicate = engine.register(author_rule("V-001")).unwrap_err();
+    assert_eq!(duplicate.kind, RuleEngineErrorKind::DuplicateRule);
+    assert_eq!(duplicate.rule, "V-001");
+
+    let mut engine = RuleEngine::new();
+    engine
+        .register(author_rule("X-001").requires(&["X-002"]))
+        .unwrap();
+    let cycle = engine
+        .register(author_rule("X-002").requires(&["X-001"]))
+        .unwrap_err();
+    assert_eq!(cycle.kind, RuleEngineErrorKind::PrerequisiteCycle);
+    assert_eq!(engine.rules().len(), 1);
+}
+
+// ─── Revalidation ───────────────────────────────────────────────────────────
+
+#[test]
+fn rules_declare_the_paths_they_read() {
+    let engine = RuleEngine::builtin();
+    let rule = |id: &str| engine.rules().iter().find(|r| r.id == id).unwrap();
+
+    assert!(rule("V-013").is_affected_by("attack.indicators[1].pattern.regex"));
+    assert!(rule("V-013").is_affected_by("attack.execution.phases[0].trigger"));
+    assert!(!rule("V-013").is_affected_by("attack.severity"));
+    assert!(rule("V-011").is_affected_by("attack.execution.phases[3]"));
+    assert!(rule("V-011").is_affected_by("attack.execution.actors[0].phases[1].name"));
+    assert!(rule("V-011").is_affected_by("attack.execution"));
+    assert!(!rule("V-011").is_affected_by("attack.indicators[0]"));
+    // Declared beyond its node kinds: V-024 checks indicator IDs against
+    // attack.id, and V-018 infers protocols from execution modes.
+    assert!(rule("V-024").is_affected_by("attack.id"));
+    assert!(rule("V-018").is_affected_by("attack.execution.mode"));
+    assert!(!rule("V-018").is_affected_by("attack.execution.phases[0].trigger"));
+    for rule in engine.rules() {
+        assert!(rule.is_affected_by(""), "{}", rule.id);
+        assert_eq!(
+            rule.is_affected_by("attack"),
+            rule.targets != [NodeKind::Document],
+            "{}",
+            rule.id
+        );
+    }
+}
+
+#[test]
+fn revalidate_runs_only_affected_rules() {
+    let doc = oatf::parse(DOC).unwrap();
+    let full = oatf::validate(&doc);
+
+    let indicator = oatf::validate::revalidate(&doc, &["attack.indicators[0].pattern.regex"]);
+    assert_eq!(rules_of(&indicator), vec!["V-013", "V-014"]);
+    assert!(indicator.errors.iter().all(|e| full.errors.contains(e)));
+
+    let phase = oatf::validate::revalidate(&doc, &["attack.execution.phases[1].name"]);
+    assert_eq!(rules_of(&phase), vec!["V-011"]);
+
+    let both = oatf::validate::revalidate(&doc, &["attack.id", "attack.execution.phases[1]"]);
+    // V-013 also checks trigger regexes, and reports its indicator findings.
+    assert_eq!(rules_of(&both), vec!["V-011", "V-013", "V-023"]);
+
+    let all = oatf::validate::revalidate(&doc, &[""]);
+    assert_eq!(all.errors, full.errors);
+    assert_eq!(all.warnings, full.warnings);
+    assert!(
+        oatf::validate::revalidate::<&str>(&doc, &[])
+            .errors
+            .is_empty()
+    );
+}
+
+#[test]
+fn changed_paths_select_custom_rules() {
+    let doc = oatf::parse(DOC).unwrap();
+    let mut engine = RuleEngine::new();
+    engine
+        .register(author_rule("X-001").watching(&["attack.extensions"]))
+        .unwrap();
+    let run = |path: &str| {
+        engine.run(
+            &doc,
+            &RunOptions {
+                changed_paths: Some(vec![path.to_string()]),
+                ..RunOptions::default()
+            },
+        )
+    };
+    assert_eq!(rules_of(&run("attack.author")), vec!["X-001"]);
+    assert_eq!(rules_of(&run("attack.extensions.owner")), vec!["X-001"]);
+    assert!(run("attack.indicators").errors.is_empty());
+}
@@ -0,0 +1,119 @@
+use oatf::select::FieldSelection;
+use oatf::sink::{Stdout, VerdictSink};
+use oatf::{AttackVerdict, SelectionErrorKind};
+use serde_json::json;
+
+fn verdict() -> AttackVerdict {
+    serde_json::from_value(json!({
+        "attack_id": "OATF-001",
+        "result": "exploited",
+        "indicator_verdicts": [
+            {
+                "indicator_id": "OATF-001-01",
+                "result": "matched",
+                "evidence": "said \"cat ~/.ssh/id_rsa\"\nthen stopped",
+                "captures": {"path": "~/.ssh/id_rsa"}
+            },
+            {"indicator_id": "OATF-001-02", "result": "not_matched"}
+        ],
+        "evaluation_summary": {"matched": 1, "not_matched": 1, "error": 0, "skipped": 0},
+        "source": "scanner"
+    }))
+    .unwrap()
+}
+
+fn select(input: &str) -> FieldSelection {
+    input.parse().unwrap()
+}
+
+#[test]
+fn to_json_with_keeps_only_selected_fields() {
+    let fields = select("attack_id result indicator_verdicts { indicator_id result } timestamp");
+    assert_eq!(
+        verdict().to_json_with(&fields),
+        json!({
+            "attack_id": "OATF-001",
+            "result": "exploited",
+            "indicator_verdicts": [
+                {"indicator_id": "OATF-001-01", "result": "matched"},
+                {"indicator_id": "OATF-001-02", "result": "not_matched"}
+            ]
+        })
+    );
+
+    // Dotted paths and repeated fields merge into one selection.
+    assert_eq!(
+        select("evaluation_summary.matched, evaluation_summary { error }, result"),
+        select("result evaluation_summary { matched error }")
+    );
+    // A field without a nested selection keeps all of it.
+    assert_eq!(
+        select("evaluation_summary evaluation_summary.matched"),
+        select("evaluation_summary")
+    );
+    assert_eq!(
+        verdict().to_json_with(&FieldSelection::all()),
+        serde_json::to_value(verdict()).unwrap()
+    );
+}
+
+#[test]
+fn invalid_selections_are_rejected() {
+    let kind = |input: &str| FieldSelection::parse(input).unwrap_err().kind;
+    assert_eq!(kind(""), SelectionErrorKind::Syntax);
+    assert_eq!(kind("indicator_verdicts { }"), SelectionErrorKind::Syntax);
+    assert_eq!(
+        kind("indicator_verdicts { result"),
+        SelectionErrorKind::Syntax
+    );
+    assert_eq!(kind("result }"), SelectionErrorKind::Syntax);
+    assert_eq!(kind("result."), SelectionErrorKind::Syntax);
+    assert_eq!(kind("result | attack_id"), SelectionErrorKind::Syntax);
+    assert_eq!(kind("verdict"), SelectionErrorKind::UnknownField);
+    assert_eq!(kind("result.value"), SelectionErrorKind::UnknownField);
+
+    let error = FieldSelection::parse("indicator_verdicts { severity }").unwrap_err();
+    assert_eq!(
+        error.message,
+        "unknown verdict field 'indicator_verdicts.severity'"
+    );
+}
+
+#[test]
+fn compact_lines_flatten_and_quote() {
+    let fields = select("attack_id result indicator_verdicts { result evidence captures }");
+    assert_eq!(
+        verdict().to_compact_line(&fields),
+        r#"attack_id=OATF-001 result=exploited indicator_verdicts[0].result=matched indicator_verdicts[0].evidence="said \"cat ~/.ssh/id_rsa\"\nthen stopped" indicator_verdicts[0].captures.path=~/.ssh/id_rsa indicator_verdicts[1].result=not_matched"#
+    );
+    assert_eq!(
+        verdict().to_compact_line(&select("evaluation_summary")),
+        "evaluation_summary.matched=1 evaluation_summary.not_matched=1 \
+         evaluation_summary.error=0 evaluation_summary.skipped=0"
+    );
+}
+
+#[test]
+fn stdout_sink_writes_selected_fields() {
+    let mut sink = Stdout::to_writer(Vec::new()).select(select("attack_id result"));
+    sink.publish(&verdict()).unwrap();
+    let output = String::from_utf8(sink.into_inner()).unwrap();
+    assert_eq!(
+        output,
+        "{\"attack_id\":\"OATF-001\",\"result\":\"exploited\"}\n"
+    );
+
+    let mut sink = Stdout::to_writer(Vec::new())
+        .select(select("attack_id source"))
+        .compact();
+    sink.publish(&verdict()).unwrap();
+    let output = String::from_utf8(sink.into_inner()).unwrap();
+    assert_eq!(output, "attack_id=OATF-001 source=scanner\n");
+
+    let mut sink = Stdout::to_writer(Vec::new()).compact();
+    sink.publish(&verdict()).unwrap();
+    let output = String::from_utf8(sink.into_inner()).unwrap();
+    assert_eq!(output.lines().count(), 1);
+    assert!(output.starts_with("attack_id=OATF-001 result=exploited "));
+    assert!(output.ends_with(" source=scanner\n"));
+}
@@ -0,0 +1,176 @@
+#![cfg(all(feature = "validate", feature = "normalize"))]
+
+use oatf::catalog::Catalog;
+use oatf::enums::Tlp;
+use oatf::serialize::{SerializeOptions, serialize_with};
+use oatf::sharing::SharingPolicy;
+use oatf::{SharingErrorKind, parse};
+
+fn doc_source(metadata: &str) -> String {
+    format!(
+        r#"
+oatf: "0.1"
+attack:
+  id: OATF-070
+{}
+  execution:
+    mode: mcp_server
+    state:
+      tools: []
+  indicators:
+    - surface: tool_description
+      pattern:
+        contains: "test"
+"#,
+        metadata
+    )
+}
+
+fn doc(metadata: &str) -> oatf::Document {
+    parse(&doc_source(metadata)).unwrap()
+}
+
+// ─── Markings ───────────────────────────────────────────────────────────────
+
+#[test]
+fn license_and_distribution_round_trip() {
+    let document = doc("  license: CC-BY-4.0 OR Apache-2.0\n  distribution: amber");
+    assert_eq!(
+        document.attack.license.as_deref(),
+        Some("CC-BY-4.0 OR Apache-2.0")
+    );
+    assert_eq!(document.attack.distribution, Some(Tlp::Amber));
+    assert!(oatf::validate(&document).is_valid());
+
+    let yaml = oatf::serialize(&document).unwrap();
+    assert!(yaml.contains("distribution: amber"), "{}", yaml);
+    assert_eq!(parse(&yaml).unwrap().attack.distribution, Some(Tlp::Amber));
+
+    assert!(parse(&doc_source("  distribution: white")).is_err());
+}
+
+#[test]
+fn levels_are_ordered_by_restriction() {
+    assert!(Tlp::Clear < Tlp::Green && Tlp::Green < Tlp::Amber && Tlp::Amber < Tlp::Red);
+    assert_eq!(Tlp::Amber.to_string(), "TLP:AMBER");
+    assert_eq!(Tlp::Red.as_str(), "red");
+}
+
+// ─── Policy ─────────────────────────────────────────────────────────────────
+
+#[test]
+fn policy_permits_markings_up_to_its_limit() {
+    let policy = SharingPolicy::new(Tlp::Green);
+    assert!(policy.permits(&doc("  distribution: clear")));
+    assert!(policy.permits(&doc("  distribution: green")));
+    assert!(policy.permits(&doc("")));
+    assert!(!policy.permits(&doc("  distribution: amber")));
+
+    let error = policy.check(&doc("  distribution: red")).unwrap_err();
+    assert_eq!(error.kind, SharingErrorKind::Restricted);
+    assert_eq!(
+        error.message,
+        "attack 'OATF-070' is marked TLP:RED, above the sharing limit TLP:GREEN"
+    );
+
+    let strict = policy.unmarked_as(Tlp::Amber);
+    assert!(strict.permits(&doc("  distribution: green")));
+    assert_eq!(
+        strict.check(&doc("")).unwrap_err().message,
+        "attack 'OATF-070' is unmarked, treated as TLP:AMBER, above the sharing limit TLP:GREEN"
+    );
+}
+
+#[test]
+fn policy_reads_markings_from_sources() {
+    let policy = SharingPolicy::new(Tlp::Green);
+    assert!(policy.check_source(doc_source("").as_bytes()).is_ok());
+    assert!(
+        policy
+            .check_source(br#"{"oatf": "0.1", "attack": {"distribution": "clear"}}"#)
+            .is_ok()
+    );
+    assert_eq!(
+        policy
+            .check_source(doc_source("  distribution: amber").as_bytes())
+            .unwrap_err()
+            .kind,
+        SharingErrorKind::Restricted
+    );
+
+    // Indicator sets carry no attack, so they count as unmarked.
+    let set = "oatf: \"0.1\"\nindicators:\n  - surface: tool_description\n    pattern:\n      contains: x\n";
+    assert!(policy.check_source(set.as_bytes()).is_ok());
+    assert!(
+        policy
+            .unmarked_as(Tlp::Red)
+            .check_source(set.as_bytes())
+            .is_err()
+    );
+
+    for unreadable in [
+        doc_source("  distribution: white").into_bytes(),
+        b"- not a mapping".to_vec(),
+        vec![0xff, 0xfe],
+    ] {
+        assert_eq!(
+            policy.check_source(&unreadable).unwrap_err().kind,
+            SharingErrorKind::Unreadable
+        );
+    }
+}
+
+// ─── Sharing APIs ───────────────────────────────────────────────────────────
+
+#[test]
+fn serialize_for_sharing_refuses_restricted_documents() {
+    let options = SerializeOptions {
+        sharing: Some(SharingPolicy::new(Tlp::Amber)),
+        ..SerializeOptions::default()
+    };
+    assert!(serialize_with(&doc("  distribution: amber"), &options).is_ok());
+    let error = serialize_with(&doc("  distribution: red"), &options).unwrap_err();
+    assert_eq!(
+        error.message,
+        "attack 'OATF-070' is marked TLP:RED, above the sharing limit TLP:AMBER"
+    );
+    let mut out = Vec::new();
+    assert!(
+        oatf::serialize::serialize_to_writer_with(&doc("  distribution: red"), &mut out, &options)
+            .is_err()
+    );
+    assert!(out.is_empty());
+
+    // Without a policy any document is serialized.
+    assert!(oatf::serialize(&doc("  distribution: red")).is_ok());
+}
+
+#[test]
+fn catalog_filters_by_marking() {
+    let mut catalog = Catalog::new();
+    for (source, tlp) in [
+        ("clear.yaml", "clear"),
+        ("green.yaml", "green"),
+        ("amber.yaml", "amber"),
+        ("red.yaml", "red"),
+    ] {
+        catalog.insert(source, doc(&format!("  distribution: {}", tlp)));
+    }
+    catalog.insert("unmarked.yaml", doc(""));
+
+    let sources = |policy: SharingPolicy| -> Vec<&str> {
+        catalog
+            .shareable(policy)
+            .map(|e| e.source.as_str())
+            .collect()
+    };
+    assert_eq!(
+        sources(SharingPolicy::new(Tlp::Green)),
+        ["clear.yaml", "green.yaml", "unmarked.yaml"]
+    );
+    assert_eq!(
+        sources(SharingPolicy::new(Tlp::Green).unmarked_as(Tlp::Red)),
+        ["clear.yaml", "green.yaml"]
+    );
+    assert_eq!(sources(SharingPolicy::new(Tlp::Red)).len(), 5);
+}
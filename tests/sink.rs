@@ -0,0 +1,246 @@
+use oatf::sink::{Buffered, Kafka, KafkaProducer, Retrying, Stdout, VerdictSink};
+use oatf::{AttackVerdict, SinkError, SinkErrorKind};
+use serde_json::json;
+use std::sync::mpsc::{Receiver, Sender, channel};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+fn verdict(id: &str) -> AttackVerdict {
+    serde_json::from_value(json!({
+        "attack_id": id,
+        "result": "exploited",
+        "indicator_verdicts": [],
+        "evaluation_summary": {"matched": 1, "not_matched": 0, "error": 0, "skipped": 0}
+    }))
+    .unwrap()
+}
+
+fn unavailable() -> SinkError {
+    SinkError {
+        kind: SinkErrorKind::Unavailable,
+        message: "down".to_string(),
+    }
+}
+
+/// Records published attack IDs, failing the first `failures` attempts.
+#[derive(Clone, Default)]
+struct Recorder {
+    published: Arc<Mutex<Vec<String>>>,
+    failures: Arc<Mutex<u32>>,
+}
+
+impl VerdictSink for Recorder {
+    fn publish(&mut self, verdict: &AttackVerdict) -> Result<(), SinkError> {
+        let mut failures = self.failures.lock().unwrap();
+        if *failures > 0 {
+            *failures -= 1;
+            return Err(unavailable());
+        }
+        let id = verdict.attack_id.clone().unwrap_or_default();
+        self.published.lock().unwrap().push(id);
+        Ok(())
+    }
+}
+
+#[test]
+fn stdout_writes_json_lines() {
+    let mut sink = Stdout::to_writer(Vec::new());
+    sink.publish(&verdict("OATF-001")).unwrap();
+    sink.publish(&verdict("OATF-002")).unwrap();
+    let output = String::from_utf8(sink.into_inner()).unwrap();
+    let lines: Vec<serde_json::Value> = output
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[1]["attack_id"], "OATF-002");
+    assert_eq!(lines[0]["result"], "exploited");
+}
+
+#[test]
+fn kafka_records_are_keyed_by_attack_id() {
+    #[derive(Default)]
+    struct Producer(Vec<(String, Option<String>, Vec<u8>)>);
+
+    impl KafkaProducer for Producer {
+        fn send(
+            &mut self,
+            topic: &str,
+            key: Option<&str>,
+            payload: &[u8],
+        ) -> Result<(), SinkError> {
+            self.0
+                .push((topic.to_string(), key.map(str::to_string), payload.to_vec()));
+            Ok(())
+        }
+    }
+
+    let mut sink = Kafka::new(Producer::default(), "oatf.verdicts");
+    sink.publish(&verdict("OATF-003")).unwrap();
+    let producer = sink.into_inner();
+    let (topic, key, payload) = &producer.0[0];
+    assert_eq!(topic, "oatf.verdicts");
+    assert_eq!(key.as_deref(), Some("OATF-003"));
+    let record: serde_json::Value = serde_json::from_slice(payload).unwrap();
+    assert_eq!(record["attack_id"], "OATF-003");
+}
+
+#[test]
+fn retrying_retries_only_unavailable_failures() {
+    let recorder = Recorder::default();
+    *recorder.failures.lock().unwrap() = 2;
+    let mut sink = Retrying::new(recorder.clone()).backoff(Duration::ZERO, Duration::ZERO);
+    sink.publish(&verdict("OATF-001")).unwrap();
+    assert_eq!(*recorder.published.lock().unwrap(), ["OATF-001"]);
+
+    *recorder.failures.lock().unwrap() = 5;
+    let mut sink = Retrying::new(recorder.clone())
+        .max_attempts(3)
+        .backoff(Duration::ZERO, Duration::ZERO);
+    let err = sink.publish(&verdict("OATF-002")).unwrap_err();
+    assert_eq!(err.kind, SinkErrorKind::Unavailable);
+    assert_eq!(*recorder.failures.lock().unwrap(), 2);
+
+    struct Rejecting(u32);
+    impl VerdictSink for Rejecting {
+        fn publish(&mut self, _: &AttackVerdict) -> Result<(), SinkError> {
+            self.0 += 1;
+            Err(SinkError {
+                kind: SinkErrorKind::Rejected,
+                message: "400".to_string(),
+            })
+        }
+    }
+    let mut sink = Retrying::new(Rejecting(0)).backoff(Duration::ZERO, Duration::ZERO);
+    assert!(sink.publish(&verdict("OATF-003")).is_err());
+    assert_eq!(sink.into_inner().0, 1);
+}
+
+/// Blocks each publish until the test releases it.
+struct Gate {
+    release: Receiver<()>,
+    delivered: Sender<String>,
+}
+
+impl VerdictSink for Gate {
+    fn publish(&mut self, verdict: &AttackVerdict) -> Result<(), SinkError> {
+        self.release.recv().unwrap();
+        self.delivered
+            .send(verdict.attack_id.clone().unwrap_or_default())
+            .unwrap();
+        Ok(())
+    }
+}
+
+#[test]
+fn buffered_applies_backpressure() {
+    let (release, release_rx) = channel();
+    let (delivered_tx, delivered) = channel();
+    let mut sink = Buffered::new(
+        Gate {
+            release: release_rx,
+            delivered: delivered_tx,
+        },
+        1,
+    );
+
+    // The worker takes the first verdict and blocks; the second fills the
+    // queue, so the third is refused.
+    sink.publish(&verdict("OATF-001")).unwrap();
+    let mut queued = false;
+    for _ in 0..100 {
+        if sink.try_publish(&verdict("OATF-002")).is_ok() {
+            queued = true;
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    assert!(queued);
+    let err = sink.try_publish(&verdict("OATF-003")).unwrap_err();
+    assert_eq!(err.kind, SinkErrorKind::Full);
+
+    release.send(()).unwrap();
+    release.send(()).unwrap();
+    sink.flush().unwrap();
+    let order: Vec<_> = delivered.try_iter().collect();
+    assert_eq!(order, ["OATF-001", "OATF-002"]);
+}
+
+#[test]
+fn buffered_reports_worker_failures_on_flush() {
+    let recorder = Recorder::default();
+    *recorder.failures.lock().unwrap() = 1;
+    let mut sink = Buffered::new(recorder.clone(), 4);
+    sink.publish(&verdict("OATF-001")).unwrap();
+    sink.publish(&verdict("OATF-002")).unwrap();
+    assert_eq!(sink.flush().unwrap_err().kind, SinkErrorKind::Unavailable);
+    assert_eq!(sink.failed(), 1);
+    sink.flush().unwrap();
+
+    sink.publish(&verdict("OATF-003")).unwrap();
+    drop(sink);
+    assert_eq!(
+        *recorder.published.lock().unwrap(),
+        ["OATF-002", "OATF-003"]
+    );
+}
+
+#[cfg(feature = "webhook")]
+#[test]
+fn webhook_posts_json_and_classifies_statuses() {
+    use oatf::sink::Webhook;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    let server = std::thread::spawn(move || {
+        let mut bodies = Vec::new();
+        for status in [
+            "204 No Content",
+            "503 Service Unavailable",
+            "400 Bad Request",
+        ] {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            loop {
+                let n = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request);
+                if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                    let length = head
+                        .lines()
+                        .find_map(|l| {
+                            l.to_lowercase()
+                                .strip_prefix("content-length: ")
+                                .map(|v| v.trim().parse::<usize>().unwrap())
+                        })
+                        .unwrap_or(0);
+                    if body.len() >= length {
+                        bodies.push(text.to_string());
+                        break;
+                    }
+                }
+            }
+            let response =
+                format!("HTTP/1.1 {status}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+        bodies
+    });
+
+    let mut sink = Webhook::new(&url).header("Authorization", "Bearer t");
+    sink.publish(&verdict("OATF-001")).unwrap();
+    let err = sink.publish(&verdict("OATF-002")).unwrap_err();
+    assert_eq!(err.kind, SinkErrorKind::Unavailable);
+    let err = sink.publish(&verdict("OATF-003")).unwrap_err();
+    assert_eq!(err.kind, SinkErrorKind::Rejected);
+
+    let requests = server.join().unwrap();
+    assert!(requests[0].starts_with("POST /hook"));
+    let lower = requests[0].to_lowercase();
+    assert!(lower.contains("authorization: bearer t"));
+    assert!(lower.contains("content-type: application/json"));
+    assert!(requests[0].contains(r#""attack_id":"OATF-001""#));
+}
@@ -0,0 +1,115 @@
+#![cfg(feature = "normalize")]
+
+//! Golden snapshots of the canonical serialization format.
+//!
+//! Each document in `tests/snapshots/input` is normalized and serialized
+//! with `serialize_canonical`; the output must match
+//! `tests/snapshots/v{STABLE_FORMAT_VERSION}` byte for byte. Run with
+//! `OATF_UPDATE_SNAPSHOTS=1` to write missing snapshots. Existing snapshots
+//! are never overwritten: a change to canonical output needs a new format
+//! version (see CONTRIBUTING.md).
+
+use oatf::serialize::{STABLE_FORMAT_VERSION, serialize_canonical};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn snapshot_dir() -> PathBuf {
+    root().join(format!("tests/snapshots/v{}", STABLE_FORMAT_VERSION))
+}
+
+fn yaml_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("{}: {}", dir.display(), e))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "yaml"))
+        .collect();
+    files.sort();
+    files
+}
+
+fn canonical(yaml: &str) -> String {
+    let doc = oatf::parse(yaml).expect("snapshot input parses");
+    serialize_canonical(&oatf::normalize(doc)).unwrap()
+}
+
+// ─── Snapshots ──────────────────────────────────────────────────────────────
+
+#[test]
+fn canonical_output_matches_snapshots() {
+    let update = std::env::var_os("OATF_UPDATE_SNAPSHOTS").is_some();
+    let inputs = yaml_files(&root().join("tests/snapshots/input"));
+    assert!(!inputs.is_empty());
+
+    for input in inputs {
+        let output = canonical(&fs::read_to_string(&input).unwrap());
+        let snapshot = snapshot_dir().join(input.file_name().unwrap());
+        match fs::read_to_string(&snapshot) {
+            Ok(expected) => assert_eq!(
+                output,
+                expected,
+                "canonical output of {} changed; this requires a new STABLE_FORMAT_VERSION",
+                input.display()
+            ),
+            Err(_) if update => {
+                fs::create_dir_all(snapshot_dir()).unwrap();
+                fs::write(&snapshot, &output).unwrap();
+            }
+            Err(_) => panic!(
+                "missing snapshot {}; run with OATF_UPDATE_SNAPSHOTS=1",
+                snapshot.display()
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "validate")]
+#[test]
+fn snapshot_inputs_are_valid() {
+    for input in yaml_files(&root().join("tests/snapshots/input")) {
+        let doc = oatf::parse(&fs::read_to_string(&input).unwrap()).unwrap();
+        let result = oatf::validate(&doc);
+        assert!(
+            result.errors.is_empty(),
+            "{}: {:?}",
+            input.display(),
+            result.errors
+        );
+    }
+}
+
+#[test]
+fn every_snapshot_has_an_input() {
+    let inputs = root().join("tests/snapshots/input");
+    for snapshot in yaml_files(&snapshot_dir()) {
+        assert!(
+            inputs.join(snapshot.file_name().unwrap()).exists(),
+            "stale snapshot {}",
+            snapshot.display()
+        );
+    }
+}
+
+#[test]
+fn canonical_output_is_a_fixed_point() {
+    for input in yaml_files(&root().join("tests/snapshots/input")) {
+        let output = canonical(&fs::read_to_string(&input).unwrap());
+        assert_eq!(canonical(&output), output, "{}", input.display());
+    }
+}
+
+// ─── Format version ─────────────────────────────────────────────────────────
+
+#[test]
+fn format_version_is_recorded_in_the_changelog() {
+    let changelog = fs::read_to_string(root().join("CHANGELOG.md")).unwrap();
+    let entry = format!("`STABLE_FORMAT_VERSION = {}`", STABLE_FORMAT_VERSION);
+    assert!(
+        changelog.contains(&entry),
+        "CHANGELOG.md must record {}",
+        entry
+    );
+}
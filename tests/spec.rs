@@ -0,0 +1,62 @@
+#![cfg(feature = "validate")]
+
+use oatf::spec::{RULES, SPEC_VERSION, rule_info};
+use oatf::validate::explain;
+
+#[test]
+fn spec_info_reports_embedded_registries() {
+    let info = oatf::spec_info();
+    assert_eq!(info.spec_version, SPEC_VERSION);
+    assert_eq!(info.crate_version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(info.registry_snapshot.len(), 16);
+    assert_eq!(info.registry_snapshot, oatf::spec_info().registry_snapshot);
+
+    let mcp = info.protocols.iter().find(|p| p.name == "mcp").unwrap();
+    assert_eq!(mcp.modes, vec!["mcp_server", "mcp_client"]);
+    assert!(mcp.versions.contains(&"2025-06-18"));
+    let ag_ui = info.protocols.iter().find(|p| p.name == "ag_ui").unwrap();
+    assert_eq!(ag_ui.modes, vec!["ag_ui_client"]);
+    assert!(ag_ui.versions.is_empty());
+
+    let call = info.events.iter().find(|e| e.name == "tools/call").unwrap();
+    assert!(call.modes.contains(&"mcp_server"));
+    let surface = info
+        .surfaces
+        .iter()
+        .find(|s| s.name == "tool_description")
+        .unwrap();
+    assert_eq!(surface.default_target, "tools[*].description");
+    assert!(info.modes.contains(&"a2a_client"));
+}
+
+#[test]
+fn rules_are_ordered_and_cover_validation() {
+    let ids: Vec<_> = RULES.iter().map(|r| r.id).collect();
+    let expected: Vec<_> = (1..=RULES.len()).map(|n| format!("V-{:03}", n)).collect();
+    assert_eq!(ids, expected);
+    assert!(RULES.iter().all(|r| !r.description.is_empty()));
+
+    // Validation errors carry the registered spec reference.
+    let doc =
+        oatf::parse("oatf: \"0.2\"\nattack:\n  execution:\n    mode: mcp_server\n    state: {}\n")
+            .unwrap();
+    let err = &oatf::validate(&doc).errors[0];
+    assert_eq!(err.rule, "V-001");
+    assert_eq!(err.spec_ref, rule_info("V-001").unwrap().spec_ref);
+}
+
+#[test]
+fn explain_documents_every_rule() {
+    for rule in RULES {
+        let doc = explain(rule.id).unwrap_or_else(|| panic!("{} is undocumented", rule.id));
+        assert_eq!(doc.spec_ref, rule.spec_ref);
+        assert!(!doc.rationale.is_empty() && !doc.fix.is_empty());
+        assert_ne!(doc.failing_example, doc.passing_example);
+    }
+    assert!(explain("V-999").is_none());
+    assert!(explain("W-001").is_none());
+
+    let doc = explain("V-018").unwrap();
+    assert_eq!(doc.spec_ref, "§7");
+    assert!(doc.passing_example.contains("tool_description"));
+}
@@ -0,0 +1,221 @@
+#![cfg(feature = "store")]
+
+use oatf::AttackVerdict;
+use oatf::enums::AttackResult;
+use oatf::sink::VerdictSink;
+use oatf::store::{RetentionPolicy, Session, VerdictQuery, VerdictStore};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::time::{Duration, SystemTime};
+
+fn verdict(id: &str, result: &str, timestamp: &str) -> AttackVerdict {
+    serde_json::from_value(json!({
+        "attack_id": id,
+        "result": result,
+        "indicator_verdicts": [],
+        "evaluation_summary": {"matched": 1, "not_matched": 0, "error": 0, "skipped": 0},
+        "timestamp": timestamp
+    }))
+    .unwrap()
+}
+
+/// 2026-03-01T00:00:00Z plus `hours`.
+fn at(hours: u64) -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_secs(1_772_323_200 + hours * 3600)
+}
+
+fn populated() -> VerdictStore {
+    let mut store = VerdictStore::open_in_memory().unwrap();
+    for (id, result, timestamp) in [
+        ("OATF-001", "exploited", "2026-03-01T00:00:00Z"),
+        ("OATF-002", "not_exploited", "2026-03-01T01:00:00Z"),
+        ("OATF-001", "not_exploited", "2026-03-01T02:00:00Z"),
+        ("OATF-001", "exploited", "2026-03-01T03:00:00+00:00"),
+        ("OATF-002", "exploited", "2026-03-01T04:00:00.500Z"),
+    ] {
+        store.insert(&verdict(id, result, timestamp), None).unwrap();
+    }
+    store
+}
+
+fn ids(verdicts: &[oatf::store::StoredVerdict]) -> Vec<i64> {
+    verdicts.iter().map(|v| v.id).collect()
+}
+
+#[test]
+fn query_filters_by_attack_result_and_time() {
+    let store = populated();
+    assert_eq!(store.len().unwrap(), 5);
+
+    let all = store.query(&VerdictQuery::default()).unwrap();
+    assert_eq!(ids(&all), vec![1, 2, 3, 4, 5]);
+    assert_eq!(all[0].recorded_at, at(0));
+    assert_eq!(all[4].recorded_at, at(4) + Duration::from_millis(500));
+
+    let by_attack = store
+        .query(&VerdictQuery {
+            attack_id: Some("OATF-001".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(ids(&by_attack), vec![1, 3, 4]);
+
+    let exploited = store
+        .query(&VerdictQuery {
+            attack_id: Some("OATF-001".to_string()),
+            result: Some(AttackResult::Exploited),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(ids(&exploited), vec![1, 4]);
+    assert_eq!(exploited[1].verdict.result, AttackResult::Exploited);
+
+    let window = store
+        .query(&VerdictQuery {
+            since: Some(at(1)),
+            until: Some(at(3)),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(ids(&window), vec![2, 3]);
+}
+
+#[test]
+fn limit_keeps_most_recent_in_chronological_order() {
+    let store = populated();
+    let recent = store
+        .query(&VerdictQuery {
+            limit: Some(2),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(ids(&recent), vec![4, 5]);
+}
+
+#[test]
+fn missing_timestamp_uses_insertion_time() {
+    let mut store = VerdictStore::open_in_memory().unwrap();
+    let mut v = verdict("OATF-001", "error", "");
+    v.timestamp = None;
+    let before = SystemTime::now() - Duration::from_secs(1);
+    store.insert(&v, None).unwrap();
+
+    let stored = store.query(&VerdictQuery::default()).unwrap();
+    assert!(stored[0].recorded_at >= before);
+    assert!(stored[0].verdict.timestamp.is_none());
+}
+
+#[test]
+fn sessions_group_verdicts() {
+    let mut store = VerdictStore::open_in_memory().unwrap();
+    let session = Session {
+        id: "nightly-42".to_string(),
+        started_at: Some("2026-03-01T00:00:00Z".to_string()),
+        ended_at: None,
+        metadata: BTreeMap::from([("target".to_string(), "staging".to_string())]),
+    };
+    store.put_session(&session).unwrap();
+    store
+        .insert(
+            &verdict("OATF-001", "exploited", "2026-03-01T00:10:00Z"),
+            Some("nightly-42"),
+        )
+        .unwrap();
+    store
+        .insert(
+            &verdict("OATF-002", "exploited", "2026-03-01T00:20:00Z"),
+            None,
+        )
+        .unwrap();
+
+    assert_eq!(store.session("nightly-42").unwrap(), Some(session.clone()));
+    assert_eq!(store.session("missing").unwrap(), None);
+    assert_eq!(store.sessions().unwrap(), vec![session]);
+
+    let in_session = store
+        .query(&VerdictQuery {
+            session_id: Some("nightly-42".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(in_session.len(), 1);
+    assert_eq!(in_session[0].session_id.as_deref(), Some("nightly-42"));
+}
+
+#[test]
+fn retention_by_age() {
+    let mut store = populated();
+    let policy = RetentionPolicy {
+        max_age: Some(Duration::from_secs(2 * 3600)),
+        ..Default::default()
+    };
+    assert_eq!(store.apply_retention_at(&policy, at(4)).unwrap(), 2);
+    assert_eq!(
+        ids(&store.query(&VerdictQuery::default()).unwrap()),
+        vec![3, 4, 5]
+    );
+}
+
+#[test]
+fn retention_by_count() {
+    let mut store = populated();
+    let per_attack = RetentionPolicy {
+        max_per_attack: Some(1),
+        ..Default::default()
+    };
+    assert_eq!(store.apply_retention_at(&per_attack, at(4)).unwrap(), 3);
+    assert_eq!(
+        ids(&store.query(&VerdictQuery::default()).unwrap()),
+        vec![4, 5]
+    );
+
+    let total = RetentionPolicy {
+        max_verdicts: Some(1),
+        ..Default::default()
+    };
+    assert_eq!(store.apply_retention_at(&total, at(4)).unwrap(), 1);
+    assert_eq!(
+        ids(&store.query(&VerdictQuery::default()).unwrap()),
+        vec![5]
+    );
+}
+
+#[test]
+fn retention_drops_empty_expired_sessions() {
+    let mut store = VerdictStore::open_in_memory().unwrap();
+    store
+        .put_session(&Session {
+            id: "old".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+    let policy = RetentionPolicy {
+        max_age: Some(Duration::from_secs(3600)),
+        ..Default::default()
+    };
+    store.apply_retention(&policy).unwrap();
+    assert!(store.session("old").unwrap().is_some());
+
+    let later = SystemTime::now() + Duration::from_secs(7200);
+    store.apply_retention_at(&policy, later).unwrap();
+    assert!(store.session("old").unwrap().is_none());
+}
+
+#[test]
+fn store_persists_across_reopen() {
+    let path = std::env::temp_dir().join(format!("oatf-store-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    {
+        let mut store = VerdictStore::open(&path).unwrap();
+        store
+            .publish(&verdict("OATF-001", "partial", "2026-03-01T00:00:00Z"))
+            .unwrap();
+    }
+    let store = VerdictStore::open(&path).unwrap();
+    let stored = store.query(&VerdictQuery::default()).unwrap();
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0].verdict.attack_id.as_deref(), Some("OATF-001"));
+    assert_eq!(stored[0].verdict.result, AttackResult::Partial);
+    drop(store);
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,413 @@
+#![cfg(feature = "validate")]
+
+use oatf::TransformErrorKind;
+use oatf::transform::{
+    AnonymizationPolicy, Treatment, anonymize, deanonymize, merge, rename_attack_id,
+    renumber_indicators,
+};
+
+const DOC: &str = r#"
+oatf: "0.1"
+attack:
+  id: OATF-070
+  name: Exfiltration via internal wiki tool
+  author: Jane Analyst
+  description: Found during ACME-1234 on wiki.corp.example.com.
+  x-ticket: ACME-1234
+  execution:
+    mode: mcp_server
+    state:
+      tools:
+        - name: fetch_page
+          description: "Fetch https://wiki.corp.example.com/page and post to https://WIKI.corp.example.com/upload"
+          inputSchema:
+            type: object
+  indicators:
+    - id: OATF-070-01
+      surface: tool_description
+      description: Seen on build.corp.example.com
+      pattern:
+        contains: corp.example.com
+      false_positives:
+        - Internal review of ACME-99
+  references:
+    - url: https://jira.corp.example.com/browse/ACME-1234
+      description: Internal ticket
+    - url: https://notcorp.example.com/advisory
+"#;
+
+fn document() -> oatf::Document {
+    oatf::parse(DOC).unwrap()
+}
+
+fn policy() -> AnonymizationPolicy {
+    AnonymizationPolicy {
+        author: Treatment::Pseudonymize,
+        descriptions: Treatment::Keep,
+        internal_domains: vec!["corp.example.com".to_string()],
+        hosts: Treatment::Pseudonymize,
+        identifier_patterns: vec![r"ACME-[0-9]+".to_string()],
+        identifiers: Treatment::Pseudonymize,
+        strip_extensions: false,
+    }
+}
+
+#[test]
+fn default_policy_keeps_everything() {
+    let anonymized = anonymize(&document(), &AnonymizationPolicy::default()).unwrap();
+    assert_eq!(
+        oatf::serialize(&anonymized.document).unwrap(),
+        oatf::serialize(&document()).unwrap()
+    );
+    assert!(anonymized.mapping.pseudonyms.is_empty());
+}
+
+#[test]
+fn pseudonyms_are_stable_within_a_document() {
+    let anonymized = anonymize(&document(), &policy()).unwrap();
+    let attack = &anonymized.document.attack;
+    assert_eq!(attack.author.as_deref(), Some("anon-author-1"));
+    assert_eq!(
+        attack.description.as_deref(),
+        Some("Found during anon-id-1 on anon-host-1.example.")
+    );
+    assert_eq!(attack.extensions["x-ticket"], "anon-id-1");
+
+    let state = attack.execution.state.as_ref().unwrap();
+    assert_eq!(
+        state["tools"][0]["description"],
+        "Fetch https://anon-host-1.example/page and post to https://anon-host-1.example/upload"
+    );
+
+    let references = attack.references.as_ref().unwrap();
+    assert_eq!(
+        references[0].url,
+        "https://anon-host-2.example/browse/anon-id-1"
+    );
+    assert_eq!(references[1].url, "https://notcorp.example.com/advisory");
+
+    let pseudonyms = &anonymized.mapping.pseudonyms;
+    assert_eq!(pseudonyms["anon-author-1"], "Jane Analyst");
+    assert_eq!(pseudonyms["anon-host-1.example"], "wiki.corp.example.com");
+    assert_eq!(pseudonyms["anon-id-2"], "ACME-99");
+}
+
+#[test]
+fn deanonymize_restores_pseudonyms() {
+    let anonymized = anonymize(&document(), &policy()).unwrap();
+    let mapping: oatf::transform::AnonymizationMapping =
+        serde_json::from_str(&serde_json::to_string(&anonymized.mapping).unwrap()).unwrap();
+    let restored = deanonymize(&anonymized.document, &mapping).unwrap();
+    let attack = &restored.attack;
+    assert_eq!(attack.author.as_deref(), Some("Jane Analyst"));
+    assert_eq!(
+        attack.references.as_ref().unwrap()[0].url,
+        "https://jira.corp.example.com/browse/ACME-1234"
+    );
+    // Case variants share a pseudonym and come back as first seen.
+    assert_eq!(
+        attack.execution.state.as_ref().unwrap()["tools"][0]["description"],
+        "Fetch https://wiki.corp.example.com/page and post to https://wiki.corp.example.com/upload"
+    );
+}
+
+#[test]
+fn strip_removes_content() {
+    let policy = AnonymizationPolicy {
+        author: Treatment::Strip,
+        descriptions: Treatment::Strip,
+        hosts: Treatment::Strip,
+        identifiers: Treatment::Strip,
+        strip_extensions: true,
+        ..policy()
+    };
+    let anonymized = anonymize(&document(), &policy).unwrap();
+    let attack = &anonymized.document.attack;
+    assert!(attack.author.is_none());
+    assert!(attack.description.is_none());
+    assert!(attack.extensions.is_empty());
+    let indicator = &attack.indicators.as_ref().unwrap()[0];
+    assert!(indicator.description.is_none());
+    assert_eq!(
+        indicator.false_positives.as_ref().unwrap()[0].description,
+        "[redacted]"
+    );
+    assert_eq!(
+        attack.references.as_ref().unwrap()[0].url,
+        "https://redacted.invalid/browse/[redacted]"
+    );
+    assert!(anonymized.mapping.pseudonyms.is_empty());
+    assert!(oatf::validate(&anonymized.document).is_valid());
+}
+
+#[test]
+fn pseudonymized_descriptions() {
+    let policy = AnonymizationPolicy {
+        descriptions: Treatment::Pseudonymize,
+        ..AnonymizationPolicy::default()
+    };
+    let anonymized = anonymize(&document(), &policy).unwrap();
+    let attack = &anonymized.document.attack;
+    assert_eq!(attack.description.as_deref(), Some("anon-description-1"));
+    assert_eq!(
+        attack.references.as_ref().unwrap()[0]
+            .description
+            .as_deref(),
+        Some("anon-description-3")
+    );
+    assert_eq!(anonymized.mapping.pseudonyms.len(), 4);
+}
+
+#[test]
+fn invalid_policy_is_rejected() {
+    let bad_pattern = AnonymizationPolicy {
+        identifier_patterns: vec!["(".to_string()],
+        ..policy()
+    };
+    assert_eq!(
+        anonymize(&document(), &bad_pattern).unwrap_err().kind,
+        TransformErrorKind::InvalidPolicy
+    );
+    let empty_domain = AnonymizationPolicy {
+        internal_domains: vec![" ".to_string()],
+        ..policy()
+    };
+    assert_eq!(
+        anonymize(&document(), &empty_domain).unwrap_err().kind,
+        TransformErrorKind::InvalidPolicy
+    );
+}
+
+const BASE: &str = r#"
+oatf: "0.1"
+attack:
+  id: OATF-080
+  name: Tool poisoning
+  severity: high
+  execution:
+    mode: mcp_server
+    phases:
+      - name: setup
+        state:
+          tools: []
+        trigger:
+          event: tools/call
+      - name: done
+  indicators:
+    - id: OATF-080-01
+      surface: tool_description
+      pattern:
+        contains: ignore previous
+    - id: OATF-080-02
+      surface: tool_arguments
+      pattern:
+        contains: .ssh
+"#;
+
+fn edit(f: impl FnOnce(&mut oatf::Document)) -> oatf::Document {
+    let mut doc = oatf::parse(BASE).unwrap();
+    f(&mut doc);
+    doc
+}
+
+fn indicator(id: &str, text: &str) -> oatf::Indicator {
+    let yaml = format!(
+        "id: {}\nsurface: tool_arguments\npattern:\n  contains: {}\n",
+        id, text
+    );
+    serde_saphyr::from_str(&yaml).unwrap()
+}
+
+#[test]
+fn merge_combines_edits_to_different_entries() {
+    let base = oatf::parse(BASE).unwrap();
+    let ours = edit(|doc| {
+        let indicators = doc.attack.indicators.as_mut().unwrap();
+        indicators[0].confidence = Some(80);
+        indicators.insert(1, indicator("OATF-080-03", "id_rsa"));
+    });
+    let theirs = edit(|doc| {
+        doc.attack.name = Some("MCP tool poisoning".to_string());
+        let indicators = doc.attack.indicators.as_mut().unwrap();
+        indicators[1].description = Some("Reads SSH keys".to_string());
+        indicators.push(indicator("OATF-080-04", "aws/credentials"));
+        let phases = doc.attack.execution.phases.as_mut().unwrap();
+        phases[0].description = Some("Serve the poisoned tool".to_string());
+    });
+
+    let merged = merge(&base, &ours, &theirs).unwrap();
+    let attack = &merged.attack;
+    assert_eq!(attack.name.as_deref(), Some("MCP tool poisoning"));
+    let ids: Vec<_> = attack
+        .indicators
+        .iter()
+        .flatten()
+        .map(|i| i.id.as_deref().unwrap())
+        .collect();
+    assert_eq!(
+        ids,
+        vec!["OATF-080-01", "OATF-080-03", "OATF-080-02", "OATF-080-04"]
+    );
+    let indicators = attack.indicators.as_ref().unwrap();
+    assert_eq!(indicators[0].confidence, Some(80));
+    assert_eq!(indicators[2].description.as_deref(), Some("Reads SSH keys"));
+    assert_eq!(
+        attack.execution.phases.as_ref().unwrap()[0]
+            .description
+            .as_deref(),
+        Some("Serve the poisoned tool")
+    );
+}
+
+#[test]
+fn merge_applies_one_sided_removals() {
+    let base = oatf::parse(BASE).unwrap();
+    let ours = edit(|doc| {
+        doc.attack.indicators.as_mut().unwrap().remove(0);
+    });
+    let theirs = edit(|doc| {
+        doc.attack.indicators.as_mut().unwrap()[1].confidence = Some(60);
+    });
+    let merged = merge(&base, &ours, &theirs).unwrap();
+    let indicators = merged.attack.indicators.unwrap();
+    assert_eq!(indicators.len(), 1);
+    assert_eq!(indicators[0].id.as_deref(), Some("OATF-080-02"));
+    assert_eq!(indicators[0].confidence, Some(60));
+}
+
+#[test]
+fn merge_reports_conflicts_with_paths() {
+    let base = oatf::parse(BASE).unwrap();
+    let ours = edit(|doc| {
+        let indicators = doc.attack.indicators.as_mut().unwrap();
+        indicators[1].pattern.as_mut().unwrap().contains = Some("id_rsa".to_string());
+        indicators.remove(0);
+    });
+    let theirs = edit(|doc| {
+        let indicators = doc.attack.indicators.as_mut().unwrap();
+        indicators[0].confidence = Some(90);
+        indicators[1].pattern.as_mut().unwrap().contains = Some("id_ed25519".to_string());
+    });
+    let conflicts = merge(&base, &ours, &theirs).unwrap_err().conflicts;
+    let summary: Vec<_> = conflicts
+        .iter()
+        .map(|c| format!("{}: {}", c.path, c.message))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            "attack.indicators[0].pattern.contains: changed to different values",
+            "attack.indicators[1]: removed by us but changed by them",
+        ]
+    );
+    assert_eq!(conflicts[0].ours, Some(serde_json::json!("id_rsa")));
+    assert_eq!(conflicts[0].theirs, Some(serde_json::json!("id_ed25519")));
+    assert!(conflicts[1].ours.is_none());
+}
+
+#[test]
+fn identical_edits_merge_cleanly() {
+    let base = oatf::parse(BASE).unwrap();
+    let ours = edit(|doc| doc.attack.author = Some("Red team".to_string()));
+    let merged = merge(&base, &ours, &ours).unwrap();
+    assert_eq!(merged.attack.author.as_deref(), Some("Red team"));
+    assert_eq!(
+        oatf::serialize(&merge(&base, &base, &base).unwrap()).unwrap(),
+        oatf::serialize(&base).unwrap()
+    );
+}
+
+// ─── Identifiers ────────────────────────────────────────────────────────────
+
+fn ids(doc: &oatf::Document) -> Vec<Option<&str>> {
+    doc.attack
+        .indicators
+        .iter()
+        .flatten()
+        .map(|ind| ind.id.as_deref())
+        .collect()
+}
+
+fn v023_v024(doc: &oatf::Document) -> Vec<String> {
+    oatf::validate(doc)
+        .errors
+        .into_iter()
+        .filter(|e| e.rule == "V-023" || e.rule == "V-024")
+        .map(|e| e.message)
+        .collect()
+}
+
+#[test]
+fn renumber_indicators_follows_position() {
+    let mut doc = edit(|doc| {
+        let indicators = doc.attack.indicators.as_mut().unwrap();
+        indicators[0].id = Some("OATF-080-07".to_string());
+        indicators.push(indicator("OATF-080-02", "id_rsa"));
+        indicators[1].id = None;
+    });
+    let changes = renumber_indicators(&mut doc);
+    assert_eq!(
+        ids(&doc),
+        [
+            Some("OATF-080-01"),
+            Some("OATF-080-02"),
+            Some("OATF-080-03")
+        ]
+    );
+    assert_eq!(
+        changes.into_iter().collect::<Vec<_>>(),
+        [
+            ("OATF-080-02".to_string(), "OATF-080-03".to_string()),
+            ("OATF-080-07".to_string(), "OATF-080-01".to_string()),
+        ]
+    );
+    assert!(renumber_indicators(&mut doc).is_empty());
+
+    doc.attack.id = None;
+    renumber_indicators(&mut doc);
+    assert_eq!(ids(&doc)[2], Some("indicator-03"));
+}
+
+#[test]
+fn rename_attack_id_moves_indicator_ids() {
+    let mut doc = edit(|doc| {
+        let indicators = doc.attack.indicators.as_mut().unwrap();
+        indicators[0].id = Some("OATF-080-03".to_string());
+        indicators[1].id = None;
+    });
+    let changes = rename_attack_id(&mut doc, "ACME-PROMPT-042").unwrap();
+    assert_eq!(doc.attack.id.as_deref(), Some("ACME-PROMPT-042"));
+    assert_eq!(ids(&doc), [Some("ACME-PROMPT-042-03"), None]);
+    assert_eq!(
+        changes.into_iter().collect::<Vec<_>>(),
+        [("OATF-080-03".to_string(), "ACME-PROMPT-042-03".to_string())]
+    );
+    assert_eq!(v023_v024(&doc), Vec::<String>::new());
+    assert!(
+        rename_attack_id(&mut doc, "ACME-PROMPT-042")
+            .unwrap()
+            .is_empty()
+    );
+
+    // A duplicated suffix and an ID without one get fresh numbers.
+    let mut doc = edit(|doc| {
+        let indicators = doc.attack.indicators.as_mut().unwrap();
+        indicators.push(indicator("OATF-080-02", "id_rsa"));
+        indicators.push(indicator("detect-secrets", "id_ed25519"));
+    });
+    rename_attack_id(&mut doc, "OATF-081").unwrap();
+    assert_eq!(
+        ids(&doc),
+        [
+            Some("OATF-081-01"),
+            Some("OATF-081-02"),
+            Some("OATF-081-03"),
+            Some("OATF-081-04"),
+        ]
+    );
+    assert_eq!(v023_v024(&doc), Vec::<String>::new());
+
+    let error = rename_attack_id(&mut doc, "oatf-1").unwrap_err();
+    assert_eq!(error.kind, TransformErrorKind::InvalidId);
+    assert_eq!(doc.attack.id.as_deref(), Some("OATF-081"));
+}
@@ -0,0 +1,246 @@
+#![cfg(feature = "evaluate")]
+
+use oatf::engine::actors::{ActorInput, ActorOutput, ActorSystem, Output};
+use oatf::engine::{McpClient, Transport};
+use oatf::enums::AdvanceReason;
+use oatf::primitives::{evaluate_predicate, interpolate_predicate};
+use oatf::{EngineError, MatchPredicate};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+
+const REPLAY_SERVER: &str = r#"
+oatf: "0.1"
+attack:
+  execution:
+    mode: mcp_server
+    phases:
+      - name: issue
+        state:
+          tools:
+            - name: login
+              description: Logs in
+              inputSchema: {type: object}
+              responses:
+                - content:
+                    - type: text
+                      text: "token=s3cr3t"
+            - name: transfer
+              description: Transfers funds
+              inputSchema: {type: object}
+        extractors:
+          - name: session_token
+            source: response
+            type: regex
+            selector: "token=(\\w+)"
+        trigger:
+          event: tools/call:login
+      - name: replay
+        trigger:
+          event: tools/call:transfer
+          match:
+            arguments.token: "{{session_token}}"
+      - name: done
+  indicators:
+    - surface: tool_arguments
+      pattern:
+        contains: s3cr3t
+"#;
+
+fn call(name: &str, arguments: Value) -> ActorInput {
+    ActorInput::Request {
+        method: "tools/call".to_string(),
+        params: json!({"name": name, "arguments": arguments}),
+    }
+}
+
+fn advanced(outputs: &[Output]) -> bool {
+    outputs
+        .iter()
+        .any(|o| matches!(o.output, ActorOutput::Advanced { .. }))
+}
+
+fn v056(input: &str) -> Vec<(String, String)> {
+    oatf::validate(&oatf::parse(input).unwrap())
+        .errors
+        .into_iter()
+        .filter(|e| e.rule == "V-056")
+        .map(|e| (e.path, e.message))
+        .collect()
+}
+
+#[test]
+fn interpolate_predicate_resolves_values_and_operands() {
+    let predicate: MatchPredicate = serde_json::from_value(json!({
+        "arguments.token": "{{session_token}}",
+        "arguments.note": {"contains": "{{session_token}}"},
+        "arguments.user": "admin",
+    }))
+    .unwrap();
+    let extracted = HashMap::from([("session_token".to_string(), "abc".to_string())]);
+
+    let (resolved, diagnostics) = interpolate_predicate(&predicate, &extracted);
+    assert!(diagnostics.is_empty());
+    assert!(evaluate_predicate(
+        &resolved,
+        &json!({"arguments": {"token": "abc", "note": "sent abc", "user": "admin"}})
+    ));
+    assert!(!evaluate_predicate(
+        &resolved,
+        &json!({"arguments": {"token": "{{session_token}}", "note": "abc", "user": "admin"}})
+    ));
+
+    let (_, diagnostics) = interpolate_predicate(&predicate, &HashMap::new());
+    assert_eq!(diagnostics.len(), 2);
+    assert!(diagnostics.iter().all(|d| d.code == "W-004"));
+}
+
+#[test]
+fn actor_trigger_matches_previously_extracted_value() {
+    let doc = oatf::load(REPLAY_SERVER).unwrap().document;
+    let mut system = ActorSystem::new(&doc).unwrap();
+    system.start();
+
+    let login = system.deliver("default", call("login", json!({}))).unwrap();
+    assert!(advanced(&login));
+
+    let wrong = system
+        .deliver("default", call("transfer", json!({"token": "guess"})))
+        .unwrap();
+    assert!(!advanced(&wrong));
+
+    let replayed = system
+        .deliver("default", call("transfer", json!({"token": "s3cr3t"})))
+        .unwrap();
+    assert!(advanced(&replayed));
+    assert!(system.is_complete());
+    assert!(system.actor("default").unwrap().diagnostics().is_empty());
+}
+
+/// Issues a session on `login` and reports `owner` on `whoami`.
+struct SessionServer {
+    owner: &'static str,
+}
+
+impl Transport for SessionServer {
+    fn request(&mut self, method: &str, params: Value) -> Result<Value, EngineError> {
+        Ok(match (method, params["name"].as_str()) {
+            ("tools/call", Some("login")) => json!({"session": "abc123"}),
+            ("tools/call", Some("whoami")) => json!({"owner": self.owner}),
+            _ => json!({}),
+        })
+    }
+
+    fn notify(&mut self, _method: &str, _params: Value) -> Result<(), EngineError> {
+        Ok(())
+    }
+}
+
+#[test]
+fn client_trigger_matches_previously_extracted_value() {
+    let doc = oatf::load(
+        r#"
+oatf: "0.1"
+attack:
+  execution:
+    mode: mcp_client
+    phases:
+      - name: login
+        state:
+          tools:
+            - name: login
+        extractors:
+          - name: session
+            source: response
+            type: json_path
+            selector: "$.session"
+        trigger:
+          event: tools/call:login
+      - name: confirm
+        state:
+          tools:
+            - name: whoami
+        trigger:
+          event: tools/call:whoami
+          match:
+            owner: "{{session}}"
+      - name: done
+  indicators:
+    - surface: tool_response
+      pattern:
+        contains: abc123
+"#,
+    )
+    .unwrap()
+    .document;
+
+    let report = McpClient::new(SessionServer { owner: "abc123" })
+        .run(&doc)
+        .unwrap();
+    assert!(report.completed);
+    assert_eq!(report.phases[1].advance, Some(AdvanceReason::EventMatched));
+
+    let report = McpClient::new(SessionServer { owner: "someone" })
+        .run(&doc)
+        .unwrap();
+    assert!(!report.completed);
+    assert_eq!(report.phases[1].advance, None);
+}
+
+#[test]
+fn v056_accepts_values_extracted_by_earlier_phases() {
+    assert!(v056(REPLAY_SERVER).is_empty());
+}
+
+#[test]
+fn v056_reports_references_without_an_earlier_extractor() {
+    let input = r#"
+oatf: "0.1"
+attack:
+  execution:
+    actors:
+      - name: client
+        mode: mcp_client
+        phases:
+          - name: login
+            extractors:
+              - name: session
+                source: response
+                type: json_path
+                selector: "$.session"
+            state:
+              tools:
+                - name: login
+            trigger:
+              event: tools/call
+              match:
+                session: "{{session}}"
+          - name: done
+      - name: server
+        mode: mcp_server
+        phases:
+          - name: wait
+            state:
+              tools:
+                - name: transfer
+                  description: Transfers funds
+                  inputSchema: {type: object}
+            trigger:
+              event: tools/call
+              match:
+                arguments.a: "{{client.session}}"
+                arguments.b: "{{client.missing}}"
+                arguments.c: {contains: "{{nobody}}"}
+                arguments.d: "{{request.arguments.a}}"
+          - name: done
+"#;
+    let paths: Vec<_> = v056(input).into_iter().map(|(path, _)| path).collect();
+    assert_eq!(
+        paths,
+        [
+            "attack.execution.actors[0].phases[0].trigger.match.session",
+            "attack.execution.actors[1].phases[0].trigger.match.arguments.b",
+            "attack.execution.actors[1].phases[0].trigger.match.arguments.c",
+            "attack.execution.actors[1].phases[0].trigger.match.arguments.d",
+        ]
+    );
+}
@@ -1,3 +1,5 @@
+#![cfg(all(feature = "validate", feature = "normalize"))]
+
 use oatf::parse::parse;
 use oatf::validate::validate;
 
@@ -35,6 +37,9 @@ fn v021_valid_wildcard_paths() {
         r#"arguments"#,
         r#"skills[*].description"#,
         r#"messages[*].content"#,
+        r#"arguments.payload!json.url"#,
+        r#"items[*]!json"#,
+        r#"query!url!json.cmd"#,
     ];
     for target in &cases {
         let input = format!(
@@ -102,6 +107,34 @@ attack:
     assert_has_error(input, "V-021");
 }
 
+#[test]
+fn v021_rejects_malformed_decoders() {
+    for target in ["payload!yaml", "!json.url", "payload!json[*]", "payload!"] {
+        let input = format!(
+            r#"
+oatf: "0.1"
+attack:
+  execution:
+    mode: mcp_server
+    state:
+      tools: []
+  indicators:
+    - surface: tool_description
+      pattern:
+        target: "{}"
+        condition:
+          contains: "test"
+"#,
+            target
+        );
+        assert!(
+            !errors_for(&input, "V-021").is_empty(),
+            "target '{}' should be invalid",
+            target
+        );
+    }
+}
+
 // ─── V-022: Semantic threshold ──────────────────────────────────────────────
 
 #[test]
@@ -241,6 +274,70 @@ attack:
     assert!(errs.is_empty(), "valid event should not error: {:?}", errs);
 }
 
+#[test]
+fn v029_progress_and_elicitation_events() {
+    let input = |mode: &str, event: &str| {
+        format!(
+            r#"
+oatf: "0.1"
+attack:
+  execution:
+    mode: {}
+    phases:
+      - name: phase-1
+        state:
+          tools: []
+        trigger:
+          event: {}
+      - name: phase-2
+  indicators:
+    - surface: progress_notification
+      pattern:
+        contains: "test"
+"#,
+            mode, event
+        )
+    };
+    for mode in ["mcp_server", "mcp_client"] {
+        let errs = errors_for(&input(mode, "notifications/progress"), "V-029");
+        assert!(errs.is_empty(), "{}: {:?}", mode, errs);
+    }
+    let event = "notifications/elicitation/complete";
+    assert!(errors_for(&input("mcp_client", event), "V-029").is_empty());
+    assert_has_error(&input("mcp_server", event), "V-029");
+}
+
+#[test]
+fn v029_a2a_push_notification_config_events() {
+    let input = |mode: &str, event: &str| {
+        format!(
+            r#"
+oatf: "0.1"
+attack:
+  execution:
+    mode: {}
+    phases:
+      - name: phase-1
+        state:
+          task_responses: []
+        trigger:
+          event: {}
+      - name: phase-2
+  indicators:
+    - surface: task_status
+      pattern:
+        contains: "completed"
+"#,
+            mode, event
+        )
+    };
+    for action in ["set", "get", "list", "delete"] {
+        let event = format!("tasks/pushNotificationConfig/{}", action);
+        assert!(errors_for(&input("a2a_server", &event), "V-029").is_empty());
+        assert_has_error(&input("a2a_client", &event), "V-029");
+    }
+}
+
 // ─── V-030: Mutual exclusion (state/phases/actors) ──────────────────────────
 
 #[test]
@@ -634,3 +731,717 @@ attack:
 "#;
     assert_has_error(input, "V-007");
 }
+
+// ─── V-046: Mode protocol version pin ───────────────────────────────────────
+
+fn pinned_mode_doc(mode: &str) -> String {
+    format!(
+        r#"
+oatf: "0.1"
+attack:
+  execution:
+    mode: {}
+    phases:
+      - name: exploit
+        state:
+          tools: []
+        trigger:
+          event: tools/call
+      - name: terminal
+  indicators:
+    - surface: tool_description
+      pattern:
+        contains: "test"
+"#,
+        mode
+    )
+}
+
+#[test]
+fn v046_known_version_pin_is_valid() {
+    let doc = parse(&pinned_mode_doc("mcp_server@2025-03-26")).unwrap();
+    let result = validate(&doc);
+    assert!(result.errors.is_empty(), "got: {:?}", result.errors);
+    assert!(
+        !result.warnings.iter().any(|w| w.code == "W-002"),
+        "pinned known mode should not be unrecognized: {:?}",
+        result.warnings
+    );
+}
+
+#[test]
+fn v046_unknown_version_pin() {
+    let errs = errors_for(&pinned_mode_doc("mcp_server@1999-01-01"), "V-046");
+    assert_eq!(errs, vec!["attack.execution.mode"]);
+}
+
+#[test]
+fn v046_malformed_version_pin_is_v005() {
+    assert_has_error(&pinned_mode_doc("mcp_server@"), "V-005");
+}
+
+#[test]
+fn v046_pinned_mode_survives_normalization() {
+    let result = oatf::load(&pinned_mode_doc("mcp_server@2025-06-18")).expect("valid document");
+    let actors = result.document.attack.execution.actors.unwrap();
+    let mode = oatf::Mode::parse(&actors[0].mode);
+    assert_eq!(mode.name, "mcp_server");
+    assert_eq!(mode.version.as_deref(), Some("2025-06-18"));
+    assert_eq!(mode.protocol(), "mcp");
+    assert_eq!(mode.to_string(), "mcp_server@2025-06-18");
+}
+
+// ─── V-047: Pacing durations ────────────────────────────────────────────────
+
+#[test]
+fn v047_invalid_pacing_duration() {
+    let input = r#"
+oatf: "0.1"
+attack:
+  execution:
+    mode: mcp_client
+    phases:
+      - name: slow
+        pacing:
+          delay: 2s
+          jitter: "soon"
+        state:
+          tools: []
+        trigger:
+          event: tools/call
+      - name: terminal
+  indicators:
+    - surface: tool_response
+      pattern:
+        contains: "x"
+"#;
+    let errs = errors_for(input, "V-047");
+    assert_eq!(errs, vec!["attack.execution.phases[0].pacing.jitter"]);
+}
+
+// ─── V-048: Safety limits ───────────────────────────────────────────────────
+
+#[test]
+fn v048_invalid_safety_section() {
+    let input = r#"
+oatf: "0.1"
+attack:
+  safety:
+    max_runtime: forever
+    max_messages: 0
+    forbidden_hosts: ["prod.example.com", "https://x.example.com/"]
+  execution:
+    mode: mcp_server
+    state:
+      tools: []
+  indicators:
+    - surface: tool_description
+      pattern:
+        contains: "test"
+"#;
+    let errs = errors_for(input, "V-048");
+    assert_eq!(
+        errs,
+        vec![
+            "attack.safety.max_runtime",
+            "attack.safety.max_messages",
+            "attack.safety.forbidden_hosts[1]",
+        ]
+    );
+}
+
+// ─── V-049 / W-006: Lifecycle references ────────────────────────────────────
+
+fn lifecycle_doc(lifecycle: &str) -> String {
+    format!(
+        r#"
+oatf: "0.1"
+attack:
+  id: OATF-010
+{}
+  execution:
+    mode: mcp_server
+    state:
+      tools: []
+  indicators:
+    - surface: tool_description
+      pattern:
+        contains: "test"
+"#,
+        lifecycle
+    )
+}
+
+#[test]
+fn v049_invalid_lifecycle_references() {
+    let input = lifecycle_doc("  supersedes: [OATF-003, \"oatf 9\"]\n  superseded_by: OATF-010");
+    assert_eq!(
+        errors_for(&input, "V-049"),
+        vec!["attack.supersedes[1]", "attack.superseded_by"]
+    );
+    let empty = lifecycle_doc("  supersedes: []");
+    assert_eq!(errors_for(&empty, "V-049"), vec!["attack.supersedes"]);
+}
+
+#[test]
+fn w006_superseded_attack_should_be_deprecated() {
+    let warned = |lifecycle: &str| {
+        let doc = parse(&lifecycle_doc(lifecycle)).unwrap();
+        let result = validate(&doc);
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        result.warnings.iter().any(|w| w.code == "W-006")
+    };
+    assert!(warned("  status: stable\n  superseded_by: OATF-012"));
+    assert!(!warned("  status: deprecated\n  superseded_by: OATF-012"));
+    assert!(!warned("  supersedes: [OATF-003]"));
+}
+
+// ─── V-050: Structured false positives ──────────────────────────────────────
+
+#[test]
+fn v050_invalid_false_positive_entries() {
+    let input = r#"
+oatf: "0.1"
+attack:
+  execution:
+    mode: mcp_server
+    state:
+      tools: []
+  indicators:
+    - surface: tool_description
+      pattern:
+        contains: "test"
+      false_positives:
+        - Plain notes are fine
+        - description: " "
+          when:
+            "bad path[0]": 1
+        - description: Empty predicate
+          when: {}
+"#;
+    assert_eq!(
+        errors_for(input, "V-050"),
+        vec![
+            "attack.indicators[0].false_positives[1].description",
+            "attack.indicators[0].false_positives[1].when.bad path[0]",
+            "attack.indicators[0].false_positives[2].when",
+        ]
+    );
+}
+
+// ─── V-051: Indicator stages ────────────────────────────────────────────────
+
+#[test]
+fn v051_invalid_stages() {
+    let input = r#"
+oatf: "0.1"
+attack:
+  execution:
+    mode: mcp_server
+    state:
+      tools: []
+  correlation:
+    stages: [delivery, delivery, exfiltration]
+  indicators:
+    - surface: tool_description
+      stage: delivery
+      pattern:
+        contains: "test"
+    - surface: tool_description
+      stage: Exfil-Stage
+      pattern:
+        contains: "test"
+"#;
+    assert_eq!(
+        errors_for(input, "V-051"),
+        vec![
+            "attack.indicators[1].stage",
+            "attack.correlation.stages[1]",
+            "attack.correlation.stages[2]",
+        ]
+    );
+}
+
+// ─── W-007: Indicator surfaces absent from execution state ──────────────────
+
+fn w007_paths(mode: &str, state: &str) -> Vec<String> {
+    let input = format!(
+        r#"
+oatf: "0.1"
+attack:
+  execution:
+    mode: {mode}
+    state:
+{state}
+  indicators:
+    - surface: tool_description
+      pattern:
+        contains: "test"
+    - surface: prompt_content
+      pattern:
+        contains: "test"
+    - surface: tool_arguments
+      pattern:
+        contains: "test"
+"#
+    );
+    let doc = parse(&input).unwrap();
+    let result = validate(&doc);
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    result
+        .warnings
+        .iter()
+        .filter(|w| w.code == "W-007")
+        .filter_map(|w| w.path.clone())
+        .collect()
+}
+
+#[test]
+fn w007_indicator_surface_never_served() {
+    let tools = "      tools:\n        - name: t\n          description: d\n          inputSchema: {type: object}\n";
+    assert_eq!(
+        w007_paths("mcp_server", tools),
+        vec!["attack.indicators[1].surface"]
+    );
+    let prompts = "      prompts:\n        - name: p\n          responses:\n            - messages:\n                - role: user\n                  content: {type: text, text: hi}\n";
+    assert_eq!(
+        w007_paths("mcp_server", prompts),
+        vec!["attack.indicators[0].surface"]
+    );
+    // Client-mode state says nothing about what the server exposes.
+    assert!(w007_paths("mcp_client", "      actions: []\n").is_empty());
+}
+
+// ─── W-010: Category and surface consistency ───────────────────────────────
+
+fn w010(category: &str, mode: &str, surfaces: &[&str]) -> Vec<String> {
+    let indicators: String = surfaces
+        .iter()
+        .map(|s| {
+            format!(
+                "    - surface: {}\n      pattern:\n        contains: x\n",
+                s
+            )
+        })
+        .collect();
+    let input = format!(
+        "oatf: \"0.1\"\nattack:\n  classification:\n    category: {}\n  execution:\n    mode: {}\n    state: {{}}\n  indicators:\n{}",
+        category, mode, indicators
+    );
+    validate(&parse(&input).unwrap())
+        .warnings
+        .into_iter()
+        .filter(|w| w.code == "W-010")
+        .map(|w| w.message)
+        .collect()
+}
+
+#[test]
+fn w010_category_surface_mismatch() {
+    assert!(w010("capability_poisoning", "mcp_server", &["tool_description"]).is_empty());
+    assert!(w010("capability_poisoning", "a2a_server", &["skill_description"]).is_empty());
+    assert_eq!(
+        w010(
+            "capability_poisoning",
+            "a2a_server",
+            &["task_message", "task_artifact"]
+        ),
+        [
+            "classification.category 'capability_poisoning' is not observable on the indicator surfaces (task_message, task_artifact); expected a surface such as tool_description, tool_input_schema, tool_name"
+        ]
+    );
+    assert_eq!(
+        w010("response_fabrication", "mcp_server", &["tool_name"]).len(),
+        1
+    );
+    // One compatible surface is enough, and unconstrained categories pass.
+    assert!(
+        w010(
+            "oversight_bypass",
+            "mcp_server",
+            &["tool_name", "tool_arguments"]
+        )
+        .is_empty()
+    );
+    assert!(w010("context_manipulation", "ag_ui_client", &["agent_event"]).is_empty());
+
+    assert_eq!(
+        w010("cross_protocol_chain", "mcp_server", &["tool_description"]),
+        [
+            "classification.category 'cross_protocol_chain' but the modes and indicator surfaces only use the 'mcp' protocol"
+        ]
+    );
+    assert!(
+        w010(
+            "cross_protocol_chain",
+            "mcp_server",
+            &["tool_description", "task_message"]
+        )
+        .is_empty()
+    );
+}
+
+// ─── V-052: Profile content ─────────────────────────────────────────────────
+
+fn v052_paths(indicators: &str, profile: oatf::enums::Profile) -> Vec<String> {
+    let input = format!(
+        "oatf: \"0.1\"\nattack:\n  execution:\n    mode: mcp_server\n    state:\n      tools: []\n{}",
+        indicators
+    );
+    let doc = parse(&input).unwrap();
+    oatf::validate_profile(&doc, profile)
+        .errors
+        .into_iter()
+        .filter(|e| e.rule == "V-052")
+        .map(|e| e.path)
+        .collect()
+}
+
+#[test]
+fn v052_profile_content() {
+    use oatf::enums::Profile;
+
+    let detection = "  indicators:\n    - surface: tool_description\n      pattern:\n        contains: x\n  correlation:\n    logic: any\n";
+    assert!(v052_paths(detection, Profile::Full).is_empty());
+    assert!(v052_paths("", Profile::ExecutionOnly).is_empty());
+    assert_eq!(v052_paths("", Profile::Full), vec!["attack.indicators"]);
+    assert_eq!(
+        v052_paths(detection, Profile::ExecutionOnly),
+        vec!["attack.indicators", "attack.correlation"]
+    );
+
+    // Plain validation accepts either shape and reports the profile it fits.
+    let minimal = "oatf: \"0.1\"\nattack:\n  execution:\n    mode: mcp_server\n    state: {}\n";
+    assert!(errors_for(minimal, "V-052").is_empty());
+    assert_eq!(parse(minimal).unwrap().profile(), Profile::ExecutionOnly);
+    let loaded = oatf::load(minimal).unwrap();
+    assert_eq!(loaded.profile, Profile::ExecutionOnly);
+    let errors = oatf::load_with_profile(minimal, Profile::Full).unwrap_err();
+    assert!(matches!(&errors[0], oatf::OATFError::Validation(e) if e.rule == "V-052"));
+}
+
+// ─── Diagnostic consolidation ───────────────────────────────────────────────
+
+fn warning(code: &str, path: &str, message: &str) -> oatf::Diagnostic {
+    oatf::Diagnostic {
+        severity: oatf::DiagnosticSeverity::Warning,
+        code: code.to_string(),
+        path: Some(path.to_string()),
+        message: message.to_string(),
+        suggestion: None,
+    }
+}
+
+fn noisy_result() -> oatf::ValidationResult {
+    oatf::ValidationResult {
+        errors: Vec::new(),
+        warnings: vec![
+            warning("W-004", "a", "undefined 'x'"),
+            warning("W-001", "b", "not kebab-case"),
+            warning("W-004", "a", "undefined 'x'"),
+            warning("W-004", "a", "undefined 'y'"),
+            warning("W-004", "c", "undefined 'x'"),
+            warning("W-001", "d", "not kebab-case"),
+        ],
+    }
+}
+
+fn listed(result: &oatf::ValidationResult) -> Vec<(String, String, String)> {
+    result
+        .warnings
+        .iter()
+        .map(|w| {
+            (
+                w.code.clone(),
+                w.path.clone().unwrap_or_default(),
+                w.message.clone(),
+            )
+        })
+        .collect()
+}
+
+fn triple(code: &str, path: &str, message: &str) -> (String, String, String) {
+    (code.to_string(), path.to_string(), message.to_string())
+}
+
+#[test]
+fn consolidate_dedupes_identical_or_by_path() {
+    use oatf::{Dedupe, DiagnosticPolicy};
+
+    let identical = noisy_result().consolidate(&DiagnosticPolicy::default());
+    assert_eq!(identical.warnings.len(), 5);
+
+    let by_path = noisy_result().consolidate(&DiagnosticPolicy {
+        dedupe: Dedupe::ByPath,
+        ..DiagnosticPolicy::default()
+    });
+    assert_eq!(
+        listed(&by_path),
+        [
+            triple("W-004", "a", "undefined 'x'"),
+            triple("W-001", "b", "not kebab-case"),
+            triple("W-004", "c", "undefined 'x'"),
+            triple("W-001", "d", "not kebab-case"),
+        ]
+    );
+
+    let all = noisy_result().consolidate(&DiagnosticPolicy::keep_all());
+    assert_eq!(all.warnings.len(), 6);
+}
+
+#[test]
+fn consolidate_groups_and_truncates_per_rule() {
+    use oatf::{Dedupe, DiagnosticPolicy};
+
+    let result = noisy_result().consolidate(&DiagnosticPolicy {
+        dedupe: Dedupe::Off,
+        group: true,
+        max_per_rule: Some(2),
+    });
+    assert_eq!(
+        listed(&result),
+        [
+            triple("W-004", "a", "undefined 'x'"),
+            triple("W-004", "a", "undefined 'x'"),
+            triple("W-004", "a", "and 2 more W-004 warnings"),
+            triple("W-001", "b", "not kebab-case"),
+            triple("W-001", "d", "not kebab-case"),
+        ]
+    );
+}
+
+#[test]
+fn truncated_errors_keep_the_document_invalid() {
+    use oatf::{DiagnosticPolicy, validate_with_policy};
+
+    let indicator = "    - surface: tool_description\n      pattern:\n        regex: \"(\"\n";
+    let input = format!(
+        "oatf: \"0.1\"\nattack:\n  execution:\n    mode: mcp_server\n    state:\n      tools: []\n  indicators:\n{}",
+        indicator.repeat(4)
+    );
+    let doc = parse(&input).unwrap();
+    assert_eq!(errors_for(&input, "V-013").len(), 4);
+
+    let result = validate_with_policy(
+        &doc,
+        &DiagnosticPolicy {
+            max_per_rule: Some(0),
+            ..DiagnosticPolicy::default()
+        },
+    );
+    let v013: Vec<_> = result.errors.iter().filter(|e| e.rule == "V-013").collect();
+    assert_eq!(v013.len(), 1);
+    assert_eq!(v013[0].message, "and 4 more V-013 errors");
+    assert_eq!(v013[0].path, "attack.indicators[0].pattern.regex");
+    assert!(!result.is_valid());
+}
+
+// ─── Suggestions ────────────────────────────────────────────────────────────
+
+fn suggestion_doc(mode: &str, event: &str, action: &str, surface: &str) -> String {
+    format!(
+        r#"
+oatf: "0.1"
+attack:
+  execution:
+    mode: {}
+    phases:
+      - name: exploit
+        state:
+          tools: []
+        on_enter:
+          - {}: {{message: "hi"}}
+        trigger:
+          event: {}
+      - name: terminal
+  indicators:
+    - surface: {}
+      protocol: mcpp
+      pattern:
+        contains: "test"
+"#,
+        mode, action, event, surface
+    )
+}
+
+/// `(code, path, suggestion)` for every diagnostic that carries a suggestion.
+fn suggestions(input: &str) -> Vec<(String, String, String)> {
+    let result = validate(&parse(input).unwrap());
+    let errors = result
+        .errors
+        .into_iter()
+        .filter_map(|e| Some((e.rule, e.path, e.suggestion?)));
+    let warnings = result
+        .warnings
+        .into_iter()
+        .filter_map(|w| Some((w.code, w.path?, w.suggestion?)));
+    errors.chain(warnings).collect()
+}
+
+fn suggested(code: &str, path: &str, suggestion: &str) -> (String, String, String) {
+    (code.to_string(), path.to_string(), suggestion.to_string())
+}
+
+#[test]
+fn misspelled_names_get_suggestions() {
+    let input = suggestion_doc(
+        "mcp_sever",
+        "tools/cal:calc",
+        "send_notifcation",
+        "tool_descripton",
+    );
+    assert_eq!(
+        suggestions(&input),
+        [
+            suggested("V-005", "attack.execution.mode", "mcp_server"),
+            suggested("V-005", "attack.indicators[0].surface", "tool_description"),
+            suggested("W-003", "attack.indicators[0].protocol", "mcp"),
+            suggested(
+                "W-011",
+                "attack.execution.phases[0].on_enter[0].send_notifcation",
+                "send_notification"
+            ),
+        ]
+    );
+    let input = input.replace("mcp_sever", "mcpp_server");
+    assert!(suggestions(&input).contains(&suggested(
+        "W-002",
+        "attack.execution.mode",
+        "mcp_server"
+    )));
+
+    // Events are checked once the mode is recognized.
+    let input = suggestion_doc(
+        "mcp_server@2025-03-26",
+        "tools/cal:calc",
+        "send_notifcation",
+        "tool_description",
+    );
+    assert_eq!(
+        suggestions(&input),
+        [
+            suggested("W-003", "attack.indicators[0].protocol", "mcp"),
+            suggested(
+                "W-011",
+                "attack.execution.phases[0].trigger.event",
+                "tools/call:calc"
+            ),
+            suggested(
+                "W-011",
+                "attack.execution.phases[0].on_enter[0].send_notifcation",
+                "send_notification"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn malformed_modes_and_wrong_events_get_suggestions() {
+    let input = suggestion_doc(
+        "MCP_SERVER@2025-03-26",
+        "tools/call",
+        "log",
+        "tool_description",
+    );
+    assert!(suggestions(&input).contains(&suggested(
+        "V-005",
+        "attack.execution.mode",
+        "mcp_server@2025-03-26"
+    )));
+
+    // An event registered for other modes suggests the closest one valid
+    // for this mode.
+    let v029 = |mode: &str, event: &str| -> Vec<Option<String>> {
+        let input = suggestion_doc(mode, event, "log", "tool_description");
+        validate(&parse(&input).unwrap())
+            .errors
+            .into_iter()
+            .filter(|e| e.rule == "V-029")
+            .map(|e| e.suggestion)
+            .collect()
+    };
+    assert_eq!(
+        v029("mcp_client", "tasks/list:t1"),
+        [Some("tools/list:t1".to_string())]
+    );
+    assert_eq!(v029("a2a_server", "tools/call"), [None]);
+
+    let input = suggestion_doc("mcp_server", "tools/lis", "log", "tool_description");
+    assert!(suggestions(&input).contains(&suggested(
+        "W-011",
+        "attack.execution.phases[0].trigger.event",
+        "tools/list"
+    )));
+}
+
+#[test]
+fn unrelated_names_are_not_reported() {
+    let input = suggestion_doc(
+        "mcp_server",
+        "notifications/custom",
+        "x_acme_page",
+        "tool_description",
+    )
+    .replace("protocol: mcpp", "protocol: mcp");
+    let result = validate(&parse(&input).unwrap());
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    assert!(
+        !result.warnings.iter().any(|w| w.code == "W-011"),
+        "{:?}",
+        result.warnings
+    );
+    assert!(result.warnings.iter().all(|w| w.suggestion.is_none()));
+
+    // Diagnostics without a suggestion serialize as before.
+    let json = serde_json::to_value(&result.warnings).unwrap();
+    assert!(!json.to_string().contains("suggestion"));
+}
+
+// ─── V-062: License expressions ─────────────────────────────────────────────
+
+fn license_errors(license: &str) -> Vec<String> {
+    let input = lifecycle_doc(&format!("  license: \"{}\"", license));
+    validate(&parse(&input).unwrap())
+        .errors
+        .into_iter()
+        .filter(|e| e.rule == "V-062")
+        .map(|e| e.message)
+        .collect()
+}
+
+#[test]
+fn license_must_be_an_spdx_expression() {
+    for valid in [
+        "MIT",
+        "CC-BY-4.0 OR Apache-2.0",
+        "GPL-2.0+ WITH Classpath-exception-2.0",
+        "(MIT AND BSD-3-Clause) OR LicenseRef-acme-internal",
+        "DocumentRef-spdx-tool:LicenseRef-MIT-Style-1",
+    ] {
+        assert!(license_errors(valid).is_empty(), "{}", valid);
+    }
+
+    let invalid = [
+        ("", "it is empty"),
+        ("MIT or Apache-2.0", "operators are uppercase; use 'OR'"),
+        ("MIT OR", "it ends where a license was expected"),
+        ("(MIT AND Apache-2.0", "'(' is not closed"),
+        ("MIT)", "unexpected ')'"),
+        ("GPL-2.0 WITH", "WITH must be followed by an exception ID"),
+        ("MIT OR AND Apache-2.0", "unexpected 'AND'"),
+        ("Apache 2.0", "unexpected '2.0'"),
+        (
+            "MIT OR Apache-2.0/BSD",
+            "'Apache-2.0/BSD' is not a license ID",
+        ),
+    ];
+    for (license, reason) in invalid {
+        assert_eq!(
+            license_errors(license),
+            [format!(
+                "'{}' is not a valid SPDX license expression: {}",
+                license, reason
+            )]
+        );
+    }
+}